@@ -14,10 +14,14 @@ use {
     solana_sdk::{compute_budget::ComputeBudgetInstruction, transaction::Transaction},
     solana_stake_interface as stake,
     spl_stake_pool::{
+        find_direct_stake_program_address, find_user_stake_program_address,
         find_withdraw_authority_program_address,
-        state::{StakePool, ValidatorList},
+        state::{
+            AccountType, DirectStake, FeeSplit, InsuranceFund, RateHistory, Referrer, StakePool,
+            ValidatorList, ValidatorMetadata,
+        },
     },
-    std::collections::HashSet,
+    std::collections::{HashMap, HashSet},
 };
 
 pub(crate) type Error = Box<dyn std::error::Error>;
@@ -27,8 +31,17 @@ pub fn get_stake_pool(
     stake_pool_address: &Pubkey,
 ) -> Result<StakePool, Error> {
     let account_data = rpc_client.get_account_data(stake_pool_address)?;
-    let stake_pool = try_from_slice_unchecked::<StakePool>(account_data.as_slice())
-        .map_err(|err| format!("Invalid stake pool {}: {}", stake_pool_address, err))?;
+    let stake_pool =
+        try_from_slice_unchecked::<StakePool>(account_data.as_slice()).map_err(|err| {
+            if account_data.first() == Some(&(AccountType::StakePool as u8)) {
+                format!(
+                    "Stake pool {} has an earlier layout, run `migrate-pool` first",
+                    stake_pool_address
+                )
+            } else {
+                format!("Invalid stake pool {}: {}", stake_pool_address, err)
+            }
+        })?;
     Ok(stake_pool)
 }
 
@@ -42,6 +55,134 @@ pub fn get_validator_list(
     Ok(validator_list)
 }
 
+pub fn get_referrer(rpc_client: &RpcClient, referrer_address: &Pubkey) -> Result<Referrer, Error> {
+    let account_data = rpc_client.get_account_data(referrer_address)?;
+    let referrer = try_from_slice_unchecked::<Referrer>(account_data.as_slice())
+        .map_err(|err| format!("Invalid referrer {}: {}", referrer_address, err))?;
+    if !referrer.is_valid() {
+        return Err(format!("Invalid referrer {}", referrer_address).into());
+    }
+    Ok(referrer)
+}
+
+pub fn get_fee_split(
+    rpc_client: &RpcClient,
+    fee_split_address: &Pubkey,
+) -> Result<FeeSplit, Error> {
+    let account_data = rpc_client.get_account_data(fee_split_address)?;
+    let fee_split = try_from_slice_unchecked::<FeeSplit>(account_data.as_slice())
+        .map_err(|err| format!("Invalid fee split {}: {}", fee_split_address, err))?;
+    if !fee_split.is_valid() {
+        return Err(format!("Invalid fee split {}", fee_split_address).into());
+    }
+    Ok(fee_split)
+}
+
+pub fn get_insurance_fund(
+    rpc_client: &RpcClient,
+    insurance_fund_address: &Pubkey,
+) -> Result<InsuranceFund, Error> {
+    let account_data = rpc_client.get_account_data(insurance_fund_address)?;
+    let insurance_fund = try_from_slice_unchecked::<InsuranceFund>(account_data.as_slice())
+        .map_err(|err| format!("Invalid insurance fund {}: {}", insurance_fund_address, err))?;
+    if !insurance_fund.is_valid() {
+        return Err(format!("Invalid insurance fund {}", insurance_fund_address).into());
+    }
+    Ok(insurance_fund)
+}
+
+pub fn get_rate_history(
+    rpc_client: &RpcClient,
+    rate_history_address: &Pubkey,
+) -> Result<RateHistory, Error> {
+    let account_data = rpc_client.get_account_data(rate_history_address)?;
+    let rate_history = try_from_slice_unchecked::<RateHistory>(account_data.as_slice())
+        .map_err(|err| format!("Invalid rate history {}: {}", rate_history_address, err))?;
+    if !rate_history.is_valid() {
+        return Err(format!("Invalid rate history {}", rate_history_address).into());
+    }
+    Ok(rate_history)
+}
+
+pub fn get_validator_metadata(
+    rpc_client: &RpcClient,
+    validator_metadata_address: &Pubkey,
+) -> Result<ValidatorMetadata, Error> {
+    let account_data = rpc_client.get_account_data(validator_metadata_address)?;
+    let validator_metadata = try_from_slice_unchecked::<ValidatorMetadata>(account_data.as_slice())
+        .map_err(|err| {
+            format!(
+                "Invalid validator metadata {}: {}",
+                validator_metadata_address, err
+            )
+        })?;
+    if !validator_metadata.is_valid() {
+        return Err(format!("Invalid validator metadata {}", validator_metadata_address).into());
+    }
+    Ok(validator_metadata)
+}
+
+/// Reads the lamports pending delegation to each of the given validators
+/// from their direct stake accounts. Validators without one are left out.
+pub(crate) fn get_pending_direct_stake(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    vote_accounts: &[Pubkey],
+) -> Result<HashMap<Pubkey, u64>, Error> {
+    let mut pending_direct_stake = HashMap::new();
+    for vote_accounts in vote_accounts.chunks(100) {
+        let addresses = vote_accounts
+            .iter()
+            .map(|vote_account| {
+                find_direct_stake_program_address(program_id, stake_pool_address, vote_account).0
+            })
+            .collect::<Vec<_>>();
+        let accounts = rpc_client.get_multiple_accounts(&addresses)?;
+        for (vote_account, account) in vote_accounts.iter().zip(accounts) {
+            let Some(account) = account.filter(|account| account.owner == *program_id) else {
+                continue;
+            };
+            if let Ok(direct_stake) = try_from_slice_unchecked::<DirectStake>(&account.data) {
+                if direct_stake.is_valid() && direct_stake.pending_lamports > 0 {
+                    pending_direct_stake.insert(*vote_account, direct_stake.pending_lamports);
+                }
+            }
+        }
+    }
+    Ok(pending_direct_stake)
+}
+
+/// Finds the user stake accounts created for `user_wallet` by session stake
+/// withdrawals, scanning seeds below `max_seed`. Returns the seed, address,
+/// lamports and stake state of every account still open.
+pub(crate) fn get_user_stake_accounts(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    user_wallet: &Pubkey,
+    max_seed: u64,
+) -> Result<Vec<(u64, Pubkey, u64, stake::state::StakeStateV2)>, Error> {
+    let mut user_stake_accounts = vec![];
+    let seeds = (0..max_seed).collect::<Vec<_>>();
+    for seeds in seeds.chunks(100) {
+        let addresses = seeds
+            .iter()
+            .map(|seed| find_user_stake_program_address(program_id, user_wallet, *seed).0)
+            .collect::<Vec<_>>();
+        let accounts = rpc_client.get_multiple_accounts(&addresses)?;
+        for ((seed, address), account) in seeds.iter().zip(addresses).zip(accounts) {
+            let Some(account) = account.filter(|account| account.owner == stake::program::id())
+            else {
+                continue;
+            };
+            if let Ok(stake_state) = deserialize(account.data.as_slice()) {
+                user_stake_accounts.push((*seed, address, account.lamports, stake_state));
+            }
+        }
+    }
+    Ok(user_stake_accounts)
+}
+
 pub fn get_token_account(
     rpc_client: &RpcClient,
     token_account_address: &Pubkey,
@@ -1,17 +1,25 @@
 #![allow(clippy::arithmetic_side_effects)]
 mod client;
 mod output;
+mod rebalance;
 mod squads;
 
 use {
     crate::{
         client::*,
-        output::{CliStakePool, CliStakePoolDetails, CliStakePoolStakeAccountInfo, CliStakePools},
+        output::{
+            CliInsuranceFund, CliRateHistory, CliRateHistoryEntry, CliRebalancePlan,
+            CliRebalanceValidator, CliStakePool, CliStakePoolDetails, CliStakePoolStakeAccountInfo,
+            CliStakePools,
+        },
+        rebalance::{
+            get_validator_performance, plan_rebalance, target_weights, RebalanceParams, StakeChange,
+        },
     },
     bincode::deserialize,
     clap::{
-        crate_description, crate_name, crate_version, value_t, value_t_or_exit, App, AppSettings,
-        Arg, ArgGroup, ArgMatches, SubCommand,
+        crate_description, crate_name, crate_version, value_t, value_t_or_exit, values_t, App,
+        AppSettings, Arg, ArgGroup, ArgMatches, SubCommand,
     },
     solana_clap_utils::{
         compute_unit_price::{compute_unit_price_arg, COMPUTE_UNIT_PRICE_ARG},
@@ -47,15 +55,25 @@ use {
     spl_associated_token_account::instruction::create_associated_token_account,
     spl_associated_token_account_client::address::get_associated_token_address_with_program_id,
     spl_stake_pool::{
-        self, find_stake_program_address, find_transient_stake_program_address,
-        find_withdraw_authority_program_address,
+        self, find_direct_stake_program_address, find_fee_split_program_address,
+        find_insurance_fund_program_address, find_oracle_program_address,
+        find_rate_history_program_address, find_referrer_program_address,
+        find_stake_program_address, find_transient_stake_program_address,
+        find_validator_metadata_program_address, find_withdraw_authority_program_address,
         instruction::{FundingType, PreferredValidatorType},
         minimum_delegation,
-        state::{Fee, FeeType, StakePool, ValidatorList, ValidatorStakeInfo},
-        MINIMUM_RESERVE_LAMPORTS,
+        state::{
+            AccountType, CapType, Fee, FeeType, PausedOperation, Referrer, SolWithdrawalFeeCurve,
+            StakePool, ValidatorList, ValidatorStakeInfo,
+        },
+        MAX_COMMISSION, MINIMUM_RESERVE_LAMPORTS,
     },
     spl_token_2022::{
-        check_spl_token_program_account, extension::StateWithExtensions, state::Mint,
+        check_spl_token_program_account,
+        extension::{
+            metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::Mint,
     },
     std::str::FromStr,
     std::{cmp::Ordering, num::NonZeroU32, process::exit, rc::Rc},
@@ -69,6 +87,7 @@ pub(crate) struct Config {
     manager: Box<dyn Signer>,
     staker: Box<dyn Signer>,
     funding_authority: Option<Box<dyn Signer>>,
+    pauser: Option<Box<dyn Signer>>,
     token_owner: Box<dyn Signer>,
     fee_payer: Box<dyn Signer>,
     dry_run: bool,
@@ -232,6 +251,42 @@ fn get_latest_blockhash(client: &RpcClient) -> Result<Hash, Error> {
         .0)
 }
 
+/// Fetch the recipient token accounts of the pool's fee split, if it has one
+fn get_fee_split_recipients(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    stake_pool: &StakePool,
+) -> Result<Option<Vec<Pubkey>>, Error> {
+    if !stake_pool.has_fee_split {
+        return Ok(None);
+    }
+    let (fee_split_address, _) =
+        find_fee_split_program_address(&config.stake_pool_program_id, stake_pool_address);
+    let fee_split = get_fee_split(&config.rpc_client, &fee_split_address)?;
+    Ok(Some(
+        fee_split
+            .recipients()
+            .iter()
+            .map(|recipient| recipient.token_account)
+            .collect(),
+    ))
+}
+
+/// Append the fee split accounts to a fee-paying instruction, if the pool has
+/// a fee split
+fn with_fee_split(
+    instruction: Instruction,
+    stake_pool_address: &Pubkey,
+    fee_split_recipients: Option<&[Pubkey]>,
+) -> Instruction {
+    match fee_split_recipients {
+        Some(recipients) => {
+            spl_stake_pool::instruction::with_fee_split(instruction, stake_pool_address, recipients)
+        }
+        None => instruction,
+    }
+}
+
 /// Send a transaction, or if Squads mode is enabled, wrap the instructions
 /// in a Squads multisig proposal instead.
 fn send_transaction_or_squads_proposal(
@@ -842,26 +897,74 @@ fn command_create_pool(
     Ok(())
 }
 
+/// Whether the pool mint holds its own metadata through the Token-2022
+/// metadata extension, instead of a Metaplex metadata account
+fn uses_token_metadata_extension(config: &Config, stake_pool: &StakePool) -> Result<bool, Error> {
+    if stake_pool.token_program_id != spl_token_2022::id() {
+        return Ok(false);
+    }
+    let mint_account = config.rpc_client.get_account(&stake_pool.pool_mint)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_account.data)?;
+    let metadata_address = mint
+        .get_extension::<MetadataPointer>()
+        .ok()
+        .and_then(|metadata_pointer| Option::<Pubkey>::from(metadata_pointer.metadata_address));
+    Ok(metadata_address == Some(stake_pool.pool_mint))
+}
+
 fn create_token_metadata(
     config: &Config,
     stake_pool_address: &Pubkey,
     name: String,
     symbol: String,
     uri: String,
+    fields: Vec<(String, String)>,
 ) -> CommandResult {
     let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
 
     let mut signers = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
-    let instructions = vec![spl_stake_pool::instruction::create_token_metadata(
-        &config.stake_pool_program_id,
-        stake_pool_address,
-        &stake_pool.manager,
-        &stake_pool.pool_mint,
-        &config.fee_payer.pubkey(),
-        name,
-        symbol,
-        uri,
-    )];
+    let instructions = if uses_token_metadata_extension(config, &stake_pool)? {
+        let mut instructions = vec![
+            spl_stake_pool::instruction::create_token_metadata_extension(
+                &config.stake_pool_program_id,
+                stake_pool_address,
+                &stake_pool.manager,
+                &stake_pool.pool_mint,
+                &config.fee_payer.pubkey(),
+                &stake_pool.token_program_id,
+                name,
+                symbol,
+                uri,
+            ),
+        ];
+        instructions.extend(fields.into_iter().map(|(field, value)| {
+            spl_stake_pool::instruction::update_token_metadata_extension_field(
+                &config.stake_pool_program_id,
+                stake_pool_address,
+                &stake_pool.manager,
+                &stake_pool.pool_mint,
+                &config.fee_payer.pubkey(),
+                &stake_pool.token_program_id,
+                field,
+                value,
+            )
+        }));
+        instructions
+    } else {
+        if !fields.is_empty() {
+            return Err("Additional metadata fields require a Token-2022 pool mint whose metadata pointer points to itself".into());
+        }
+        vec![spl_stake_pool::instruction::create_token_metadata(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &stake_pool.manager,
+            &stake_pool.pool_mint,
+            &config.fee_payer.pubkey(),
+            name,
+            symbol,
+            uri,
+        )]
+    };
     unique_signers!(signers);
     let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
     send_transaction(config, transaction)?;
@@ -874,19 +977,59 @@ fn update_token_metadata(
     name: String,
     symbol: String,
     uri: String,
+    fields: Vec<(String, String)>,
+    removed_fields: Vec<String>,
 ) -> CommandResult {
     let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
 
     let mut signers = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
-    let instructions = vec![spl_stake_pool::instruction::update_token_metadata(
-        &config.stake_pool_program_id,
-        stake_pool_address,
-        &stake_pool.manager,
-        &stake_pool.pool_mint,
-        name,
-        symbol,
-        uri,
-    )];
+    let instructions = if uses_token_metadata_extension(config, &stake_pool)? {
+        let base_fields = [
+            ("name".to_string(), name),
+            ("symbol".to_string(), symbol),
+            ("uri".to_string(), uri),
+        ];
+        let mut instructions: Vec<Instruction> = base_fields
+            .into_iter()
+            .chain(fields)
+            .map(|(field, value)| {
+                spl_stake_pool::instruction::update_token_metadata_extension_field(
+                    &config.stake_pool_program_id,
+                    stake_pool_address,
+                    &stake_pool.manager,
+                    &stake_pool.pool_mint,
+                    &config.fee_payer.pubkey(),
+                    &stake_pool.token_program_id,
+                    field,
+                    value,
+                )
+            })
+            .collect();
+        instructions.extend(removed_fields.into_iter().map(|key| {
+            spl_stake_pool::instruction::remove_token_metadata_extension_key(
+                &config.stake_pool_program_id,
+                stake_pool_address,
+                &stake_pool.manager,
+                &stake_pool.pool_mint,
+                &stake_pool.token_program_id,
+                key,
+            )
+        }));
+        instructions
+    } else {
+        if !fields.is_empty() || !removed_fields.is_empty() {
+            return Err("Additional metadata fields require a Token-2022 pool mint whose metadata pointer points to itself".into());
+        }
+        vec![spl_stake_pool::instruction::update_token_metadata(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &stake_pool.manager,
+            &stake_pool.pool_mint,
+            name,
+            symbol,
+            uri,
+        )]
+    };
     unique_signers!(signers);
     let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
     send_transaction(config, transaction)?;
@@ -941,11 +1084,17 @@ fn command_vsa_add(
         stake_account_address, vote_account
     );
 
+    // the fee payer also pays the rent of the validator's metadata
+    let (validator_metadata_address, _) = find_validator_metadata_program_address(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        vote_account,
+    );
     let mut signers = vec![config.fee_payer.as_ref(), config.staker.as_ref()];
     unique_signers!(signers);
     let transaction = checked_transaction_with_signers(
         config,
-        &[
+        &[spl_stake_pool::instruction::with_validator_metadata(
             spl_stake_pool::instruction::add_validator_to_pool_with_vote(
                 &config.stake_pool_program_id,
                 &stake_pool,
@@ -953,7 +1102,9 @@ fn command_vsa_add(
                 vote_account,
                 validator_seed,
             ),
-        ],
+            &validator_metadata_address,
+            Some(&config.fee_payer.pubkey()),
+        )],
         &signers,
     )?;
 
@@ -1006,6 +1157,114 @@ fn command_vsa_remove(
     Ok(())
 }
 
+fn command_deactivate_high_commission_validator(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    vote_account: &Pubkey,
+) -> CommandResult {
+    if !config.no_update {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let validator_list = get_validator_list(&config.rpc_client, &stake_pool.validator_list)?;
+    let validator_stake_info = validator_list
+        .find(vote_account)
+        .ok_or("Vote account not found in validator list")?;
+
+    let (stake_account_address, _) = find_stake_program_address(
+        &config.stake_pool_program_id,
+        vote_account,
+        stake_pool_address,
+        NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+    );
+    let (transient_stake_account_address, _) = find_transient_stake_program_address(
+        &config.stake_pool_program_id,
+        vote_account,
+        stake_pool_address,
+        validator_stake_info.transient_seed_suffix.into(),
+    );
+    println!(
+        "Deactivating stake account {}, delegated to {}",
+        stake_account_address, vote_account
+    );
+
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(&config.stake_pool_program_id, stake_pool_address)
+            .0;
+    let instructions = vec![
+        spl_stake_pool::instruction::deactivate_high_commission_validator(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &pool_withdraw_authority,
+            &stake_pool.validator_list,
+            &stake_account_address,
+            &transient_stake_account_address,
+            vote_account,
+        ),
+    ];
+    let transaction =
+        checked_transaction_with_signers(config, &instructions, &[config.fee_payer.as_ref()])?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_deactivate_delinquent_validator(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    vote_account: &Pubkey,
+) -> CommandResult {
+    if !config.no_update {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let validator_list = get_validator_list(&config.rpc_client, &stake_pool.validator_list)?;
+    let validator_stake_info = validator_list
+        .find(vote_account)
+        .ok_or("Vote account not found in validator list")?;
+
+    let (stake_account_address, _) = find_stake_program_address(
+        &config.stake_pool_program_id,
+        vote_account,
+        stake_pool_address,
+        NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+    );
+    let (transient_stake_account_address, _) = find_transient_stake_program_address(
+        &config.stake_pool_program_id,
+        vote_account,
+        stake_pool_address,
+        validator_stake_info.transient_seed_suffix.into(),
+    );
+    println!(
+        "Deactivating stake account {}, delegated to {}",
+        stake_account_address, vote_account
+    );
+
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(&config.stake_pool_program_id, stake_pool_address)
+            .0;
+    // the reward goes to the fee payer, which already exists and so can take
+    // an amount below the rent-exempt minimum
+    let instructions = vec![
+        spl_stake_pool::instruction::deactivate_delinquent_validator(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &pool_withdraw_authority,
+            &stake_pool.validator_list,
+            &stake_pool.reserve_stake,
+            &stake_account_address,
+            &transient_stake_account_address,
+            vote_account,
+            &config.fee_payer.pubkey(),
+        ),
+    ];
+    let transaction =
+        checked_transaction_with_signers(config, &instructions, &[config.fee_payer.as_ref()])?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
 fn command_increase_validator_stake(
     config: &Config,
     stake_pool_address: &Pubkey,
@@ -1082,6 +1341,200 @@ fn command_decrease_validator_stake(
     Ok(())
 }
 
+fn command_redelegate(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    source_vote_account: &Pubkey,
+    destination_vote_account: &Pubkey,
+    lamports: u64,
+) -> CommandResult {
+    if !config.no_update {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let validator_list = get_validator_list(&config.rpc_client, &stake_pool.validator_list)?;
+    let source_stake_info = validator_list
+        .find(source_vote_account)
+        .ok_or("Source vote account not found in validator list")?;
+    let destination_stake_info = validator_list
+        .find(destination_vote_account)
+        .ok_or("Destination vote account not found in validator list")?;
+
+    // the reserve funds the ephemeral account's rent-exemption and the
+    // destination stake with its own, while keeping its rent-exemption and
+    // the withdrawal claim pool
+    let stake_rent = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(STAKE_STATE_LEN)?;
+    let required_reserve_lamports = lamports
+        .saturating_add(stake_rent.saturating_mul(3))
+        .saturating_add(stake_pool.withdrawal_claim_pool_lamports);
+    let reserve_lamports = config.rpc_client.get_balance(&stake_pool.reserve_stake)?;
+    if reserve_lamports < required_reserve_lamports {
+        return Err(format!(
+            "Reserve stake holds {}, redelegating {} requires {}",
+            Sol(reserve_lamports),
+            Sol(lamports),
+            Sol(required_reserve_lamports),
+        )
+        .into());
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.staker.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(
+        config,
+        &[spl_stake_pool::instruction::redelegate_with_vote(
+            &config.stake_pool_program_id,
+            &stake_pool,
+            stake_pool_address,
+            source_vote_account,
+            NonZeroU32::new(source_stake_info.validator_seed_suffix.into()),
+            source_stake_info.transient_seed_suffix.into(),
+            destination_vote_account,
+            NonZeroU32::new(destination_stake_info.validator_seed_suffix.into()),
+            destination_stake_info.transient_seed_suffix.into(),
+            lamports,
+            0,
+        )],
+        &signers,
+    )?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_rebalance(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    retained_reserve_lamports: u64,
+    max_commission: u8,
+    tolerance_lamports: u64,
+    dry_run: bool,
+) -> CommandResult {
+    if !config.no_update {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let validator_list = get_validator_list(&config.rpc_client, &stake_pool.validator_list)?;
+    let vote_accounts = validator_list
+        .validators
+        .iter()
+        .map(|v| v.vote_account_address)
+        .collect::<Vec<_>>();
+    let performance = get_validator_performance(&config.rpc_client, &vote_accounts)?;
+    let weights = target_weights(&vote_accounts, &performance, max_commission)
+        .ok_or("No validator in the pool is eligible for stake")?;
+
+    let stake_rent = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(STAKE_STATE_LEN)?;
+    let stake_minimum_delegation = config.rpc_client.get_stake_minimum_delegation()?;
+    let reserve_lamports = config.rpc_client.get_balance(&stake_pool.reserve_stake)?;
+    // lamports set aside for withdrawal claims can't be delegated
+    let available_reserve_lamports = reserve_lamports
+        .saturating_sub(stake_pool.withdrawal_claim_pool_lamports)
+        .saturating_sub(std::cmp::max(
+            retained_reserve_lamports,
+            stake_rent.saturating_add(MINIMUM_RESERVE_LAMPORTS),
+        ));
+    let params = RebalanceParams {
+        total_lamports: stake_pool.total_lamports,
+        available_reserve_lamports,
+        retained_reserve_lamports,
+        stake_rent,
+        minimum_delegation: minimum_delegation(stake_minimum_delegation),
+        tolerance_lamports,
+        max_validator_share_bps: stake_pool.max_validator_share_bps,
+    };
+    let pending_direct_stake = get_pending_direct_stake(
+        &config.rpc_client,
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &vote_accounts,
+    )?;
+    let plan = plan_rebalance(&validator_list, &weights, &pending_direct_stake, &params);
+
+    let cli_plan = CliRebalancePlan {
+        total_lamports: stake_pool.total_lamports,
+        retained_reserve_lamports,
+        available_reserve_lamports,
+        validators: plan.iter().map(CliRebalanceValidator::from).collect(),
+    };
+    println!("{}", config.output_format.formatted_string(&cli_plan));
+    if dry_run {
+        return Ok(());
+    }
+
+    // decreases first, their lamports only reach the reserve next epoch anyway
+    let mut instructions = vec![];
+    for planned in &plan {
+        let Some(change) = planned.change else {
+            continue;
+        };
+        let validator_stake_info = validator_list
+            .find(&planned.vote_account)
+            .ok_or("Vote account not found in validator list")?;
+        let validator_seed = NonZeroU32::new(validator_stake_info.validator_seed_suffix.into());
+        let transient_seed = validator_stake_info.transient_seed_suffix.into();
+        let instruction = match change {
+            StakeChange::Decrease(lamports) => {
+                spl_stake_pool::instruction::decrease_additional_validator_stake_with_vote(
+                    &config.stake_pool_program_id,
+                    &stake_pool,
+                    stake_pool_address,
+                    &planned.vote_account,
+                    lamports,
+                    validator_seed,
+                    transient_seed,
+                    0,
+                )
+            }
+            StakeChange::Increase(lamports) => {
+                let instruction =
+                    spl_stake_pool::instruction::increase_additional_validator_stake_with_vote(
+                        &config.stake_pool_program_id,
+                        &stake_pool,
+                        stake_pool_address,
+                        &planned.vote_account,
+                        lamports,
+                        validator_seed,
+                        transient_seed,
+                        0,
+                    );
+                if planned.pending_direct_lamports > 0 {
+                    let (direct_stake_address, _) = find_direct_stake_program_address(
+                        &config.stake_pool_program_id,
+                        stake_pool_address,
+                        &planned.vote_account,
+                    );
+                    spl_stake_pool::instruction::with_direct_stake(
+                        instruction,
+                        &direct_stake_address,
+                    )
+                } else {
+                    instruction
+                }
+            }
+        };
+        instructions.push((matches!(change, StakeChange::Increase(_)), instruction));
+    }
+    instructions.sort_by_key(|(is_increase, _)| *is_increase);
+    if instructions.is_empty() {
+        println!("Stake pool is balanced, nothing to do");
+        return Ok(());
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.staker.as_ref()];
+    unique_signers!(signers);
+    for (_, instruction) in instructions {
+        let transaction = checked_transaction_with_signers(config, &[instruction], &signers)?;
+        send_transaction(config, transaction)?;
+    }
+    Ok(())
+}
+
 fn command_set_preferred_validator(
     config: &Config,
     stake_pool_address: &Pubkey,
@@ -1255,6 +1708,14 @@ fn command_deposit_stake(
             )
         };
 
+    let fee_split_recipients = get_fee_split_recipients(config, stake_pool_address, &stake_pool)?;
+    if let Some(deposit_instruction) = deposit_instructions.pop() {
+        deposit_instructions.push(with_fee_split(
+            deposit_instruction,
+            stake_pool_address,
+            fee_split_recipients.as_deref(),
+        ));
+    }
     instructions.append(&mut deposit_instructions);
 
     unique_signers!(signers);
@@ -1311,6 +1772,7 @@ fn command_deposit_all_stake(
         find_withdraw_authority_program_address(&config.stake_pool_program_id, stake_pool_address)
             .0;
     let validator_list = get_validator_list(&config.rpc_client, &stake_pool.validator_list)?;
+    let fee_split_recipients = get_fee_split_recipients(config, stake_pool_address, &stake_pool)?;
     let mut signers = if let Some(stake_deposit_authority) = config.funding_authority.as_ref() {
         if stake_deposit_authority.pubkey() != stake_pool.stake_deposit_authority {
             let error = format!(
@@ -1359,41 +1821,48 @@ fn command_deposit_all_stake(
             validator_stake_account, validator_stake_state
         );
 
-        let instructions = if let Some(stake_deposit_authority) = config.funding_authority.as_ref()
-        {
-            spl_stake_pool::instruction::deposit_stake_with_authority(
-                &config.stake_pool_program_id,
+        let mut instructions =
+            if let Some(stake_deposit_authority) = config.funding_authority.as_ref() {
+                spl_stake_pool::instruction::deposit_stake_with_authority(
+                    &config.stake_pool_program_id,
+                    stake_pool_address,
+                    &stake_pool.validator_list,
+                    &stake_deposit_authority.pubkey(),
+                    &pool_withdraw_authority,
+                    &stake_address,
+                    &withdraw_authority.pubkey(),
+                    &validator_stake_account,
+                    &stake_pool.reserve_stake,
+                    &pool_token_receiver_account,
+                    &stake_pool.manager_fee_account,
+                    &referrer_token_account,
+                    &stake_pool.pool_mint,
+                    &stake_pool.token_program_id,
+                )
+            } else {
+                spl_stake_pool::instruction::deposit_stake(
+                    &config.stake_pool_program_id,
+                    stake_pool_address,
+                    &stake_pool.validator_list,
+                    &pool_withdraw_authority,
+                    &stake_address,
+                    &withdraw_authority.pubkey(),
+                    &validator_stake_account,
+                    &stake_pool.reserve_stake,
+                    &pool_token_receiver_account,
+                    &stake_pool.manager_fee_account,
+                    &referrer_token_account,
+                    &stake_pool.pool_mint,
+                    &stake_pool.token_program_id,
+                )
+            };
+        if let Some(deposit_instruction) = instructions.pop() {
+            instructions.push(with_fee_split(
+                deposit_instruction,
                 stake_pool_address,
-                &stake_pool.validator_list,
-                &stake_deposit_authority.pubkey(),
-                &pool_withdraw_authority,
-                &stake_address,
-                &withdraw_authority.pubkey(),
-                &validator_stake_account,
-                &stake_pool.reserve_stake,
-                &pool_token_receiver_account,
-                &stake_pool.manager_fee_account,
-                &referrer_token_account,
-                &stake_pool.pool_mint,
-                &stake_pool.token_program_id,
-            )
-        } else {
-            spl_stake_pool::instruction::deposit_stake(
-                &config.stake_pool_program_id,
-                stake_pool_address,
-                &stake_pool.validator_list,
-                &pool_withdraw_authority,
-                &stake_address,
-                &withdraw_authority.pubkey(),
-                &validator_stake_account,
-                &stake_pool.reserve_stake,
-                &pool_token_receiver_account,
-                &stake_pool.manager_fee_account,
-                &referrer_token_account,
-                &stake_pool.pool_mint,
-                &stake_pool.token_program_id,
-            )
-        };
+                fee_split_recipients.as_deref(),
+            ));
+        }
 
         let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
         send_transaction(config, transaction)?;
@@ -1401,12 +1870,15 @@ fn command_deposit_all_stake(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn command_deposit_sol(
     config: &Config,
     stake_pool_address: &Pubkey,
     from: &Option<Keypair>,
     pool_token_receiver_account: &Option<Pubkey>,
     referrer_token_account: &Option<Pubkey>,
+    referral_code: Option<&str>,
+    vote_account: Option<Pubkey>,
     lamports: u64,
 ) -> CommandResult {
     if !config.no_update {
@@ -1433,19 +1905,27 @@ fn command_deposit_sol(
 
     // ephemeral SOL account just to do the transfer
     let user_sol_transfer = Keypair::new();
-    let mut signers = vec![config.fee_payer.as_ref(), &user_sol_transfer];
+    let mut signers = vec![config.fee_payer.as_ref()];
     if let Some(keypair) = from.as_ref() {
         signers.push(keypair)
     }
 
     let mut total_rent_free_balances: u64 = 0;
 
-    // Create the ephemeral SOL account
-    instructions.push(system_instruction::transfer(
-        &from_pubkey,
-        &user_sol_transfer.pubkey(),
-        lamports,
-    ));
+    // Deposits for a validator pay the direct stake account's rent from the
+    // source of funds, so they can't go through the ephemeral account
+    let lamports_from = if vote_account.is_some() {
+        from_pubkey
+    } else {
+        signers.push(&user_sol_transfer);
+        // Create the ephemeral SOL account
+        instructions.push(system_instruction::transfer(
+            &from_pubkey,
+            &user_sol_transfer.pubkey(),
+            lamports,
+        ));
+        user_sol_transfer.pubkey()
+    };
 
     // Create token account if not specified
     let pool_token_receiver_account =
@@ -1458,13 +1938,57 @@ fn command_deposit_sol(
             &mut total_rent_free_balances,
         ));
 
-    let referrer_token_account = referrer_token_account.unwrap_or(pool_token_receiver_account);
+    // A registered referral code pays its own payout account, regardless of `--referrer`
+    let referrer = referral_code
+        .map(|code| {
+            let (referrer_address, _) = find_referrer_program_address(
+                &config.stake_pool_program_id,
+                stake_pool_address,
+                code,
+            );
+            let referrer = get_referrer(&config.rpc_client, &referrer_address)?;
+            if referrer.stake_pool != *stake_pool_address {
+                return Err(format!(
+                    "Referral code {} is registered for stake pool {}",
+                    code, referrer.stake_pool
+                )
+                .into());
+            }
+            Ok::<_, Error>((referrer_address, referrer.payout_token_account))
+        })
+        .transpose()?;
+    let referrer_token_account = referrer
+        .map(|(_, payout_token_account)| payout_token_account)
+        .or(*referrer_token_account)
+        .unwrap_or(pool_token_receiver_account);
 
     let pool_withdraw_authority =
         find_withdraw_authority_program_address(&config.stake_pool_program_id, stake_pool_address)
             .0;
 
-    let deposit_instruction = if let Some(deposit_authority) = config.funding_authority.as_ref() {
+    let deposit_instruction = if let Some(vote_account) = vote_account {
+        let deposit_authority = config.funding_authority.as_ref().map(|deposit_authority| {
+            signers.push(deposit_authority.as_ref());
+            deposit_authority.pubkey()
+        });
+        spl_stake_pool::instruction::deposit_sol_to_validator(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &stake_pool.validator_list,
+            &vote_account,
+            &pool_withdraw_authority,
+            &stake_pool.reserve_stake,
+            &lamports_from,
+            &pool_token_receiver_account,
+            &stake_pool.manager_fee_account,
+            &referrer_token_account,
+            &stake_pool.pool_mint,
+            &stake_pool.token_program_id,
+            deposit_authority.as_ref(),
+            lamports,
+            0,
+        )
+    } else if let Some(deposit_authority) = config.funding_authority.as_ref() {
         let expected_sol_deposit_authority = stake_pool.sol_deposit_authority.ok_or_else(|| {
             "SOL deposit authority specified in arguments but stake pool has none".to_string()
         })?;
@@ -1484,7 +2008,7 @@ fn command_deposit_sol(
             &deposit_authority.pubkey(),
             &pool_withdraw_authority,
             &stake_pool.reserve_stake,
-            &user_sol_transfer.pubkey(),
+            &lamports_from,
             &pool_token_receiver_account,
             &stake_pool.manager_fee_account,
             &referrer_token_account,
@@ -1498,7 +2022,7 @@ fn command_deposit_sol(
             stake_pool_address,
             &pool_withdraw_authority,
             &stake_pool.reserve_stake,
-            &user_sol_transfer.pubkey(),
+            &lamports_from,
             &pool_token_receiver_account,
             &stake_pool.manager_fee_account,
             &referrer_token_account,
@@ -1507,6 +2031,18 @@ fn command_deposit_sol(
             lamports,
         )
     };
+    let deposit_instruction = match referrer {
+        Some((referrer_address, _)) => {
+            spl_stake_pool::instruction::with_referrer(deposit_instruction, &referrer_address)
+        }
+        None => deposit_instruction,
+    };
+    let fee_split_recipients = get_fee_split_recipients(config, stake_pool_address, &stake_pool)?;
+    let deposit_instruction = with_fee_split(
+        deposit_instruction,
+        stake_pool_address,
+        fee_split_recipients.as_deref(),
+    );
 
     instructions.push(deposit_instruction);
 
@@ -1521,6 +2057,89 @@ fn command_deposit_sol(
     Ok(())
 }
 
+fn command_register_referrer(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    code: &str,
+    payout_account: Option<Pubkey>,
+) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let (referrer_address, _) =
+        find_referrer_program_address(&config.stake_pool_program_id, stake_pool_address, code);
+    println!("Registering referral code {} at {}", code, referrer_address);
+
+    let mut instructions: Vec<Instruction> = vec![];
+    let mut total_rent_free_balances: u64 = 0;
+    let payout_account = payout_account.unwrap_or(add_associated_token_account(
+        config,
+        &stake_pool.pool_mint,
+        &stake_pool.token_program_id,
+        &config.token_owner.pubkey(),
+        &mut instructions,
+        &mut total_rent_free_balances,
+    ));
+
+    instructions.push(spl_stake_pool::instruction::register_referrer(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &referrer_address,
+        &config.token_owner.pubkey(),
+        &payout_account,
+        &config.fee_payer.pubkey(),
+        code,
+    ));
+    total_rent_free_balances += config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(get_packed_len::<Referrer>())?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.token_owner.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers_and_additional_fee(
+        config,
+        &instructions,
+        &signers,
+        total_rent_free_balances,
+    )?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_update_referrer(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    code: &str,
+    payout_account: &Pubkey,
+    new_authority: Option<Pubkey>,
+) -> CommandResult {
+    let (referrer_address, _) =
+        find_referrer_program_address(&config.stake_pool_program_id, stake_pool_address, code);
+    let referrer = get_referrer(&config.rpc_client, &referrer_address)?;
+    if referrer.authority != config.token_owner.pubkey() {
+        return Err(format!(
+            "Referral code {} is controlled by {}, not the token owner {}",
+            code,
+            referrer.authority,
+            config.token_owner.pubkey()
+        )
+        .into());
+    }
+
+    let instructions = vec![spl_stake_pool::instruction::update_referrer(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &referrer_address,
+        &config.token_owner.pubkey(),
+        payout_account,
+        new_authority.as_ref(),
+    )];
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.token_owner.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
 fn command_list(
     config: &Config,
     stake_pool_address: &Pubkey,
@@ -1572,6 +2191,7 @@ fn command_list(
                 validator_transient_stake_account_address: transient_stake_account_address
                     .to_string(),
                 validator_transient_stake_lamports: validator.transient_stake_lamports.into(),
+                validator_max_share_bps: validator.max_share_bps(),
                 update_required,
             }
         })
@@ -1642,7 +2262,7 @@ fn command_update(
 
     let validator_list = get_validator_list(&config.rpc_client, &stake_pool.validator_list)?;
 
-    let (mut update_list_instructions, final_instructions) = if stale_only {
+    let (mut update_list_instructions, mut final_instructions) = if stale_only {
         spl_stake_pool::instruction::update_stale_stake_pool(
             &config.stake_pool_program_id,
             &stake_pool,
@@ -1661,6 +2281,14 @@ fn command_update(
         )
     };
 
+    // The first final instruction updates the pool balance, which pays fees
+    let fee_split_recipients = get_fee_split_recipients(config, stake_pool_address, &stake_pool)?;
+    final_instructions[0] = with_fee_split(
+        final_instructions[0].clone(),
+        stake_pool_address,
+        fee_split_recipients.as_deref(),
+    );
+
     let update_list_instructions_len = update_list_instructions.len();
     if update_list_instructions_len > 0 {
         let last_instruction = update_list_instructions.split_off(update_list_instructions_len - 1);
@@ -2037,7 +2665,9 @@ fn command_withdraw_stake(
         )?,
     );
 
+    let fee_split_recipients = get_fee_split_recipients(config, stake_pool_address, &stake_pool)?;
     let mut total_rent_free_balances = 0;
+    let mut withdrawals = vec![];
     // Go through prepared accounts and withdraw/claim them
     for withdraw_account in withdraw_accounts {
         // Convert pool tokens amount to lamports
@@ -2083,23 +2713,54 @@ fn command_withdraw_stake(
                 stake_receiver_param.unwrap()
             };
 
-        instructions.push(spl_stake_pool::instruction::withdraw_stake(
-            &config.stake_pool_program_id,
-            stake_pool_address,
-            &stake_pool.validator_list,
-            &pool_withdraw_authority,
-            &withdraw_account.stake_address,
-            &stake_receiver,
-            &config.staker.pubkey(),
-            &user_transfer_authority.pubkey(),
-            &pool_token_account,
-            &stake_pool.manager_fee_account,
-            &stake_pool.pool_mint,
-            &stake_pool.token_program_id,
+        withdrawals.push((
+            withdraw_account.stake_address,
+            stake_receiver,
             withdraw_account.pool_amount,
         ));
     }
 
+    // Withdraw from several stake accounts in a single instruction, burning
+    // the pool tokens once
+    let withdraw_instruction =
+        if let [(stake_to_split, stake_to_receive, pool_amount)] = withdrawals[..] {
+            spl_stake_pool::instruction::withdraw_stake(
+                &config.stake_pool_program_id,
+                stake_pool_address,
+                &stake_pool.validator_list,
+                &pool_withdraw_authority,
+                &stake_to_split,
+                &stake_to_receive,
+                &config.staker.pubkey(),
+                &user_transfer_authority.pubkey(),
+                &pool_token_account,
+                &stake_pool.manager_fee_account,
+                &stake_pool.pool_mint,
+                &stake_pool.token_program_id,
+                pool_amount,
+            )
+        } else {
+            spl_stake_pool::instruction::withdraw_stake_multi(
+                &config.stake_pool_program_id,
+                stake_pool_address,
+                &stake_pool.validator_list,
+                &pool_withdraw_authority,
+                &config.staker.pubkey(),
+                &user_transfer_authority.pubkey(),
+                &pool_token_account,
+                &stake_pool.manager_fee_account,
+                &stake_pool.pool_mint,
+                &stake_pool.token_program_id,
+                &withdrawals,
+                0,
+            )
+        };
+    instructions.push(with_fee_split(
+        withdraw_instruction,
+        stake_pool_address,
+        fee_split_recipients.as_deref(),
+    ));
+
     // Merging the stake with account provided by user
     if maybe_stake_receiver_state.is_some() {
         for new_stake_keypair in &new_stake_keypairs {
@@ -2230,6 +2891,12 @@ fn command_withdraw_sol(
             pool_amount,
         )
     };
+    let fee_split_recipients = get_fee_split_recipients(config, stake_pool_address, &stake_pool)?;
+    let withdraw_instruction = with_fee_split(
+        withdraw_instruction,
+        stake_pool_address,
+        fee_split_recipients.as_deref(),
+    );
 
     instructions.push(withdraw_instruction);
 
@@ -2239,6 +2906,73 @@ fn command_withdraw_sol(
     Ok(())
 }
 
+fn command_close_user_stake_accounts(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    max_seed: u64,
+) -> CommandResult {
+    // Number of user stake accounts closed per transaction
+    const CLOSE_BATCH_SIZE: usize = 8;
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let user_wallet = config.token_owner.pubkey();
+    let epoch = config.rpc_client.get_epoch_info()?.epoch;
+
+    let user_stake_accounts = get_user_stake_accounts(
+        &config.rpc_client,
+        &config.stake_pool_program_id,
+        &user_wallet,
+        max_seed,
+    )?;
+    let mut closable_seeds = vec![];
+    let mut closable_lamports = 0;
+    for (seed, address, lamports, stake_state) in user_stake_accounts {
+        match stake_state {
+            stake::state::StakeStateV2::Stake(_, stake, _)
+                if stake.delegation.deactivation_epoch < epoch =>
+            {
+                closable_seeds.push(seed);
+                closable_lamports += lamports;
+            }
+            _ => {
+                if config.verbose {
+                    println!("Skipping user stake account {}, not deactivated", address);
+                }
+            }
+        }
+    }
+    if closable_seeds.is_empty() {
+        return Err(format!(
+            "No deactivated user stake accounts found for {}",
+            user_wallet
+        )
+        .into());
+    }
+
+    println!(
+        "Closing {} user stake accounts holding {}",
+        closable_seeds.len(),
+        Sol(closable_lamports)
+    );
+    let mut signers = vec![config.fee_payer.as_ref(), config.token_owner.as_ref()];
+    unique_signers!(signers);
+    for user_stake_seeds in closable_seeds.chunks(CLOSE_BATCH_SIZE) {
+        let instructions = vec![
+            spl_stake_pool::instruction::close_user_stake_accounts_with_session(
+                &config.stake_pool_program_id,
+                stake_pool_address,
+                &stake_pool.reserve_stake,
+                &user_wallet,
+                &user_wallet,
+                user_stake_seeds.to_vec(),
+            ),
+        ];
+        let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+        send_transaction(config, transaction)?;
+    }
+    Ok(())
+}
+
 fn command_set_manager(
     config: &Config,
     stake_pool_address: &Pubkey,
@@ -2413,16 +3147,11 @@ fn command_set_staker(
     Ok(())
 }
 
-fn command_set_funding_authority(
+fn command_propose_manager(
     config: &Config,
     stake_pool_address: &Pubkey,
-    new_authority: Option<Pubkey>,
-    funding_type: FundingType,
+    new_manager: Option<Pubkey>,
 ) -> CommandResult {
-    if !config.no_update && config.squads_multisig.is_none() {
-        command_update(config, stake_pool_address, false, false, false)?;
-    }
-
     let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
 
     // For Squads mode, use the on-chain manager (which is the multisig vault)
@@ -2432,12 +3161,11 @@ fn command_set_funding_authority(
         config.manager.pubkey()
     };
 
-    let instructions = vec![spl_stake_pool::instruction::set_funding_authority(
+    let instructions = vec![spl_stake_pool::instruction::propose_manager(
         &config.stake_pool_program_id,
         stake_pool_address,
         &manager_pubkey,
-        new_authority.as_ref(),
-        funding_type,
+        new_manager.as_ref(),
     )];
 
     // Squads mode: wrap in a proposal
@@ -2456,15 +3184,69 @@ fn command_set_funding_authority(
     Ok(())
 }
 
-fn command_set_fee(
+fn command_accept_manager(
     config: &Config,
     stake_pool_address: &Pubkey,
-    new_fee: FeeType,
+    new_fee_receiver: &Option<Pubkey>,
 ) -> CommandResult {
-    if !config.no_update && config.squads_multisig.is_none() {
-        command_update(config, stake_pool_address, false, false, false)?;
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // In Squads mode the vault accepts, otherwise the manager keypair does
+    let pending_manager = if let Some(multisig_address) = config.squads_multisig {
+        squads::get_vault_pubkey(&config.rpc_client, &multisig_address)?
+    } else {
+        config.manager.pubkey()
+    };
+    if stake_pool.pending_manager != Some(pending_manager) {
+        return Err(format!(
+            "{} is not the pending manager of the stake pool",
+            pending_manager
+        )
+        .into());
+    }
+
+    let new_fee_receiver = match new_fee_receiver {
+        None => stake_pool.manager_fee_account,
+        Some(value) => {
+            let token_account =
+                get_token_account(&config.rpc_client, value, &stake_pool.pool_mint)?;
+            if token_account.mint != stake_pool.pool_mint {
+                return Err("Fee receiver account belongs to a different mint"
+                    .to_string()
+                    .into());
+            }
+            *value
+        }
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::accept_manager(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &pending_manager,
+        &new_fee_receiver,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
     }
 
+    let mut signers = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_propose_staker(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    new_staker: Option<Pubkey>,
+) -> CommandResult {
     let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
 
     // For Squads mode, use the on-chain manager (which is the multisig vault)
@@ -2474,11 +3256,11 @@ fn command_set_fee(
         config.manager.pubkey()
     };
 
-    let instructions = vec![spl_stake_pool::instruction::set_fee(
+    let instructions = vec![spl_stake_pool::instruction::propose_staker(
         &config.stake_pool_program_id,
         stake_pool_address,
         &manager_pubkey,
-        new_fee,
+        new_staker.as_ref(),
     )];
 
     // Squads mode: wrap in a proposal
@@ -2490,31 +3272,865 @@ fn command_set_fee(
         );
     }
 
-    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    let mut signers = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
     unique_signers!(signers);
     let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
     send_transaction(config, transaction)?;
     Ok(())
 }
 
-fn command_list_all_pools(config: &Config) -> CommandResult {
-    let all_pools = get_stake_pools(&config.rpc_client, &config.stake_pool_program_id)?;
-    let cli_stake_pool_vec: Vec<CliStakePool> =
-        all_pools.into_iter().map(CliStakePool::from).collect();
-    let cli_stake_pools = CliStakePools {
-        pools: cli_stake_pool_vec,
-    };
-    println!(
-        "{}",
-        config.output_format.formatted_string(&cli_stake_pools)
-    );
-    Ok(())
-}
-
-fn main() {
-    solana_logger::setup_with_default("solana=info");
+fn command_accept_staker(config: &Config, stake_pool_address: &Pubkey) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
 
-    let matches = App::new(crate_name!())
+    // In Squads mode the vault accepts, otherwise the staker keypair does
+    let pending_staker = if let Some(multisig_address) = config.squads_multisig {
+        squads::get_vault_pubkey(&config.rpc_client, &multisig_address)?
+    } else {
+        config.staker.pubkey()
+    };
+    if stake_pool.pending_staker != Some(pending_staker) {
+        return Err(format!(
+            "{} is not the pending staker of the stake pool",
+            pending_staker
+        )
+        .into());
+    }
+
+    let instructions = vec![spl_stake_pool::instruction::accept_staker(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &pending_staker,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.staker.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_funding_authority(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    new_authority: Option<Pubkey>,
+    funding_type: FundingType,
+) -> CommandResult {
+    if !config.no_update && config.squads_multisig.is_none() {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault)
+    let manager_pubkey = if config.squads_multisig.is_some() {
+        stake_pool.manager
+    } else {
+        config.manager.pubkey()
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::set_funding_authority(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        new_authority.as_ref(),
+        funding_type,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_fee(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    new_fee: FeeType,
+) -> CommandResult {
+    if !config.no_update && config.squads_multisig.is_none() {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault)
+    let manager_pubkey = if config.squads_multisig.is_some() {
+        stake_pool.manager
+    } else {
+        config.manager.pubkey()
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::set_fee(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        new_fee,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_fee_change_delay(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    delay_epochs: u64,
+) -> CommandResult {
+    if !config.no_update && config.squads_multisig.is_none() {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault)
+    let manager_pubkey = if config.squads_multisig.is_some() {
+        stake_pool.manager
+    } else {
+        config.manager.pubkey()
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::set_fee_change_delay(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        delay_epochs,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_fee_split(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    recipients: &[(Pubkey, u16)],
+) -> CommandResult {
+    if !config.no_update && config.squads_multisig.is_none() {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault),
+    // which also pays for the fee split account
+    let (manager_pubkey, payer_pubkey) = if config.squads_multisig.is_some() {
+        (stake_pool.manager, stake_pool.manager)
+    } else {
+        (config.manager.pubkey(), config.fee_payer.pubkey())
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::set_fee_split(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        &payer_pubkey,
+        recipients,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_insurance_fee(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    insurance_fee_bps: u16,
+) -> CommandResult {
+    if !config.no_update && config.squads_multisig.is_none() {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault),
+    // which also pays for the insurance fund account
+    let (manager_pubkey, payer_pubkey) = if config.squads_multisig.is_some() {
+        (stake_pool.manager, stake_pool.manager)
+    } else {
+        (config.manager.pubkey(), config.fee_payer.pubkey())
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::set_insurance_fee(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        &payer_pubkey,
+        insurance_fee_bps,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn get_insurance_fund_balance(
+    config: &Config,
+    insurance_fund_address: &Pubkey,
+) -> Result<u64, Error> {
+    let account = config.rpc_client.get_account(insurance_fund_address)?;
+    let rent_exempt_lamports = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(account.data.len())?;
+    Ok(account.lamports.saturating_sub(rent_exempt_lamports))
+}
+
+fn command_inject_insurance_fund(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    lamports: Option<u64>,
+) -> CommandResult {
+    if !config.no_update && config.squads_multisig.is_none() {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let (insurance_fund_address, _) =
+        find_insurance_fund_program_address(&config.stake_pool_program_id, stake_pool_address);
+    let available_lamports = get_insurance_fund_balance(config, &insurance_fund_address)?;
+    let lamports =
+        lamports.unwrap_or_else(|| available_lamports.min(stake_pool.uncovered_loss_lamports));
+    if lamports == 0 {
+        return Err("Nothing to inject: no uncovered stake loss or empty insurance fund".into());
+    }
+    if lamports > stake_pool.uncovered_loss_lamports {
+        return Err(format!(
+            "Injection of {} exceeds the uncovered stake loss of {}",
+            Sol(lamports),
+            Sol(stake_pool.uncovered_loss_lamports)
+        )
+        .into());
+    }
+    if lamports > available_lamports {
+        return Err(format!(
+            "Injection of {} exceeds the insurance fund balance of {}",
+            Sol(lamports),
+            Sol(available_lamports)
+        )
+        .into());
+    }
+
+    // For Squads mode, the multisig vault must be the manager or the staker
+    let authority_pubkey = if let Some(multisig_address) = config.squads_multisig {
+        squads::get_vault_pubkey(&config.rpc_client, &multisig_address)?
+    } else {
+        config.staker.pubkey()
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::inject_insurance_fund(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &authority_pubkey,
+        &stake_pool.reserve_stake,
+        lamports,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    println!(
+        "Injecting {} from insurance fund {} into the stake pool",
+        Sol(lamports),
+        insurance_fund_address
+    );
+    let mut signers = vec![config.fee_payer.as_ref(), config.staker.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_insurance_fund(config: &Config, stake_pool_address: &Pubkey) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    let (insurance_fund_address, _) =
+        find_insurance_fund_program_address(&config.stake_pool_program_id, stake_pool_address);
+    let insurance_fund = get_insurance_fund(&config.rpc_client, &insurance_fund_address)?;
+    let available_lamports = get_insurance_fund_balance(config, &insurance_fund_address)?;
+
+    let cli_insurance_fund = CliInsuranceFund {
+        address: insurance_fund_address.to_string(),
+        stake_pool: stake_pool_address.to_string(),
+        insurance_fee_bps: stake_pool.insurance_fee_bps,
+        available_lamports,
+        pending_lamports: stake_pool.pending_insurance_lamports,
+        deposited_lamports: insurance_fund.deposited_lamports,
+        injected_lamports: insurance_fund.injected_lamports,
+        uncovered_loss_lamports: stake_pool.uncovered_loss_lamports,
+    };
+    println!(
+        "{}",
+        config.output_format.formatted_string(&cli_insurance_fund)
+    );
+    Ok(())
+}
+
+fn command_create_rate_history(config: &Config, stake_pool_address: &Pubkey) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    if stake_pool.has_rate_history {
+        return Err(format!(
+            "Stake pool {} already records its rate history",
+            stake_pool_address
+        )
+        .into());
+    }
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault),
+    // which also pays for the rate history account
+    let (manager_pubkey, payer_pubkey) = if config.squads_multisig.is_some() {
+        (stake_pool.manager, stake_pool.manager)
+    } else {
+        (config.manager.pubkey(), config.fee_payer.pubkey())
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::create_rate_history(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        &payer_pubkey,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let (rate_history_address, _) =
+        find_rate_history_program_address(&config.stake_pool_program_id, stake_pool_address);
+    println!("Creating rate history {}", rate_history_address);
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_create_oracle(config: &Config, stake_pool_address: &Pubkey) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+    if stake_pool.has_oracle {
+        return Err(format!(
+            "Stake pool {} already keeps a price oracle",
+            stake_pool_address
+        )
+        .into());
+    }
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault),
+    // which also pays for the price oracle account
+    let (manager_pubkey, payer_pubkey) = if config.squads_multisig.is_some() {
+        (stake_pool.manager, stake_pool.manager)
+    } else {
+        (config.manager.pubkey(), config.fee_payer.pubkey())
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::create_oracle(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        &payer_pubkey,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let (oracle_address, _) =
+        find_oracle_program_address(&config.stake_pool_program_id, stake_pool_address);
+    println!("Creating price oracle {}", oracle_address);
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_migrate_pool(config: &Config, stake_pool_address: &Pubkey) -> CommandResult {
+    let account = config.rpc_client.get_account(stake_pool_address)?;
+    if account.owner != config.stake_pool_program_id
+        || account.data.first() != Some(&(AccountType::StakePool as u8))
+    {
+        return Err(format!("{} is not a stake pool", stake_pool_address).into());
+    }
+    if account.data.len() >= get_packed_len::<StakePool>() {
+        println!(
+            "Stake pool {} already has the current layout",
+            stake_pool_address
+        );
+        return Ok(());
+    }
+
+    let instructions = vec![spl_stake_pool::instruction::migrate_stake_pool(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &config.fee_payer.pubkey(),
+    )];
+
+    println!("Migrating stake pool {}", stake_pool_address);
+    let signers = vec![config.fee_payer.as_ref()];
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_history(config: &Config, stake_pool_address: &Pubkey, epochs: u64) -> CommandResult {
+    let (rate_history_address, _) =
+        find_rate_history_program_address(&config.stake_pool_program_id, stake_pool_address);
+    let rate_history = get_rate_history(&config.rpc_client, &rate_history_address)?;
+
+    let mut entries = rate_history
+        .entries()
+        .rev()
+        .take(epochs.saturating_add(1) as usize)
+        .map(|entry| CliRateHistoryEntry {
+            epoch: entry.epoch,
+            unix_timestamp: entry.unix_timestamp,
+            total_lamports: entry.total_lamports,
+            pool_token_supply: entry.pool_token_supply,
+            fee_pool_tokens: entry.fee_pool_tokens,
+            exchange_rate: entry.exchange_rate(),
+        })
+        .collect::<Vec<_>>();
+    entries.reverse();
+
+    let cli_rate_history = CliRateHistory {
+        address: rate_history_address.to_string(),
+        stake_pool: stake_pool_address.to_string(),
+        trailing_apy: rate_history.trailing_apy(epochs),
+        trailing_apy_epochs: epochs,
+        entries,
+    };
+    println!(
+        "{}",
+        config.output_format.formatted_string(&cli_rate_history)
+    );
+    Ok(())
+}
+
+fn command_set_max_validator_share(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    vote_account: Option<Pubkey>,
+    max_share_bps: Option<u16>,
+) -> CommandResult {
+    if !config.no_update && config.squads_multisig.is_none() {
+        command_update(config, stake_pool_address, false, false, false)?;
+    }
+
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault)
+    let manager_pubkey = if config.squads_multisig.is_some() {
+        stake_pool.manager
+    } else {
+        config.manager.pubkey()
+    };
+
+    let instruction = match vote_account {
+        Some(vote_account) => spl_stake_pool::instruction::set_validator_max_share(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &manager_pubkey,
+            &stake_pool.validator_list,
+            &vote_account,
+            max_share_bps,
+        ),
+        None => spl_stake_pool::instruction::set_cap(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &manager_pubkey,
+            CapType::ValidatorShare(max_share_bps),
+        ),
+    };
+    let instructions = vec![instruction];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_max_validator_commission(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    vote_account: Option<Pubkey>,
+    max_commission: Option<u8>,
+) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    let Some(vote_account) = vote_account else {
+        // For Squads mode, use the on-chain manager (which is the multisig vault)
+        let manager_pubkey = if config.squads_multisig.is_some() {
+            stake_pool.manager
+        } else {
+            config.manager.pubkey()
+        };
+        let instructions = vec![spl_stake_pool::instruction::set_cap(
+            &config.stake_pool_program_id,
+            stake_pool_address,
+            &manager_pubkey,
+            CapType::ValidatorCommission(max_commission),
+        )];
+
+        // Squads mode: wrap in a proposal
+        if config.squads_multisig.is_some() {
+            return send_transaction_or_squads_proposal(
+                config,
+                &instructions,
+                vec![config.fee_payer.as_ref()],
+            );
+        }
+
+        let mut signers: Vec<&dyn Signer> =
+            vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+        unique_signers!(signers);
+        let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+        send_transaction(config, transaction)?;
+        return Ok(());
+    };
+
+    // a validator's own limit lives in its metadata, next to the staker notes
+    let (validator_metadata_address, _) = find_validator_metadata_program_address(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &vote_account,
+    );
+    let validator_metadata =
+        get_validator_metadata(&config.rpc_client, &validator_metadata_address)?;
+    let instructions = vec![spl_stake_pool::instruction::update_validator_metadata(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &config.staker.pubkey(),
+        &vote_account,
+        validator_metadata.notes_hash,
+        max_commission.unwrap_or(MAX_COMMISSION),
+    )];
+    let mut signers = vec![config.fee_payer.as_ref(), config.staker.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_max_validator_vote_age(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    max_vote_age_epochs: Option<u64>,
+) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault)
+    let manager_pubkey = if config.squads_multisig.is_some() {
+        stake_pool.manager
+    } else {
+        config.manager.pubkey()
+    };
+    let instructions = vec![spl_stake_pool::instruction::set_cap(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        CapType::ValidatorVoteAge(max_vote_age_epochs),
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers: Vec<&dyn Signer> = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_pauser(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    new_pauser: Option<Pubkey>,
+) -> CommandResult {
+    let stake_pool = get_stake_pool(&config.rpc_client, stake_pool_address)?;
+
+    // For Squads mode, use the on-chain manager (which is the multisig vault)
+    let manager_pubkey = if config.squads_multisig.is_some() {
+        stake_pool.manager
+    } else {
+        config.manager.pubkey()
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::set_pauser(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &manager_pubkey,
+        new_pauser.as_ref(),
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.manager.as_ref()];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_paused(
+    config: &Config,
+    stake_pool_address: &Pubkey,
+    operations: u8,
+    pause: bool,
+) -> CommandResult {
+    // Only flip the named operations, so that concurrent proposals do not
+    // overwrite each other
+    let (pause_operations, unpause_operations) = if pause {
+        (operations, 0)
+    } else {
+        (0, operations)
+    };
+
+    // For Squads mode, the multisig vault must be the manager or the pauser
+    let authority: &dyn Signer = config
+        .pauser
+        .as_ref()
+        .map_or(config.manager.as_ref(), |pauser| pauser.as_ref());
+    let authority_pubkey = if let Some(multisig_address) = config.squads_multisig {
+        squads::get_vault_pubkey(&config.rpc_client, &multisig_address)?
+    } else {
+        authority.pubkey()
+    };
+
+    let instructions = vec![spl_stake_pool::instruction::update_paused(
+        &config.stake_pool_program_id,
+        stake_pool_address,
+        &authority_pubkey,
+        pause_operations,
+        unpause_operations,
+    )];
+
+    // Squads mode: wrap in a proposal
+    if config.squads_multisig.is_some() {
+        return send_transaction_or_squads_proposal(
+            config,
+            &instructions,
+            vec![config.fee_payer.as_ref()],
+        );
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), authority];
+    unique_signers!(signers);
+    let transaction = checked_transaction_with_signers(config, &instructions, &signers)?;
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_list_all_pools(config: &Config) -> CommandResult {
+    let all_pools = get_stake_pools(&config.rpc_client, &config.stake_pool_program_id)?;
+    let cli_stake_pool_vec: Vec<CliStakePool> =
+        all_pools.into_iter().map(CliStakePool::from).collect();
+    let cli_stake_pools = CliStakePools {
+        pools: cli_stake_pool_vec,
+    };
+    println!(
+        "{}",
+        config.output_format.formatted_string(&cli_stake_pools)
+    );
+    Ok(())
+}
+
+fn paused_operations_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("operations")
+        .long("operations")
+        .value_name("OPERATION")
+        .possible_values(&["deposit", "withdrawal", "session", "rebalance"]) // PausedOperation enum
+        .takes_value(true)
+        .multiple(true)
+        .use_delimiter(true)
+        .help("Operations to update, comma separated. [default: all operations]")
+}
+
+fn is_fee_split_recipient(value: String) -> Result<(), String> {
+    let (token_account, weight_bps) = value.split_once(':').ok_or_else(|| {
+        format!(
+            "Expected <TOKEN_ACCOUNT>:<BASIS_POINTS>, received {}",
+            value
+        )
+    })?;
+    Pubkey::from_str(token_account)
+        .map_err(|err| format!("Invalid token account {}: {}", token_account, err))?;
+    weight_bps
+        .parse::<u16>()
+        .map_err(|err| format!("Invalid weight {}: {}", weight_bps, err))?;
+    Ok(())
+}
+
+fn is_token_metadata_field(value: String) -> Result<(), String> {
+    match value.split_once('=') {
+        Some((key, _)) if !key.is_empty() => Ok(()),
+        _ => Err(format!("Expected <KEY>=<VALUE>, received {}", value)),
+    }
+}
+
+fn token_metadata_field_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("field")
+        .long("field")
+        .validator(is_token_metadata_field)
+        .value_name("KEY=VALUE")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .help("Additional key-value metadata field to set. May be repeated. Only supported by Token-2022 pool mints holding their own metadata.")
+}
+
+fn token_metadata_fields_of(arg_matches: &ArgMatches<'_>) -> Vec<(String, String)> {
+    arg_matches
+        .values_of("field")
+        .map(|fields| {
+            fields
+                .map(|field| {
+                    let (key, value) = field.split_once('=').unwrap();
+                    (key.to_string(), value.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn fee_split_recipients_of(arg_matches: &ArgMatches<'_>) -> Vec<(Pubkey, u16)> {
+    arg_matches
+        .values_of("recipient")
+        .map(|recipients| {
+            recipients
+                .map(|recipient| {
+                    let (token_account, weight_bps) = recipient.split_once(':').unwrap();
+                    (
+                        Pubkey::from_str(token_account).unwrap(),
+                        weight_bps.parse().unwrap(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn paused_operations_of(arg_matches: &ArgMatches<'_>) -> u8 {
+    arg_matches
+        .values_of("operations")
+        .map_or(PausedOperation::ALL, |operations| {
+            operations
+                .map(|operation| match operation {
+                    "deposit" => PausedOperation::Deposit,
+                    "withdrawal" => PausedOperation::Withdrawal,
+                    "session" => PausedOperation::Session,
+                    "rebalance" => PausedOperation::Rebalance,
+                    _ => unreachable!(),
+                })
+                .fold(0, |mask, operation| mask | operation.mask())
+        })
+}
+
+fn main() {
+    solana_logger::setup_with_default("solana=info");
+
+    let matches = App::new(crate_name!())
         .about(crate_description!())
         .version(crate_version!())
         .setting(AppSettings::SubcommandRequiredElseHelp)
@@ -2541,315 +4157,1036 @@ fn main() {
                 .help("Show additional information"),
         )
         .arg(
-            Arg::with_name("output_format")
-                .long("output")
-                .value_name("FORMAT")
-                .global(true)
+            Arg::with_name("output_format")
+                .long("output")
+                .value_name("FORMAT")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["json", "json-compact"])
+                .help("Return information in specified output format"),
+        )
+        .arg(
+            Arg::with_name("dry_run")
+                .long("dry-run")
+                .takes_value(false)
+                .global(true)
+                .help("Simulate transaction instead of executing"),
+        )
+        .arg(
+            Arg::with_name("squads_multisig")
+                .long("squads-multisig")
+                .value_name("PUBKEY")
+                .validator(is_valid_pubkey)
+                .takes_value(true)
+                .global(true)
+                .help("Squads v3 multisig address. When set, creates a proposal instead of executing directly."),
+        )
+        .arg(
+            Arg::with_name("squads_auto_approve")
+                .long("squads-auto-approve")
+                .takes_value(false)
+                .global(true)
+                .requires("squads_multisig")
+                .help("Automatically approve the Squads proposal after creating it (requires --squads-multisig)."),
+        )
+        .arg(
+            Arg::with_name("no_update")
+                .long("no-update")
+                .takes_value(false)
+                .global(true)
+                .help("Do not automatically update the stake pool if needed"),
+        )
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .global(true)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(
+            Arg::with_name("staker")
+                .long("staker")
+                .value_name("KEYPAIR")
+                .validator(is_valid_signer)
+                .takes_value(true)
+                .global(true)
+                .help("Stake pool staker. [default: cli config keypair]"),
+        )
+        .arg(
+            Arg::with_name("manager")
+                .long("manager")
+                .value_name("KEYPAIR")
+                .validator(is_valid_signer)
+                .takes_value(true)
+                .global(true)
+                .help("Stake pool manager. [default: cli config keypair]"),
+        )
+        .arg(
+            Arg::with_name("funding_authority")
+                .long("funding-authority")
+                .value_name("KEYPAIR")
+                .validator(is_valid_signer)
+                .takes_value(true)
+                .global(true)
+                .help("Stake pool funding authority for deposits or withdrawals. [default: cli config keypair]"),
+        )
+        .arg(
+            Arg::with_name("pauser")
+                .long("pauser")
+                .value_name("KEYPAIR")
+                .validator(is_valid_signer)
+                .takes_value(true)
+                .global(true)
+                .help("Stake pool pauser, used to pause or unpause instead of the manager."),
+        )
+        .arg(
+            Arg::with_name("token_owner")
+                .long("token-owner")
+                .value_name("KEYPAIR")
+                .validator(is_valid_signer)
+                .takes_value(true)
+                .global(true)
+                .help("Owner of pool token account [default: cli config keypair]"),
+        )
+        .arg(
+            Arg::with_name("fee_payer")
+                .long("fee-payer")
+                .value_name("KEYPAIR")
+                .validator(is_valid_signer)
+                .takes_value(true)
+                .global(true)
+                .help("Transaction fee payer account [default: cli config keypair]"),
+        )
+        .arg(compute_unit_price_arg().validator(is_parsable::<u64>).global(true))
+        .arg(
+            Arg::with_name(COMPUTE_UNIT_LIMIT_ARG.name)
+                .long(COMPUTE_UNIT_LIMIT_ARG.long)
+                .takes_value(true)
+                .value_name("COMPUTE-UNIT-LIMIT")
+                .help(COMPUTE_UNIT_LIMIT_ARG.help)
+                .validator(is_compute_unit_limit_or_simulated)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .long("program-id")
+                .validator(is_pubkey)
+                .value_name("PROGRAM-ID")
+                .takes_value(true)
+                .help("Stake pool program id. [default: spl_stake_pool::id() or spl_stake_pool::devnet::id(), depending on target network]")
+                .global(true)
+        )
+        .subcommand(SubCommand::with_name("create-pool")
+            .about("Create a new stake pool")
+            .arg(
+                Arg::with_name("epoch_fee_numerator")
+                    .long("epoch-fee-numerator")
+                    .short("n")
+                    .validator(is_parsable::<u64>)
+                    .value_name("NUMERATOR")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Epoch fee numerator, fee amount is numerator divided by denominator."),
+            )
+            .arg(
+                Arg::with_name("epoch_fee_denominator")
+                    .long("epoch-fee-denominator")
+                    .short("d")
+                    .validator(is_parsable::<u64>)
+                    .value_name("DENOMINATOR")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Epoch fee denominator, fee amount is numerator divided by denominator."),
+            )
+            .arg(
+                Arg::with_name("withdrawal_fee_numerator")
+                    .long("withdrawal-fee-numerator")
+                    .validator(is_parsable::<u64>)
+                    .value_name("NUMERATOR")
+                    .takes_value(true)
+                    .requires("withdrawal_fee_denominator")
+                    .help("Withdrawal fee numerator, fee amount is numerator divided by denominator [default: 0]"),
+            ).arg(
+                Arg::with_name("withdrawal_fee_denominator")
+                    .long("withdrawal-fee-denominator")
+                    .validator(is_parsable::<u64>)
+                    .value_name("DENOMINATOR")
+                    .takes_value(true)
+                    .requires("withdrawal_fee_numerator")
+                    .help("Withdrawal fee denominator, fee amount is numerator divided by denominator [default: 0]"),
+            )
+            .arg(
+                Arg::with_name("deposit_fee_numerator")
+                    .long("deposit-fee-numerator")
+                    .validator(is_parsable::<u64>)
+                    .value_name("NUMERATOR")
+                    .takes_value(true)
+                    .requires("deposit_fee_denominator")
+                    .help("Deposit fee numerator, fee amount is numerator divided by denominator [default: 0]"),
+            ).arg(
+                Arg::with_name("deposit_fee_denominator")
+                    .long("deposit-fee-denominator")
+                    .validator(is_parsable::<u64>)
+                    .value_name("DENOMINATOR")
+                    .takes_value(true)
+                    .requires("deposit_fee_numerator")
+                    .help("Deposit fee denominator, fee amount is numerator divided by denominator [default: 0]"),
+            )
+            .arg(
+                Arg::with_name("referral_fee")
+                    .long("referral-fee")
+                    .validator(is_valid_percentage)
+                    .value_name("FEE_PERCENTAGE")
+                    .takes_value(true)
+                    .help("Referral fee percentage, maximum 100"),
+            )
+            .arg(
+                Arg::with_name("max_validators")
+                    .long("max-validators")
+                    .short("m")
+                    .validator(is_parsable::<u32>)
+                    .value_name("NUMBER")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Max number of validators included in the stake pool"),
+            )
+            .arg(
+                Arg::with_name("deposit_authority")
+                    .long("deposit-authority")
+                    .short("a")
+                    .validator(is_valid_signer)
+                    .value_name("DEPOSIT_AUTHORITY_KEYPAIR")
+                    .takes_value(true)
+                    .help("Deposit authority required to sign all deposits into the stake pool"),
+            )
+            .arg(
+                Arg::with_name("pool_keypair")
+                    .long("pool-keypair")
+                    .short("p")
+                    .validator(is_keypair_or_ask_keyword)
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .help("Stake pool keypair [default: new keypair]"),
+            )
+            .arg(
+                Arg::with_name("validator_list_keypair")
+                    .long("validator-list-keypair")
+                    .validator(is_keypair_or_ask_keyword)
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .help("Validator list keypair [default: new keypair]"),
+            )
+            .arg(
+                Arg::with_name("mint_keypair")
+                    .long("mint-keypair")
+                    .validator(is_keypair_or_ask_keyword)
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .help("Stake pool mint keypair [default: new keypair]"),
+            )
+            .arg(
+                Arg::with_name("reserve_keypair")
+                    .long("reserve-keypair")
+                    .validator(is_keypair_or_ask_keyword)
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .help("Stake pool reserve keypair [default: new keypair]"),
+            )
+            .arg(
+                Arg::with_name("unsafe_fees")
+                    .long("unsafe-fees")
+                    .takes_value(false)
+                    .help("Bypass fee checks, allowing pool to be created with unsafe fees"),
+            )
+        )
+        .subcommand(SubCommand::with_name("create-token-metadata")
+        .about("Creates stake pool token metadata")
+        .arg(
+            Arg::with_name("pool")
+                .index(1)
+                .validator(is_pubkey)
+                .value_name("POOL_ADDRESS")
                 .takes_value(true)
-                .possible_values(&["json", "json-compact"])
-                .help("Return information in specified output format"),
+                .required(true)
+                .help("Stake pool address"),
         )
         .arg(
-            Arg::with_name("dry_run")
-                .long("dry-run")
-                .takes_value(false)
-                .global(true)
-                .help("Simulate transaction instead of executing"),
+            Arg::with_name("name")
+                .index(2)
+                .value_name("TOKEN_NAME")
+                .takes_value(true)
+                .required(true)
+                .help("Name of the token"),
         )
         .arg(
-            Arg::with_name("squads_multisig")
-                .long("squads-multisig")
-                .value_name("PUBKEY")
-                .validator(is_valid_pubkey)
+            Arg::with_name("symbol")
+                .index(3)
+                .value_name("TOKEN_SYMBOL")
                 .takes_value(true)
-                .global(true)
-                .help("Squads v3 multisig address. When set, creates a proposal instead of executing directly."),
+                .required(true)
+                .help("Symbol of the token"),
         )
         .arg(
-            Arg::with_name("squads_auto_approve")
-                .long("squads-auto-approve")
-                .takes_value(false)
-                .global(true)
-                .requires("squads_multisig")
-                .help("Automatically approve the Squads proposal after creating it (requires --squads-multisig)."),
+            Arg::with_name("uri")
+                .index(4)
+                .value_name("TOKEN_URI")
+                .takes_value(true)
+                .required(true)
+                .help("URI of the token metadata json"),
+        )
+        .arg(token_metadata_field_arg())
+    )
+    .subcommand(SubCommand::with_name("update-token-metadata")
+    .about("Updates stake pool token metadata")
+    .arg(
+        Arg::with_name("pool")
+            .index(1)
+            .validator(is_pubkey)
+            .value_name("POOL_ADDRESS")
+            .takes_value(true)
+            .required(true)
+            .help("Stake pool address"),
+    )
+    .arg(
+        Arg::with_name("name")
+            .index(2)
+            .value_name("TOKEN_NAME")
+            .takes_value(true)
+            .required(true)
+            .help("Name of the token"),
+    )
+    .arg(
+        Arg::with_name("symbol")
+            .index(3)
+            .value_name("TOKEN_SYMBOL")
+            .takes_value(true)
+            .required(true)
+            .help("Symbol of the token"),
+    )
+    .arg(
+        Arg::with_name("uri")
+            .index(4)
+            .value_name("TOKEN_URI")
+            .takes_value(true)
+            .required(true)
+            .help("URI of the token metadata json"),
+        )
+    .arg(token_metadata_field_arg())
+    .arg(
+        Arg::with_name("remove_field")
+            .long("remove-field")
+            .value_name("KEY")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .help("Additional metadata field to remove. May be repeated. Only supported by Token-2022 pool mints holding their own metadata."),
+        )
+    )
+        .subcommand(SubCommand::with_name("add-validator")
+            .about("Add validator account to the stake pool. Must be signed by the pool staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("The validator vote account that the stake is delegated to"),
+            )
+        )
+        .subcommand(SubCommand::with_name("remove-validator")
+            .about("Remove validator account from the stake pool. Must be signed by the pool staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Vote account for the validator to remove from the pool"),
+            )
+        )
+        .subcommand(SubCommand::with_name("deactivate-high-commission-validator")
+            .about("Start deactivating a validator whose commission is above the maximum allowed by the pool or its metadata. Anyone can do it.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Vote account of the validator to deactivate"),
+            )
+        )
+        .subcommand(SubCommand::with_name("deactivate-delinquent-validator")
+            .about("Start deactivating a validator that has not voted for longer than the pool allows, collecting a small reward from the reserve. Anyone can do it.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Vote account of the validator to deactivate"),
+            )
+        )
+        .subcommand(SubCommand::with_name("increase-validator-stake")
+            .about("Increase stake to a validator, drawing from the stake pool reserve. Must be signed by the pool staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Vote account for the validator to increase stake to"),
+            )
+            .arg(
+                Arg::with_name("amount")
+                    .index(3)
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .help("Amount in SOL to add to the validator stake account. Must be at least the rent-exempt amount for a stake plus 1 SOL for merging."),
+            )
         )
-        .arg(
-            Arg::with_name("no_update")
-                .long("no-update")
-                .takes_value(false)
-                .global(true)
-                .help("Do not automatically update the stake pool if needed"),
+        .subcommand(SubCommand::with_name("decrease-validator-stake")
+            .about("Decrease stake to a validator, splitting from the active stake. Must be signed by the pool staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Vote account for the validator to decrease stake from"),
+            )
+            .arg(
+                Arg::with_name("amount")
+                    .index(3)
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .help("Amount in SOL to remove from the validator stake account. Must be at least the rent-exempt amount for a stake."),
+            )
         )
-        .arg(
-            Arg::with_name("json_rpc_url")
-                .long("url")
-                .value_name("URL")
-                .takes_value(true)
-                .validator(is_url)
-                .global(true)
-                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        .subcommand(SubCommand::with_name("redelegate")
+            .about("Decrease active stake on one validator and increase another by the same \
+                    amount. The source stake is deactivated and returns to the reserve after \
+                    cooling down, while the reserve funds the destination right away. The reserve pays the amount plus twice the stake \
+                    rent-exemption, and must keep its own rent-exemption and the lamports set \
+                    aside for withdrawal tickets. Must be signed by the pool staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("source_vote_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("SOURCE_VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Vote account for the validator to move stake from"),
+            )
+            .arg(
+                Arg::with_name("destination_vote_account")
+                    .index(3)
+                    .validator(is_pubkey)
+                    .value_name("DESTINATION_VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Vote account for the validator to move stake to"),
+            )
+            .arg(
+                Arg::with_name("amount")
+                    .index(4)
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Amount in SOL to move. The reserve must hold this amount plus three times \
+                           the rent-exempt amount for a stake, plus the lamports set aside for \
+                           withdrawal tickets."),
+            )
         )
-        .arg(
-            Arg::with_name("staker")
-                .long("staker")
-                .value_name("KEYPAIR")
-                .validator(is_valid_signer)
-                .takes_value(true)
-                .global(true)
-                .help("Stake pool staker. [default: cli config keypair]"),
+        .subcommand(SubCommand::with_name("rebalance")
+            .about("Rebalance stake between the pool's validators, weighted by their \
+                    vote credits, commission and skip rate. Must be signed by the pool staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("retained_reserve")
+                    .long("retained-reserve")
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .default_value("0")
+                    .help("Amount in SOL to keep in the reserve for SOL withdrawals"),
+            )
+            .arg(
+                Arg::with_name("max_commission")
+                    .long("max-commission")
+                    .validator(is_parsable::<u8>)
+                    .value_name("PERCENT")
+                    .takes_value(true)
+                    .default_value("100")
+                    .help("Validators charging a higher commission get no stake above the minimum"),
+            )
+            .arg(
+                Arg::with_name("tolerance")
+                    .long("tolerance")
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .default_value("0")
+                    .help("Amount in SOL a validator may be off its target before its stake is moved"),
+            )
+            .arg(
+                Arg::with_name("dry_run")
+                    .long("dry-run")
+                    .takes_value(false)
+                    .help("Print the rebalancing plan without sending any transaction"),
+            )
         )
-        .arg(
-            Arg::with_name("manager")
-                .long("manager")
-                .value_name("KEYPAIR")
-                .validator(is_valid_signer)
-                .takes_value(true)
-                .global(true)
-                .help("Stake pool manager. [default: cli config keypair]"),
+        .subcommand(SubCommand::with_name("set-preferred-validator")
+            .about("Set the preferred validator for deposits or withdrawals. Must be signed by the pool staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("preferred_type")
+                    .index(2)
+                    .value_name("OPERATION")
+                    .possible_values(&["deposit", "withdraw"]) // PreferredValidatorType enum
+                    .takes_value(true)
+                    .required(true)
+                    .help("Operation for which to restrict the validator"),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .long("vote-account")
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .help("Vote account for the validator that users must deposit into."),
+            )
+            .arg(
+                Arg::with_name("unset")
+                    .long("unset")
+                    .takes_value(false)
+                    .help("Unset the preferred validator."),
+            )
+            .group(ArgGroup::with_name("validator")
+                .arg("vote_account")
+                .arg("unset")
+                .required(true)
+            )
         )
-        .arg(
-            Arg::with_name("funding_authority")
-                .long("funding-authority")
-                .value_name("KEYPAIR")
-                .validator(is_valid_signer)
-                .takes_value(true)
-                .global(true)
-                .help("Stake pool funding authority for deposits or withdrawals. [default: cli config keypair]"),
+        .subcommand(SubCommand::with_name("deposit-stake")
+            .about("Deposit active stake account into the stake pool in exchange for pool tokens")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("stake_account")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("STAKE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake address to join the pool"),
+            )
+            .arg(
+                Arg::with_name("withdraw_authority")
+                    .long("withdraw-authority")
+                    .validator(is_valid_signer)
+                    .value_name("KEYPAIR")
+                    .takes_value(true)
+                    .help("Withdraw authority for the stake account to be deposited. [default: cli config keypair]"),
+            )
+            .arg(
+                Arg::with_name("token_receiver")
+                    .long("token-receiver")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .help("Account to receive the minted pool tokens. \
+                          Defaults to the token-owner's associated pool token account. \
+                          Creates the account if it does not exist."),
+            )
+            .arg(
+                Arg::with_name("referrer")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .help("Pool token account to receive the referral fees for deposits. \
+                          Defaults to the token receiver."),
+            )
+        )
+        .subcommand(SubCommand::with_name("deposit-all-stake")
+            .about("Deposit all active stake accounts into the stake pool in exchange for pool tokens")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("stake_authority")
+                    .index(2)
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake authority address to search for stake accounts"),
+            )
+            .arg(
+                Arg::with_name("withdraw_authority")
+                    .long("withdraw-authority")
+                    .validator(is_valid_signer)
+                    .value_name("KEYPAIR")
+                    .takes_value(true)
+                    .help("Withdraw authority for the stake account to be deposited. [default: cli config keypair]"),
+            )
+            .arg(
+                Arg::with_name("token_receiver")
+                    .long("token-receiver")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .help("Account to receive the minted pool tokens. \
+                          Defaults to the token-owner's associated pool token account. \
+                          Creates the account if it does not exist."),
+            )
+            .arg(
+                Arg::with_name("referrer")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .help("Pool token account to receive the referral fees for deposits. \
+                          Defaults to the token receiver."),
+            )
         )
-        .arg(
-            Arg::with_name("token_owner")
-                .long("token-owner")
-                .value_name("KEYPAIR")
-                .validator(is_valid_signer)
-                .takes_value(true)
-                .global(true)
-                .help("Owner of pool token account [default: cli config keypair]"),
+        .subcommand(SubCommand::with_name("deposit-sol")
+            .about("Deposit SOL into the stake pool in exchange for pool tokens")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            ).arg(
+                Arg::with_name("amount")
+                    .index(2)
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
+                    .takes_value(true)
+                    .help("Amount in SOL to deposit into the stake pool reserve account."),
+            )
+            .arg(
+                Arg::with_name("from")
+                    .long("from")
+                    .validator(is_valid_signer)
+                    .value_name("KEYPAIR")
+                    .takes_value(true)
+                    .help("Source account of funds. [default: cli config keypair]"),
+            )
+            .arg(
+                Arg::with_name("token_receiver")
+                    .long("token-receiver")
+                    .validator(is_pubkey)
+                    .value_name("POOL_TOKEN_RECEIVER_ADDRESS")
+                    .takes_value(true)
+                    .help("Account to receive the minted pool tokens. \
+                          Defaults to the token-owner's associated pool token account. \
+                          Creates the account if it does not exist."),
+            )
+            .arg(
+                Arg::with_name("referrer")
+                    .long("referrer")
+                    .validator(is_pubkey)
+                    .value_name("REFERRER_TOKEN_ADDRESS")
+                    .takes_value(true)
+                    .help("Account to receive the referral fees for deposits. \
+                          Defaults to the token receiver."),
+            )
+            .arg(
+                Arg::with_name("referral_code")
+                    .long("referral-code")
+                    .value_name("CODE")
+                    .takes_value(true)
+                    .conflicts_with("referrer")
+                    .help("Referral code registered on-chain with this stake pool. \
+                          The referral fee goes to the code's payout account and \
+                          the deposit is attributed to the referrer."),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .long("vote-account")
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .help("Deposit on behalf of this validator in the pool. \
+                          The SOL still lands in the reserve, and is recorded as \
+                          stake for the staker to delegate to the validator."),
+            )
         )
-        .arg(
-            Arg::with_name("fee_payer")
-                .long("fee-payer")
-                .value_name("KEYPAIR")
-                .validator(is_valid_signer)
-                .takes_value(true)
-                .global(true)
-                .help("Transaction fee payer account [default: cli config keypair]"),
+        .subcommand(SubCommand::with_name("register-referrer")
+            .about("Register an on-chain referral code for the stake pool. \
+                    The token owner becomes the referrer authority.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("code")
+                    .index(2)
+                    .value_name("CODE")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Referral code: up to 32 ASCII letters, digits, '-' or '_'"),
+            )
+            .arg(
+                Arg::with_name("payout_account")
+                    .long("payout-account")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .help("Pool token account to receive the referral fees. \
+                          Defaults to the token-owner's associated pool token account. \
+                          Creates the account if it does not exist."),
+            )
         )
-        .arg(compute_unit_price_arg().validator(is_parsable::<u64>).global(true))
-        .arg(
-            Arg::with_name(COMPUTE_UNIT_LIMIT_ARG.name)
-                .long(COMPUTE_UNIT_LIMIT_ARG.long)
-                .takes_value(true)
-                .value_name("COMPUTE-UNIT-LIMIT")
-                .help(COMPUTE_UNIT_LIMIT_ARG.help)
-                .validator(is_compute_unit_limit_or_simulated)
-                .global(true)
+        .subcommand(SubCommand::with_name("update-referrer")
+            .about("Change the payout account or authority of a registered referral code. \
+                    Must be signed by the current referrer authority, the token owner.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address"),
+            )
+            .arg(
+                Arg::with_name("code")
+                    .index(2)
+                    .value_name("CODE")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Registered referral code"),
+            )
+            .arg(
+                Arg::with_name("payout_account")
+                    .long("payout-account")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("New pool token account to receive the referral fees"),
+            )
+            .arg(
+                Arg::with_name("new_authority")
+                    .long("new-authority")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .help("Transfer the referral code to a new authority"),
+            )
         )
-        .arg(
-            Arg::with_name("program_id")
-                .long("program-id")
-                .validator(is_pubkey)
-                .value_name("PROGRAM-ID")
-                .takes_value(true)
-                .help("Stake pool program id. [default: spl_stake_pool::id() or spl_stake_pool::devnet::id(), depending on target network]")
-                .global(true)
+        .subcommand(SubCommand::with_name("list")
+            .about("List stake accounts managed by this pool")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address."),
+            )
+            .arg(
+                Arg::with_name("sort")
+                    .long("sort")
+                    .value_name("SORT_OPTION")
+                    .takes_value(true)
+                    .possible_values(&["stake", "update-epoch"])
+                    .help("Sort validators by: stake (largest first) or update-epoch (most recent first)"),
+            )
         )
-        .subcommand(SubCommand::with_name("create-pool")
-            .about("Create a new stake pool")
+        .subcommand(SubCommand::with_name("update")
+            .about("Updates all balances in the pool after validator stake accounts receive rewards.")
             .arg(
-                Arg::with_name("epoch_fee_numerator")
-                    .long("epoch-fee-numerator")
-                    .short("n")
-                    .validator(is_parsable::<u64>)
-                    .value_name("NUMERATOR")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Epoch fee numerator, fee amount is numerator divided by denominator."),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("epoch_fee_denominator")
-                    .long("epoch-fee-denominator")
-                    .short("d")
-                    .validator(is_parsable::<u64>)
-                    .value_name("DENOMINATOR")
+                Arg::with_name("force")
+                    .long("force")
+                    .takes_value(false)
+                    .help("Update balances, even if it has already been performed this epoch."),
+            )
+            .arg(
+                Arg::with_name("no_merge")
+                    .long("no-merge")
+                    .takes_value(false)
+                    .help("Do not automatically merge transient stakes. Useful if the stake pool is in an expected state, but the balances still need to be updated."),
+            )
+            .arg(
+                Arg::with_name("stale_only")
+                    .long("stale-only")
+                    .takes_value(false)
+                    .help("If set, only updates validator list balances that have not been updated for this epoch. Otherwise, updates all validator balances on the validator list."),
+            )
+        )
+        .subcommand(SubCommand::with_name("withdraw-stake")
+            .about("Withdraw active stake from the stake pool in exchange for pool tokens")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Epoch fee denominator, fee amount is numerator divided by denominator."),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("withdrawal_fee_numerator")
-                    .long("withdrawal-fee-numerator")
-                    .validator(is_parsable::<u64>)
-                    .value_name("NUMERATOR")
+                Arg::with_name("amount")
+                    .index(2)
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
                     .takes_value(true)
-                    .requires("withdrawal_fee_denominator")
-                    .help("Withdrawal fee numerator, fee amount is numerator divided by denominator [default: 0]"),
-            ).arg(
-                Arg::with_name("withdrawal_fee_denominator")
-                    .long("withdrawal-fee-denominator")
-                    .validator(is_parsable::<u64>)
-                    .value_name("DENOMINATOR")
+                    .required(true)
+                    .help("Amount of pool tokens to withdraw for activated stake."),
+            )
+            .arg(
+                Arg::with_name("pool_account")
+                    .long("pool-account")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
                     .takes_value(true)
-                    .requires("withdrawal_fee_numerator")
-                    .help("Withdrawal fee denominator, fee amount is numerator divided by denominator [default: 0]"),
+                    .help("Pool token account to withdraw tokens from. Defaults to the token-owner's associated token account."),
+            )
+            .arg(
+                Arg::with_name("stake_receiver")
+                    .long("stake-receiver")
+                    .validator(is_pubkey)
+                    .value_name("STAKE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .requires("withdraw_from")
+                    .help("Stake account from which to receive a stake from the stake pool. Defaults to a new stake account."),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .long("vote-account")
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .help("Validator to withdraw from. Defaults to the largest validator stakes in the pool."),
+            )
+            .arg(
+                Arg::with_name("use_reserve")
+                    .long("use-reserve")
+                    .takes_value(false)
+                    .help("Withdraw from the stake pool's reserve. Only possible if all validator stakes are at the minimum possible amount."),
+            )
+            .group(ArgGroup::with_name("withdraw_from")
+                .arg("use_reserve")
+                .arg("vote_account")
             )
+        )
+        .subcommand(SubCommand::with_name("withdraw-sol")
+            .about("Withdraw SOL from the stake pool's reserve in exchange for pool tokens")
             .arg(
-                Arg::with_name("deposit_fee_numerator")
-                    .long("deposit-fee-numerator")
-                    .validator(is_parsable::<u64>)
-                    .value_name("NUMERATOR")
-                    .takes_value(true)
-                    .requires("deposit_fee_denominator")
-                    .help("Deposit fee numerator, fee amount is numerator divided by denominator [default: 0]"),
-            ).arg(
-                Arg::with_name("deposit_fee_denominator")
-                    .long("deposit-fee-denominator")
-                    .validator(is_parsable::<u64>)
-                    .value_name("DENOMINATOR")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
-                    .requires("deposit_fee_numerator")
-                    .help("Deposit fee denominator, fee amount is numerator divided by denominator [default: 0]"),
+                    .required(true)
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("referral_fee")
-                    .long("referral-fee")
-                    .validator(is_valid_percentage)
-                    .value_name("FEE_PERCENTAGE")
+                Arg::with_name("sol_receiver")
+                    .index(2)
+                    .validator(is_valid_pubkey)
+                    .value_name("SYSTEM_ACCOUNT_ADDRESS_OR_KEYPAIR")
                     .takes_value(true)
-                    .help("Referral fee percentage, maximum 100"),
+                    .required(true)
+                    .help("System account to receive SOL from the stake pool. Defaults to the payer."),
             )
             .arg(
-                Arg::with_name("max_validators")
-                    .long("max-validators")
-                    .short("m")
-                    .validator(is_parsable::<u32>)
-                    .value_name("NUMBER")
+                Arg::with_name("amount")
+                    .index(3)
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
                     .takes_value(true)
                     .required(true)
-                    .help("Max number of validators included in the stake pool"),
+                    .help("Amount of pool tokens to withdraw for SOL."),
             )
             .arg(
-                Arg::with_name("deposit_authority")
-                    .long("deposit-authority")
-                    .short("a")
-                    .validator(is_valid_signer)
-                    .value_name("DEPOSIT_AUTHORITY_KEYPAIR")
+                Arg::with_name("pool_account")
+                    .long("pool-account")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
                     .takes_value(true)
-                    .help("Deposit authority required to sign all deposits into the stake pool"),
+                    .help("Pool token account to withdraw tokens from. Defaults to the token-owner's associated token account."),
             )
+        )
+        .subcommand(SubCommand::with_name("close-user-stake-accounts")
+            .about("Close the token owner's deactivated user stake accounts left by session stake withdrawals, returning their rent to the pool reserve and their lamports to the token owner")
             .arg(
-                Arg::with_name("pool_keypair")
-                    .long("pool-keypair")
-                    .short("p")
-                    .validator(is_keypair_or_ask_keyword)
-                    .value_name("PATH")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
-                    .help("Stake pool keypair [default: new keypair]"),
+                    .required(true)
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("validator_list_keypair")
-                    .long("validator-list-keypair")
-                    .validator(is_keypair_or_ask_keyword)
-                    .value_name("PATH")
+                Arg::with_name("max_seed")
+                    .long("max-seed")
+                    .validator(is_parsable::<u64>)
+                    .value_name("SEED")
                     .takes_value(true)
-                    .help("Validator list keypair [default: new keypair]"),
+                    .default_value("100")
+                    .help("Number of user stake account seeds to scan, starting from 0."),
             )
+        )
+        .subcommand(SubCommand::with_name("set-manager")
+            .about("Change manager or fee receiver account for the stake pool. Must be signed by the current manager.")
             .arg(
-                Arg::with_name("mint_keypair")
-                    .long("mint-keypair")
-                    .validator(is_keypair_or_ask_keyword)
-                    .value_name("PATH")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
-                    .help("Stake pool mint keypair [default: new keypair]"),
+                    .required(true)
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("reserve_keypair")
-                    .long("reserve-keypair")
-                    .validator(is_keypair_or_ask_keyword)
-                    .value_name("PATH")
+                Arg::with_name("new_manager")
+                    .long("new-manager")
+                    .validator(is_pubkey_or_signer)
+                    .value_name("KEYPAIR_OR_PUBKEY")
                     .takes_value(true)
-                    .help("Stake pool reserve keypair [default: new keypair]"),
+                    .help("Keypair or pubkey for the new stake pool manager. Use pubkey when transferring to a multisig vault."),
             )
             .arg(
-                Arg::with_name("unsafe_fees")
-                    .long("unsafe-fees")
-                    .takes_value(false)
-                    .help("Bypass fee checks, allowing pool to be created with unsafe fees"),
+                Arg::with_name("new_fee_receiver")
+                    .long("new-fee-receiver")
+                    .validator(is_pubkey)
+                    .value_name("ADDRESS")
+                    .takes_value(true)
+                    .help("Public key for the new account to set as the stake pool fee receiver."),
             )
-        )
-        .subcommand(SubCommand::with_name("create-token-metadata")
-        .about("Creates stake pool token metadata")
-        .arg(
-            Arg::with_name("pool")
-                .index(1)
-                .validator(is_pubkey)
-                .value_name("POOL_ADDRESS")
-                .takes_value(true)
-                .required(true)
-                .help("Stake pool address"),
-        )
-        .arg(
-            Arg::with_name("name")
-                .index(2)
-                .value_name("TOKEN_NAME")
-                .takes_value(true)
-                .required(true)
-                .help("Name of the token"),
-        )
-        .arg(
-            Arg::with_name("symbol")
-                .index(3)
-                .value_name("TOKEN_SYMBOL")
-                .takes_value(true)
-                .required(true)
-                .help("Symbol of the token"),
-        )
-        .arg(
-            Arg::with_name("uri")
-                .index(4)
-                .value_name("TOKEN_URI")
-                .takes_value(true)
+            .group(ArgGroup::with_name("new_accounts")
+                .arg("new_manager")
+                .arg("new_fee_receiver")
                 .required(true)
-                .help("URI of the token metadata json"),
-        )
-    )
-    .subcommand(SubCommand::with_name("update-token-metadata")
-    .about("Updates stake pool token metadata")
-    .arg(
-        Arg::with_name("pool")
-            .index(1)
-            .validator(is_pubkey)
-            .value_name("POOL_ADDRESS")
-            .takes_value(true)
-            .required(true)
-            .help("Stake pool address"),
-    )
-    .arg(
-        Arg::with_name("name")
-            .index(2)
-            .value_name("TOKEN_NAME")
-            .takes_value(true)
-            .required(true)
-            .help("Name of the token"),
-    )
-    .arg(
-        Arg::with_name("symbol")
-            .index(3)
-            .value_name("TOKEN_SYMBOL")
-            .takes_value(true)
-            .required(true)
-            .help("Symbol of the token"),
-    )
-    .arg(
-        Arg::with_name("uri")
-            .index(4)
-            .value_name("TOKEN_URI")
-            .takes_value(true)
-            .required(true)
-            .help("URI of the token metadata json"),
+                .multiple(true)
+            )
         )
-    )
-        .subcommand(SubCommand::with_name("add-validator")
-            .about("Add validator account to the stake pool. Must be signed by the pool staker.")
+        .subcommand(SubCommand::with_name("set-staker")
+            .about("Change staker account for the stake pool. Must be signed by the manager or current staker.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -2857,20 +5194,19 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("vote_account")
+                Arg::with_name("new_staker")
                     .index(2)
                     .validator(is_pubkey)
-                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .value_name("ADDRESS")
                     .takes_value(true)
-                    .required(true)
-                    .help("The validator vote account that the stake is delegated to"),
+                    .help("Public key for the new stake pool staker."),
             )
         )
-        .subcommand(SubCommand::with_name("remove-validator")
-            .about("Remove validator account from the stake pool. Must be signed by the pool staker.")
+        .subcommand(SubCommand::with_name("propose-manager")
+            .about("Propose a new manager for the stake pool, who must accept before taking over. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -2878,20 +5214,30 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("vote_account")
+                Arg::with_name("new_manager")
                     .index(2)
                     .validator(is_pubkey)
-                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .value_name("ADDRESS")
                     .takes_value(true)
-                    .required(true)
-                    .help("Vote account for the validator to remove from the pool"),
+                    .help("Public key for the proposed stake pool manager."),
+            )
+            .arg(
+                Arg::with_name("cancel")
+                    .long("cancel")
+                    .takes_value(false)
+                    .help("Cancel the pending manager proposal.")
+            )
+            .group(ArgGroup::with_name("new_manager_group")
+                .arg("new_manager")
+                .arg("cancel")
+                .required(true)
             )
         )
-        .subcommand(SubCommand::with_name("increase-validator-stake")
-            .about("Increase stake to a validator, drawing from the stake pool reserve. Must be signed by the pool staker.")
+        .subcommand(SubCommand::with_name("accept-manager")
+            .about("Accept a pending manager proposal. Must be signed by the proposed manager, given as --manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -2899,28 +5245,19 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("vote_account")
-                    .index(2)
+                Arg::with_name("new_fee_receiver")
+                    .long("new-fee-receiver")
                     .validator(is_pubkey)
-                    .value_name("VOTE_ACCOUNT_ADDRESS")
-                    .takes_value(true)
-                    .required(true)
-                    .help("Vote account for the validator to increase stake to"),
-            )
-            .arg(
-                Arg::with_name("amount")
-                    .index(3)
-                    .validator(is_amount)
-                    .value_name("AMOUNT")
+                    .value_name("ADDRESS")
                     .takes_value(true)
-                    .help("Amount in SOL to add to the validator stake account. Must be at least the rent-exempt amount for a stake plus 1 SOL for merging."),
+                    .help("Public key for the new account to set as the stake pool fee receiver. Defaults to the current fee receiver."),
             )
         )
-        .subcommand(SubCommand::with_name("decrease-validator-stake")
-            .about("Decrease stake to a validator, splitting from the active stake. Must be signed by the pool staker.")
+        .subcommand(SubCommand::with_name("propose-staker")
+            .about("Propose a new staker for the stake pool, who must accept before taking over. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -2928,28 +5265,42 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("vote_account")
+                Arg::with_name("new_staker")
                     .index(2)
                     .validator(is_pubkey)
-                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .value_name("ADDRESS")
                     .takes_value(true)
-                    .required(true)
-                    .help("Vote account for the validator to decrease stake from"),
+                    .help("Public key for the proposed stake pool staker."),
             )
             .arg(
-                Arg::with_name("amount")
-                    .index(3)
-                    .validator(is_amount)
-                    .value_name("AMOUNT")
+                Arg::with_name("cancel")
+                    .long("cancel")
+                    .takes_value(false)
+                    .help("Cancel the pending staker proposal.")
+            )
+            .group(ArgGroup::with_name("new_staker_group")
+                .arg("new_staker")
+                .arg("cancel")
+                .required(true)
+            )
+        )
+        .subcommand(SubCommand::with_name("accept-staker")
+            .about("Accept a pending staker proposal. Must be signed by the proposed staker, given as --staker.")
+            .arg(
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
-                    .help("Amount in SOL to remove from the validator stake account. Must be at least the rent-exempt amount for a stake."),
+                    .required(true)
+                    .help("Stake pool address."),
             )
         )
-        .subcommand(SubCommand::with_name("set-preferred-validator")
-            .about("Set the preferred validator for deposits or withdrawals. Must be signed by the pool staker.")
+        .subcommand(SubCommand::with_name("set-funding-authority")
+            .about("Change one of the funding authorities for the stake pool. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -2957,39 +5308,39 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("preferred_type")
+                Arg::with_name("funding_type")
                     .index(2)
-                    .value_name("OPERATION")
-                    .possible_values(&["deposit", "withdraw"]) // PreferredValidatorType enum
+                    .value_name("FUNDING_TYPE")
+                    .possible_values(&["stake-deposit", "sol-deposit", "sol-withdraw"]) // FundingType enum
                     .takes_value(true)
                     .required(true)
-                    .help("Operation for which to restrict the validator"),
+                    .help("Funding type to be updated."),
             )
             .arg(
-                Arg::with_name("vote_account")
-                    .long("vote-account")
+                Arg::with_name("new_authority")
+                    .index(3)
                     .validator(is_pubkey)
-                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .value_name("AUTHORITY_ADDRESS")
                     .takes_value(true)
-                    .help("Vote account for the validator that users must deposit into."),
+                    .help("Public key for the new stake pool funding authority."),
             )
             .arg(
                 Arg::with_name("unset")
                     .long("unset")
                     .takes_value(false)
-                    .help("Unset the preferred validator."),
+                    .help("Unset the stake deposit authority. The program will use a program derived address.")
             )
             .group(ArgGroup::with_name("validator")
-                .arg("vote_account")
+                .arg("new_authority")
                 .arg("unset")
                 .required(true)
             )
         )
-        .subcommand(SubCommand::with_name("deposit-stake")
-            .about("Deposit active stake account into the stake pool in exchange for pool tokens")
+        .subcommand(SubCommand::with_name("set-fee")
+            .about("Change the [epoch/withdraw/stake deposit/sol deposit] fee assessed by the stake pool. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -2997,46 +5348,37 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
-            )
-            .arg(
-                Arg::with_name("stake_account")
-                    .index(2)
-                    .validator(is_pubkey)
-                    .value_name("STAKE_ACCOUNT_ADDRESS")
-                    .takes_value(true)
-                    .required(true)
-                    .help("Stake address to join the pool"),
+                    .help("Stake pool address."),
             )
-            .arg(
-                Arg::with_name("withdraw_authority")
-                    .long("withdraw-authority")
-                    .validator(is_valid_signer)
-                    .value_name("KEYPAIR")
-                    .takes_value(true)
-                    .help("Withdraw authority for the stake account to be deposited. [default: cli config keypair]"),
+            .arg(Arg::with_name("fee_type")
+                .index(2)
+                .value_name("FEE_TYPE")
+                .possible_values(&["epoch", "stake-deposit", "sol-deposit", "stake-withdrawal", "sol-withdrawal"]) // FeeType enum
+                .takes_value(true)
+                .required(true)
+                .help("Fee type to be updated."),
             )
             .arg(
-                Arg::with_name("token_receiver")
-                    .long("token-receiver")
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
+                Arg::with_name("fee_numerator")
+                    .index(3)
+                    .validator(is_parsable::<u64>)
+                    .value_name("NUMERATOR")
                     .takes_value(true)
-                    .help("Account to receive the minted pool tokens. \
-                          Defaults to the token-owner's associated pool token account. \
-                          Creates the account if it does not exist."),
+                    .required(true)
+                    .help("Fee numerator, fee amount is numerator divided by denominator."),
             )
             .arg(
-                Arg::with_name("referrer")
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
+                Arg::with_name("fee_denominator")
+                    .index(4)
+                    .validator(is_parsable::<u64>)
+                    .value_name("DENOMINATOR")
                     .takes_value(true)
-                    .help("Pool token account to receive the referral fees for deposits. \
-                          Defaults to the token receiver."),
+                    .required(true)
+                    .help("Fee denominator, fee amount is numerator divided by denominator."),
             )
         )
-        .subcommand(SubCommand::with_name("deposit-all-stake")
-            .about("Deposit all active stake accounts into the stake pool in exchange for pool tokens")
+        .subcommand(SubCommand::with_name("set-sol-withdrawal-fee-curve")
+            .about("Change the curve raising the SOL withdrawal fee as the reserve drains below its target share of the pool. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3044,46 +5386,54 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("stake_authority")
+                Arg::with_name("target_reserve_bps")
                     .index(2)
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
+                    .validator(is_parsable::<u16>)
+                    .value_name("BASIS_POINTS")
                     .takes_value(true)
-                    .required(true)
-                    .help("Stake authority address to search for stake accounts"),
+                    .required_unless("unset")
+                    .help("Share of the pool in basis points, up to 10000, the reserve should keep for SOL withdrawals."),
             )
             .arg(
-                Arg::with_name("withdraw_authority")
-                    .long("withdraw-authority")
-                    .validator(is_valid_signer)
-                    .value_name("KEYPAIR")
+                Arg::with_name("max_fee_numerator")
+                    .index(3)
+                    .validator(is_parsable::<u64>)
+                    .value_name("NUMERATOR")
                     .takes_value(true)
-                    .help("Withdraw authority for the stake account to be deposited. [default: cli config keypair]"),
+                    .required_unless("unset")
+                    .help("Numerator of the fee charged when a withdrawal empties the reserve."),
             )
             .arg(
-                Arg::with_name("token_receiver")
-                    .long("token-receiver")
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
+                Arg::with_name("max_fee_denominator")
+                    .index(4)
+                    .validator(is_parsable::<u64>)
+                    .value_name("DENOMINATOR")
                     .takes_value(true)
-                    .help("Account to receive the minted pool tokens. \
-                          Defaults to the token-owner's associated pool token account. \
-                          Creates the account if it does not exist."),
+                    .required_unless("unset")
+                    .help("Denominator of the fee charged when a withdrawal empties the reserve."),
             )
             .arg(
-                Arg::with_name("referrer")
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
+                Arg::with_name("exponent")
+                    .index(5)
+                    .validator(is_parsable::<u8>)
+                    .value_name("EXPONENT")
                     .takes_value(true)
-                    .help("Pool token account to receive the referral fees for deposits. \
-                          Defaults to the token receiver."),
+                    .required_unless("unset")
+                    .help("Exponent of the curve, from 1 for a linear rise up to 4."),
+            )
+            .arg(
+                Arg::with_name("unset")
+                    .long("unset")
+                    .takes_value(false)
+                    .conflicts_with("target_reserve_bps")
+                    .help("Turn the curve off, charging only the flat SOL withdrawal fee.")
             )
         )
-        .subcommand(SubCommand::with_name("deposit-sol")
-            .about("Deposit SOL into the stake pool in exchange for pool tokens")
+        .subcommand(SubCommand::with_name("set-max-validator-share")
+            .about("Change the largest share of the pool a validator's stake may reach through stake increases and deposits, for every validator or, with --vote-account, a single one. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3091,45 +5441,38 @@ fn main() {
                     .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Stake pool address"),
-            ).arg(
-                Arg::with_name("amount")
-                    .index(2)
-                    .validator(is_amount)
-                    .value_name("AMOUNT")
-                    .takes_value(true)
-                    .help("Amount in SOL to deposit into the stake pool reserve account."),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("from")
-                    .long("from")
-                    .validator(is_valid_signer)
-                    .value_name("KEYPAIR")
+                Arg::with_name("max_share_bps")
+                    .index(2)
+                    .validator(is_parsable::<u16>)
+                    .value_name("BASIS_POINTS")
                     .takes_value(true)
-                    .help("Source account of funds. [default: cli config keypair]"),
+                    .help("Maximum share of the pool in basis points, up to 10000."),
             )
             .arg(
-                Arg::with_name("token_receiver")
-                    .long("token-receiver")
+                Arg::with_name("vote_account")
+                    .long("vote-account")
                     .validator(is_pubkey)
-                    .value_name("POOL_TOKEN_RECEIVER_ADDRESS")
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
                     .takes_value(true)
-                    .help("Account to receive the minted pool tokens. \
-                          Defaults to the token-owner's associated pool token account. \
-                          Creates the account if it does not exist."),
+                    .help("Set the limit of this validator only. The tighter of its own and the pool-wide limit applies."),
             )
             .arg(
-                Arg::with_name("referrer")
-                    .long("referrer")
-                    .validator(is_pubkey)
-                    .value_name("REFERRER_TOKEN_ADDRESS")
-                    .takes_value(true)
-                    .help("Account to receive the referral fees for deposits. \
-                          Defaults to the token receiver."),
+                Arg::with_name("unset")
+                    .long("unset")
+                    .takes_value(false)
+                    .help("Remove the limit.")
+            )
+            .group(ArgGroup::with_name("max_share_group")
+                .arg("max_share_bps")
+                .arg("unset")
+                .required(true)
             )
         )
-        .subcommand(SubCommand::with_name("list")
-            .about("List stake accounts managed by this pool")
+        .subcommand(SubCommand::with_name("set-max-validator-commission")
+            .about("Change the highest commission a validator may charge before anyone can start deactivating it, for every validator or, with --vote-account, a single one. Must be signed by the manager, or by the staker with --vote-account.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3140,16 +5483,35 @@ fn main() {
                     .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("sort")
-                    .long("sort")
-                    .value_name("SORT_OPTION")
+                Arg::with_name("max_commission")
+                    .index(2)
+                    .validator(is_valid_percentage)
+                    .value_name("PERCENTAGE")
                     .takes_value(true)
-                    .possible_values(&["stake", "update-epoch"])
-                    .help("Sort validators by: stake (largest first) or update-epoch (most recent first)"),
+                    .help("Maximum commission, as a percentage."),
+            )
+            .arg(
+                Arg::with_name("vote_account")
+                    .long("vote-account")
+                    .validator(is_pubkey)
+                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .help("Set the limit of this validator only, kept in its metadata. The tighter of its own and the pool-wide limit applies."),
+            )
+            .arg(
+                Arg::with_name("unset")
+                    .long("unset")
+                    .takes_value(false)
+                    .help("Remove the limit.")
+            )
+            .group(ArgGroup::with_name("max_commission_group")
+                .arg("max_commission")
+                .arg("unset")
+                .required(true)
             )
         )
-        .subcommand(SubCommand::with_name("update")
-            .about("Updates all balances in the pool after validator stake accounts receive rewards.")
+        .subcommand(SubCommand::with_name("set-max-validator-vote-age")
+            .about("Change the number of epochs a validator may go without earning vote credits before anyone can start deactivating it. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3160,26 +5522,27 @@ fn main() {
                     .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("force")
-                    .long("force")
-                    .takes_value(false)
-                    .help("Update balances, even if it has already been performed this epoch."),
+                Arg::with_name("max_vote_age_epochs")
+                    .index(2)
+                    .validator(is_parsable::<u64>)
+                    .value_name("EPOCHS")
+                    .takes_value(true)
+                    .help("Maximum number of epochs without vote credits, at least 1."),
             )
             .arg(
-                Arg::with_name("no_merge")
-                    .long("no-merge")
+                Arg::with_name("unset")
+                    .long("unset")
                     .takes_value(false)
-                    .help("Do not automatically merge transient stakes. Useful if the stake pool is in an expected state, but the balances still need to be updated."),
+                    .help("Leave delinquent validators to the staker.")
             )
-            .arg(
-                Arg::with_name("stale_only")
-                    .long("stale-only")
-                    .takes_value(false)
-                    .help("If set, only updates validator list balances that have not been updated for this epoch. Otherwise, updates all validator balances on the validator list."),
+            .group(ArgGroup::with_name("max_vote_age_group")
+                .arg("max_vote_age_epochs")
+                .arg("unset")
+                .required(true)
             )
         )
-        .subcommand(SubCommand::with_name("withdraw-stake")
-            .about("Withdraw active stake from the stake pool in exchange for pool tokens")
+        .subcommand(SubCommand::with_name("set-pauser")
+            .about("Change the pauser authority of the stake pool. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3190,52 +5553,53 @@ fn main() {
                     .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("amount")
+                Arg::with_name("new_pauser")
                     .index(2)
-                    .validator(is_amount)
-                    .value_name("AMOUNT")
-                    .takes_value(true)
-                    .required(true)
-                    .help("Amount of pool tokens to withdraw for activated stake."),
-            )
-            .arg(
-                Arg::with_name("pool_account")
-                    .long("pool-account")
                     .validator(is_pubkey)
                     .value_name("ADDRESS")
                     .takes_value(true)
-                    .help("Pool token account to withdraw tokens from. Defaults to the token-owner's associated token account."),
+                    .help("Public key for the new stake pool pauser."),
             )
             .arg(
-                Arg::with_name("stake_receiver")
-                    .long("stake-receiver")
-                    .validator(is_pubkey)
-                    .value_name("STAKE_ACCOUNT_ADDRESS")
-                    .takes_value(true)
-                    .requires("withdraw_from")
-                    .help("Stake account from which to receive a stake from the stake pool. Defaults to a new stake account."),
+                Arg::with_name("unset")
+                    .long("unset")
+                    .takes_value(false)
+                    .help("Unset the pauser. Only the manager will be able to pause the pool.")
+            )
+            .group(ArgGroup::with_name("pauser_group")
+                .arg("new_pauser")
+                .arg("unset")
+                .required(true)
             )
+        )
+        .subcommand(SubCommand::with_name("pause")
+            .about("Pause operations of the stake pool. Must be signed by the manager or pauser.")
             .arg(
-                Arg::with_name("vote_account")
-                    .long("vote-account")
+                Arg::with_name("pool")
+                    .index(1)
                     .validator(is_pubkey)
-                    .value_name("VOTE_ACCOUNT_ADDRESS")
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
-                    .help("Validator to withdraw from. Defaults to the largest validator stakes in the pool."),
+                    .required(true)
+                    .help("Stake pool address."),
             )
+            .arg(paused_operations_arg())
+        )
+        .subcommand(SubCommand::with_name("unpause")
+            .about("Unpause operations of the stake pool. Must be signed by the manager or pauser.")
             .arg(
-                Arg::with_name("use_reserve")
-                    .long("use-reserve")
-                    .takes_value(false)
-                    .help("Withdraw from the stake pool's reserve. Only possible if all validator stakes are at the minimum possible amount."),
-            )
-            .group(ArgGroup::with_name("withdraw_from")
-                .arg("use_reserve")
-                .arg("vote_account")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Stake pool address."),
             )
+            .arg(paused_operations_arg())
         )
-        .subcommand(SubCommand::with_name("withdraw-sol")
-            .about("Withdraw SOL from the stake pool's reserve in exchange for pool tokens")
+        .subcommand(SubCommand::with_name("set-referral-fee")
+            .about("Change the referral fee assessed by the stake pool for stake deposits. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3245,35 +5609,47 @@ fn main() {
                     .required(true)
                     .help("Stake pool address."),
             )
+            .arg(Arg::with_name("fee_type")
+                .index(2)
+                .value_name("FEE_TYPE")
+                .possible_values(&["stake", "sol"]) // FeeType enum, kind of
+                .takes_value(true)
+                .required(true)
+                .help("Fee type to be updated."),
+            )
             .arg(
-                Arg::with_name("sol_receiver")
-                    .index(2)
-                    .validator(is_valid_pubkey)
-                    .value_name("SYSTEM_ACCOUNT_ADDRESS_OR_KEYPAIR")
+                Arg::with_name("fee")
+                    .index(3)
+                    .validator(is_valid_percentage)
+                    .value_name("FEE_PERCENTAGE")
                     .takes_value(true)
                     .required(true)
-                    .help("System account to receive SOL from the stake pool. Defaults to the payer."),
+                    .help("Fee percentage, maximum 100"),
             )
+        )
+        .subcommand(SubCommand::with_name("set-fee-change-delay")
+            .about("Change the number of epochs that fee changes are held back before taking effect. Must be signed by the manager.")
             .arg(
-                Arg::with_name("amount")
-                    .index(3)
-                    .validator(is_amount)
-                    .value_name("AMOUNT")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Amount of pool tokens to withdraw for SOL."),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("pool_account")
-                    .long("pool-account")
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
+                Arg::with_name("delay_epochs")
+                    .index(2)
+                    .validator(is_parsable::<u64>)
+                    .value_name("EPOCHS")
                     .takes_value(true)
-                    .help("Pool token account to withdraw tokens from. Defaults to the token-owner's associated token account."),
+                    .required(true)
+                    .help("Fee change delay in epochs. A shorter delay only takes effect after the current delay."),
             )
         )
-        .subcommand(SubCommand::with_name("set-manager")
-            .about("Change manager or fee receiver account for the stake pool. Must be signed by the current manager.")
+        .subcommand(SubCommand::with_name("set-fee-split")
+            .about("Split the manager's share of every fee between weighted recipient token accounts. The manager fee account keeps what the weights leave over. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3284,30 +5660,29 @@ fn main() {
                     .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("new_manager")
-                    .long("new-manager")
-                    .validator(is_pubkey_or_signer)
-                    .value_name("KEYPAIR_OR_PUBKEY")
+                Arg::with_name("recipient")
+                    .long("recipient")
+                    .validator(is_fee_split_recipient)
+                    .value_name("TOKEN_ACCOUNT:BASIS_POINTS")
                     .takes_value(true)
-                    .help("Keypair or pubkey for the new stake pool manager. Use pubkey when transferring to a multisig vault."),
+                    .multiple(true)
+                    .number_of_values(1)
+                    .help("Pool token account receiving the given share of fees, in basis points. May be repeated, up to 8 recipients, with weights summing to at most 10000."),
             )
             .arg(
-                Arg::with_name("new_fee_receiver")
-                    .long("new-fee-receiver")
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
-                    .takes_value(true)
-                    .help("Public key for the new account to set as the stake pool fee receiver."),
+                Arg::with_name("unset")
+                    .long("unset")
+                    .takes_value(false)
+                    .help("Remove every recipient, paying all fees to the manager fee account.")
             )
-            .group(ArgGroup::with_name("new_accounts")
-                .arg("new_manager")
-                .arg("new_fee_receiver")
+            .group(ArgGroup::with_name("recipients_group")
+                .arg("recipient")
+                .arg("unset")
                 .required(true)
-                .multiple(true)
             )
         )
-        .subcommand(SubCommand::with_name("set-staker")
-            .about("Change staker account for the stake pool. Must be signed by the manager or current staker.")
+        .subcommand(SubCommand::with_name("set-insurance-fee")
+            .about("Set the share of the epoch fee paid into the pool's insurance fund, creating the fund if needed. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3318,16 +5693,17 @@ fn main() {
                     .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("new_staker")
+                Arg::with_name("insurance_fee_bps")
                     .index(2)
-                    .validator(is_pubkey)
-                    .value_name("ADDRESS")
+                    .validator(is_parsable::<u16>)
+                    .value_name("BASIS_POINTS")
                     .takes_value(true)
-                    .help("Public key for the new stake pool staker."),
+                    .required(true)
+                    .help("Share of the epoch fee diverted to the insurance fund, in basis points, maximum 10000. 0 stops funding."),
             )
         )
-        .subcommand(SubCommand::with_name("set-funding-authority")
-            .about("Change one of the funding authorities for the stake pool. Must be signed by the manager.")
+        .subcommand(SubCommand::with_name("inject-insurance-fund")
+            .about("Move lamports from the insurance fund into the pool reserve to cover recorded validator stake losses. Must be signed by the manager or staker.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3338,36 +5714,28 @@ fn main() {
                     .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("funding_type")
+                Arg::with_name("amount")
                     .index(2)
-                    .value_name("FUNDING_TYPE")
-                    .possible_values(&["stake-deposit", "sol-deposit", "sol-withdraw"]) // FundingType enum
+                    .validator(is_amount)
+                    .value_name("AMOUNT")
                     .takes_value(true)
-                    .required(true)
-                    .help("Funding type to be updated."),
+                    .help("Amount in SOL to inject. [default: the uncovered loss, capped at the fund balance]"),
             )
+        )
+        .subcommand(SubCommand::with_name("insurance-fund")
+            .about("Show the insurance fund balance and the pool's uncovered stake losses")
             .arg(
-                Arg::with_name("new_authority")
-                    .index(3)
+                Arg::with_name("pool")
+                    .index(1)
                     .validator(is_pubkey)
-                    .value_name("AUTHORITY_ADDRESS")
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
-                    .help("Public key for the new stake pool funding authority."),
-            )
-            .arg(
-                Arg::with_name("unset")
-                    .long("unset")
-                    .takes_value(false)
-                    .help("Unset the stake deposit authority. The program will use a program derived address.")
-            )
-            .group(ArgGroup::with_name("validator")
-                .arg("new_authority")
-                .arg("unset")
-                .required(true)
+                    .required(true)
+                    .help("Stake pool address."),
             )
         )
-        .subcommand(SubCommand::with_name("set-fee")
-            .about("Change the [epoch/withdraw/stake deposit/sol deposit] fee assessed by the stake pool. Must be signed by the manager.")
+        .subcommand(SubCommand::with_name("create-rate-history")
+            .about("Start recording the pool's exchange rate on every balance update, keeping the last 250 epochs. Must be signed by the manager.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3377,35 +5745,30 @@ fn main() {
                     .required(true)
                     .help("Stake pool address."),
             )
-            .arg(Arg::with_name("fee_type")
-                .index(2)
-                .value_name("FEE_TYPE")
-                .possible_values(&["epoch", "stake-deposit", "sol-deposit", "stake-withdrawal", "sol-withdrawal"]) // FeeType enum
-                .takes_value(true)
-                .required(true)
-                .help("Fee type to be updated."),
-            )
+        )
+        .subcommand(SubCommand::with_name("history")
+            .about("Show the pool's recorded exchange rates and trailing APY")
             .arg(
-                Arg::with_name("fee_numerator")
-                    .index(3)
-                    .validator(is_parsable::<u64>)
-                    .value_name("NUMERATOR")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Fee numerator, fee amount is numerator divided by denominator."),
+                    .help("Stake pool address."),
             )
             .arg(
-                Arg::with_name("fee_denominator")
-                    .index(4)
+                Arg::with_name("epochs")
+                    .long("epochs")
                     .validator(is_parsable::<u64>)
-                    .value_name("DENOMINATOR")
+                    .value_name("EPOCHS")
                     .takes_value(true)
-                    .required(true)
-                    .help("Fee denominator, fee amount is numerator divided by denominator."),
+                    .default_value("10")
+                    .help("Number of epochs to show and to compute the trailing APY over."),
             )
         )
-        .subcommand(SubCommand::with_name("set-referral-fee")
-            .about("Change the referral fee assessed by the stake pool for stake deposits. Must be signed by the manager.")
+        .subcommand(SubCommand::with_name("migrate-pool")
+            .about("Grow a stake pool account created by an earlier version of the program to the current layout, which every other instruction requires. Anyone can migrate a pool, the fee payer covering the extra rent.")
             .arg(
                 Arg::with_name("pool")
                     .index(1)
@@ -3415,22 +5778,17 @@ fn main() {
                     .required(true)
                     .help("Stake pool address."),
             )
-            .arg(Arg::with_name("fee_type")
-                .index(2)
-                .value_name("FEE_TYPE")
-                .possible_values(&["stake", "sol"]) // FeeType enum, kind of
-                .takes_value(true)
-                .required(true)
-                .help("Fee type to be updated."),
-            )
+        )
+        .subcommand(SubCommand::with_name("create-oracle")
+            .about("Create the price oracle account of the pool token, rewritten with the exchange rate on every balance update. Must be signed by the manager.")
             .arg(
-                Arg::with_name("fee")
-                    .index(3)
-                    .validator(is_valid_percentage)
-                    .value_name("FEE_PERCENTAGE")
+                Arg::with_name("pool")
+                    .index(1)
+                    .validator(is_pubkey)
+                    .value_name("POOL_ADDRESS")
                     .takes_value(true)
                     .required(true)
-                    .help("Fee percentage, maximum 100"),
+                    .help("Stake pool address."),
             )
         )
         .subcommand(SubCommand::with_name("list-all")
@@ -3471,6 +5829,19 @@ fn main() {
         } else {
             None
         };
+        let pauser = if matches.is_present("pauser") {
+            Some(get_signer(
+                &matches,
+                "pauser",
+                &cli_config.keypair_path,
+                &mut wallet_manager,
+                SignerFromPathConfig {
+                    allow_null_signer: false,
+                },
+            ))
+        } else {
+            None
+        };
         let manager = get_signer(
             &matches,
             "manager",
@@ -3540,6 +5911,7 @@ fn main() {
             manager,
             staker,
             funding_authority,
+            pauser,
             token_owner,
             fee_payer,
             dry_run,
@@ -3596,14 +5968,25 @@ fn main() {
             let name = value_t_or_exit!(arg_matches, "name", String);
             let symbol = value_t_or_exit!(arg_matches, "symbol", String);
             let uri = value_t_or_exit!(arg_matches, "uri", String);
-            create_token_metadata(&config, &stake_pool_address, name, symbol, uri)
+            let fields = token_metadata_fields_of(arg_matches);
+            create_token_metadata(&config, &stake_pool_address, name, symbol, uri, fields)
         }
         ("update-token-metadata", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
             let name = value_t_or_exit!(arg_matches, "name", String);
             let symbol = value_t_or_exit!(arg_matches, "symbol", String);
             let uri = value_t_or_exit!(arg_matches, "uri", String);
-            update_token_metadata(&config, &stake_pool_address, name, symbol, uri)
+            let fields = token_metadata_fields_of(arg_matches);
+            let removed_fields = values_t!(arg_matches, "remove_field", String).unwrap_or_default();
+            update_token_metadata(
+                &config,
+                &stake_pool_address,
+                name,
+                symbol,
+                uri,
+                fields,
+                removed_fields,
+            )
         }
         ("add-validator", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
@@ -3615,6 +5998,20 @@ fn main() {
             let vote_account = pubkey_of(arg_matches, "vote_account").unwrap();
             command_vsa_remove(&config, &stake_pool_address, &vote_account)
         }
+        ("deactivate-high-commission-validator", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let vote_account = pubkey_of(arg_matches, "vote_account").unwrap();
+            command_deactivate_high_commission_validator(
+                &config,
+                &stake_pool_address,
+                &vote_account,
+            )
+        }
+        ("deactivate-delinquent-validator", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let vote_account = pubkey_of(arg_matches, "vote_account").unwrap();
+            command_deactivate_delinquent_validator(&config, &stake_pool_address, &vote_account)
+        }
         ("increase-validator-stake", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
             let vote_account = pubkey_of(arg_matches, "vote_account").unwrap();
@@ -3629,6 +6026,41 @@ fn main() {
             let lamports = native_token::sol_str_to_lamports(amount_str).unwrap();
             command_decrease_validator_stake(&config, &stake_pool_address, &vote_account, lamports)
         }
+        ("redelegate", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let source_vote_account = pubkey_of(arg_matches, "source_vote_account").unwrap();
+            let destination_vote_account =
+                pubkey_of(arg_matches, "destination_vote_account").unwrap();
+            let amount_str = arg_matches.value_of("amount").unwrap();
+            let lamports = native_token::sol_str_to_lamports(amount_str).unwrap();
+            command_redelegate(
+                &config,
+                &stake_pool_address,
+                &source_vote_account,
+                &destination_vote_account,
+                lamports,
+            )
+        }
+        ("rebalance", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let retained_reserve_lamports = native_token::sol_str_to_lamports(
+                arg_matches.value_of("retained_reserve").unwrap(),
+            )
+            .unwrap();
+            let max_commission = value_t_or_exit!(arg_matches, "max_commission", u8);
+            let tolerance_lamports =
+                native_token::sol_str_to_lamports(arg_matches.value_of("tolerance").unwrap())
+                    .unwrap();
+            let dry_run = arg_matches.is_present("dry_run");
+            command_rebalance(
+                &config,
+                &stake_pool_address,
+                retained_reserve_lamports,
+                max_commission,
+                tolerance_lamports,
+                dry_run,
+            )
+        }
         ("set-preferred-validator", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
             let preferred_type = match arg_matches.value_of("preferred_type").unwrap() {
@@ -3674,6 +6106,8 @@ fn main() {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
             let token_receiver: Option<Pubkey> = pubkey_of(arg_matches, "token_receiver");
             let referrer: Option<Pubkey> = pubkey_of(arg_matches, "referrer");
+            let referral_code = arg_matches.value_of("referral_code");
+            let vote_account = pubkey_of(arg_matches, "vote_account");
             let from = keypair_of(arg_matches, "from");
             let amount_str = arg_matches.value_of("amount").unwrap();
             let lamports = native_token::sol_str_to_lamports(amount_str).unwrap();
@@ -3683,9 +6117,30 @@ fn main() {
                 &from,
                 &token_receiver,
                 &referrer,
+                referral_code,
+                vote_account,
                 lamports,
             )
         }
+        ("register-referrer", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let code = arg_matches.value_of("code").unwrap();
+            let payout_account = pubkey_of(arg_matches, "payout_account");
+            command_register_referrer(&config, &stake_pool_address, code, payout_account)
+        }
+        ("update-referrer", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let code = arg_matches.value_of("code").unwrap();
+            let payout_account = pubkey_of(arg_matches, "payout_account").unwrap();
+            let new_authority = pubkey_of(arg_matches, "new_authority");
+            command_update_referrer(
+                &config,
+                &stake_pool_address,
+                code,
+                &payout_account,
+                new_authority,
+            )
+        }
         ("list", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
             let sort_by = arg_matches.value_of("sort");
@@ -3737,6 +6192,11 @@ fn main() {
                 pool_amount,
             )
         }
+        ("close-user-stake-accounts", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let max_seed = value_t_or_exit!(arg_matches, "max_seed", u64);
+            command_close_user_stake_accounts(&config, &stake_pool_address, max_seed)
+        }
         ("set-manager", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
 
@@ -3770,6 +6230,25 @@ fn main() {
             let new_staker = pubkey_of(arg_matches, "new_staker").unwrap();
             command_set_staker(&config, &stake_pool_address, &new_staker)
         }
+        ("propose-manager", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let new_manager = pubkey_of(arg_matches, "new_manager");
+            command_propose_manager(&config, &stake_pool_address, new_manager)
+        }
+        ("accept-manager", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let new_fee_receiver: Option<Pubkey> = pubkey_of(arg_matches, "new_fee_receiver");
+            command_accept_manager(&config, &stake_pool_address, &new_fee_receiver)
+        }
+        ("propose-staker", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let new_staker = pubkey_of(arg_matches, "new_staker");
+            command_propose_staker(&config, &stake_pool_address, new_staker)
+        }
+        ("accept-staker", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            command_accept_staker(&config, &stake_pool_address)
+        }
         ("set-funding-authority", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
             let new_authority = pubkey_of(arg_matches, "new_authority");
@@ -3811,6 +6290,26 @@ fn main() {
                 _ => unreachable!(),
             }
         }
+        ("set-sol-withdrawal-fee-curve", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let curve = if arg_matches.is_present("unset") {
+                SolWithdrawalFeeCurve::default()
+            } else {
+                SolWithdrawalFeeCurve {
+                    target_reserve_bps: value_t_or_exit!(arg_matches, "target_reserve_bps", u16),
+                    max_fee: Fee {
+                        numerator: value_t_or_exit!(arg_matches, "max_fee_numerator", u64),
+                        denominator: value_t_or_exit!(arg_matches, "max_fee_denominator", u64),
+                    },
+                    exponent: value_t_or_exit!(arg_matches, "exponent", u8),
+                }
+            };
+            command_set_fee(
+                &config,
+                &stake_pool_address,
+                FeeType::SolWithdrawalCurve(curve),
+            )
+        }
         ("set-referral-fee", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
             let fee = value_t_or_exit!(arg_matches, "fee", u8);
@@ -3826,6 +6325,91 @@ fn main() {
             };
             command_set_fee(&config, &stake_pool_address, fee_type)
         }
+        ("set-fee-change-delay", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let delay_epochs = value_t_or_exit!(arg_matches, "delay_epochs", u64);
+            command_set_fee_change_delay(&config, &stake_pool_address, delay_epochs)
+        }
+        ("set-fee-split", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let recipients = fee_split_recipients_of(arg_matches);
+            command_set_fee_split(&config, &stake_pool_address, &recipients)
+        }
+        ("set-insurance-fee", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let insurance_fee_bps = value_t_or_exit!(arg_matches, "insurance_fee_bps", u16);
+            command_set_insurance_fee(&config, &stake_pool_address, insurance_fee_bps)
+        }
+        ("inject-insurance-fund", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let lamports = arg_matches
+                .value_of("amount")
+                .map(|amount_str| native_token::sol_str_to_lamports(amount_str).unwrap());
+            command_inject_insurance_fund(&config, &stake_pool_address, lamports)
+        }
+        ("insurance-fund", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            command_insurance_fund(&config, &stake_pool_address)
+        }
+        ("create-rate-history", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            command_create_rate_history(&config, &stake_pool_address)
+        }
+        ("history", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let epochs = value_t_or_exit!(arg_matches, "epochs", u64);
+            command_history(&config, &stake_pool_address, epochs)
+        }
+        ("migrate-pool", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            command_migrate_pool(&config, &stake_pool_address)
+        }
+        ("create-oracle", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            command_create_oracle(&config, &stake_pool_address)
+        }
+        ("set-max-validator-share", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let vote_account = pubkey_of(arg_matches, "vote_account");
+            let max_share_bps = value_t!(arg_matches, "max_share_bps", u16).ok();
+            command_set_max_validator_share(
+                &config,
+                &stake_pool_address,
+                vote_account,
+                max_share_bps,
+            )
+        }
+        ("set-max-validator-commission", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let vote_account = pubkey_of(arg_matches, "vote_account");
+            let max_commission = value_t!(arg_matches, "max_commission", u8).ok();
+            command_set_max_validator_commission(
+                &config,
+                &stake_pool_address,
+                vote_account,
+                max_commission,
+            )
+        }
+        ("set-max-validator-vote-age", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let max_vote_age_epochs = value_t!(arg_matches, "max_vote_age_epochs", u64).ok();
+            command_set_max_validator_vote_age(&config, &stake_pool_address, max_vote_age_epochs)
+        }
+        ("set-pauser", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let new_pauser = pubkey_of(arg_matches, "new_pauser");
+            command_set_pauser(&config, &stake_pool_address, new_pauser)
+        }
+        ("pause", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let operations = paused_operations_of(arg_matches);
+            command_set_paused(&config, &stake_pool_address, operations, true)
+        }
+        ("unpause", Some(arg_matches)) => {
+            let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
+            let operations = paused_operations_of(arg_matches);
+            command_set_paused(&config, &stake_pool_address, operations, false)
+        }
         ("list-all", _) => command_list_all_pools(&config),
         ("deposit-all-stake", Some(arg_matches)) => {
             let stake_pool_address = pubkey_of(arg_matches, "pool").unwrap();
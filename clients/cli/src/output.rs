@@ -1,9 +1,11 @@
 use {
+    crate::rebalance::{PlannedValidator, StakeChange},
     serde::{Deserialize, Serialize},
     solana_cli_output::{QuietDisplay, VerboseDisplay},
     solana_sdk::{native_token::Sol, pubkey::Pubkey, stake::state::Lockup},
     spl_stake_pool::state::{
-        Fee, PodStakeStatus, StakePool, StakeStatus, ValidatorList, ValidatorStakeInfo,
+        Fee, PodStakeStatus, SolWithdrawalFeeCurve, StakePool, StakeStatus, ValidatorList,
+        ValidatorStakeInfo,
     },
     std::fmt::{Display, Formatter, Result, Write},
 };
@@ -71,6 +73,37 @@ pub(crate) struct CliStakePool {
     pub next_sol_withdrawal_fee: Option<CliStakePoolFee>,
     pub last_epoch_pool_token_supply: u64,
     pub last_epoch_total_lamports: u64,
+    pub pending_withdrawal_lamports: u64,
+    pub withdrawal_claim_pool_lamports: u64,
+    pub max_deposit_lamports_per_epoch: Option<u64>,
+    pub max_sol_withdrawal_lamports_per_epoch: Option<u64>,
+    pub max_total_lamports: Option<u64>,
+    pub flow_epoch: u64,
+    pub epoch_deposited_lamports: u64,
+    pub epoch_sol_withdrawn_lamports: u64,
+    pub pauser: Option<String>,
+    pub paused_operations: u8,
+    pub pending_manager: Option<String>,
+    pub pending_staker: Option<String>,
+    pub next_stake_deposit_fee: Option<CliStakePoolFee>,
+    pub next_sol_deposit_fee: Option<CliStakePoolFee>,
+    pub next_stake_referral_fee: Option<u8>,
+    pub next_sol_referral_fee: Option<u8>,
+    pub fee_change_delay_epochs: u64,
+    pub next_fee_change_delay_epochs: Option<u64>,
+    pub fee_change_epoch: u64,
+    pub max_direct_stake_bps: Option<u16>,
+    pub max_validator_share_bps: Option<u16>,
+    pub max_validator_commission: Option<u8>,
+    pub max_validator_vote_age_epochs: Option<u64>,
+    pub sol_withdrawal_fee_curve: CliSolWithdrawalFeeCurve,
+    pub next_sol_withdrawal_fee_curve: Option<CliSolWithdrawalFeeCurve>,
+    pub has_fee_split: bool,
+    pub insurance_fee_bps: u16,
+    pub uncovered_loss_lamports: u64,
+    pub has_rate_history: bool,
+    pub has_oracle: bool,
+    pub pending_insurance_lamports: u64,
     pub details: Option<CliStakePoolDetails>,
 }
 
@@ -107,6 +140,36 @@ impl VerboseDisplay for CliStakePool {
         writeln!(w, "Withdraw Authority: {}", &self.pool_withdraw_authority)?;
         writeln!(w, "Pool Token Mint: {}", &self.pool_mint)?;
         writeln!(w, "Fee Account: {}", &self.manager_fee_account)?;
+        if self.has_fee_split {
+            writeln!(w, "Fee Split: Enabled")?;
+        }
+        if self.insurance_fee_bps > 0 {
+            writeln!(
+                w,
+                "Insurance Fee: {}% of epoch fee",
+                f64::from(self.insurance_fee_bps) / 100.0
+            )?;
+        }
+        if self.pending_insurance_lamports > 0 {
+            writeln!(
+                w,
+                "Pending Insurance Deposit: {}",
+                Sol(self.pending_insurance_lamports)
+            )?;
+        }
+        if self.uncovered_loss_lamports > 0 {
+            writeln!(
+                w,
+                "Uncovered Stake Loss: {}",
+                Sol(self.uncovered_loss_lamports)
+            )?;
+        }
+        if self.has_rate_history {
+            writeln!(w, "Rate History: Enabled")?;
+        }
+        if self.has_oracle {
+            writeln!(w, "Price Oracle: Enabled")?;
+        }
         match &self.preferred_deposit_validator_vote_address {
             None => {}
             Some(s) => {
@@ -147,26 +210,103 @@ impl VerboseDisplay for CliStakePool {
                 next_sol_withdrawal_fee
             )?;
         }
+        writeln!(
+            w,
+            "SOL Withdrawal Fee Curve: {}",
+            &self.sol_withdrawal_fee_curve
+        )?;
+        if let Some(next_sol_withdrawal_fee_curve) = &self.next_sol_withdrawal_fee_curve {
+            writeln!(
+                w,
+                "Next SOL Withdrawal Fee Curve: {}",
+                next_sol_withdrawal_fee_curve
+            )?;
+        }
         writeln!(
             w,
             "Stake Deposit Fee: {} of deposit amount",
             &self.stake_deposit_fee
         )?;
+        if let Some(next_stake_deposit_fee) = &self.next_stake_deposit_fee {
+            writeln!(
+                w,
+                "Next Stake Deposit Fee: {} of deposit amount",
+                next_stake_deposit_fee
+            )?;
+        }
         writeln!(
             w,
             "SOL Deposit Fee: {} of deposit amount",
             &self.sol_deposit_fee
         )?;
+        if let Some(next_sol_deposit_fee) = &self.next_sol_deposit_fee {
+            writeln!(
+                w,
+                "Next SOL Deposit Fee: {} of deposit amount",
+                next_sol_deposit_fee
+            )?;
+        }
         writeln!(
             w,
             "Stake Deposit Referral Fee: {}% of Stake Deposit Fee",
             &self.stake_referral_fee
         )?;
+        if let Some(next_stake_referral_fee) = &self.next_stake_referral_fee {
+            writeln!(
+                w,
+                "Next Stake Deposit Referral Fee: {}% of Stake Deposit Fee",
+                next_stake_referral_fee
+            )?;
+        }
         writeln!(
             w,
             "SOL Deposit Referral Fee: {}% of SOL Deposit Fee",
             &self.sol_referral_fee
         )?;
+        if let Some(next_sol_referral_fee) = &self.next_sol_referral_fee {
+            writeln!(
+                w,
+                "Next SOL Deposit Referral Fee: {}% of SOL Deposit Fee",
+                next_sol_referral_fee
+            )?;
+        }
+        writeln!(
+            w,
+            "Fee Change Delay: {} epochs",
+            &self.fee_change_delay_epochs
+        )?;
+        if let Some(next_fee_change_delay_epochs) = &self.next_fee_change_delay_epochs {
+            writeln!(
+                w,
+                "Next Fee Change Delay: {} epochs",
+                next_fee_change_delay_epochs
+            )?;
+        }
+        if let Some(max_validator_share_bps) = &self.max_validator_share_bps {
+            writeln!(
+                w,
+                "Max Validator Share: {} bps of the pool",
+                max_validator_share_bps
+            )?;
+        }
+        if let Some(max_validator_commission) = &self.max_validator_commission {
+            writeln!(w, "Max Validator Commission: {}%", max_validator_commission)?;
+        }
+        if let Some(max_validator_vote_age_epochs) = &self.max_validator_vote_age_epochs {
+            writeln!(
+                w,
+                "Max Validator Vote Age: {} epochs",
+                max_validator_vote_age_epochs
+            )?;
+        }
+        match &self.max_direct_stake_bps {
+            None => writeln!(w, "Direct Stake Deposits: Disabled")?,
+            Some(bps) => writeln!(
+                w,
+                "Direct Stake Deposits: up to {} bps of the pool per validator",
+                bps
+            )?,
+        }
         writeln!(w)?;
 
         match &self.details {
@@ -305,7 +445,7 @@ impl VerboseDisplay for CliStakePoolDetails {
         for stake_account in &self.stake_accounts {
             writeln!(
                 w,
-                "Index: {}\tVote Account: {}\tStake Account: {}\tActive Balance: {}\tTransient Stake Account: {}\tTransient Balance: {}\tLast Update Epoch: {}{}",
+                "Index: {}\tVote Account: {}\tStake Account: {}\tActive Balance: {}\tTransient Stake Account: {}\tTransient Balance: {}\tLast Update Epoch: {}{}{}",
                 stake_account.index,
                 stake_account.vote_account_address,
                 stake_account.stake_account_address,
@@ -313,6 +453,10 @@ impl VerboseDisplay for CliStakePoolDetails {
                 stake_account.validator_transient_stake_account_address,
                 Sol(stake_account.validator_transient_stake_lamports),
                 stake_account.validator_last_update_epoch,
+                match stake_account.validator_max_share_bps {
+                    Some(bps) => format!("\tMax Share: {} bps", bps),
+                    None => String::new(),
+                },
                 if stake_account.update_required {
                     " [UPDATE REQUIRED]"
                 } else {
@@ -356,6 +500,7 @@ pub(crate) struct CliStakePoolStakeAccountInfo {
     pub validator_lamports: u64,
     pub validator_transient_stake_account_address: String,
     pub validator_transient_stake_lamports: u64,
+    pub validator_max_share_bps: Option<u32>,
     pub update_required: bool,
 }
 
@@ -366,7 +511,7 @@ pub(crate) struct CliStakePoolValidator {
     pub transient_stake_lamports: u64,
     pub last_update_epoch: u64,
     pub transient_seed_suffix: u64,
-    pub unused: u32,
+    pub max_share_bps: Option<u32>,
     pub validator_seed_suffix: u32,
     pub status: CliStakePoolValidatorStakeStatus,
     pub vote_account_address: String,
@@ -379,7 +524,7 @@ impl From<ValidatorStakeInfo> for CliStakePoolValidator {
             transient_stake_lamports: v.transient_stake_lamports.into(),
             last_update_epoch: v.last_update_epoch.into(),
             transient_seed_suffix: v.transient_seed_suffix.into(),
-            unused: v.unused.into(),
+            max_share_bps: v.max_share_bps(),
             validator_seed_suffix: v.validator_seed_suffix.into(),
             status: CliStakePoolValidatorStakeStatus::from(v.status),
             vote_account_address: v.vote_account_address.to_string(),
@@ -453,6 +598,38 @@ impl From<Fee> for CliStakePoolFee {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliSolWithdrawalFeeCurve {
+    pub target_reserve_bps: u16,
+    pub max_fee: CliStakePoolFee,
+    pub exponent: u8,
+}
+
+impl Display for CliSolWithdrawalFeeCurve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.target_reserve_bps == 0 {
+            write!(f, "Disabled")
+        } else {
+            write!(
+                f,
+                "up to {} of withdrawal amount as the reserve drains below {} bps of the pool, exponent {}",
+                &self.max_fee, &self.target_reserve_bps, &self.exponent
+            )
+        }
+    }
+}
+
+impl From<SolWithdrawalFeeCurve> for CliSolWithdrawalFeeCurve {
+    fn from(c: SolWithdrawalFeeCurve) -> Self {
+        Self {
+            target_reserve_bps: c.target_reserve_bps,
+            max_fee: CliStakePoolFee::from(c.max_fee),
+            exponent: c.exponent,
+        }
+    }
+}
+
 impl From<(Pubkey, StakePool, ValidatorList, Pubkey)> for CliStakePool {
     fn from(s: (Pubkey, StakePool, ValidatorList, Pubkey)) -> Self {
         let (address, stake_pool, validator_list, pool_withdraw_authority) = s;
@@ -501,7 +678,229 @@ impl From<(Pubkey, StakePool, ValidatorList, Pubkey)> for CliStakePool {
                 .map(CliStakePoolFee::from),
             last_epoch_pool_token_supply: stake_pool.last_epoch_pool_token_supply,
             last_epoch_total_lamports: stake_pool.last_epoch_total_lamports,
+            pending_withdrawal_lamports: stake_pool.pending_withdrawal_lamports,
+            withdrawal_claim_pool_lamports: stake_pool.withdrawal_claim_pool_lamports,
+            max_deposit_lamports_per_epoch: stake_pool.max_deposit_lamports_per_epoch,
+            max_sol_withdrawal_lamports_per_epoch: stake_pool.max_sol_withdrawal_lamports_per_epoch,
+            max_total_lamports: stake_pool.max_total_lamports,
+            flow_epoch: stake_pool.flow_epoch,
+            epoch_deposited_lamports: stake_pool.epoch_deposited_lamports,
+            epoch_sol_withdrawn_lamports: stake_pool.epoch_sol_withdrawn_lamports,
+            pauser: stake_pool.pauser.map(|x| x.to_string()),
+            paused_operations: stake_pool.paused_operations,
+            pending_manager: stake_pool.pending_manager.map(|x| x.to_string()),
+            pending_staker: stake_pool.pending_staker.map(|x| x.to_string()),
+            next_stake_deposit_fee: Option::<Fee>::from(stake_pool.next_stake_deposit_fee)
+                .map(CliStakePoolFee::from),
+            next_sol_deposit_fee: Option::<Fee>::from(stake_pool.next_sol_deposit_fee)
+                .map(CliStakePoolFee::from),
+            next_stake_referral_fee: stake_pool.next_stake_referral_fee.into(),
+            next_sol_referral_fee: stake_pool.next_sol_referral_fee.into(),
+            fee_change_delay_epochs: stake_pool.fee_change_delay_epochs,
+            next_fee_change_delay_epochs: stake_pool.next_fee_change_delay_epochs,
+            fee_change_epoch: stake_pool.fee_change_epoch,
+            max_direct_stake_bps: stake_pool.max_direct_stake_bps,
+            max_validator_share_bps: stake_pool.max_validator_share_bps,
+            max_validator_commission: stake_pool.max_validator_commission,
+            max_validator_vote_age_epochs: stake_pool.max_validator_vote_age_epochs,
+            sol_withdrawal_fee_curve: CliSolWithdrawalFeeCurve::from(
+                stake_pool.sol_withdrawal_fee_curve,
+            ),
+            next_sol_withdrawal_fee_curve: Option::<SolWithdrawalFeeCurve>::from(
+                stake_pool.next_sol_withdrawal_fee_curve,
+            )
+            .map(CliSolWithdrawalFeeCurve::from),
+            has_fee_split: stake_pool.has_fee_split,
+            insurance_fee_bps: stake_pool.insurance_fee_bps,
+            uncovered_loss_lamports: stake_pool.uncovered_loss_lamports,
+            has_rate_history: stake_pool.has_rate_history,
+            has_oracle: stake_pool.has_oracle,
+            pending_insurance_lamports: stake_pool.pending_insurance_lamports,
             details: None,
         }
     }
 }
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliInsuranceFund {
+    pub address: String,
+    pub stake_pool: String,
+    pub insurance_fee_bps: u16,
+    pub available_lamports: u64,
+    pub pending_lamports: u64,
+    pub deposited_lamports: u64,
+    pub injected_lamports: u64,
+    pub uncovered_loss_lamports: u64,
+}
+
+impl QuietDisplay for CliInsuranceFund {}
+impl VerboseDisplay for CliInsuranceFund {}
+
+impl Display for CliInsuranceFund {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Insurance Fund: {}", &self.address)?;
+        writeln!(f, "Stake Pool: {}", &self.stake_pool)?;
+        writeln!(
+            f,
+            "Insurance Fee: {}% of epoch fee",
+            f64::from(self.insurance_fee_bps) / 100.0
+        )?;
+        writeln!(f, "Available: {}", Sol(self.available_lamports))?;
+        writeln!(f, "Pending Deposit: {}", Sol(self.pending_lamports))?;
+        writeln!(f, "Total Deposited: {}", Sol(self.deposited_lamports))?;
+        writeln!(f, "Total Injected: {}", Sol(self.injected_lamports))?;
+        writeln!(
+            f,
+            "Uncovered Stake Loss: {}",
+            Sol(self.uncovered_loss_lamports)
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliRateHistory {
+    pub address: String,
+    pub stake_pool: String,
+    pub trailing_apy: Option<f64>,
+    pub trailing_apy_epochs: u64,
+    pub entries: Vec<CliRateHistoryEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliRateHistoryEntry {
+    pub epoch: u64,
+    pub unix_timestamp: i64,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub fee_pool_tokens: u64,
+    pub exchange_rate: Option<f64>,
+}
+
+impl QuietDisplay for CliRateHistory {}
+impl VerboseDisplay for CliRateHistory {}
+
+impl Display for CliRateHistory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Rate History: {}", &self.address)?;
+        writeln!(f, "Stake Pool: {}", &self.stake_pool)?;
+        match self.trailing_apy {
+            Some(apy) => writeln!(
+                f,
+                "Trailing APY ({} epochs): {:.2}%",
+                self.trailing_apy_epochs,
+                apy * 100.0
+            )?,
+            None => writeln!(
+                f,
+                "Trailing APY ({} epochs): not enough history",
+                self.trailing_apy_epochs
+            )?,
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>8} {:>20} {:>20} {:>14} {:>12}",
+            "Epoch", "Total Lamports", "Pool Tokens", "Fee Tokens", "Rate"
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:>8} {:>20} {:>20} {:>14} {:>12}",
+                entry.epoch,
+                entry.total_lamports,
+                entry.pool_token_supply,
+                entry.fee_pool_tokens,
+                entry
+                    .exchange_rate
+                    .map_or_else(|| "-".to_string(), |rate| format!("{:.9}", rate)),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliRebalancePlan {
+    pub total_lamports: u64,
+    pub retained_reserve_lamports: u64,
+    pub available_reserve_lamports: u64,
+    pub validators: Vec<CliRebalanceValidator>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliRebalanceValidator {
+    pub vote_account_address: String,
+    pub weight: f64,
+    pub current_lamports: u64,
+    pub pending_direct_lamports: u64,
+    pub target_lamports: u64,
+    pub increase_lamports: Option<u64>,
+    pub decrease_lamports: Option<u64>,
+    pub skip_reason: Option<String>,
+}
+
+impl QuietDisplay for CliRebalancePlan {}
+impl VerboseDisplay for CliRebalancePlan {}
+
+impl Display for CliRebalancePlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Total Pool Stake: {}", Sol(self.total_lamports))?;
+        writeln!(
+            f,
+            "Retained Reserve: {}",
+            Sol(self.retained_reserve_lamports)
+        )?;
+        writeln!(
+            f,
+            "Available Reserve: {}",
+            Sol(self.available_reserve_lamports)
+        )?;
+        for validator in &self.validators {
+            let action = match (validator.increase_lamports, validator.decrease_lamports) {
+                (Some(lamports), _) => format!("increase by {}", Sol(lamports)),
+                (_, Some(lamports)) => format!("decrease by {}", Sol(lamports)),
+                _ => match &validator.skip_reason {
+                    Some(reason) => format!("skipped: {}", reason),
+                    None => "none".to_string(),
+                },
+            };
+            writeln!(
+                f,
+                "Vote Account: {}\tWeight: {:.2}%\tCurrent: {}\tDirect: {}\tTarget: {}\tAction: {}",
+                validator.vote_account_address,
+                validator.weight * 100.0,
+                Sol(validator.current_lamports),
+                Sol(validator.pending_direct_lamports),
+                Sol(validator.target_lamports),
+                action,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&PlannedValidator> for CliRebalanceValidator {
+    fn from(planned: &PlannedValidator) -> Self {
+        let (increase_lamports, decrease_lamports) = match planned.change {
+            Some(StakeChange::Increase(lamports)) => (Some(lamports), None),
+            Some(StakeChange::Decrease(lamports)) => (None, Some(lamports)),
+            None => (None, None),
+        };
+        Self {
+            vote_account_address: planned.vote_account.to_string(),
+            weight: planned.weight,
+            current_lamports: planned.current_lamports,
+            pending_direct_lamports: planned.pending_direct_lamports,
+            target_lamports: planned.target_lamports,
+            increase_lamports,
+            decrease_lamports,
+            skip_reason: planned.skip_reason.clone(),
+        }
+    }
+}
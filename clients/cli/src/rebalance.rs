@@ -0,0 +1,544 @@
+//! Performance-weighted stake rebalancing.
+//!
+//! Validators are scored from their vote accounts and leader schedule, the
+//! scores become target weights over the pool's stakeable lamports, and the
+//! plan only moves stake where a validator is further from its target than
+//! the tolerance allows. SOL deposited on behalf of a validator and not yet
+//! delegated raises its target, so depositors' choices are honoured.
+
+use {
+    crate::client::Error,
+    solana_client::rpc_client::RpcClient,
+    solana_program::pubkey::Pubkey,
+    spl_stake_pool::{
+        state::{StakeStatus, ValidatorList, ValidatorStakeInfo},
+        MAX_BASIS_POINTS,
+    },
+    std::{collections::HashMap, str::FromStr},
+};
+
+/// Performance of a validator over the last completed epoch
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ValidatorPerformance {
+    /// Vote credits earned in the last completed epoch
+    pub epoch_credits: u64,
+    /// Commission, as a percentage
+    pub commission: u8,
+    /// Share of leader slots skipped in the current epoch, if it had any
+    pub skip_rate: Option<f64>,
+    /// Whether the validator is currently delinquent
+    pub delinquent: bool,
+}
+
+impl ValidatorPerformance {
+    /// Scores the validator between 0 and 1: its vote credits relative to
+    /// the best validator, scaled down by its commission and skip rate.
+    /// Delinquent validators and those above `max_commission` score 0.
+    pub fn score(&self, max_epoch_credits: u64, max_commission: u8) -> f64 {
+        if self.delinquent || self.commission > max_commission || max_epoch_credits == 0 {
+            return 0.0;
+        }
+        let credits = self.epoch_credits as f64 / max_epoch_credits as f64;
+        let commission = 1.0 - f64::from(self.commission.min(100)) / 100.0;
+        let skip_rate = 1.0 - self.skip_rate.unwrap_or(0.0).clamp(0.0, 1.0);
+        credits * commission * skip_rate
+    }
+}
+
+/// Reads the performance of the given vote accounts. Vote accounts that are
+/// not staked on the cluster are left out, and score as delinquent.
+pub(crate) fn get_validator_performance(
+    rpc_client: &RpcClient,
+    vote_accounts: &[Pubkey],
+) -> Result<HashMap<Pubkey, ValidatorPerformance>, Error> {
+    let epoch = rpc_client.get_epoch_info()?.epoch;
+    let vote_account_status = rpc_client.get_vote_accounts()?;
+    let block_production = rpc_client.get_block_production()?.value.by_identity;
+
+    let mut performance = HashMap::new();
+    let current = vote_account_status.current.iter().map(|v| (v, false));
+    let delinquent = vote_account_status.delinquent.iter().map(|v| (v, true));
+    for (vote_account_info, delinquent) in current.chain(delinquent) {
+        let vote_account = Pubkey::from_str(&vote_account_info.vote_pubkey)?;
+        if !vote_accounts.contains(&vote_account) {
+            continue;
+        }
+        let epoch_credits = vote_account_info
+            .epoch_credits
+            .iter()
+            .find(|(credits_epoch, _, _)| credits_epoch.saturating_add(1) == epoch)
+            .map(|(_, credits, previous_credits)| credits.saturating_sub(*previous_credits))
+            .unwrap_or_default();
+        let skip_rate = block_production
+            .get(&vote_account_info.node_pubkey)
+            .filter(|(leader_slots, _)| *leader_slots > 0)
+            .map(|(leader_slots, blocks_produced)| {
+                1.0 - *blocks_produced as f64 / *leader_slots as f64
+            });
+        performance.insert(
+            vote_account,
+            ValidatorPerformance {
+                epoch_credits,
+                commission: vote_account_info.commission,
+                skip_rate,
+                delinquent,
+            },
+        );
+    }
+    Ok(performance)
+}
+
+/// Computes target weights summing to 1 for the given vote accounts, or
+/// `None` if none of them is eligible for stake
+pub(crate) fn target_weights(
+    vote_accounts: &[Pubkey],
+    performance: &HashMap<Pubkey, ValidatorPerformance>,
+    max_commission: u8,
+) -> Option<HashMap<Pubkey, f64>> {
+    let max_epoch_credits = performance
+        .values()
+        .filter(|p| !p.delinquent)
+        .map(|p| p.epoch_credits)
+        .max()
+        .unwrap_or_default();
+    let scores = vote_accounts
+        .iter()
+        .map(|vote_account| {
+            let score = performance
+                .get(vote_account)
+                .map(|p| p.score(max_epoch_credits, max_commission))
+                .unwrap_or_default();
+            (*vote_account, score)
+        })
+        .collect::<Vec<_>>();
+    let total_score: f64 = scores.iter().map(|(_, score)| score).sum();
+    if total_score <= 0.0 {
+        return None;
+    }
+    Some(
+        scores
+            .into_iter()
+            .map(|(vote_account, score)| (vote_account, score / total_score))
+            .collect(),
+    )
+}
+
+/// Pool-wide amounts the plan is bounded by
+#[derive(Clone, Debug)]
+pub(crate) struct RebalanceParams {
+    /// Lamports in the pool to split between validators and the reserve
+    pub total_lamports: u64,
+    /// Lamports in the reserve that may be moved to validators, net of the
+    /// lamports set aside for withdrawal claims
+    pub available_reserve_lamports: u64,
+    /// Lamports to keep in the reserve
+    pub retained_reserve_lamports: u64,
+    /// Rent-exempt reserve of a stake account
+    pub stake_rent: u64,
+    /// Minimum delegation of a stake account in the pool
+    pub minimum_delegation: u64,
+    /// Smallest difference from the target worth moving stake for
+    pub tolerance_lamports: u64,
+    /// Pool-wide maximum share of a single validator, in basis points
+    pub max_validator_share_bps: Option<u16>,
+}
+
+impl RebalanceParams {
+    /// Smallest stake a validator stake account can hold
+    fn minimum_validator_lamports(&self) -> u64 {
+        self.stake_rent.saturating_add(self.minimum_delegation)
+    }
+
+    /// Most lamports the validator may hold under the tighter of its own
+    /// maximum share and the pool-wide one, mirroring the program's check
+    fn max_validator_lamports(&self, validator: &ValidatorStakeInfo) -> Option<u64> {
+        let max_share_bps = [
+            self.max_validator_share_bps.map(u32::from),
+            validator.max_share_bps(),
+        ]
+        .into_iter()
+        .flatten()
+        .min()?;
+        let max_validator_lamports = u128::from(self.total_lamports) * u128::from(max_share_bps)
+            / u128::from(MAX_BASIS_POINTS);
+        Some(u64::try_from(max_validator_lamports).unwrap_or(u64::MAX))
+    }
+}
+
+/// A stake movement for one validator
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StakeChange {
+    Increase(u64),
+    Decrease(u64),
+}
+
+/// A validator's place in the rebalancing plan
+#[derive(Clone, Debug)]
+pub(crate) struct PlannedValidator {
+    pub vote_account: Pubkey,
+    pub weight: f64,
+    pub current_lamports: u64,
+    /// Lamports deposited for the validator and not yet delegated to it
+    pub pending_direct_lamports: u64,
+    pub target_lamports: u64,
+    pub change: Option<StakeChange>,
+    /// Why a validator off its target is left alone
+    pub skip_reason: Option<String>,
+}
+
+/// Plans the stake movements bringing every active validator to its target.
+///
+/// Every validator keeps at least the minimum validator stake, and the rest
+/// of the stakeable lamports is split by weight. Any direct stake pending
+/// delegation to a validator, taken from `pending_direct_stake`, comes on top
+/// of its weighted target, which is capped at the validator's maximum share
+/// of the pool; what a capped validator can't take stays in the reserve.
+/// Validators with transient stake are skipped, since it can't be told
+/// whether it is activating or deactivating. Increases are paid for by the
+/// reserve, largest deficit first, including the rent of the transient stake
+/// account.
+pub(crate) fn plan_rebalance(
+    validator_list: &ValidatorList,
+    weights: &HashMap<Pubkey, f64>,
+    pending_direct_stake: &HashMap<Pubkey, u64>,
+    params: &RebalanceParams,
+) -> Vec<PlannedValidator> {
+    let minimum_validator_lamports = params.minimum_validator_lamports();
+    let active_validators = validator_list
+        .validators
+        .iter()
+        .filter(|v| StakeStatus::try_from(v.status) == Ok(StakeStatus::Active))
+        .collect::<Vec<_>>();
+    let stakeable_lamports = params
+        .total_lamports
+        .saturating_sub(params.retained_reserve_lamports)
+        .saturating_sub(minimum_validator_lamports.saturating_mul(active_validators.len() as u64));
+
+    let mut plan = active_validators
+        .iter()
+        .map(|validator| {
+            let weight = weights
+                .get(&validator.vote_account_address)
+                .copied()
+                .unwrap_or_default();
+            let current_lamports: u64 = validator.active_stake_lamports.into();
+            let pending_direct_lamports = pending_direct_stake
+                .get(&validator.vote_account_address)
+                .copied()
+                .unwrap_or_default();
+            let mut target_lamports = minimum_validator_lamports
+                .saturating_add((stakeable_lamports as f64 * weight) as u64)
+                .saturating_add(pending_direct_lamports);
+            if let Some(max_validator_lamports) = params.max_validator_lamports(validator) {
+                target_lamports = target_lamports
+                    .min(max_validator_lamports)
+                    .max(minimum_validator_lamports);
+            }
+            PlannedValidator {
+                vote_account: validator.vote_account_address,
+                weight,
+                current_lamports,
+                pending_direct_lamports,
+                target_lamports,
+                change: None,
+                skip_reason: None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut increases = vec![];
+    for (planned, validator) in plan.iter_mut().zip(active_validators.iter()) {
+        let difference = planned.target_lamports.abs_diff(planned.current_lamports);
+        if difference == 0 || difference < params.tolerance_lamports {
+            continue;
+        }
+        if u64::from(validator.transient_stake_lamports) != 0 {
+            planned.skip_reason = Some(format!(
+                "{} transient lamports",
+                u64::from(validator.transient_stake_lamports)
+            ));
+            continue;
+        }
+        if planned.target_lamports > planned.current_lamports {
+            increases.push((difference, planned));
+        } else if difference < minimum_validator_lamports {
+            planned.skip_reason = Some(format!(
+                "decrease below the minimum of {} lamports",
+                minimum_validator_lamports
+            ));
+        } else {
+            planned.change = Some(StakeChange::Decrease(difference));
+        }
+    }
+
+    increases.sort_by(|(left, _), (right, _)| right.cmp(left));
+    let mut available_reserve_lamports = params.available_reserve_lamports;
+    for (difference, planned) in increases {
+        let lamports = difference.min(available_reserve_lamports.saturating_sub(params.stake_rent));
+        if lamports < params.minimum_delegation {
+            planned.skip_reason = Some(if lamports < difference {
+                "not enough lamports in the reserve".to_string()
+            } else {
+                format!(
+                    "increase below the minimum delegation of {} lamports",
+                    params.minimum_delegation
+                )
+            });
+            continue;
+        }
+        available_reserve_lamports = available_reserve_lamports
+            .saturating_sub(lamports)
+            .saturating_sub(params.stake_rent);
+        planned.change = Some(StakeChange::Increase(lamports));
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        spl_stake_pool::state::{AccountType, ValidatorListHeader},
+    };
+
+    const STAKE_RENT: u64 = 2_000;
+    const MINIMUM_DELEGATION: u64 = 8_000;
+    const MINIMUM_VALIDATOR_LAMPORTS: u64 = STAKE_RENT + MINIMUM_DELEGATION;
+
+    fn vote_account(index: u8) -> Pubkey {
+        Pubkey::new_from_array([index; 32])
+    }
+
+    fn validator_list(active_stake_lamports: &[u64]) -> ValidatorList {
+        ValidatorList {
+            header: ValidatorListHeader {
+                account_type: AccountType::ValidatorList,
+                max_validators: active_stake_lamports.len() as u32,
+            },
+            validators: active_stake_lamports
+                .iter()
+                .enumerate()
+                .map(|(index, lamports)| ValidatorStakeInfo {
+                    status: StakeStatus::Active.into(),
+                    vote_account_address: vote_account(index as u8),
+                    active_stake_lamports: (*lamports).into(),
+                    ..ValidatorStakeInfo::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn weights(weights: &[f64]) -> HashMap<Pubkey, f64> {
+        weights
+            .iter()
+            .enumerate()
+            .map(|(index, weight)| (vote_account(index as u8), *weight))
+            .collect()
+    }
+
+    /// Params with `stakeable_lamports` to split over `validators` validators
+    /// on top of their minimum stake
+    fn params(
+        validators: u64,
+        stakeable_lamports: u64,
+        available_reserve_lamports: u64,
+    ) -> RebalanceParams {
+        RebalanceParams {
+            total_lamports: stakeable_lamports + validators * MINIMUM_VALIDATOR_LAMPORTS,
+            available_reserve_lamports,
+            retained_reserve_lamports: 0,
+            stake_rent: STAKE_RENT,
+            minimum_delegation: MINIMUM_DELEGATION,
+            tolerance_lamports: 0,
+            max_validator_share_bps: None,
+        }
+    }
+
+    fn changes(plan: &[PlannedValidator]) -> Vec<Option<StakeChange>> {
+        plan.iter().map(|planned| planned.change).collect()
+    }
+
+    #[test]
+    fn score() {
+        let performance = ValidatorPerformance {
+            epoch_credits: 50,
+            commission: 10,
+            skip_rate: Some(0.2),
+            delinquent: false,
+        };
+        let score = performance.score(100, 10);
+        assert!((score - 0.5 * 0.9 * 0.8).abs() < f64::EPSILON);
+
+        assert_eq!(performance.score(100, 9), 0.0);
+        assert_eq!(performance.score(0, 10), 0.0);
+        let delinquent = ValidatorPerformance {
+            delinquent: true,
+            ..performance
+        };
+        assert_eq!(delinquent.score(100, 10), 0.0);
+    }
+
+    #[test]
+    fn target_weights_follow_scores() {
+        let vote_accounts = [vote_account(0), vote_account(1), vote_account(2)];
+        let performance = HashMap::from([
+            (
+                vote_accounts[0],
+                ValidatorPerformance {
+                    epoch_credits: 100,
+                    ..ValidatorPerformance::default()
+                },
+            ),
+            (
+                vote_accounts[1],
+                ValidatorPerformance {
+                    epoch_credits: 50,
+                    ..ValidatorPerformance::default()
+                },
+            ),
+            (
+                vote_accounts[2],
+                ValidatorPerformance {
+                    epoch_credits: 200,
+                    delinquent: true,
+                    ..ValidatorPerformance::default()
+                },
+            ),
+        ]);
+
+        let weights = target_weights(&vote_accounts, &performance, 10).unwrap();
+        assert!((weights[&vote_accounts[0]] - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert!((weights[&vote_accounts[1]] - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(weights[&vote_accounts[2]], 0.0);
+
+        assert!(target_weights(&vote_accounts[2..], &performance, 10).is_none());
+    }
+
+    #[test]
+    fn plan_splits_by_weight() {
+        let plan = plan_rebalance(
+            &validator_list(&[MINIMUM_VALIDATOR_LAMPORTS, MINIMUM_VALIDATOR_LAMPORTS]),
+            &weights(&[0.75, 0.25]),
+            &HashMap::new(),
+            &params(2, 1_000_000, 2_000_000),
+        );
+        assert_eq!(
+            changes(&plan),
+            vec![
+                Some(StakeChange::Increase(750_000)),
+                Some(StakeChange::Increase(250_000)),
+            ]
+        );
+        assert_eq!(
+            plan[0].target_lamports,
+            MINIMUM_VALIDATOR_LAMPORTS + 750_000
+        );
+    }
+
+    #[test]
+    fn plan_rounds_targets_down() {
+        let third = 1.0 / 3.0;
+        let plan = plan_rebalance(
+            &validator_list(&[MINIMUM_VALIDATOR_LAMPORTS; 3]),
+            &weights(&[third, third, third]),
+            &HashMap::new(),
+            &params(3, 1_000_000, 2_000_000),
+        );
+        assert!(plan
+            .iter()
+            .all(|planned| planned.change == Some(StakeChange::Increase(333_333))));
+    }
+
+    #[test]
+    fn plan_respects_reserve() {
+        // the largest deficit is filled first, then the rest of the reserve,
+        // keeping the rent of each transient stake account
+        let plan = plan_rebalance(
+            &validator_list(&[MINIMUM_VALIDATOR_LAMPORTS, MINIMUM_VALIDATOR_LAMPORTS]),
+            &weights(&[0.25, 0.75]),
+            &HashMap::new(),
+            &params(2, 1_000_000, 800_000),
+        );
+        assert_eq!(
+            changes(&plan),
+            vec![
+                Some(StakeChange::Increase(800_000 - 752_000 - STAKE_RENT)),
+                Some(StakeChange::Increase(750_000)),
+            ]
+        );
+
+        // not enough left for a minimum delegation
+        let plan = plan_rebalance(
+            &validator_list(&[MINIMUM_VALIDATOR_LAMPORTS, MINIMUM_VALIDATOR_LAMPORTS]),
+            &weights(&[0.25, 0.75]),
+            &HashMap::new(),
+            &params(2, 1_000_000, 757_000),
+        );
+        assert_eq!(
+            changes(&plan),
+            vec![None, Some(StakeChange::Increase(750_000))]
+        );
+        assert_eq!(
+            plan[0].skip_reason.as_deref(),
+            Some("not enough lamports in the reserve")
+        );
+    }
+
+    #[test]
+    fn plan_respects_share_caps() {
+        let mut validator_list = validator_list(&[
+            MINIMUM_VALIDATOR_LAMPORTS,
+            MINIMUM_VALIDATOR_LAMPORTS,
+            700_000,
+        ]);
+        validator_list.validators[1].max_share_bps = 1_000.into();
+        let mut params = params(3, 1_000_000, 2_000_000);
+        params.max_validator_share_bps = Some(5_000);
+        let max_pool_share = params.total_lamports / 2;
+        let max_validator_share = params.total_lamports / 10;
+
+        let plan = plan_rebalance(
+            &validator_list,
+            &weights(&[0.3, 0.1, 0.6]),
+            &HashMap::new(),
+            &params,
+        );
+        assert_eq!(
+            changes(&plan),
+            vec![
+                Some(StakeChange::Increase(300_000)),
+                Some(StakeChange::Increase(
+                    max_validator_share - MINIMUM_VALIDATOR_LAMPORTS
+                )),
+                Some(StakeChange::Decrease(700_000 - max_pool_share)),
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_decreases_over_weight_validators() {
+        // a validator that lost its weight gives its stake back, except what
+        // was deposited for it and is still pending delegation
+        let plan = plan_rebalance(
+            &validator_list(&[MINIMUM_VALIDATOR_LAMPORTS, 600_000, 400_000]),
+            &weights(&[1.0, 0.0, 0.0]),
+            &HashMap::from([(vote_account(2), 100_000)]),
+            &params(3, 1_000_000, 2_000_000),
+        );
+        assert_eq!(
+            changes(&plan),
+            vec![
+                Some(StakeChange::Increase(1_000_000)),
+                Some(StakeChange::Decrease(600_000 - MINIMUM_VALIDATOR_LAMPORTS)),
+                Some(StakeChange::Decrease(
+                    400_000 - MINIMUM_VALIDATOR_LAMPORTS - 100_000
+                )),
+            ]
+        );
+        assert_eq!(
+            plan[2].target_lamports,
+            MINIMUM_VALIDATOR_LAMPORTS + 100_000
+        );
+    }
+}
@@ -496,6 +496,8 @@ export type WithdrawFromStakeAccountWithSessionParams = {
   userWallet: PublicKey
   /** The session signer (user or session) */
   sessionSigner: PublicKey
+  /** The stake pool, checked for paused session operations */
+  stakePool: PublicKey
   /** Seed used to derive the user stake PDA */
   userStakeSeed: AmountInput
   /** Lamports to withdraw (use BigInt(Number.MAX_SAFE_INTEGER) for full withdrawal) */
@@ -1349,6 +1351,7 @@ export class StakePoolInstruction {
       { pubkey: SYSVAR_STAKE_HISTORY_PUBKEY, isSigner: false, isWritable: false },
       { pubkey: params.sessionSigner, isSigner: true, isWritable: false },
       { pubkey: StakeProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: params.stakePool, isSigner: false, isWritable: false },
     ]
 
     return new TransactionInstruction({
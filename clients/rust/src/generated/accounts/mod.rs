@@ -5,8 +5,24 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+pub(crate) mod r#direct_stake_account;
+pub(crate) mod r#fee_split_account;
+pub(crate) mod r#insurance_fund_account;
+pub(crate) mod r#oracle_account;
+pub(crate) mod r#rate_history_account;
+pub(crate) mod r#referrer_account;
 pub(crate) mod r#stake_pool_account;
 pub(crate) mod r#validator_list_account;
+pub(crate) mod r#validator_metadata_account;
+pub(crate) mod r#withdrawal_ticket_account;
 
+pub use self::r#direct_stake_account::*;
+pub use self::r#fee_split_account::*;
+pub use self::r#insurance_fund_account::*;
+pub use self::r#oracle_account::*;
+pub use self::r#rate_history_account::*;
+pub use self::r#referrer_account::*;
 pub use self::r#stake_pool_account::*;
 pub use self::r#validator_list_account::*;
+pub use self::r#validator_metadata_account::*;
+pub use self::r#withdrawal_ticket_account::*;
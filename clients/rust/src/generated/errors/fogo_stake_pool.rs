@@ -145,6 +145,87 @@ pub enum FogoStakePoolError {
     /// 44 - The stake pool has too many validators
     #[error("The stake pool has too many validators")]
     TooManyValidatorsInPool = 0x2C,
+    /// 45 - InvalidWithdrawalTicket
+    #[error("InvalidWithdrawalTicket")]
+    InvalidWithdrawalTicket = 0x2D,
+    /// 46 - WithdrawalTicketNotClaimable
+    #[error("WithdrawalTicketNotClaimable")]
+    WithdrawalTicketNotClaimable = 0x2E,
+    /// 47 - InsufficientWithdrawalClaimPool
+    #[error("InsufficientWithdrawalClaimPool")]
+    InsufficientWithdrawalClaimPool = 0x2F,
+    /// 48 - DepositCapExceeded
+    #[error("DepositCapExceeded")]
+    DepositCapExceeded = 0x30,
+    /// 49 - SolWithdrawalCapExceeded
+    #[error("SolWithdrawalCapExceeded")]
+    SolWithdrawalCapExceeded = 0x31,
+    /// 50 - TotalLamportsCapExceeded
+    #[error("TotalLamportsCapExceeded")]
+    TotalLamportsCapExceeded = 0x32,
+    /// 51 - OperationPaused
+    #[error("OperationPaused")]
+    OperationPaused = 0x33,
+    /// 52 - WrongPauser
+    #[error("WrongPauser")]
+    WrongPauser = 0x34,
+    /// 53 - WrongPendingAuthority
+    #[error("WrongPendingAuthority")]
+    WrongPendingAuthority = 0x35,
+    /// 54 - FeeChangeDelayTooLong
+    #[error("FeeChangeDelayTooLong")]
+    FeeChangeDelayTooLong = 0x36,
+    /// 55 - InvalidReferralCode
+    #[error("InvalidReferralCode")]
+    InvalidReferralCode = 0x37,
+    /// 56 - InvalidReferrer
+    #[error("InvalidReferrer")]
+    InvalidReferrer = 0x38,
+    /// 57 - DirectStakeShareExceeded
+    #[error("DirectStakeShareExceeded")]
+    DirectStakeShareExceeded = 0x39,
+    /// 58 - InvalidDirectStake
+    #[error("InvalidDirectStake")]
+    InvalidDirectStake = 0x3A,
+    /// 59 - ValidatorShareExceeded
+    #[error("ValidatorShareExceeded")]
+    ValidatorShareExceeded = 0x3B,
+    /// 60 - InvalidValidatorMetadata
+    #[error("InvalidValidatorMetadata")]
+    InvalidValidatorMetadata = 0x3C,
+    /// 61 - CommissionTooHigh
+    #[error("CommissionTooHigh")]
+    CommissionTooHigh = 0x3D,
+    /// 62 - CommissionWithinLimit
+    #[error("CommissionWithinLimit")]
+    CommissionWithinLimit = 0x3E,
+    /// 63 - ValidatorNotDelinquent
+    #[error("ValidatorNotDelinquent")]
+    ValidatorNotDelinquent = 0x3F,
+    /// 64 - InvalidSolWithdrawalFeeCurve
+    #[error("InvalidSolWithdrawalFeeCurve")]
+    InvalidSolWithdrawalFeeCurve = 0x40,
+    /// 65 - InvalidFeeSplit
+    #[error("InvalidFeeSplit")]
+    InvalidFeeSplit = 0x41,
+    /// 66 - InvalidInsuranceFund
+    #[error("InvalidInsuranceFund")]
+    InvalidInsuranceFund = 0x42,
+    /// 67 - InsuranceInjectionTooLarge
+    #[error("InsuranceInjectionTooLarge")]
+    InsuranceInjectionTooLarge = 0x43,
+    /// 68 - InvalidRateHistory
+    #[error("InvalidRateHistory")]
+    InvalidRateHistory = 0x44,
+    /// 69 - InvalidOracle
+    #[error("InvalidOracle")]
+    InvalidOracle = 0x45,
+    /// 70 - InvalidMetadataPointer
+    #[error("InvalidMetadataPointer")]
+    InvalidMetadataPointer = 0x46,
+    /// 71 - StakePoolNeedsMigration
+    #[error("StakePoolNeedsMigration")]
+    StakePoolNeedsMigration = 0x47,
 }
 
 impl From<FogoStakePoolError> for solana_program_error::ProgramError {
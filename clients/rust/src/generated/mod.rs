@@ -7,6 +7,7 @@
 
 pub mod accounts;
 pub mod errors;
+pub mod instructions;
 pub mod programs;
 pub mod shared;
 pub mod types;
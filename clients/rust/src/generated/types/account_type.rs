@@ -17,4 +17,12 @@ pub enum AccountType {
     Uninitialized,
     StakePool,
     ValidatorList,
+    WithdrawalTicket,
+    Referrer,
+    DirectStake,
+    ValidatorMetadata,
+    FeeSplit,
+    InsuranceFund,
+    RateHistory,
+    Oracle,
 }
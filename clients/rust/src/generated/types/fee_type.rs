@@ -6,6 +6,7 @@
 //!
 
 use crate::generated::types::Fee;
+use crate::generated::types::SolWithdrawalFeeCurve;
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 
@@ -19,4 +20,5 @@ pub enum FeeType {
     SolDeposit(Fee),
     StakeDeposit(Fee),
     SolWithdrawal(Fee),
+    SolWithdrawalCurve(SolWithdrawalFeeCurve),
 }
@@ -6,23 +6,63 @@
 //!
 
 pub(crate) mod r#account_type;
+pub(crate) mod r#authority_type;
+pub(crate) mod r#cap_type;
+pub(crate) mod r#direct_stake;
+pub(crate) mod r#exchange_rate;
 pub(crate) mod r#fee;
+pub(crate) mod r#fee_split;
+pub(crate) mod r#fee_split_recipient;
 pub(crate) mod r#fee_type;
+pub(crate) mod r#funding_type;
 pub(crate) mod r#future_epoch_fee;
+pub(crate) mod r#future_epoch_referral_fee;
+pub(crate) mod r#future_epoch_sol_withdrawal_fee_curve;
+pub(crate) mod r#insurance_fund;
+pub(crate) mod r#offboarding_reason;
+pub(crate) mod r#oracle;
 pub(crate) mod r#pod_stake_status;
+pub(crate) mod r#preferred_validator_type;
+pub(crate) mod r#rate_history;
+pub(crate) mod r#rate_history_entry;
+pub(crate) mod r#referrer;
+pub(crate) mod r#sol_withdrawal_fee_curve;
 pub(crate) mod r#stake_pool;
+pub(crate) mod r#stake_pool_event;
 pub(crate) mod r#stake_status;
 pub(crate) mod r#validator_list;
 pub(crate) mod r#validator_list_header;
+pub(crate) mod r#validator_metadata;
 pub(crate) mod r#validator_stake_info;
+pub(crate) mod r#withdrawal_ticket;
 
 pub use self::r#account_type::*;
+pub use self::r#authority_type::*;
+pub use self::r#cap_type::*;
+pub use self::r#direct_stake::*;
+pub use self::r#exchange_rate::*;
 pub use self::r#fee::*;
+pub use self::r#fee_split::*;
+pub use self::r#fee_split_recipient::*;
 pub use self::r#fee_type::*;
+pub use self::r#funding_type::*;
 pub use self::r#future_epoch_fee::*;
+pub use self::r#future_epoch_referral_fee::*;
+pub use self::r#future_epoch_sol_withdrawal_fee_curve::*;
+pub use self::r#insurance_fund::*;
+pub use self::r#offboarding_reason::*;
+pub use self::r#oracle::*;
 pub use self::r#pod_stake_status::*;
+pub use self::r#preferred_validator_type::*;
+pub use self::r#rate_history::*;
+pub use self::r#rate_history_entry::*;
+pub use self::r#referrer::*;
+pub use self::r#sol_withdrawal_fee_curve::*;
 pub use self::r#stake_pool::*;
+pub use self::r#stake_pool_event::*;
 pub use self::r#stake_status::*;
 pub use self::r#validator_list::*;
 pub use self::r#validator_list_header::*;
+pub use self::r#validator_metadata::*;
 pub use self::r#validator_stake_info::*;
+pub use self::r#withdrawal_ticket::*;
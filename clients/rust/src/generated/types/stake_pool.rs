@@ -8,6 +8,9 @@
 use crate::generated::types::AccountType;
 use crate::generated::types::Fee;
 use crate::generated::types::FutureEpochFee;
+use crate::generated::types::FutureEpochReferralFee;
+use crate::generated::types::FutureEpochSolWithdrawalFeeCurve;
+use crate::generated::types::SolWithdrawalFeeCurve;
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 use solana_program::stake::state::Lockup;
@@ -78,4 +81,35 @@ pub struct StakePool {
     pub next_sol_withdrawal_fee: FutureEpochFee,
     pub last_epoch_pool_token_supply: u64,
     pub last_epoch_total_lamports: u64,
+    pub pending_withdrawal_lamports: u64,
+    pub withdrawal_claim_pool_lamports: u64,
+    pub max_deposit_lamports_per_epoch: Option<u64>,
+    pub max_sol_withdrawal_lamports_per_epoch: Option<u64>,
+    pub max_total_lamports: Option<u64>,
+    pub flow_epoch: u64,
+    pub epoch_deposited_lamports: u64,
+    pub epoch_sol_withdrawn_lamports: u64,
+    pub pauser: Option<Pubkey>,
+    pub paused_operations: u8,
+    pub pending_manager: Option<Pubkey>,
+    pub pending_staker: Option<Pubkey>,
+    pub next_stake_deposit_fee: FutureEpochFee,
+    pub next_sol_deposit_fee: FutureEpochFee,
+    pub next_stake_referral_fee: FutureEpochReferralFee,
+    pub next_sol_referral_fee: FutureEpochReferralFee,
+    pub fee_change_delay_epochs: u64,
+    pub next_fee_change_delay_epochs: Option<u64>,
+    pub fee_change_epoch: u64,
+    pub max_direct_stake_bps: Option<u16>,
+    pub max_validator_share_bps: Option<u16>,
+    pub max_validator_commission: Option<u8>,
+    pub max_validator_vote_age_epochs: Option<u64>,
+    pub sol_withdrawal_fee_curve: SolWithdrawalFeeCurve,
+    pub next_sol_withdrawal_fee_curve: FutureEpochSolWithdrawalFeeCurve,
+    pub has_fee_split: bool,
+    pub insurance_fee_bps: u16,
+    pub uncovered_loss_lamports: u64,
+    pub has_rate_history: bool,
+    pub has_oracle: bool,
+    pub pending_insurance_lamports: u64,
 }
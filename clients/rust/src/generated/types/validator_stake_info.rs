@@ -19,7 +19,7 @@ pub struct ValidatorStakeInfo {
     pub transient_stake_lamports: PodU64,
     pub last_update_epoch: PodU64,
     pub transient_seed_suffix: PodU64,
-    pub unused: PodU32,
+    pub max_share_bps: PodU32,
     pub validator_seed_suffix: PodU32,
     pub status: PodStakeStatus,
     #[cfg_attr(
@@ -3,7 +3,7 @@
 //! Generated Rust client for the Fogo Stake Pool program.
 //!
 //! This crate provides types and utilities for interacting with Fogo Stake Pool
-//! accounts from Rust code.
+//! accounts and building its instructions from Rust code.
 
 #![deny(missing_docs)]
 #![allow(clippy::arithmetic_side_effects)]
@@ -11,7 +11,13 @@
 #[allow(missing_docs)]
 mod generated;
 
-pub use generated::{accounts::*, errors::*, programs::FOGO_STAKE_POOL_ID as ID, types::*};
+pub use generated::{
+    accounts::*,
+    errors::*,
+    instructions::*,
+    programs::{FOGO_STAKE_POOL_ID, FOGO_STAKE_POOL_ID as ID},
+    types::*,
+};
 
 /// Re-export the program ID module.
 pub mod programs {
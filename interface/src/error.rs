@@ -2,7 +2,12 @@
 
 #[cfg(feature = "codama")]
 use codama_macros::CodamaErrors;
-use {num_derive::FromPrimitive, solana_program::program_error::ProgramError, thiserror::Error};
+#[allow(deprecated)]
+use solana_program::{decode_error::DecodeError, msg, program_error::PrintProgramError};
+use {
+    num_derive::FromPrimitive, num_traits::FromPrimitive,
+    solana_program::program_error::ProgramError, thiserror::Error,
+};
 
 /// Errors that may be returned by the Stake Pool program.
 #[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
@@ -168,6 +173,114 @@ pub enum StakePoolError {
     /// The stake pool has too many validators in the pool
     #[error("The stake pool has too many validators")]
     TooManyValidatorsInPool,
+
+    // 45.
+    /// The withdrawal ticket account is invalid or belongs to another pool
+    #[error("InvalidWithdrawalTicket")]
+    InvalidWithdrawalTicket,
+    /// The withdrawal ticket cannot be claimed before its cooldown epoch
+    #[error("WithdrawalTicketNotClaimable")]
+    WithdrawalTicketNotClaimable,
+    /// The withdrawal claim pool does not have enough lamports to pay out the
+    /// ticket, the staker must unstake more lamports into the reserve
+    #[error("InsufficientWithdrawalClaimPool")]
+    InsufficientWithdrawalClaimPool,
+    /// Deposit would exceed the pool's per-epoch deposit cap
+    #[error("DepositCapExceeded")]
+    DepositCapExceeded,
+    /// SOL withdrawal would exceed the pool's per-epoch SOL withdrawal cap
+    #[error("SolWithdrawalCapExceeded")]
+    SolWithdrawalCapExceeded,
+
+    // 50.
+    /// Deposit would push the pool's total lamports above its cap
+    #[error("TotalLamportsCapExceeded")]
+    TotalLamportsCapExceeded,
+    /// The operation is currently paused on this stake pool
+    #[error("OperationPaused")]
+    OperationPaused,
+    /// Wrong pauser account
+    #[error("WrongPauser")]
+    WrongPauser,
+    /// Signer is not the pending manager or staker being handed control
+    #[error("WrongPendingAuthority")]
+    WrongPendingAuthority,
+    /// The fee change delay is longer than `MAX_FEE_CHANGE_DELAY_EPOCHS`
+    #[error("FeeChangeDelayTooLong")]
+    FeeChangeDelayTooLong,
+
+    // 55.
+    /// The referral code is empty, too long, or contains invalid characters
+    #[error("InvalidReferralCode")]
+    InvalidReferralCode,
+    /// The referrer account does not belong to the stake pool or does not
+    /// pay out to the referral fee account
+    #[error("InvalidReferrer")]
+    InvalidReferrer,
+    /// Validator-targeted deposits are off, or the deposit would take the
+    /// validator above its allowed share of the pool
+    #[error("DirectStakeShareExceeded")]
+    DirectStakeShareExceeded,
+    /// The direct stake account does not belong to the stake pool and
+    /// validator
+    #[error("InvalidDirectStake")]
+    InvalidDirectStake,
+    /// The stake increase or deposit would take the validator above its
+    /// allowed share of the pool
+    #[error("ValidatorShareExceeded")]
+    ValidatorShareExceeded,
+
+    // 60.
+    /// The validator metadata account does not belong to the stake pool and
+    /// validator
+    #[error("InvalidValidatorMetadata")]
+    InvalidValidatorMetadata,
+    /// The validator's commission is above the maximum allowed by the pool
+    #[error("CommissionTooHigh")]
+    CommissionTooHigh,
+    /// The validator's commission is within the maximum allowed, so it can
+    /// only be removed by the staker
+    #[error("CommissionWithinLimit")]
+    CommissionWithinLimit,
+    /// The validator voted recently enough, so it can only be removed by the
+    /// staker
+    #[error("ValidatorNotDelinquent")]
+    ValidatorNotDelinquent,
+    /// The SOL withdrawal fee curve has a target above 100% or an exponent
+    /// out of range
+    #[error("InvalidSolWithdrawalFeeCurve")]
+    InvalidSolWithdrawalFeeCurve,
+
+    // 65.
+    /// The fee split has too many recipients, a zero weight, or weights adding
+    /// up to more than 100%, or the fee split accounts passed do not match
+    /// the stake pool's fee split
+    #[error("InvalidFeeSplit")]
+    InvalidFeeSplit,
+    /// The insurance fund accounts are missing or do not match the stake
+    /// pool's insurance fund, or its share of the epoch fee is above 100%
+    #[error("InvalidInsuranceFund")]
+    InvalidInsuranceFund,
+    /// The insurance fund injection is above the uncovered validator stake
+    /// losses or the fund's balance
+    #[error("InsuranceInjectionTooLarge")]
+    InsuranceInjectionTooLarge,
+    /// The exchange rate history account does not belong to the stake pool
+    #[error("InvalidRateHistory")]
+    InvalidRateHistory,
+    /// The price oracle account does not belong to the stake pool
+    #[error("InvalidOracle")]
+    InvalidOracle,
+
+    // 70.
+    /// The pool mint is not a Token-2022 mint whose metadata pointer points
+    /// to the mint itself
+    #[error("InvalidMetadataPointer")]
+    InvalidMetadataPointer,
+    /// The stake pool account was created with an earlier layout and must be
+    /// grown with `MigrateStakePool`
+    #[error("StakePoolNeedsMigration")]
+    StakePoolNeedsMigration,
 }
 
 impl From<StakePoolError> for ProgramError {
@@ -175,3 +288,95 @@ impl From<StakePoolError> for ProgramError {
         ProgramError::Custom(e as u32)
     }
 }
+
+#[allow(deprecated)]
+impl<T> DecodeError<T> for StakePoolError {
+    fn type_of() -> &'static str {
+        "Stake Pool Error"
+    }
+}
+
+#[allow(deprecated)]
+impl PrintProgramError for StakePoolError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        match self {
+            StakePoolError::AlreadyInUse => msg!("Error: The account cannot be initialized because it is already being used"),
+            StakePoolError::InvalidProgramAddress => msg!("Error: The program address provided doesn't match the value generated by the program"),
+            StakePoolError::InvalidState => msg!("Error: The stake pool state is invalid"),
+            StakePoolError::CalculationFailure => msg!("Error: The calculation failed"),
+            StakePoolError::FeeTooHigh => msg!("Error: Stake pool fee > 1"),
+            StakePoolError::WrongAccountMint => msg!("Error: Token account is associated with the wrong mint"),
+            StakePoolError::WrongManager => msg!("Error: Wrong pool manager account"),
+            StakePoolError::SignatureMissing => msg!("Error: Required signature is missing"),
+            StakePoolError::InvalidValidatorStakeList => msg!("Error: Invalid validator stake list account"),
+            StakePoolError::InvalidFeeAccount => msg!("Error: Invalid manager fee account"),
+            StakePoolError::WrongPoolMint => msg!("Error: Specified pool mint account is wrong"),
+            StakePoolError::WrongStakeStake => msg!("Error: Stake account is not in the state expected by the program"),
+            StakePoolError::UserStakeNotActive => msg!("Error: User stake is not active"),
+            StakePoolError::ValidatorAlreadyAdded => msg!("Error: Stake account voting for this validator already exists in the pool"),
+            StakePoolError::ValidatorNotFound => msg!("Error: Stake account for this validator not found in the pool"),
+            StakePoolError::InvalidStakeAccountAddress => msg!("Error: Stake account address not properly derived from the validator address"),
+            StakePoolError::StakeListOutOfDate => msg!("Error: Identify validator stake accounts with old balances and update them"),
+            StakePoolError::StakeListAndPoolOutOfDate => msg!("Error: First update old validator stake account balances and then pool stake balance"),
+            StakePoolError::UnknownValidatorStakeAccount => {
+                msg!("Error: Validator stake account is not found in the list storage")
+            }
+            StakePoolError::WrongMintingAuthority => msg!("Error: Wrong minting authority set for mint pool account"),
+            StakePoolError::UnexpectedValidatorListAccountSize=> msg!("Error: The size of the given validator stake list does match the expected amount"),
+            StakePoolError::WrongStaker=> msg!("Error: Wrong pool staker account"),
+            StakePoolError::NonZeroPoolTokenSupply => msg!("Error: Pool token supply is not zero on initialization"),
+            StakePoolError::StakeLamportsNotEqualToMinimum => msg!("Error: The lamports in the validator stake account is not equal to the minimum"),
+            StakePoolError::IncorrectDepositVoteAddress => msg!("Error: The provided deposit stake account is not delegated to the preferred deposit vote account"),
+            StakePoolError::IncorrectWithdrawVoteAddress => msg!("Error: The provided withdraw stake account is not the preferred deposit vote account"),
+            StakePoolError::InvalidMintFreezeAuthority => msg!("Error: The mint has an invalid freeze authority"),
+            StakePoolError::FeeIncreaseTooHigh => msg!("Error: The fee cannot increase by a factor exceeding the stipulated ratio"),
+            StakePoolError::WithdrawalTooSmall => msg!("Error: Not enough pool tokens provided to withdraw 1-lamport stake"),
+            StakePoolError::DepositTooSmall => msg!("Error: Not enough lamports provided for deposit to result in one pool token"),
+            StakePoolError::InvalidStakeDepositAuthority => msg!("Error: Provided stake deposit authority does not match the program's"),
+            StakePoolError::InvalidSolDepositAuthority => msg!("Error: Provided sol deposit authority does not match the program's"),
+            StakePoolError::InvalidPreferredValidator => msg!("Error: Provided preferred validator is invalid"),
+            StakePoolError::TransientAccountInUse => msg!("Error: Provided validator stake account already has a transient stake account in use"),
+            StakePoolError::InvalidSolWithdrawAuthority => msg!("Error: Provided sol withdraw authority does not match the program's"),
+            StakePoolError::SolWithdrawalTooLarge => msg!("Error: Too much SOL withdrawn from the stake pool's reserve account"),
+            StakePoolError::InvalidMetadataAccount => msg!("Error: Metadata account derived from pool mint account does not match the one passed to program"),
+            StakePoolError::UnsupportedMintExtension => msg!("Error: mint has an unsupported extension"),
+            StakePoolError::UnsupportedFeeAccountExtension => msg!("Error: fee account has an unsupported extension"),
+            StakePoolError::ExceededSlippage => msg!("Error: instruction exceeds desired slippage limit"),
+            StakePoolError::IncorrectMintDecimals => msg!("Error: Provided mint does not have 9 decimals to match SOL"),
+            StakePoolError::ReserveDepleted => msg!("Error: Pool reserve does not have enough lamports to fund rent-exempt reserve in split destination. Deposit more SOL in reserve, or pre-fund split destination with the rent-exempt reserve for a stake account."),
+            StakePoolError::MissingRequiredSysvar => msg!("Missing required sysvar account"),
+            StakePoolError::EpochRewardDistributionInProgress => msg!("Epoch reward distribution is currently in progress, stakes are still being updated"),
+            StakePoolError::TooManyValidatorsInPool => msg!("The stake pool has too many validators in the pool"),
+            StakePoolError::InvalidWithdrawalTicket => msg!("Error: The withdrawal ticket account is invalid or belongs to another pool"),
+            StakePoolError::WithdrawalTicketNotClaimable => msg!("Error: The withdrawal ticket cannot be claimed before its cooldown epoch"),
+            StakePoolError::InsufficientWithdrawalClaimPool => msg!("Error: The withdrawal claim pool does not have enough lamports to pay out the ticket"),
+            StakePoolError::DepositCapExceeded => msg!("Error: Deposit would exceed the pool's per-epoch deposit cap"),
+            StakePoolError::SolWithdrawalCapExceeded => msg!("Error: SOL withdrawal would exceed the pool's per-epoch SOL withdrawal cap"),
+            StakePoolError::TotalLamportsCapExceeded => msg!("Error: Deposit would push the pool's total lamports above its cap"),
+            StakePoolError::OperationPaused => msg!("Error: The operation is currently paused on this stake pool"),
+            StakePoolError::WrongPauser => msg!("Error: Wrong pauser account"),
+            StakePoolError::WrongPendingAuthority => msg!("Error: Signer is not the pending manager or staker being handed control"),
+            StakePoolError::FeeChangeDelayTooLong => msg!("Error: The fee change delay is longer than the maximum allowed"),
+            StakePoolError::InvalidReferralCode => msg!("Error: The referral code is empty, too long, or contains invalid characters"),
+            StakePoolError::InvalidReferrer => msg!("Error: The referrer account does not belong to the stake pool or does not pay out to the referral fee account"),
+            StakePoolError::DirectStakeShareExceeded => msg!("Error: Validator-targeted deposits are off, or the deposit would take the validator above its allowed share of the pool"),
+            StakePoolError::InvalidDirectStake => msg!("Error: The direct stake account does not belong to the stake pool and validator"),
+            StakePoolError::ValidatorShareExceeded => msg!("Error: The stake increase or deposit would take the validator above its allowed share of the pool"),
+            StakePoolError::InvalidValidatorMetadata => msg!("Error: The validator metadata account does not belong to the stake pool and validator"),
+            StakePoolError::CommissionTooHigh => msg!("Error: The validator's commission is above the maximum allowed by the pool"),
+            StakePoolError::CommissionWithinLimit => msg!("Error: The validator's commission is within the maximum allowed"),
+            StakePoolError::ValidatorNotDelinquent => msg!("Error: The validator voted too recently to be deactivated as delinquent"),
+            StakePoolError::InvalidSolWithdrawalFeeCurve => msg!("Error: The SOL withdrawal fee curve has a target above 100% or an exponent out of range"),
+            StakePoolError::InvalidFeeSplit => msg!("Error: The fee split is invalid, or its accounts are missing or do not match the stake pool's fee split"),
+            StakePoolError::InvalidInsuranceFund => msg!("Error: The insurance fund accounts are missing or do not match the stake pool's insurance fund, or the insurance fee is above 100%"),
+            StakePoolError::InsuranceInjectionTooLarge => msg!("Error: The insurance fund injection is above the uncovered losses or the fund's balance"),
+            StakePoolError::InvalidRateHistory => msg!("Error: The exchange rate history account does not belong to the stake pool"),
+            StakePoolError::InvalidOracle => msg!("Error: The price oracle account does not belong to the stake pool"),
+            StakePoolError::InvalidMetadataPointer => msg!("Error: The pool mint is not a Token-2022 mint whose metadata pointer points to the mint itself"),
+            StakePoolError::StakePoolNeedsMigration => msg!("Error: The stake pool account has an earlier layout and must be migrated"),
+        }
+    }
+}
@@ -0,0 +1,487 @@
+//! Events logged by the Fogo Stake Pool program.
+//!
+//! Every state-changing instruction logs a Borsh-encoded [`StakePoolEvent`]
+//! through `sol_log_data`, which shows up in the transaction logs as a
+//! `Program data: <base64>` line. [`parse_events`] turns the log lines of a
+//! transaction into typed events.
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+#[cfg(feature = "codama")]
+use codama_macros::CodamaType;
+
+use {
+    crate::{
+        instruction::PreferredValidatorType,
+        state::{CapType, FeeSplitRecipient, FeeType},
+    },
+    solana_program::pubkey::Pubkey,
+};
+
+/// Authority of the stake pool changed in `StakePoolEvent::AuthorityChanged`
+/// or proposed in `StakePoolEvent::AuthorityProposed`
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum AuthorityType {
+    /// Manager of the stake pool
+    Manager,
+    /// Staker of the stake pool
+    Staker,
+    /// Pauser of the stake pool
+    Pauser,
+    /// Stake deposit authority
+    StakeDeposit,
+    /// SOL deposit authority
+    SolDeposit,
+    /// SOL withdraw authority
+    SolWithdraw,
+}
+
+/// Event logged by the program as a single Borsh-encoded `sol_log_data` field
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum StakePoolEvent {
+    /// Stake, and any extra lamports it held, deposited into a validator
+    DepositStake {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator receiving the stake
+        vote_account: Pubkey,
+        /// Lamports deposited, including the stake account's extra lamports
+        lamports: u64,
+        /// Pool tokens minted to the depositor
+        pool_tokens: u64,
+        /// Pool tokens minted to the manager as deposit fee
+        manager_fee_pool_tokens: u64,
+        /// Pool tokens minted to the referrer as referral fee
+        referral_fee_pool_tokens: u64,
+    },
+    /// SOL deposited into the reserve
+    DepositSol {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Lamports deposited
+        lamports: u64,
+        /// Pool tokens minted to the depositor
+        pool_tokens: u64,
+        /// Pool tokens minted to the manager as deposit fee
+        manager_fee_pool_tokens: u64,
+        /// Pool tokens minted to the referrer as referral fee
+        referral_fee_pool_tokens: u64,
+    },
+    /// Stake split off a validator or the reserve by burning pool tokens
+    WithdrawStake {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator withdrawn from, `None` for the
+        /// reserve
+        vote_account: Option<Pubkey>,
+        /// Pool tokens burned
+        pool_tokens: u64,
+        /// Pool tokens transferred to the manager as withdrawal fee
+        manager_fee_pool_tokens: u64,
+        /// Lamports withdrawn
+        lamports: u64,
+    },
+    /// SOL withdrawn from the reserve by burning pool tokens
+    WithdrawSol {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Pool tokens burned
+        pool_tokens: u64,
+        /// Pool tokens transferred to the manager as withdrawal fee
+        manager_fee_pool_tokens: u64,
+        /// Lamports withdrawn
+        lamports: u64,
+    },
+    /// Withdrawal ticket requested by burning pool tokens
+    WithdrawalTicketRequested {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Withdrawal ticket
+        ticket: Pubkey,
+        /// Pool tokens burned
+        pool_tokens: u64,
+        /// Pool tokens transferred to the manager as withdrawal fee
+        manager_fee_pool_tokens: u64,
+        /// Lamports owed on the ticket
+        lamports: u64,
+    },
+    /// Withdrawal ticket claimed
+    WithdrawalTicketClaimed {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Withdrawal ticket
+        ticket: Pubkey,
+        /// Lamports paid out
+        lamports: u64,
+    },
+    /// Stake pool balance updated for the epoch
+    UpdateStakePoolBalance {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Epoch of the update
+        epoch: u64,
+        /// Total lamports in the pool after the update
+        total_lamports: u64,
+        /// Pool token supply after the update, including the epoch fee
+        pool_token_supply: u64,
+        /// Pool tokens minted to the manager as epoch fee
+        epoch_fee_pool_tokens: u64,
+    },
+    /// Validator added to the pool
+    ValidatorAdded {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator
+        vote_account: Pubkey,
+    },
+    /// Validator removed from the pool
+    ValidatorRemoved {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator
+        vote_account: Pubkey,
+    },
+    /// Stake moved from the reserve to a validator
+    ValidatorStakeIncreased {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator
+        vote_account: Pubkey,
+        /// Lamports activating on the validator
+        lamports: u64,
+    },
+    /// Stake moved from a validator to the reserve
+    ValidatorStakeDecreased {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator
+        vote_account: Pubkey,
+        /// Lamports deactivating from the validator
+        lamports: u64,
+    },
+    /// Authority of the stake pool changed
+    AuthorityChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Authority changed
+        authority_type: AuthorityType,
+        /// New authority, `None` if it was removed
+        new_authority: Option<Pubkey>,
+    },
+    /// SOL deposited into the reserve on behalf of a validator, following
+    /// the `DepositSol` event of the deposit
+    DirectStakeDeposited {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator the deposit was made for
+        vote_account: Pubkey,
+        /// Lamports deposited
+        lamports: u64,
+        /// Lamports the staker has yet to delegate to the validator
+        pending_lamports: u64,
+    },
+    /// Validator stake account found holding fewer lamports than at its last
+    /// update
+    ValidatorStakeLoss {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator
+        vote_account: Pubkey,
+        /// Epoch of the update
+        epoch: u64,
+        /// Lamports lost
+        lamports: u64,
+    },
+    /// Share of the epoch fee paid from the reserve into the insurance fund
+    InsuranceFundDeposited {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Epoch of the update
+        epoch: u64,
+        /// Lamports paid into the fund
+        lamports: u64,
+    },
+    /// Insurance fund lamports moved into the reserve to cover losses
+    InsuranceFundInjected {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Lamports injected
+        lamports: u64,
+        /// Losses left uncovered after the injection
+        uncovered_loss_lamports: u64,
+    },
+    /// Authority proposed for a two-step handover, to take over once it
+    /// accepts
+    AuthorityProposed {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Authority proposed
+        authority_type: AuthorityType,
+        /// Proposed authority, `None` if the proposal was withdrawn
+        proposed_authority: Option<Pubkey>,
+    },
+    /// Operations paused by the pauser or the manager
+    PausedOperationsChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Bitmask of the `PausedOperation`s paused from now on
+        paused_operations: u8,
+    },
+    /// Fee set by the manager, in effect at once for a pool without
+    /// depositors and after the fee change delay otherwise
+    FeeChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// New fee
+        fee: FeeType,
+    },
+    /// Number of epochs fee changes are held back for set by the manager
+    FeeChangeDelayChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// New delay, in epochs
+        delay_epochs: u64,
+    },
+    /// Cap set by the manager
+    CapChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// New cap
+        cap: CapType,
+    },
+    /// Maximum share of the pool of a single validator set by the manager
+    ValidatorMaxShareChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator
+        vote_account: Pubkey,
+        /// New maximum share, in basis points, `None` if it was removed
+        max_share_bps: Option<u16>,
+    },
+    /// Preferred deposit or withdrawal validator set by the staker
+    PreferredValidatorChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Whether the deposit or the withdrawal validator was set
+        validator_type: PreferredValidatorType,
+        /// Vote account of the new preferred validator, `None` if it was
+        /// removed
+        vote_account: Option<Pubkey>,
+    },
+    /// Recipients of the manager's fees set by the manager
+    FeeSplitChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// New recipients, empty if the fee split was turned off
+        recipients: Vec<FeeSplitRecipient>,
+    },
+    /// Share of the epoch fee paid into the insurance fund set by the manager
+    InsuranceFeeChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// New share of the epoch fee, in basis points
+        insurance_fee_bps: u16,
+    },
+    /// Notes and maximum commission of a validator set by the staker
+    ValidatorMetadataChanged {
+        /// Stake pool
+        stake_pool: Pubkey,
+        /// Vote account of the validator
+        vote_account: Pubkey,
+        /// Hash of the staker's off-chain notes on the validator
+        notes_hash: [u8; 32],
+        /// New maximum commission, as a percentage
+        max_commission: u8,
+    },
+}
+
+#[cfg(feature = "borsh")]
+impl StakePoolEvent {
+    /// Logs the event through `sol_log_data`, as the program does
+    pub fn emit(&self) {
+        if let Ok(data) = borsh::to_vec(self) {
+            solana_program::log::sol_log_data(&[&data]);
+        }
+    }
+}
+
+#[cfg(all(feature = "borsh", not(target_os = "solana")))]
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+#[cfg(all(feature = "borsh", not(target_os = "solana")))]
+impl StakePoolEvent {
+    /// Decodes the event from the data of a `Program data:` log line
+    pub fn from_log_data(data: &[u8]) -> Option<Self> {
+        borsh::from_slice(data).ok()
+    }
+}
+
+/// Decodes the events logged by the stake pool program `program_id` from the
+/// log lines of a transaction, in order. `Program data:` lines logged by other
+/// programs, including ones invoked by the stake pool program, are skipped.
+#[cfg(all(feature = "borsh", not(target_os = "solana")))]
+pub fn parse_events(program_id: &Pubkey, logs: &[String]) -> Vec<StakePoolEvent> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let program_id = program_id.to_string();
+    let mut invoke_stack: Vec<&str> = vec![];
+    let mut events = vec![];
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if invoke_stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let event = STANDARD
+                .decode(data)
+                .ok()
+                .and_then(|data| StakePoolEvent::from_log_data(&data));
+            events.extend(event);
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let (Some(program), Some(status)) = (words.next(), words.next()) else {
+                continue;
+            };
+            // `Program log:`, `Program return:` and the like
+            if program.ends_with(':') {
+                continue;
+            }
+            match status {
+                "invoke" => invoke_stack.push(program),
+                "success" | "failed:" => {
+                    invoke_stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+#[cfg(all(test, feature = "borsh"))]
+mod tests {
+    use {
+        super::*,
+        base64::{engine::general_purpose::STANDARD, Engine},
+    };
+
+    fn program_data(event: &StakePoolEvent) -> String {
+        format!(
+            "{}{}",
+            PROGRAM_DATA_PREFIX,
+            STANDARD.encode(borsh::to_vec(event).unwrap())
+        )
+    }
+
+    fn event(stake_pool: &Pubkey, paused_operations: u8) -> StakePoolEvent {
+        StakePoolEvent::PausedOperationsChanged {
+            stake_pool: *stake_pool,
+            paused_operations,
+        }
+    }
+
+    #[test]
+    fn from_log_data() {
+        let stake_pool = Pubkey::new_unique();
+        let data = borsh::to_vec(&event(&stake_pool, 1)).unwrap();
+        assert_eq!(
+            StakePoolEvent::from_log_data(&data),
+            Some(event(&stake_pool, 1))
+        );
+        assert_eq!(StakePoolEvent::from_log_data(&[u8::MAX]), None);
+        // trailing bytes are not part of an event
+        assert_eq!(
+            StakePoolEvent::from_log_data(&[data, vec![0]].concat()),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_events_skips_nested_programs() {
+        let program_id = Pubkey::new_unique();
+        let token_program_id = Pubkey::new_unique();
+        let stake_pool = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            format!("Program {} invoke [2]", token_program_id),
+            program_data(&event(&stake_pool, 1)),
+            format!("Program {} success", token_program_id),
+            program_data(&event(&stake_pool, 2)),
+            format!(
+                "Program {} consumed 1000 of 200000 compute units",
+                program_id
+            ),
+            format!("Program {} success", program_id),
+        ];
+        assert_eq!(
+            parse_events(&program_id, &logs),
+            vec![event(&stake_pool, 2)]
+        );
+    }
+
+    #[test]
+    fn parse_events_pops_failed_invocations() {
+        let program_id = Pubkey::new_unique();
+        let other_program_id = Pubkey::new_unique();
+        let stake_pool = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", other_program_id),
+            format!("Program {} invoke [2]", program_id),
+            program_data(&event(&stake_pool, 1)),
+            format!("Program {} failed: custom program error: 0x0", program_id),
+            // back in the calling program, which logs data of its own
+            program_data(&event(&stake_pool, 2)),
+            format!("Program {} success", other_program_id),
+            format!("Program {} invoke [1]", program_id),
+            program_data(&event(&stake_pool, 3)),
+            format!("Program {} success", program_id),
+        ];
+        assert_eq!(
+            parse_events(&program_id, &logs),
+            vec![event(&stake_pool, 1), event(&stake_pool, 3)]
+        );
+    }
+
+    #[test]
+    fn parse_events_skips_undecodable_data() {
+        let program_id = Pubkey::new_unique();
+        let stake_pool = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Instruction: SetPaused".to_string(),
+            format!("{}not base64!", PROGRAM_DATA_PREFIX),
+            format!(
+                "{}{}",
+                PROGRAM_DATA_PREFIX,
+                STANDARD.encode([u8::MAX, 1, 2])
+            ),
+            // several fields in one `sol_log_data` call are not an event
+            format!(
+                "{}{} {}",
+                PROGRAM_DATA_PREFIX,
+                STANDARD.encode([0]),
+                STANDARD.encode([1])
+            ),
+            program_data(&event(&stake_pool, 1)),
+            "Program return: Sysvar1111111111111111111111111111111111111 AQ==".to_string(),
+            format!("Program {} success", program_id),
+            // data logged outside of any invocation
+            program_data(&event(&stake_pool, 2)),
+        ];
+        assert_eq!(
+            parse_events(&program_id, &logs),
+            vec![event(&stake_pool, 1)]
+        );
+    }
+}
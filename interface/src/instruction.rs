@@ -0,0 +1,7423 @@
+//! Instruction types for the Fogo Stake Pool program.
+//!
+//! This module mirrors the instruction enum of the on-chain program so that
+//! the Codama IDL (and the clients generated from it) can describe every
+//! instruction, its accounts and its arguments.
+//!
+//! The instruction builders mirror the ones in the program crate, so on-chain
+//! integrators can CPI into the pool without depending on the program itself.
+
+// Required to avoid warnings from the deprecated `solana_program` modules and
+// the deprecated builders kept for compatibility.
+#![allow(deprecated)]
+#![allow(clippy::too_many_arguments)]
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+#[cfg(feature = "codama")]
+use codama_macros::{CodamaInstructions, CodamaType};
+
+use {
+    crate::state::{CapType, Fee, FeeType},
+    solana_program::pubkey::Pubkey,
+};
+#[cfg(feature = "borsh")]
+use {
+    crate::{
+        find_deposit_authority_program_address, find_direct_stake_program_address,
+        find_ephemeral_stake_program_address, find_fee_split_program_address,
+        find_insurance_fund_program_address, find_oracle_program_address,
+        find_rate_history_program_address, find_stake_program_address,
+        find_transient_stake_program_address, find_user_stake_program_address,
+        find_validator_metadata_program_address, find_withdraw_authority_program_address,
+        state::{StakePool, ValidatorList, ValidatorStakeInfo},
+        MAX_VALIDATORS_TO_UPDATE,
+    },
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey, stake,
+        stake_history::Epoch,
+        system_program, sysvar,
+    },
+    std::num::NonZeroU32,
+};
+
+/// Wrapped SOL mint, used by the wSOL session instructions
+#[cfg(feature = "borsh")]
+const NATIVE_MINT_ID: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+/// Associated token account program, used by the wSOL session instructions
+#[cfg(feature = "borsh")]
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Metaplex token metadata program, used by the token metadata instructions
+#[cfg(feature = "borsh")]
+const MPL_TOKEN_METADATA_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Finds the Metaplex metadata account for the given mint
+#[cfg(feature = "borsh")]
+fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", MPL_TOKEN_METADATA_ID.as_ref(), mint.as_ref()],
+        &MPL_TOKEN_METADATA_ID,
+    )
+}
+
+/// Defines which validator vote account is set during the
+/// `SetPreferredValidator` instruction
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum PreferredValidatorType {
+    /// Set preferred validator for deposits
+    Deposit,
+    /// Set preferred validator for withdraws
+    Withdraw,
+}
+
+/// Defines which authority to update in the `SetFundingAuthority`
+/// instruction
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum FundingType {
+    /// Sets the stake deposit authority
+    StakeDeposit,
+    /// Sets the SOL deposit authority
+    SolDeposit,
+    /// Sets the SOL withdraw authority
+    SolWithdraw,
+}
+
+/// Instructions supported by the `StakePool` program.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+#[cfg_attr(feature = "codama", derive(CodamaInstructions))]
+pub enum StakePoolInstruction {
+    ///   Initializes a new `StakePool`.
+    ///
+    ///   0. `[w]` New `StakePool` to create.
+    ///   1. `[s]` Manager
+    ///   2. `[]` Staker
+    ///   3. `[]` Stake pool withdraw authority
+    ///   4. `[w]` Uninitialized validator stake list storage account
+    ///   5. `[]` Reserve stake account must be initialized, have zero balance,
+    ///      and staker / withdrawer authority set to pool withdraw authority.
+    ///   6. `[]` Pool token mint. Must have zero supply, owned by withdraw
+    ///      authority.
+    ///   7. `[]` Pool account to deposit the generated fee for manager.
+    ///   8. `[]` Token program id
+    ///   9. `[]` (Optional) Deposit authority that must sign all deposits.
+    ///      Defaults to the program address generated using
+    ///      `find_deposit_authority_program_address`, making deposits
+    ///      permissionless.
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "New stake pool to create"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "staker", docs = "Staker")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Uninitialized validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", docs = "Reserve stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Pool account to deposit the generated fee for manager"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "deposit_authority",
+            signer,
+            optional,
+            docs = "Deposit authority that must sign all deposits"
+        ))
+    )]
+    Initialize {
+        /// Fee assessed as percentage of perceived rewards
+        fee: Fee,
+        /// Fee charged per withdrawal as percentage of withdrawal
+        withdrawal_fee: Fee,
+        /// Fee charged per deposit as percentage of deposit
+        deposit_fee: Fee,
+        /// Percentage [0-100] of `deposit_fee` that goes to referrer
+        referral_fee: u8,
+        /// Maximum expected number of validators
+        max_validators: u32,
+    },
+
+    ///   (Staker only) Adds stake account delegated to validator to the pool's
+    ///   list of managed validators.
+    ///
+    ///   The stake account will have the rent-exempt amount plus
+    ///   `max(
+    ///     crate::MINIMUM_ACTIVE_STAKE,
+    ///     solana_program::stake::tools::get_minimum_delegation()
+    ///   )`.
+    ///   It is funded from the stake pool reserve.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` Staker
+    ///   2. `[w]` Reserve stake account
+    ///   3. `[]` Stake pool withdraw authority
+    ///   4. `[w]` Validator stake list storage account
+    ///   5. `[w]` Stake account to add to the pool
+    ///   6. `[]` Validator this stake account will be delegated to
+    ///   7. `[]` Rent sysvar
+    ///   8. `[]` Clock sysvar
+    ///   9. '[]' Stake history sysvar
+    ///  10. '[]' Stake config sysvar
+    ///  11. `[]` System program
+    ///  12. `[]` Stake program
+    ///  13. `[w]` (Optional) Validator metadata account (must be the validator
+    ///      metadata PDA), created if it does not exist yet
+    ///  14. `[ws]` (Optional) Account paying the rent of a new validator
+    ///      metadata account
+    ///
+    ///  If the pool has a maximum validator commission, the validator's
+    ///  commission must be within it.
+    ///
+    ///  User data: optional non-zero `u32` seed used for generating the
+    ///  validator stake address
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Reserve stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Stake account to add to the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator",
+            docs = "Validator this stake account will be delegated to"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "rent", default_value = sysvar("rent"), docs = "Rent sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_config", default_value = public_key("StakeConfig11111111111111111111111111111111"), docs = "Stake config sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_metadata",
+            writable,
+            optional,
+            docs = "Validator metadata account (must be the validator metadata PDA), created if it does not exist yet"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "rent_payer",
+            writable,
+            signer,
+            optional,
+            docs = "Account paying the rent of a new validator metadata account"
+        ))
+    )]
+    AddValidatorToPool(#[cfg_attr(feature = "codama", codama(name = "raw_validator_seed"))] u32),
+
+    ///   (Staker only) Removes validator from the pool, deactivating its stake
+    ///
+    ///   Only succeeds if the validator stake account has the minimum of
+    ///   `max(crate::MINIMUM_ACTIVE_STAKE,
+    /// solana_program::stake::tools::get_minimum_delegation())`.   plus the
+    /// rent-exempt amount.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` Staker
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Validator stake list storage account
+    ///   4. `[w]` Stake account to remove from the pool
+    ///   5. `[w]` Transient stake account, to deactivate if necessary
+    ///   6. `[]` Sysvar clock
+    ///   7. `[]` Stake program id,
+    ///   8. `[w]` (Optional) Validator metadata account, to record the removal
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Stake account to remove from the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "transient_stake",
+            writable,
+            docs = "Transient stake account, to deactivate if necessary"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_metadata",
+            writable,
+            docs = "Validator metadata account, to check the admission epoch and record the removal"
+        ))
+    )]
+    RemoveValidatorFromPool,
+
+    /// NOTE: This instruction has been deprecated since version 0.7.0. Please
+    /// use `DecreaseValidatorStakeWithReserve` instead.
+    ///
+    /// (Staker only) Decrease active stake on a validator, eventually moving it
+    /// to the reserve
+    ///
+    /// Internally, this instruction splits a validator stake account into its
+    /// corresponding transient stake account and deactivates it.
+    ///
+    /// In order to rebalance the pool without taking custody, the staker needs
+    /// a way of reducing the stake on a stake account. This instruction splits
+    /// some amount of stake, up to the total activated stake, from the
+    /// canonical validator stake account, into its "transient" stake
+    /// account.
+    ///
+    /// The instruction only succeeds if the transient stake account does not
+    /// exist. The amount of lamports to move must be at least rent-exemption
+    /// plus `max(crate::MINIMUM_ACTIVE_STAKE,
+    /// solana_program::stake::tools::get_minimum_delegation())`.
+    ///
+    ///  0. `[]` Stake pool
+    ///  1. `[s]` Stake pool staker
+    ///  2. `[]` Stake pool withdraw authority
+    ///  3. `[w]` Validator list
+    ///  4. `[w]` Canonical stake account to split from
+    ///  5. `[w]` Transient stake account to receive split
+    ///  6. `[]` Clock sysvar
+    ///  7. `[]` Rent sysvar
+    ///  8. `[]` System program
+    ///  9. `[]` Stake program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Stake pool staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", writable, docs = "Validator list"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Canonical stake account to split from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "transient_stake",
+            writable,
+            docs = "Transient stake account to receive split"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "rent", default_value = sysvar("rent"), docs = "Rent sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    DecreaseValidatorStake {
+        /// amount of lamports to split into the transient stake account
+        lamports: u64,
+        /// seed used to create transient stake account
+        transient_stake_seed: u64,
+    },
+
+    /// (Staker only) Increase stake on a validator from the reserve account
+    ///
+    /// Internally, this instruction splits reserve stake into a transient stake
+    /// account and delegate to the appropriate validator.
+    /// `UpdateValidatorListBalance` will do the work of merging once it's
+    /// ready.
+    ///
+    /// This instruction only succeeds if the transient stake account does not
+    /// exist. The minimum amount to move is rent-exemption plus
+    /// `max(crate::MINIMUM_ACTIVE_STAKE,
+    /// solana_program::stake::tools::get_minimum_delegation())`.
+    ///
+    ///  0. `[]` Stake pool
+    ///  1. `[s]` Stake pool staker
+    ///  2. `[]` Stake pool withdraw authority
+    ///  3. `[w]` Validator list
+    ///  4. `[w]` Stake pool reserve stake
+    ///  5. `[w]` Transient stake account
+    ///  6. `[]` Validator stake account
+    ///  7. `[]` Validator vote account to delegate to
+    ///  8. '[]' Clock sysvar
+    ///  9. '[]' Rent sysvar
+    /// 10. `[]` Stake History sysvar
+    /// 11. `[]` Stake Config sysvar
+    /// 12. `[]` System program
+    /// 13. `[]` Stake program
+    /// 14. `[w]` (Optional) Direct stake account of the validator, whose
+    ///     pending lamports are reduced by the increase
+    ///
+    /// User data: amount of lamports to increase on the given validator.
+    ///
+    /// The actual amount split into the transient stake account is:
+    /// `lamports + stake_rent_exemption`.
+    ///
+    /// The rent-exemption of the stake account is withdrawn back to the
+    /// reserve after it is merged.
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Stake pool staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", writable, docs = "Validator list"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Stake pool reserve stake"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "transient_stake", writable, docs = "Transient stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_stake", docs = "Validator stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator", docs = "Validator vote account to delegate to"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "rent", default_value = sysvar("rent"), docs = "Rent sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_config", default_value = public_key("StakeConfig11111111111111111111111111111111"), docs = "Stake config sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "direct_stake",
+            writable,
+            optional,
+            docs = "Direct stake account of the validator, whose pending lamports are reduced by the increase"
+        ))
+    )]
+    IncreaseValidatorStake {
+        /// amount of lamports to increase on the given validator
+        lamports: u64,
+        /// seed used to create transient stake account
+        transient_stake_seed: u64,
+    },
+
+    /// (Staker only) Set the preferred deposit or withdraw stake account for
+    /// the stake pool
+    ///
+    /// In order to avoid users abusing the stake pool as a free conversion
+    /// between SOL staked on different validators, the staker can force all
+    /// deposits and/or withdraws to go to one chosen account, or unset that
+    /// account.
+    ///
+    /// 0. `[w]` Stake pool
+    /// 1. `[s]` Stake pool staker
+    /// 2. `[]` Validator list
+    ///
+    /// Fails if the validator is not part of the stake pool.
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Stake pool staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", docs = "Validator list"))
+    )]
+    SetPreferredValidator {
+        /// Affected operation (deposit or withdraw)
+        validator_type: PreferredValidatorType,
+        /// Validator vote account that deposits or withdraws must go through,
+        /// unset with None
+        validator_vote_address: Option<Pubkey>,
+    },
+
+    ///  Updates balances of validator and transient stake accounts in the pool
+    ///
+    ///  While going through the pairs of validator and transient stake
+    ///  accounts, if the transient stake is inactive, it is merged into the
+    ///  reserve stake account. If the transient stake is active and has
+    ///  matching credits observed, it is merged into the canonical
+    ///  validator stake account. In all other states, nothing is done, and
+    ///  the balance is simply added to the canonical stake account balance.
+    ///
+    ///  A validator stake account holding fewer lamports than at its last
+    ///  update is reported as a loss, and added to the pool's uncovered
+    ///  losses. The instruction fails if it finds a loss while the stake
+    ///  pool is read-only.
+    ///
+    ///  0. `[w]` Stake pool, only written to record losses
+    ///  1. `[]` Stake pool withdraw authority
+    ///  2. `[w]` Validator stake list storage account
+    ///  3. `[w]` Reserve stake account
+    ///  4. `[]` Sysvar clock
+    ///  5. `[]` Sysvar stake history
+    ///  6. `[]` Stake program
+    ///  7. `..7+2N` [] N pairs of validator and transient stake accounts
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_pool",
+            writable,
+            docs = "Stake pool, only written to record losses"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Reserve stake account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    UpdateValidatorListBalance {
+        /// Index to start updating on the validator list
+        start_index: u32,
+        /// If true, don't try merging transient stake accounts into the reserve
+        /// or validator stake account.  Useful for testing or if a
+        /// particular stake account is in a bad state, but we still
+        /// want to update
+        no_merge: bool,
+    },
+
+    ///   Updates total pool balance based on balances in the reserve and
+    ///   validator list
+    ///
+    ///   The insurance fund's share of the epoch fee is excluded from the pool
+    ///   right away and accrues in the stake pool's
+    ///   `pending_insurance_lamports`. When the insurance fund accounts are
+    ///   passed, the pending share is withdrawn from the reserve into the
+    ///   insurance fund, as far as the reserve can spare it.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Validator stake list storage account
+    ///   3. `[w]` Reserve stake account
+    ///   4. `[w]` Account to receive pool fee tokens
+    ///   5. `[w]` Pool mint account
+    ///   6. `[]` Pool token program
+    ///   7. `[w]` (Optional) Insurance fund account, followed by the clock
+    ///      sysvar, stake history sysvar and stake program, used to pay out
+    ///      the pending insurance share. The sysvars are only checked by the
+    ///      stake program's withdraw.
+    ///   8. `[w]` (Optional) Exchange rate history account, recording the
+    ///      exchange rate of the epoch when passed
+    ///   9. `[w]` (Optional) Price oracle account, refreshed with the new
+    ///      exchange rate when passed
+    ///   10. `[]` (Optional) Fee split account, followed by its writable
+    ///       recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Reserve stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool mint account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "insurance_fund",
+            writable,
+            optional,
+            docs = "Insurance fund account, followed by the clock sysvar, stake history sysvar and stake program, used to pay out the pending insurance share"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "rate_history",
+            writable,
+            optional,
+            docs = "Exchange rate history account, recording the exchange rate of the epoch when passed"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "oracle",
+            writable,
+            optional,
+            docs = "Price oracle account, refreshed with the new exchange rate when passed"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    UpdateStakePoolBalance,
+
+    ///   Cleans up validator stake account entries marked as `ReadyForRemoval`
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    CleanupRemovedValidatorEntries,
+
+    ///   Deposit some stake into the pool. The output is a "pool" token
+    ///   representing ownership into the pool. Inputs are converted to the
+    ///   current ratio.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    ///   2. `[s]/[]` Stake pool deposit authority
+    ///   3. `[]` Stake pool withdraw authority
+    ///   4. `[w]` Stake account to join the pool (withdraw authority for the
+    ///      stake account should be first set to the stake pool deposit
+    ///      authority)
+    ///   5. `[w]` Validator stake account for the stake account to be merged
+    ///      with
+    ///   6. `[w]` Reserve stake account, to withdraw rent exempt reserve
+    ///   7. `[w]` User account to receive pool tokens
+    ///   8. `[w]` Account to receive pool fee tokens
+    ///   9. `[w]` Account to receive a portion of pool fee tokens as referral
+    ///      fees
+    ///   10. `[w]` Pool token mint account
+    ///   11. '[]' Sysvar clock account
+    ///   12. '[]' Sysvar stake history account
+    ///   13. `[]` Pool token program id,
+    ///   14. `[]` Stake program id,
+    ///   15. `[w]` (Optional) Referrer account, whose payout account must be
+    ///       the referral fee account
+    ///   16. `[]` (Optional) Fee split account, followed by its writable
+    ///       recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "deposit_authority",
+            signer = "either",
+            docs = "Stake pool deposit authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "deposit_stake",
+            writable,
+            docs = "Stake account to join the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Validator stake account for the stake account to be merged with"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to withdraw rent exempt reserve"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_to",
+            writable,
+            docs = "User account to receive pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer_pool_tokens_account",
+            writable,
+            docs = "Account to receive a portion of pool fee tokens as referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer",
+            writable,
+            optional,
+            docs = "Referrer account, whose payout account must be the referral fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    DepositStake,
+
+    ///   Withdraw the token from the pool at the current ratio.
+    ///
+    ///   Succeeds if the stake account has enough SOL to cover the desired
+    ///   amount of pool tokens, and if the withdrawal keeps the total
+    ///   staked amount above the minimum of rent-exempt amount plus `max(
+    ///     crate::MINIMUM_ACTIVE_STAKE,
+    ///     solana_program::stake::tools::get_minimum_delegation()
+    ///   )`.
+    ///
+    ///   When allowing withdrawals, the order of priority goes:
+    ///
+    ///   * preferred withdraw validator stake account (if set)
+    ///   * validator stake accounts
+    ///   * transient stake accounts
+    ///   * reserve stake account OR totally remove validator stake accounts
+    ///
+    ///   A user can freely withdraw from a validator stake account, and if they
+    ///   are all at the minimum, then they can withdraw from transient stake
+    ///   accounts, and if they are all at minimum, then they can withdraw from
+    ///   the reserve or remove any validator from the pool.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Validator or reserve stake account to split
+    ///   4. `[w]` Uninitialized stake account to receive withdrawal
+    ///   5. `[]` User account to set as a new withdraw authority
+    ///   6. `[s]` User transfer authority, for pool token account
+    ///   7. `[w]` User account with pool tokens to burn from
+    ///   8. `[w]` Account to receive pool fee tokens
+    ///   9. `[w]` Pool token mint account
+    ///  10. `[]` Sysvar clock account (required)
+    ///  11. `[]` Pool token program id
+    ///  12. `[]` Stake program id,
+    ///  13. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    ///
+    ///  User data: amount of pool tokens to withdraw
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_to_split",
+            writable,
+            docs = "Validator or reserve stake account to split"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_to_receive",
+            writable,
+            docs = "Uninitialized stake account to receive withdrawal"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_stake_authority",
+            docs = "User account to set as a new withdraw authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_transfer_authority",
+            signer,
+            docs = "User transfer authority, for pool token account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account with pool tokens to burn from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    WithdrawStake(#[cfg_attr(feature = "codama", codama(name = "pool_tokens_in"))] u64),
+
+    ///  (Manager only) Update manager
+    ///
+    ///  Only processed when the program is built with the
+    ///  `legacy-authority-transfer` feature, off by default, prefer `ProposeManager` and
+    ///  `AcceptManager`.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager
+    ///  2. `[s]` New manager
+    ///  3. `[]` New manager fee account
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_manager", signer, docs = "New manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_manager_fee_account", docs = "New manager fee account"))
+    )]
+    SetManager,
+
+    ///  (Manager only) Update fee
+    ///
+    ///  The new fee takes effect two epochs later, or later still while the
+    ///  pool's fee change delay holds it back. Deposit and referral fees are
+    ///  set immediately while the pool has no pool tokens.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    SetFee {
+        /// Type of fee to update and value to update it to
+        fee: FeeType,
+    },
+
+    ///  (Manager or staker only) Update staker
+    ///
+    ///  Only processed when the program is built with the
+    ///  `legacy-authority-transfer` feature, off by default, prefer `ProposeStaker` and
+    ///  `AcceptStaker`.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager or current staker
+    ///  2. '[]` New staker pubkey
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "set_staker_authority",
+            signer,
+            docs = "Manager or current staker"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_staker", docs = "New staker pubkey"))
+    )]
+    SetStaker,
+
+    ///   Deposit SOL directly into the pool's reserve account. The output is a
+    ///   "pool" token representing ownership into the pool. Inputs are
+    ///   converted to the current ratio.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Reserve stake account, to deposit SOL
+    ///   3. `[s]` Account providing the lamports to be deposited into the pool
+    ///   4. `[w]` User account to receive pool tokens
+    ///   5. `[w]` Account to receive fee tokens
+    ///   6. `[w]` Account to receive a portion of fee as referral fees
+    ///   7. `[w]` Pool token mint account
+    ///   8. `[]` System program account
+    ///   9. `[]` Token program id
+    ///  10. `[s]` (Optional) Stake pool sol deposit authority.
+    ///  11. `[w]` (Optional) Referrer account, whose payout account must be
+    ///      the referral fee account
+    ///  12. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to deposit SOL"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "lamports_from",
+            writable,
+            signer,
+            docs = "Account providing the lamports to be deposited into the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_to",
+            writable,
+            docs = "User account to receive pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer_pool_tokens_account",
+            writable,
+            docs = "Account to receive a portion of fee as referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_deposit_authority",
+            signer,
+            optional,
+            docs = "Stake pool sol deposit authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer",
+            writable,
+            optional,
+            docs = "Referrer account, whose payout account must be the referral fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    DepositSol(#[cfg_attr(feature = "codama", codama(name = "lamports_in"))] u64),
+
+    ///  (Manager only) Update SOL deposit, stake deposit, or SOL withdrawal
+    /// authority.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager
+    ///  2. '[]` New authority pubkey or none
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "new_funding_authority",
+            optional,
+            docs = "New authority pubkey or none"
+        ))
+    )]
+    SetFundingAuthority(#[cfg_attr(feature = "codama", codama(name = "funding_type"))] FundingType),
+
+    ///   Withdraw SOL directly from the pool's reserve account. Fails if the
+    ///   reserve does not have enough SOL.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[s]` User transfer authority, for pool token account
+    ///   3. `[w]` User account to burn pool tokens
+    ///   4. `[w]` Reserve stake account, to withdraw SOL
+    ///   5. `[w]` Account receiving the lamports from the reserve, must be a
+    ///      system account
+    ///   6. `[w]` Account to receive pool fee tokens
+    ///   7. `[w]` Pool token mint account
+    ///   8. '[]' Clock sysvar
+    ///   9. '[]' Stake history sysvar
+    ///  10. `[]` Stake program account
+    ///  11. `[]` Token program id
+    ///  12. `[s]` (Optional) Stake pool sol withdraw authority
+    ///  13. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_transfer_authority",
+            signer,
+            docs = "User transfer authority, for pool token account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account to burn pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to withdraw SOL"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "lamports_to",
+            writable,
+            docs = "Account receiving the lamports from the reserve, must be a system account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_withdraw_authority",
+            signer,
+            optional,
+            docs = "Stake pool sol withdraw authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    WithdrawSol(#[cfg_attr(feature = "codama", codama(name = "pool_tokens_in"))] u64),
+
+    /// Create token metadata for the stake-pool token in the
+    /// metaplex-token program
+    /// 0. `[]` Stake pool
+    /// 1. `[s]` Manager
+    /// 2. `[]` Stake pool withdraw authority
+    /// 3. `[]` Pool token mint account
+    /// 4. `[s, w]` Payer for creation of token metadata account
+    /// 5. `[w]` Token metadata account
+    /// 6. `[]` Metadata program id
+    /// 7. `[]` System program id
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for creation of token metadata account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_metadata", writable, docs = "Token metadata account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "mpl_token_metadata", default_value = public_key("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"), docs = "Metadata program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    CreateTokenMetadata {
+        /// Token name
+        name: String,
+        /// Token symbol e.g. `stkSOL`
+        symbol: String,
+        /// URI of the uploaded metadata of the spl-token
+        uri: String,
+    },
+    /// Update token metadata for the stake-pool token in the
+    /// metaplex-token program
+    ///
+    /// 0. `[]` Stake pool
+    /// 1. `[s]` Manager
+    /// 2. `[]` Stake pool withdraw authority
+    /// 3. `[w]` Token metadata account
+    /// 4. `[]` Metadata program id
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_metadata", writable, docs = "Token metadata account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "mpl_token_metadata", default_value = public_key("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"), docs = "Metadata program")))]
+    UpdateTokenMetadata {
+        /// Token name
+        name: String,
+        /// Token symbol e.g. `stkSOL`
+        symbol: String,
+        /// URI of the uploaded metadata of the spl-token
+        uri: String,
+    },
+
+    /// (Staker only) Increase stake on a validator again in an epoch.
+    ///
+    /// Works regardless if the transient stake account exists.
+    ///
+    /// Internally, this instruction splits reserve stake into an ephemeral
+    /// stake account, activates it, then merges or splits it into the
+    /// transient stake account delegated to the appropriate validator.
+    /// `UpdateValidatorListBalance` will do the work of merging once it's
+    /// ready.
+    ///
+    /// The minimum amount to move is rent-exemption plus
+    /// `max(crate::MINIMUM_ACTIVE_STAKE,
+    /// solana_program::stake::tools::get_minimum_delegation())`.
+    ///
+    ///  0. `[]` Stake pool
+    ///  1. `[s]` Stake pool staker
+    ///  2. `[]` Stake pool withdraw authority
+    ///  3. `[w]` Validator list
+    ///  4. `[w]` Stake pool reserve stake
+    ///  5. `[w]` Uninitialized ephemeral stake account to receive stake
+    ///  6. `[w]` Transient stake account
+    ///  7. `[]` Validator stake account
+    ///  8. `[]` Validator vote account to delegate to
+    ///  9. '[]' Clock sysvar
+    /// 10. `[]` Stake History sysvar
+    /// 11. `[]` Stake Config sysvar
+    /// 12. `[]` System program
+    /// 13. `[]` Stake program
+    /// 14. `[w]` (Optional) Direct stake account of the validator, whose
+    ///     pending lamports are reduced by the increase
+    ///
+    /// User data: amount of lamports to increase on the given validator.
+    ///
+    /// The actual amount split into the transient stake account is:
+    /// `lamports + stake_rent_exemption`.
+    ///
+    /// The rent-exemption of the stake account is withdrawn back to the
+    /// reserve after it is merged.
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Stake pool staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", writable, docs = "Validator list"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Stake pool reserve stake"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "ephemeral_stake",
+            writable,
+            docs = "Uninitialized ephemeral stake account to receive stake"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "transient_stake", writable, docs = "Transient stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_stake", docs = "Validator stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator", docs = "Validator vote account to delegate to"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_config", default_value = public_key("StakeConfig11111111111111111111111111111111"), docs = "Stake config sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "direct_stake",
+            writable,
+            optional,
+            docs = "Direct stake account of the validator, whose pending lamports are reduced by the increase"
+        ))
+    )]
+    IncreaseAdditionalValidatorStake {
+        /// amount of lamports to increase on the given validator
+        lamports: u64,
+        /// seed used to create transient stake account
+        transient_stake_seed: u64,
+        /// seed used to create ephemeral account.
+        ephemeral_stake_seed: u64,
+    },
+
+    /// (Staker only) Decrease active stake again from a validator, eventually
+    /// moving it to the reserve
+    ///
+    /// Works regardless if the transient stake account already exists.
+    ///
+    /// Internally, this instruction:
+    ///  * withdraws rent-exempt reserve lamports from the reserve into the
+    ///    ephemeral stake
+    ///  * splits a validator stake account into an ephemeral stake account
+    ///  * deactivates the ephemeral account
+    ///  * merges or splits the ephemeral account into the transient stake
+    ///    account delegated to the appropriate validator
+    ///
+    ///  The amount of lamports to move must be at least
+    /// `max(crate::MINIMUM_ACTIVE_STAKE,
+    /// solana_program::stake::tools::get_minimum_delegation())`.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Stake pool staker
+    ///  2. `[]` Stake pool withdraw authority
+    ///  3. `[w]` Validator list
+    ///  4. `[w]` Reserve stake account, to fund rent exempt reserve
+    ///  5. `[w]` Canonical stake account to split from
+    ///  6. `[w]` Uninitialized ephemeral stake account to receive stake
+    ///  7. `[w]` Transient stake account
+    ///  8. `[]` Clock sysvar
+    ///  9. '[]' Stake history sysvar
+    /// 10. `[]` System program
+    /// 11. `[]` Stake program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Stake pool staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", writable, docs = "Validator list"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to fund rent exempt reserve"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Canonical stake account to split from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "ephemeral_stake",
+            writable,
+            docs = "Uninitialized ephemeral stake account to receive stake"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "transient_stake", writable, docs = "Transient stake account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    DecreaseAdditionalValidatorStake {
+        /// amount of lamports to split into the transient stake account
+        lamports: u64,
+        /// seed used to create transient stake account
+        transient_stake_seed: u64,
+        /// seed used to create ephemeral account.
+        ephemeral_stake_seed: u64,
+    },
+
+    /// (Staker only) Decrease active stake on a validator, eventually moving it
+    /// to the reserve
+    ///
+    /// Internally, this instruction:
+    /// * withdraws enough lamports to make the transient account rent-exempt
+    /// * splits from a validator stake account into a transient stake account
+    /// * deactivates the transient stake account
+    ///
+    /// In order to rebalance the pool without taking custody, the staker needs
+    /// a way of reducing the stake on a stake account. This instruction splits
+    /// some amount of stake, up to the total activated stake, from the
+    /// canonical validator stake account, into its "transient" stake
+    /// account.
+    ///
+    /// The instruction only succeeds if the transient stake account does not
+    /// exist. The amount of lamports to move must be at least rent-exemption
+    /// plus `max(crate::MINIMUM_ACTIVE_STAKE,
+    /// solana_program::stake::tools::get_minimum_delegation())`.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Stake pool staker
+    ///  2. `[]` Stake pool withdraw authority
+    ///  3. `[w]` Validator list
+    ///  4. `[w]` Reserve stake account, to fund rent exempt reserve
+    ///  5. `[w]` Canonical stake account to split from
+    ///  6. `[w]` Transient stake account to receive split
+    ///  7. `[]` Clock sysvar
+    ///  8. '[]' Stake history sysvar
+    ///  9. `[]` System program
+    /// 10. `[]` Stake program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Stake pool staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", writable, docs = "Validator list"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to fund rent exempt reserve"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Canonical stake account to split from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "transient_stake",
+            writable,
+            docs = "Transient stake account to receive split"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    DecreaseValidatorStakeWithReserve {
+        /// amount of lamports to split into the transient stake account
+        lamports: u64,
+        /// seed used to create transient stake account
+        transient_stake_seed: u64,
+    },
+
+    /// (Staker only) Decrease active stake on one validator and increase
+    /// another by the same amount, funded by the reserve
+    ///
+    /// Internally, this instruction:
+    ///  * splits the rent-exemption from the reserve into an ephemeral stake
+    ///    account
+    ///  * moves active stake from the source validator stake account into the
+    ///    ephemeral account with `MoveStake`
+    ///  * deactivates the ephemeral account
+    ///  * merges or splits the ephemeral account into the source transient
+    ///    stake account
+    ///  * splits the same amount plus rent-exemption from the reserve into the
+    ///    drained ephemeral account, and delegates it to the destination
+    ///  * merges or splits the ephemeral account into the destination transient
+    ///    stake account
+    ///
+    /// The stake program only moves active stake between accounts delegated to
+    /// the same validator, so this does not avoid deactivation: the source
+    /// stake cools down exactly as with `DecreaseAdditionalValidatorStake`.
+    /// The reserve fronts the destination stake, which starts activating in
+    /// the same epoch, and the deactivated stake returns to the reserve on the
+    /// next update.
+    ///
+    /// Seeds follow `DecreaseAdditionalValidatorStake` on the source and
+    /// `IncreaseAdditionalValidatorStake` on the destination: if either
+    /// validator already has transient stake, its seed must be used.
+    ///
+    /// The amount of lamports to move must be at least
+    /// `max(crate::MINIMUM_ACTIVE_STAKE,
+    /// solana_program::stake::tools::get_minimum_delegation())`.
+    ///
+    /// The reserve pays `lamports + 2 * stake_rent`: one rent-exemption for
+    /// the ephemeral account, and the amount plus a rent-exemption for the
+    /// destination. It must still keep its own rent-exemption and the
+    /// withdrawal claim pool afterwards, so it needs at least
+    /// `lamports + 3 * stake_rent + withdrawal_claim_pool_lamports`, and the
+    /// instruction fails with `InsufficientFunds` otherwise.
+    ///
+    ///  0. `[]` Stake pool
+    ///  1. `[s]` Stake pool staker
+    ///  2. `[]` Stake pool withdraw authority
+    ///  3. `[w]` Validator list
+    ///  4. `[w]` Reserve stake account, to fund the redelegated stake
+    ///  5. `[w]` Source canonical stake account to move stake from
+    ///  6. `[w]` Source transient stake account to receive deactivated stake
+    ///  7. `[w]` Uninitialized ephemeral stake account
+    ///  8. `[w]` Destination transient stake account to receive activating
+    ///     stake
+    ///  9. `[]` Destination canonical stake account
+    /// 10. `[]` Destination validator vote account
+    /// 11. `[]` Clock sysvar
+    /// 12. `[]` Stake History sysvar
+    /// 13. `[]` Stake Config sysvar
+    /// 14. `[]` System program
+    /// 15. `[]` Stake program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Stake pool staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", writable, docs = "Validator list"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to fund the redelegated stake"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "source_validator_stake",
+            writable,
+            docs = "Source canonical stake account to move stake from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "source_transient_stake",
+            writable,
+            docs = "Source transient stake account to receive deactivated stake"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "ephemeral_stake",
+            writable,
+            docs = "Uninitialized ephemeral stake account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "destination_transient_stake",
+            writable,
+            docs = "Destination transient stake account to receive activating stake"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "destination_validator_stake",
+            docs = "Destination canonical stake account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator", docs = "Destination validator vote account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_config", default_value = public_key("StakeConfig11111111111111111111111111111111"), docs = "Stake config sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    Redelegate {
+        /// Amount of lamports to redelegate
+        lamports: u64,
+        /// Seed used to create source transient stake account. If there is
+        /// already transient stake, this must match the current seed, otherwise
+        /// it can be anything
+        source_transient_stake_seed: u64,
+        /// Seed used to create ephemeral account
+        ephemeral_stake_seed: u64,
+        /// Seed used to create destination transient stake account. If there is
+        /// already transient stake, this must match the current seed, otherwise
+        /// it can be anything
+        destination_transient_stake_seed: u64,
+    },
+
+    ///   Deposit some stake into the pool, with a specified slippage
+    ///   constraint. The output is a "pool" token representing ownership
+    ///   into the pool. Inputs are converted at the current ratio.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    ///   2. `[s]/[]` Stake pool deposit authority
+    ///   3. `[]` Stake pool withdraw authority
+    ///   4. `[w]` Stake account to join the pool (withdraw authority for the
+    ///      stake account should be first set to the stake pool deposit
+    ///      authority)
+    ///   5. `[w]` Validator stake account for the stake account to be merged
+    ///      with
+    ///   6. `[w]` Reserve stake account, to withdraw rent exempt reserve
+    ///   7. `[w]` User account to receive pool tokens
+    ///   8. `[w]` Account to receive pool fee tokens
+    ///   9. `[w]` Account to receive a portion of pool fee tokens as referral
+    ///      fees
+    ///   10. `[w]` Pool token mint account
+    ///   11. '[]' Sysvar clock account
+    ///   12. '[]' Sysvar stake history account
+    ///   13. `[]` Pool token program id,
+    ///   14. `[]` Stake program id,
+    ///   15. `[w]` (Optional) Referrer account, whose payout account must be
+    ///       the referral fee account
+    ///   16. `[]` (Optional) Fee split account, followed by its writable
+    ///       recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "deposit_authority",
+            signer = "either",
+            docs = "Stake pool deposit authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "deposit_stake",
+            writable,
+            docs = "Stake account to join the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Validator stake account for the stake account to be merged with"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to withdraw rent exempt reserve"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_to",
+            writable,
+            docs = "User account to receive pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer_pool_tokens_account",
+            writable,
+            docs = "Account to receive a portion of pool fee tokens as referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer",
+            writable,
+            optional,
+            docs = "Referrer account, whose payout account must be the referral fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    DepositStakeWithSlippage {
+        /// Minimum amount of pool tokens that must be received
+        minimum_pool_tokens_out: u64,
+    },
+
+    ///   Withdraw the token from the pool at the current ratio, specifying a
+    ///   minimum expected output lamport amount.
+    ///
+    ///   Succeeds if the stake account has enough SOL to cover the desired
+    ///   amount of pool tokens, and if the withdrawal keeps the total
+    ///   staked amount above the minimum of rent-exempt amount plus `max(
+    ///     crate::MINIMUM_ACTIVE_STAKE,
+    ///     solana_program::stake::tools::get_minimum_delegation()
+    ///   )`.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Validator or reserve stake account to split
+    ///   4. `[w]` Uninitialized stake account to receive withdrawal
+    ///   5. `[]` User account to set as a new withdraw authority
+    ///   6. `[s]` User transfer authority, for pool token account
+    ///   7. `[w]` User account with pool tokens to burn from
+    ///   8. `[w]` Account to receive pool fee tokens
+    ///   9. `[w]` Pool token mint account
+    ///  10. `[]` Sysvar clock account (required)
+    ///  11. `[]` Pool token program id
+    ///  12. `[]` Stake program id,
+    ///  13. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    ///
+    ///  User data: amount of pool tokens to withdraw
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_to_split",
+            writable,
+            docs = "Validator or reserve stake account to split"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_to_receive",
+            writable,
+            docs = "Uninitialized stake account to receive withdrawal"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_stake_authority",
+            docs = "User account to set as a new withdraw authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_transfer_authority",
+            signer,
+            docs = "User transfer authority, for pool token account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account with pool tokens to burn from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    WithdrawStakeWithSlippage {
+        /// Pool tokens to burn in exchange for lamports
+        pool_tokens_in: u64,
+        /// Minimum amount of lamports that must be received
+        minimum_lamports_out: u64,
+    },
+
+    ///   Deposit SOL directly into the pool's reserve account, with a
+    ///   specified slippage constraint. The output is a "pool" token
+    ///   representing ownership into the pool. Inputs are converted at the
+    ///   current ratio.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Reserve stake account, to deposit SOL
+    ///   3. `[s]` Account providing the lamports to be deposited into the pool
+    ///   4. `[w]` User account to receive pool tokens
+    ///   5. `[w]` Account to receive fee tokens
+    ///   6. `[w]` Account to receive a portion of fee as referral fees
+    ///   7. `[w]` Pool token mint account
+    ///   8. `[]` System program account
+    ///   9. `[]` Token program id
+    ///  10. `[s]` (Optional) Stake pool sol deposit authority.
+    ///  11. `[w]` (Optional) Referrer account, whose payout account must be
+    ///      the referral fee account
+    ///  12. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to deposit SOL"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "lamports_from",
+            writable,
+            signer,
+            docs = "Account providing the lamports to be deposited into the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_to",
+            writable,
+            docs = "User account to receive pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer_pool_tokens_account",
+            writable,
+            docs = "Account to receive a portion of fee as referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_deposit_authority",
+            signer,
+            optional,
+            docs = "Stake pool sol deposit authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer",
+            writable,
+            optional,
+            docs = "Referrer account, whose payout account must be the referral fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    DepositSolWithSlippage {
+        /// Amount of lamports to deposit into the reserve
+        lamports_in: u64,
+        /// Minimum amount of pool tokens that must be received
+        minimum_pool_tokens_out: u64,
+    },
+
+    ///   Withdraw SOL directly from the pool's reserve account. Fails if the
+    ///   reserve does not have enough SOL or if the slippage constraint is not
+    ///   met.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[s]` User transfer authority, for pool token account
+    ///   3. `[w]` User account to burn pool tokens
+    ///   4. `[w]` Reserve stake account, to withdraw SOL
+    ///   5. `[w]` Account receiving the lamports from the reserve, must be a
+    ///      system account
+    ///   6. `[w]` Account to receive pool fee tokens
+    ///   7. `[w]` Pool token mint account
+    ///   8. '[]' Clock sysvar
+    ///   9. '[]' Stake history sysvar
+    ///  10. `[]` Stake program account
+    ///  11. `[]` Token program id
+    ///  12. `[s]` (Optional) Stake pool sol withdraw authority
+    ///  13. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_transfer_authority",
+            signer,
+            docs = "User transfer authority, for pool token account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account to burn pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to withdraw SOL"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "lamports_to",
+            writable,
+            docs = "Account receiving the lamports from the reserve, must be a system account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_withdraw_authority",
+            signer,
+            optional,
+            docs = "Stake pool sol withdraw authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    WithdrawSolWithSlippage {
+        /// Pool tokens to burn in exchange for lamports
+        pool_tokens_in: u64,
+        /// Minimum amount of lamports that must be received
+        minimum_lamports_out: u64,
+    },
+
+    ///   Deposit wrapped SOL via a Fogo session.
+    ///
+    ///   This is the only session path for SOL deposits: the session program
+    ///   signer holds token delegations, but lamports can only leave the
+    ///   user's wallet with the wallet's own signature.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Reserve stake account
+    ///   3. `[s]` Signer or Session wSOL source account
+    ///   4. `[w]` User's destination pool token account (ATA)
+    ///   5. `[w]` Manager fee account
+    ///   6. `[w]` Referrer fee account
+    ///   7. `[w]` Pool token mint
+    ///   8. `[]` System Program
+    ///   9. `[]` Token Program
+    ///  10. `[]` Native mint (wSOL)
+    ///  11. `[w]` wSOL token account owned by the user
+    ///  12. `[w]` Transient wSOL token account
+    ///  13. `[w]` Session Program Signer
+    ///  14. `[s]` Payer (Paymaster)
+    ///  15. `[]` User wallet (owner of the ATA)
+    ///  16. `[s]` (Optional) Stake pool SOL deposit authority
+    ///  17. `[]` Associated Token Program
+    ///  18. `[w]` (Optional) Referrer account, whose payout account must be
+    ///      the referral fee account
+    ///  19. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Reserve stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "signer_or_session", signer, docs = "Signer or session"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_to",
+            writable,
+            docs = "User's destination pool token account (ATA)"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager_fee_account", writable, docs = "Manager fee account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer_pool_tokens_account",
+            writable,
+            docs = "Referrer fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "wsol_mint", default_value = public_key("So11111111111111111111111111111111111111112"), docs = "Native mint (wSOL)")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "wsol_token_account",
+            writable,
+            docs = "wSOL token account owned by the user"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "transient_wsol_account",
+            writable,
+            docs = "Transient wSOL token account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "program_signer", writable, docs = "Session program signer"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "payer", writable, signer, docs = "Payer (paymaster)"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "user_wallet", docs = "User wallet (owner of the ATA)"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_deposit_authority",
+            signer,
+            optional,
+            docs = "Stake pool SOL deposit authority"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "associated_token_program", default_value = program("associated-token"), docs = "Associated token program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer",
+            writable,
+            optional,
+            docs = "Referrer account, whose payout account must be the referral fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    DepositWsolWithSession {
+        /// Amount of lamports to deposit
+        lamports_in: u64,
+        /// Minimum amount of pool tokens that must be received
+        minimum_pool_tokens_out: u64,
+    },
+
+    ///   Withdraw wrapped SOL via a Fogo session.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[s]` Signer or Session wSOL source account
+    ///   3. `[w]` User account to burn pool tokens
+    ///   4. `[w]` Reserve stake account, to withdraw SOL
+    ///   5. `[w]` Account receiving the wSOL from the reserve, must be a token account
+    ///   6. `[w]` Account to receive pool fee tokens
+    ///   7. `[w]` Pool token mint account
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Stake history sysvar
+    ///  10. `[]` Stake program account
+    ///  11. `[]` Token program id
+    ///  12. `[]` Native mint (wSOL)
+    ///  13. `[w]` Session Program Signer
+    ///  14. `[]` User wallet (owner of the ATA)
+    ///  15. `[]` System Program
+    ///  16. `[s]` (Optional) Stake pool SOL withdraw authority
+    ///  17. `[]` Associated Token Program
+    ///  18. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "signer_or_session", signer, docs = "Signer or session"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account to burn pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to withdraw SOL"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "lamports_to",
+            writable,
+            docs = "Account receiving the wSOL from the reserve, must be a token account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "wsol_mint", default_value = public_key("So11111111111111111111111111111111111111112"), docs = "Native mint (wSOL)")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "program_signer", writable, docs = "Session program signer"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "user_wallet", docs = "User wallet (owner of the ATA)"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_withdraw_authority",
+            signer,
+            optional,
+            docs = "Stake pool SOL withdraw authority"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "associated_token_program", default_value = program("associated-token"), docs = "Associated token program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    WithdrawWsolWithSession {
+        /// Pool tokens to burn in exchange for lamports
+        pool_tokens_in: u64,
+        /// Minimum amount of lamports that must be received
+        minimum_lamports_out: u64,
+    },
+
+    ///   Withdraw stake via a Fogo session.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Validator or reserve stake account to split
+    ///   4. `[w]` Uninitialized stake account to receive withdrawal (must be user stake PDA)
+    ///   5. `[s]` Signer or Session (user_stake_authority)
+    ///   6. `[]` User transfer authority (same as account 5 for session path)
+    ///   7. `[w]` User account with pool tokens to burn from
+    ///   8. `[w]` Account to receive pool fee tokens
+    ///   9. `[w]` Pool token mint account
+    ///  10. `[]` Clock sysvar
+    ///  11. `[]` Token program id
+    ///  12. `[]` Stake program id
+    ///  13. `[]` Program signer PDA
+    ///  14. `[]` System program
+    ///  15. `[w]` Reserve stake account (to fund rent)
+    ///  16. `[]` Stake history sysvar
+    ///  17. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_to_split",
+            writable,
+            docs = "Validator or reserve stake account to split"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_to_receive",
+            writable,
+            docs = "Uninitialized user stake PDA to receive withdrawal"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "signer_or_session",
+            signer,
+            docs = "Signer or session (user stake authority)"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_transfer_authority",
+            docs = "User transfer authority (same as the session signer)"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account with pool tokens to burn from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "program_signer", docs = "Session program signer"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account (to fund rent)"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    WithdrawStakeWithSession {
+        /// Pool tokens to burn in exchange for stake
+        pool_tokens_in: u64,
+        /// Minimum amount of lamports that must be received
+        minimum_lamports_out: u64,
+        /// Seed for the user stake account PDA
+        user_stake_seed: u64,
+    },
+
+    ///   Withdraw lamports from a user stake account via a Fogo session.
+    ///   Used after cooldown period to convert deactivated stake to SOL.
+    ///
+    ///   0. `[w]` User stake account (must be user stake PDA, fully deactivated)
+    ///   1. `[w]` Recipient account for withdrawn lamports (must be session user)
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` Stake history sysvar
+    ///   4. `[s]` Signer or Session (stake authority)
+    ///   5. `[]` Stake program
+    ///   6. `[]` Stake pool, checked for paused session operations
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_stake_account",
+            writable,
+            docs = "User stake PDA, fully deactivated"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "recipient",
+            writable,
+            docs = "Recipient of the withdrawn lamports (must be the session user)"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "signer_or_session",
+            signer,
+            docs = "Signer or session (stake authority)"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "stake_pool",
+            docs = "Stake pool, checked for paused session operations"
+        ))
+    )]
+    WithdrawFromStakeAccountWithSession {
+        /// Amount of lamports to withdraw (u64::MAX for full withdrawal)
+        lamports: u64,
+        /// Seed for the user stake account PDA
+        user_stake_seed: u64,
+    },
+
+    ///   Burn pool tokens in exchange for a withdrawal ticket, claimable for
+    ///   lamports from the reserve after the cooldown epoch.
+    ///
+    ///   The lamports owed are fixed at the current exchange rate and no
+    ///   longer earn rewards, and count against the pool's SOL withdrawal cap
+    ///   for the epoch the ticket is requested in. The staker funds the claim
+    ///   pool by unstaking with `DecreaseValidatorStakeWithReserve`.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` User transfer authority, for pool token account, becomes
+    ///      the ticket owner
+    ///   2. `[w]` User account to burn pool tokens
+    ///   3. `[w]` Account to receive pool fee tokens
+    ///   4. `[w]` Pool token mint account
+    ///   5. `[w]` Uninitialized withdrawal ticket account (must be ticket PDA)
+    ///   6. `[ws]` Payer for the ticket account rent
+    ///   7. `[]` System program
+    ///   8. `[]` Token program id
+    ///   9. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_transfer_authority",
+            signer,
+            docs = "User transfer authority, becomes the ticket owner"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_from",
+            writable,
+            docs = "User account to burn pool tokens from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "withdrawal_ticket",
+            writable,
+            docs = "Uninitialized withdrawal ticket PDA"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the ticket account rent"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    RequestWithdrawalTicket {
+        /// Pool tokens to burn in exchange for the ticket
+        pool_tokens_in: u64,
+        /// Seed for the withdrawal ticket PDA
+        ticket_seed: u64,
+    },
+
+    ///   Claim the lamports of a withdrawal ticket from the reserve, closing
+    ///   the ticket.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Reserve stake account, to withdraw SOL
+    ///   3. `[w]` Withdrawal ticket account
+    ///   4. `[s]` Ticket owner
+    ///   5. `[w]` Account receiving the lamports and the ticket rent
+    ///   6. `[]` Clock sysvar
+    ///   7. `[]` Stake history sysvar
+    ///   8. `[]` Stake program account
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Reserve stake account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "withdrawal_ticket",
+            writable,
+            docs = "Withdrawal ticket account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "owner", signer, docs = "Ticket owner"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "lamports_to",
+            writable,
+            docs = "Account receiving the lamports and the ticket rent"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    ClaimWithdrawalTicket,
+
+    ///   Deposit a user stake account via a Fogo session, with a specified
+    ///   slippage constraint.
+    ///
+    ///   The stake account must be the user stake PDA, derived from
+    ///   `USER_STAKE_SEED_PREFIX`, the session user and `user_stake_seed`,
+    ///   which is its own stake and withdraw authority, so the program can
+    ///   hand it over to the pool on behalf of the session user. Such PDAs
+    ///   come from `WithdrawStakeWithSession`. A stake account owned by the
+    ///   user's wallet can't be deposited this way, since a session can't sign
+    ///   as the wallet's stake authority; the wallet deposits it with
+    ///   `DepositStakeWithSlippage` instead.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    ///   2. `[s]/[]` Stake pool deposit authority
+    ///   3. `[]` Stake pool withdraw authority
+    ///   4. `[w]` User stake account to join the pool (must be user stake PDA)
+    ///   5. `[w]` Validator stake account for the stake account to be merged
+    ///      with
+    ///   6. `[w]` Reserve stake account, to withdraw rent exempt reserve
+    ///   7. `[w]` User's pool token ATA to receive pool tokens
+    ///   8. `[w]` Account to receive pool fee tokens
+    ///   9. `[w]` Account to receive a portion of pool fee tokens as referral
+    ///      fees
+    ///  10. `[w]` Pool token mint account
+    ///  11. `[]` Sysvar clock account
+    ///  12. `[]` Sysvar stake history account
+    ///  13. `[]` Pool token program id
+    ///  14. `[]` Stake program id
+    ///  15. `[s]` Signer or Session (owner of the user stake PDA)
+    ///  16. `[w]` (Optional) Referrer account, whose payout account must be
+    ///      the referral fee account
+    ///  17. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "deposit_authority",
+            signer = "either",
+            docs = "Stake pool deposit authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_stake_account",
+            writable,
+            docs = "User stake PDA to join the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Validator stake account for the stake account to be merged with"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to withdraw rent exempt reserve"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_to",
+            writable,
+            docs = "User's pool token ATA to receive pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer_pool_tokens_account",
+            writable,
+            docs = "Account to receive a portion of pool fee tokens as referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "signer_or_session",
+            signer,
+            docs = "Signer or session (owner of the user stake PDA)"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer",
+            writable,
+            optional,
+            docs = "Referrer account, whose payout account must be the referral fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    DepositStakeWithSession {
+        /// Minimum amount of pool tokens that must be received
+        minimum_pool_tokens_out: u64,
+        /// Seed for the user stake account PDA
+        user_stake_seed: u64,
+    },
+
+    ///  (Manager only) Update one of the pool's deposit or withdrawal caps
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    SetCap {
+        /// Type of cap to update and value to update it to
+        cap: CapType,
+    },
+
+    ///  (Manager only) Update the pauser authority
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager
+    ///  2. '[]` New pauser pubkey or none
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_pauser", optional, docs = "New pauser pubkey or none"))
+    )]
+    SetPauser,
+
+    ///  (Manager or pauser only) Pause or unpause operations of the pool
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager or pauser
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager_or_pauser", signer, docs = "Manager or pauser"))
+    )]
+    SetPaused {
+        /// Bitmask of `PausedOperation`s to pause, all others are unpaused
+        paused_operations: u8,
+    },
+
+    ///  (Manager only) Propose a new manager, who must accept with
+    ///  `AcceptManager` before taking over. Omitting the new manager cancels
+    ///  any pending proposal.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager
+    ///  2. `[]` New manager pubkey or none
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_manager", optional, docs = "New manager pubkey or none"))
+    )]
+    ProposeManager,
+
+    ///  (Pending manager only) Accept a manager proposal and take over the
+    ///  pool
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Pending manager
+    ///  2. `[]` New manager fee account
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pending_manager", signer, docs = "Pending manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_manager_fee_account", docs = "New manager fee account"))
+    )]
+    AcceptManager,
+
+    ///  (Manager or staker only) Propose a new staker, who must accept with
+    ///  `AcceptStaker` before taking over. Omitting the new staker cancels
+    ///  any pending proposal.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager or current staker
+    ///  2. `[]` New staker pubkey or none
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "set_staker_authority",
+            signer,
+            docs = "Manager or current staker"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_staker", optional, docs = "New staker pubkey or none"))
+    )]
+    ProposeStaker,
+
+    ///  (Pending staker only) Accept a staker proposal and take over staking
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Pending staker
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pending_staker", signer, docs = "Pending staker"))
+    )]
+    AcceptStaker,
+
+    ///  (Manager only) Update the number of epochs that fee changes are held
+    ///  back for before counting down. A shorter delay only applies once the
+    ///  current one has passed.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    SetFeeChangeDelay {
+        /// Number of epochs to hold fee changes back for
+        delay_epochs: u64,
+    },
+
+    ///   Register a referrer for the stake pool under a unique referral code.
+    ///   Deposits that pass the referrer account must pay the referral fee
+    ///   out to its payout account, and are recorded in its stats.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[w]` Uninitialized referrer account (must be referrer PDA)
+    ///   2. `[s]` Referrer authority
+    ///   3. `[]` Pool token account receiving the referral fees
+    ///   4. `[ws]` Payer for the referrer account rent
+    ///   5. `[]` System program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "referrer", writable, docs = "Uninitialized referrer PDA"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "authority", signer, docs = "Referrer authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payout_token_account",
+            docs = "Pool token account receiving the referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the referrer account rent"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    RegisterReferrer {
+        /// Referral code, 1 to 32 ASCII alphanumerics, `-` or `_`
+        code: String,
+    },
+
+    ///   (Referrer authority only) Update the payout account and optionally
+    ///   the authority of a referrer
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[w]` Referrer account
+    ///   2. `[s]` Referrer authority
+    ///   3. `[]` New pool token account receiving the referral fees
+    ///   4. `[]` (Optional) New referrer authority
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "referrer", writable, docs = "Referrer account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "authority", signer, docs = "Referrer authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payout_token_account",
+            docs = "New pool token account receiving the referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "new_authority", optional, docs = "New referrer authority"))
+    )]
+    UpdateReferrer,
+
+    ///   Deposit SOL into the pool's reserve on behalf of a validator. The
+    ///   deposit mints pool tokens like `DepositSolWithSlippage`, and is
+    ///   recorded as pending direct stake for the staker to delegate to the
+    ///   validator. The validator's stake and pending direct stake must stay
+    ///   within `StakePool::max_direct_stake_bps` of the pool.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Validator stake list storage account
+    ///   2. `[w]` Direct stake account (must be direct stake PDA), created if
+    ///      it does not exist yet
+    ///   3. `[]` Validator vote account
+    ///   4. `[]` Stake pool withdraw authority
+    ///   5. `[w]` Reserve stake account, to deposit SOL
+    ///   6. `[ws]` Account providing the lamports to be deposited into the
+    ///      pool, and the rent of a new direct stake account
+    ///   7. `[w]` User account to receive pool tokens
+    ///   8. `[w]` Account to receive fee tokens
+    ///   9. `[w]` Account to receive a portion of fee as referral fees
+    ///  10. `[w]` Pool token mint account
+    ///  11. `[]` System program account
+    ///  12. `[]` Token program id
+    ///  13. `[s]` (Optional) Stake pool sol deposit authority.
+    ///  14. `[w]` (Optional) Referrer account, whose payout account must be
+    ///      the referral fee account
+    ///  15. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator_list", docs = "Validator stake list storage account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "direct_stake",
+            writable,
+            docs = "Direct stake account (must be direct stake PDA), created if it does not exist yet"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator", docs = "Validator vote account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to deposit SOL"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "lamports_from",
+            writable,
+            signer,
+            docs = "Account providing the lamports to be deposited into the pool, and the rent of a new direct stake account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "pool_tokens_to",
+            writable,
+            docs = "User account to receive pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer_pool_tokens_account",
+            writable,
+            docs = "Account to receive a portion of fee as referral fees"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_deposit_authority",
+            signer,
+            optional,
+            docs = "Stake pool sol deposit authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "referrer",
+            writable,
+            optional,
+            docs = "Referrer account, whose payout account must be the referral fee account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    DepositSolToValidator {
+        /// Amount of lamports to deposit into the reserve
+        lamports_in: u64,
+        /// Minimum amount of pool tokens that must be received
+        minimum_pool_tokens_out: u64,
+    },
+
+    ///   (Manager only) Set the maximum share of the pool a validator's stake
+    ///   may reach through stake increases and deposits, on top of the
+    ///   pool-wide `CapType::ValidatorShare`
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[w]` Validator stake list storage account
+    ///   3. `[]` Validator vote account
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "vote_account", docs = "Validator vote account"))
+    )]
+    SetValidatorMaxShare {
+        /// Maximum share in basis points, 1 to 10,000, or `None` to remove
+        /// the validator's own limit
+        max_share_bps: Option<u16>,
+    },
+
+    ///   (Staker only) Update the staker notes hash and maximum commission kept
+    ///   in a validator's metadata
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[s]` Staker
+    ///   2. `[w]` Validator metadata account
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "staker", signer, docs = "Staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_metadata",
+            writable,
+            docs = "Validator metadata account"
+        ))
+    )]
+    UpdateValidatorMetadata {
+        /// Hash of the staker's off-chain notes on the validator
+        notes_hash: [u8; 32],
+        /// Maximum commission, as a percentage, the validator may charge
+        /// before anyone can start deactivating it
+        max_commission: u8,
+    },
+
+    ///   Start deactivating a validator whose vote account commission is above
+    ///   the maximum allowed by its metadata or the pool, marking it for
+    ///   removal like `RemoveValidatorFromPool`. Anyone can call it.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Validator stake list storage account
+    ///   3. `[w]` Stake account to remove from the pool
+    ///   4. `[w]` Transient stake account, to deactivate if necessary
+    ///   5. `[]` Validator vote account
+    ///   6. `[]` Sysvar clock
+    ///   7. `[]` Stake program id
+    ///   8. `[w]` (Optional) Validator metadata account, to check against
+    ///      its maximum commission and record the removal
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Stake account to remove from the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "transient_stake",
+            writable,
+            docs = "Transient stake account, to deactivate if necessary"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator", docs = "Validator vote account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_metadata",
+            writable,
+            optional,
+            docs = "Validator metadata account, to check against its maximum commission and record the removal"
+        ))
+    )]
+    DeactivateHighCommissionValidator,
+
+    ///   Start deactivating a validator whose vote account has not earned vote
+    ///   credits for more than the pool's `max_validator_vote_age_epochs`,
+    ///   marking it for removal like `RemoveValidatorFromPool`. Anyone can call
+    ///   it, and is paid `DELINQUENT_VALIDATOR_CRANK_REWARD` from the reserve
+    ///   if it can afford it.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[w]` Validator stake list storage account
+    ///   3. `[w]` Reserve stake account, to pay the reward from
+    ///   4. `[w]` Stake account to remove from the pool
+    ///   5. `[w]` Transient stake account, to deactivate if necessary
+    ///   6. `[]` Validator vote account
+    ///   7. `[w]` Account receiving the reward
+    ///   8. `[]` Sysvar clock
+    ///   9. `[]` Sysvar stake history
+    ///  10. `[]` Stake program id
+    ///  11. `[w]` Validator metadata account, to check the admission epoch and
+    ///      record the removal
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to pay the reward from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_stake",
+            writable,
+            docs = "Stake account to remove from the pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "transient_stake",
+            writable,
+            docs = "Transient stake account, to deactivate if necessary"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "validator", docs = "Validator vote account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reward_recipient",
+            writable,
+            docs = "Account receiving the reward"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_metadata",
+            writable,
+            docs = "Validator metadata account, to check the admission epoch and record the removal"
+        ))
+    )]
+    DeactivateDelinquentValidator,
+
+    ///   (Manager only) Split the pool's fees across several pool token
+    ///   accounts, each taking its weight in basis points of every fee, with
+    ///   the rest going to the manager fee account. Creates the fee split
+    ///   account if needed. Once fees are split, instructions paying fees
+    ///   must be passed the fee split account followed by its recipients.
+    ///   Setting no recipients sends all fees to the manager fee account
+    ///   again.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[w]` Fee split account, derived from the stake pool
+    ///   3. `[ws]` Payer for the fee split account rent
+    ///   4. `[]` System program
+    ///   5. ..5+N `[]` Pool token accounts receiving the fees, in the order of
+    ///      their weights
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            writable,
+            docs = "Fee split account, derived from the stake pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the fee split account rent"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    SetFeeSplit {
+        /// Share of every fee for each recipient, in basis points, adding up
+        /// to at most 100%
+        weights_bps: Vec<u16>,
+    },
+
+    ///   (Manager only) Set the share of the epoch fee paid in lamports to the
+    ///   insurance fund rather than in pool tokens to the manager. Creates
+    ///   the insurance fund account if needed. Once set, `UpdateStakePoolBalance`
+    ///   pays the insurance fund when passed the insurance fund accounts.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[w]` Insurance fund account, derived from the stake pool
+    ///   3. `[ws]` Payer for the insurance fund account rent
+    ///   4. `[]` System program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "insurance_fund",
+            writable,
+            docs = "Insurance fund account, derived from the stake pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the insurance fund account rent"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    SetInsuranceFee {
+        /// Share of the epoch fee, in basis points, up to 100%
+        insurance_fee_bps: u16,
+    },
+
+    ///   (Manager or staker only) Move lamports from the insurance fund into
+    ///   the reserve, adding them to the pool's total lamports, to cover
+    ///   validator stake losses found while updating the validator list.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` Manager or staker
+    ///   2. `[w]` Insurance fund account
+    ///   3. `[w]` Reserve stake account
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "authority", signer, docs = "Manager or staker"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "insurance_fund", writable, docs = "Insurance fund account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "reserve_stake", writable, docs = "Reserve stake account"))
+    )]
+    InjectInsuranceFund {
+        /// Lamports to inject, up to the uncovered losses and the fund's
+        /// balance above its rent-exempt minimum
+        lamports: u64,
+    },
+
+    ///   (Manager only) Create the exchange rate history account of the
+    ///   pool. From then on, `UpdateStakePoolBalance` records the exchange
+    ///   rate of the epoch in it whenever passed the account.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[w]` Exchange rate history account, derived from the stake pool
+    ///   3. `[ws]` Payer for the exchange rate history account rent
+    ///   4. `[]` System program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "rate_history",
+            writable,
+            docs = "Exchange rate history account, derived from the stake pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the exchange rate history account rent"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    CreateRateHistory,
+
+    ///   (Manager only) Create the price oracle account of the pool token,
+    ///   holding the exchange rate as of the last balance update. From then
+    ///   on, `UpdateStakePoolBalance` refreshes it whenever passed the
+    ///   account.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[w]` Price oracle account, derived from the stake pool
+    ///   3. `[ws]` Payer for the price oracle account rent
+    ///   4. `[]` System program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "oracle",
+            writable,
+            docs = "Price oracle account, derived from the stake pool"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the price oracle account rent"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    CreateOracle,
+
+    ///   Return the exchange rate of the pool token as of the last balance
+    ///   update, as a Borsh-serialized `ExchangeRate` in the transaction's
+    ///   return data. Meant to be called through CPI or simulated.
+    ///
+    ///   0. `[]` Stake pool
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    GetExchangeRate,
+
+    ///   (Manager only) Initialize the Token-2022 metadata extension of the
+    ///   pool mint, with the stake pool withdraw authority as update
+    ///   authority. The mint's metadata pointer must point to the mint
+    ///   itself. The payer covers the rent of the grown mint account.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Pool token mint account
+    ///   4. `[ws]` Payer for the rent of the metadata
+    ///   5. `[]` Token-2022 program
+    ///   6. `[]` System program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the rent of the metadata"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "token_program", default_value = program("token-2022"), docs = "Token-2022 program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    CreateTokenMetadataExtension {
+        /// Token name
+        name: String,
+        /// Token symbol e.g. `stkSOL`
+        symbol: String,
+        /// URI of the uploaded metadata of the spl-token
+        uri: String,
+    },
+
+    ///   (Manager only) Set a field of the Token-2022 metadata extension of
+    ///   the pool mint. `name`, `symbol` and `uri` update the base fields,
+    ///   any other key adds or replaces an additional key-value field.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Pool token mint account
+    ///   4. `[ws]` Payer for the rent of the metadata
+    ///   5. `[]` Token-2022 program
+    ///   6. `[]` System program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the rent of the metadata"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "token_program", default_value = program("token-2022"), docs = "Token-2022 program")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    UpdateTokenMetadataExtensionField {
+        /// Field to set
+        field: String,
+        /// New value of the field
+        value: String,
+    },
+
+    ///   (Manager only) Remove an additional key-value field from the
+    ///   Token-2022 metadata extension of the pool mint. Removing a key
+    ///   that is not present is a no-op.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[s]` Manager
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[w]` Pool token mint account
+    ///   4. `[]` Token-2022 program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager", signer, docs = "Manager"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "token_program", default_value = program("token-2022"), docs = "Token-2022 program")))]
+    RemoveTokenMetadataExtensionKey {
+        /// Key of the additional field to remove
+        key: String,
+    },
+
+    ///   Withdraw SOL via a Fogo session, crediting the session user's wallet
+    ///   directly instead of a wSOL token account.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[]` Stake pool withdraw authority
+    ///   2. `[s]` Signer or Session
+    ///   3. `[w]` User account to burn pool tokens
+    ///   4. `[w]` Reserve stake account, to withdraw SOL
+    ///   5. `[w]` User wallet, credited, must be the session user
+    ///   6. `[w]` Account to receive pool fee tokens
+    ///   7. `[w]` Pool token mint account
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Stake history sysvar
+    ///  10. `[]` Stake program account
+    ///  11. `[]` Token program id
+    ///  12. `[]` Session Program Signer
+    ///  13. `[s]` (Optional) Stake pool SOL withdraw authority
+    ///  14. `[]` (Optional) Fee split account, followed by its writable
+    ///      recipient token accounts, required if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "signer_or_session", signer, docs = "Signer or session"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account to burn pool tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, to withdraw SOL"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_wallet",
+            writable,
+            docs = "User wallet, credited, must be the session user"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "program_signer", docs = "Session program signer"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "sol_withdraw_authority",
+            signer,
+            optional,
+            docs = "Stake pool SOL withdraw authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "fee_split",
+            optional,
+            docs = "Fee split account, followed by its recipient token accounts, required if the pool splits its fees"
+        ))
+    )]
+    WithdrawSolWithSession {
+        /// Pool tokens to burn in exchange for lamports
+        pool_tokens_in: u64,
+        /// Minimum amount of lamports that must be received
+        minimum_lamports_out: u64,
+    },
+
+    ///   Close several fully deactivated user stake accounts via a Fogo
+    ///   session, as created by `WithdrawStakeWithSession`. The rent of each
+    ///   account goes back to the reserve that funded it, the remaining
+    ///   lamports to the session user.
+    ///
+    ///   0. `[]` Stake pool
+    ///   1. `[w]` Reserve stake account, receiving the rent
+    ///   2. `[w]` Recipient account for withdrawn lamports (must be session user)
+    ///   3. `[]` Clock sysvar
+    ///   4. `[]` Stake history sysvar
+    ///   5. `[s]` Signer or Session (stake authority)
+    ///   6. `[]` Stake program
+    ///   7. ..7+N `[w]` User stake accounts, in the order of their seeds
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "reserve_stake",
+            writable,
+            docs = "Reserve stake account, receiving the rent"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "recipient",
+            writable,
+            docs = "Recipient account for withdrawn lamports (must be session user)"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_history", default_value = sysvar("stake_history"), docs = "Stake history sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "signer_or_session",
+            signer,
+            docs = "Signer or session (stake authority)"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    CloseUserStakeAccountsWithSession {
+        /// Seeds of the user stake account PDAs to close
+        user_stake_seeds: Vec<u64>,
+    },
+
+    ///   Withdraw the token from the pool at the current ratio, splitting from
+    ///   several stake accounts at once, with a single burn of pool tokens and
+    ///   a single slippage check over all of them.
+    ///
+    ///   Each (stake account to split, stake account to receive) pair is
+    ///   checked in order like a `WithdrawStake` of its pool tokens, against
+    ///   the validator list as left by the previous pairs, so the withdrawal
+    ///   priority of `WithdrawStake` applies across the whole instruction.
+    ///   All pairs are priced at the exchange rate from before the
+    ///   instruction.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[w]` Validator stake list storage account
+    ///   2. `[]` Stake pool withdraw authority
+    ///   3. `[]` User account to set as a new withdraw authority
+    ///   4. `[s]` User transfer authority, for pool token account
+    ///   5. `[w]` User account with pool tokens to burn from
+    ///   6. `[w]` Account to receive pool fee tokens
+    ///   7. `[w]` Pool token mint account
+    ///   8. `[]` Sysvar clock account (required)
+    ///   9. `[]` Pool token program id
+    ///  10. `[]` Stake program id
+    ///  11. ..11+2N `[w]` Pairs of validator or reserve stake account to
+    ///      split and uninitialized stake account to receive withdrawal, then
+    ///      the fee split account followed by its writable recipient token
+    ///      accounts if the pool splits its fees
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "validator_list",
+            writable,
+            docs = "Validator stake list storage account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "withdraw_authority", docs = "Stake pool withdraw authority"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_stake_authority",
+            docs = "User account to set as a new withdraw authority"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "user_transfer_authority",
+            signer,
+            docs = "User transfer authority, for pool token account"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "burn_from_pool",
+            writable,
+            docs = "User account with pool tokens to burn from"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "manager_fee_account",
+            writable,
+            docs = "Account to receive pool fee tokens"
+        ))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "pool_mint", writable, docs = "Pool token mint account"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "clock", default_value = sysvar("clock"), docs = "Clock sysvar")))]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "token_program", docs = "Pool token program"))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "stake_program", default_value = program("stake"), docs = "Stake program")))]
+    WithdrawStakeMulti {
+        /// Pool tokens to burn for each pair of stake accounts
+        pool_tokens_in: Vec<u64>,
+        /// Minimum amount of lamports that must be received in total
+        minimum_lamports_out: u64,
+    },
+
+    ///   Grow a stake pool account created with an earlier layout to the
+    ///   current one. The fields added since then take their default values.
+    ///   Until then, the other instructions fail on the pool with
+    ///   `StakePoolNeedsMigration`. Anyone can migrate a pool, the payer
+    ///   covering the rent of the grown account.
+    ///
+    ///   0. `[w]` Stake pool
+    ///   1. `[ws]` Payer for the rent of the grown account
+    ///   2. `[]` System program
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(
+            name = "payer",
+            writable,
+            signer,
+            docs = "Payer for the rent of the grown account"
+        ))
+    )]
+    #[cfg_attr(feature = "codama", codama(account(name = "system_program", default_value = program("system"), docs = "System program")))]
+    MigrateStakePool,
+
+    ///  (Manager or pauser only) Pause and unpause some operations of the
+    ///  pool, leaving the others as they are. Unlike `SetPaused`, concurrent
+    ///  updates naming different operations do not undo each other.
+    ///
+    ///  0. `[w]` Stake pool
+    ///  1. `[s]` Manager or pauser
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "stake_pool", writable, docs = "Stake pool"))
+    )]
+    #[cfg_attr(
+        feature = "codama",
+        codama(account(name = "manager_or_pauser", signer, docs = "Manager or pauser"))
+    )]
+    UpdatePaused {
+        /// Bitmask of `PausedOperation`s to pause
+        pause: u8,
+        /// Bitmask of `PausedOperation`s to unpause, disjoint from `pause`
+        unpause: u8,
+    },
+}
+
+/// Creates an `Initialize` instruction.
+#[cfg(feature = "borsh")]
+pub fn initialize(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    pool_mint: &Pubkey,
+    manager_pool_account: &Pubkey,
+    token_program_id: &Pubkey,
+    deposit_authority: Option<Pubkey>,
+    fee: Fee,
+    withdrawal_fee: Fee,
+    deposit_fee: Fee,
+    referral_fee: u8,
+    max_validators: u32,
+) -> Instruction {
+    let init_data = StakePoolInstruction::Initialize {
+        fee,
+        withdrawal_fee,
+        deposit_fee,
+        referral_fee,
+        max_validators,
+    };
+    let data = borsh::to_vec(&init_data).unwrap();
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(*staker, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new_readonly(*reserve_stake, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new(*manager_pool_account, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(deposit_authority) = deposit_authority {
+        accounts.push(AccountMeta::new_readonly(deposit_authority, true));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates `AddValidatorToPool` instruction (add new validator stake account to
+/// the pool)
+#[cfg(feature = "borsh")]
+pub fn add_validator_to_pool(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    reserve: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    validator_list: &Pubkey,
+    stake: &Pubkey,
+    validator: &Pubkey,
+    seed: Option<NonZeroU32>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new(*reserve, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*stake, false),
+        AccountMeta::new_readonly(*validator, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        #[allow(deprecated)]
+        AccountMeta::new_readonly(stake::config::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    let data = borsh::to_vec(&StakePoolInstruction::AddValidatorToPool(
+        seed.map(|s| s.get()).unwrap_or(0),
+    ))
+    .unwrap();
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates `RemoveValidatorFromPool` instruction (remove validator stake
+/// account from the pool)
+#[cfg(feature = "borsh")]
+pub fn remove_validator_from_pool(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    validator_list: &Pubkey,
+    stake_account: &Pubkey,
+    transient_stake_account: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new_readonly(*stake_pool_withdraw, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*stake_account, false),
+        AccountMeta::new(*transient_stake_account, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::RemoveValidatorFromPool).unwrap(),
+    }
+}
+
+/// Creates `DecreaseValidatorStake` instruction (rebalance from validator
+/// account to transient account)
+#[deprecated(
+    since = "0.7.0",
+    note = "please use `decrease_validator_stake_with_reserve`"
+)]
+#[cfg(feature = "borsh")]
+pub fn decrease_validator_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list: &Pubkey,
+    validator_stake: &Pubkey,
+    transient_stake: &Pubkey,
+    lamports: u64,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*validator_stake, false),
+        AccountMeta::new(*transient_stake, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::DecreaseValidatorStake {
+            lamports,
+            transient_stake_seed,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates `DecreaseAdditionalValidatorStake` instruction (rebalance from
+/// validator account to transient account)
+#[cfg(feature = "borsh")]
+pub fn decrease_additional_validator_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    validator_stake: &Pubkey,
+    ephemeral_stake: &Pubkey,
+    transient_stake: &Pubkey,
+    lamports: u64,
+    transient_stake_seed: u64,
+    ephemeral_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*validator_stake, false),
+        AccountMeta::new(*ephemeral_stake, false),
+        AccountMeta::new(*transient_stake, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::DecreaseAdditionalValidatorStake {
+            lamports,
+            transient_stake_seed,
+            ephemeral_stake_seed,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates `DecreaseValidatorStakeWithReserve` instruction (rebalance from
+/// validator account to transient account)
+#[cfg(feature = "borsh")]
+pub fn decrease_validator_stake_with_reserve(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    validator_stake: &Pubkey,
+    transient_stake: &Pubkey,
+    lamports: u64,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*validator_stake, false),
+        AccountMeta::new(*transient_stake, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::DecreaseValidatorStakeWithReserve {
+            lamports,
+            transient_stake_seed,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates `IncreaseValidatorStake` instruction (rebalance from reserve account
+/// to transient account)
+#[cfg(feature = "borsh")]
+pub fn increase_validator_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    transient_stake: &Pubkey,
+    validator_stake: &Pubkey,
+    validator: &Pubkey,
+    lamports: u64,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*transient_stake, false),
+        AccountMeta::new_readonly(*validator_stake, false),
+        AccountMeta::new_readonly(*validator, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        #[allow(deprecated)]
+        AccountMeta::new_readonly(stake::config::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::IncreaseValidatorStake {
+            lamports,
+            transient_stake_seed,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates `IncreaseAdditionalValidatorStake` instruction (rebalance from
+/// reserve account to transient account)
+#[cfg(feature = "borsh")]
+pub fn increase_additional_validator_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    ephemeral_stake: &Pubkey,
+    transient_stake: &Pubkey,
+    validator_stake: &Pubkey,
+    validator: &Pubkey,
+    lamports: u64,
+    transient_stake_seed: u64,
+    ephemeral_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*ephemeral_stake, false),
+        AccountMeta::new(*transient_stake, false),
+        AccountMeta::new_readonly(*validator_stake, false),
+        AccountMeta::new_readonly(*validator, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        #[allow(deprecated)]
+        AccountMeta::new_readonly(stake::config::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::IncreaseAdditionalValidatorStake {
+            lamports,
+            transient_stake_seed,
+            ephemeral_stake_seed,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates `Redelegate` instruction (rebalance from one validator account to
+/// another)
+#[cfg(feature = "borsh")]
+pub fn redelegate(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    source_validator_stake: &Pubkey,
+    source_transient_stake: &Pubkey,
+    ephemeral_stake: &Pubkey,
+    destination_transient_stake: &Pubkey,
+    destination_validator_stake: &Pubkey,
+    validator: &Pubkey,
+    lamports: u64,
+    source_transient_stake_seed: u64,
+    ephemeral_stake_seed: u64,
+    destination_transient_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*source_validator_stake, false),
+        AccountMeta::new(*source_transient_stake, false),
+        AccountMeta::new(*ephemeral_stake, false),
+        AccountMeta::new(*destination_transient_stake, false),
+        AccountMeta::new_readonly(*destination_validator_stake, false),
+        AccountMeta::new_readonly(*validator, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        #[allow(deprecated)]
+        AccountMeta::new_readonly(stake::config::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::Redelegate {
+            lamports,
+            source_transient_stake_seed,
+            ephemeral_stake_seed,
+            destination_transient_stake_seed,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates `SetPreferredDepositValidator` instruction
+#[cfg(feature = "borsh")]
+pub fn set_preferred_validator(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    staker: &Pubkey,
+    validator_list_address: &Pubkey,
+    validator_type: PreferredValidatorType,
+    validator_vote_address: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*stake_pool_address, false),
+            AccountMeta::new_readonly(*staker, true),
+            AccountMeta::new_readonly(*validator_list_address, false),
+        ],
+        data: borsh::to_vec(&StakePoolInstruction::SetPreferredValidator {
+            validator_type,
+            validator_vote_address,
+        })
+        .unwrap(),
+    }
+}
+
+/// Create an `AddValidatorToPool` instruction given an existing stake pool and
+/// vote account
+#[cfg(feature = "borsh")]
+pub fn add_validator_to_pool_with_vote(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    seed: Option<NonZeroU32>,
+) -> Instruction {
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(program_id, stake_pool_address).0;
+    let (stake_account_address, _) =
+        find_stake_program_address(program_id, vote_account_address, stake_pool_address, seed);
+    add_validator_to_pool(
+        program_id,
+        stake_pool_address,
+        &stake_pool.staker,
+        &stake_pool.reserve_stake,
+        &pool_withdraw_authority,
+        &stake_pool.validator_list,
+        &stake_account_address,
+        vote_account_address,
+        seed,
+    )
+}
+
+/// Create an `RemoveValidatorFromPool` instruction given an existing stake pool
+/// and vote account
+#[cfg(feature = "borsh")]
+pub fn remove_validator_from_pool_with_vote(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    validator_stake_seed: Option<NonZeroU32>,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(program_id, stake_pool_address).0;
+    let (stake_account_address, _) = find_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        validator_stake_seed,
+    );
+    let (transient_stake_account, _) = find_transient_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        transient_stake_seed,
+    );
+    let (validator_metadata, _) = find_validator_metadata_program_address(
+        program_id,
+        stake_pool_address,
+        vote_account_address,
+    );
+    with_validator_metadata(
+        remove_validator_from_pool(
+            program_id,
+            stake_pool_address,
+            &stake_pool.staker,
+            &pool_withdraw_authority,
+            &stake_pool.validator_list,
+            &stake_account_address,
+            &transient_stake_account,
+        ),
+        &validator_metadata,
+        None,
+    )
+}
+
+/// Create an `IncreaseValidatorStake` instruction given an existing stake pool
+/// and vote account
+#[cfg(feature = "borsh")]
+pub fn increase_validator_stake_with_vote(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    lamports: u64,
+    validator_stake_seed: Option<NonZeroU32>,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(program_id, stake_pool_address).0;
+    let (transient_stake_address, _) = find_transient_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        transient_stake_seed,
+    );
+    let (validator_stake_address, _) = find_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        validator_stake_seed,
+    );
+
+    increase_validator_stake(
+        program_id,
+        stake_pool_address,
+        &stake_pool.staker,
+        &pool_withdraw_authority,
+        &stake_pool.validator_list,
+        &stake_pool.reserve_stake,
+        &transient_stake_address,
+        &validator_stake_address,
+        vote_account_address,
+        lamports,
+        transient_stake_seed,
+    )
+}
+
+/// Create an `IncreaseAdditionalValidatorStake` instruction given an existing
+/// stake pool and vote account
+#[cfg(feature = "borsh")]
+pub fn increase_additional_validator_stake_with_vote(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    lamports: u64,
+    validator_stake_seed: Option<NonZeroU32>,
+    transient_stake_seed: u64,
+    ephemeral_stake_seed: u64,
+) -> Instruction {
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(program_id, stake_pool_address).0;
+    let (ephemeral_stake_address, _) =
+        find_ephemeral_stake_program_address(program_id, stake_pool_address, ephemeral_stake_seed);
+    let (transient_stake_address, _) = find_transient_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        transient_stake_seed,
+    );
+    let (validator_stake_address, _) = find_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        validator_stake_seed,
+    );
+
+    increase_additional_validator_stake(
+        program_id,
+        stake_pool_address,
+        &stake_pool.staker,
+        &pool_withdraw_authority,
+        &stake_pool.validator_list,
+        &stake_pool.reserve_stake,
+        &ephemeral_stake_address,
+        &transient_stake_address,
+        &validator_stake_address,
+        vote_account_address,
+        lamports,
+        transient_stake_seed,
+        ephemeral_stake_seed,
+    )
+}
+
+/// Create a `DecreaseValidatorStake` instruction given an existing stake pool
+/// and vote account
+#[cfg(feature = "borsh")]
+pub fn decrease_validator_stake_with_vote(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    lamports: u64,
+    validator_stake_seed: Option<NonZeroU32>,
+    transient_stake_seed: u64,
+) -> Instruction {
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(program_id, stake_pool_address).0;
+    let (validator_stake_address, _) = find_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        validator_stake_seed,
+    );
+    let (transient_stake_address, _) = find_transient_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        transient_stake_seed,
+    );
+    decrease_validator_stake_with_reserve(
+        program_id,
+        stake_pool_address,
+        &stake_pool.staker,
+        &pool_withdraw_authority,
+        &stake_pool.validator_list,
+        &stake_pool.reserve_stake,
+        &validator_stake_address,
+        &transient_stake_address,
+        lamports,
+        transient_stake_seed,
+    )
+}
+
+/// Create a `IncreaseAdditionalValidatorStake` instruction given an existing
+/// stake pool, validator list and vote account
+#[cfg(feature = "borsh")]
+pub fn increase_additional_validator_stake_with_list(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    validator_list: &ValidatorList,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    lamports: u64,
+    ephemeral_stake_seed: u64,
+) -> Result<Instruction, ProgramError> {
+    let validator_info = validator_list
+        .find(vote_account_address)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let transient_stake_seed = u64::from(validator_info.transient_seed_suffix);
+    let validator_stake_seed = NonZeroU32::new(validator_info.validator_seed_suffix.into());
+    Ok(increase_additional_validator_stake_with_vote(
+        program_id,
+        stake_pool,
+        stake_pool_address,
+        vote_account_address,
+        lamports,
+        validator_stake_seed,
+        transient_stake_seed,
+        ephemeral_stake_seed,
+    ))
+}
+
+/// Create a `DecreaseAdditionalValidatorStake` instruction given an existing
+/// stake pool, validator list and vote account
+#[cfg(feature = "borsh")]
+pub fn decrease_additional_validator_stake_with_list(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    validator_list: &ValidatorList,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    lamports: u64,
+    ephemeral_stake_seed: u64,
+) -> Result<Instruction, ProgramError> {
+    let validator_info = validator_list
+        .find(vote_account_address)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let transient_stake_seed = u64::from(validator_info.transient_seed_suffix);
+    let validator_stake_seed = NonZeroU32::new(validator_info.validator_seed_suffix.into());
+    Ok(decrease_additional_validator_stake_with_vote(
+        program_id,
+        stake_pool,
+        stake_pool_address,
+        vote_account_address,
+        lamports,
+        validator_stake_seed,
+        transient_stake_seed,
+        ephemeral_stake_seed,
+    ))
+}
+
+/// Create a `DecreaseAdditionalValidatorStake` instruction given an existing
+/// stake pool and vote account
+#[cfg(feature = "borsh")]
+pub fn decrease_additional_validator_stake_with_vote(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    lamports: u64,
+    validator_stake_seed: Option<NonZeroU32>,
+    transient_stake_seed: u64,
+    ephemeral_stake_seed: u64,
+) -> Instruction {
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(program_id, stake_pool_address).0;
+    let (validator_stake_address, _) = find_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        validator_stake_seed,
+    );
+    let (ephemeral_stake_address, _) =
+        find_ephemeral_stake_program_address(program_id, stake_pool_address, ephemeral_stake_seed);
+    let (transient_stake_address, _) = find_transient_stake_program_address(
+        program_id,
+        vote_account_address,
+        stake_pool_address,
+        transient_stake_seed,
+    );
+    decrease_additional_validator_stake(
+        program_id,
+        stake_pool_address,
+        &stake_pool.staker,
+        &pool_withdraw_authority,
+        &stake_pool.validator_list,
+        &stake_pool.reserve_stake,
+        &validator_stake_address,
+        &ephemeral_stake_address,
+        &transient_stake_address,
+        lamports,
+        transient_stake_seed,
+        ephemeral_stake_seed,
+    )
+}
+
+/// Create a `Redelegate` instruction given an existing stake pool and the
+/// source and destination vote accounts
+#[cfg(feature = "borsh")]
+pub fn redelegate_with_vote(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    source_vote_account_address: &Pubkey,
+    source_validator_stake_seed: Option<NonZeroU32>,
+    source_transient_stake_seed: u64,
+    destination_vote_account_address: &Pubkey,
+    destination_validator_stake_seed: Option<NonZeroU32>,
+    destination_transient_stake_seed: u64,
+    lamports: u64,
+    ephemeral_stake_seed: u64,
+) -> Instruction {
+    let pool_withdraw_authority =
+        find_withdraw_authority_program_address(program_id, stake_pool_address).0;
+    let (source_validator_stake_address, _) = find_stake_program_address(
+        program_id,
+        source_vote_account_address,
+        stake_pool_address,
+        source_validator_stake_seed,
+    );
+    let (source_transient_stake_address, _) = find_transient_stake_program_address(
+        program_id,
+        source_vote_account_address,
+        stake_pool_address,
+        source_transient_stake_seed,
+    );
+    let (ephemeral_stake_address, _) =
+        find_ephemeral_stake_program_address(program_id, stake_pool_address, ephemeral_stake_seed);
+    let (destination_validator_stake_address, _) = find_stake_program_address(
+        program_id,
+        destination_vote_account_address,
+        stake_pool_address,
+        destination_validator_stake_seed,
+    );
+    let (destination_transient_stake_address, _) = find_transient_stake_program_address(
+        program_id,
+        destination_vote_account_address,
+        stake_pool_address,
+        destination_transient_stake_seed,
+    );
+    redelegate(
+        program_id,
+        stake_pool_address,
+        &stake_pool.staker,
+        &pool_withdraw_authority,
+        &stake_pool.validator_list,
+        &stake_pool.reserve_stake,
+        &source_validator_stake_address,
+        &source_transient_stake_address,
+        &ephemeral_stake_address,
+        &destination_transient_stake_address,
+        &destination_validator_stake_address,
+        destination_vote_account_address,
+        lamports,
+        source_transient_stake_seed,
+        ephemeral_stake_seed,
+        destination_transient_stake_seed,
+    )
+}
+
+/// Creates `UpdateValidatorListBalance` instruction (update validator stake
+/// account balances)
+#[deprecated(
+    since = "1.1.0",
+    note = "please use `update_validator_list_balance_chunk`"
+)]
+#[cfg(feature = "borsh")]
+pub fn update_validator_list_balance(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list_address: &Pubkey,
+    reserve_stake: &Pubkey,
+    validator_list: &ValidatorList,
+    validator_vote_accounts: &[Pubkey],
+    start_index: u32,
+    no_merge: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list_address, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    accounts.append(
+        &mut validator_vote_accounts
+            .iter()
+            .flat_map(|vote_account_address| {
+                let validator_stake_info = validator_list.find(vote_account_address);
+                if let Some(validator_stake_info) = validator_stake_info {
+                    let (validator_stake_account, _) = find_stake_program_address(
+                        program_id,
+                        vote_account_address,
+                        stake_pool,
+                        NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+                    );
+                    let (transient_stake_account, _) = find_transient_stake_program_address(
+                        program_id,
+                        vote_account_address,
+                        stake_pool,
+                        validator_stake_info.transient_seed_suffix.into(),
+                    );
+                    vec![
+                        AccountMeta::new(validator_stake_account, false),
+                        AccountMeta::new(transient_stake_account, false),
+                    ]
+                } else {
+                    vec![]
+                }
+            })
+            .collect::<Vec<AccountMeta>>(),
+    );
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdateValidatorListBalance {
+            start_index,
+            no_merge,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates an `UpdateValidatorListBalance` instruction (update validator stake
+/// account balances) to update `validator_list[start_index..start_index +
+/// len]`.
+///
+/// Returns `Err(ProgramError::InvalidInstructionData)` if:
+/// - `start_index..start_index + len` is out of bounds for
+///   `validator_list.validators`
+#[cfg(feature = "borsh")]
+pub fn update_validator_list_balance_chunk(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list_address: &Pubkey,
+    reserve_stake: &Pubkey,
+    validator_list: &ValidatorList,
+    len: usize,
+    start_index: usize,
+    no_merge: bool,
+) -> Result<Instruction, ProgramError> {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*validator_list_address, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    let validator_list_subslice = validator_list
+        .validators
+        .get(start_index..start_index.saturating_add(len))
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    accounts.extend(validator_list_subslice.iter().flat_map(
+        |ValidatorStakeInfo {
+             vote_account_address,
+             validator_seed_suffix,
+             transient_seed_suffix,
+             ..
+         }| {
+            let (validator_stake_account, _) = find_stake_program_address(
+                program_id,
+                vote_account_address,
+                stake_pool,
+                NonZeroU32::new((*validator_seed_suffix).into()),
+            );
+            let (transient_stake_account, _) = find_transient_stake_program_address(
+                program_id,
+                vote_account_address,
+                stake_pool,
+                (*transient_seed_suffix).into(),
+            );
+            [
+                AccountMeta::new(validator_stake_account, false),
+                AccountMeta::new(transient_stake_account, false),
+            ]
+        },
+    ));
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdateValidatorListBalance {
+            start_index: start_index.try_into().unwrap(),
+            no_merge,
+        })
+        .unwrap(),
+    })
+}
+
+/// Creates `UpdateValidatorListBalance` instruction (update validator stake
+/// account balances)
+///
+/// Returns `None` if all validators in the given chunk has already been updated
+/// for this epoch, returns the required instruction otherwise.
+#[cfg(feature = "borsh")]
+pub fn update_stale_validator_list_balance_chunk(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    validator_list_address: &Pubkey,
+    reserve_stake: &Pubkey,
+    validator_list: &ValidatorList,
+    len: usize,
+    start_index: usize,
+    no_merge: bool,
+    current_epoch: Epoch,
+) -> Result<Option<Instruction>, ProgramError> {
+    let validator_list_subslice = validator_list
+        .validators
+        .get(start_index..start_index.saturating_add(len))
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if validator_list_subslice.iter().all(|info| {
+        let last_update_epoch: u64 = info.last_update_epoch.into();
+        last_update_epoch >= current_epoch
+    }) {
+        return Ok(None);
+    }
+    update_validator_list_balance_chunk(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        validator_list_address,
+        reserve_stake,
+        validator_list,
+        len,
+        start_index,
+        no_merge,
+    )
+    .map(Some)
+}
+
+/// Creates `UpdateStakePoolBalance` instruction (pool balance from the stake
+/// account list balances)
+#[cfg(feature = "borsh")]
+pub fn update_stake_pool_balance(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    withdraw_authority: &Pubkey,
+    validator_list_storage: &Pubkey,
+    reserve_stake: &Pubkey,
+    manager_fee_account: &Pubkey,
+    stake_pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*withdraw_authority, false),
+        AccountMeta::new(*validator_list_storage, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*stake_pool_mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdateStakePoolBalance).unwrap(),
+    }
+}
+
+/// Appends the insurance fund accounts to an `UpdateStakePoolBalance`
+/// instruction, paying out the share of the epoch fee owed to the insurance
+/// fund. Must come before any fee split accounts.
+#[cfg(feature = "borsh")]
+pub fn with_insurance_fund(mut instruction: Instruction, stake_pool: &Pubkey) -> Instruction {
+    let (insurance_fund, _) =
+        find_insurance_fund_program_address(&instruction.program_id, stake_pool);
+    instruction.accounts.extend([
+        AccountMeta::new(insurance_fund, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ]);
+    instruction
+}
+
+/// Appends the exchange rate history account to an `UpdateStakePoolBalance`
+/// instruction, recording the exchange rate of the epoch. Must come
+/// after any insurance fund accounts and before any fee split accounts.
+#[cfg(feature = "borsh")]
+pub fn with_rate_history(mut instruction: Instruction, stake_pool: &Pubkey) -> Instruction {
+    let (rate_history, _) = find_rate_history_program_address(&instruction.program_id, stake_pool);
+    instruction
+        .accounts
+        .push(AccountMeta::new(rate_history, false));
+    instruction
+}
+
+/// Appends the price oracle account to an `UpdateStakePoolBalance`
+/// instruction, refreshing the oracle's exchange rate. Must come after
+/// any exchange rate history account and before any fee split accounts.
+#[cfg(feature = "borsh")]
+pub fn with_oracle(mut instruction: Instruction, stake_pool: &Pubkey) -> Instruction {
+    let (oracle, _) = find_oracle_program_address(&instruction.program_id, stake_pool);
+    instruction.accounts.push(AccountMeta::new(oracle, false));
+    instruction
+}
+
+/// Creates the `UpdateStakePoolBalance` instruction for a stake pool, with
+/// the insurance fund, exchange rate history and price oracle accounts if
+/// needed
+#[cfg(feature = "borsh")]
+fn update_stake_pool_balance_for(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    stake_pool_address: &Pubkey,
+    withdraw_authority: &Pubkey,
+) -> Instruction {
+    let instruction = update_stake_pool_balance(
+        program_id,
+        stake_pool_address,
+        withdraw_authority,
+        &stake_pool.validator_list,
+        &stake_pool.reserve_stake,
+        &stake_pool.manager_fee_account,
+        &stake_pool.pool_mint,
+        &stake_pool.token_program_id,
+    );
+    let instruction =
+        if stake_pool.insurance_fee_bps > 0 || stake_pool.pending_insurance_lamports > 0 {
+            with_insurance_fund(instruction, stake_pool_address)
+        } else {
+            instruction
+        };
+    let instruction = if stake_pool.has_rate_history {
+        with_rate_history(instruction, stake_pool_address)
+    } else {
+        instruction
+    };
+    if stake_pool.has_oracle {
+        with_oracle(instruction, stake_pool_address)
+    } else {
+        instruction
+    }
+}
+
+/// Creates `CleanupRemovedValidatorEntries` instruction (removes entries from
+/// the validator list)
+#[cfg(feature = "borsh")]
+pub fn cleanup_removed_validator_entries(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new(*validator_list_storage, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::CleanupRemovedValidatorEntries).unwrap(),
+    }
+}
+
+/// Creates all `UpdateValidatorListBalance` and `UpdateStakePoolBalance`
+/// instructions for fully updating a stake pool each epoch
+#[cfg(feature = "borsh")]
+pub fn update_stake_pool(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    validator_list: &ValidatorList,
+    stake_pool_address: &Pubkey,
+    no_merge: bool,
+) -> (Vec<Instruction>, Vec<Instruction>) {
+    let (withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool_address);
+
+    let update_list_instructions = validator_list
+        .validators
+        .chunks(MAX_VALIDATORS_TO_UPDATE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            // unwrap-safety: chunk len and offset are derived
+            update_validator_list_balance_chunk(
+                program_id,
+                stake_pool_address,
+                &withdraw_authority,
+                &stake_pool.validator_list,
+                &stake_pool.reserve_stake,
+                validator_list,
+                chunk.len(),
+                i.saturating_mul(MAX_VALIDATORS_TO_UPDATE),
+                no_merge,
+            )
+            .unwrap()
+        })
+        .collect();
+
+    let final_instructions = vec![
+        update_stake_pool_balance_for(
+            program_id,
+            stake_pool,
+            stake_pool_address,
+            &withdraw_authority,
+        ),
+        cleanup_removed_validator_entries(
+            program_id,
+            stake_pool_address,
+            &stake_pool.validator_list,
+        ),
+    ];
+    (update_list_instructions, final_instructions)
+}
+
+/// Creates the `UpdateValidatorListBalance` instructions only for validators on
+/// `validator_list` that have not been updated for this epoch, and the
+/// `UpdateStakePoolBalance` instruction for fully updating the stake pool.
+///
+/// Basically same as [`update_stake_pool`], but skips validators that are
+/// already updated for this epoch
+#[cfg(feature = "borsh")]
+pub fn update_stale_stake_pool(
+    program_id: &Pubkey,
+    stake_pool: &StakePool,
+    validator_list: &ValidatorList,
+    stake_pool_address: &Pubkey,
+    no_merge: bool,
+    current_epoch: Epoch,
+) -> (Vec<Instruction>, Vec<Instruction>) {
+    let (withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool_address);
+
+    let update_list_instructions = validator_list
+        .validators
+        .chunks(MAX_VALIDATORS_TO_UPDATE)
+        .enumerate()
+        .filter_map(|(i, chunk)| {
+            // unwrap-safety: chunk len and offset are derived
+            update_stale_validator_list_balance_chunk(
+                program_id,
+                stake_pool_address,
+                &withdraw_authority,
+                &stake_pool.validator_list,
+                &stake_pool.reserve_stake,
+                validator_list,
+                chunk.len(),
+                i.saturating_mul(MAX_VALIDATORS_TO_UPDATE),
+                no_merge,
+                current_epoch,
+            )
+            .unwrap()
+        })
+        .collect();
+
+    let final_instructions = vec![
+        update_stake_pool_balance_for(
+            program_id,
+            stake_pool,
+            stake_pool_address,
+            &withdraw_authority,
+        ),
+        cleanup_removed_validator_entries(
+            program_id,
+            stake_pool_address,
+            &stake_pool.validator_list,
+        ),
+    ];
+    (update_list_instructions, final_instructions)
+}
+
+#[cfg(feature = "borsh")]
+fn deposit_stake_internal(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_deposit_authority: Option<&Pubkey>,
+    stake_pool_withdraw_authority: &Pubkey,
+    deposit_stake_address: &Pubkey,
+    deposit_stake_withdraw_authority: &Pubkey,
+    validator_stake_account: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    minimum_pool_tokens_out: Option<u64>,
+) -> Vec<Instruction> {
+    let mut instructions = vec![];
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new(*validator_list_storage, false),
+    ];
+    if let Some(stake_pool_deposit_authority) = stake_pool_deposit_authority {
+        accounts.push(AccountMeta::new_readonly(
+            *stake_pool_deposit_authority,
+            true,
+        ));
+        instructions.extend_from_slice(&[
+            stake::instruction::authorize(
+                deposit_stake_address,
+                deposit_stake_withdraw_authority,
+                stake_pool_deposit_authority,
+                stake::state::StakeAuthorize::Staker,
+                None,
+            ),
+            stake::instruction::authorize(
+                deposit_stake_address,
+                deposit_stake_withdraw_authority,
+                stake_pool_deposit_authority,
+                stake::state::StakeAuthorize::Withdrawer,
+                None,
+            ),
+        ]);
+    } else {
+        let stake_pool_deposit_authority =
+            find_deposit_authority_program_address(program_id, stake_pool).0;
+        accounts.push(AccountMeta::new_readonly(
+            stake_pool_deposit_authority,
+            false,
+        ));
+        instructions.extend_from_slice(&[
+            stake::instruction::authorize(
+                deposit_stake_address,
+                deposit_stake_withdraw_authority,
+                &stake_pool_deposit_authority,
+                stake::state::StakeAuthorize::Staker,
+                None,
+            ),
+            stake::instruction::authorize(
+                deposit_stake_address,
+                deposit_stake_withdraw_authority,
+                &stake_pool_deposit_authority,
+                stake::state::StakeAuthorize::Withdrawer,
+                None,
+            ),
+        ]);
+    };
+
+    accounts.extend_from_slice(&[
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*deposit_stake_address, false),
+        AccountMeta::new(*validator_stake_account, false),
+        AccountMeta::new(*reserve_stake_account, false),
+        AccountMeta::new(*pool_tokens_to, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*referrer_pool_tokens_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ]);
+    instructions.push(
+        if let Some(minimum_pool_tokens_out) = minimum_pool_tokens_out {
+            Instruction {
+                program_id: *program_id,
+                accounts,
+                data: borsh::to_vec(&StakePoolInstruction::DepositStakeWithSlippage {
+                    minimum_pool_tokens_out,
+                })
+                .unwrap(),
+            }
+        } else {
+            Instruction {
+                program_id: *program_id,
+                accounts,
+                data: borsh::to_vec(&StakePoolInstruction::DepositStake).unwrap(),
+            }
+        },
+    );
+    instructions
+}
+
+/// Creates instructions required to deposit into a stake pool, given a stake
+/// account owned by the user.
+#[cfg(feature = "borsh")]
+pub fn deposit_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    deposit_stake_address: &Pubkey,
+    deposit_stake_withdraw_authority: &Pubkey,
+    validator_stake_account: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Vec<Instruction> {
+    deposit_stake_internal(
+        program_id,
+        stake_pool,
+        validator_list_storage,
+        None,
+        stake_pool_withdraw_authority,
+        deposit_stake_address,
+        deposit_stake_withdraw_authority,
+        validator_stake_account,
+        reserve_stake_account,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        None,
+    )
+}
+
+/// Creates instructions to deposit into a stake pool with slippage
+#[cfg(feature = "borsh")]
+pub fn deposit_stake_with_slippage(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    deposit_stake_address: &Pubkey,
+    deposit_stake_withdraw_authority: &Pubkey,
+    validator_stake_account: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    minimum_pool_tokens_out: u64,
+) -> Vec<Instruction> {
+    deposit_stake_internal(
+        program_id,
+        stake_pool,
+        validator_list_storage,
+        None,
+        stake_pool_withdraw_authority,
+        deposit_stake_address,
+        deposit_stake_withdraw_authority,
+        validator_stake_account,
+        reserve_stake_account,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        Some(minimum_pool_tokens_out),
+    )
+}
+
+/// Creates instructions required to deposit into a stake pool, given a stake
+/// account owned by the user. The difference with `deposit()` is that a deposit
+/// authority must sign this instruction, which is required for private pools.
+#[cfg(feature = "borsh")]
+pub fn deposit_stake_with_authority(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_deposit_authority: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    deposit_stake_address: &Pubkey,
+    deposit_stake_withdraw_authority: &Pubkey,
+    validator_stake_account: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Vec<Instruction> {
+    deposit_stake_internal(
+        program_id,
+        stake_pool,
+        validator_list_storage,
+        Some(stake_pool_deposit_authority),
+        stake_pool_withdraw_authority,
+        deposit_stake_address,
+        deposit_stake_withdraw_authority,
+        validator_stake_account,
+        reserve_stake_account,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        None,
+    )
+}
+
+/// Creates instructions required to deposit into a stake pool with slippage,
+/// given a stake account owned by the user. The difference with `deposit()` is
+/// that a deposit authority must sign this instruction, which is required for
+/// private pools.
+#[cfg(feature = "borsh")]
+pub fn deposit_stake_with_authority_and_slippage(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_deposit_authority: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    deposit_stake_address: &Pubkey,
+    deposit_stake_withdraw_authority: &Pubkey,
+    validator_stake_account: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    minimum_pool_tokens_out: u64,
+) -> Vec<Instruction> {
+    deposit_stake_internal(
+        program_id,
+        stake_pool,
+        validator_list_storage,
+        Some(stake_pool_deposit_authority),
+        stake_pool_withdraw_authority,
+        deposit_stake_address,
+        deposit_stake_withdraw_authority,
+        validator_stake_account,
+        reserve_stake_account,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        Some(minimum_pool_tokens_out),
+    )
+}
+
+/// Creates instructions required to deposit SOL directly into a stake pool.
+#[cfg(feature = "borsh")]
+fn deposit_sol_internal(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_from: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    sol_deposit_authority: Option<&Pubkey>,
+    lamports_in: u64,
+    minimum_pool_tokens_out: Option<u64>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*reserve_stake_account, false),
+        AccountMeta::new(*lamports_from, true),
+        AccountMeta::new(*pool_tokens_to, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*referrer_pool_tokens_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(sol_deposit_authority) = sol_deposit_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_deposit_authority, true));
+    }
+    if let Some(minimum_pool_tokens_out) = minimum_pool_tokens_out {
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data: borsh::to_vec(&StakePoolInstruction::DepositSolWithSlippage {
+                lamports_in,
+                minimum_pool_tokens_out,
+            })
+            .unwrap(),
+        }
+    } else {
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data: borsh::to_vec(&StakePoolInstruction::DepositSol(lamports_in)).unwrap(),
+        }
+    }
+}
+
+/// Creates instruction to deposit SOL directly into a stake pool.
+#[cfg(feature = "borsh")]
+pub fn deposit_sol(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_from: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    lamports_in: u64,
+) -> Instruction {
+    deposit_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        reserve_stake_account,
+        lamports_from,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        None,
+        lamports_in,
+        None,
+    )
+}
+
+/// Creates instruction to deposit SOL directly into a stake pool with slippage
+/// constraint.
+#[cfg(feature = "borsh")]
+pub fn deposit_sol_with_slippage(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_from: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    lamports_in: u64,
+    minimum_pool_tokens_out: u64,
+) -> Instruction {
+    deposit_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        reserve_stake_account,
+        lamports_from,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        None,
+        lamports_in,
+        Some(minimum_pool_tokens_out),
+    )
+}
+
+/// Creates instruction required to deposit SOL directly into a stake pool.
+/// The difference with `deposit_sol()` is that a deposit
+/// authority must sign this instruction.
+#[cfg(feature = "borsh")]
+pub fn deposit_sol_with_authority(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    sol_deposit_authority: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_from: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    lamports_in: u64,
+) -> Instruction {
+    deposit_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        reserve_stake_account,
+        lamports_from,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        Some(sol_deposit_authority),
+        lamports_in,
+        None,
+    )
+}
+
+/// Creates instruction to deposit SOL directly into a stake pool with slippage
+/// constraint.
+#[cfg(feature = "borsh")]
+pub fn deposit_sol_with_authority_and_slippage(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    sol_deposit_authority: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_from: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    lamports_in: u64,
+    minimum_pool_tokens_out: u64,
+) -> Instruction {
+    deposit_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        reserve_stake_account,
+        lamports_from,
+        pool_tokens_to,
+        manager_fee_account,
+        referrer_pool_tokens_account,
+        pool_mint,
+        token_program_id,
+        Some(sol_deposit_authority),
+        lamports_in,
+        Some(minimum_pool_tokens_out),
+    )
+}
+
+#[cfg(feature = "borsh")]
+fn withdraw_stake_internal(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    stake_to_split: &Pubkey,
+    stake_to_receive: &Pubkey,
+    user_stake_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    user_pool_token_account: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+    minimum_lamports_out: Option<u64>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new(*validator_list_storage, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw, false),
+        AccountMeta::new(*stake_to_split, false),
+        AccountMeta::new(*stake_to_receive, false),
+        AccountMeta::new_readonly(*user_stake_authority, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new(*user_pool_token_account, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    if let Some(minimum_lamports_out) = minimum_lamports_out {
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data: borsh::to_vec(&StakePoolInstruction::WithdrawStakeWithSlippage {
+                pool_tokens_in,
+                minimum_lamports_out,
+            })
+            .unwrap(),
+        }
+    } else {
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data: borsh::to_vec(&StakePoolInstruction::WithdrawStake(pool_tokens_in)).unwrap(),
+        }
+    }
+}
+
+/// Creates a `WithdrawStake` instruction.
+#[cfg(feature = "borsh")]
+pub fn withdraw_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    stake_to_split: &Pubkey,
+    stake_to_receive: &Pubkey,
+    user_stake_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    user_pool_token_account: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+) -> Instruction {
+    withdraw_stake_internal(
+        program_id,
+        stake_pool,
+        validator_list_storage,
+        stake_pool_withdraw,
+        stake_to_split,
+        stake_to_receive,
+        user_stake_authority,
+        user_transfer_authority,
+        user_pool_token_account,
+        manager_fee_account,
+        pool_mint,
+        token_program_id,
+        pool_tokens_in,
+        None,
+    )
+}
+
+/// Creates a `WithdrawStakeWithSlippage` instruction.
+#[cfg(feature = "borsh")]
+pub fn withdraw_stake_with_slippage(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    stake_to_split: &Pubkey,
+    stake_to_receive: &Pubkey,
+    user_stake_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    user_pool_token_account: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    withdraw_stake_internal(
+        program_id,
+        stake_pool,
+        validator_list_storage,
+        stake_pool_withdraw,
+        stake_to_split,
+        stake_to_receive,
+        user_stake_authority,
+        user_transfer_authority,
+        user_pool_token_account,
+        manager_fee_account,
+        pool_mint,
+        token_program_id,
+        pool_tokens_in,
+        Some(minimum_lamports_out),
+    )
+}
+
+#[cfg(feature = "borsh")]
+fn withdraw_sol_internal(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool_tokens_from: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    sol_withdraw_authority: Option<&Pubkey>,
+    pool_tokens_in: u64,
+    minimum_lamports_out: Option<u64>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new(*pool_tokens_from, false),
+        AccountMeta::new(*reserve_stake_account, false),
+        AccountMeta::new(*lamports_to, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(sol_withdraw_authority) = sol_withdraw_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_withdraw_authority, true));
+    }
+    if let Some(minimum_lamports_out) = minimum_lamports_out {
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data: borsh::to_vec(&StakePoolInstruction::WithdrawSolWithSlippage {
+                pool_tokens_in,
+                minimum_lamports_out,
+            })
+            .unwrap(),
+        }
+    } else {
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data: borsh::to_vec(&StakePoolInstruction::WithdrawSol(pool_tokens_in)).unwrap(),
+        }
+    }
+}
+
+/// Creates instruction required to withdraw SOL directly from a stake pool.
+#[cfg(feature = "borsh")]
+pub fn withdraw_sol(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool_tokens_from: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+) -> Instruction {
+    withdraw_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        user_transfer_authority,
+        pool_tokens_from,
+        reserve_stake_account,
+        lamports_to,
+        manager_fee_account,
+        pool_mint,
+        token_program_id,
+        None,
+        pool_tokens_in,
+        None,
+    )
+}
+
+/// Creates instruction required to withdraw SOL directly from a stake pool with
+/// slippage constraints.
+#[cfg(feature = "borsh")]
+pub fn withdraw_sol_with_slippage(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool_tokens_from: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    withdraw_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        user_transfer_authority,
+        pool_tokens_from,
+        reserve_stake_account,
+        lamports_to,
+        manager_fee_account,
+        pool_mint,
+        token_program_id,
+        None,
+        pool_tokens_in,
+        Some(minimum_lamports_out),
+    )
+}
+
+/// Creates instruction required to withdraw SOL directly from a stake pool.
+/// The difference with `withdraw_sol()` is that the sol withdraw authority
+/// must sign this instruction.
+#[cfg(feature = "borsh")]
+pub fn withdraw_sol_with_authority(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    sol_withdraw_authority: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool_tokens_from: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+) -> Instruction {
+    withdraw_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        user_transfer_authority,
+        pool_tokens_from,
+        reserve_stake_account,
+        lamports_to,
+        manager_fee_account,
+        pool_mint,
+        token_program_id,
+        Some(sol_withdraw_authority),
+        pool_tokens_in,
+        None,
+    )
+}
+
+/// Creates instruction required to withdraw SOL directly from a stake pool with
+/// a slippage constraint.
+/// The difference with `withdraw_sol()` is that the sol withdraw authority
+/// must sign this instruction.
+#[cfg(feature = "borsh")]
+pub fn withdraw_sol_with_authority_and_slippage(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    sol_withdraw_authority: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool_tokens_from: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    withdraw_sol_internal(
+        program_id,
+        stake_pool,
+        stake_pool_withdraw_authority,
+        user_transfer_authority,
+        pool_tokens_from,
+        reserve_stake_account,
+        lamports_to,
+        manager_fee_account,
+        pool_mint,
+        token_program_id,
+        Some(sol_withdraw_authority),
+        pool_tokens_in,
+        Some(minimum_lamports_out),
+    )
+}
+
+/// Creates a `SetManager` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_manager(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    new_manager: &Pubkey,
+    new_fee_receiver: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(*new_manager, true),
+        AccountMeta::new_readonly(*new_fee_receiver, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetManager).unwrap(),
+    }
+}
+
+/// Creates a `SetFee` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_fee(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    fee: FeeType,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetFee { fee }).unwrap(),
+    }
+}
+
+/// Creates a `SetCap` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_cap(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    cap: CapType,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetCap { cap }).unwrap(),
+    }
+}
+
+/// Creates a `RegisterReferrer` instruction.
+#[cfg(feature = "borsh")]
+pub fn register_referrer(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    referrer: &Pubkey,
+    authority: &Pubkey,
+    payout_token_account: &Pubkey,
+    payer: &Pubkey,
+    code: &str,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new(*referrer, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*payout_token_account, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::RegisterReferrer {
+            code: code.to_string(),
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates an `UpdateReferrer` instruction.
+#[cfg(feature = "borsh")]
+pub fn update_referrer(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    referrer: &Pubkey,
+    authority: &Pubkey,
+    payout_token_account: &Pubkey,
+    new_authority: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new(*referrer, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*payout_token_account, false),
+    ];
+    if let Some(new_authority) = new_authority {
+        accounts.push(AccountMeta::new_readonly(*new_authority, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdateReferrer).unwrap(),
+    }
+}
+
+/// Appends a referrer account to a deposit instruction, attributing the
+/// deposit to the referrer. The referral fee account of the deposit must be
+/// the referrer's payout account.
+#[cfg(feature = "borsh")]
+pub fn with_referrer(mut deposit_instruction: Instruction, referrer: &Pubkey) -> Instruction {
+    deposit_instruction
+        .accounts
+        .push(AccountMeta::new(*referrer, false));
+    deposit_instruction
+}
+
+/// Creates a `SetFeeSplit` instruction, each recipient taking its weight in
+/// basis points of every fee.
+#[cfg(feature = "borsh")]
+pub fn set_fee_split(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    payer: &Pubkey,
+    recipients: &[(Pubkey, u16)],
+) -> Instruction {
+    let (fee_split, _) = find_fee_split_program_address(program_id, stake_pool);
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(fee_split, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(
+        recipients
+            .iter()
+            .map(|(token_account, _)| AccountMeta::new_readonly(*token_account, false)),
+    );
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetFeeSplit {
+            weights_bps: recipients.iter().map(|(_, weight)| *weight).collect(),
+        })
+        .unwrap(),
+    }
+}
+
+/// Appends the fee split account of a stake pool and its recipient token
+/// accounts to an instruction paying fees, paying the recipients their share.
+/// `UpdateStakePoolBalance` requires them once the pool splits its fees, other
+/// instructions pay the whole fee to the manager without them. Any referrer
+/// account must be appended first.
+#[cfg(feature = "borsh")]
+pub fn with_fee_split(
+    mut instruction: Instruction,
+    stake_pool: &Pubkey,
+    recipients: &[Pubkey],
+) -> Instruction {
+    let (fee_split, _) = find_fee_split_program_address(&instruction.program_id, stake_pool);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(fee_split, false));
+    instruction.accounts.extend(
+        recipients
+            .iter()
+            .map(|recipient| AccountMeta::new(*recipient, false)),
+    );
+    instruction
+}
+
+/// Creates a `SetInsuranceFee` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_insurance_fee(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    payer: &Pubkey,
+    insurance_fee_bps: u16,
+) -> Instruction {
+    let (insurance_fund, _) = find_insurance_fund_program_address(program_id, stake_pool);
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(insurance_fund, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetInsuranceFee { insurance_fee_bps }).unwrap(),
+    }
+}
+
+/// Creates an `InjectInsuranceFund` instruction.
+#[cfg(feature = "borsh")]
+pub fn inject_insurance_fund(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    authority: &Pubkey,
+    reserve_stake: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let (insurance_fund, _) = find_insurance_fund_program_address(program_id, stake_pool);
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(insurance_fund, false),
+        AccountMeta::new(*reserve_stake, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::InjectInsuranceFund { lamports }).unwrap(),
+    }
+}
+
+/// Creates a `CreateRateHistory` instruction.
+#[cfg(feature = "borsh")]
+pub fn create_rate_history(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (rate_history, _) = find_rate_history_program_address(program_id, stake_pool);
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(rate_history, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::CreateRateHistory).unwrap(),
+    }
+}
+
+/// Creates a `CreateOracle` instruction.
+#[cfg(feature = "borsh")]
+pub fn create_oracle(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (oracle, _) = find_oracle_program_address(program_id, stake_pool);
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(oracle, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::CreateOracle).unwrap(),
+    }
+}
+
+/// Creates a `GetExchangeRate` instruction.
+#[cfg(feature = "borsh")]
+pub fn get_exchange_rate(program_id: &Pubkey, stake_pool: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(*stake_pool, false)],
+        data: borsh::to_vec(&StakePoolInstruction::GetExchangeRate).unwrap(),
+    }
+}
+
+/// Creates a `CreateTokenMetadataExtension` instruction.
+#[cfg(feature = "borsh")]
+pub fn create_token_metadata_extension(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    pool_mint: &Pubkey,
+    payer: &Pubkey,
+    token_program_id: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    let (stake_pool_withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool);
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(stake_pool_withdraw_authority, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::CreateTokenMetadataExtension {
+            name,
+            symbol,
+            uri,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates an `UpdateTokenMetadataExtensionField` instruction.
+#[cfg(feature = "borsh")]
+pub fn update_token_metadata_extension_field(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    pool_mint: &Pubkey,
+    payer: &Pubkey,
+    token_program_id: &Pubkey,
+    field: String,
+    value: String,
+) -> Instruction {
+    let (stake_pool_withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool);
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(stake_pool_withdraw_authority, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdateTokenMetadataExtensionField {
+            field,
+            value,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates a `RemoveTokenMetadataExtensionKey` instruction.
+#[cfg(feature = "borsh")]
+pub fn remove_token_metadata_extension_key(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    key: String,
+) -> Instruction {
+    let (stake_pool_withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool);
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(stake_pool_withdraw_authority, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::RemoveTokenMetadataExtensionKey { key })
+            .unwrap(),
+    }
+}
+
+/// Appends the direct stake account of a validator to an
+/// `IncreaseValidatorStake` or `IncreaseAdditionalValidatorStake`
+/// instruction, counting the increase towards the pending direct stake
+#[cfg(feature = "borsh")]
+pub fn with_direct_stake(
+    mut increase_instruction: Instruction,
+    direct_stake: &Pubkey,
+) -> Instruction {
+    increase_instruction
+        .accounts
+        .push(AccountMeta::new(*direct_stake, false));
+    increase_instruction
+}
+
+/// Creates a `DepositSolToValidator` instruction.
+#[cfg(feature = "borsh")]
+pub fn deposit_sol_to_validator(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    vote_account: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    lamports_from: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    sol_deposit_authority: Option<&Pubkey>,
+    lamports_in: u64,
+    minimum_pool_tokens_out: u64,
+) -> Instruction {
+    let (direct_stake, _) = find_direct_stake_program_address(program_id, stake_pool, vote_account);
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*validator_list_storage, false),
+        AccountMeta::new(direct_stake, false),
+        AccountMeta::new_readonly(*vote_account, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*reserve_stake_account, false),
+        AccountMeta::new(*lamports_from, true),
+        AccountMeta::new(*pool_tokens_to, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*referrer_pool_tokens_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(sol_deposit_authority) = sol_deposit_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_deposit_authority, true));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::DepositSolToValidator {
+            lamports_in,
+            minimum_pool_tokens_out,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates a `SetValidatorMaxShare` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_validator_max_share(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    validator_list: &Pubkey,
+    vote_account: &Pubkey,
+    max_share_bps: Option<u16>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new_readonly(*vote_account, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetValidatorMaxShare { max_share_bps }).unwrap(),
+    }
+}
+
+/// Appends the metadata account of a validator to an `AddValidatorToPool` or
+/// `RemoveValidatorFromPool` instruction. Adding a validator whose metadata
+/// does not exist yet creates it, with `rent_payer` paying its rent.
+#[cfg(feature = "borsh")]
+pub fn with_validator_metadata(
+    mut instruction: Instruction,
+    validator_metadata: &Pubkey,
+    rent_payer: Option<&Pubkey>,
+) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new(*validator_metadata, false));
+    if let Some(rent_payer) = rent_payer {
+        instruction
+            .accounts
+            .push(AccountMeta::new(*rent_payer, true));
+    }
+    instruction
+}
+
+/// Creates an `UpdateValidatorMetadata` instruction.
+#[cfg(feature = "borsh")]
+pub fn update_validator_metadata(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    staker: &Pubkey,
+    vote_account: &Pubkey,
+    notes_hash: [u8; 32],
+    max_commission: u8,
+) -> Instruction {
+    let (validator_metadata, _) =
+        find_validator_metadata_program_address(program_id, stake_pool, vote_account);
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*staker, true),
+        AccountMeta::new(validator_metadata, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdateValidatorMetadata {
+            notes_hash,
+            max_commission,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates a `DeactivateHighCommissionValidator` instruction.
+#[cfg(feature = "borsh")]
+pub fn deactivate_high_commission_validator(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    validator_list: &Pubkey,
+    stake_account: &Pubkey,
+    transient_stake_account: &Pubkey,
+    vote_account: &Pubkey,
+) -> Instruction {
+    let (validator_metadata, _) =
+        find_validator_metadata_program_address(program_id, stake_pool, vote_account);
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*stake_account, false),
+        AccountMeta::new(*transient_stake_account, false),
+        AccountMeta::new_readonly(*vote_account, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new(validator_metadata, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::DeactivateHighCommissionValidator).unwrap(),
+    }
+}
+
+/// Creates a `DeactivateDelinquentValidator` instruction.
+#[cfg(feature = "borsh")]
+pub fn deactivate_delinquent_validator(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    validator_list: &Pubkey,
+    reserve_stake: &Pubkey,
+    stake_account: &Pubkey,
+    transient_stake_account: &Pubkey,
+    vote_account: &Pubkey,
+    reward_recipient: &Pubkey,
+) -> Instruction {
+    let (validator_metadata, _) =
+        find_validator_metadata_program_address(program_id, stake_pool, vote_account);
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*stake_account, false),
+        AccountMeta::new(*transient_stake_account, false),
+        AccountMeta::new_readonly(*vote_account, false),
+        AccountMeta::new(*reward_recipient, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new(validator_metadata, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::DeactivateDelinquentValidator).unwrap(),
+    }
+}
+
+/// Creates a `SetFeeChangeDelay` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_fee_change_delay(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    delay_epochs: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetFeeChangeDelay { delay_epochs }).unwrap(),
+    }
+}
+
+/// Creates a `SetPauser` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_pauser(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    new_pauser: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+    if let Some(new_pauser) = new_pauser {
+        accounts.push(AccountMeta::new_readonly(*new_pauser, false))
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetPauser).unwrap(),
+    }
+}
+
+/// Creates a `SetPaused` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_paused(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager_or_pauser: &Pubkey,
+    paused_operations: u8,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager_or_pauser, true),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetPaused { paused_operations }).unwrap(),
+    }
+}
+
+/// Creates an `UpdatePaused` instruction.
+#[cfg(feature = "borsh")]
+pub fn update_paused(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager_or_pauser: &Pubkey,
+    pause: u8,
+    unpause: u8,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager_or_pauser, true),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdatePaused { pause, unpause }).unwrap(),
+    }
+}
+
+/// Creates a `ProposeManager` instruction, or cancels the pending proposal
+/// if `new_manager` is `None`.
+#[cfg(feature = "borsh")]
+pub fn propose_manager(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    new_manager: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+    if let Some(new_manager) = new_manager {
+        accounts.push(AccountMeta::new_readonly(*new_manager, false))
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::ProposeManager).unwrap(),
+    }
+}
+
+/// Creates an `AcceptManager` instruction.
+#[cfg(feature = "borsh")]
+pub fn accept_manager(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    pending_manager: &Pubkey,
+    new_fee_receiver: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*pending_manager, true),
+        AccountMeta::new_readonly(*new_fee_receiver, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::AcceptManager).unwrap(),
+    }
+}
+
+/// Creates a `ProposeStaker` instruction, or cancels the pending proposal if
+/// `new_staker` is `None`.
+#[cfg(feature = "borsh")]
+pub fn propose_staker(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    set_staker_authority: &Pubkey,
+    new_staker: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*set_staker_authority, true),
+    ];
+    if let Some(new_staker) = new_staker {
+        accounts.push(AccountMeta::new_readonly(*new_staker, false))
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::ProposeStaker).unwrap(),
+    }
+}
+
+/// Creates an `AcceptStaker` instruction.
+#[cfg(feature = "borsh")]
+pub fn accept_staker(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    pending_staker: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*pending_staker, true),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::AcceptStaker).unwrap(),
+    }
+}
+
+/// Creates a `SetStaker` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_staker(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    set_staker_authority: &Pubkey,
+    new_staker: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*set_staker_authority, true),
+        AccountMeta::new_readonly(*new_staker, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetStaker).unwrap(),
+    }
+}
+
+/// Creates a `SetFundingAuthority` instruction.
+#[cfg(feature = "borsh")]
+pub fn set_funding_authority(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    new_sol_deposit_authority: Option<&Pubkey>,
+    funding_type: FundingType,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+    if let Some(auth) = new_sol_deposit_authority {
+        accounts.push(AccountMeta::new_readonly(*auth, false))
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::SetFundingAuthority(funding_type)).unwrap(),
+    }
+}
+
+/// Creates an instruction to update metadata in the mpl token metadata program
+/// account for the pool token
+#[cfg(feature = "borsh")]
+pub fn update_token_metadata(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    pool_mint: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    let (stake_pool_withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool);
+    let (token_metadata, _) = find_metadata_account(pool_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(stake_pool_withdraw_authority, false),
+        AccountMeta::new(token_metadata, false),
+        AccountMeta::new_readonly(MPL_TOKEN_METADATA_ID, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::UpdateTokenMetadata { name, symbol, uri })
+            .unwrap(),
+    }
+}
+
+/// Creates an instruction to create metadata using the mpl token metadata
+/// program for the pool token
+#[cfg(feature = "borsh")]
+pub fn create_token_metadata(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    manager: &Pubkey,
+    pool_mint: &Pubkey,
+    payer: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    let (stake_pool_withdraw_authority, _) =
+        find_withdraw_authority_program_address(program_id, stake_pool);
+    let (token_metadata, _) = find_metadata_account(pool_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(stake_pool_withdraw_authority, false),
+        AccountMeta::new_readonly(*pool_mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(token_metadata, false),
+        AccountMeta::new_readonly(MPL_TOKEN_METADATA_ID, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::CreateTokenMetadata { name, symbol, uri })
+            .unwrap(),
+    }
+}
+
+/// Creates instruction required to deposit wSOL directly
+/// into a stake pool using a session signer.
+#[cfg(feature = "borsh")]
+pub fn deposit_wsol_with_session(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    withdraw_authority: &Pubkey,
+    reserve_stake: &Pubkey,
+    session_signer: &Pubkey,
+    pool_token_account: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    wsol_token_account: &Pubkey,
+    transient_wsol_account: &Pubkey,
+    program_signer: &Pubkey,
+    payer: &Pubkey,
+    user_wallet: &Pubkey,
+    sol_deposit_authority: Option<&Pubkey>,
+    lamports_in: u64,
+    minimum_pool_tokens_out: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*withdraw_authority, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new_readonly(*session_signer, true),
+        AccountMeta::new(*pool_token_account, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*referrer_pool_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(NATIVE_MINT_ID, false),
+        AccountMeta::new(*wsol_token_account, false),
+        AccountMeta::new(*transient_wsol_account, false),
+        AccountMeta::new(*program_signer, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*user_wallet, false),
+    ];
+
+    if let Some(sol_deposit_authority) = sol_deposit_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_deposit_authority, true));
+    }
+
+    accounts.push(AccountMeta::new_readonly(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        false,
+    ));
+
+    let data = borsh::to_vec(&StakePoolInstruction::DepositWsolWithSession {
+        lamports_in,
+        minimum_pool_tokens_out,
+    })
+    .unwrap();
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates instruction required to withdraw wSOL directly
+/// from a stake pool using a session signer.
+#[cfg(feature = "borsh")]
+pub fn withdraw_wsol_with_session(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    withdraw_authority: &Pubkey,
+    session_signer: &Pubkey,
+    burn_from_pool: &Pubkey,
+    reserve_stake: &Pubkey,
+    destination_wsol_account: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    program_signer: &Pubkey,
+    user_wallet: &Pubkey,
+    sol_withdraw_authority: Option<&Pubkey>,
+    pool_tokens_in: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*withdraw_authority, false),
+        AccountMeta::new_readonly(*session_signer, true),
+        AccountMeta::new(*burn_from_pool, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*destination_wsol_account, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(NATIVE_MINT_ID, false),
+        AccountMeta::new(*program_signer, false),
+        AccountMeta::new_readonly(*user_wallet, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    if let Some(sol_withdraw_authority) = sol_withdraw_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_withdraw_authority, true));
+    }
+
+    // Associated Token Program must be last - only needed in transaction for CPI routing
+    accounts.push(AccountMeta::new_readonly(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        false,
+    ));
+
+    let data = borsh::to_vec(&StakePoolInstruction::WithdrawWsolWithSession {
+        pool_tokens_in,
+        minimum_lamports_out,
+    })
+    .unwrap();
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates instruction required to withdraw SOL from a stake pool to the
+/// session user's wallet using a session signer.
+#[cfg(feature = "borsh")]
+pub fn withdraw_sol_with_session(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    withdraw_authority: &Pubkey,
+    session_signer: &Pubkey,
+    burn_from_pool: &Pubkey,
+    reserve_stake: &Pubkey,
+    user_wallet: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    program_signer: &Pubkey,
+    sol_withdraw_authority: Option<&Pubkey>,
+    pool_tokens_in: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*withdraw_authority, false),
+        AccountMeta::new_readonly(*session_signer, true),
+        AccountMeta::new(*burn_from_pool, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*user_wallet, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*program_signer, false),
+    ];
+
+    if let Some(sol_withdraw_authority) = sol_withdraw_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_withdraw_authority, true));
+    }
+
+    let data = borsh::to_vec(&StakePoolInstruction::WithdrawSolWithSession {
+        pool_tokens_in,
+        minimum_lamports_out,
+    })
+    .unwrap();
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates instruction to close deactivated user stake accounts using a
+/// session signer, returning their rent to the reserve.
+#[cfg(feature = "borsh")]
+pub fn close_user_stake_accounts_with_session(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    reserve_stake: &Pubkey,
+    session_signer: &Pubkey,
+    user_wallet: &Pubkey,
+    user_stake_seeds: Vec<u64>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*stake_pool, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*user_wallet, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(*session_signer, true),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    accounts.extend(user_stake_seeds.iter().map(|seed| {
+        AccountMeta::new(
+            find_user_stake_program_address(program_id, user_wallet, *seed).0,
+            false,
+        )
+    }));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::CloseUserStakeAccountsWithSession {
+            user_stake_seeds,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates a `WithdrawStakeMulti` instruction, withdrawing `pool_tokens_in`
+/// from each `(stake_to_split, stake_to_receive, pool_tokens_in)` entry of
+/// `withdrawals`
+#[cfg(feature = "borsh")]
+pub fn withdraw_stake_multi(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_withdraw: &Pubkey,
+    user_stake_authority: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    user_pool_token_account: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    withdrawals: &[(Pubkey, Pubkey, u64)],
+    minimum_lamports_out: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new(*validator_list_storage, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw, false),
+        AccountMeta::new_readonly(*user_stake_authority, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new(*user_pool_token_account, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    for (stake_to_split, stake_to_receive, _) in withdrawals {
+        accounts.push(AccountMeta::new(*stake_to_split, false));
+        accounts.push(AccountMeta::new(*stake_to_receive, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::WithdrawStakeMulti {
+            pool_tokens_in: withdrawals
+                .iter()
+                .map(|(_, _, pool_tokens_in)| *pool_tokens_in)
+                .collect(),
+            minimum_lamports_out,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates a `MigrateStakePool` instruction.
+#[cfg(feature = "borsh")]
+pub fn migrate_stake_pool(program_id: &Pubkey, stake_pool: &Pubkey, payer: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::MigrateStakePool).unwrap(),
+    }
+}
+
+/// Creates instruction to withdraw stake from a stake pool using a session signer.
+#[cfg(feature = "borsh")]
+pub fn withdraw_stake_with_session(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list: &Pubkey,
+    withdraw_authority: &Pubkey,
+    stake_to_split: &Pubkey,
+    stake_to_receive: &Pubkey,
+    session_signer: &Pubkey,
+    burn_from_pool: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    program_signer: &Pubkey,
+    reserve_stake: &Pubkey,
+    pool_tokens_in: u64,
+    minimum_lamports_out: u64,
+    user_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new(*validator_list, false),
+        AccountMeta::new_readonly(*withdraw_authority, false),
+        AccountMeta::new(*stake_to_split, false),
+        AccountMeta::new(*stake_to_receive, false),
+        AccountMeta::new_readonly(*session_signer, true), // user_stake_authority (signer_or_session)
+        AccountMeta::new_readonly(*session_signer, false), // user_transfer_authority (not used in session path)
+        AccountMeta::new(*burn_from_pool, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new_readonly(*program_signer, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+    ];
+
+    let data = borsh::to_vec(&StakePoolInstruction::WithdrawStakeWithSession {
+        pool_tokens_in,
+        minimum_lamports_out,
+        user_stake_seed,
+    })
+    .unwrap();
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates instruction to withdraw lamports from a user stake account using a session signer.
+/// Used after the cooldown period to convert deactivated stake to SOL.
+#[cfg(feature = "borsh")]
+pub fn withdraw_from_stake_account_with_session(
+    program_id: &Pubkey,
+    user_stake_account: &Pubkey,
+    recipient: &Pubkey,
+    session_signer: &Pubkey,
+    stake_pool: &Pubkey,
+    lamports: u64,
+    user_stake_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*user_stake_account, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(*session_signer, true),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new_readonly(*stake_pool, false),
+    ];
+
+    let data = borsh::to_vec(&StakePoolInstruction::WithdrawFromStakeAccountWithSession {
+        lamports,
+        user_stake_seed,
+    })
+    .unwrap();
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates instruction to burn pool tokens in exchange for a withdrawal ticket
+#[cfg(feature = "borsh")]
+pub fn request_withdrawal_ticket(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    pool_tokens_from: &Pubkey,
+    manager_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    withdrawal_ticket: &Pubkey,
+    payer: &Pubkey,
+    token_program_id: &Pubkey,
+    pool_tokens_in: u64,
+    ticket_seed: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new(*pool_tokens_from, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new(*withdrawal_ticket, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::RequestWithdrawalTicket {
+            pool_tokens_in,
+            ticket_seed,
+        })
+        .unwrap(),
+    }
+}
+
+/// Creates instruction to claim the lamports of a withdrawal ticket
+#[cfg(feature = "borsh")]
+pub fn claim_withdrawal_ticket(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    reserve_stake: &Pubkey,
+    withdrawal_ticket: &Pubkey,
+    owner: &Pubkey,
+    lamports_to: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*reserve_stake, false),
+        AccountMeta::new(*withdrawal_ticket, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*lamports_to, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+    ];
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: borsh::to_vec(&StakePoolInstruction::ClaimWithdrawalTicket).unwrap(),
+    }
+}
+
+/// Creates instruction to deposit a user stake account into a stake pool using
+/// a session signer, with slippage.
+/// If the pool has a custom stake deposit authority, it must sign.
+#[cfg(feature = "borsh")]
+pub fn deposit_stake_with_session(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    validator_list_storage: &Pubkey,
+    stake_pool_deposit_authority: Option<&Pubkey>,
+    stake_pool_withdraw_authority: &Pubkey,
+    user_stake_account: &Pubkey,
+    validator_stake_account: &Pubkey,
+    reserve_stake_account: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    manager_fee_account: &Pubkey,
+    referrer_pool_tokens_account: &Pubkey,
+    pool_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    session_signer: &Pubkey,
+    minimum_pool_tokens_out: u64,
+    user_stake_seed: u64,
+) -> Instruction {
+    let stake_pool_deposit_authority = stake_pool_deposit_authority.map_or_else(
+        || {
+            AccountMeta::new_readonly(
+                find_deposit_authority_program_address(program_id, stake_pool).0,
+                false,
+            )
+        },
+        |authority| AccountMeta::new_readonly(*authority, true),
+    );
+    let accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new(*validator_list_storage, false),
+        stake_pool_deposit_authority,
+        AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+        AccountMeta::new(*user_stake_account, false),
+        AccountMeta::new(*validator_stake_account, false),
+        AccountMeta::new(*reserve_stake_account, false),
+        AccountMeta::new(*pool_tokens_to, false),
+        AccountMeta::new(*manager_fee_account, false),
+        AccountMeta::new(*referrer_pool_tokens_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(stake::program::id(), false),
+        AccountMeta::new_readonly(*session_signer, true),
+    ];
+
+    let data = borsh::to_vec(&StakePoolInstruction::DepositStakeWithSession {
+        minimum_pool_tokens_out,
+        user_stake_seed,
+    })
+    .unwrap();
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
@@ -2,8 +2,10 @@
 
 //! Fogo Stake Pool Interface
 //!
-//! This crate provides the state types for deserializing Fogo Stake Pool accounts
-//! without pulling in the full program dependencies.
+//! This crate provides the state and instruction types for interacting with
+//! Fogo Stake Pool accounts without pulling in the full program dependencies.
+//! The on-chain program uses these same definitions, so they are the single
+//! source of truth for the account layouts and the instruction set.
 //!
 //! # Example
 //!
@@ -23,22 +25,38 @@
 #[cfg(feature = "codama")]
 use codama_macros::codama;
 
+#[cfg(feature = "borsh")]
+pub mod big_vec;
 pub mod error;
+pub mod event;
+pub mod instruction;
 pub mod pda;
 pub mod state;
 
 // Re-export commonly used types at the crate root
 pub use state::{
-    AccountType, Fee, FeeType, FutureEpoch, FutureEpochFee, StakePool, StakeStatus, ValidatorList,
-    ValidatorListHeader, ValidatorStakeInfo,
+    AccountType, CapType, DirectStake, Fee, FeeType, FutureEpoch, FutureEpochFee,
+    FutureEpochReferralFee, PausedOperation, Referrer, StakePool, StakeStatus, ValidatorList,
+    ValidatorListHeader, ValidatorStakeInfo, WithdrawalTicket,
 };
 
+// Re-export event types at the crate root
+#[cfg(all(feature = "borsh", not(target_os = "solana")))]
+pub use event::parse_events;
+pub use event::{AuthorityType, StakePoolEvent};
+
+// Re-export instruction types at the crate root
+pub use instruction::{FundingType, PreferredValidatorType, StakePoolInstruction};
+
 // Re-export PDA functions at the crate root for convenience
 pub use pda::{
     check_program_account, find_deposit_authority_program_address,
-    find_ephemeral_stake_program_address, find_stake_program_address,
-    find_transient_stake_program_address, find_user_stake_program_address,
-    find_withdraw_authority_program_address,
+    find_direct_stake_program_address, find_ephemeral_stake_program_address,
+    find_fee_split_program_address, find_insurance_fund_program_address,
+    find_oracle_program_address, find_rate_history_program_address, find_referrer_program_address,
+    find_stake_program_address, find_transient_stake_program_address,
+    find_user_stake_program_address, find_validator_metadata_program_address,
+    find_withdraw_authority_program_address, find_withdrawal_ticket_program_address,
 };
 
 /// Program module with the program ID
@@ -64,6 +82,53 @@ pub const EPHEMERAL_STAKE_SEED_PREFIX: &[u8] = b"ephemeral";
 /// Seed for user stake account created during session withdrawal
 pub const USER_STAKE_SEED_PREFIX: &[u8] = b"user_stake";
 
+/// Seed for withdrawal ticket account
+pub const WITHDRAWAL_TICKET_SEED_PREFIX: &[u8] = b"withdrawal_ticket";
+
+/// Seed for referrer account
+pub const REFERRER_SEED_PREFIX: &[u8] = b"referrer";
+
+/// Maximum length of a referral code, in bytes
+pub const MAX_REFERRAL_CODE_LENGTH: usize = 32;
+
+/// Seed for direct stake account
+pub const DIRECT_STAKE_SEED_PREFIX: &[u8] = b"direct_stake";
+
+/// Seed for validator metadata account
+pub const VALIDATOR_METADATA_SEED_PREFIX: &[u8] = b"validator_metadata";
+
+/// Seed for fee split account
+pub const FEE_SPLIT_SEED_PREFIX: &[u8] = b"fee_split";
+
+/// Maximum number of token accounts the fees of a pool can be split across,
+/// on top of the manager fee account
+pub const MAX_FEE_SPLIT_RECIPIENTS: usize = 8;
+
+/// Seed for insurance fund account
+pub const INSURANCE_FUND_SEED_PREFIX: &[u8] = b"insurance_fund";
+
+/// Seed for exchange rate history account
+pub const RATE_HISTORY_SEED_PREFIX: &[u8] = b"rate_history";
+
+/// Epochs kept by the exchange rate history. The account is created by the
+/// program through the system program, and an account created that way may
+/// hold at most 10 KiB (`MAX_PERMITTED_DATA_INCREASE`), so 250 entries of 40
+/// bytes are the most that fit.
+pub const RATE_HISTORY_LEN: usize = 250;
+
+/// Seed for pool token price oracle account
+pub const ORACLE_SEED_PREFIX: &[u8] = b"oracle";
+
+/// Maximum commission of a validator, as a percentage
+pub const MAX_COMMISSION: u8 = 100;
+
+/// Lamports paid from the reserve to whoever deactivates a delinquent
+/// validator
+pub const DELINQUENT_VALIDATOR_CRANK_REWARD: u64 = 10_000;
+
+/// Basis points making up a whole, for shares of the pool
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
 /// Minimum amount of staked lamports required in a validator stake account to
 /// allow for merges without a mismatch on credits observed
 pub const MINIMUM_ACTIVE_STAKE: u64 = 1_000_000;
@@ -83,6 +148,11 @@ pub const MAX_TRANSIENT_STAKE_ACCOUNTS: usize = 10;
 /// for stake withdrawals to still work
 pub const MAX_VALIDATORS_IN_POOL: u32 = 20_000;
 
+/// Size of a stake pool account with the current layout. Fields are only
+/// appended to the stake pool, growing this size, so that accounts created
+/// with an earlier layout are shorter and can be migrated.
+pub const STAKE_POOL_LEN: usize = 918;
+
 /// Maximum factor by which a withdrawal fee can be increased per epoch,
 /// protecting stakers from malicious fee increases.
 /// If current fee is 0, `WITHDRAWAL_BASELINE_FEE` is used as the baseline.
@@ -96,3 +166,24 @@ pub const WITHDRAWAL_BASELINE_FEE: Fee = Fee {
     numerator: 1,
     denominator: 1000,
 };
+
+/// Maximum factor by which a deposit fee can be increased per fee change,
+/// protecting depositors from sudden deposit fee spikes.
+/// If current fee is 0, `DEPOSIT_BASELINE_FEE` is used as the baseline.
+pub const MAX_DEPOSIT_FEE_INCREASE: Fee = Fee {
+    numerator: 3,
+    denominator: 2,
+};
+
+/// Drop-in baseline fee when evaluating deposit fee increases when fee is 0
+pub const DEPOSIT_BASELINE_FEE: Fee = Fee {
+    numerator: 1,
+    denominator: 1000,
+};
+
+/// Maximum number of epochs that fee changes can be held back for, on top of
+/// the two epochs every fee change waits
+pub const MAX_FEE_CHANGE_DELAY_EPOCHS: u64 = 30;
+
+/// Maximum exponent of the SOL withdrawal fee curve
+pub const MAX_SOL_WITHDRAWAL_FEE_CURVE_EXPONENT: u8 = 4;
@@ -4,8 +4,11 @@
 
 use {
     crate::{
-        AUTHORITY_DEPOSIT, AUTHORITY_WITHDRAW, EPHEMERAL_STAKE_SEED_PREFIX,
-        TRANSIENT_STAKE_SEED_PREFIX, USER_STAKE_SEED_PREFIX,
+        AUTHORITY_DEPOSIT, AUTHORITY_WITHDRAW, DIRECT_STAKE_SEED_PREFIX,
+        EPHEMERAL_STAKE_SEED_PREFIX, FEE_SPLIT_SEED_PREFIX, INSURANCE_FUND_SEED_PREFIX,
+        ORACLE_SEED_PREFIX, RATE_HISTORY_SEED_PREFIX, REFERRER_SEED_PREFIX,
+        TRANSIENT_STAKE_SEED_PREFIX, USER_STAKE_SEED_PREFIX, VALIDATOR_METADATA_SEED_PREFIX,
+        WITHDRAWAL_TICKET_SEED_PREFIX,
     },
     solana_program::pubkey::Pubkey,
     std::num::NonZeroU32,
@@ -113,6 +116,140 @@ pub fn find_user_stake_program_address(
     )
 }
 
+/// Generates the withdrawal ticket PDA for a delayed SOL withdrawal.
+///
+/// This PDA is derived from the stake pool, the ticket owner and a unique
+/// seed, allowing an owner to hold several outstanding tickets per pool.
+pub fn find_withdrawal_ticket_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    owner: &Pubkey,
+    seed: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            WITHDRAWAL_TICKET_SEED_PREFIX,
+            stake_pool_address.as_ref(),
+            owner.as_ref(),
+            &seed.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Generates the referrer PDA for a referral code.
+///
+/// This PDA is derived from the stake pool and the referral code, so each
+/// code can only be registered once per pool.
+pub fn find_referrer_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    code: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REFERRER_SEED_PREFIX,
+            stake_pool_address.as_ref(),
+            code.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Generates the direct stake PDA for a validator in the stake pool.
+///
+/// This PDA is derived from the stake pool and the validator vote account,
+/// so deposits made for the same validator share one account.
+pub fn find_direct_stake_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DIRECT_STAKE_SEED_PREFIX,
+            stake_pool_address.as_ref(),
+            vote_account_address.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Generates the validator metadata PDA for a validator in the stake pool.
+///
+/// This PDA is derived from the stake pool and the validator vote account,
+/// and is created when the validator is added to the pool.
+pub fn find_validator_metadata_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VALIDATOR_METADATA_SEED_PREFIX,
+            stake_pool_address.as_ref(),
+            vote_account_address.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Generates the fee split PDA for the stake pool.
+///
+/// This PDA is derived from the stake pool, and is created when the manager
+/// first splits the pool's fees.
+pub fn find_fee_split_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FEE_SPLIT_SEED_PREFIX, stake_pool_address.as_ref()],
+        program_id,
+    )
+}
+
+/// Generates the insurance fund PDA for the stake pool.
+///
+/// This PDA is derived from the stake pool, and is created when the manager
+/// first sets the insurance fee.
+pub fn find_insurance_fund_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[INSURANCE_FUND_SEED_PREFIX, stake_pool_address.as_ref()],
+        program_id,
+    )
+}
+
+/// Generates the exchange rate history PDA for the stake pool.
+///
+/// This PDA is derived from the stake pool, and is created when the manager
+/// turns on exchange rate recording.
+pub fn find_rate_history_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RATE_HISTORY_SEED_PREFIX, stake_pool_address.as_ref()],
+        program_id,
+    )
+}
+
+/// Generates the pool token price oracle PDA for the stake pool.
+///
+/// This PDA is derived from the stake pool, and is created when the manager
+/// sets up the price oracle.
+pub fn find_oracle_program_address(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ORACLE_SEED_PREFIX, stake_pool_address.as_ref()],
+        program_id,
+    )
+}
+
 /// Checks that the supplied program ID is correct for the Fogo Stake Pool
 pub fn check_program_account(
     program_id: &Pubkey,
@@ -174,4 +311,46 @@ mod tests {
         let (pda, _bump) = find_user_stake_program_address(&id(), &user, 0);
         assert!(!pda.is_on_curve());
     }
+
+    #[test]
+    fn test_withdrawal_ticket_derivation() {
+        let stake_pool = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let (pda, _bump) = find_withdrawal_ticket_program_address(&id(), &stake_pool, &owner, 0);
+        assert!(!pda.is_on_curve());
+        let (other, _bump) = find_withdrawal_ticket_program_address(&id(), &stake_pool, &owner, 1);
+        assert_ne!(pda, other);
+    }
+
+    #[test]
+    fn test_referrer_derivation() {
+        let stake_pool = Pubkey::new_unique();
+        let (pda, _bump) = find_referrer_program_address(&id(), &stake_pool, "xlabstest");
+        assert!(!pda.is_on_curve());
+        let (other, _bump) = find_referrer_program_address(&id(), &stake_pool, "xlabs");
+        assert_ne!(pda, other);
+    }
+
+    #[test]
+    fn test_direct_stake_derivation() {
+        let stake_pool = Pubkey::new_unique();
+        let vote_account = Pubkey::new_unique();
+        let (pda, _bump) = find_direct_stake_program_address(&id(), &stake_pool, &vote_account);
+        assert!(!pda.is_on_curve());
+        let (other, _bump) =
+            find_direct_stake_program_address(&id(), &stake_pool, &Pubkey::new_unique());
+        assert_ne!(pda, other);
+    }
+
+    #[test]
+    fn test_validator_metadata_derivation() {
+        let stake_pool = Pubkey::new_unique();
+        let vote_account = Pubkey::new_unique();
+        let (pda, _bump) =
+            find_validator_metadata_program_address(&id(), &stake_pool, &vote_account);
+        assert!(!pda.is_on_curve());
+        let (direct_stake, _bump) =
+            find_direct_stake_program_address(&id(), &stake_pool, &vote_account);
+        assert_ne!(pda, direct_stake);
+    }
 }
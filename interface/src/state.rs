@@ -8,11 +8,28 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 #[cfg(feature = "codama")]
 use codama_macros::CodamaType;
 
+#[cfg(feature = "borsh")]
+use {
+    crate::{big_vec::BigVec, STAKE_POOL_LEN},
+    solana_program::{
+        borsh1::{get_instance_packed_len, try_from_slice_unchecked},
+        entrypoint::MAX_PERMITTED_DATA_INCREASE,
+    },
+    std::borrow::Borrow,
+};
 use {
+    crate::{
+        error::StakePoolError, DEPOSIT_BASELINE_FEE, MAX_BASIS_POINTS, MAX_DEPOSIT_FEE_INCREASE,
+        MAX_FEE_CHANGE_DELAY_EPOCHS, MAX_FEE_SPLIT_RECIPIENTS, MAX_REFERRAL_CODE_LENGTH,
+        MAX_SOL_WITHDRAWAL_FEE_CURVE_EXPONENT, MAX_WITHDRAWAL_FEE_INCREASE,
+        MINIMUM_RESERVE_LAMPORTS, RATE_HISTORY_LEN, WITHDRAWAL_BASELINE_FEE,
+    },
     bytemuck::{Pod, Zeroable},
     num_derive::{FromPrimitive, ToPrimitive},
     num_traits::{FromPrimitive, ToPrimitive},
     solana_program::{
+        account_info::AccountInfo,
+        msg,
         program_error::ProgramError,
         program_memory::sol_memcmp,
         program_pack::{Pack, Sealed},
@@ -38,6 +55,22 @@ pub enum AccountType {
     StakePool,
     /// Validator stake list
     ValidatorList,
+    /// Withdrawal ticket
+    WithdrawalTicket,
+    /// Referrer
+    Referrer,
+    /// Direct stake requested for a validator
+    DirectStake,
+    /// Metadata of a validator in the pool
+    ValidatorMetadata,
+    /// Split of the pool's fees across several token accounts
+    FeeSplit,
+    /// Insurance fund covering validator stake losses
+    InsuranceFund,
+    /// Exchange rate history of a stake pool
+    RateHistory,
+    /// Price oracle of a stake pool's token
+    Oracle,
 }
 
 /// Initialized program details.
@@ -156,9 +189,142 @@ pub struct StakePool {
 
     /// Last epoch's total lamports, used only for APR estimation
     pub last_epoch_total_lamports: u64,
+
+    /// Lamports owed to unclaimed withdrawal tickets.
+    /// These lamports are excluded from `total_lamports`, locking in the
+    /// exchange rate at the time each ticket was requested
+    pub pending_withdrawal_lamports: u64,
+
+    /// Portion of `pending_withdrawal_lamports` unstaked into the reserve by
+    /// the staker, set aside to pay out claimable withdrawal tickets
+    pub withdrawal_claim_pool_lamports: u64,
+
+    /// Maximum lamports that may be deposited, as SOL or stake, in a single
+    /// epoch, or `None` for no limit
+    pub max_deposit_lamports_per_epoch: Option<u64>,
+
+    /// Maximum lamports that may be withdrawn as SOL, directly or through
+    /// withdrawal tickets, in a single epoch, or `None` for no limit
+    pub max_sol_withdrawal_lamports_per_epoch: Option<u64>,
+
+    /// Maximum `total_lamports` that deposits may bring the pool up to, or
+    /// `None` for no limit
+    pub max_total_lamports: Option<u64>,
+
+    /// Epoch that `epoch_deposited_lamports` and
+    /// `epoch_sol_withdrawn_lamports` are being tracked for
+    pub flow_epoch: u64,
+
+    /// Lamports deposited during `flow_epoch`
+    pub epoch_deposited_lamports: u64,
+
+    /// Lamports withdrawn as SOL during `flow_epoch`
+    pub epoch_sol_withdrawn_lamports: u64,
+
+    /// Authority allowed to pause and unpause operations, in addition to the
+    /// manager
+    pub pauser: Option<Pubkey>,
+
+    /// Bitmask of the `PausedOperation`s that are currently paused
+    pub paused_operations: u8,
+
+    /// Manager proposed by the current manager, who must sign to take over
+    pub pending_manager: Option<Pubkey>,
+
+    /// Staker proposed by the current manager or staker, who must sign to
+    /// take over
+    pub pending_staker: Option<Pubkey>,
+
+    /// Future stake deposit fee, to be set for the following epoch
+    pub next_stake_deposit_fee: FutureEpochFee,
+
+    /// Future SOL deposit fee, to be set for the following epoch
+    pub next_sol_deposit_fee: FutureEpochFee,
+
+    /// Future stake deposit referral fee, to be set for the following epoch
+    pub next_stake_referral_fee: FutureEpochReferralFee,
+
+    /// Future SOL deposit referral fee, to be set for the following epoch
+    pub next_sol_referral_fee: FutureEpochReferralFee,
+
+    /// Number of epochs after the latest fee change during which pending
+    /// fees are held back before starting their countdown
+    pub fee_change_delay_epochs: u64,
+
+    /// Shorter `fee_change_delay_epochs`, applied once the current delay has
+    /// passed
+    pub next_fee_change_delay_epochs: Option<u64>,
+
+    /// Epoch of the latest fee change
+    pub fee_change_epoch: u64,
+
+    /// Maximum share of `total_lamports`, in basis points, that a validator's
+    /// stake and pending direct stake may reach through validator-targeted
+    /// SOL deposits, or `None` if such deposits are not accepted
+    pub max_direct_stake_bps: Option<u16>,
+
+    /// Maximum share of `total_lamports`, in basis points, that any single
+    /// validator's stake may reach through stake increases and deposits
+    pub max_validator_share_bps: Option<u16>,
+
+    /// Maximum commission, as a percentage, a validator's vote account may
+    /// charge before anyone can start deactivating the validator, or `None`
+    /// for no pool-wide limit
+    pub max_validator_commission: Option<u8>,
+
+    /// Number of epochs a validator's vote account may go without earning
+    /// vote credits before anyone can start deactivating the validator, or
+    /// `None` to leave delinquent validators to the staker
+    pub max_validator_vote_age_epochs: Option<u64>,
+
+    /// Curve raising the SOL withdrawal fee above `sol_withdrawal_fee` as
+    /// the reserve drains below its target share of the pool
+    pub sol_withdrawal_fee_curve: SolWithdrawalFeeCurve,
+
+    /// Future SOL withdrawal fee curve, to be set for the following epoch
+    pub next_sol_withdrawal_fee_curve: FutureEpochSolWithdrawalFeeCurve,
+
+    /// Whether the fees are split across the recipients of the pool's fee
+    /// split account, which must then be passed to every instruction paying
+    /// fees
+    pub has_fee_split: bool,
+
+    /// Share of the epoch fee, in basis points, paid in lamports to the
+    /// insurance fund instead of the manager
+    pub insurance_fee_bps: u16,
+
+    /// Validator stake losses detected while updating the validator list and
+    /// not yet covered by the insurance fund
+    pub uncovered_loss_lamports: u64,
+
+    /// Whether the pool records its exchange rate, in which case
+    /// `UpdateStakePoolBalance` should be passed the rate history account
+    pub has_rate_history: bool,
+
+    /// Whether the pool keeps a price oracle for its token, in which case
+    /// `UpdateStakePoolBalance` should be passed the oracle account
+    pub has_oracle: bool,
+
+    /// Share of the epoch fee owed to the insurance fund but not yet moved
+    /// out of the reserve, which is excluded from `total_lamports`
+    pub pending_insurance_lamports: u64,
 }
 
 impl StakePool {
+    /// Deserializes a stake pool account.
+    ///
+    /// Fields are only ever appended to the stake pool, so an account created
+    /// with an earlier layout is shorter than `STAKE_POOL_LEN`. Such an
+    /// account fails with `StakePoolNeedsMigration` until `MigrateStakePool`
+    /// rewrites it with the current layout.
+    #[cfg(feature = "borsh")]
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.first() == Some(&(AccountType::StakePool as u8)) && data.len() < STAKE_POOL_LEN {
+            return Err(StakePoolError::StakePoolNeedsMigration.into());
+        }
+        try_from_slice_unchecked::<Self>(data).map_err(Into::into)
+    }
+
     /// Calculate the pool tokens that should be minted for a deposit of
     /// `stake_lamports`
     #[inline]
@@ -198,6 +364,82 @@ impl StakePool {
         u64::try_from(self.sol_withdrawal_fee.apply(pool_tokens)?).ok()
     }
 
+    /// Calculate pool tokens to be deducted as SOL withdrawal fees: the flat
+    /// `sol_withdrawal_fee`, rising towards the curve's `max_fee` with the
+    /// shortfall of the reserve liquidity left after the withdrawal below
+    /// the curve's target share of `total_lamports`, raised to the curve's
+    /// exponent.
+    ///
+    /// `reserve_liquidity_lamports` is what the reserve can pay out before
+    /// the withdrawal, over its minimum balance and the withdrawal claim pool
+    pub fn calc_dynamic_sol_withdrawal_fee(
+        &self,
+        pool_tokens: u64,
+        reserve_liquidity_lamports: u64,
+    ) -> Option<u64> {
+        let curve = &self.sol_withdrawal_fee_curve;
+        let base_fee = self.calc_pool_tokens_sol_withdrawal_fee(pool_tokens)?;
+        let max_fee = u64::try_from(curve.max_fee.apply(pool_tokens)?).ok()?;
+        if curve.target_reserve_bps == 0 || max_fee <= base_fee {
+            return Some(base_fee);
+        }
+        let max_bps = u128::from(MAX_BASIS_POINTS);
+        let target_lamports = u128::from(self.total_lamports)
+            .checked_mul(u128::from(curve.target_reserve_bps))?
+            .checked_div(max_bps)?;
+        // the withdrawal is measured before its fee, so splitting it up does
+        // not lower the fee
+        let withdraw_lamports = self.calc_lamports_withdraw_amount(pool_tokens)?;
+        let remaining_lamports =
+            u128::from(reserve_liquidity_lamports.saturating_sub(withdraw_lamports));
+        if remaining_lamports >= target_lamports {
+            return Some(base_fee);
+        }
+        let shortfall_bps = target_lamports
+            .checked_sub(remaining_lamports)?
+            .checked_mul(max_bps)?
+            .checked_div(target_lamports)?;
+        let mut scale_bps = max_bps;
+        for _ in 0..curve.exponent {
+            scale_bps = scale_bps.checked_mul(shortfall_bps)?.checked_div(max_bps)?;
+        }
+        let curve_fee = u64::try_from(
+            u128::from(max_fee - base_fee)
+                .checked_mul(scale_bps)?
+                .checked_div(max_bps)?,
+        )
+        .ok()?;
+        base_fee.checked_add(curve_fee)
+    }
+
+    /// Preview the lamports received for withdrawing `pool_tokens` as SOL,
+    /// after the dynamic SOL withdrawal fee, or `None` if the reserve cannot
+    /// pay them out
+    pub fn preview_sol_withdrawal(
+        &self,
+        pool_tokens: u64,
+        reserve_liquidity_lamports: u64,
+    ) -> Option<u64> {
+        let pool_tokens_fee =
+            self.calc_dynamic_sol_withdrawal_fee(pool_tokens, reserve_liquidity_lamports)?;
+        let withdraw_lamports =
+            self.calc_lamports_withdraw_amount(pool_tokens.checked_sub(pool_tokens_fee)?)?;
+        (withdraw_lamports <= reserve_liquidity_lamports).then_some(withdraw_lamports)
+    }
+
+    /// Lamports the reserve can pay out for SOL withdrawals, given its
+    /// balance and the rent-exempt reserve of the stake account
+    #[inline]
+    pub fn calc_reserve_liquidity_lamports(
+        &self,
+        reserve_lamports: u64,
+        reserve_rent_exempt_lamports: u64,
+    ) -> u64 {
+        reserve_lamports
+            .saturating_sub(reserve_rent_exempt_lamports.saturating_add(MINIMUM_RESERVE_LAMPORTS))
+            .saturating_sub(self.withdrawal_claim_pool_lamports)
+    }
+
     /// Calculate pool tokens to be deducted as stake deposit fees
     #[inline]
     pub fn calc_pool_tokens_stake_deposit_fee(&self, pool_tokens_minted: u64) -> Option<u64> {
@@ -238,13 +480,46 @@ impl StakePool {
     /// to the stake pool's `total_lamports`
     #[inline]
     pub fn calc_epoch_fee_amount(&self, reward_lamports: u64) -> Option<u64> {
+        self.calc_epoch_fee_amount_after_insurance(reward_lamports, 0)
+    }
+
+    /// Calculate the lamports of the epoch fee on `reward_lamports` owed to
+    /// the insurance fund, rounded down
+    #[inline]
+    pub fn calc_insurance_fee_lamports(&self, reward_lamports: u64) -> Option<u64> {
+        u64::try_from(
+            self.epoch_fee
+                .apply(reward_lamports)?
+                .checked_mul(self.insurance_fee_bps as u128)?
+                .checked_div(MAX_BASIS_POINTS as u128)?,
+        )
+        .ok()
+    }
+
+    /// Calculate the fee in pool tokens that goes to the manager, once
+    /// `insurance_lamports` of the epoch fee were paid out of the pool to the
+    /// insurance fund
+    ///
+    /// This function assumes that `reward_lamports` has not already been added
+    /// to the stake pool's `total_lamports`
+    #[inline]
+    pub fn calc_epoch_fee_amount_after_insurance(
+        &self,
+        reward_lamports: u64,
+        insurance_lamports: u64,
+    ) -> Option<u64> {
         if reward_lamports == 0 {
             return Some(0);
         }
-        let total_lamports = (self.total_lamports as u128).checked_add(reward_lamports as u128)?;
-        let fee_lamports = self.epoch_fee.apply(reward_lamports)?;
+        let total_lamports = (self.total_lamports as u128)
+            .checked_add(reward_lamports as u128)?
+            .checked_sub(insurance_lamports as u128)?;
+        let fee_lamports = self
+            .epoch_fee
+            .apply(reward_lamports)?
+            .checked_sub(insurance_lamports as u128)?;
         if total_lamports == fee_lamports || self.pool_token_supply == 0 {
-            Some(reward_lamports)
+            reward_lamports.checked_sub(insurance_lamports)
         } else {
             u64::try_from(
                 (self.pool_token_supply as u128)
@@ -255,6 +530,16 @@ impl StakePool {
         }
     }
 
+    /// Calculate how many of `unstaked_lamports` should be set aside in the
+    /// withdrawal claim pool, covering pending tickets that are not yet funded
+    #[inline]
+    pub fn calc_withdrawal_claim_pool_funding(&self, unstaked_lamports: u64) -> Option<u64> {
+        let unfunded_lamports = self
+            .pending_withdrawal_lamports
+            .checked_sub(self.withdrawal_claim_pool_lamports)?;
+        Some(unstaked_lamports.min(unfunded_lamports))
+    }
+
     /// Get the current value of pool tokens, rounded up
     #[inline]
     pub fn get_lamports_per_pool_token(&self) -> Option<u64> {
@@ -264,6 +549,216 @@ impl StakePool {
             .checked_div(self.pool_token_supply)
     }
 
+    /// Checks that the withdraw or deposit authority is valid
+    fn check_program_derived_authority(
+        authority_address: &Pubkey,
+        program_id: &Pubkey,
+        stake_pool_address: &Pubkey,
+        authority_seed: &[u8],
+        bump_seed: u8,
+    ) -> Result<(), ProgramError> {
+        let expected_address = Pubkey::create_program_address(
+            &[stake_pool_address.as_ref(), authority_seed, &[bump_seed]],
+            program_id,
+        )?;
+
+        if *authority_address == expected_address {
+            Ok(())
+        } else {
+            msg!(
+                "Incorrect authority provided, expected {}, received {}",
+                expected_address,
+                authority_address
+            );
+            Err(StakePoolError::InvalidProgramAddress.into())
+        }
+    }
+
+    /// Checks that the withdraw authority is valid
+    #[inline]
+    pub fn check_authority_withdraw(
+        &self,
+        withdraw_authority: &Pubkey,
+        program_id: &Pubkey,
+        stake_pool_address: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        Self::check_program_derived_authority(
+            withdraw_authority,
+            program_id,
+            stake_pool_address,
+            crate::AUTHORITY_WITHDRAW,
+            self.stake_withdraw_bump_seed,
+        )
+    }
+    /// Checks that the deposit authority is valid
+    #[inline]
+    pub fn check_stake_deposit_authority(
+        &self,
+        stake_deposit_authority: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.stake_deposit_authority == *stake_deposit_authority {
+            Ok(())
+        } else {
+            Err(StakePoolError::InvalidStakeDepositAuthority.into())
+        }
+    }
+
+    /// Checks that the deposit authority is valid
+    /// Does nothing if `sol_deposit_authority` is currently not set
+    #[inline]
+    pub fn check_sol_deposit_authority(
+        &self,
+        maybe_sol_deposit_authority: Result<&AccountInfo, ProgramError>,
+    ) -> Result<(), ProgramError> {
+        if let Some(auth) = self.sol_deposit_authority {
+            let sol_deposit_authority = maybe_sol_deposit_authority?;
+            if auth != *sol_deposit_authority.key {
+                msg!("Expected {}, received {}", auth, sol_deposit_authority.key);
+                return Err(StakePoolError::InvalidSolDepositAuthority.into());
+            }
+            if !sol_deposit_authority.is_signer {
+                msg!("SOL Deposit authority signature missing");
+                return Err(StakePoolError::SignatureMissing.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the sol withdraw authority is valid
+    /// Does nothing if `sol_withdraw_authority` is currently not set
+    #[inline]
+    pub fn check_sol_withdraw_authority(
+        &self,
+        maybe_sol_withdraw_authority: Result<&AccountInfo, ProgramError>,
+    ) -> Result<(), ProgramError> {
+        if let Some(auth) = self.sol_withdraw_authority {
+            let sol_withdraw_authority = maybe_sol_withdraw_authority?;
+            if auth != *sol_withdraw_authority.key {
+                return Err(StakePoolError::InvalidSolWithdrawAuthority.into());
+            }
+            if !sol_withdraw_authority.is_signer {
+                msg!("SOL withdraw authority signature missing");
+                return Err(StakePoolError::SignatureMissing.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Check manager validity and signature
+    pub fn check_manager(&self, manager_info: &AccountInfo) -> Result<(), ProgramError> {
+        if *manager_info.key != self.manager {
+            msg!(
+                "Incorrect manager provided, expected {}, received {}",
+                self.manager,
+                manager_info.key
+            );
+            return Err(StakePoolError::WrongManager.into());
+        }
+        if !manager_info.is_signer {
+            msg!("Manager signature missing");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        Ok(())
+    }
+
+    /// Check staker validity and signature
+    pub fn check_staker(&self, staker_info: &AccountInfo) -> Result<(), ProgramError> {
+        if *staker_info.key != self.staker {
+            msg!(
+                "Incorrect staker provided, expected {}, received {}",
+                self.staker,
+                staker_info.key
+            );
+            return Err(StakePoolError::WrongStaker.into());
+        }
+        if !staker_info.is_signer {
+            msg!("Staker signature missing");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        Ok(())
+    }
+
+    /// Check pauser validity and signature
+    pub fn check_pauser(&self, pauser_info: &AccountInfo) -> Result<(), ProgramError> {
+        if self.pauser != Some(*pauser_info.key) {
+            msg!(
+                "Incorrect pauser provided, expected {:?}, received {}",
+                self.pauser,
+                pauser_info.key
+            );
+            return Err(StakePoolError::WrongPauser.into());
+        }
+        if !pauser_info.is_signer {
+            msg!("Pauser signature missing");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        Ok(())
+    }
+
+    /// Check that the signer is the pending manager or staker
+    pub fn check_pending_authority(
+        pending_authority: Option<Pubkey>,
+        authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if pending_authority != Some(*authority_info.key) {
+            msg!(
+                "Incorrect pending authority provided, expected {:?}, received {}",
+                pending_authority,
+                authority_info.key
+            );
+            return Err(StakePoolError::WrongPendingAuthority.into());
+        }
+        if !authority_info.is_signer {
+            msg!("Pending authority signature missing");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        Ok(())
+    }
+
+    /// Check that `operation` is not currently paused
+    #[inline]
+    pub fn check_not_paused(&self, operation: PausedOperation) -> Result<(), ProgramError> {
+        if self.is_paused(operation) {
+            msg!("{:?} operations are paused", operation);
+            return Err(StakePoolError::OperationPaused.into());
+        }
+        Ok(())
+    }
+
+    /// Check the validator list is valid
+    pub fn check_validator_list(
+        &self,
+        validator_list_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if *validator_list_info.key != self.validator_list {
+            msg!(
+                "Invalid validator list provided, expected {}, received {}",
+                self.validator_list,
+                validator_list_info.key
+            );
+            Err(StakePoolError::InvalidValidatorStakeList.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check the reserve stake is valid
+    pub fn check_reserve_stake(
+        &self,
+        reserve_stake_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if *reserve_stake_info.key != self.reserve_stake {
+            msg!(
+                "Invalid reserve stake provided, expected {}, received {}",
+                self.reserve_stake,
+                reserve_stake_info.key
+            );
+            Err(StakePoolError::InvalidProgramAddress.into())
+        } else {
+            Ok(())
+        }
+    }
+
     /// Check if `StakePool` is actually initialized as a stake pool
     pub fn is_valid(&self) -> bool {
         self.account_type == AccountType::StakePool
@@ -273,9 +768,335 @@ impl StakePool {
     pub fn is_uninitialized(&self) -> bool {
         self.account_type == AccountType::Uninitialized
     }
+
+    /// Check if `operation` is currently paused
+    #[inline]
+    pub fn is_paused(&self, operation: PausedOperation) -> bool {
+        self.paused_operations & operation.mask() != 0
+    }
+
+    /// Check if the pool may have depositors to protect from fee changes.
+    /// Before the first deposit and epoch update, the only pool tokens are
+    /// the ones minted to the manager for the initial reserve lamports.
+    fn has_depositors(&self) -> bool {
+        self.pool_token_supply != 0
+            && (self.last_epoch_pool_token_supply != 0 || self.epoch_deposited_lamports != 0)
+    }
+
+    /// Announces a change to one of the `StakePool`'s fees in `epoch`, to be
+    /// applied through its future epoch field by `apply_pending_fees`.
+    ///
+    /// Deposit and referral fees are set immediately until the pool takes its
+    /// first deposit, so a new pool can be configured right after `Initialize`.
+    pub fn update_fee(&mut self, fee: &FeeType, epoch: u64) -> Result<(), StakePoolError> {
+        if !self.has_depositors() {
+            match fee {
+                FeeType::SolReferral(new_fee) => {
+                    self.sol_referral_fee = *new_fee;
+                    self.next_sol_referral_fee = FutureEpochReferralFee::None;
+                    return Ok(());
+                }
+                FeeType::StakeReferral(new_fee) => {
+                    self.stake_referral_fee = *new_fee;
+                    self.next_stake_referral_fee = FutureEpochReferralFee::None;
+                    return Ok(());
+                }
+                FeeType::SolDeposit(new_fee) => {
+                    self.sol_deposit_fee = *new_fee;
+                    self.next_sol_deposit_fee = FutureEpochFee::None;
+                    return Ok(());
+                }
+                FeeType::StakeDeposit(new_fee) => {
+                    self.stake_deposit_fee = *new_fee;
+                    self.next_stake_deposit_fee = FutureEpochFee::None;
+                    return Ok(());
+                }
+                FeeType::Epoch(_)
+                | FeeType::StakeWithdrawal(_)
+                | FeeType::SolWithdrawal(_)
+                | FeeType::SolWithdrawalCurve(_) => {}
+            }
+        }
+
+        match fee {
+            FeeType::SolReferral(new_fee) => {
+                self.next_sol_referral_fee = FutureEpochReferralFee::new(*new_fee)
+            }
+            FeeType::StakeReferral(new_fee) => {
+                self.next_stake_referral_fee = FutureEpochReferralFee::new(*new_fee)
+            }
+            FeeType::Epoch(new_fee) => self.next_epoch_fee = FutureEpochFee::new(*new_fee),
+            FeeType::StakeWithdrawal(new_fee) => {
+                new_fee.check_withdrawal(&self.stake_withdrawal_fee)?;
+                self.next_stake_withdrawal_fee = FutureEpochFee::new(*new_fee)
+            }
+            FeeType::SolWithdrawal(new_fee) => {
+                new_fee.check_withdrawal(&self.sol_withdrawal_fee)?;
+                self.next_sol_withdrawal_fee = FutureEpochFee::new(*new_fee)
+            }
+            FeeType::SolWithdrawalCurve(new_curve) => {
+                new_curve.check()?;
+                // the top of the curve rises no faster than the flat fee,
+                // measured from the highest fee withdrawals can pay today
+                let old_max_fee = if self.sol_withdrawal_fee_curve.is_enabled() {
+                    &self.sol_withdrawal_fee_curve.max_fee
+                } else {
+                    &self.sol_withdrawal_fee
+                };
+                if new_curve.is_enabled() {
+                    new_curve.max_fee.check_withdrawal(old_max_fee)?;
+                }
+                self.next_sol_withdrawal_fee_curve =
+                    FutureEpochSolWithdrawalFeeCurve::new(*new_curve)
+            }
+            FeeType::SolDeposit(new_fee) => {
+                new_fee.check_deposit(&self.sol_deposit_fee)?;
+                self.next_sol_deposit_fee = FutureEpochFee::new(*new_fee)
+            }
+            FeeType::StakeDeposit(new_fee) => {
+                new_fee.check_deposit(&self.stake_deposit_fee)?;
+                self.next_stake_deposit_fee = FutureEpochFee::new(*new_fee)
+            }
+        };
+        self.fee_change_epoch = epoch;
+        Ok(())
+    }
+
+    /// Updates the fee change delay in `epoch`. A longer delay is applied
+    /// immediately, a shorter one only once the current delay has passed.
+    pub fn update_fee_change_delay(
+        &mut self,
+        delay_epochs: u64,
+        epoch: u64,
+    ) -> Result<(), StakePoolError> {
+        if delay_epochs > MAX_FEE_CHANGE_DELAY_EPOCHS {
+            msg!(
+                "Fee change delay of {} epochs exceeds the maximum of {}",
+                delay_epochs,
+                MAX_FEE_CHANGE_DELAY_EPOCHS
+            );
+            return Err(StakePoolError::FeeChangeDelayTooLong);
+        }
+        if delay_epochs >= self.fee_change_delay_epochs {
+            self.fee_change_delay_epochs = delay_epochs;
+            self.next_fee_change_delay_epochs = None;
+        } else {
+            self.next_fee_change_delay_epochs = Some(delay_epochs);
+            self.fee_change_epoch = epoch;
+        }
+        Ok(())
+    }
+
+    /// Check if pending fee changes are still held back by the fee change
+    /// delay in `epoch`, which holds them for `fee_change_delay_epochs` epoch
+    /// boundaries after the latest fee change
+    #[inline]
+    pub fn fee_changes_held(&self, epoch: u64) -> bool {
+        epoch
+            <= self
+                .fee_change_epoch
+                .saturating_add(self.fee_change_delay_epochs)
+    }
+
+    /// Applies the pending fees that are ready and counts down the others, to
+    /// be done once per epoch. Nothing moves while the fee change delay holds
+    /// the fee changes back.
+    pub fn apply_pending_fees(&mut self, epoch: u64) {
+        if self.fee_changes_held(epoch) {
+            return;
+        }
+
+        if let Some(fee) = self.next_epoch_fee.get() {
+            self.epoch_fee = *fee;
+        }
+        self.next_epoch_fee.update_epoch();
+
+        if let Some(fee) = self.next_stake_withdrawal_fee.get() {
+            self.stake_withdrawal_fee = *fee;
+        }
+        self.next_stake_withdrawal_fee.update_epoch();
+
+        if let Some(fee) = self.next_sol_withdrawal_fee.get() {
+            self.sol_withdrawal_fee = *fee;
+        }
+        self.next_sol_withdrawal_fee.update_epoch();
+
+        if let Some(curve) = self.next_sol_withdrawal_fee_curve.get() {
+            self.sol_withdrawal_fee_curve = *curve;
+        }
+        self.next_sol_withdrawal_fee_curve.update_epoch();
+
+        if let Some(fee) = self.next_stake_deposit_fee.get() {
+            self.stake_deposit_fee = *fee;
+        }
+        self.next_stake_deposit_fee.update_epoch();
+
+        if let Some(fee) = self.next_sol_deposit_fee.get() {
+            self.sol_deposit_fee = *fee;
+        }
+        self.next_sol_deposit_fee.update_epoch();
+
+        if let Some(fee) = self.next_stake_referral_fee.get() {
+            self.stake_referral_fee = *fee;
+        }
+        self.next_stake_referral_fee.update_epoch();
+
+        if let Some(fee) = self.next_sol_referral_fee.get() {
+            self.sol_referral_fee = *fee;
+        }
+        self.next_sol_referral_fee.update_epoch();
+
+        if let Some(delay_epochs) = self.next_fee_change_delay_epochs.take() {
+            self.fee_change_delay_epochs = delay_epochs;
+        }
+    }
+
+    /// Updates one of the StakePool's flow caps.
+    pub fn update_cap(&mut self, cap: &CapType) {
+        match cap {
+            CapType::DepositPerEpoch(new_cap) => self.max_deposit_lamports_per_epoch = *new_cap,
+            CapType::SolWithdrawalPerEpoch(new_cap) => {
+                self.max_sol_withdrawal_lamports_per_epoch = *new_cap
+            }
+            CapType::TotalLamports(new_cap) => self.max_total_lamports = *new_cap,
+            CapType::DirectStakeShare(new_cap) => self.max_direct_stake_bps = *new_cap,
+            CapType::ValidatorShare(new_cap) => self.max_validator_share_bps = *new_cap,
+            CapType::ValidatorCommission(new_cap) => self.max_validator_commission = *new_cap,
+            CapType::ValidatorVoteAge(new_cap) => self.max_validator_vote_age_epochs = *new_cap,
+        }
+    }
+
+    /// Checks that `validator`, once holding `validator_lamports`, stays
+    /// within both its own maximum share of the pool and the pool-wide one
+    pub fn check_validator_share(
+        &self,
+        validator: &ValidatorStakeInfo,
+        validator_lamports: u64,
+    ) -> Result<(), StakePoolError> {
+        let max_share_bps = [
+            self.max_validator_share_bps.map(u32::from),
+            validator.max_share_bps(),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        let Some(max_share_bps) = max_share_bps else {
+            return Ok(());
+        };
+        let max_validator_lamports = u128::from(self.total_lamports)
+            .checked_mul(u128::from(max_share_bps))
+            .ok_or(StakePoolError::CalculationFailure)?
+            / u128::from(MAX_BASIS_POINTS);
+        if u128::from(validator_lamports) > max_validator_lamports {
+            msg!(
+                "Validator {} would hold {} lamports, above its limit of {} lamports",
+                validator.vote_account_address,
+                validator_lamports,
+                max_validator_lamports
+            );
+            return Err(StakePoolError::ValidatorShareExceeded);
+        }
+        Ok(())
+    }
+
+    /// Checks that a validator with `validator_lamports`, including its
+    /// pending direct stake, stays within the direct stake share of the pool
+    pub fn check_direct_stake_share(&self, validator_lamports: u64) -> Result<(), StakePoolError> {
+        let Some(max_direct_stake_bps) = self.max_direct_stake_bps else {
+            msg!("The stake pool does not accept validator-targeted deposits");
+            return Err(StakePoolError::DirectStakeShareExceeded);
+        };
+        let max_validator_lamports = u128::from(self.total_lamports)
+            .checked_mul(u128::from(max_direct_stake_bps))
+            .ok_or(StakePoolError::CalculationFailure)?
+            / u128::from(MAX_BASIS_POINTS);
+        if u128::from(validator_lamports) > max_validator_lamports {
+            msg!(
+                "Validator would hold {} lamports, above the direct stake limit of {} lamports",
+                validator_lamports,
+                max_validator_lamports
+            );
+            return Err(StakePoolError::DirectStakeShareExceeded);
+        }
+        Ok(())
+    }
+
+    /// Resets the per-epoch flow counters if `epoch` is a new epoch
+    fn roll_flow_epoch(&mut self, epoch: u64) {
+        if self.flow_epoch != epoch {
+            self.flow_epoch = epoch;
+            self.epoch_deposited_lamports = 0;
+            self.epoch_sol_withdrawn_lamports = 0;
+        }
+    }
+
+    /// Checks a deposit of `lamports` against the deposit and total lamports
+    /// caps, and records it in the current epoch's deposited lamports.
+    /// Must be called before the deposit is added to `total_lamports`.
+    pub fn record_deposit(&mut self, epoch: u64, lamports: u64) -> Result<(), StakePoolError> {
+        self.roll_flow_epoch(epoch);
+        if let Some(max_total_lamports) = self.max_total_lamports {
+            let new_total_lamports = self
+                .total_lamports
+                .checked_add(lamports)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            if new_total_lamports > max_total_lamports {
+                msg!(
+                    "Depositing {} lamports brings the pool to {} lamports, maximum is {}",
+                    lamports,
+                    new_total_lamports,
+                    max_total_lamports
+                );
+                return Err(StakePoolError::TotalLamportsCapExceeded);
+            }
+        }
+        let epoch_deposited_lamports = self
+            .epoch_deposited_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        if let Some(max_deposit_lamports) = self.max_deposit_lamports_per_epoch {
+            if epoch_deposited_lamports > max_deposit_lamports {
+                msg!(
+                    "Attempting to deposit {} lamports, remaining deposit cap this epoch is {} lamports",
+                    lamports,
+                    max_deposit_lamports.saturating_sub(self.epoch_deposited_lamports)
+                );
+                return Err(StakePoolError::DepositCapExceeded);
+            }
+        }
+        self.epoch_deposited_lamports = epoch_deposited_lamports;
+        Ok(())
+    }
+
+    /// Checks a SOL withdrawal of `lamports` against the SOL withdrawal cap,
+    /// and records it in the current epoch's withdrawn lamports
+    pub fn record_sol_withdrawal(
+        &mut self,
+        epoch: u64,
+        lamports: u64,
+    ) -> Result<(), StakePoolError> {
+        self.roll_flow_epoch(epoch);
+        let epoch_sol_withdrawn_lamports = self
+            .epoch_sol_withdrawn_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        if let Some(max_withdrawal_lamports) = self.max_sol_withdrawal_lamports_per_epoch {
+            if epoch_sol_withdrawn_lamports > max_withdrawal_lamports {
+                msg!(
+                    "Attempting to withdraw {} lamports, remaining SOL withdrawal cap this epoch is {} lamports",
+                    lamports,
+                    max_withdrawal_lamports.saturating_sub(self.epoch_sol_withdrawn_lamports)
+                );
+                return Err(StakePoolError::SolWithdrawalCapExceeded);
+            }
+        }
+        self.epoch_sol_withdrawn_lamports = epoch_sol_withdrawn_lamports;
+        Ok(())
+    }
 }
 
-/// Storage list for all validator stake accounts in the pool.
+/// Ticket for a delayed withdrawal, created by burning pool tokens and
+/// claimable for lamports from the reserve once its cooldown epoch is reached
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(
@@ -283,16 +1104,43 @@ impl StakePool {
     derive(BorshDeserialize, BorshSerialize, BorshSchema)
 )]
 #[cfg_attr(feature = "codama", derive(CodamaType))]
-pub struct ValidatorList {
-    /// Data outside of the validator list, separated out for cheaper
-    /// deserialization
-    pub header: ValidatorListHeader,
+pub struct WithdrawalTicket {
+    /// Account type, must be `WithdrawalTicket` currently
+    pub account_type: AccountType,
 
-    /// List of stake info for each validator in the pool
-    pub validators: Vec<ValidatorStakeInfo>,
+    /// Stake pool the ticket was requested from
+    pub stake_pool: Pubkey,
+
+    /// Owner of the ticket, allowed to claim it
+    pub owner: Pubkey,
+
+    /// Lamports owed to the owner, locked at the exchange rate of the request
+    pub lamports: u64,
+
+    /// Pool tokens burnt when requesting the ticket
+    pub pool_tokens: u64,
+
+    /// Epoch in which the ticket was requested
+    pub request_epoch: u64,
+
+    /// First epoch in which the ticket can be claimed
+    pub claimable_epoch: u64,
 }
 
-/// Helper type to deserialize just the start of a `ValidatorList`
+impl WithdrawalTicket {
+    /// Check if `WithdrawalTicket` is actually initialized as a ticket
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::WithdrawalTicket
+    }
+
+    /// Check if the ticket's cooldown has passed at the given epoch
+    pub fn is_claimable(&self, epoch: u64) -> bool {
+        epoch >= self.claimable_epoch
+    }
+}
+
+/// Registered referrer of a stake pool, receiving referral fees into its
+/// payout account for deposits that reference it
 #[repr(C)]
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(
@@ -300,58 +1148,683 @@ pub struct ValidatorList {
     derive(BorshDeserialize, BorshSerialize, BorshSchema)
 )]
 #[cfg_attr(feature = "codama", derive(CodamaType))]
-pub struct ValidatorListHeader {
-    /// Account type, must be `ValidatorList` currently
+pub struct Referrer {
+    /// Account type, must be `Referrer` currently
     pub account_type: AccountType,
 
-    /// Maximum allowable number of validators
-    pub max_validators: u32,
-}
+    /// Stake pool the referrer is registered with
+    pub stake_pool: Pubkey,
 
-impl ValidatorListHeader {
-    /// Length of the header in bytes
-    pub const LEN: usize = 1 + 4;
+    /// Authority allowed to update the referrer
+    pub authority: Pubkey,
 
-    /// Check if validator stake list is actually initialized as a validator
-    /// stake list
-    pub fn is_valid(&self) -> bool {
-        self.account_type == AccountType::ValidatorList
-    }
+    /// Referral code, zero-padded
+    #[cfg_attr(feature = "codama", codama(type = fixed_size(bytes, 32)))]
+    pub code: [u8; MAX_REFERRAL_CODE_LENGTH],
 
-    /// Check if the validator stake list is uninitialized
-    pub fn is_uninitialized(&self) -> bool {
-        self.account_type == AccountType::Uninitialized
-    }
+    /// Pool token account receiving the referral fees
+    pub payout_token_account: Pubkey,
+
+    /// Lifetime lamports deposited with this referrer
+    pub referred_lamports: u64,
+
+    /// Lifetime referral fees earned, in pool tokens
+    pub earned_pool_tokens: u64,
 }
 
-impl ValidatorList {
-    /// Create an empty instance containing space for `max_validators`
-    pub fn new(max_validators: u32) -> Self {
-        Self {
-            header: ValidatorListHeader {
-                account_type: AccountType::ValidatorList,
-                max_validators,
-            },
-            validators: vec![ValidatorStakeInfo::default(); max_validators as usize],
-        }
+impl Referrer {
+    /// Check if `Referrer` is actually initialized as a referrer
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::Referrer
     }
 
-    /// Calculate the number of validator entries that fit in the provided length
-    pub fn calculate_max_validators(buffer_length: usize) -> usize {
-        let header_size = ValidatorListHeader::LEN.saturating_add(4);
-        buffer_length
-            .saturating_sub(header_size)
-            .saturating_div(ValidatorStakeInfo::LEN)
+    /// Checks that a referral code is 1 to `MAX_REFERRAL_CODE_LENGTH` bytes of
+    /// ASCII alphanumerics, `-` or `_`, and pads it for storage
+    pub fn pack_code(code: &str) -> Result<[u8; MAX_REFERRAL_CODE_LENGTH], StakePoolError> {
+        if code.is_empty()
+            || code.len() > MAX_REFERRAL_CODE_LENGTH
+            || !code
+                .bytes()
+                .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_')
+        {
+            return Err(StakePoolError::InvalidReferralCode);
+        }
+        let mut packed = [0; MAX_REFERRAL_CODE_LENGTH];
+        packed[..code.len()].copy_from_slice(code.as_bytes());
+        Ok(packed)
     }
 
-    /// Check if contains validator with particular pubkey
-    pub fn contains(&self, vote_account_address: &Pubkey) -> bool {
-        self.validators
+    /// Get the referral code without its padding
+    pub fn code(&self) -> &str {
+        let len = self
+            .code
             .iter()
-            .any(|x| x.vote_account_address == *vote_account_address)
+            .position(|c| *c == 0)
+            .unwrap_or(MAX_REFERRAL_CODE_LENGTH);
+        std::str::from_utf8(&self.code[..len]).unwrap_or_default()
     }
 
-    /// Find a validator by vote account address (mutable)
+    /// Record a deposit made with this referrer
+    pub fn record_deposit(
+        &mut self,
+        lamports: u64,
+        referral_fee_pool_tokens: u64,
+    ) -> Result<(), StakePoolError> {
+        self.referred_lamports = self
+            .referred_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        self.earned_pool_tokens = self
+            .earned_pool_tokens
+            .checked_add(referral_fee_pool_tokens)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        Ok(())
+    }
+}
+
+/// Stake requested for a validator by validator-targeted SOL deposits, for
+/// the staker to delegate to it out of the reserve
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct DirectStake {
+    /// Account type, must be `DirectStake` currently
+    pub account_type: AccountType,
+
+    /// Stake pool the deposits were made to
+    pub stake_pool: Pubkey,
+
+    /// Vote account of the validator the deposits were made for
+    pub vote_account: Pubkey,
+
+    /// Lamports deposited for the validator that the staker has yet to
+    /// delegate to it
+    pub pending_lamports: u64,
+
+    /// Lifetime lamports deposited for the validator
+    pub deposited_lamports: u64,
+}
+
+impl DirectStake {
+    /// Check if `DirectStake` is actually initialized as a direct stake
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::DirectStake
+    }
+
+    /// Record a deposit made for the validator
+    pub fn record_deposit(&mut self, lamports: u64) -> Result<(), StakePoolError> {
+        self.pending_lamports = self
+            .pending_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        self.deposited_lamports = self
+            .deposited_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        Ok(())
+    }
+
+    /// Record stake delegated to the validator by the staker, which counts
+    /// towards the pending lamports
+    pub fn record_delegation(&mut self, lamports: u64) {
+        self.pending_lamports = self.pending_lamports.saturating_sub(lamports);
+    }
+}
+
+/// Why a validator was taken out of the pool, as recorded in its
+/// `ValidatorMetadata`
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum OffboardingReason {
+    /// The validator has not been taken out of the pool
+    #[default]
+    None,
+    /// Removed by the staker
+    RemovedByStaker,
+    /// Deactivated by anyone because its commission went above the maximum
+    /// allowed
+    CommissionTooHigh,
+    /// Deactivated by anyone because its vote account stopped voting
+    Delinquent,
+}
+
+/// Metadata kept for a validator alongside its entry in the validator list,
+/// created when the validator is added to the pool
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct ValidatorMetadata {
+    /// Account type, must be `ValidatorMetadata` currently
+    pub account_type: AccountType,
+
+    /// Stake pool the validator was added to
+    pub stake_pool: Pubkey,
+
+    /// Vote account of the validator
+    pub vote_account: Pubkey,
+
+    /// Epoch the validator was last added to the pool
+    pub admission_epoch: u64,
+
+    /// Hash of the staker's off-chain notes on the validator
+    pub notes_hash: [u8; 32],
+
+    /// Maximum commission, as a percentage, the validator may charge before
+    /// anyone can start deactivating it, on top of the pool-wide limit
+    pub max_commission: u8,
+
+    /// Why the validator was taken out of the pool, if it was
+    pub offboarding_reason: OffboardingReason,
+}
+
+impl ValidatorMetadata {
+    /// Check if `ValidatorMetadata` is actually initialized as validator
+    /// metadata
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::ValidatorMetadata
+    }
+
+    /// Maximum commission allowed for the validator under both its own
+    /// limit and the pool-wide one
+    pub fn max_commission(&self, stake_pool: &StakePool) -> u8 {
+        stake_pool
+            .max_validator_commission
+            .map_or(self.max_commission, |max| max.min(self.max_commission))
+    }
+}
+
+/// Token account receiving a share of the pool's fees
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct FeeSplitRecipient {
+    /// Pool token account receiving the share
+    pub token_account: Pubkey,
+
+    /// Share of every fee, in basis points
+    pub weight_bps: u16,
+}
+
+impl FeeSplitRecipient {
+    /// Share of `fee` pool tokens going to the recipient, rounded down
+    pub fn share(&self, fee: u64) -> Option<u64> {
+        u64::try_from(
+            (fee as u128)
+                .checked_mul(self.weight_bps as u128)?
+                .checked_div(MAX_BASIS_POINTS as u128)?,
+        )
+        .ok()
+    }
+}
+
+/// Split of the fees of a stake pool across several pool token accounts, with
+/// whatever the recipients do not take going to the manager fee account
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct FeeSplit {
+    /// Account type, must be `FeeSplit` currently
+    pub account_type: AccountType,
+
+    /// Stake pool whose fees are split
+    pub stake_pool: Pubkey,
+
+    /// Number of recipients in use at the start of `recipients`
+    pub recipient_count: u8,
+
+    /// Recipients of the fees, the unused ones zeroed. Spelled out as
+    /// `MAX_FEE_SPLIT_RECIPIENTS` for the IDL, which needs a literal length.
+    pub recipients: [FeeSplitRecipient; 8],
+}
+
+impl FeeSplit {
+    /// Check if `FeeSplit` is actually initialized as a fee split
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::FeeSplit
+    }
+
+    /// Recipients in use
+    pub fn recipients(&self) -> &[FeeSplitRecipient] {
+        &self.recipients[..(self.recipient_count as usize).min(MAX_FEE_SPLIT_RECIPIENTS)]
+    }
+
+    /// Checks that there are at most `MAX_FEE_SPLIT_RECIPIENTS` weights, all
+    /// non-zero and adding up to at most `MAX_BASIS_POINTS`
+    pub fn check_weights(weights_bps: &[u16]) -> Result<(), StakePoolError> {
+        let total_bps = weights_bps
+            .iter()
+            .try_fold(0u16, |total, weight| total.checked_add(*weight));
+        if weights_bps.len() > MAX_FEE_SPLIT_RECIPIENTS
+            || weights_bps.contains(&0)
+            || total_bps.is_none_or(|total| total > MAX_BASIS_POINTS)
+        {
+            Err(StakePoolError::InvalidFeeSplit)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Insurance fund of a stake pool, holding lamports above its rent-exempt
+/// minimum to cover validator stake losses
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct InsuranceFund {
+    /// Account type, must be `InsuranceFund` currently
+    pub account_type: AccountType,
+
+    /// Stake pool insured by the fund
+    pub stake_pool: Pubkey,
+
+    /// Total lamports paid into the fund from the epoch fee
+    pub deposited_lamports: u64,
+
+    /// Total lamports injected back into the pool to cover losses
+    pub injected_lamports: u64,
+}
+
+impl InsuranceFund {
+    /// Check if `InsuranceFund` is actually initialized as an insurance fund
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::InsuranceFund
+    }
+}
+
+/// Exchange rate of a stake pool at its last balance update in an epoch
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct RateHistoryEntry {
+    /// Epoch of the update
+    pub epoch: u64,
+
+    /// Unix timestamp of the update
+    pub unix_timestamp: i64,
+
+    /// Total lamports in the pool after the update
+    pub total_lamports: u64,
+
+    /// Pool token supply after the update
+    pub pool_token_supply: u64,
+
+    /// Pool tokens minted as epoch fees during the epoch
+    pub fee_pool_tokens: u64,
+}
+
+impl RateHistoryEntry {
+    /// Size of an entry in account data
+    pub const LEN: usize = 40;
+
+    /// Lamports per pool token, or `None` without pool tokens
+    pub fn exchange_rate(&self) -> Option<f64> {
+        if self.pool_token_supply == 0 {
+            None
+        } else {
+            Some(self.total_lamports as f64 / self.pool_token_supply as f64)
+        }
+    }
+}
+
+/// Ring buffer of the exchange rates of a stake pool, one entry per epoch
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct RateHistory {
+    /// Account type, must be `RateHistory` currently
+    pub account_type: AccountType,
+
+    /// Stake pool whose exchange rate is recorded
+    pub stake_pool: Pubkey,
+
+    /// Index of the entry written for the next epoch
+    pub next_index: u16,
+
+    /// Number of entries recorded, up to `RATE_HISTORY_LEN`
+    pub len: u16,
+
+    /// Recorded entries, oldest first from `next_index` once full. Spelled
+    /// out as `RATE_HISTORY_LEN` for the IDL, which needs a literal
+    /// length.
+    pub entries: [RateHistoryEntry; 250],
+}
+
+impl RateHistory {
+    /// Offset of the entries in account data, after the account type, stake
+    /// pool, next index and length
+    pub const ENTRIES_OFFSET: usize = 37;
+
+    /// Check if `RateHistory` is actually initialized as a rate history
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::RateHistory
+    }
+
+    /// Recorded entries, oldest first
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &RateHistoryEntry> {
+        let len = (self.len as usize).min(RATE_HISTORY_LEN);
+        // Entries fill up from the start, then wrap around at `next_index`
+        let (older, newer): (&[RateHistoryEntry], &[RateHistoryEntry]) = if len < RATE_HISTORY_LEN {
+            (&self.entries[..len], &[])
+        } else {
+            let (newer, older) = self
+                .entries
+                .split_at(self.next_index as usize % RATE_HISTORY_LEN);
+            (older, newer)
+        };
+        older.iter().chain(newer)
+    }
+
+    /// Most recent entry
+    pub fn latest(&self) -> Option<&RateHistoryEntry> {
+        self.entries().next_back()
+    }
+
+    /// Annual percentage yield of the pool tokens over at least the last
+    /// `epochs` epochs, compounded from the exchange rate growth since the
+    /// newest entry that old, or `None` without enough history
+    pub fn trailing_apy(&self, epochs: u64) -> Option<f64> {
+        const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+        let latest = self.latest()?;
+        let start = self
+            .entries()
+            .rev()
+            .find(|entry| entry.epoch.saturating_add(epochs) <= latest.epoch)?;
+        let elapsed_seconds = latest.unix_timestamp.checked_sub(start.unix_timestamp)?;
+        if elapsed_seconds <= 0 {
+            return None;
+        }
+        let growth = latest.exchange_rate()? / start.exchange_rate()?;
+        Some(growth.powf(SECONDS_PER_YEAR / elapsed_seconds as f64) - 1.0)
+    }
+
+    /// Records an entry directly in the account data of a rate history,
+    /// adding to the latest entry if it is from the same epoch
+    #[cfg(feature = "borsh")]
+    pub fn record(data: &mut [u8], mut entry: RateHistoryEntry) -> Result<(), ProgramError> {
+        if data.len() < Self::ENTRIES_OFFSET + RATE_HISTORY_LEN * RateHistoryEntry::LEN {
+            return Err(StakePoolError::InvalidRateHistory.into());
+        }
+        let next_index = u16::from_le_bytes([data[33], data[34]]) as usize;
+        let len = u16::from_le_bytes([data[35], data[36]]) as usize;
+        if next_index >= RATE_HISTORY_LEN {
+            return Err(StakePoolError::InvalidRateHistory.into());
+        }
+        let entry_range = |index: usize| {
+            let start = Self::ENTRIES_OFFSET + index * RateHistoryEntry::LEN;
+            start..start + RateHistoryEntry::LEN
+        };
+
+        if len > 0 {
+            let latest_index = (next_index + RATE_HISTORY_LEN - 1) % RATE_HISTORY_LEN;
+            let latest = RateHistoryEntry::try_from_slice(&data[entry_range(latest_index)])?;
+            if latest.epoch == entry.epoch {
+                entry.fee_pool_tokens = entry
+                    .fee_pool_tokens
+                    .checked_add(latest.fee_pool_tokens)
+                    .ok_or(StakePoolError::CalculationFailure)?;
+                borsh::to_writer(&mut data[entry_range(latest_index)], &entry)?;
+                return Ok(());
+            }
+        }
+
+        borsh::to_writer(&mut data[entry_range(next_index)], &entry)?;
+        let next_index = (next_index + 1) % RATE_HISTORY_LEN;
+        let len = (len + 1).min(RATE_HISTORY_LEN);
+        data[33..35].copy_from_slice(&(next_index as u16).to_le_bytes());
+        data[35..37].copy_from_slice(&(len as u16).to_le_bytes());
+        Ok(())
+    }
+}
+
+// The rate history is created through the system program, and must fit in the
+// data an account created that way may hold
+#[cfg(feature = "borsh")]
+const _: () = assert!(
+    RateHistory::ENTRIES_OFFSET + RATE_HISTORY_LEN * RateHistoryEntry::LEN
+        <= MAX_PERMITTED_DATA_INCREASE
+);
+
+/// Exchange rate of the pool token as of the stake pool's last balance
+/// update, returned by `GetExchangeRate` and kept in the price oracle
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct ExchangeRate {
+    /// Total lamports in the pool
+    pub total_lamports: u64,
+
+    /// Pool token supply
+    pub pool_token_supply: u64,
+
+    /// Epoch of the last balance update. The rate is stale in any later
+    /// epoch, until the pool is updated again.
+    pub last_update_epoch: u64,
+}
+
+impl ExchangeRate {
+    /// Whether the rate was not updated yet in `epoch`
+    pub fn is_stale(&self, epoch: u64) -> bool {
+        self.last_update_epoch < epoch
+    }
+
+    /// Lamports the given pool tokens are worth, rounded down
+    pub fn pool_tokens_to_lamports(&self, pool_tokens: u64) -> Option<u64> {
+        if self.pool_token_supply == 0 {
+            return Some(0);
+        }
+        u64::try_from(
+            (pool_tokens as u128)
+                .checked_mul(self.total_lamports as u128)?
+                .checked_div(self.pool_token_supply as u128)?,
+        )
+        .ok()
+    }
+
+    /// Pool tokens the given lamports are worth, rounded down, one for one
+    /// while the pool is empty
+    pub fn lamports_to_pool_tokens(&self, lamports: u64) -> Option<u64> {
+        if self.total_lamports == 0 || self.pool_token_supply == 0 {
+            return Some(lamports);
+        }
+        u64::try_from(
+            (lamports as u128)
+                .checked_mul(self.pool_token_supply as u128)?
+                .checked_div(self.total_lamports as u128)?,
+        )
+        .ok()
+    }
+
+    /// Lamports per pool token, or `None` without pool tokens
+    pub fn lamports_per_pool_token(&self) -> Option<f64> {
+        if self.pool_token_supply == 0 {
+            None
+        } else {
+            Some(self.total_lamports as f64 / self.pool_token_supply as f64)
+        }
+    }
+}
+
+impl From<&StakePool> for ExchangeRate {
+    fn from(stake_pool: &StakePool) -> Self {
+        Self {
+            total_lamports: stake_pool.total_lamports,
+            pool_token_supply: stake_pool.pool_token_supply,
+            last_update_epoch: stake_pool.last_update_epoch,
+        }
+    }
+}
+
+/// Price oracle of a stake pool's token, rewritten on every balance update
+/// so that other programs can read the exchange rate from a small account
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct Oracle {
+    /// Account type, must be `Oracle` currently
+    pub account_type: AccountType,
+
+    /// Stake pool whose token is priced
+    pub stake_pool: Pubkey,
+
+    /// Pool token mint
+    pub pool_mint: Pubkey,
+
+    /// Exchange rate as of the last balance update
+    pub exchange_rate: ExchangeRate,
+
+    /// Slot of the last balance update
+    pub last_update_slot: u64,
+}
+
+impl Oracle {
+    /// Check if `Oracle` is actually initialized as a price oracle
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::Oracle
+    }
+}
+
+/// Storage list for all validator stake accounts in the pool.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct ValidatorList {
+    /// Data outside of the validator list, separated out for cheaper
+    /// deserialization
+    pub header: ValidatorListHeader,
+
+    /// List of stake info for each validator in the pool
+    pub validators: Vec<ValidatorStakeInfo>,
+}
+
+/// Helper type to deserialize just the start of a `ValidatorList`
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct ValidatorListHeader {
+    /// Account type, must be `ValidatorList` currently
+    pub account_type: AccountType,
+
+    /// Maximum allowable number of validators
+    pub max_validators: u32,
+}
+
+impl ValidatorListHeader {
+    /// Length of the header in bytes
+    pub const LEN: usize = 1 + 4;
+
+    /// Check if validator stake list is actually initialized as a validator
+    /// stake list
+    pub fn is_valid(&self) -> bool {
+        self.account_type == AccountType::ValidatorList
+    }
+
+    /// Check if the validator stake list is uninitialized
+    pub fn is_uninitialized(&self) -> bool {
+        self.account_type == AccountType::Uninitialized
+    }
+
+    /// Extracts a slice of `ValidatorStakeInfo` types from the vec part
+    /// of the `ValidatorList`
+    #[cfg(feature = "borsh")]
+    pub fn deserialize_mut_slice<'a>(
+        big_vec: &'a mut BigVec,
+        skip: usize,
+        len: usize,
+    ) -> Result<&'a mut [ValidatorStakeInfo], ProgramError> {
+        big_vec.deserialize_mut_slice::<ValidatorStakeInfo>(skip, len)
+    }
+
+    /// Extracts the validator list into its header and internal `BigVec`
+    #[cfg(feature = "borsh")]
+    pub fn deserialize_vec(data: &mut [u8]) -> Result<(Self, BigVec<'_>), ProgramError> {
+        let mut data_mut = data.borrow();
+        let header = ValidatorListHeader::deserialize(&mut data_mut)?;
+        let length = get_instance_packed_len(&header)?;
+
+        let big_vec = BigVec {
+            data: &mut data[length..],
+        };
+        Ok((header, big_vec))
+    }
+}
+
+impl ValidatorList {
+    /// Create an empty instance containing space for `max_validators`
+    pub fn new(max_validators: u32) -> Self {
+        Self {
+            header: ValidatorListHeader {
+                account_type: AccountType::ValidatorList,
+                max_validators,
+            },
+            validators: vec![ValidatorStakeInfo::default(); max_validators as usize],
+        }
+    }
+
+    /// Calculate the number of validator entries that fit in the provided length
+    pub fn calculate_max_validators(buffer_length: usize) -> usize {
+        let header_size = ValidatorListHeader::LEN.saturating_add(4);
+        buffer_length
+            .saturating_sub(header_size)
+            .saturating_div(ValidatorStakeInfo::LEN)
+    }
+
+    /// Check if contains validator with particular pubkey
+    pub fn contains(&self, vote_account_address: &Pubkey) -> bool {
+        self.validators
+            .iter()
+            .any(|x| x.vote_account_address == *vote_account_address)
+    }
+
+    /// Find a validator by vote account address (mutable)
     pub fn find_mut(&mut self, vote_account_address: &Pubkey) -> Option<&mut ValidatorStakeInfo> {
         self.validators
             .iter_mut()
@@ -409,6 +1882,37 @@ pub enum StakeStatus {
 #[cfg_attr(feature = "codama", derive(CodamaType))]
 pub struct PodStakeStatus(u8);
 
+impl PodStakeStatus {
+    /// Downgrade the status towards ready for removal by removing the validator
+    /// stake
+    pub fn remove_validator_stake(&mut self) -> Result<(), ProgramError> {
+        let status = StakeStatus::try_from(*self)?;
+        let new_self = match status {
+            StakeStatus::Active
+            | StakeStatus::DeactivatingTransient
+            | StakeStatus::ReadyForRemoval => status,
+            StakeStatus::DeactivatingAll => StakeStatus::DeactivatingTransient,
+            StakeStatus::DeactivatingValidator => StakeStatus::ReadyForRemoval,
+        };
+        *self = new_self.into();
+        Ok(())
+    }
+    /// Downgrade the status towards ready for removal by removing the transient
+    /// stake
+    pub fn remove_transient_stake(&mut self) -> Result<(), ProgramError> {
+        let status = StakeStatus::try_from(*self)?;
+        let new_self = match status {
+            StakeStatus::Active
+            | StakeStatus::DeactivatingValidator
+            | StakeStatus::ReadyForRemoval => status,
+            StakeStatus::DeactivatingAll => StakeStatus::DeactivatingValidator,
+            StakeStatus::DeactivatingTransient => StakeStatus::ReadyForRemoval,
+        };
+        *self = new_self.into();
+        Ok(())
+    }
+}
+
 impl TryFrom<PodStakeStatus> for StakeStatus {
     type Error = ProgramError;
     fn try_from(pod: PodStakeStatus) -> Result<Self, Self::Error> {
@@ -457,8 +1961,10 @@ pub struct ValidatorStakeInfo {
     /// account address
     pub transient_seed_suffix: PodU64,
 
-    /// Unused space, initially meant to specify the end of seed suffixes
-    pub unused: PodU32,
+    /// Maximum share of the pool's `total_lamports`, in basis points, that
+    /// this validator's stake may reach, or 0 for no limit of its own.
+    /// Initially unused space, meant to specify the end of seed suffixes.
+    pub max_share_bps: PodU32,
 
     /// Validator account seed suffix
     pub validator_seed_suffix: PodU32,
@@ -471,9 +1977,17 @@ pub struct ValidatorStakeInfo {
 }
 
 impl ValidatorStakeInfo {
+    /// Maximum share of the pool this validator may hold, in basis points,
+    /// if it has a limit of its own
+    pub fn max_share_bps(&self) -> Option<u32> {
+        Some(u32::from(self.max_share_bps)).filter(|bps| *bps != 0)
+    }
+
     /// Get the total lamports on this validator (active and transient)
-    pub fn stake_lamports(&self) -> Option<u64> {
-        u64::from(self.active_stake_lamports).checked_add(self.transient_stake_lamports.into())
+    pub fn stake_lamports(&self) -> Result<u64, StakePoolError> {
+        u64::from(self.active_stake_lamports)
+            .checked_add(self.transient_stake_lamports.into())
+            .ok_or(StakePoolError::CalculationFailure)
     }
 
     /// Performs a very cheap comparison, for checking if this validator stake
@@ -667,11 +2181,164 @@ impl FutureEpochFee {
     }
 }
 
-impl From<FutureEpochFee> for Option<Fee> {
-    fn from(v: FutureEpochFee) -> Option<Fee> {
+impl From<FutureEpochFee> for Option<Fee> {
+    fn from(v: FutureEpochFee) -> Option<Fee> {
+        match v {
+            FutureEpochFee::None => None,
+            FutureEpochFee::One(inner) | FutureEpochFee::Two(inner) => Some(inner),
+        }
+    }
+}
+
+/// Concrete type for FutureEpoch<u8>, used in StakePool for referral fees to
+/// support IDL generation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshSerialize, BorshDeserialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum FutureEpochReferralFee {
+    /// Nothing is set
+    #[default]
+    None,
+    /// Value is ready after the next epoch boundary
+    One(u8),
+    /// Value is ready after two epoch boundaries
+    Two(u8),
+}
+
+impl From<FutureEpoch<u8>> for FutureEpochReferralFee {
+    fn from(value: FutureEpoch<u8>) -> Self {
+        match value {
+            FutureEpoch::None => FutureEpochReferralFee::None,
+            FutureEpoch::One(fee) => FutureEpochReferralFee::One(fee),
+            FutureEpoch::Two(fee) => FutureEpochReferralFee::Two(fee),
+        }
+    }
+}
+
+impl From<FutureEpochReferralFee> for FutureEpoch<u8> {
+    fn from(value: FutureEpochReferralFee) -> Self {
+        match value {
+            FutureEpochReferralFee::None => FutureEpoch::None,
+            FutureEpochReferralFee::One(fee) => FutureEpoch::One(fee),
+            FutureEpochReferralFee::Two(fee) => FutureEpoch::Two(fee),
+        }
+    }
+}
+
+impl FutureEpochReferralFee {
+    /// Create a new value to be unlocked in two epochs
+    pub fn new(value: u8) -> Self {
+        Self::Two(value)
+    }
+
+    /// Update the epoch, to be done after `get`ting the underlying value
+    pub fn update_epoch(&mut self) {
+        match self {
+            Self::None => {}
+            Self::One(_) => {
+                *self = Self::None;
+            }
+            Self::Two(v) => {
+                *self = Self::One(*v);
+            }
+        }
+    }
+
+    /// Get the value if it's ready, which is only at `One` epoch remaining
+    pub fn get(&self) -> Option<&u8> {
+        match self {
+            Self::None | Self::Two(_) => None,
+            Self::One(v) => Some(v),
+        }
+    }
+}
+
+impl From<FutureEpochReferralFee> for Option<u8> {
+    fn from(v: FutureEpochReferralFee) -> Option<u8> {
+        match v {
+            FutureEpochReferralFee::None => None,
+            FutureEpochReferralFee::One(inner) | FutureEpochReferralFee::Two(inner) => Some(inner),
+        }
+    }
+}
+
+/// Concrete type for FutureEpoch<SolWithdrawalFeeCurve>, used in StakePool to
+/// support IDL generation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshSerialize, BorshDeserialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum FutureEpochSolWithdrawalFeeCurve {
+    /// Nothing is set
+    #[default]
+    None,
+    /// Value is ready after the next epoch boundary
+    One(SolWithdrawalFeeCurve),
+    /// Value is ready after two epoch boundaries
+    Two(SolWithdrawalFeeCurve),
+}
+
+impl From<FutureEpoch<SolWithdrawalFeeCurve>> for FutureEpochSolWithdrawalFeeCurve {
+    fn from(value: FutureEpoch<SolWithdrawalFeeCurve>) -> Self {
+        match value {
+            FutureEpoch::None => FutureEpochSolWithdrawalFeeCurve::None,
+            FutureEpoch::One(curve) => FutureEpochSolWithdrawalFeeCurve::One(curve),
+            FutureEpoch::Two(curve) => FutureEpochSolWithdrawalFeeCurve::Two(curve),
+        }
+    }
+}
+
+impl From<FutureEpochSolWithdrawalFeeCurve> for FutureEpoch<SolWithdrawalFeeCurve> {
+    fn from(value: FutureEpochSolWithdrawalFeeCurve) -> Self {
+        match value {
+            FutureEpochSolWithdrawalFeeCurve::None => FutureEpoch::None,
+            FutureEpochSolWithdrawalFeeCurve::One(curve) => FutureEpoch::One(curve),
+            FutureEpochSolWithdrawalFeeCurve::Two(curve) => FutureEpoch::Two(curve),
+        }
+    }
+}
+
+impl FutureEpochSolWithdrawalFeeCurve {
+    /// Create a new value to be unlocked in two epochs
+    pub fn new(value: SolWithdrawalFeeCurve) -> Self {
+        Self::Two(value)
+    }
+
+    /// Update the epoch, to be done after `get`ting the underlying value
+    pub fn update_epoch(&mut self) {
+        match self {
+            Self::None => {}
+            Self::One(_) => {
+                *self = Self::None;
+            }
+            Self::Two(v) => {
+                *self = Self::One(*v);
+            }
+        }
+    }
+
+    /// Get the value if it's ready, which is only at `One` epoch remaining
+    pub fn get(&self) -> Option<&SolWithdrawalFeeCurve> {
+        match self {
+            Self::None | Self::Two(_) => None,
+            Self::One(v) => Some(v),
+        }
+    }
+}
+
+impl From<FutureEpochSolWithdrawalFeeCurve> for Option<SolWithdrawalFeeCurve> {
+    fn from(v: FutureEpochSolWithdrawalFeeCurve) -> Option<SolWithdrawalFeeCurve> {
         match v {
-            FutureEpochFee::None => None,
-            FutureEpochFee::One(inner) | FutureEpochFee::Two(inner) => Some(inner),
+            FutureEpochSolWithdrawalFeeCurve::None => None,
+            FutureEpochSolWithdrawalFeeCurve::One(inner)
+            | FutureEpochSolWithdrawalFeeCurve::Two(inner) => Some(inner),
         }
     }
 }
@@ -711,6 +2378,64 @@ impl Fee {
             .checked_sub(1)?
             .checked_div(denominator)
     }
+
+    /// Withdrawal fees have some additional restrictions, this function checks
+    /// if those are met, returning an error if not.
+    pub fn check_withdrawal(&self, old_withdrawal_fee: &Fee) -> Result<(), StakePoolError> {
+        self.check_increase(
+            old_withdrawal_fee,
+            &WITHDRAWAL_BASELINE_FEE,
+            &MAX_WITHDRAWAL_FEE_INCREASE,
+        )
+    }
+
+    /// Deposit fees can only increase by `MAX_DEPOSIT_FEE_INCREASE` per fee
+    /// change, this function checks that, returning an error if not.
+    pub fn check_deposit(&self, old_deposit_fee: &Fee) -> Result<(), StakePoolError> {
+        self.check_increase(
+            old_deposit_fee,
+            &DEPOSIT_BASELINE_FEE,
+            &MAX_DEPOSIT_FEE_INCREASE,
+        )
+    }
+
+    /// Checks that `self / old_fee <= max_increase`, using `baseline` instead
+    /// of `old_fee` if it's 0
+    fn check_increase(
+        &self,
+        old_fee: &Fee,
+        baseline: &Fee,
+        max_increase: &Fee,
+    ) -> Result<(), StakePoolError> {
+        // If the previous fee was 0, we allow the fee to be set to a
+        // maximum of (baseline * max_increase)
+        let (old_num, old_denom) = if old_fee.denominator == 0 || old_fee.numerator == 0 {
+            (baseline.numerator, baseline.denominator)
+        } else {
+            (old_fee.numerator, old_fee.denominator)
+        };
+
+        // Check that new_fee / old_fee <= max_increase
+        // Program fails if provided numerator or denominator is too large, resulting in
+        // overflow
+        if (old_num as u128)
+            .checked_mul(self.denominator as u128)
+            .map(|x| x.checked_mul(max_increase.numerator as u128))
+            .ok_or(StakePoolError::CalculationFailure)?
+            < (self.numerator as u128)
+                .checked_mul(old_denom as u128)
+                .map(|x| x.checked_mul(max_increase.denominator as u128))
+                .ok_or(StakePoolError::CalculationFailure)?
+        {
+            msg!(
+                "Fee increase exceeds maximum allowed, proposed increase factor ({} / {})",
+                self.numerator.saturating_mul(old_denom),
+                old_num.saturating_mul(self.denominator),
+            );
+            return Err(StakePoolError::FeeIncreaseTooHigh);
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for Fee {
@@ -723,6 +2448,56 @@ impl fmt::Display for Fee {
     }
 }
 
+/// Curve raising the SOL withdrawal fee as the reserve drains, from the flat
+/// `sol_withdrawal_fee` when the reserve holds its target share of the pool
+/// up to `max_fee` once it is empty
+///
+/// If `target_reserve_bps` is 0, the curve is off and only the flat fee is
+/// charged
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshSerialize, BorshDeserialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub struct SolWithdrawalFeeCurve {
+    /// Share of `total_lamports`, in basis points, that the reserve should
+    /// keep available for SOL withdrawals
+    pub target_reserve_bps: u16,
+    /// Fee charged when a withdrawal empties the reserve
+    pub max_fee: Fee,
+    /// Exponent of the curve, 1 raising the fee linearly with the shortfall
+    /// and higher values keeping it low until the reserve is nearly empty
+    pub exponent: u8,
+}
+
+impl SolWithdrawalFeeCurve {
+    /// Check if the curve applies to SOL withdrawals
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.target_reserve_bps > 0
+    }
+
+    /// Check if the curve is off, or has a valid target and exponent
+    pub fn is_valid(&self) -> bool {
+        !self.is_enabled()
+            || (self.target_reserve_bps <= MAX_BASIS_POINTS
+                && self.exponent > 0
+                && self.exponent <= MAX_SOL_WITHDRAWAL_FEE_CURVE_EXPONENT)
+    }
+
+    /// Checks that an enabled curve has a valid target and exponent,
+    /// returning an error if not
+    pub fn check(&self) -> Result<(), StakePoolError> {
+        if !self.is_valid() {
+            msg!("Invalid SOL withdrawal fee curve: {:?}", self);
+            return Err(StakePoolError::InvalidSolWithdrawalFeeCurve);
+        }
+        Ok(())
+    }
+}
+
 /// The type of fees that can be set on the stake pool
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(
@@ -745,6 +2520,8 @@ pub enum FeeType {
     StakeDeposit(Fee),
     /// SOL withdrawal fee
     SolWithdrawal(Fee),
+    /// Curve raising the SOL withdrawal fee as the reserve drains
+    SolWithdrawalCurve(SolWithdrawalFeeCurve),
 }
 
 impl FeeType {
@@ -758,22 +2535,107 @@ impl FeeType {
             Self::SolWithdrawal(fee) => fee.numerator > fee.denominator,
             Self::SolDeposit(fee) => fee.numerator > fee.denominator,
             Self::StakeDeposit(fee) => fee.numerator > fee.denominator,
+            Self::SolWithdrawalCurve(curve) => curve.max_fee.numerator > curve.max_fee.denominator,
         }
     }
 
-    /// Returns if the contained fee can only be updated earliest on the next epoch
+    /// Checks if the provided fee is too high, returning an error if so
+    pub fn check_too_high(&self) -> Result<(), StakePoolError> {
+        if self.is_too_high() {
+            msg!("Fee greater than 100%: {:?}", self);
+            return Err(StakePoolError::FeeTooHigh);
+        }
+        Ok(())
+    }
+
+    /// Returns if the contained fee can only be updated earliest on the next
+    /// epoch, which is every fee since they all go through a `FutureEpoch`
     #[inline]
     pub fn can_only_change_next_epoch(&self) -> bool {
         matches!(
             self,
-            Self::StakeWithdrawal(_) | Self::SolWithdrawal(_) | Self::Epoch(_)
+            Self::SolReferral(_)
+                | Self::StakeReferral(_)
+                | Self::Epoch(_)
+                | Self::StakeWithdrawal(_)
+                | Self::SolDeposit(_)
+                | Self::StakeDeposit(_)
+                | Self::SolWithdrawal(_)
+                | Self::SolWithdrawalCurve(_)
         )
     }
 }
 
+/// Operations of the stake pool that can be paused independently, each one
+/// bit of `StakePool::paused_operations`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PausedOperation {
+    /// Stake and SOL deposits
+    Deposit,
+    /// Stake and SOL withdrawals, including withdrawal tickets
+    Withdrawal,
+    /// All instructions authorized through a Fogo session
+    Session,
+    /// Increasing, decreasing and redelegating validator stake, and
+    /// deactivating validators through the permissionless cranks
+    Rebalance,
+}
+
+impl PausedOperation {
+    /// Bitmask with every operation paused
+    pub const ALL: u8 = 0b1111;
+
+    /// Bit of this operation in `StakePool::paused_operations`
+    #[inline]
+    pub const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The type of flow caps that can be set on the stake pool, each in lamports
+/// or `None` to remove the cap
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(BorshDeserialize, BorshSerialize, BorshSchema)
+)]
+#[cfg_attr(feature = "codama", derive(CodamaType))]
+pub enum CapType {
+    /// Maximum lamports deposited as SOL or stake per epoch
+    DepositPerEpoch(Option<u64>),
+    /// Maximum lamports withdrawn as SOL per epoch, including withdrawal
+    /// tickets
+    SolWithdrawalPerEpoch(Option<u64>),
+    /// Maximum total lamports in the pool, checked on deposit
+    TotalLamports(Option<u64>),
+    /// Maximum share of the pool, in basis points, a validator may reach
+    /// through validator-targeted SOL deposits. `None` turns those deposits
+    /// off rather than removing the limit.
+    DirectStakeShare(Option<u16>),
+    /// Maximum share of the pool, in basis points, any single validator's
+    /// stake may reach through stake increases and deposits
+    ValidatorShare(Option<u16>),
+    /// Maximum commission, as a percentage, of validators in the pool. Anyone
+    /// can start deactivating a validator whose commission goes above it.
+    ValidatorCommission(Option<u8>),
+    /// Maximum number of epochs, at least 1, a validator may go without
+    /// earning vote credits. Anyone can start deactivating a validator that
+    /// stays silent for longer.
+    ValidatorVoteAge(Option<u64>),
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    #![allow(clippy::arithmetic_side_effects)]
+    use {
+        super::*,
+        proptest::prelude::*,
+        solana_program::{
+            borsh1::{get_packed_len, try_from_slice_unchecked},
+            clock::{DEFAULT_SLOTS_PER_EPOCH, DEFAULT_S_PER_SLOT, SECONDS_PER_DAY},
+            native_token::LAMPORTS_PER_SOL,
+        },
+    };
 
     #[test]
     fn test_stake_pool_is_valid() {
@@ -818,6 +2680,92 @@ mod tests {
         assert_eq!(empty_pool.calc_pool_tokens_for_deposit(100), Some(100));
     }
 
+    #[test]
+    fn test_preview_sol_withdrawal() {
+        let pool = StakePool {
+            total_lamports: 1_000_000,
+            pool_token_supply: 1_000_000,
+            sol_withdrawal_fee: Fee {
+                numerator: 1,
+                denominator: 1000,
+            },
+            sol_withdrawal_fee_curve: SolWithdrawalFeeCurve {
+                target_reserve_bps: 1_000,
+                max_fee: Fee {
+                    numerator: 1,
+                    denominator: 100,
+                },
+                exponent: 1,
+            },
+            ..Default::default()
+        };
+        // Reserve stays above its 100_000 lamport target, flat fee only
+        assert_eq!(
+            pool.calc_dynamic_sol_withdrawal_fee(10_000, 200_000),
+            Some(10)
+        );
+        assert_eq!(pool.preview_sol_withdrawal(10_000, 200_000), Some(9_990));
+
+        // Reserve ends half way to its target
+        assert_eq!(
+            pool.calc_dynamic_sol_withdrawal_fee(10_000, 60_000),
+            Some(55)
+        );
+        assert_eq!(pool.preview_sol_withdrawal(10_000, 60_000), Some(9_945));
+
+        // Reserve cannot pay out the withdrawal
+        assert_eq!(pool.preview_sol_withdrawal(10_000, 5_000), None);
+
+        assert_eq!(pool.calc_reserve_liquidity_lamports(12_000, 2_000), 10_000);
+    }
+
+    #[test]
+    fn test_sol_withdrawal_fee_curve() {
+        use solana_program::native_token::LAMPORTS_PER_SOL;
+
+        let curve = SolWithdrawalFeeCurve {
+            target_reserve_bps: 1_000,
+            max_fee: Fee {
+                numerator: 1,
+                denominator: 100,
+            },
+            exponent: 1,
+        };
+        let mut pool = StakePool {
+            total_lamports: 100 * LAMPORTS_PER_SOL,
+            pool_token_supply: 100 * LAMPORTS_PER_SOL,
+            sol_withdrawal_fee: Fee {
+                numerator: 1,
+                denominator: 1000,
+            },
+            ..Default::default()
+        };
+        let pool_tokens = LAMPORTS_PER_SOL;
+        let fee = |pool: &StakePool, reserve_liquidity_lamports| {
+            pool.calc_dynamic_sol_withdrawal_fee(pool_tokens, reserve_liquidity_lamports)
+                .unwrap()
+        };
+
+        // only the flat fee without a curve
+        assert_eq!(fee(&pool, 0), 1_000_000);
+
+        pool.sol_withdrawal_fee_curve = curve;
+        // the reserve stays above its 10 SOL target
+        assert_eq!(fee(&pool, 20 * LAMPORTS_PER_SOL), 1_000_000);
+        // the reserve ends half way to its target
+        assert_eq!(fee(&pool, 6 * LAMPORTS_PER_SOL), 5_500_000);
+        // the withdrawal empties the reserve
+        assert_eq!(fee(&pool, LAMPORTS_PER_SOL), 10_000_000);
+
+        pool.sol_withdrawal_fee_curve.exponent = 2;
+        assert_eq!(fee(&pool, 6 * LAMPORTS_PER_SOL), 3_250_000);
+        assert_eq!(fee(&pool, LAMPORTS_PER_SOL), 10_000_000);
+
+        // a curve below the flat fee never lowers it
+        pool.sol_withdrawal_fee_curve.max_fee = Fee::default();
+        assert_eq!(fee(&pool, LAMPORTS_PER_SOL), 1_000_000);
+    }
+
     #[test]
     fn test_future_epoch() {
         let mut future: FutureEpoch<u64> = FutureEpoch::new(42);
@@ -829,4 +2777,954 @@ mod tests {
         future.update_epoch();
         assert_eq!(future.get(), None); // Gone
     }
+
+    #[test]
+    fn test_rate_history_trailing_apy() {
+        const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+        let entry = |epoch: u64, total_lamports: u64| RateHistoryEntry {
+            epoch,
+            unix_timestamp: epoch as i64 * SECONDS_PER_DAY,
+            total_lamports,
+            pool_token_supply: 1_000_000,
+            fee_pool_tokens: 0,
+        };
+        let mut history = RateHistory {
+            account_type: AccountType::RateHistory,
+            stake_pool: Pubkey::new_unique(),
+            next_index: 0,
+            len: 0,
+            entries: [RateHistoryEntry::default(); RATE_HISTORY_LEN],
+        };
+        assert_eq!(history.latest(), None);
+        assert_eq!(history.trailing_apy(1), None);
+
+        // full buffer, wrapped around so that the newest entry is at index 1
+        for (index, epoch) in (10..10 + RATE_HISTORY_LEN as u64).enumerate() {
+            history.entries[(index + 2) % RATE_HISTORY_LEN] = entry(epoch, 1_000_000 + epoch - 10);
+        }
+        history.next_index = 2;
+        history.len = RATE_HISTORY_LEN as u16;
+        assert_eq!(history.entries().next().unwrap().epoch, 10);
+        let latest_epoch = 10 + RATE_HISTORY_LEN as u64 - 1;
+        assert_eq!(history.latest().unwrap().epoch, latest_epoch);
+
+        // not enough history
+        assert_eq!(history.trailing_apy(RATE_HISTORY_LEN as u64), None);
+
+        // compounding a steady daily rate over the last 100 epochs
+        for epoch in latest_epoch - 100..=latest_epoch {
+            let lamports = 1_000_000.0 * 1.0001f64.powi((epoch - (latest_epoch - 100)) as i32);
+            let index = (epoch as usize - 10 + 2) % RATE_HISTORY_LEN;
+            history.entries[index] = entry(epoch, lamports as u64);
+        }
+        let apy = history.trailing_apy(100).unwrap();
+        let expected = 1.0001f64.powf(365.25) - 1.0;
+        assert!((apy - expected).abs() < 1e-3, "{apy} != {expected}");
+    }
+
+    #[test]
+    fn test_exchange_rate_matches_stake_pool() {
+        let stake_pool = StakePool {
+            total_lamports: 1_100,
+            pool_token_supply: 1_000,
+            last_update_epoch: 5,
+            ..StakePool::default()
+        };
+        let exchange_rate = ExchangeRate::from(&stake_pool);
+        assert!(!exchange_rate.is_stale(5));
+        assert!(exchange_rate.is_stale(6));
+        for amount in [0, 1, 999, 1_000, 123_456_789] {
+            assert_eq!(
+                exchange_rate.pool_tokens_to_lamports(amount),
+                stake_pool.calc_lamports_withdraw_amount(amount)
+            );
+            assert_eq!(
+                exchange_rate.lamports_to_pool_tokens(amount),
+                stake_pool.calc_pool_tokens_for_deposit(amount)
+            );
+        }
+        assert_eq!(exchange_rate.lamports_per_pool_token(), Some(1.1));
+    }
+
+    fn uninitialized_validator_list() -> ValidatorList {
+        ValidatorList {
+            header: ValidatorListHeader {
+                account_type: AccountType::Uninitialized,
+                max_validators: 0,
+            },
+            validators: vec![],
+        }
+    }
+
+    fn test_validator_list(max_validators: u32) -> ValidatorList {
+        ValidatorList {
+            header: ValidatorListHeader {
+                account_type: AccountType::ValidatorList,
+                max_validators,
+            },
+            validators: vec![
+                ValidatorStakeInfo {
+                    status: StakeStatus::Active.into(),
+                    vote_account_address: Pubkey::new_from_array([1; 32]),
+                    active_stake_lamports: u64::from_le_bytes([255; 8]).into(),
+                    transient_stake_lamports: u64::from_le_bytes([128; 8]).into(),
+                    last_update_epoch: u64::from_le_bytes([64; 8]).into(),
+                    transient_seed_suffix: 0.into(),
+                    max_share_bps: 0.into(),
+                    validator_seed_suffix: 0.into(),
+                },
+                ValidatorStakeInfo {
+                    status: StakeStatus::DeactivatingTransient.into(),
+                    vote_account_address: Pubkey::new_from_array([2; 32]),
+                    active_stake_lamports: 998877665544.into(),
+                    transient_stake_lamports: 222222222.into(),
+                    last_update_epoch: 11223445566.into(),
+                    transient_seed_suffix: 0.into(),
+                    max_share_bps: 0.into(),
+                    validator_seed_suffix: 0.into(),
+                },
+                ValidatorStakeInfo {
+                    status: StakeStatus::ReadyForRemoval.into(),
+                    vote_account_address: Pubkey::new_from_array([3; 32]),
+                    active_stake_lamports: 0.into(),
+                    transient_stake_lamports: 0.into(),
+                    last_update_epoch: 999999999999999.into(),
+                    transient_seed_suffix: 0.into(),
+                    max_share_bps: 0.into(),
+                    validator_seed_suffix: 0.into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn state_packing() {
+        let max_validators = 10_000;
+        let size = get_instance_packed_len(&ValidatorList::new(max_validators)).unwrap();
+        let stake_list = uninitialized_validator_list();
+        let mut byte_vec = vec![0u8; size];
+        let bytes = byte_vec.as_mut_slice();
+        borsh::to_writer(bytes, &stake_list).unwrap();
+        let stake_list_unpacked = try_from_slice_unchecked::<ValidatorList>(&byte_vec).unwrap();
+        assert_eq!(stake_list_unpacked, stake_list);
+
+        // Empty, one preferred key
+        let stake_list = ValidatorList {
+            header: ValidatorListHeader {
+                account_type: AccountType::ValidatorList,
+                max_validators: 0,
+            },
+            validators: vec![],
+        };
+        let mut byte_vec = vec![0u8; size];
+        let bytes = byte_vec.as_mut_slice();
+        borsh::to_writer(bytes, &stake_list).unwrap();
+        let stake_list_unpacked = try_from_slice_unchecked::<ValidatorList>(&byte_vec).unwrap();
+        assert_eq!(stake_list_unpacked, stake_list);
+
+        // With several accounts
+        let stake_list = test_validator_list(max_validators);
+        let mut byte_vec = vec![0u8; size];
+        let bytes = byte_vec.as_mut_slice();
+        borsh::to_writer(bytes, &stake_list).unwrap();
+        let stake_list_unpacked = try_from_slice_unchecked::<ValidatorList>(&byte_vec).unwrap();
+        assert_eq!(stake_list_unpacked, stake_list);
+    }
+
+    #[test]
+    fn validator_list_active_stake() {
+        let max_validators = 10_000;
+        let mut validator_list = test_validator_list(max_validators);
+        assert!(validator_list.has_active_stake());
+        for validator in validator_list.validators.iter_mut() {
+            validator.active_stake_lamports = 0.into();
+        }
+        assert!(!validator_list.has_active_stake());
+    }
+
+    #[test]
+    fn validator_list_deserialize_mut_slice() {
+        let max_validators = 10;
+        let stake_list = test_validator_list(max_validators);
+        let mut serialized = borsh::to_vec(&stake_list).unwrap();
+        let (header, mut big_vec) = ValidatorListHeader::deserialize_vec(&mut serialized).unwrap();
+        let list = ValidatorListHeader::deserialize_mut_slice(
+            &mut big_vec,
+            0,
+            stake_list.validators.len(),
+        )
+        .unwrap();
+        assert_eq!(header.account_type, AccountType::ValidatorList);
+        assert_eq!(header.max_validators, max_validators);
+        assert!(list
+            .iter()
+            .zip(stake_list.validators.iter())
+            .all(|(a, b)| a == b));
+
+        let list = ValidatorListHeader::deserialize_mut_slice(&mut big_vec, 1, 2).unwrap();
+        assert!(list
+            .iter()
+            .zip(stake_list.validators[1..].iter())
+            .all(|(a, b)| a == b));
+        let list = ValidatorListHeader::deserialize_mut_slice(&mut big_vec, 2, 1).unwrap();
+        assert!(list
+            .iter()
+            .zip(stake_list.validators[2..].iter())
+            .all(|(a, b)| a == b));
+        let list = ValidatorListHeader::deserialize_mut_slice(&mut big_vec, 0, 2).unwrap();
+        assert!(list
+            .iter()
+            .zip(stake_list.validators[..2].iter())
+            .all(|(a, b)| a == b));
+
+        assert_eq!(
+            ValidatorListHeader::deserialize_mut_slice(&mut big_vec, 0, 4).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+        assert_eq!(
+            ValidatorListHeader::deserialize_mut_slice(&mut big_vec, 1, 3).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+    }
+
+    #[test]
+    fn validator_list_iter() {
+        let max_validators = 10;
+        let stake_list = test_validator_list(max_validators);
+        let mut serialized = borsh::to_vec(&stake_list).unwrap();
+        let (_, big_vec) = ValidatorListHeader::deserialize_vec(&mut serialized).unwrap();
+        for (a, b) in big_vec
+            .deserialize_slice::<ValidatorStakeInfo>(0, big_vec.len() as usize)
+            .unwrap()
+            .iter()
+            .zip(stake_list.validators.iter())
+        {
+            assert_eq!(a, b);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn stake_list_size_calculation(test_amount in 0..=100_000_u32) {
+            let validators = ValidatorList::new(test_amount);
+            let size = get_instance_packed_len(&validators).unwrap();
+            assert_eq!(ValidatorList::calculate_max_validators(size), test_amount as usize);
+            assert_eq!(ValidatorList::calculate_max_validators(size.saturating_add(1)), test_amount as usize);
+            assert_eq!(ValidatorList::calculate_max_validators(size.saturating_add(get_packed_len::<ValidatorStakeInfo>())), (test_amount + 1)as usize);
+            assert_eq!(ValidatorList::calculate_max_validators(size.saturating_sub(1)), (test_amount.saturating_sub(1)) as usize);
+        }
+    }
+
+    prop_compose! {
+        fn fee()(denominator in 1..=u16::MAX)(
+            denominator in Just(denominator),
+            numerator in 0..=denominator,
+        ) -> (u64, u64) {
+            (numerator as u64, denominator as u64)
+        }
+    }
+
+    prop_compose! {
+        fn total_stake_and_rewards()(total_lamports in 1..u64::MAX)(
+            total_lamports in Just(total_lamports),
+            rewards in 0..=total_lamports,
+        ) -> (u64, u64) {
+            (total_lamports - rewards, rewards)
+        }
+    }
+
+    #[test]
+    fn specific_fee_calculation() {
+        // 10% of 10 SOL in rewards should be 1 SOL in fees
+        let epoch_fee = Fee {
+            numerator: 1,
+            denominator: 10,
+        };
+        let mut stake_pool = StakePool {
+            total_lamports: 100 * LAMPORTS_PER_SOL,
+            pool_token_supply: 100 * LAMPORTS_PER_SOL,
+            epoch_fee,
+            ..StakePool::default()
+        };
+        let reward_lamports = 10 * LAMPORTS_PER_SOL;
+        let pool_token_fee = stake_pool.calc_epoch_fee_amount(reward_lamports).unwrap();
+
+        stake_pool.total_lamports += reward_lamports;
+        stake_pool.pool_token_supply += pool_token_fee;
+
+        let fee_lamports = stake_pool
+            .calc_lamports_withdraw_amount(pool_token_fee)
+            .unwrap();
+        assert_eq!(fee_lamports, LAMPORTS_PER_SOL - 1); // off-by-one due to
+                                                        // truncation
+    }
+
+    #[test]
+    fn insurance_fee_calculation() {
+        // a quarter of a 1 SOL fee goes to the insurance fund, the manager's
+        // pool tokens are worth the rest
+        let mut stake_pool = StakePool {
+            total_lamports: 100 * LAMPORTS_PER_SOL,
+            pool_token_supply: 100 * LAMPORTS_PER_SOL,
+            epoch_fee: Fee {
+                numerator: 1,
+                denominator: 10,
+            },
+            insurance_fee_bps: 2_500,
+            ..StakePool::default()
+        };
+        let reward_lamports = 10 * LAMPORTS_PER_SOL;
+        let insurance_lamports = stake_pool
+            .calc_insurance_fee_lamports(reward_lamports)
+            .unwrap();
+        assert_eq!(insurance_lamports, LAMPORTS_PER_SOL / 4);
+        let pool_token_fee = stake_pool
+            .calc_epoch_fee_amount_after_insurance(reward_lamports, insurance_lamports)
+            .unwrap();
+
+        stake_pool.total_lamports += reward_lamports - insurance_lamports;
+        stake_pool.pool_token_supply += pool_token_fee;
+
+        let fee_lamports = stake_pool
+            .calc_lamports_withdraw_amount(pool_token_fee)
+            .unwrap();
+        assert_eq!(fee_lamports, 3 * LAMPORTS_PER_SOL / 4 - 1);
+
+        // without a share, everything goes to the manager
+        stake_pool.insurance_fee_bps = 0;
+        assert_eq!(
+            stake_pool.calc_insurance_fee_lamports(reward_lamports),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn rate_history_record() {
+        let mut data = vec![0u8; get_packed_len::<RateHistory>()];
+        data[0] = AccountType::RateHistory as u8;
+        let entry = |epoch: u64| RateHistoryEntry {
+            epoch,
+            unix_timestamp: epoch as i64 * 100,
+            total_lamports: 1_000 + epoch,
+            pool_token_supply: 1_000,
+            fee_pool_tokens: 1,
+        };
+
+        RateHistory::record(&mut data, entry(0)).unwrap();
+        RateHistory::record(&mut data, entry(0)).unwrap();
+        let history = try_from_slice_unchecked::<RateHistory>(&data).unwrap();
+        assert!(history.is_valid());
+        assert_eq!(history.len, 1);
+        assert_eq!(history.latest().unwrap().fee_pool_tokens, 2);
+
+        // wrap around, keeping the most recent epochs in order
+        let epochs = RATE_HISTORY_LEN as u64 + 10;
+        for epoch in 1..epochs {
+            RateHistory::record(&mut data, entry(epoch)).unwrap();
+        }
+        let history = try_from_slice_unchecked::<RateHistory>(&data).unwrap();
+        assert_eq!(history.len as usize, RATE_HISTORY_LEN);
+        assert_eq!(history.next_index, 10);
+        assert!(history
+            .entries()
+            .map(|entry| entry.epoch)
+            .eq(epochs - RATE_HISTORY_LEN as u64..epochs));
+
+        let mut truncated = data.clone();
+        truncated.pop();
+        assert_eq!(
+            RateHistory::record(&mut truncated, entry(epochs)),
+            Err(StakePoolError::InvalidRateHistory.into())
+        );
+    }
+
+    #[test]
+    fn exchange_rate_matches_stake_pool() {
+        let stake_pool = StakePool {
+            total_lamports: 1_100,
+            pool_token_supply: 1_000,
+            last_update_epoch: 5,
+            ..StakePool::default()
+        };
+        let exchange_rate = ExchangeRate::from(&stake_pool);
+        assert!(!exchange_rate.is_stale(5));
+        assert!(exchange_rate.is_stale(6));
+        for amount in [0, 1, 999, 1_000, 123_456_789] {
+            assert_eq!(
+                exchange_rate.pool_tokens_to_lamports(amount),
+                stake_pool.calc_lamports_withdraw_amount(amount)
+            );
+            assert_eq!(
+                exchange_rate.lamports_to_pool_tokens(amount),
+                stake_pool.calc_pool_tokens_for_deposit(amount)
+            );
+        }
+        assert_eq!(exchange_rate.lamports_per_pool_token(), Some(1.1));
+        assert_eq!(ExchangeRate::default().lamports_per_pool_token(), None);
+        assert_eq!(
+            ExchangeRate::default().lamports_to_pool_tokens(10),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn zero_withdraw_calculation() {
+        let epoch_fee = Fee {
+            numerator: 0,
+            denominator: 1,
+        };
+        let stake_pool = StakePool {
+            epoch_fee,
+            ..StakePool::default()
+        };
+        let fee_lamports = stake_pool.calc_lamports_withdraw_amount(0).unwrap();
+        assert_eq!(fee_lamports, 0);
+    }
+
+    #[test]
+    fn divide_by_zero_fee() {
+        let stake_pool = StakePool {
+            total_lamports: 0,
+            epoch_fee: Fee {
+                numerator: 1,
+                denominator: 10,
+            },
+            ..StakePool::default()
+        };
+        let rewards = 10;
+        let fee = stake_pool.calc_epoch_fee_amount(rewards).unwrap();
+        assert_eq!(fee, rewards);
+    }
+
+    #[test]
+    fn approximate_apr_calculation() {
+        // 8% / year means roughly .044% / epoch
+        let stake_pool = StakePool {
+            last_epoch_total_lamports: 100_000,
+            last_epoch_pool_token_supply: 100_000,
+            total_lamports: 100_044,
+            pool_token_supply: 100_000,
+            ..StakePool::default()
+        };
+        let pool_token_value =
+            stake_pool.total_lamports as f64 / stake_pool.pool_token_supply as f64;
+        let last_epoch_pool_token_value = stake_pool.last_epoch_total_lamports as f64
+            / stake_pool.last_epoch_pool_token_supply as f64;
+        let epoch_rate = pool_token_value / last_epoch_pool_token_value - 1.0;
+        const SECONDS_PER_EPOCH: f64 = DEFAULT_SLOTS_PER_EPOCH as f64 * DEFAULT_S_PER_SLOT;
+        const EPOCHS_PER_YEAR: f64 = SECONDS_PER_DAY as f64 * 365.25 / SECONDS_PER_EPOCH;
+        const EPSILON: f64 = 0.00001;
+        let yearly_rate = epoch_rate * EPOCHS_PER_YEAR;
+        assert!((yearly_rate - 0.080355).abs() < EPSILON);
+    }
+
+    proptest! {
+        #[test]
+        fn fee_calculation(
+            (numerator, denominator) in fee(),
+            (total_lamports, reward_lamports) in total_stake_and_rewards(),
+        ) {
+            let epoch_fee = Fee { denominator, numerator };
+            let mut stake_pool = StakePool {
+                total_lamports,
+                pool_token_supply: total_lamports,
+                epoch_fee,
+                ..StakePool::default()
+            };
+            let pool_token_fee = stake_pool.calc_epoch_fee_amount(reward_lamports).unwrap();
+
+            stake_pool.total_lamports += reward_lamports;
+            stake_pool.pool_token_supply += pool_token_fee;
+
+            let fee_lamports = stake_pool.calc_lamports_withdraw_amount(pool_token_fee).unwrap();
+            let max_fee_lamports = u64::try_from((reward_lamports as u128) * (epoch_fee.numerator as u128) / (epoch_fee.denominator as u128)).unwrap();
+            assert!(max_fee_lamports >= fee_lamports,
+                "Max possible fee must always be greater than or equal to what is actually withdrawn, max {} actual {}",
+                max_fee_lamports,
+                fee_lamports);
+
+            // since we do two "flooring" conversions, the max epsilon should be
+            // correct up to 2 lamports (one for each floor division), plus a
+            // correction for huge discrepancies between rewards and total stake
+            let epsilon = 2 + reward_lamports / total_lamports;
+            assert!(max_fee_lamports - fee_lamports <= epsilon,
+                "Max expected fee in lamports {}, actually receive {}, epsilon {}",
+                max_fee_lamports, fee_lamports, epsilon);
+        }
+    }
+
+    prop_compose! {
+        fn total_tokens_and_deposit()(total_lamports in 1..u64::MAX)(
+            total_lamports in Just(total_lamports),
+            pool_token_supply in 1..=total_lamports,
+            deposit_lamports in 1..total_lamports,
+        ) -> (u64, u64, u64) {
+            (total_lamports - deposit_lamports, pool_token_supply.saturating_sub(deposit_lamports).max(1), deposit_lamports)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn deposit_and_withdraw(
+            (total_lamports, pool_token_supply, deposit_stake) in total_tokens_and_deposit()
+        ) {
+            let mut stake_pool = StakePool {
+                total_lamports,
+                pool_token_supply,
+                ..StakePool::default()
+            };
+            let deposit_result = stake_pool.calc_pool_tokens_for_deposit(deposit_stake).unwrap();
+            prop_assume!(deposit_result > 0);
+            stake_pool.total_lamports += deposit_stake;
+            stake_pool.pool_token_supply += deposit_result;
+            let withdraw_result = stake_pool.calc_lamports_withdraw_amount(deposit_result).unwrap();
+            assert!(withdraw_result <= deposit_stake);
+
+            // also test splitting the withdrawal in two operations
+            if deposit_result >= 2 {
+                let first_half_deposit = deposit_result / 2;
+                let first_withdraw_result = stake_pool.calc_lamports_withdraw_amount(first_half_deposit).unwrap();
+                stake_pool.total_lamports -= first_withdraw_result;
+                stake_pool.pool_token_supply -= first_half_deposit;
+                let second_half_deposit = deposit_result - first_half_deposit; // do the whole thing
+                let second_withdraw_result = stake_pool.calc_lamports_withdraw_amount(second_half_deposit).unwrap();
+                assert!(first_withdraw_result + second_withdraw_result <= deposit_stake);
+            }
+        }
+    }
+
+    #[test]
+    fn specific_split_withdrawal() {
+        let total_lamports = 1_100_000_000_000;
+        let pool_token_supply = 1_000_000_000_000;
+        let deposit_stake = 3;
+        let mut stake_pool = StakePool {
+            total_lamports,
+            pool_token_supply,
+            ..StakePool::default()
+        };
+        let deposit_result = stake_pool
+            .calc_pool_tokens_for_deposit(deposit_stake)
+            .unwrap();
+        assert!(deposit_result > 0);
+        stake_pool.total_lamports += deposit_stake;
+        stake_pool.pool_token_supply += deposit_result;
+        let withdraw_result = stake_pool
+            .calc_lamports_withdraw_amount(deposit_result / 2)
+            .unwrap();
+        assert!(withdraw_result * 2 <= deposit_stake);
+    }
+
+    #[test]
+    fn withdraw_all() {
+        let total_lamports = 1_100_000_000_000;
+        let pool_token_supply = 1_000_000_000_000;
+        let mut stake_pool = StakePool {
+            total_lamports,
+            pool_token_supply,
+            ..StakePool::default()
+        };
+        // take everything out at once
+        let withdraw_result = stake_pool
+            .calc_lamports_withdraw_amount(pool_token_supply)
+            .unwrap();
+        assert_eq!(stake_pool.total_lamports, withdraw_result);
+
+        // take out 1, then the rest
+        let withdraw_result = stake_pool.calc_lamports_withdraw_amount(1).unwrap();
+        stake_pool.total_lamports -= withdraw_result;
+        stake_pool.pool_token_supply -= 1;
+        let withdraw_result = stake_pool
+            .calc_lamports_withdraw_amount(stake_pool.pool_token_supply)
+            .unwrap();
+        assert_eq!(stake_pool.total_lamports, withdraw_result);
+
+        // take out all except 1, then the rest
+        let mut stake_pool = StakePool {
+            total_lamports,
+            pool_token_supply,
+            ..StakePool::default()
+        };
+        let withdraw_result = stake_pool
+            .calc_lamports_withdraw_amount(pool_token_supply - 1)
+            .unwrap();
+        stake_pool.total_lamports -= withdraw_result;
+        stake_pool.pool_token_supply = 1;
+        assert_ne!(stake_pool.total_lamports, 0);
+
+        let withdraw_result = stake_pool.calc_lamports_withdraw_amount(1).unwrap();
+        assert_eq!(stake_pool.total_lamports, withdraw_result);
+    }
+
+    #[test]
+    fn paused_operations() {
+        let mut stake_pool = StakePool {
+            paused_operations: PausedOperation::Deposit.mask() | PausedOperation::Session.mask(),
+            ..StakePool::default()
+        };
+        assert!(stake_pool.is_paused(PausedOperation::Deposit));
+        assert!(!stake_pool.is_paused(PausedOperation::Withdrawal));
+        assert!(stake_pool.is_paused(PausedOperation::Session));
+        assert!(!stake_pool.is_paused(PausedOperation::Rebalance));
+
+        stake_pool.paused_operations = PausedOperation::ALL;
+        for operation in [
+            PausedOperation::Deposit,
+            PausedOperation::Withdrawal,
+            PausedOperation::Session,
+            PausedOperation::Rebalance,
+        ] {
+            assert_eq!(
+                stake_pool.check_not_paused(operation),
+                Err(StakePoolError::OperationPaused.into())
+            );
+        }
+    }
+
+    #[test]
+    fn referral_codes() {
+        let code = Referrer::pack_code("xlabs-test_1").unwrap();
+        let referrer = Referrer {
+            code,
+            ..Referrer::default()
+        };
+        assert_eq!(referrer.code(), "xlabs-test_1");
+
+        let longest = "a".repeat(MAX_REFERRAL_CODE_LENGTH);
+        let referrer = Referrer {
+            code: Referrer::pack_code(&longest).unwrap(),
+            ..Referrer::default()
+        };
+        assert_eq!(referrer.code(), longest);
+
+        for invalid in ["", "with space", "ref:code", "ünicode", &"a".repeat(33)] {
+            assert_eq!(
+                Referrer::pack_code(invalid),
+                Err(StakePoolError::InvalidReferralCode)
+            );
+        }
+    }
+
+    #[test]
+    fn timelocked_fees() {
+        let old_fee = Fee {
+            numerator: 1,
+            denominator: 100,
+        };
+        let new_fee = Fee {
+            numerator: 3,
+            denominator: 200,
+        };
+        let mut stake_pool = StakePool {
+            sol_deposit_fee: old_fee,
+            ..StakePool::default()
+        };
+
+        // set immediately while there are no pool tokens
+        stake_pool.update_fee(&FeeType::SolReferral(50), 1).unwrap();
+        assert_eq!(stake_pool.sol_referral_fee, 50);
+        assert_eq!(
+            stake_pool.next_sol_referral_fee,
+            FutureEpochReferralFee::None
+        );
+
+        // a new pool holding only the manager's initial pool tokens
+        stake_pool.pool_token_supply = 1;
+        stake_pool.update_fee(&FeeType::SolReferral(60), 1).unwrap();
+        assert_eq!(stake_pool.sol_referral_fee, 60);
+
+        stake_pool.epoch_deposited_lamports = 1;
+        assert_eq!(
+            stake_pool.update_fee(
+                &FeeType::SolDeposit(Fee {
+                    numerator: 2,
+                    denominator: 100,
+                }),
+                1
+            ),
+            Err(StakePoolError::FeeIncreaseTooHigh)
+        );
+        stake_pool
+            .update_fee(&FeeType::SolDeposit(new_fee), 1)
+            .unwrap();
+        assert_eq!(stake_pool.sol_deposit_fee, old_fee);
+        assert_eq!(
+            stake_pool.next_sol_deposit_fee,
+            FutureEpochFee::Two(new_fee)
+        );
+        assert_eq!(stake_pool.fee_change_epoch, 1);
+
+        stake_pool.apply_pending_fees(2);
+        assert_eq!(stake_pool.sol_deposit_fee, old_fee);
+        stake_pool.apply_pending_fees(3);
+        assert_eq!(stake_pool.sol_deposit_fee, new_fee);
+        assert_eq!(stake_pool.next_sol_deposit_fee, FutureEpochFee::None);
+
+        // the delay holds fee changes back before their countdown
+        stake_pool.update_fee_change_delay(3, 3).unwrap();
+        assert_eq!(stake_pool.fee_change_delay_epochs, 3);
+        stake_pool
+            .update_fee(&FeeType::StakeReferral(10), 4)
+            .unwrap();
+        for epoch in 5..=7 {
+            stake_pool.apply_pending_fees(epoch);
+        }
+        assert_eq!(
+            stake_pool.next_stake_referral_fee,
+            FutureEpochReferralFee::Two(10)
+        );
+        stake_pool.apply_pending_fees(8);
+        stake_pool.apply_pending_fees(9);
+        assert_eq!(stake_pool.stake_referral_fee, 10);
+
+        // shortening the delay waits for the current delay
+        stake_pool.update_fee_change_delay(1, 9).unwrap();
+        assert_eq!(stake_pool.fee_change_delay_epochs, 3);
+        assert_eq!(stake_pool.next_fee_change_delay_epochs, Some(1));
+        stake_pool.apply_pending_fees(12);
+        assert_eq!(stake_pool.fee_change_delay_epochs, 3);
+        stake_pool.apply_pending_fees(13);
+        assert_eq!(stake_pool.fee_change_delay_epochs, 1);
+        assert_eq!(stake_pool.next_fee_change_delay_epochs, None);
+
+        assert_eq!(
+            stake_pool.update_fee_change_delay(MAX_FEE_CHANGE_DELAY_EPOCHS + 1, 13),
+            Err(StakePoolError::FeeChangeDelayTooLong)
+        );
+    }
+
+    #[test]
+    fn sol_withdrawal_fee_curve() {
+        let curve = SolWithdrawalFeeCurve {
+            target_reserve_bps: 1_000,
+            max_fee: Fee {
+                numerator: 1,
+                denominator: 100,
+            },
+            exponent: 1,
+        };
+        let mut stake_pool = StakePool {
+            sol_withdrawal_fee: Fee {
+                numerator: 1,
+                denominator: 1000,
+            },
+            ..StakePool::default()
+        };
+
+        // curve changes are timelocked, and the top of the curve can only
+        // rise as fast as a withdrawal fee
+        for invalid_curve in [
+            SolWithdrawalFeeCurve {
+                exponent: 0,
+                ..curve
+            },
+            SolWithdrawalFeeCurve {
+                exponent: MAX_SOL_WITHDRAWAL_FEE_CURVE_EXPONENT + 1,
+                ..curve
+            },
+            SolWithdrawalFeeCurve {
+                target_reserve_bps: MAX_BASIS_POINTS + 1,
+                ..curve
+            },
+        ] {
+            assert_eq!(
+                stake_pool.update_fee(&FeeType::SolWithdrawalCurve(invalid_curve), 1),
+                Err(StakePoolError::InvalidSolWithdrawalFeeCurve)
+            );
+        }
+        assert_eq!(
+            stake_pool.update_fee(&FeeType::SolWithdrawalCurve(curve), 1),
+            Err(StakePoolError::FeeIncreaseTooHigh)
+        );
+        let new_curve = SolWithdrawalFeeCurve {
+            max_fee: Fee {
+                numerator: 3,
+                denominator: 2000,
+            },
+            ..curve
+        };
+        stake_pool
+            .update_fee(&FeeType::SolWithdrawalCurve(new_curve), 1)
+            .unwrap();
+        assert_eq!(
+            stake_pool.next_sol_withdrawal_fee_curve,
+            FutureEpochSolWithdrawalFeeCurve::Two(new_curve)
+        );
+        stake_pool.apply_pending_fees(2);
+        assert!(!stake_pool.sol_withdrawal_fee_curve.is_enabled());
+        stake_pool.apply_pending_fees(3);
+        assert_eq!(stake_pool.sol_withdrawal_fee_curve, new_curve);
+
+        // turning the curve off is always allowed
+        stake_pool
+            .update_fee(
+                &FeeType::SolWithdrawalCurve(SolWithdrawalFeeCurve::default()),
+                3,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn flow_caps() {
+        let mut stake_pool = StakePool {
+            total_lamports: 100,
+            max_deposit_lamports_per_epoch: Some(50),
+            max_sol_withdrawal_lamports_per_epoch: Some(30),
+            max_total_lamports: Some(1_000),
+            flow_epoch: 1,
+            ..StakePool::default()
+        };
+
+        stake_pool.record_deposit(1, 40).unwrap();
+        assert_eq!(
+            stake_pool.record_deposit(1, 11),
+            Err(StakePoolError::DepositCapExceeded)
+        );
+        stake_pool.record_deposit(1, 10).unwrap();
+        assert_eq!(stake_pool.epoch_deposited_lamports, 50);
+
+        stake_pool.record_sol_withdrawal(1, 30).unwrap();
+        assert_eq!(
+            stake_pool.record_sol_withdrawal(1, 1),
+            Err(StakePoolError::SolWithdrawalCapExceeded)
+        );
+
+        // counters reset on a new epoch
+        stake_pool.record_deposit(2, 50).unwrap();
+        assert_eq!(stake_pool.flow_epoch, 2);
+        assert_eq!(stake_pool.epoch_deposited_lamports, 50);
+        assert_eq!(stake_pool.epoch_sol_withdrawn_lamports, 0);
+        stake_pool.record_sol_withdrawal(2, 30).unwrap();
+
+        // total lamports cap is checked against the current pool size
+        stake_pool.max_deposit_lamports_per_epoch = None;
+        stake_pool.total_lamports = 990;
+        stake_pool.record_deposit(2, 10).unwrap();
+        assert_eq!(
+            stake_pool.record_deposit(2, 11),
+            Err(StakePoolError::TotalLamportsCapExceeded)
+        );
+
+        // no caps
+        stake_pool.update_cap(&CapType::TotalLamports(None));
+        stake_pool.update_cap(&CapType::SolWithdrawalPerEpoch(None));
+        stake_pool.record_deposit(2, u64::MAX / 2).unwrap();
+        stake_pool.record_sol_withdrawal(2, u64::MAX / 2).unwrap();
+    }
+
+    #[test]
+    fn validator_share() {
+        let mut stake_pool = StakePool {
+            total_lamports: 1_000,
+            ..StakePool::default()
+        };
+        let mut validator = ValidatorStakeInfo::default();
+
+        // no limits
+        stake_pool.check_validator_share(&validator, 1_000).unwrap();
+
+        stake_pool.update_cap(&CapType::ValidatorShare(Some(2_000)));
+        stake_pool.check_validator_share(&validator, 200).unwrap();
+        assert_eq!(
+            stake_pool.check_validator_share(&validator, 201),
+            Err(StakePoolError::ValidatorShareExceeded)
+        );
+
+        // the tighter of the validator's and the pool-wide limit applies
+        validator.max_share_bps = 1_000.into();
+        assert_eq!(
+            stake_pool.check_validator_share(&validator, 101),
+            Err(StakePoolError::ValidatorShareExceeded)
+        );
+        validator.max_share_bps = 5_000.into();
+        stake_pool.check_validator_share(&validator, 200).unwrap();
+        stake_pool.update_cap(&CapType::ValidatorShare(None));
+        stake_pool.check_validator_share(&validator, 500).unwrap();
+        assert_eq!(
+            stake_pool.check_validator_share(&validator, 501),
+            Err(StakePoolError::ValidatorShareExceeded)
+        );
+    }
+
+    #[test]
+    fn stake_pool_layout() {
+        assert_eq!(get_packed_len::<StakePool>(), STAKE_POOL_LEN);
+        // size of the original layout, ending with `last_epoch_total_lamports`
+        let legacy_len = 611;
+
+        // a pool with an earlier layout is rejected as needing migration
+        let stake_pool = StakePool {
+            account_type: AccountType::StakePool,
+            total_lamports: 1_000,
+            ..StakePool::default()
+        };
+        let mut data = vec![0u8; STAKE_POOL_LEN];
+        borsh::to_writer(&mut data[..], &stake_pool).unwrap();
+        assert_eq!(StakePool::try_from_account_data(&data), Ok(stake_pool));
+        assert_eq!(
+            StakePool::try_from_account_data(&data[..legacy_len]),
+            Err(StakePoolError::StakePoolNeedsMigration.into())
+        );
+    }
+
+    #[test]
+    fn validator_metadata_max_commission() {
+        let mut stake_pool = StakePool::default();
+        let validator_metadata = ValidatorMetadata {
+            account_type: AccountType::ValidatorMetadata,
+            max_commission: 10,
+            ..ValidatorMetadata::default()
+        };
+        assert_eq!(
+            get_packed_len::<ValidatorMetadata>(),
+            1 + 32 + 32 + 8 + 32 + 1 + 1
+        );
+
+        // no pool-wide limit
+        assert_eq!(validator_metadata.max_commission(&stake_pool), 10);
+
+        // the tighter of the validator's and the pool-wide limit applies
+        stake_pool.update_cap(&CapType::ValidatorCommission(Some(5)));
+        assert_eq!(validator_metadata.max_commission(&stake_pool), 5);
+        stake_pool.update_cap(&CapType::ValidatorCommission(Some(20)));
+        assert_eq!(validator_metadata.max_commission(&stake_pool), 10);
+    }
+
+    #[test]
+    fn fee_split() {
+        assert_eq!(
+            get_packed_len::<FeeSplit>(),
+            1 + 32 + 1 + (32 + 2) * MAX_FEE_SPLIT_RECIPIENTS
+        );
+
+        assert_eq!(FeeSplit::check_weights(&[]), Ok(()));
+        assert_eq!(FeeSplit::check_weights(&[5_000, 3_000, 2_000]), Ok(()));
+        for invalid in [
+            vec![5_000, 5_001],
+            vec![u16::MAX, 1],
+            vec![1_000, 0],
+            vec![1; MAX_FEE_SPLIT_RECIPIENTS + 1],
+        ] {
+            assert_eq!(
+                FeeSplit::check_weights(&invalid),
+                Err(StakePoolError::InvalidFeeSplit)
+            );
+        }
+
+        let mut fee_split = FeeSplit {
+            account_type: AccountType::FeeSplit,
+            recipient_count: 2,
+            ..FeeSplit::default()
+        };
+        fee_split.recipients[0].weight_bps = 5_000;
+        fee_split.recipients[1].weight_bps = 3_333;
+        fee_split.recipients[2].weight_bps = 1_000;
+        assert_eq!(fee_split.recipients().len(), 2);
+        assert_eq!(fee_split.recipients()[0].share(1_001), Some(500));
+        assert_eq!(fee_split.recipients()[1].share(1_001), Some(333));
+        assert_eq!(
+            fee_split.recipients()[1].share(u64::MAX),
+            Some(6_148_299_799_767_393_553)
+        );
+    }
 }
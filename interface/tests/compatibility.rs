@@ -1,446 +1,590 @@
-//! Compatibility tests to ensure interface types stay in sync with program types.
+//! Golden-bytes tests pinning the wire format of the interface types.
 //!
-//! These tests serialize data using the original program types and then deserialize
-//! using the interface types. If the program's state.rs changes in a way that breaks
-//! binary compatibility, these tests will fail.
-
-use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{pubkey::Pubkey, stake::state::Lockup};
-use spl_pod::primitives::{PodU32, PodU64};
-
-// Import program types (the source of truth)
-use spl_stake_pool::state as program;
-
-// Import interface types (must be compatible)
-use fogo_stake_pool_interface::state as interface;
+//! Deployed clients build instructions and read accounts with these exact
+//! encodings, so reordering an enum variant or a struct field, or changing a
+//! field's type, must fail here rather than on chain. When an encoding is
+//! changed on purpose, the expected bytes are updated along with it.
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    fogo_stake_pool_interface::{
+        instruction::{FundingType, PreferredValidatorType, StakePoolInstruction},
+        state::{
+            AccountType, CapType, Fee, FeeType, FutureEpochFee, FutureEpochReferralFee,
+            FutureEpochSolWithdrawalFeeCurve, SolWithdrawalFeeCurve, StakePool, StakeStatus,
+            ValidatorList, ValidatorListHeader, ValidatorStakeInfo,
+        },
+    },
+    solana_program::{pubkey::Pubkey, stake::state::Lockup},
+};
 
-/// Helper function to serialize using borsh
-fn serialize<T: BorshSerialize>(value: &T) -> Vec<u8> {
-    borsh::to_vec(value).expect("serialization failed")
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
 }
 
-/// Helper function to deserialize using borsh
-fn deserialize<T: BorshDeserialize>(data: &[u8]) -> T {
-    T::try_from_slice(data).expect("deserialization failed")
+/// Checks that `value` encodes to `expected_hex` and decodes back to itself
+fn check_encoding<T: BorshSerialize + BorshDeserialize + PartialEq + std::fmt::Debug>(
+    value: &T,
+    expected_hex: &str,
+) {
+    let bytes = borsh::to_vec(value).unwrap();
+    assert_eq!(to_hex(&bytes), expected_hex, "encoding of {:?}", value);
+    assert_eq!(&T::try_from_slice(&bytes).unwrap(), value);
 }
 
-/// Creates a sample StakePool with all fields populated for testing
-fn create_sample_program_stake_pool() -> program::StakePool {
-    program::StakePool {
-        account_type: program::AccountType::StakePool,
-        manager: Pubkey::new_unique(),
-        staker: Pubkey::new_unique(),
-        stake_deposit_authority: Pubkey::new_unique(),
-        stake_withdraw_bump_seed: 255,
-        validator_list: Pubkey::new_unique(),
-        reserve_stake: Pubkey::new_unique(),
-        pool_mint: Pubkey::new_unique(),
-        manager_fee_account: Pubkey::new_unique(),
-        token_program_id: Pubkey::new_unique(),
-        total_lamports: 1_000_000_000,
-        pool_token_supply: 500_000_000,
-        last_update_epoch: 100,
-        lockup: Lockup {
-            unix_timestamp: 1234567890,
-            epoch: 50,
-            custodian: Pubkey::new_unique(),
-        },
-        epoch_fee: program::Fee {
-            denominator: 100,
-            numerator: 1,
-        },
-        next_epoch_fee: program::FutureEpoch::None,
-        preferred_deposit_validator_vote_address: Some(Pubkey::new_unique()),
-        preferred_withdraw_validator_vote_address: None,
-        stake_deposit_fee: program::Fee {
-            denominator: 1000,
-            numerator: 5,
-        },
-        stake_withdrawal_fee: program::Fee {
-            denominator: 1000,
-            numerator: 10,
-        },
-        next_stake_withdrawal_fee: program::FutureEpoch::None,
-        stake_referral_fee: 50,
-        sol_deposit_authority: Some(Pubkey::new_unique()),
-        sol_deposit_fee: program::Fee {
-            denominator: 1000,
-            numerator: 3,
-        },
-        sol_referral_fee: 25,
-        sol_withdraw_authority: None,
-        sol_withdrawal_fee: program::Fee {
-            denominator: 1000,
-            numerator: 8,
-        },
-        next_sol_withdrawal_fee: program::FutureEpoch::None,
-        last_epoch_pool_token_supply: 400_000_000,
-        last_epoch_total_lamports: 900_000_000,
-    }
-}
-
-/// Creates a sample ValidatorListHeader for testing
-fn create_sample_program_validator_list_header() -> program::ValidatorListHeader {
-    program::ValidatorListHeader {
-        account_type: program::AccountType::ValidatorList,
-        max_validators: 1000,
-    }
+fn pubkey(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
 }
 
-/// Creates a sample ValidatorStakeInfo for testing
-fn create_sample_program_validator_stake_info() -> program::ValidatorStakeInfo {
-    program::ValidatorStakeInfo {
-        active_stake_lamports: PodU64::from(100_000_000),
-        transient_stake_lamports: PodU64::from(50_000_000),
-        last_update_epoch: PodU64::from(99),
-        transient_seed_suffix: PodU64::from(1),
-        unused: PodU32::from(0),
-        validator_seed_suffix: PodU32::from(42),
-        status: program::StakeStatus::Active.into(),
-        vote_account_address: Pubkey::new_unique(),
+fn fee(numerator: u64, denominator: u64) -> Fee {
+    Fee {
+        denominator,
+        numerator,
     }
 }
 
-/// Creates a sample Fee for testing
-fn create_sample_program_fee() -> program::Fee {
-    program::Fee {
-        denominator: 10000,
-        numerator: 250,
+fn sample_fee_curve() -> SolWithdrawalFeeCurve {
+    SolWithdrawalFeeCurve {
+        target_reserve_bps: 1_000,
+        max_fee: fee(2, 100),
+        exponent: 2,
     }
 }
 
 #[test]
-fn stake_pool_serialization_compatibility() {
-    let program_pool = create_sample_program_stake_pool();
-
-    // Serialize using program types
-    let serialized = serialize(&program_pool);
-
-    // Deserialize using interface types
-    let interface_pool: interface::StakePool = deserialize(&serialized);
-
-    // Verify key fields match
-    assert_eq!(
-        interface_pool.account_type,
-        interface::AccountType::StakePool
-    );
-    assert_eq!(interface_pool.manager, program_pool.manager);
-    assert_eq!(interface_pool.staker, program_pool.staker);
-    assert_eq!(
-        interface_pool.stake_deposit_authority,
-        program_pool.stake_deposit_authority
-    );
-    assert_eq!(
-        interface_pool.stake_withdraw_bump_seed,
-        program_pool.stake_withdraw_bump_seed
-    );
-    assert_eq!(interface_pool.validator_list, program_pool.validator_list);
-    assert_eq!(interface_pool.reserve_stake, program_pool.reserve_stake);
-    assert_eq!(interface_pool.pool_mint, program_pool.pool_mint);
-    assert_eq!(
-        interface_pool.manager_fee_account,
-        program_pool.manager_fee_account
-    );
-    assert_eq!(
-        interface_pool.token_program_id,
-        program_pool.token_program_id
-    );
-    assert_eq!(interface_pool.total_lamports, program_pool.total_lamports);
-    assert_eq!(
-        interface_pool.pool_token_supply,
-        program_pool.pool_token_supply
-    );
-    assert_eq!(
-        interface_pool.last_update_epoch,
-        program_pool.last_update_epoch
-    );
-    assert_eq!(interface_pool.lockup, program_pool.lockup);
-    assert_eq!(
-        interface_pool.epoch_fee.numerator,
-        program_pool.epoch_fee.numerator
-    );
-    assert_eq!(
-        interface_pool.epoch_fee.denominator,
-        program_pool.epoch_fee.denominator
-    );
-    assert_eq!(
-        interface_pool.preferred_deposit_validator_vote_address,
-        program_pool.preferred_deposit_validator_vote_address
-    );
-    assert_eq!(
-        interface_pool.preferred_withdraw_validator_vote_address,
-        program_pool.preferred_withdraw_validator_vote_address
-    );
-    assert_eq!(
-        interface_pool.stake_deposit_fee.numerator,
-        program_pool.stake_deposit_fee.numerator
-    );
-    assert_eq!(
-        interface_pool.stake_withdrawal_fee.numerator,
-        program_pool.stake_withdrawal_fee.numerator
-    );
-    assert_eq!(
-        interface_pool.stake_referral_fee,
-        program_pool.stake_referral_fee
-    );
-    assert_eq!(
-        interface_pool.sol_deposit_authority,
-        program_pool.sol_deposit_authority
-    );
-    assert_eq!(
-        interface_pool.sol_deposit_fee.numerator,
-        program_pool.sol_deposit_fee.numerator
-    );
-    assert_eq!(
-        interface_pool.sol_referral_fee,
-        program_pool.sol_referral_fee
-    );
-    assert_eq!(
-        interface_pool.sol_withdraw_authority,
-        program_pool.sol_withdraw_authority
-    );
-    assert_eq!(
-        interface_pool.sol_withdrawal_fee.numerator,
-        program_pool.sol_withdrawal_fee.numerator
-    );
-    assert_eq!(
-        interface_pool.last_epoch_pool_token_supply,
-        program_pool.last_epoch_pool_token_supply
-    );
-    assert_eq!(
-        interface_pool.last_epoch_total_lamports,
-        program_pool.last_epoch_total_lamports
-    );
-}
-
-#[test]
-fn stake_pool_roundtrip_compatibility() {
-    let program_pool = create_sample_program_stake_pool();
-
-    // Serialize with program, deserialize with interface, serialize with interface
-    let program_serialized = serialize(&program_pool);
-    let interface_pool: interface::StakePool = deserialize(&program_serialized);
-    let interface_serialized = serialize(&interface_pool);
-
-    // The serialized bytes should be identical
-    assert_eq!(
-        program_serialized, interface_serialized,
-        "Roundtrip serialization mismatch - program and interface produce different bytes"
-    );
-}
-
-#[test]
-fn validator_list_header_serialization_compatibility() {
-    let program_header = create_sample_program_validator_list_header();
-
-    // Serialize using program types
-    let serialized = serialize(&program_header);
-
-    // Deserialize using interface types
-    let interface_header: interface::ValidatorListHeader = deserialize(&serialized);
-
-    // Verify fields match
-    assert_eq!(
-        interface_header.account_type,
-        interface::AccountType::ValidatorList
-    );
-    assert_eq!(
-        interface_header.max_validators,
-        program_header.max_validators
-    );
-}
-
-#[test]
-fn validator_list_header_roundtrip_compatibility() {
-    let program_header = create_sample_program_validator_list_header();
-
-    let program_serialized = serialize(&program_header);
-    let interface_header: interface::ValidatorListHeader = deserialize(&program_serialized);
-    let interface_serialized = serialize(&interface_header);
-
-    assert_eq!(
-        program_serialized, interface_serialized,
-        "ValidatorListHeader roundtrip serialization mismatch"
-    );
-}
-
-#[test]
-fn validator_stake_info_serialization_compatibility() {
-    let program_info = create_sample_program_validator_stake_info();
-
-    // ValidatorStakeInfo uses bytemuck (Pod), so we use bytes_of instead of borsh
-    let serialized = bytemuck::bytes_of(&program_info);
-
-    // Deserialize using interface types via bytemuck
-    let interface_info: &interface::ValidatorStakeInfo = bytemuck::from_bytes(serialized);
-
-    // Verify fields match
-    assert_eq!(
-        interface_info.active_stake_lamports,
-        program_info.active_stake_lamports
-    );
-    assert_eq!(
-        interface_info.transient_stake_lamports,
-        program_info.transient_stake_lamports
-    );
-    assert_eq!(
-        interface_info.last_update_epoch,
-        program_info.last_update_epoch
-    );
-    assert_eq!(
-        interface_info.transient_seed_suffix,
-        program_info.transient_seed_suffix
-    );
-    assert_eq!(interface_info.unused, program_info.unused);
-    assert_eq!(
-        interface_info.validator_seed_suffix,
-        program_info.validator_seed_suffix
-    );
-    assert_eq!(
-        interface_info.vote_account_address,
-        program_info.vote_account_address
-    );
-}
-
-#[test]
-fn fee_serialization_compatibility() {
-    let program_fee = create_sample_program_fee();
-
-    // Serialize using program types
-    let serialized = serialize(&program_fee);
-
-    // Deserialize using interface types
-    let interface_fee: interface::Fee = deserialize(&serialized);
-
-    // Verify fields match
-    assert_eq!(interface_fee.numerator, program_fee.numerator);
-    assert_eq!(interface_fee.denominator, program_fee.denominator);
-}
-
-#[test]
-fn fee_roundtrip_compatibility() {
-    let program_fee = create_sample_program_fee();
-
-    let program_serialized = serialize(&program_fee);
-    let interface_fee: interface::Fee = deserialize(&program_serialized);
-    let interface_serialized = serialize(&interface_fee);
-
-    assert_eq!(
-        program_serialized, interface_serialized,
-        "Fee roundtrip serialization mismatch"
-    );
-}
-
-#[test]
-fn account_type_serialization_compatibility() {
-    // Test all AccountType variants
-    let variants = [
+fn instruction_encoding() {
+    // (discriminant, instruction, hex of the data after the discriminant)
+    let instructions = [
         (
-            program::AccountType::Uninitialized,
-            interface::AccountType::Uninitialized,
+            0,
+            StakePoolInstruction::Initialize {
+                fee: fee(1, 100),
+                withdrawal_fee: fee(2, 1_000),
+                deposit_fee: fee(3, 1_000),
+                referral_fee: 50,
+                max_validators: 2_000,
+            },
+            "64000000000000000100000000000000e8030000000000000200000000000000e803000000000000030000000000000032d0070000",
         ),
+        (1, StakePoolInstruction::AddValidatorToPool(7), "07000000"),
+        (2, StakePoolInstruction::RemoveValidatorFromPool, ""),
         (
-            program::AccountType::StakePool,
-            interface::AccountType::StakePool,
+            3,
+            StakePoolInstruction::DecreaseValidatorStake {
+                lamports: 1_000_000,
+                transient_stake_seed: 3,
+            },
+            "40420f00000000000300000000000000",
         ),
         (
-            program::AccountType::ValidatorList,
-            interface::AccountType::ValidatorList,
+            4,
+            StakePoolInstruction::IncreaseValidatorStake {
+                lamports: 1_000_000,
+                transient_stake_seed: 3,
+            },
+            "40420f00000000000300000000000000",
+        ),
+        (
+            5,
+            StakePoolInstruction::SetPreferredValidator {
+                validator_type: PreferredValidatorType::Withdraw,
+                validator_vote_address: Some(pubkey(1)),
+            },
+            "01010101010101010101010101010101010101010101010101010101010101010101",
+        ),
+        (
+            6,
+            StakePoolInstruction::UpdateValidatorListBalance {
+                start_index: 5,
+                no_merge: true,
+            },
+            "0500000001",
+        ),
+        (7, StakePoolInstruction::UpdateStakePoolBalance, ""),
+        (8, StakePoolInstruction::CleanupRemovedValidatorEntries, ""),
+        (9, StakePoolInstruction::DepositStake, ""),
+        (10, StakePoolInstruction::WithdrawStake(1_000_000), "40420f0000000000"),
+        (11, StakePoolInstruction::SetManager, ""),
+        (
+            12,
+            StakePoolInstruction::SetFee {
+                fee: FeeType::SolWithdrawal(fee(1, 1_000)),
+            },
+            "06e8030000000000000100000000000000",
+        ),
+        (13, StakePoolInstruction::SetStaker, ""),
+        (14, StakePoolInstruction::DepositSol(1_000_000), "40420f0000000000"),
+        (
+            15,
+            StakePoolInstruction::SetFundingAuthority(FundingType::SolWithdraw),
+            "02",
+        ),
+        (16, StakePoolInstruction::WithdrawSol(1_000_000), "40420f0000000000"),
+        (
+            17,
+            StakePoolInstruction::CreateTokenMetadata {
+                name: "Pool".to_string(),
+                symbol: "POOL".to_string(),
+                uri: "uri".to_string(),
+            },
+            "04000000506f6f6c04000000504f4f4c03000000757269",
+        ),
+        (
+            18,
+            StakePoolInstruction::UpdateTokenMetadata {
+                name: "Pool".to_string(),
+                symbol: "POOL".to_string(),
+                uri: "uri".to_string(),
+            },
+            "04000000506f6f6c04000000504f4f4c03000000757269",
+        ),
+        (
+            19,
+            StakePoolInstruction::IncreaseAdditionalValidatorStake {
+                lamports: 1_000_000,
+                transient_stake_seed: 3,
+                ephemeral_stake_seed: 4,
+            },
+            "40420f000000000003000000000000000400000000000000",
+        ),
+        (
+            20,
+            StakePoolInstruction::DecreaseAdditionalValidatorStake {
+                lamports: 1_000_000,
+                transient_stake_seed: 3,
+                ephemeral_stake_seed: 4,
+            },
+            "40420f000000000003000000000000000400000000000000",
+        ),
+        (
+            21,
+            StakePoolInstruction::DecreaseValidatorStakeWithReserve {
+                lamports: 1_000_000,
+                transient_stake_seed: 3,
+            },
+            "40420f00000000000300000000000000",
+        ),
+        (
+            22,
+            StakePoolInstruction::Redelegate {
+                lamports: 1_000_000,
+                source_transient_stake_seed: 3,
+                ephemeral_stake_seed: 4,
+                destination_transient_stake_seed: 5,
+            },
+            "40420f0000000000030000000000000004000000000000000500000000000000",
+        ),
+        (
+            23,
+            StakePoolInstruction::DepositStakeWithSlippage {
+                minimum_pool_tokens_out: 900_000,
+            },
+            "a0bb0d0000000000",
+        ),
+        (
+            24,
+            StakePoolInstruction::WithdrawStakeWithSlippage {
+                pool_tokens_in: 1_000_000,
+                minimum_lamports_out: 900_000,
+            },
+            "40420f0000000000a0bb0d0000000000",
+        ),
+        (
+            25,
+            StakePoolInstruction::DepositSolWithSlippage {
+                lamports_in: 1_000_000,
+                minimum_pool_tokens_out: 900_000,
+            },
+            "40420f0000000000a0bb0d0000000000",
+        ),
+        (
+            26,
+            StakePoolInstruction::WithdrawSolWithSlippage {
+                pool_tokens_in: 1_000_000,
+                minimum_lamports_out: 900_000,
+            },
+            "40420f0000000000a0bb0d0000000000",
+        ),
+        (
+            27,
+            StakePoolInstruction::DepositWsolWithSession {
+                lamports_in: 1_000_000,
+                minimum_pool_tokens_out: 900_000,
+            },
+            "40420f0000000000a0bb0d0000000000",
+        ),
+        (
+            28,
+            StakePoolInstruction::WithdrawWsolWithSession {
+                pool_tokens_in: 1_000_000,
+                minimum_lamports_out: 900_000,
+            },
+            "40420f0000000000a0bb0d0000000000",
+        ),
+        (
+            29,
+            StakePoolInstruction::WithdrawStakeWithSession {
+                pool_tokens_in: 1_000_000,
+                minimum_lamports_out: 900_000,
+                user_stake_seed: 6,
+            },
+            "40420f0000000000a0bb0d00000000000600000000000000",
+        ),
+        (
+            30,
+            StakePoolInstruction::WithdrawFromStakeAccountWithSession {
+                lamports: 1_000_000,
+                user_stake_seed: 6,
+            },
+            "40420f00000000000600000000000000",
+        ),
+        (
+            31,
+            StakePoolInstruction::RequestWithdrawalTicket {
+                pool_tokens_in: 1_000_000,
+                ticket_seed: 8,
+            },
+            "40420f00000000000800000000000000",
+        ),
+        (32, StakePoolInstruction::ClaimWithdrawalTicket, ""),
+        (
+            33,
+            StakePoolInstruction::DepositStakeWithSession {
+                minimum_pool_tokens_out: 900_000,
+                user_stake_seed: 6,
+            },
+            "a0bb0d00000000000600000000000000",
         ),
-    ];
-
-    for (program_variant, expected_interface_variant) in variants {
-        let serialized = serialize(&program_variant);
-        let interface_variant: interface::AccountType = deserialize(&serialized);
-        assert_eq!(
-            interface_variant, expected_interface_variant,
-            "AccountType variant mismatch"
-        );
-    }
-}
-
-#[test]
-fn stake_status_serialization_compatibility() {
-    // Test all StakeStatus variants
-    let variants = [
-        (program::StakeStatus::Active, interface::StakeStatus::Active),
         (
-            program::StakeStatus::DeactivatingTransient,
-            interface::StakeStatus::DeactivatingTransient,
+            34,
+            StakePoolInstruction::SetCap {
+                cap: CapType::TotalLamports(Some(1_000_000)),
+            },
+            "020140420f0000000000",
         ),
+        (35, StakePoolInstruction::SetPauser, ""),
         (
-            program::StakeStatus::ReadyForRemoval,
-            interface::StakeStatus::ReadyForRemoval,
+            36,
+            StakePoolInstruction::SetPaused {
+                paused_operations: 0b101,
+            },
+            "05",
         ),
+        (37, StakePoolInstruction::ProposeManager, ""),
+        (38, StakePoolInstruction::AcceptManager, ""),
+        (39, StakePoolInstruction::ProposeStaker, ""),
+        (40, StakePoolInstruction::AcceptStaker, ""),
         (
-            program::StakeStatus::DeactivatingValidator,
-            interface::StakeStatus::DeactivatingValidator,
+            41,
+            StakePoolInstruction::SetFeeChangeDelay { delay_epochs: 3 },
+            "0300000000000000",
         ),
         (
-            program::StakeStatus::DeactivatingAll,
-            interface::StakeStatus::DeactivatingAll,
+            42,
+            StakePoolInstruction::RegisterReferrer {
+                code: "ref".to_string(),
+            },
+            "03000000726566",
+        ),
+        (43, StakePoolInstruction::UpdateReferrer, ""),
+        (
+            44,
+            StakePoolInstruction::DepositSolToValidator {
+                lamports_in: 1_000_000,
+                minimum_pool_tokens_out: 900_000,
+            },
+            "40420f0000000000a0bb0d0000000000",
+        ),
+        (
+            45,
+            StakePoolInstruction::SetValidatorMaxShare {
+                max_share_bps: Some(1_500),
+            },
+            "01dc05",
+        ),
+        (
+            46,
+            StakePoolInstruction::UpdateValidatorMetadata {
+                notes_hash: [9; 32],
+                max_commission: 10,
+            },
+            "09090909090909090909090909090909090909090909090909090909090909090a",
+        ),
+        (47, StakePoolInstruction::DeactivateHighCommissionValidator, ""),
+        (48, StakePoolInstruction::DeactivateDelinquentValidator, ""),
+        (
+            49,
+            StakePoolInstruction::SetFeeSplit {
+                weights_bps: vec![2_500, 1_000],
+            },
+            "02000000c409e803",
+        ),
+        (
+            50,
+            StakePoolInstruction::SetInsuranceFee {
+                insurance_fee_bps: 2_500,
+            },
+            "c409",
+        ),
+        (
+            51,
+            StakePoolInstruction::InjectInsuranceFund { lamports: 1_000_000 },
+            "40420f0000000000",
+        ),
+        (52, StakePoolInstruction::CreateRateHistory, ""),
+        (53, StakePoolInstruction::CreateOracle, ""),
+        (54, StakePoolInstruction::GetExchangeRate, ""),
+        (
+            55,
+            StakePoolInstruction::CreateTokenMetadataExtension {
+                name: "Pool".to_string(),
+                symbol: "POOL".to_string(),
+                uri: "uri".to_string(),
+            },
+            "04000000506f6f6c04000000504f4f4c03000000757269",
+        ),
+        (
+            56,
+            StakePoolInstruction::UpdateTokenMetadataExtensionField {
+                field: "key".to_string(),
+                value: "value".to_string(),
+            },
+            "030000006b65790500000076616c7565",
+        ),
+        (
+            57,
+            StakePoolInstruction::RemoveTokenMetadataExtensionKey {
+                key: "key".to_string(),
+            },
+            "030000006b6579",
+        ),
+        (
+            58,
+            StakePoolInstruction::WithdrawSolWithSession {
+                pool_tokens_in: 1_000_000,
+                minimum_lamports_out: 900_000,
+            },
+            "40420f0000000000a0bb0d0000000000",
+        ),
+        (
+            59,
+            StakePoolInstruction::CloseUserStakeAccountsWithSession {
+                user_stake_seeds: vec![6, 7],
+            },
+            "0200000006000000000000000700000000000000",
+        ),
+        (
+            60,
+            StakePoolInstruction::WithdrawStakeMulti {
+                pool_tokens_in: vec![1_000_000, 2_000_000],
+                minimum_lamports_out: 900_000,
+            },
+            "0200000040420f000000000080841e0000000000a0bb0d0000000000",
+        ),
+        (61, StakePoolInstruction::MigrateStakePool, ""),
+        (
+            62,
+            StakePoolInstruction::UpdatePaused {
+                pause: 0b0001,
+                unpause: 0b0100,
+            },
+            "0104",
         ),
     ];
 
-    for (program_variant, expected_interface_variant) in variants {
-        let serialized = serialize(&program_variant);
-        let interface_variant: interface::StakeStatus = deserialize(&serialized);
-        assert_eq!(
-            interface_variant, expected_interface_variant,
-            "StakeStatus variant mismatch"
+    for (discriminant, instruction, expected_data_hex) in instructions {
+        check_encoding(
+            &instruction,
+            &format!("{:02x}{}", discriminant, expected_data_hex),
         );
     }
 }
 
 #[test]
-fn future_epoch_none_serialization_compatibility() {
-    let program_future: program::FutureEpoch<program::Fee> = program::FutureEpoch::None;
-
-    let serialized = serialize(&program_future);
-    let interface_future: interface::FutureEpochFee = deserialize(&serialized);
-
-    assert!(
-        matches!(interface_future, interface::FutureEpochFee::None),
-        "FutureEpoch::None mismatch"
-    );
+fn fee_type_encoding() {
+    let curve = sample_fee_curve();
+    let fee_types = [
+        FeeType::SolReferral(50),
+        FeeType::StakeReferral(50),
+        FeeType::Epoch(fee(1, 100)),
+        FeeType::StakeWithdrawal(fee(1, 100)),
+        FeeType::SolDeposit(fee(1, 100)),
+        FeeType::StakeDeposit(fee(1, 100)),
+        FeeType::SolWithdrawal(fee(1, 100)),
+        FeeType::SolWithdrawalCurve(curve),
+    ];
+    let expected = [
+        "0032",
+        "0132",
+        "0264000000000000000100000000000000",
+        "0364000000000000000100000000000000",
+        "0464000000000000000100000000000000",
+        "0564000000000000000100000000000000",
+        "0664000000000000000100000000000000",
+        "07e8036400000000000000020000000000000002",
+    ];
+    for (fee_type, expected_hex) in fee_types.iter().zip(expected) {
+        check_encoding(fee_type, expected_hex);
+    }
 }
 
 #[test]
-fn future_epoch_one_serialization_compatibility() {
-    let fee = program::Fee {
-        denominator: 100,
-        numerator: 5,
-    };
-    let program_future: program::FutureEpoch<program::Fee> = program::FutureEpoch::One(fee);
-
-    let serialized = serialize(&program_future);
-    let interface_future: interface::FutureEpochFee = deserialize(&serialized);
-
-    match interface_future {
-        interface::FutureEpochFee::One(interface_fee) => {
-            assert_eq!(interface_fee.numerator, fee.numerator);
-            assert_eq!(interface_fee.denominator, fee.denominator);
-        }
-        _ => panic!("Expected FutureEpoch::One"),
+fn cap_type_encoding() {
+    let cap_types = [
+        CapType::DepositPerEpoch(Some(1_000_000)),
+        CapType::SolWithdrawalPerEpoch(None),
+        CapType::TotalLamports(Some(1_000_000)),
+        CapType::DirectStakeShare(Some(1_500)),
+        CapType::ValidatorShare(None),
+        CapType::ValidatorCommission(Some(10)),
+    ];
+    let expected = [
+        "000140420f0000000000",
+        "0100",
+        "020140420f0000000000",
+        "0301dc05",
+        "0400",
+        "05010a",
+    ];
+    for (cap_type, expected_hex) in cap_types.iter().zip(expected) {
+        check_encoding(cap_type, expected_hex);
     }
 }
 
 #[test]
-fn future_epoch_two_serialization_compatibility() {
-    let fee = program::Fee {
-        denominator: 200,
-        numerator: 10,
+fn stake_pool_encoding() {
+    let stake_pool = StakePool {
+        account_type: AccountType::StakePool,
+        manager: pubkey(1),
+        staker: pubkey(2),
+        stake_deposit_authority: pubkey(3),
+        stake_withdraw_bump_seed: 255,
+        validator_list: pubkey(4),
+        reserve_stake: pubkey(5),
+        pool_mint: pubkey(6),
+        manager_fee_account: pubkey(7),
+        token_program_id: pubkey(8),
+        total_lamports: 1_000_000_000,
+        pool_token_supply: 900_000_000,
+        last_update_epoch: 100,
+        lockup: Lockup {
+            unix_timestamp: 1_234_567_890,
+            epoch: 50,
+            custodian: pubkey(9),
+        },
+        epoch_fee: fee(1, 100),
+        next_epoch_fee: FutureEpochFee::One(fee(2, 100)),
+        preferred_deposit_validator_vote_address: Some(pubkey(10)),
+        preferred_withdraw_validator_vote_address: None,
+        stake_deposit_fee: fee(5, 1_000),
+        stake_withdrawal_fee: fee(10, 1_000),
+        next_stake_withdrawal_fee: FutureEpochFee::None,
+        stake_referral_fee: 50,
+        sol_deposit_authority: Some(pubkey(11)),
+        sol_deposit_fee: fee(3, 1_000),
+        sol_referral_fee: 25,
+        sol_withdraw_authority: None,
+        sol_withdrawal_fee: fee(8, 1_000),
+        next_sol_withdrawal_fee: FutureEpochFee::Two(fee(9, 1_000)),
+        last_epoch_pool_token_supply: 800_000_000,
+        last_epoch_total_lamports: 850_000_000,
+        pending_withdrawal_lamports: 30_000_000,
+        withdrawal_claim_pool_lamports: 10_000_000,
+        max_deposit_lamports_per_epoch: Some(5_000_000_000),
+        max_sol_withdrawal_lamports_per_epoch: None,
+        max_total_lamports: Some(50_000_000_000),
+        flow_epoch: 100,
+        epoch_deposited_lamports: 1_000_000,
+        epoch_sol_withdrawn_lamports: 2_000_000,
+        pauser: Some(pubkey(12)),
+        paused_operations: 0b101,
+        pending_manager: Some(pubkey(13)),
+        pending_staker: None,
+        next_stake_deposit_fee: FutureEpochFee::Two(fee(6, 1_000)),
+        next_sol_deposit_fee: FutureEpochFee::None,
+        next_stake_referral_fee: FutureEpochReferralFee::None,
+        next_sol_referral_fee: FutureEpochReferralFee::One(40),
+        fee_change_delay_epochs: 3,
+        next_fee_change_delay_epochs: Some(1),
+        fee_change_epoch: 99,
+        max_direct_stake_bps: Some(1_500),
+        max_validator_share_bps: Some(2_000),
+        max_validator_commission: Some(10),
+        max_validator_vote_age_epochs: Some(3),
+        sol_withdrawal_fee_curve: sample_fee_curve(),
+        next_sol_withdrawal_fee_curve: FutureEpochSolWithdrawalFeeCurve::Two(
+            SolWithdrawalFeeCurve {
+                exponent: 3,
+                ..sample_fee_curve()
+            },
+        ),
+        has_fee_split: true,
+        insurance_fee_bps: 2_500,
+        uncovered_loss_lamports: 1_000_000,
+        has_rate_history: true,
+        has_oracle: true,
+        pending_insurance_lamports: 500_000,
     };
-    let program_future: program::FutureEpoch<program::Fee> = program::FutureEpoch::Two(fee);
+    let expected_hex = concat!(
+        "0101010101010101010101010101010101010101010101010101010101010101",
+        "0102020202020202020202020202020202020202020202020202020202020202",
+        "0203030303030303030303030303030303030303030303030303030303030303",
+        "03ff040404040404040404040404040404040404040404040404040404040404",
+        "0404050505050505050505050505050505050505050505050505050505050505",
+        "0505060606060606060606060606060606060606060606060606060606060606",
+        "0606070707070707070707070707070707070707070707070707070707070707",
+        "0707080808080808080808080808080808080808080808080808080808080808",
+        "080800ca9a3b0000000000e9a435000000006400000000000000d20296490000",
+        "0000320000000000000009090909090909090909090909090909090909090909",
+        "0909090909090909090964000000000000000100000000000000016400000000",
+        "0000000200000000000000010a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a",
+        "0a0a0a0a0a0a0a0a0a0a0a0a00e8030000000000000500000000000000e80300",
+        "00000000000a000000000000000032010b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+        "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0be8030000000000000300000000000000",
+        "1900e803000000000000080000000000000002e8030000000000000900000000",
+        "0000000008af2f0000000080f8a9320000000080c3c901000000008096980000",
+        "0000000100f2052a01000000000100743ba40b00000064000000000000004042",
+        "0f000000000080841e0000000000010c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c",
+        "0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c05010d0d0d0d0d0d0d0d0d0d0d0d0d0d0d",
+        "0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0d0002e8030000000000000600000000",
+        "0000000000012803000000000000000101000000000000006300000000000000",
+        "01dc0501d007010a010300000000000000e80364000000000000000200000000",
+        "0000000202e803640000000000000002000000000000000301c40940420f0000",
+        "000000010120a1070000000000",
+    );
+    check_encoding(&stake_pool, expected_hex);
+}
 
-    let serialized = serialize(&program_future);
-    let interface_future: interface::FutureEpochFee = deserialize(&serialized);
+#[test]
+fn validator_list_encoding() {
+    let validator_list = ValidatorList {
+        header: ValidatorListHeader {
+            account_type: AccountType::ValidatorList,
+            max_validators: 2,
+        },
+        validators: vec![ValidatorStakeInfo {
+            active_stake_lamports: 100_000_000.into(),
+            transient_stake_lamports: 50_000_000.into(),
+            last_update_epoch: 99.into(),
+            transient_seed_suffix: 1.into(),
+            max_share_bps: 1_000.into(),
+            validator_seed_suffix: 42.into(),
+            status: StakeStatus::DeactivatingTransient.into(),
+            vote_account_address: pubkey(1),
+        }],
+    };
+    check_encoding(
+        &validator_list,
+        concat!(
+            "02020000000100000000e1f5050000000080f0fa020000000063000000000000",
+            "000100000000000000e80300002a000000010101010101010101010101010101",
+            "010101010101010101010101010101010101",
+        ),
+    );
 
-    match interface_future {
-        interface::FutureEpochFee::Two(interface_fee) => {
-            assert_eq!(interface_fee.numerator, fee.numerator);
-            assert_eq!(interface_fee.denominator, fee.denominator);
-        }
-        _ => panic!("Expected FutureEpoch::Two"),
-    }
+    // stake infos are read in place from the account data as well
+    assert_eq!(
+        to_hex(bytemuck::bytes_of(&validator_list.validators[0])),
+        "00e1f5050000000080f0fa020000000063000000000000000100000000000000e80300002a000000010101010101010101010101010101010101010101010101010101010101010101"
+    );
 }
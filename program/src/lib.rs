@@ -2,71 +2,31 @@
 
 //! A program for creating and managing pools of stake
 
-pub mod big_vec;
-pub mod error;
 pub mod inline_mpl_token_metadata;
-pub mod instruction;
 pub mod processor;
 pub mod state;
 
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 
+// Instructions, state, errors, events and addresses are defined once in the
+// interface crate, shared with clients and on-chain integrators
+pub use fogo_stake_pool_interface::{
+    big_vec, error, event, instruction, pda::*, AUTHORITY_DEPOSIT, AUTHORITY_WITHDRAW,
+    DELINQUENT_VALIDATOR_CRANK_REWARD, DEPOSIT_BASELINE_FEE, DIRECT_STAKE_SEED_PREFIX,
+    EPHEMERAL_STAKE_SEED_PREFIX, FEE_SPLIT_SEED_PREFIX, INSURANCE_FUND_SEED_PREFIX,
+    MAX_BASIS_POINTS, MAX_COMMISSION, MAX_DEPOSIT_FEE_INCREASE, MAX_FEE_CHANGE_DELAY_EPOCHS,
+    MAX_FEE_SPLIT_RECIPIENTS, MAX_REFERRAL_CODE_LENGTH, MAX_SOL_WITHDRAWAL_FEE_CURVE_EXPONENT,
+    MAX_TRANSIENT_STAKE_ACCOUNTS, MAX_VALIDATORS_IN_POOL, MAX_VALIDATORS_TO_UPDATE,
+    MAX_WITHDRAWAL_FEE_INCREASE, MINIMUM_ACTIVE_STAKE, MINIMUM_RESERVE_LAMPORTS,
+    ORACLE_SEED_PREFIX, RATE_HISTORY_LEN, RATE_HISTORY_SEED_PREFIX, REFERRER_SEED_PREFIX,
+    STAKE_POOL_LEN, TRANSIENT_STAKE_SEED_PREFIX, USER_STAKE_SEED_PREFIX,
+    VALIDATOR_METADATA_SEED_PREFIX, WITHDRAWAL_BASELINE_FEE, WITHDRAWAL_TICKET_SEED_PREFIX,
+};
 // Export current sdk types for downstream users building with a different sdk
 // version
 pub use solana_program;
-use {
-    crate::state::Fee,
-    solana_program::{pubkey::Pubkey, stake::state::Meta},
-    std::num::NonZeroU32,
-};
-
-/// Seed for deposit authority seed
-const AUTHORITY_DEPOSIT: &[u8] = b"deposit";
-
-/// Seed for withdraw authority seed
-const AUTHORITY_WITHDRAW: &[u8] = b"withdraw";
-
-/// Seed for transient stake account
-const TRANSIENT_STAKE_SEED_PREFIX: &[u8] = b"transient";
-
-/// Seed for ephemeral stake account
-const EPHEMERAL_STAKE_SEED_PREFIX: &[u8] = b"ephemeral";
-
-/// Seed for user stake account created during session withdrawal
-pub const USER_STAKE_SEED_PREFIX: &[u8] = b"user_stake";
-
-/// Minimum amount of staked lamports required in a validator stake account to
-/// allow for merges without a mismatch on credits observed
-pub const MINIMUM_ACTIVE_STAKE: u64 = 1_000_000;
-
-/// Minimum amount of lamports in the reserve
-pub const MINIMUM_RESERVE_LAMPORTS: u64 = 0;
-
-/// Maximum amount of validator stake accounts to update per
-/// `UpdateValidatorListBalance` instruction, based on compute limits
-pub const MAX_VALIDATORS_TO_UPDATE: usize = 4;
-
-/// Maximum factor by which a withdrawal fee can be increased per epoch
-/// protecting stakers from malicious users.
-/// If current fee is 0, `WITHDRAWAL_BASELINE_FEE` is used as the baseline
-pub const MAX_WITHDRAWAL_FEE_INCREASE: Fee = Fee {
-    numerator: 3,
-    denominator: 2,
-};
-/// Drop-in baseline fee when evaluating withdrawal fee increases when fee is 0
-pub const WITHDRAWAL_BASELINE_FEE: Fee = Fee {
-    numerator: 1,
-    denominator: 1000,
-};
-
-/// The maximum number of transient stake accounts respecting
-/// transaction account limits.
-pub const MAX_TRANSIENT_STAKE_ACCOUNTS: usize = 10;
-
-/// The maximum number of validators that can be supported in a pool in order
-/// for stake withdrawals to still work
-pub const MAX_VALIDATORS_IN_POOL: u32 = 20_000;
+use solana_program::stake::state::Meta;
 
 /// Get the stake amount under consideration when calculating pool token
 /// conversions
@@ -90,97 +50,6 @@ pub fn minimum_reserve_lamports(meta: &Meta) -> u64 {
         .saturating_add(MINIMUM_RESERVE_LAMPORTS)
 }
 
-/// Generates the deposit authority program address for the stake pool
-pub fn find_deposit_authority_program_address(
-    program_id: &Pubkey,
-    stake_pool_address: &Pubkey,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[stake_pool_address.as_ref(), AUTHORITY_DEPOSIT],
-        program_id,
-    )
-}
-
-/// Generates the withdraw authority program address for the stake pool
-pub fn find_withdraw_authority_program_address(
-    program_id: &Pubkey,
-    stake_pool_address: &Pubkey,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[stake_pool_address.as_ref(), AUTHORITY_WITHDRAW],
-        program_id,
-    )
-}
-
-/// Generates the stake program address for a validator's vote account
-pub fn find_stake_program_address(
-    program_id: &Pubkey,
-    vote_account_address: &Pubkey,
-    stake_pool_address: &Pubkey,
-    seed: Option<NonZeroU32>,
-) -> (Pubkey, u8) {
-    let seed = seed.map(|s| s.get().to_le_bytes());
-    Pubkey::find_program_address(
-        &[
-            vote_account_address.as_ref(),
-            stake_pool_address.as_ref(),
-            seed.as_ref().map(|s| s.as_slice()).unwrap_or(&[]),
-        ],
-        program_id,
-    )
-}
-
-/// Generates the stake program address for a validator's vote account
-pub fn find_transient_stake_program_address(
-    program_id: &Pubkey,
-    vote_account_address: &Pubkey,
-    stake_pool_address: &Pubkey,
-    seed: u64,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            TRANSIENT_STAKE_SEED_PREFIX,
-            vote_account_address.as_ref(),
-            stake_pool_address.as_ref(),
-            &seed.to_le_bytes(),
-        ],
-        program_id,
-    )
-}
-
-/// Generates the ephemeral program address for stake pool redelegation
-pub fn find_ephemeral_stake_program_address(
-    program_id: &Pubkey,
-    stake_pool_address: &Pubkey,
-    seed: u64,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            EPHEMERAL_STAKE_SEED_PREFIX,
-            stake_pool_address.as_ref(),
-            &seed.to_le_bytes(),
-        ],
-        program_id,
-    )
-}
-
-/// Generates the user stake account PDA for session-based withdrawals.
-/// The PDA is derived from the user's wallet and a unique seed.
-pub fn find_user_stake_program_address(
-    program_id: &Pubkey,
-    user_wallet: &Pubkey,
-    seed: u64,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            USER_STAKE_SEED_PREFIX,
-            user_wallet.as_ref(),
-            &seed.to_le_bytes(),
-        ],
-        program_id,
-    )
-}
-
 solana_program::declare_id!("SP1s4uFeTAX9jsXXmwyDs1gxYYf7cdDZ8qHUHVxE1yr");
 /// Program id for devnet
 pub mod devnet {
@@ -189,7 +58,7 @@ pub mod devnet {
 
 #[cfg(test)]
 mod test {
-    use super::*;
+    use {super::*, solana_program::pubkey::Pubkey};
 
     #[test]
     fn validator_stake_account_derivation() {
@@ -2,8 +2,14 @@
 
 use {
     crate::{
+        big_vec::BigVec,
         error::StakePoolError,
-        find_deposit_authority_program_address,
+        event::{AuthorityType, StakePoolEvent},
+        find_deposit_authority_program_address, find_direct_stake_program_address,
+        find_fee_split_program_address, find_insurance_fund_program_address,
+        find_oracle_program_address, find_rate_history_program_address,
+        find_referrer_program_address, find_validator_metadata_program_address,
+        find_withdrawal_ticket_program_address,
         inline_mpl_token_metadata::{
             self,
             instruction::{create_metadata_accounts_v3, update_metadata_accounts_v2},
@@ -13,37 +19,46 @@ use {
         instruction::{FundingType, PreferredValidatorType, StakePoolInstruction},
         minimum_delegation, minimum_reserve_lamports, minimum_stake_lamports,
         state::{
-            is_extension_supported_for_mint, AccountType, Fee, FeeType, FutureEpoch, StakePool,
-            StakeStatus, StakeWithdrawSource, ValidatorList, ValidatorListHeader,
-            ValidatorStakeInfo,
+            is_extension_supported_for_mint, AccountType, CapType, DirectStake, ExchangeRate, Fee,
+            FeeSplit, FeeType, FutureEpochFee, FutureEpochReferralFee,
+            FutureEpochSolWithdrawalFeeCurve, InsuranceFund, LegacyStakePool, OffboardingReason,
+            Oracle, PausedOperation, RateHistory, RateHistoryEntry, Referrer,
+            SolWithdrawalFeeCurve, StakePool, StakeStatus, StakeWithdrawSource, TokenAccountChecks,
+            ValidatorList, ValidatorListHeader, ValidatorMetadata, ValidatorStakeInfo,
+            WithdrawalTicket,
         },
-        AUTHORITY_DEPOSIT, AUTHORITY_WITHDRAW, EPHEMERAL_STAKE_SEED_PREFIX, MAX_VALIDATORS_IN_POOL,
-        TRANSIENT_STAKE_SEED_PREFIX,
+        AUTHORITY_DEPOSIT, AUTHORITY_WITHDRAW, DELINQUENT_VALIDATOR_CRANK_REWARD,
+        DIRECT_STAKE_SEED_PREFIX, EPHEMERAL_STAKE_SEED_PREFIX, FEE_SPLIT_SEED_PREFIX,
+        INSURANCE_FUND_SEED_PREFIX, MAX_BASIS_POINTS, MAX_COMMISSION, MAX_VALIDATORS_IN_POOL,
+        ORACLE_SEED_PREFIX, RATE_HISTORY_SEED_PREFIX, REFERRER_SEED_PREFIX, STAKE_POOL_LEN,
+        TRANSIENT_STAKE_SEED_PREFIX, VALIDATOR_METADATA_SEED_PREFIX, WITHDRAWAL_TICKET_SEED_PREFIX,
     },
     borsh::BorshDeserialize,
-    num_traits::FromPrimitive,
     solana_program::{
         account_info::{next_account_info, AccountInfo},
-        borsh1::try_from_slice_unchecked,
+        borsh1::{get_packed_len, try_from_slice_unchecked},
         clock::{Clock, Epoch},
-        decode_error::DecodeError,
         entrypoint::ProgramResult,
         epoch_rewards::EpochRewards,
         msg,
-        program::{invoke, invoke_signed},
-        program_error::{PrintProgramError, ProgramError},
+        program::{invoke, invoke_signed, set_return_data},
+        program_error::ProgramError,
         pubkey::Pubkey,
         rent::Rent,
         sysvar::Sysvar,
     },
     solana_stake_interface as stake,
     solana_system_interface::{instruction as system_instruction, program as system_program},
+    solana_vote_interface::state::VoteStateV3,
     spl_token_2022::{
         check_spl_token_program_account,
-        extension::{BaseStateWithExtensions, StateWithExtensions},
+        extension::{
+            metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions,
+        },
         native_mint,
         state::Mint,
     },
+    spl_token_metadata_interface::{instruction as token_metadata_instruction, state::Field},
     std::num::NonZeroU32,
 };
 
@@ -177,6 +192,29 @@ fn check_mpl_metadata_program(program_id: &Pubkey) -> Result<(), ProgramError> {
     }
 }
 
+/// Check that the pool mint is a Token-2022 mint whose metadata pointer
+/// points to the mint itself
+fn check_pool_mint_metadata_pointer(pool_mint_info: &AccountInfo) -> Result<(), ProgramError> {
+    if *pool_mint_info.owner != spl_token_2022::id() {
+        msg!("Pool mint {} is not a Token-2022 mint", pool_mint_info.key);
+        return Err(StakePoolError::InvalidMetadataPointer.into());
+    }
+    let mint_data = pool_mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let metadata_address = mint
+        .get_extension::<MetadataPointer>()
+        .ok()
+        .and_then(|metadata_pointer| Option::<Pubkey>::from(metadata_pointer.metadata_address));
+    if metadata_address != Some(*pool_mint_info.key) {
+        msg!(
+            "Pool mint metadata pointer must point to the mint itself, found {:?}",
+            metadata_address
+        );
+        return Err(StakePoolError::InvalidMetadataPointer.into());
+    }
+    Ok(())
+}
+
 /// Check account owner is the given program
 fn check_account_owner(
     account_info: &AccountInfo,
@@ -194,6 +232,278 @@ fn check_account_owner(
     }
 }
 
+/// Checks the owner of a vote account and deserializes its vote state,
+/// converting any version supported by the vote program to the current one
+fn get_vote_state(vote_account_info: &AccountInfo) -> Result<VoteStateV3, ProgramError> {
+    if *vote_account_info.owner != solana_vote_interface::program::id() {
+        msg!(
+            "Expected vote account {} to be owned by the vote program",
+            vote_account_info.key
+        );
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    VoteStateV3::deserialize(&vote_account_info.data.borrow()).map_err(|_| {
+        msg!("Unsupported vote state version");
+        ProgramError::InvalidAccountData
+    })
+}
+
+/// Reads the commission, as a percentage, of a vote account
+fn get_vote_commission(vote_account_info: &AccountInfo) -> Result<u8, ProgramError> {
+    get_vote_state(vote_account_info).map(|vote_state| vote_state.commission)
+}
+
+/// Reads the latest epoch in which a vote account earned vote credits, or
+/// `None` if it never did
+fn get_vote_last_credited_epoch(
+    vote_account_info: &AccountInfo,
+) -> Result<Option<Epoch>, ProgramError> {
+    get_vote_state(vote_account_info).map(|vote_state| {
+        vote_state
+            .epoch_credits()
+            .last()
+            .map(|(epoch, _, _)| *epoch)
+    })
+}
+
+/// Deserializes the metadata of a validator, checking its address. Returns
+/// `None` if it was never created, for validators added before it existed.
+fn get_validator_metadata(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    vote_account_address: &Pubkey,
+    validator_metadata_info: &AccountInfo,
+) -> Result<Option<ValidatorMetadata>, ProgramError> {
+    let (validator_metadata_address, _) = find_validator_metadata_program_address(
+        program_id,
+        stake_pool_address,
+        vote_account_address,
+    );
+    if validator_metadata_address != *validator_metadata_info.key {
+        msg!("Invalid validator metadata PDA");
+        return Err(StakePoolError::InvalidValidatorMetadata.into());
+    }
+    if validator_metadata_info.owner != program_id {
+        return Ok(None);
+    }
+    let validator_metadata =
+        try_from_slice_unchecked::<ValidatorMetadata>(&validator_metadata_info.data.borrow())?;
+    if !validator_metadata.is_valid() {
+        return Err(StakePoolError::InvalidValidatorMetadata.into());
+    }
+    Ok(Some(validator_metadata))
+}
+
+/// Splits off the optional referrer account of a deposit, which always comes
+/// last and is the only trailing account owned by the program
+fn split_referrer<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> (&'a [AccountInfo<'b>], Option<&'a AccountInfo<'b>>) {
+    match accounts.split_last() {
+        Some((last, rest)) if last.owner == program_id => (rest, Some(last)),
+        _ => (accounts, None),
+    }
+}
+
+/// Splits off the fee split account and its recipient token accounts, which
+/// trail the accounts of instructions paying fees, after any referrer
+fn split_fee_split<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> (&'a [AccountInfo<'b>], Option<&'a [AccountInfo<'b>]>) {
+    let fee_split_index = accounts.iter().rposition(|account_info| {
+        account_info.owner == program_id
+            && account_info
+                .try_borrow_data()
+                .is_ok_and(|data| data.first() == Some(&(AccountType::FeeSplit as u8)))
+    });
+    match fee_split_index {
+        Some(index) => (&accounts[..index], Some(&accounts[index..])),
+        None => (accounts, None),
+    }
+}
+
+/// Splits off the insurance fund account and the clock sysvar, stake history
+/// sysvar and stake program following it, which trail the accounts of
+/// `UpdateStakePoolBalance` before any fee split accounts
+fn split_insurance_fund<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> (&'a [AccountInfo<'b>], Option<&'a [AccountInfo<'b>]>) {
+    let insurance_fund_index = accounts.iter().rposition(|account_info| {
+        account_info.owner == program_id
+            && account_info
+                .try_borrow_data()
+                .is_ok_and(|data| data.first() == Some(&(AccountType::InsuranceFund as u8)))
+    });
+    match insurance_fund_index {
+        Some(index) => (&accounts[..index], Some(&accounts[index..])),
+        None => (accounts, None),
+    }
+}
+
+/// Deserializes the insurance fund of a stake pool, checking its address
+fn get_insurance_fund(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    insurance_fund_info: &AccountInfo,
+) -> Result<InsuranceFund, ProgramError> {
+    let (insurance_fund_address, _) =
+        find_insurance_fund_program_address(program_id, stake_pool_address);
+    if insurance_fund_address != *insurance_fund_info.key {
+        msg!("Invalid insurance fund PDA");
+        return Err(StakePoolError::InvalidInsuranceFund.into());
+    }
+    check_account_owner(insurance_fund_info, program_id)?;
+    let insurance_fund =
+        try_from_slice_unchecked::<InsuranceFund>(&insurance_fund_info.data.borrow())?;
+    if !insurance_fund.is_valid() || insurance_fund.stake_pool != *stake_pool_address {
+        return Err(StakePoolError::InvalidInsuranceFund.into());
+    }
+    Ok(insurance_fund)
+}
+
+/// Splits off the exchange rate history account, which trails the accounts
+/// of `UpdateStakePoolBalance` after any insurance fund accounts, once any
+/// fee split accounts are split off
+fn split_rate_history<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> (&'a [AccountInfo<'b>], Option<&'a AccountInfo<'b>>) {
+    match accounts.split_last() {
+        Some((last, rest))
+            if last.owner == program_id
+                && last
+                    .try_borrow_data()
+                    .is_ok_and(|data| data.first() == Some(&(AccountType::RateHistory as u8))) =>
+        {
+            (rest, Some(last))
+        }
+        _ => (accounts, None),
+    }
+}
+
+/// Checks that an account is the exchange rate history of a stake pool,
+/// without deserializing all of its entries
+fn check_rate_history(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    rate_history_info: &AccountInfo,
+) -> ProgramResult {
+    let (rate_history_address, _) =
+        find_rate_history_program_address(program_id, stake_pool_address);
+    if rate_history_address != *rate_history_info.key {
+        msg!("Invalid rate history PDA");
+        return Err(StakePoolError::InvalidRateHistory.into());
+    }
+    check_account_owner(rate_history_info, program_id)?;
+    let data = rate_history_info.try_borrow_data()?;
+    if data.first() != Some(&(AccountType::RateHistory as u8))
+        || data.get(1..33) != Some(stake_pool_address.as_ref())
+    {
+        return Err(StakePoolError::InvalidRateHistory.into());
+    }
+    Ok(())
+}
+
+/// Splits off the price oracle account, which trails the accounts of
+/// `UpdateStakePoolBalance` after any exchange rate history account, once any
+/// fee split accounts are split off
+fn split_oracle<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> (&'a [AccountInfo<'b>], Option<&'a AccountInfo<'b>>) {
+    match accounts.split_last() {
+        Some((last, rest))
+            if last.owner == program_id
+                && last
+                    .try_borrow_data()
+                    .is_ok_and(|data| data.first() == Some(&(AccountType::Oracle as u8))) =>
+        {
+            (rest, Some(last))
+        }
+        _ => (accounts, None),
+    }
+}
+
+/// Deserializes the price oracle of a stake pool, checking its address
+fn get_oracle(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    oracle_info: &AccountInfo,
+) -> Result<Oracle, ProgramError> {
+    let (oracle_address, _) = find_oracle_program_address(program_id, stake_pool_address);
+    if oracle_address != *oracle_info.key {
+        msg!("Invalid price oracle PDA");
+        return Err(StakePoolError::InvalidOracle.into());
+    }
+    check_account_owner(oracle_info, program_id)?;
+    let oracle = try_from_slice_unchecked::<Oracle>(&oracle_info.data.borrow())?;
+    if !oracle.is_valid() || oracle.stake_pool != *stake_pool_address {
+        return Err(StakePoolError::InvalidOracle.into());
+    }
+    Ok(oracle)
+}
+
+/// Shares of `fee` pool tokens going to each recipient of the stake pool's
+/// fee split, with the remainder going to the manager fee account. The fee
+/// split accounts are required whenever the pool splits its fees, so no
+/// instruction can leave the recipients out of their share.
+fn fee_payouts<'a, 'b>(
+    stake_pool_address: &Pubkey,
+    stake_pool: &StakePool,
+    manager_fee_info: &'a AccountInfo<'b>,
+    maybe_fee_split_infos: Option<&'a [AccountInfo<'b>]>,
+    fee: u64,
+) -> Result<Vec<(&'a AccountInfo<'b>, u64)>, ProgramError> {
+    if !stake_pool.has_fee_split {
+        return Ok(vec![(manager_fee_info, fee)]);
+    }
+    let Some((fee_split_info, recipient_infos)) =
+        maybe_fee_split_infos.and_then(|infos| infos.split_first())
+    else {
+        msg!("The stake pool splits its fees, but the fee split accounts are missing");
+        return Err(StakePoolError::InvalidFeeSplit.into());
+    };
+    let fee_split = try_from_slice_unchecked::<FeeSplit>(&fee_split_info.data.borrow())?;
+    if !fee_split.is_valid()
+        || fee_split.stake_pool != *stake_pool_address
+        || fee_split.recipients().len() != recipient_infos.len()
+        || fee_split
+            .recipients()
+            .iter()
+            .zip(recipient_infos)
+            .any(|(recipient, recipient_info)| recipient.token_account != *recipient_info.key)
+    {
+        return Err(StakePoolError::InvalidFeeSplit.into());
+    }
+
+    let mut payouts = Vec::with_capacity(recipient_infos.len().saturating_add(1));
+    let mut manager_fee = fee;
+    for (recipient, recipient_info) in fee_split.recipients().iter().zip(recipient_infos) {
+        let share = recipient
+            .share(fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        if share > 0 {
+            stake_pool
+                .check_manager_fee_info(recipient_info)
+                .inspect_err(|_| {
+                    msg!(
+                        "Fee split recipient {} cannot receive pool tokens",
+                        recipient_info.key
+                    )
+                })?;
+            manager_fee = manager_fee
+                .checked_sub(share)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            payouts.push((recipient_info, share));
+        }
+    }
+    payouts.push((manager_fee_info, manager_fee));
+    Ok(payouts)
+}
+
 /// Checks if a stake account can be managed by the pool
 fn stake_is_usable_by_pool(
     meta: &stake::state::Meta,
@@ -310,6 +620,262 @@ fn check_validator_stake_account(
     Ok(())
 }
 
+/// Check that a user stake PDA is its own withdrawer and was deactivated in an
+/// earlier epoch, returning its metadata
+fn check_deactivated_user_stake(
+    stake_account_info: &AccountInfo,
+    epoch: Epoch,
+) -> Result<stake::state::Meta, ProgramError> {
+    let stake_state =
+        try_from_slice_unchecked::<stake::state::StakeStateV2>(&stake_account_info.data.borrow())?;
+    match stake_state {
+        stake::state::StakeStateV2::Stake(meta, stake, _) => {
+            // Verify the stake's withdrawer is the PDA itself
+            if meta.authorized.withdrawer != *stake_account_info.key {
+                msg!(
+                    "Stake withdrawer mismatch. Expected: {}, got: {}",
+                    stake_account_info.key,
+                    meta.authorized.withdrawer
+                );
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // Check if stake is fully deactivated
+            if stake.delegation.deactivation_epoch == Epoch::MAX {
+                msg!("Stake account is not deactivated yet");
+                return Err(StakePoolError::UserStakeNotActive.into());
+            }
+            if stake.delegation.deactivation_epoch >= epoch {
+                msg!(
+                    "Stake account not fully deactivated. Deactivation epoch: {}, current: {}",
+                    stake.delegation.deactivation_epoch,
+                    epoch
+                );
+                return Err(StakePoolError::UserStakeNotActive.into());
+            }
+            Ok(meta)
+        }
+        _ => {
+            msg!("Stake account is not in staked state");
+            Err(StakePoolError::WrongStakeStake.into())
+        }
+    }
+}
+
+/// Check that `withdraw_lamports` may be split from `stake_split_from` under
+/// the withdrawal priority of the pool, returning the validator list entry to
+/// debit along with the kind of withdrawal, or `None` for the reserve. Taking a
+/// whole validator stake account truncates `withdraw_lamports` to its balance.
+fn check_stake_withdraw_source<'a>(
+    program_id: &Pubkey,
+    stake_pool_address: &Pubkey,
+    stake_pool: &mut StakePool,
+    validator_list: &'a mut BigVec,
+    stake_split_from: &AccountInfo,
+    withdraw_lamports: &mut u64,
+) -> Result<Option<(&'a mut ValidatorStakeInfo, StakeWithdrawSource)>, ProgramError> {
+    let stake_minimum_delegation = stake::tools::get_minimum_delegation()?;
+    let stake_state =
+        try_from_slice_unchecked::<stake::state::StakeStateV2>(&stake_split_from.data.borrow())?;
+    let meta = stake_state.meta().ok_or(StakePoolError::WrongStakeStake)?;
+    let required_lamports = minimum_stake_lamports(&meta, stake_minimum_delegation);
+
+    let lamports_per_pool_token = stake_pool
+        .get_lamports_per_pool_token()
+        .ok_or(StakePoolError::CalculationFailure)?;
+    let minimum_lamports_with_tolerance = required_lamports.saturating_add(lamports_per_pool_token);
+
+    let has_active_stake = validator_list
+        .find::<ValidatorStakeInfo, _>(|x| {
+            ValidatorStakeInfo::active_lamports_greater_than(x, &minimum_lamports_with_tolerance)
+                && ValidatorStakeInfo::is_active(x)
+        })
+        .is_some();
+    let has_transient_stake = validator_list
+        .find::<ValidatorStakeInfo, _>(|x| {
+            ValidatorStakeInfo::transient_lamports_greater_than(x, &minimum_lamports_with_tolerance)
+                && ValidatorStakeInfo::is_active(x)
+        })
+        .is_some();
+
+    if *stake_split_from.key == stake_pool.reserve_stake {
+        // check that the validator stake accounts have no withdrawable stake
+        if has_transient_stake || has_active_stake {
+            msg!("Error withdrawing from reserve: validator stake accounts have lamports available, please use those first.");
+            return Err(StakePoolError::StakeLamportsNotEqualToMinimum.into());
+        }
+
+        // check that reserve has enough, leaving the withdrawal claim pool
+        let minimum_reserve_lamports = minimum_reserve_lamports(&meta)
+            .saturating_add(stake_pool.withdrawal_claim_pool_lamports);
+        if stake_split_from
+            .lamports()
+            .saturating_sub(*withdraw_lamports)
+            < minimum_reserve_lamports
+        {
+            msg!("Attempting to withdraw {} lamports, maximum possible SOL withdrawal is {} lamports",
+                *withdraw_lamports,
+                stake_split_from.lamports().saturating_sub(minimum_reserve_lamports)
+            );
+            return Err(StakePoolError::SolWithdrawalTooLarge.into());
+        }
+        Ok(None)
+    } else {
+        let delegation = stake_state
+            .delegation()
+            .ok_or(StakePoolError::WrongStakeStake)?;
+        let vote_account_address = delegation.voter_pubkey;
+
+        if let Some(preferred_withdraw_validator) =
+            stake_pool.preferred_withdraw_validator_vote_address
+        {
+            // Defensive check, in case the preferred validator was somehow
+            // removed.
+            if let Some(preferred_validator_info) =
+                validator_list.find::<ValidatorStakeInfo, _>(|x| {
+                    ValidatorStakeInfo::memcmp_pubkey(x, &preferred_withdraw_validator)
+                })
+            {
+                let available_lamports = u64::from(preferred_validator_info.active_stake_lamports)
+                    .saturating_sub(minimum_lamports_with_tolerance);
+                if preferred_withdraw_validator != vote_account_address && available_lamports > 0 {
+                    msg!("Validator vote address {} is preferred for withdrawals, it currently has {} lamports available. Please withdraw those before using other validator stake accounts.", preferred_withdraw_validator, u64::from(preferred_validator_info.active_stake_lamports));
+                    return Err(StakePoolError::IncorrectWithdrawVoteAddress.into());
+                }
+            } else {
+                msg!("Preferred withdraw validator not found, allowing withdrawal from any validator");
+            }
+        }
+
+        let validator_stake_info = validator_list
+            .find_mut::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, &vote_account_address)
+            })
+            .ok_or(StakePoolError::ValidatorNotFound)?;
+
+        let withdraw_source = if has_active_stake {
+            // if there's any active stake, we must withdraw from an active
+            // stake account
+            check_validator_stake_address(
+                program_id,
+                stake_pool_address,
+                stake_split_from.key,
+                &vote_account_address,
+                NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+            )?;
+            StakeWithdrawSource::Active
+        } else if has_transient_stake || validator_stake_info.transient_stake_lamports != 0.into() {
+            // if there's any transient stake, we must withdraw from there
+            // Be particularly cautious to avoid removing a validator with
+            // transient lamports tied to it
+            check_transient_stake_address(
+                program_id,
+                stake_pool_address,
+                stake_split_from.key,
+                &vote_account_address,
+                validator_stake_info.transient_seed_suffix.into(),
+            )?;
+            StakeWithdrawSource::Transient
+        } else {
+            // if there's no active or transient stake, we can take the whole account
+            check_validator_stake_address(
+                program_id,
+                stake_pool_address,
+                stake_split_from.key,
+                &vote_account_address,
+                NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+            )?;
+            StakeWithdrawSource::ValidatorRemoval
+        };
+
+        if validator_stake_info.status != StakeStatus::Active.into() {
+            msg!("Validator is marked for removal and no longer allowing withdrawals");
+            return Err(StakePoolError::ValidatorNotFound.into());
+        }
+
+        match withdraw_source {
+            StakeWithdrawSource::Active | StakeWithdrawSource::Transient => {
+                let remaining_lamports = stake_split_from
+                    .lamports()
+                    .saturating_sub(*withdraw_lamports);
+                if remaining_lamports < required_lamports {
+                    msg!("Attempting to withdraw {} lamports from validator account with {} stake lamports, {} must remain", *withdraw_lamports, stake_split_from.lamports(), required_lamports);
+                    return Err(StakePoolError::StakeLamportsNotEqualToMinimum.into());
+                }
+            }
+            StakeWithdrawSource::ValidatorRemoval => {
+                let split_from_lamports = stake_split_from.lamports();
+                let upper_bound = split_from_lamports.saturating_add(lamports_per_pool_token);
+                if *withdraw_lamports < split_from_lamports || *withdraw_lamports > upper_bound {
+                    msg!(
+                        "Cannot withdraw a whole account worth {} lamports, \
+                          must withdraw at least {} lamports worth of pool tokens \
+                          with a margin of {} lamports",
+                        *withdraw_lamports,
+                        split_from_lamports,
+                        lamports_per_pool_token
+                    );
+                    return Err(StakePoolError::StakeLamportsNotEqualToMinimum.into());
+                }
+                // truncate the lamports down to the amount in the account
+                *withdraw_lamports = split_from_lamports;
+
+                // reset the preferred validator if needed
+                if stake_pool.preferred_deposit_validator_vote_address == Some(vote_account_address)
+                {
+                    stake_pool.preferred_deposit_validator_vote_address = None;
+                }
+                if stake_pool.preferred_withdraw_validator_vote_address
+                    == Some(vote_account_address)
+                {
+                    stake_pool.preferred_withdraw_validator_vote_address = None;
+                }
+            }
+        }
+        Ok(Some((validator_stake_info, withdraw_source)))
+    }
+}
+
+/// Debit a validator list entry for `lamports` split out of its stake accounts
+fn debit_validator_stake_info(
+    validator_stake_info: &mut ValidatorStakeInfo,
+    withdraw_source: StakeWithdrawSource,
+    lamports: u64,
+) -> ProgramResult {
+    match withdraw_source {
+        StakeWithdrawSource::Active => {
+            validator_stake_info.active_stake_lamports =
+                u64::from(validator_stake_info.active_stake_lamports)
+                    .checked_sub(lamports)
+                    .ok_or(StakePoolError::CalculationFailure)?
+                    .into()
+        }
+        StakeWithdrawSource::Transient => {
+            validator_stake_info.transient_stake_lamports =
+                u64::from(validator_stake_info.transient_stake_lamports)
+                    .checked_sub(lamports)
+                    .ok_or(StakePoolError::CalculationFailure)?
+                    .into()
+        }
+        StakeWithdrawSource::ValidatorRemoval => {
+            validator_stake_info.active_stake_lamports =
+                u64::from(validator_stake_info.active_stake_lamports)
+                    .checked_sub(lamports)
+                    .ok_or(StakePoolError::CalculationFailure)?
+                    .into();
+            if u64::from(validator_stake_info.active_stake_lamports) != 0 {
+                msg!("Attempting to remove a validator from the pool, but withdrawal leaves {} lamports, update the pool to merge any unaccounted lamports",
+                    u64::from(validator_stake_info.active_stake_lamports));
+                return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+            }
+            // since we already checked that there's no transient stake,
+            // we can immediately set this as ready for removal
+            validator_stake_info.status = StakeStatus::ReadyForRemoval.into();
+        }
+    }
+    Ok(())
+}
+
 /// Create a stake account on a PDA without transferring lamports
 fn create_stake_account(
     stake_account_info: AccountInfo<'_>,
@@ -440,6 +1006,33 @@ impl Processor {
         )
     }
 
+    /// Issue a `move_stake` instruction.
+    fn stake_move_stake<'a>(
+        stake_pool: &Pubkey,
+        source_account: AccountInfo<'a>,
+        authority: AccountInfo<'a>,
+        authority_type: &[u8],
+        bump_seed: u8,
+        amount: u64,
+        destination_account: AccountInfo<'a>,
+    ) -> Result<(), ProgramError> {
+        let authority_signature_seeds = [stake_pool.as_ref(), authority_type, &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+
+        let ix = stake::instruction::move_stake(
+            source_account.key,
+            destination_account.key,
+            authority.key,
+            amount,
+        );
+
+        invoke_signed(
+            &ix,
+            &[source_account, destination_account, authority],
+            signers,
+        )
+    }
+
     /// Issue stake::instruction::authorize instructions to update both
     /// authorities
     fn stake_authorize<'a>(
@@ -683,7 +1276,7 @@ impl Processor {
         // This check is unnecessary since the runtime will check the ownership,
         // but provides clarity that the parameter is in fact checked.
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_uninitialized() {
             msg!("Provided stake pool already in use");
             return Err(StakePoolError::AlreadyInUse.into());
@@ -873,21 +1466,52 @@ impl Processor {
         stake_pool.last_update_epoch = Clock::get()?.epoch;
         stake_pool.lockup = stake::state::Lockup::default();
         stake_pool.epoch_fee = epoch_fee;
-        stake_pool.next_epoch_fee = FutureEpoch::None;
+        stake_pool.next_epoch_fee = FutureEpochFee::None;
         stake_pool.preferred_deposit_validator_vote_address = None;
         stake_pool.preferred_withdraw_validator_vote_address = None;
         stake_pool.stake_deposit_fee = deposit_fee;
         stake_pool.stake_withdrawal_fee = withdrawal_fee;
-        stake_pool.next_stake_withdrawal_fee = FutureEpoch::None;
+        stake_pool.next_stake_withdrawal_fee = FutureEpochFee::None;
         stake_pool.stake_referral_fee = referral_fee;
         stake_pool.sol_deposit_authority = sol_deposit_authority;
         stake_pool.sol_deposit_fee = deposit_fee;
         stake_pool.sol_referral_fee = referral_fee;
         stake_pool.sol_withdraw_authority = None;
         stake_pool.sol_withdrawal_fee = withdrawal_fee;
-        stake_pool.next_sol_withdrawal_fee = FutureEpoch::None;
+        stake_pool.next_sol_withdrawal_fee = FutureEpochFee::None;
         stake_pool.last_epoch_pool_token_supply = 0;
         stake_pool.last_epoch_total_lamports = 0;
+        stake_pool.pending_withdrawal_lamports = 0;
+        stake_pool.withdrawal_claim_pool_lamports = 0;
+        stake_pool.max_deposit_lamports_per_epoch = None;
+        stake_pool.max_sol_withdrawal_lamports_per_epoch = None;
+        stake_pool.max_total_lamports = None;
+        stake_pool.flow_epoch = 0;
+        stake_pool.epoch_deposited_lamports = 0;
+        stake_pool.epoch_sol_withdrawn_lamports = 0;
+        stake_pool.pauser = None;
+        stake_pool.paused_operations = 0;
+        stake_pool.pending_manager = None;
+        stake_pool.pending_staker = None;
+        stake_pool.next_stake_deposit_fee = FutureEpochFee::None;
+        stake_pool.next_sol_deposit_fee = FutureEpochFee::None;
+        stake_pool.next_stake_referral_fee = FutureEpochReferralFee::None;
+        stake_pool.next_sol_referral_fee = FutureEpochReferralFee::None;
+        stake_pool.fee_change_delay_epochs = 0;
+        stake_pool.next_fee_change_delay_epochs = None;
+        stake_pool.fee_change_epoch = 0;
+        stake_pool.max_direct_stake_bps = None;
+        stake_pool.max_validator_share_bps = None;
+        stake_pool.max_validator_commission = None;
+        stake_pool.max_validator_vote_age_epochs = None;
+        stake_pool.sol_withdrawal_fee_curve = SolWithdrawalFeeCurve::default();
+        stake_pool.next_sol_withdrawal_fee_curve = FutureEpochSolWithdrawalFeeCurve::None;
+        stake_pool.has_fee_split = false;
+        stake_pool.insurance_fee_bps = 0;
+        stake_pool.uncovered_loss_lamports = 0;
+        stake_pool.has_rate_history = false;
+        stake_pool.has_oracle = false;
+        stake_pool.pending_insurance_lamports = 0;
 
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)
             .map_err(|e| e.into())
@@ -916,12 +1540,14 @@ impl Processor {
         let stake_config_info = next_account_info(account_info_iter)?;
         let system_program_info = next_account_info(account_info_iter)?;
         let stake_program_info = next_account_info(account_info_iter)?;
+        let maybe_validator_metadata_info = next_account_info(account_info_iter).ok();
+        let maybe_rent_payer_info = next_account_info(account_info_iter).ok();
 
         check_system_program(system_program_info.key)?;
         check_stake_program(stake_program_info.key)?;
 
         check_account_owner(stake_pool_info, program_id)?;
-        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
@@ -960,6 +1586,18 @@ impl Processor {
             return Err(StakePoolError::ValidatorAlreadyAdded.into());
         }
 
+        if let Some(max_validator_commission) = stake_pool.max_validator_commission {
+            let commission = get_vote_commission(validator_vote_info)?;
+            if commission > max_validator_commission {
+                msg!(
+                    "Validator commission of {}% is above the pool's maximum of {}%",
+                    commission,
+                    max_validator_commission
+                );
+                return Err(StakePoolError::CommissionTooHigh.into());
+            }
+        }
+
         let validator_seed = NonZeroU32::new(raw_validator_seed);
         let (stake_address, bump_seed) = crate::find_stake_program_address(
             program_id,
@@ -1038,10 +1676,127 @@ impl Processor {
             transient_stake_lamports: 0.into(),
             last_update_epoch: clock.epoch.into(),
             transient_seed_suffix: 0.into(),
-            unused: 0.into(),
+            max_share_bps: 0.into(),
             validator_seed_suffix: raw_validator_seed.into(),
         })?;
 
+        if let Some(validator_metadata_info) = maybe_validator_metadata_info {
+            Self::admit_validator_metadata(
+                program_id,
+                &stake_pool,
+                stake_pool_info.key,
+                validator_vote_info.key,
+                validator_metadata_info,
+                maybe_rent_payer_info,
+                system_program_info,
+                clock.epoch,
+            )?;
+        }
+
+        StakePoolEvent::ValidatorAdded {
+            stake_pool: *stake_pool_info.key,
+            vote_account: *validator_vote_info.key,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    /// Creates the metadata of a validator being added to the pool, or resets
+    /// it if the validator was in the pool before
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)] // needed due to stack size violation
+    fn admit_validator_metadata<'a>(
+        program_id: &Pubkey,
+        stake_pool: &StakePool,
+        stake_pool_address: &Pubkey,
+        vote_account_address: &Pubkey,
+        validator_metadata_info: &AccountInfo<'a>,
+        maybe_rent_payer_info: Option<&AccountInfo<'a>>,
+        system_program_info: &AccountInfo<'a>,
+        epoch: Epoch,
+    ) -> ProgramResult {
+        use spl_associated_token_account::tools::account::create_pda_account;
+
+        let existing_metadata = get_validator_metadata(
+            program_id,
+            stake_pool_address,
+            vote_account_address,
+            validator_metadata_info,
+        )?;
+        if existing_metadata.is_none() {
+            let rent_payer_info = maybe_rent_payer_info.ok_or_else(|| {
+                msg!("A rent payer is needed to create the validator metadata");
+                ProgramError::NotEnoughAccountKeys
+            })?;
+            let (_, validator_metadata_bump) = find_validator_metadata_program_address(
+                program_id,
+                stake_pool_address,
+                vote_account_address,
+            );
+            let validator_metadata_signer_seeds: &[&[u8]] = &[
+                VALIDATOR_METADATA_SEED_PREFIX,
+                stake_pool_address.as_ref(),
+                vote_account_address.as_ref(),
+                &[validator_metadata_bump],
+            ];
+            create_pda_account(
+                rent_payer_info,
+                &Rent::get()?,
+                get_packed_len::<ValidatorMetadata>(),
+                program_id,
+                system_program_info,
+                validator_metadata_info,
+                validator_metadata_signer_seeds,
+            )?;
+        }
+
+        let validator_metadata = ValidatorMetadata {
+            account_type: AccountType::ValidatorMetadata,
+            stake_pool: *stake_pool_address,
+            vote_account: *vote_account_address,
+            admission_epoch: epoch,
+            notes_hash: existing_metadata
+                .map(|metadata| metadata.notes_hash)
+                .unwrap_or_default(),
+            max_commission: stake_pool
+                .max_validator_commission
+                .unwrap_or(MAX_COMMISSION),
+            offboarding_reason: OffboardingReason::None,
+        };
+        borsh::to_writer(
+            &mut validator_metadata_info.data.borrow_mut()[..],
+            &validator_metadata,
+        )?;
+        Ok(())
+    }
+
+    /// Records why a validator was taken out of the pool in its metadata, if
+    /// it has any
+    fn record_offboarding_reason(
+        program_id: &Pubkey,
+        stake_pool_address: &Pubkey,
+        vote_account_address: &Pubkey,
+        maybe_validator_metadata_info: Option<&AccountInfo>,
+        offboarding_reason: OffboardingReason,
+    ) -> ProgramResult {
+        let Some(validator_metadata_info) = maybe_validator_metadata_info else {
+            return Ok(());
+        };
+        let Some(mut validator_metadata) = get_validator_metadata(
+            program_id,
+            stake_pool_address,
+            vote_account_address,
+            validator_metadata_info,
+        )?
+        else {
+            return Ok(());
+        };
+        validator_metadata.offboarding_reason = offboarding_reason;
+        borsh::to_writer(
+            &mut validator_metadata_info.data.borrow_mut()[..],
+            &validator_metadata,
+        )?;
         Ok(())
     }
 
@@ -1059,23 +1814,59 @@ impl Processor {
         let stake_account_info = next_account_info(account_info_iter)?;
         let transient_stake_account_info = next_account_info(account_info_iter)?;
         let clock_info = next_account_info(account_info_iter)?;
-        let clock = &Clock::from_account_info(clock_info)?;
         let stake_program_info = next_account_info(account_info_iter)?;
+        let maybe_validator_metadata_info = next_account_info(account_info_iter).ok();
 
         check_stake_program(stake_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
 
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_staker(staker_info)?;
 
-        stake_pool.check_authority_withdraw(
-            withdraw_authority_info.key,
+        let vote_account_address = Self::deactivate_validator(
             program_id,
-            stake_pool_info.key,
+            stake_pool,
+            stake_pool_info,
+            withdraw_authority_info,
+            validator_list_info,
+            stake_account_info,
+            transient_stake_account_info,
+            clock_info,
+        )?;
+        Self::record_offboarding_reason(
+            program_id,
+            stake_pool_info.key,
+            &vote_account_address,
+            maybe_validator_metadata_info,
+            OffboardingReason::RemovedByStaker,
+        )
+    }
+
+    /// Starts deactivating a validator's stake, and any transient stake it
+    /// has, marking it for removal from the pool. Returns the validator's
+    /// vote account.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)] // needed due to stack size violation
+    fn deactivate_validator<'a>(
+        program_id: &Pubkey,
+        mut stake_pool: StakePool,
+        stake_pool_info: &AccountInfo<'a>,
+        withdraw_authority_info: &AccountInfo<'a>,
+        validator_list_info: &AccountInfo<'a>,
+        stake_account_info: &AccountInfo<'a>,
+        transient_stake_account_info: &AccountInfo<'a>,
+        clock_info: &AccountInfo<'a>,
+    ) -> Result<Pubkey, ProgramError> {
+        let clock = &Clock::from_account_info(clock_info)?;
+
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
         )?;
-        stake_pool.check_staker(staker_info)?;
 
         if stake_pool.last_update_epoch < clock.epoch {
             msg!(
@@ -1180,7 +1971,13 @@ impl Processor {
         }
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
-        Ok(())
+        StakePoolEvent::ValidatorRemoved {
+            stake_pool: *stake_pool_info.key,
+            vote_account: vote_account_address,
+        }
+        .emit();
+
+        Ok(vote_account_address)
     }
 
     /// Processes `DecreaseValidatorStake` instruction.
@@ -1223,11 +2020,12 @@ impl Processor {
         check_stake_program(stake_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
 
-        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             msg!("Expected valid stake pool");
             return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_not_paused(PausedOperation::Rebalance)?;
 
         stake_pool.check_authority_withdraw(
             withdraw_authority_info.key,
@@ -1502,6 +2300,28 @@ impl Processor {
             transient_stake_account_info.lamports().into();
         validator_stake_info.transient_seed_suffix = transient_stake_seed.into();
 
+        if fund_rent_exempt_reserve {
+            // earmark the unstaked lamports for outstanding withdrawal tickets,
+            // they land in the reserve once the transient stake is merged
+            let claim_pool_funding = stake_pool
+                .calc_withdrawal_claim_pool_funding(lamports)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            if claim_pool_funding > 0 {
+                stake_pool.withdrawal_claim_pool_lamports = stake_pool
+                    .withdrawal_claim_pool_lamports
+                    .checked_add(claim_pool_funding)
+                    .ok_or(StakePoolError::CalculationFailure)?;
+                borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+            }
+        }
+
+        StakePoolEvent::ValidatorStakeDecreased {
+            stake_pool: *stake_pool_info.key,
+            vote_account: vote_account_address,
+            lamports,
+        }
+        .emit();
+
         Ok(())
     }
 
@@ -1540,16 +2360,18 @@ impl Processor {
         let stake_config_info = next_account_info(account_info_iter)?;
         let system_program_info = next_account_info(account_info_iter)?;
         let stake_program_info = next_account_info(account_info_iter)?;
+        let maybe_direct_stake_info = next_account_info(account_info_iter).ok();
 
         check_system_program(system_program_info.key)?;
         check_stake_program(stake_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
 
-        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             msg!("Expected valid stake pool");
             return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_not_paused(PausedOperation::Rebalance)?;
 
         stake_pool.check_authority_withdraw(
             withdraw_authority_info.key,
@@ -1619,6 +2441,13 @@ impl Processor {
             msg!("Validator is marked for removal and no longer allows increases");
             return Err(StakePoolError::ValidatorNotFound.into());
         }
+        stake_pool.check_validator_share(
+            validator_stake_info,
+            validator_stake_info
+                .stake_lamports()?
+                .checked_add(lamports)
+                .ok_or(StakePoolError::CalculationFailure)?,
+        )?;
 
         let stake_space = std::mem::size_of::<stake::state::StakeStateV2>();
         let stake_rent = rent.minimum_balance(stake_space);
@@ -1640,14 +2469,16 @@ impl Processor {
         // with `lamports + stake_rent`
         let total_lamports = lamports.saturating_add(stake_rent);
 
+        // lamports set aside for withdrawal tickets cannot be restaked
+        let reserve_minimum = stake_rent.saturating_add(stake_pool.withdrawal_claim_pool_lamports);
         if reserve_stake_account_info
             .lamports()
             .saturating_sub(total_lamports)
-            < stake_rent
+            < reserve_minimum
         {
             let max_split_amount = reserve_stake_account_info
                 .lamports()
-                .saturating_sub(stake_rent.saturating_mul(2));
+                .saturating_sub(stake_rent.saturating_add(reserve_minimum));
             msg!(
                 "Reserve stake does not have enough lamports for increase, maximum amount {}, {} requested",
                 max_split_amount,
@@ -1788,2635 +2619,5443 @@ impl Processor {
                 .into();
         validator_stake_info.transient_seed_suffix = transient_stake_seed.into();
 
+        if let Some(direct_stake_info) = maybe_direct_stake_info {
+            check_account_owner(direct_stake_info, program_id)?;
+            let mut direct_stake =
+                try_from_slice_unchecked::<DirectStake>(&direct_stake_info.data.borrow())?;
+            if !direct_stake.is_valid()
+                || direct_stake.stake_pool != *stake_pool_info.key
+                || direct_stake.vote_account != *vote_account_address
+            {
+                return Err(StakePoolError::InvalidDirectStake.into());
+            }
+            direct_stake.record_delegation(lamports);
+            borsh::to_writer(&mut direct_stake_info.data.borrow_mut()[..], &direct_stake)?;
+        }
+
+        StakePoolEvent::ValidatorStakeIncreased {
+            stake_pool: *stake_pool_info.key,
+            vote_account: *vote_account_address,
+            lamports,
+        }
+        .emit();
+
         Ok(())
     }
 
-    /// Process `SetPreferredValidator` instruction
+    /// Processes `Redelegate` instruction.
     #[inline(never)] // needed due to stack size violation
-    fn process_set_preferred_validator(
+    fn process_redelegate(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        validator_type: PreferredValidatorType,
-        vote_account_address: Option<Pubkey>,
+        lamports: u64,
+        source_transient_stake_seed: u64,
+        ephemeral_stake_seed: u64,
+        destination_transient_stake_seed: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
         let staker_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
         let validator_list_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let source_validator_stake_account_info = next_account_info(account_info_iter)?;
+        let source_transient_stake_account_info = next_account_info(account_info_iter)?;
+        let ephemeral_stake_account_info = next_account_info(account_info_iter)?;
+        let destination_transient_stake_account_info = next_account_info(account_info_iter)?;
+        let destination_validator_stake_account_info = next_account_info(account_info_iter)?;
+        let destination_vote_account_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let stake_config_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
 
+        check_system_program(system_program_info.key)?;
+        check_stake_program(stake_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
-        check_account_owner(validator_list_info, program_id)?;
 
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             msg!("Expected valid stake pool");
             return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_not_paused(PausedOperation::Rebalance)?;
 
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
         stake_pool.check_staker(staker_info)?;
+
+        if stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        }
+
         stake_pool.check_validator_list(validator_list_info)?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
+        check_account_owner(validator_list_info, program_id)?;
 
         let mut validator_list_data = validator_list_info.data.borrow_mut();
-        let (header, validator_list) =
+        let (header, mut validator_list) =
             ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
         if !header.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
 
-        if let Some(vote_account_address) = vote_account_address {
-            let maybe_validator_stake_info = validator_list.find::<ValidatorStakeInfo, _>(|x| {
-                ValidatorStakeInfo::memcmp_pubkey(x, &vote_account_address)
-            });
-            match maybe_validator_stake_info {
-                Some(vsi) => {
-                    if vsi.status != StakeStatus::Active.into() {
-                        msg!("Validator for {:?} about to be removed, cannot set as preferred deposit account", validator_type);
-                        return Err(StakePoolError::InvalidPreferredValidator.into());
-                    }
-                }
-                None => {
-                    msg!("Validator for {:?} not present in the stake pool, cannot set as preferred deposit account", validator_type);
-                    return Err(StakePoolError::ValidatorNotFound.into());
-                }
-            }
+        let (meta, stake) = get_stake_state(source_validator_stake_account_info)?;
+        let source_vote_account_address = stake.delegation.voter_pubkey;
+        let destination_vote_account_address = destination_vote_account_info.key;
+        if source_vote_account_address == *destination_vote_account_address {
+            msg!("Source and destination validators must be different");
+            return Err(StakePoolError::InvalidStakeAccountAddress.into());
         }
 
-        match validator_type {
-            PreferredValidatorType::Deposit => {
-                stake_pool.preferred_deposit_validator_vote_address = vote_account_address
-            }
-            PreferredValidatorType::Withdraw => {
-                stake_pool.preferred_withdraw_validator_vote_address = vote_account_address
-            }
-        };
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
-        Ok(())
-    }
-
-    /// Processes `UpdateValidatorListBalance` instruction.
-    #[inline(always)] // needed to maximize number of validators
-    fn process_update_validator_list_balance(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        start_index: u32,
-        no_merge: bool,
-    ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let stake_pool_info = next_account_info(account_info_iter)?;
-        let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let validator_list_info = next_account_info(account_info_iter)?;
-        let reserve_stake_info = next_account_info(account_info_iter)?;
-        let clock_info = next_account_info(account_info_iter)?;
-        let clock = &Clock::from_account_info(clock_info)?;
-        let stake_history_info = next_account_info(account_info_iter)?;
-        let stake_program_info = next_account_info(account_info_iter)?;
-        let validator_stake_accounts = account_info_iter.as_slice();
+        // copies of both entries, written back through `find_mut` at the end
+        let mut source_validator_stake_info = *validator_list
+            .find::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, &source_vote_account_address)
+            })
+            .ok_or_else(|| {
+                msg!(
+                    "Vote account {} not found in stake pool",
+                    source_vote_account_address
+                );
+                StakePoolError::ValidatorNotFound
+            })?;
+        let mut destination_validator_stake_info = *validator_list
+            .find::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, destination_vote_account_address)
+            })
+            .ok_or_else(|| {
+                msg!(
+                    "Vote account {} not found in stake pool",
+                    destination_vote_account_address
+                );
+                StakePoolError::ValidatorNotFound
+            })?;
 
-        check_account_owner(stake_pool_info, program_id)?;
-        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
-        if !stake_pool.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
-        }
-        stake_pool.check_validator_list(validator_list_info)?;
-        stake_pool.check_authority_withdraw(
+        check_validator_stake_account(
+            source_validator_stake_account_info,
+            program_id,
+            stake_pool_info.key,
             withdraw_authority_info.key,
+            &source_vote_account_address,
+            source_validator_stake_info.validator_seed_suffix.into(),
+            &stake_pool.lockup,
+        )?;
+        check_validator_stake_account(
+            destination_validator_stake_account_info,
             program_id,
             stake_pool_info.key,
+            withdraw_authority_info.key,
+            destination_vote_account_address,
+            destination_validator_stake_info
+                .validator_seed_suffix
+                .into(),
+            &stake_pool.lockup,
         )?;
-        stake_pool.check_reserve_stake(reserve_stake_info)?;
-        check_stake_program(stake_program_info.key)?;
 
-        // If rewards are being distributed, abort
-        let epoch_rewards = EpochRewards::get()?;
-        if epoch_rewards.active {
-            return Err(StakePoolError::EpochRewardDistributionInProgress.into());
+        // same rules as `DecreaseAdditionalValidatorStake` on the source and
+        // `IncreaseAdditionalValidatorStake` on the destination
+        let source_transient_exists =
+            u64::from(source_validator_stake_info.transient_stake_lamports) > 0;
+        if source_transient_exists {
+            if source_transient_stake_seed
+                != u64::from(source_validator_stake_info.transient_seed_suffix)
+            {
+                msg!(
+                    "Source transient stake already exists with seed {}, you must use that one",
+                    u64::from(source_validator_stake_info.transient_seed_suffix)
+                );
+                return Err(ProgramError::InvalidSeeds);
+            }
+            check_if_stake_deactivating(
+                source_transient_stake_account_info,
+                &source_vote_account_address,
+                clock.epoch,
+            )?;
+        }
+        let destination_transient_exists =
+            u64::from(destination_validator_stake_info.transient_stake_lamports) > 0;
+        if destination_transient_exists {
+            if destination_transient_stake_seed
+                != u64::from(destination_validator_stake_info.transient_seed_suffix)
+            {
+                msg!(
+                    "Destination transient stake already exists with seed {}, you must use that one",
+                    u64::from(destination_validator_stake_info.transient_seed_suffix)
+                );
+                return Err(ProgramError::InvalidSeeds);
+            }
+            check_if_stake_activating(
+                destination_transient_stake_account_info,
+                destination_vote_account_address,
+                clock.epoch,
+            )?;
         }
 
-        if validator_stake_accounts
-            .len()
-            .checked_rem(2)
-            .ok_or(StakePoolError::CalculationFailure)?
-            != 0
+        if source_validator_stake_info.status != StakeStatus::Active.into()
+            || destination_validator_stake_info.status != StakeStatus::Active.into()
         {
-            msg!("Odd number of validator stake accounts passed in, should be pairs of validator stake and transient stake accounts");
-            return Err(StakePoolError::UnexpectedValidatorListAccountSize.into());
+            msg!("Validator is marked for removal and no longer allows redelegation");
+            return Err(StakePoolError::ValidatorNotFound.into());
         }
-
-        check_account_owner(validator_list_info, program_id)?;
-        let mut validator_list_data = validator_list_info.data.borrow_mut();
-        let (validator_list_header, mut big_vec) =
-            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
-        let validator_slice = ValidatorListHeader::deserialize_mut_slice(
-            &mut big_vec,
-            start_index as usize,
-            validator_stake_accounts.len() / 2,
+        stake_pool.check_validator_share(
+            &destination_validator_stake_info,
+            destination_validator_stake_info
+                .stake_lamports()?
+                .checked_add(lamports)
+                .ok_or(StakePoolError::CalculationFailure)?,
         )?;
 
-        if !validator_list_header.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
+        let stake_space = std::mem::size_of::<stake::state::StakeStateV2>();
+        let stake_rent = Rent::get()?.minimum_balance(stake_space);
+        let stake_minimum_delegation = stake::tools::get_minimum_delegation()?;
+        let current_minimum_lamports = minimum_delegation(stake_minimum_delegation);
+        if lamports < current_minimum_lamports {
+            msg!(
+                "Need at least {} lamports for transient stake to meet minimum delegation and rent-exempt requirements, {} provided",
+                current_minimum_lamports,
+                lamports
+            );
+            return Err(ProgramError::AccountNotRentExempt);
         }
 
-        let validator_iter = &mut validator_slice
-            .iter_mut()
-            .zip(validator_stake_accounts.chunks_exact(2));
-        for (validator_stake_record, validator_stakes) in validator_iter {
-            // chunks_exact means that we always get 2 elements, making this safe
-            let validator_stake_info = validator_stakes
-                .first()
-                .ok_or(ProgramError::InvalidInstructionData)?;
-            let transient_stake_info = validator_stakes
-                .last()
-                .ok_or(ProgramError::InvalidInstructionData)?;
-            if check_validator_stake_address(
-                program_id,
-                stake_pool_info.key,
-                validator_stake_info.key,
-                &validator_stake_record.vote_account_address,
-                NonZeroU32::new(validator_stake_record.validator_seed_suffix.into()),
-            )
-            .is_err()
-            {
-                continue;
-            };
-            if check_transient_stake_address(
-                program_id,
-                stake_pool_info.key,
-                transient_stake_info.key,
-                &validator_stake_record.vote_account_address,
-                validator_stake_record.transient_seed_suffix.into(),
-            )
-            .is_err()
-            {
-                continue;
-            };
+        let remaining_lamports = source_validator_stake_account_info
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        let required_lamports = minimum_stake_lamports(&meta, stake_minimum_delegation);
+        if remaining_lamports < required_lamports {
+            msg!("Need at least {} lamports in the stake account after redelegation, {} requested, {} is the current possible maximum",
+                required_lamports,
+                lamports,
+                source_validator_stake_account_info.lamports().checked_sub(required_lamports).ok_or(StakePoolError::CalculationFailure)?
+            );
+            return Err(ProgramError::InsufficientFunds);
+        }
 
-            let mut active_stake_lamports = 0;
-            let mut transient_stake_lamports = 0;
-            let validator_stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-                &validator_stake_info.data.borrow(),
-            )
-            .ok();
-            let transient_stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-                &transient_stake_info.data.borrow(),
-            )
-            .ok();
+        // the reserve funds the ephemeral account's rent-exemption, then the
+        // destination stake with its own rent-exemption, and gets both back
+        // once the transient accounts are merged
+        let reserve_lamports = lamports
+            .checked_add(stake_rent.saturating_mul(2))
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let reserve_minimum = stake_rent.saturating_add(stake_pool.withdrawal_claim_pool_lamports);
+        if reserve_stake_info
+            .lamports()
+            .saturating_sub(reserve_lamports)
+            < reserve_minimum
+        {
+            msg!(
+                "Reserve stake does not have enough lamports for redelegation, {} required, {} available",
+                reserve_lamports,
+                reserve_stake_info.lamports().saturating_sub(reserve_minimum)
+            );
+            return Err(ProgramError::InsufficientFunds);
+        }
 
-            // Possible merge situations for transient stake
-            //  * active -> merge into validator stake
-            //  * activating -> nothing, just account its lamports
-            //  * deactivating -> nothing, just account its lamports
-            //  * inactive -> merge into reserve stake
-            //  * not a stake -> ignore
-            if validator_stake_record.transient_stake_lamports != 0.into() {
-                match transient_stake_state {
-                    Some(stake::state::StakeStateV2::Initialized(meta)) => {
-                        if stake_is_usable_by_pool(
-                            &meta,
-                            withdraw_authority_info.key,
-                            &stake_pool.lockup,
-                        ) {
-                            if no_merge {
-                                transient_stake_lamports = transient_stake_info.lamports();
-                            } else {
-                                // merge into reserve
-                                Self::stake_merge(
-                                    stake_pool_info.key,
-                                    transient_stake_info.clone(),
-                                    withdraw_authority_info.clone(),
-                                    AUTHORITY_WITHDRAW,
-                                    stake_pool.stake_withdraw_bump_seed,
-                                    reserve_stake_info.clone(),
-                                    clock_info.clone(),
-                                    stake_history_info.clone(),
-                                )?;
-                                validator_stake_record.status.remove_transient_stake()?;
-                            }
-                        }
-                    }
-                    Some(stake::state::StakeStateV2::Stake(meta, stake, _)) => {
-                        if stake_is_usable_by_pool(
-                            &meta,
-                            withdraw_authority_info.key,
-                            &stake_pool.lockup,
-                        ) {
-                            if !no_merge {
-                                if stake_is_inactive_without_history(&stake, clock.epoch) {
-                                    // deactivated, merge into reserve
-                                    Self::stake_merge(
-                                        stake_pool_info.key,
-                                        transient_stake_info.clone(),
-                                        withdraw_authority_info.clone(),
-                                        AUTHORITY_WITHDRAW,
-                                        stake_pool.stake_withdraw_bump_seed,
-                                        reserve_stake_info.clone(),
-                                        clock_info.clone(),
-                                        stake_history_info.clone(),
-                                    )?;
-                                    validator_stake_record.status.remove_transient_stake()?;
-                                } else if validator_stake_record.status.try_into()
-                                    == Ok(StakeStatus::Active)
-                                {
-                                    if stake.delegation.activation_epoch < clock.epoch {
-                                        if let Some(stake::state::StakeStateV2::Stake(
-                                            _,
-                                            validator_stake,
-                                            _,
-                                        )) = validator_stake_state
-                                        {
-                                            if validator_stake.delegation.activation_epoch
-                                                < clock.epoch
-                                            {
-                                                Self::stake_merge(
-                                                    stake_pool_info.key,
-                                                    transient_stake_info.clone(),
-                                                    withdraw_authority_info.clone(),
-                                                    AUTHORITY_WITHDRAW,
-                                                    stake_pool.stake_withdraw_bump_seed,
-                                                    validator_stake_info.clone(),
-                                                    clock_info.clone(),
-                                                    stake_history_info.clone(),
-                                                )?;
-                                            } else {
-                                                msg!("Stake activating or just active, not ready to merge");
-                                            }
-                                        } else {
-                                            msg!("Transient stake is activating or active, but validator stake is not, need to add the validator stake account on {} back into the stake pool", stake.delegation.voter_pubkey);
-                                        }
-                                    } else {
-                                        msg!("Transient stake not ready to be merged anywhere");
-                                    }
-                                } else if stake.delegation.deactivation_epoch == Epoch::MAX {
-                                    msg!("Transient stake is activating or active, deactivating.");
-                                    Self::stake_deactivate(
-                                        transient_stake_info.clone(),
-                                        clock_info.clone(),
-                                        withdraw_authority_info.clone(),
-                                        stake_pool_info.key,
-                                        AUTHORITY_WITHDRAW,
-                                        stake_pool.stake_withdraw_bump_seed,
-                                    )?;
-                                }
-                            }
-                            transient_stake_lamports = transient_stake_info.lamports();
-                        }
+        let ephemeral_stake_bump_seed = check_ephemeral_stake_address(
+            program_id,
+            stake_pool_info.key,
+            ephemeral_stake_account_info.key,
+            ephemeral_stake_seed,
+        )?;
+        let ephemeral_stake_account_signer_seeds: &[&[_]] = &[
+            EPHEMERAL_STAKE_SEED_PREFIX,
+            stake_pool_info.key.as_ref(),
+            &ephemeral_stake_seed.to_le_bytes(),
+            &[ephemeral_stake_bump_seed],
+        ];
+        create_stake_account(
+            ephemeral_stake_account_info.clone(),
+            ephemeral_stake_account_signer_seeds,
+            stake_space,
+        )?;
+
+        // splitting the rent-exemption from the reserve initializes the
+        // ephemeral account with the same authorities and lockup as the
+        // validator stake, as required to move stake into it
+        Self::stake_split(
+            stake_pool_info.key,
+            reserve_stake_info.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            stake_rent,
+            ephemeral_stake_account_info.clone(),
+        )?;
+
+        // the stake program only moves active stake between accounts
+        // delegated to the same validator, so the moved stake still has to
+        // cool down, but without splitting the validator stake account
+        Self::stake_move_stake(
+            stake_pool_info.key,
+            source_validator_stake_account_info.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            lamports,
+            ephemeral_stake_account_info.clone(),
+        )?;
+
+        Self::stake_deactivate(
+            ephemeral_stake_account_info.clone(),
+            clock_info.clone(),
+            withdraw_authority_info.clone(),
+            stake_pool_info.key,
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+        )?;
+
+        let source_transient_stake_bump_seed = check_transient_stake_address(
+            program_id,
+            stake_pool_info.key,
+            source_transient_stake_account_info.key,
+            &source_vote_account_address,
+            source_transient_stake_seed,
+        )?;
+        if source_transient_exists {
+            Self::stake_merge(
+                stake_pool_info.key,
+                ephemeral_stake_account_info.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                source_transient_stake_account_info.clone(),
+                clock_info.clone(),
+                stake_history_info.clone(),
+            )?;
+        } else {
+            let source_transient_stake_account_signer_seeds: &[&[_]] = &[
+                TRANSIENT_STAKE_SEED_PREFIX,
+                source_vote_account_address.as_ref(),
+                stake_pool_info.key.as_ref(),
+                &source_transient_stake_seed.to_le_bytes(),
+                &[source_transient_stake_bump_seed],
+            ];
+            create_stake_account(
+                source_transient_stake_account_info.clone(),
+                source_transient_stake_account_signer_seeds,
+                stake_space,
+            )?;
+            Self::stake_split(
+                stake_pool_info.key,
+                ephemeral_stake_account_info.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                ephemeral_stake_account_info.lamports(),
+                source_transient_stake_account_info.clone(),
+            )?;
+        }
+
+        // the ephemeral account is now drained but still allocated to the
+        // stake program, so it can receive the destination stake directly
+        Self::stake_split(
+            stake_pool_info.key,
+            reserve_stake_info.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            lamports.saturating_add(stake_rent),
+            ephemeral_stake_account_info.clone(),
+        )?;
+
+        Self::stake_delegate(
+            ephemeral_stake_account_info.clone(),
+            destination_vote_account_info.clone(),
+            clock_info.clone(),
+            stake_history_info.clone(),
+            stake_config_info.clone(),
+            withdraw_authority_info.clone(),
+            stake_pool_info.key,
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+        )?;
+
+        let destination_transient_stake_bump_seed = check_transient_stake_address(
+            program_id,
+            stake_pool_info.key,
+            destination_transient_stake_account_info.key,
+            destination_vote_account_address,
+            destination_transient_stake_seed,
+        )?;
+        if destination_transient_exists {
+            Self::stake_merge(
+                stake_pool_info.key,
+                ephemeral_stake_account_info.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                destination_transient_stake_account_info.clone(),
+                clock_info.clone(),
+                stake_history_info.clone(),
+            )?;
+        } else {
+            let destination_transient_stake_account_signer_seeds: &[&[_]] = &[
+                TRANSIENT_STAKE_SEED_PREFIX,
+                destination_vote_account_address.as_ref(),
+                stake_pool_info.key.as_ref(),
+                &destination_transient_stake_seed.to_le_bytes(),
+                &[destination_transient_stake_bump_seed],
+            ];
+            create_stake_account(
+                destination_transient_stake_account_info.clone(),
+                destination_transient_stake_account_signer_seeds,
+                stake_space,
+            )?;
+            Self::stake_split(
+                stake_pool_info.key,
+                ephemeral_stake_account_info.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                ephemeral_stake_account_info.lamports(),
+                destination_transient_stake_account_info.clone(),
+            )?;
+        }
+
+        source_validator_stake_info.active_stake_lamports =
+            u64::from(source_validator_stake_info.active_stake_lamports)
+                .checked_sub(lamports)
+                .ok_or(StakePoolError::CalculationFailure)?
+                .into();
+        source_validator_stake_info.transient_stake_lamports =
+            source_transient_stake_account_info.lamports().into();
+        source_validator_stake_info.transient_seed_suffix = source_transient_stake_seed.into();
+        destination_validator_stake_info.transient_stake_lamports =
+            destination_transient_stake_account_info.lamports().into();
+        destination_validator_stake_info.transient_seed_suffix =
+            destination_transient_stake_seed.into();
+        for validator_stake_info in [
+            source_validator_stake_info,
+            destination_validator_stake_info,
+        ] {
+            *validator_list
+                .find_mut::<ValidatorStakeInfo, _>(|x| {
+                    ValidatorStakeInfo::memcmp_pubkey(x, &validator_stake_info.vote_account_address)
+                })
+                .ok_or(StakePoolError::ValidatorNotFound)? = validator_stake_info;
+        }
+
+        StakePoolEvent::ValidatorStakeDecreased {
+            stake_pool: *stake_pool_info.key,
+            vote_account: source_vote_account_address,
+            lamports,
+        }
+        .emit();
+        StakePoolEvent::ValidatorStakeIncreased {
+            stake_pool: *stake_pool_info.key,
+            vote_account: *destination_vote_account_address,
+            lamports,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    /// Process `SetPreferredValidator` instruction
+    #[inline(never)] // needed due to stack size violation
+    fn process_set_preferred_validator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        validator_type: PreferredValidatorType,
+        vote_account_address: Option<Pubkey>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let staker_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        check_account_owner(validator_list_info, program_id)?;
+
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            msg!("Expected valid stake pool");
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        stake_pool.check_staker(staker_info)?;
+        stake_pool.check_validator_list(validator_list_info)?;
+
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        if let Some(vote_account_address) = vote_account_address {
+            let maybe_validator_stake_info = validator_list.find::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, &vote_account_address)
+            });
+            match maybe_validator_stake_info {
+                Some(vsi) => {
+                    if vsi.status != StakeStatus::Active.into() {
+                        msg!("Validator for {:?} about to be removed, cannot set as preferred deposit account", validator_type);
+                        return Err(StakePoolError::InvalidPreferredValidator.into());
                     }
-                    None
-                    | Some(stake::state::StakeStateV2::Uninitialized)
-                    | Some(stake::state::StakeStateV2::RewardsPool) => {} // do nothing
                 }
-            }
-            // Status for validator stake
-            //  * active -> do everything
-            //  * any other state / not a stake -> error state, but account for transient
-            //    stake
-            let validator_stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-                &validator_stake_info.data.borrow(),
-            )
-            .ok();
-            match validator_stake_state {
-                Some(stake::state::StakeStateV2::Stake(meta, stake, _))
-                    if stake_is_usable_by_pool(
-                        &meta,
-                        withdraw_authority_info.key,
-                        &stake_pool.lockup,
-                    ) =>
-                {
-                    let additional_lamports = validator_stake_info
-                        .lamports()
-                        .saturating_sub(stake.delegation.stake)
-                        .saturating_sub(meta.rent_exempt_reserve);
-                    // withdraw any extra lamports back to the reserve
-                    if additional_lamports > 0 {
-                        Self::stake_withdraw(
-                            stake_pool_info.key,
-                            validator_stake_info.clone(),
-                            withdraw_authority_info.clone(),
-                            AUTHORITY_WITHDRAW,
-                            stake_pool.stake_withdraw_bump_seed,
-                            reserve_stake_info.clone(),
-                            clock_info.clone(),
-                            stake_history_info.clone(),
-                            additional_lamports,
-                        )?;
-                    }
-                    match validator_stake_record.status.try_into()? {
-                        StakeStatus::Active => {
-                            active_stake_lamports = validator_stake_info.lamports();
-                        }
-                        StakeStatus::DeactivatingValidator | StakeStatus::DeactivatingAll => {
-                            if no_merge {
-                                active_stake_lamports = validator_stake_info.lamports();
-                            } else if stake_is_inactive_without_history(&stake, clock.epoch) {
-                                // Validator was removed through normal means.
-                                // Absorb the lamports into the reserve.
-                                Self::stake_merge(
-                                    stake_pool_info.key,
-                                    validator_stake_info.clone(),
-                                    withdraw_authority_info.clone(),
-                                    AUTHORITY_WITHDRAW,
-                                    stake_pool.stake_withdraw_bump_seed,
-                                    reserve_stake_info.clone(),
-                                    clock_info.clone(),
-                                    stake_history_info.clone(),
-                                )?;
-                                validator_stake_record.status.remove_validator_stake()?;
-                            } else {
-                                active_stake_lamports = validator_stake_info.lamports();
-                            }
-                        }
-                        StakeStatus::DeactivatingTransient | StakeStatus::ReadyForRemoval => {
-                            msg!("Validator stake account no longer part of the pool, ignoring");
-                        }
+                None => {
+                    msg!("Validator for {:?} not present in the stake pool, cannot set as preferred deposit account", validator_type);
+                    return Err(StakePoolError::ValidatorNotFound.into());
+                }
+            }
+        }
+
+        match validator_type {
+            PreferredValidatorType::Deposit => {
+                stake_pool.preferred_deposit_validator_vote_address = vote_account_address
+            }
+            PreferredValidatorType::Withdraw => {
+                stake_pool.preferred_withdraw_validator_vote_address = vote_account_address
+            }
+        };
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::PreferredValidatorChanged {
+            stake_pool: *stake_pool_info.key,
+            validator_type,
+            vote_account: vote_account_address,
+        }
+        .emit();
+        Ok(())
+    }
+
+    /// Processes `UpdateValidatorListBalance` instruction.
+    #[inline(always)] // needed to maximize number of validators
+    fn process_update_validator_list_balance(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        start_index: u32,
+        no_merge: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+        let validator_stake_accounts = account_info_iter.as_slice();
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_validator_list(validator_list_info)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
+        check_stake_program(stake_program_info.key)?;
+
+        // If rewards are being distributed, abort
+        let epoch_rewards = EpochRewards::get()?;
+        if epoch_rewards.active {
+            return Err(StakePoolError::EpochRewardDistributionInProgress.into());
+        }
+
+        if validator_stake_accounts
+            .len()
+            .checked_rem(2)
+            .ok_or(StakePoolError::CalculationFailure)?
+            != 0
+        {
+            msg!("Odd number of validator stake accounts passed in, should be pairs of validator stake and transient stake accounts");
+            return Err(StakePoolError::UnexpectedValidatorListAccountSize.into());
+        }
+
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (validator_list_header, mut big_vec) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        let validator_slice = ValidatorListHeader::deserialize_mut_slice(
+            &mut big_vec,
+            start_index as usize,
+            validator_stake_accounts.len() / 2,
+        )?;
+
+        if !validator_list_header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let mut loss_lamports: u64 = 0;
+        let validator_iter = &mut validator_slice
+            .iter_mut()
+            .zip(validator_stake_accounts.chunks_exact(2));
+        for (validator_stake_record, validator_stakes) in validator_iter {
+            // chunks_exact means that we always get 2 elements, making this safe
+            let validator_stake_info = validator_stakes
+                .first()
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            let transient_stake_info = validator_stakes
+                .last()
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            if check_validator_stake_address(
+                program_id,
+                stake_pool_info.key,
+                validator_stake_info.key,
+                &validator_stake_record.vote_account_address,
+                NonZeroU32::new(validator_stake_record.validator_seed_suffix.into()),
+            )
+            .is_err()
+            {
+                continue;
+            };
+            if check_transient_stake_address(
+                program_id,
+                stake_pool_info.key,
+                transient_stake_info.key,
+                &validator_stake_record.vote_account_address,
+                validator_stake_record.transient_seed_suffix.into(),
+            )
+            .is_err()
+            {
+                continue;
+            };
+
+            let mut active_stake_lamports = 0;
+            let mut transient_stake_lamports = 0;
+            let validator_stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+                &validator_stake_info.data.borrow(),
+            )
+            .ok();
+            let transient_stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+                &transient_stake_info.data.borrow(),
+            )
+            .ok();
+
+            // Possible merge situations for transient stake
+            //  * active -> merge into validator stake
+            //  * activating -> nothing, just account its lamports
+            //  * deactivating -> nothing, just account its lamports
+            //  * inactive -> merge into reserve stake
+            //  * not a stake -> ignore
+            if validator_stake_record.transient_stake_lamports != 0.into() {
+                match transient_stake_state {
+                    Some(stake::state::StakeStateV2::Initialized(meta)) => {
+                        if stake_is_usable_by_pool(
+                            &meta,
+                            withdraw_authority_info.key,
+                            &stake_pool.lockup,
+                        ) {
+                            if no_merge {
+                                transient_stake_lamports = transient_stake_info.lamports();
+                            } else {
+                                // merge into reserve
+                                Self::stake_merge(
+                                    stake_pool_info.key,
+                                    transient_stake_info.clone(),
+                                    withdraw_authority_info.clone(),
+                                    AUTHORITY_WITHDRAW,
+                                    stake_pool.stake_withdraw_bump_seed,
+                                    reserve_stake_info.clone(),
+                                    clock_info.clone(),
+                                    stake_history_info.clone(),
+                                )?;
+                                validator_stake_record.status.remove_transient_stake()?;
+                            }
+                        }
+                    }
+                    Some(stake::state::StakeStateV2::Stake(meta, stake, _)) => {
+                        if stake_is_usable_by_pool(
+                            &meta,
+                            withdraw_authority_info.key,
+                            &stake_pool.lockup,
+                        ) {
+                            if !no_merge {
+                                if stake_is_inactive_without_history(&stake, clock.epoch) {
+                                    // deactivated, merge into reserve
+                                    Self::stake_merge(
+                                        stake_pool_info.key,
+                                        transient_stake_info.clone(),
+                                        withdraw_authority_info.clone(),
+                                        AUTHORITY_WITHDRAW,
+                                        stake_pool.stake_withdraw_bump_seed,
+                                        reserve_stake_info.clone(),
+                                        clock_info.clone(),
+                                        stake_history_info.clone(),
+                                    )?;
+                                    validator_stake_record.status.remove_transient_stake()?;
+                                } else if validator_stake_record.status.try_into()
+                                    == Ok(StakeStatus::Active)
+                                {
+                                    if stake.delegation.activation_epoch < clock.epoch {
+                                        if let Some(stake::state::StakeStateV2::Stake(
+                                            _,
+                                            validator_stake,
+                                            _,
+                                        )) = validator_stake_state
+                                        {
+                                            if validator_stake.delegation.activation_epoch
+                                                < clock.epoch
+                                            {
+                                                Self::stake_merge(
+                                                    stake_pool_info.key,
+                                                    transient_stake_info.clone(),
+                                                    withdraw_authority_info.clone(),
+                                                    AUTHORITY_WITHDRAW,
+                                                    stake_pool.stake_withdraw_bump_seed,
+                                                    validator_stake_info.clone(),
+                                                    clock_info.clone(),
+                                                    stake_history_info.clone(),
+                                                )?;
+                                            } else {
+                                                msg!("Stake activating or just active, not ready to merge");
+                                            }
+                                        } else {
+                                            msg!("Transient stake is activating or active, but validator stake is not, need to add the validator stake account on {} back into the stake pool", stake.delegation.voter_pubkey);
+                                        }
+                                    } else {
+                                        msg!("Transient stake not ready to be merged anywhere");
+                                    }
+                                } else if stake.delegation.deactivation_epoch == Epoch::MAX {
+                                    msg!("Transient stake is activating or active, deactivating.");
+                                    Self::stake_deactivate(
+                                        transient_stake_info.clone(),
+                                        clock_info.clone(),
+                                        withdraw_authority_info.clone(),
+                                        stake_pool_info.key,
+                                        AUTHORITY_WITHDRAW,
+                                        stake_pool.stake_withdraw_bump_seed,
+                                    )?;
+                                }
+                            }
+                            transient_stake_lamports = transient_stake_info.lamports();
+                        }
+                    }
+                    None
+                    | Some(stake::state::StakeStateV2::Uninitialized)
+                    | Some(stake::state::StakeStateV2::RewardsPool) => {} // do nothing
+                }
+            }
+            // Status for validator stake
+            //  * active -> do everything
+            //  * any other state / not a stake -> error state, but account for transient
+            //    stake
+            let validator_stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+                &validator_stake_info.data.borrow(),
+            )
+            .ok();
+            match validator_stake_state {
+                Some(stake::state::StakeStateV2::Stake(meta, stake, _))
+                    if stake_is_usable_by_pool(
+                        &meta,
+                        withdraw_authority_info.key,
+                        &stake_pool.lockup,
+                    ) =>
+                {
+                    // an active validator stake account only loses lamports
+                    // through instructions that also update its record, so
+                    // anything else missing since the last update was lost
+                    if validator_stake_record.status.try_into() == Ok(StakeStatus::Active) {
+                        let lost_lamports = u64::from(validator_stake_record.active_stake_lamports)
+                            .saturating_sub(validator_stake_info.lamports());
+                        if lost_lamports > 0 {
+                            msg!(
+                                "Validator stake account {} lost {} lamports",
+                                validator_stake_info.key,
+                                lost_lamports
+                            );
+                            loss_lamports = loss_lamports.saturating_add(lost_lamports);
+                            StakePoolEvent::ValidatorStakeLoss {
+                                stake_pool: *stake_pool_info.key,
+                                vote_account: validator_stake_record.vote_account_address,
+                                epoch: clock.epoch,
+                                lamports: lost_lamports,
+                            }
+                            .emit();
+                        }
+                    }
+                    let additional_lamports = validator_stake_info
+                        .lamports()
+                        .saturating_sub(stake.delegation.stake)
+                        .saturating_sub(meta.rent_exempt_reserve);
+                    // withdraw any extra lamports back to the reserve
+                    if additional_lamports > 0 {
+                        Self::stake_withdraw(
+                            stake_pool_info.key,
+                            validator_stake_info.clone(),
+                            withdraw_authority_info.clone(),
+                            AUTHORITY_WITHDRAW,
+                            stake_pool.stake_withdraw_bump_seed,
+                            reserve_stake_info.clone(),
+                            clock_info.clone(),
+                            stake_history_info.clone(),
+                            additional_lamports,
+                        )?;
+                    }
+                    match validator_stake_record.status.try_into()? {
+                        StakeStatus::Active => {
+                            active_stake_lamports = validator_stake_info.lamports();
+                        }
+                        StakeStatus::DeactivatingValidator | StakeStatus::DeactivatingAll => {
+                            if no_merge {
+                                active_stake_lamports = validator_stake_info.lamports();
+                            } else if stake_is_inactive_without_history(&stake, clock.epoch) {
+                                // Validator was removed through normal means.
+                                // Absorb the lamports into the reserve.
+                                Self::stake_merge(
+                                    stake_pool_info.key,
+                                    validator_stake_info.clone(),
+                                    withdraw_authority_info.clone(),
+                                    AUTHORITY_WITHDRAW,
+                                    stake_pool.stake_withdraw_bump_seed,
+                                    reserve_stake_info.clone(),
+                                    clock_info.clone(),
+                                    stake_history_info.clone(),
+                                )?;
+                                validator_stake_record.status.remove_validator_stake()?;
+                            } else {
+                                active_stake_lamports = validator_stake_info.lamports();
+                            }
+                        }
+                        StakeStatus::DeactivatingTransient | StakeStatus::ReadyForRemoval => {
+                            msg!("Validator stake account no longer part of the pool, ignoring");
+                        }
+                    }
+                }
+                Some(stake::state::StakeStateV2::Initialized(meta))
+                    if stake_is_usable_by_pool(
+                        &meta,
+                        withdraw_authority_info.key,
+                        &stake_pool.lockup,
+                    ) =>
+                {
+                    // If a validator stake is `Initialized`, the validator could
+                    // have been destaked during a cluster restart or removed through
+                    // normal means. Either way, absorb those lamports into the reserve.
+                    // The transient stake was likely absorbed into the reserve earlier.
+                    Self::stake_merge(
+                        stake_pool_info.key,
+                        validator_stake_info.clone(),
+                        withdraw_authority_info.clone(),
+                        AUTHORITY_WITHDRAW,
+                        stake_pool.stake_withdraw_bump_seed,
+                        reserve_stake_info.clone(),
+                        clock_info.clone(),
+                        stake_history_info.clone(),
+                    )?;
+                    if transient_stake_lamports != 0 {
+                        validator_stake_record.status = StakeStatus::DeactivatingTransient.into();
+                    } else {
+                        validator_stake_record.status = StakeStatus::ReadyForRemoval.into();
+                    }
+                }
+                Some(stake::state::StakeStateV2::Stake(_, _, _))
+                | Some(stake::state::StakeStateV2::Initialized(_))
+                | Some(stake::state::StakeStateV2::Uninitialized)
+                | Some(stake::state::StakeStateV2::RewardsPool)
+                | None => {
+                    msg!("Validator stake account no longer part of the pool, ignoring");
+                }
+            }
+
+            validator_stake_record.last_update_epoch = clock.epoch.into();
+            validator_stake_record.active_stake_lamports = active_stake_lamports.into();
+            validator_stake_record.transient_stake_lamports = transient_stake_lamports.into();
+        }
+
+        if loss_lamports > 0 {
+            // the validator list forgets the loss, so it must be recorded now
+            if !stake_pool_info.is_writable {
+                msg!("Stake pool must be writable to record stake losses");
+                return Err(ProgramError::InvalidArgument);
+            }
+            stake_pool.uncovered_loss_lamports = stake_pool
+                .uncovered_loss_lamports
+                .saturating_add(loss_lamports);
+            borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        }
+
+        Ok(())
+    }
+
+    /// Processes `UpdateStakePoolBalance` instruction.
+    #[inline(always)] // needed to optimize number of validators
+    fn process_update_stake_pool_balance(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
+        let (accounts, maybe_oracle_info) = split_oracle(program_id, accounts);
+        let (accounts, maybe_rate_history_info) = split_rate_history(program_id, accounts);
+        let (accounts, maybe_insurance_fund_infos) = split_insurance_fund(program_id, accounts);
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let clock = Clock::get()?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_mint(pool_mint_info)?;
+        stake_pool.check_authority_withdraw(withdraw_info.key, program_id, stake_pool_info.key)?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
+        if stake_pool.manager_fee_account != *manager_fee_info.key {
+            return Err(StakePoolError::InvalidFeeAccount.into());
+        }
+
+        if *validator_list_info.key != stake_pool.validator_list {
+            return Err(StakePoolError::InvalidValidatorStakeList.into());
+        }
+        if stake_pool.token_program_id != *token_program_info.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let previous_lamports = stake_pool.total_lamports;
+        let previous_pool_token_supply = stake_pool.pool_token_supply;
+        let reserve_stake = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+            &reserve_stake_info.data.borrow(),
+        )?;
+        let stake::state::StakeStateV2::Initialized(reserve_meta) = reserve_stake else {
+            msg!("Reserve stake account in unknown state, aborting");
+            return Err(StakePoolError::WrongStakeStake.into());
+        };
+        let mut total_lamports = reserve_stake_info
+            .lamports()
+            .checked_sub(minimum_reserve_lamports(&reserve_meta))
+            .ok_or(StakePoolError::CalculationFailure)?;
+        for validator_stake_record in validator_list
+            .deserialize_slice::<ValidatorStakeInfo>(0, validator_list.len() as usize)?
+        {
+            if u64::from(validator_stake_record.last_update_epoch) < clock.epoch {
+                return Err(StakePoolError::StakeListOutOfDate.into());
+            }
+            total_lamports = total_lamports
+                .checked_add(validator_stake_record.stake_lamports()?)
+                .ok_or(StakePoolError::CalculationFailure)?;
+        }
+        // lamports owed to withdrawal tickets were priced when the ticket was
+        // requested, so they do not share in rewards, and the insurance
+        // fund's pending share already left the pool
+        let total_lamports = total_lamports
+            .checked_sub(stake_pool.pending_withdrawal_lamports)
+            .and_then(|lamports| lamports.checked_sub(stake_pool.pending_insurance_lamports))
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let reward_lamports = total_lamports.saturating_sub(previous_lamports);
+
+        // The insurance fund's share of the epoch fee leaves the pool right
+        // away, and waits in the reserve until the insurance fund accounts
+        // are passed
+        let insurance_lamports = stake_pool
+            .calc_insurance_fee_lamports(reward_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let total_lamports = total_lamports
+            .checked_sub(insurance_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.pending_insurance_lamports = stake_pool
+            .pending_insurance_lamports
+            .checked_add(insurance_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        // Move the pending share to the insurance fund, as far as the reserve
+        // can spare it. The clock and stake history sysvars are only there
+        // for the stake program's withdraw, which checks them.
+        if let Some(insurance_fund_infos) = maybe_insurance_fund_infos {
+            let [insurance_fund_info, clock_info, stake_history_info, stake_program_info] =
+                insurance_fund_infos
+            else {
+                msg!("Expected the insurance fund account followed by the clock sysvar, stake history sysvar and stake program");
+                return Err(StakePoolError::InvalidInsuranceFund.into());
+            };
+            check_stake_program(stake_program_info.key)?;
+            let mut insurance_fund =
+                get_insurance_fund(program_id, stake_pool_info.key, insurance_fund_info)?;
+            let reserve_minimum = minimum_reserve_lamports(&reserve_meta)
+                .saturating_add(stake_pool.withdrawal_claim_pool_lamports);
+            let deposit_lamports = stake_pool.pending_insurance_lamports.min(
+                reserve_stake_info
+                    .lamports()
+                    .saturating_sub(reserve_minimum),
+            );
+            if deposit_lamports > 0 {
+                Self::stake_withdraw(
+                    stake_pool_info.key,
+                    reserve_stake_info.clone(),
+                    withdraw_info.clone(),
+                    AUTHORITY_WITHDRAW,
+                    stake_pool.stake_withdraw_bump_seed,
+                    insurance_fund_info.clone(),
+                    clock_info.clone(),
+                    stake_history_info.clone(),
+                    deposit_lamports,
+                )?;
+                stake_pool.pending_insurance_lamports = stake_pool
+                    .pending_insurance_lamports
+                    .checked_sub(deposit_lamports)
+                    .ok_or(StakePoolError::CalculationFailure)?;
+                insurance_fund.deposited_lamports = insurance_fund
+                    .deposited_lamports
+                    .checked_add(deposit_lamports)
+                    .ok_or(StakePoolError::CalculationFailure)?;
+                borsh::to_writer(
+                    &mut insurance_fund_info.data.borrow_mut()[..],
+                    &insurance_fund,
+                )?;
+                StakePoolEvent::InsuranceFundDeposited {
+                    stake_pool: *stake_pool_info.key,
+                    epoch: clock.epoch,
+                    lamports: deposit_lamports,
+                }
+                .emit();
+            }
+        }
+
+        // If the manager fee info is invalid, they don't deserve to receive the fee.
+        let fee = if stake_pool.check_manager_fee_info(manager_fee_info).is_ok() {
+            stake_pool
+                .calc_epoch_fee_amount_after_insurance(reward_lamports, insurance_lamports)
+                .ok_or(StakePoolError::CalculationFailure)?
+        } else {
+            0
+        };
+
+        let fee_payouts = fee_payouts(
+            stake_pool_info.key,
+            &stake_pool,
+            manager_fee_info,
+            maybe_fee_split_infos,
+            fee,
+        )?;
+        for (fee_info, fee) in fee_payouts {
+            if fee > 0 {
+                Self::token_mint_to(
+                    stake_pool_info.key,
+                    token_program_info.clone(),
+                    pool_mint_info.clone(),
+                    fee_info.clone(),
+                    withdraw_info.clone(),
+                    AUTHORITY_WITHDRAW,
+                    stake_pool.stake_withdraw_bump_seed,
+                    fee,
+                )?;
+            }
+        }
+
+        if stake_pool.last_update_epoch < clock.epoch {
+            stake_pool.apply_pending_fees(clock.epoch);
+
+            stake_pool.last_update_epoch = clock.epoch;
+            stake_pool.last_epoch_total_lamports = previous_lamports;
+            stake_pool.last_epoch_pool_token_supply = previous_pool_token_supply;
+        }
+        stake_pool.total_lamports = total_lamports;
+
+        let pool_mint_data = pool_mint_info.try_borrow_data()?;
+        let pool_mint = StateWithExtensions::<Mint>::unpack(&pool_mint_data)?;
+        stake_pool.pool_token_supply = pool_mint.base.supply;
+
+        // Recording the exchange rate is best-effort, an update without the
+        // rate history account only leaves a gap in the history
+        if let Some(rate_history_info) = maybe_rate_history_info {
+            check_rate_history(program_id, stake_pool_info.key, rate_history_info)?;
+            RateHistory::record(
+                &mut rate_history_info.try_borrow_mut_data()?,
+                RateHistoryEntry {
+                    epoch: clock.epoch,
+                    unix_timestamp: clock.unix_timestamp,
+                    total_lamports: stake_pool.total_lamports,
+                    pool_token_supply: stake_pool.pool_token_supply,
+                    fee_pool_tokens: fee,
+                },
+            )?;
+        }
+
+        // Refreshing the price oracle is optional, without the account it
+        // keeps the previous rate and reads as stale until the next refresh
+        if let Some(oracle_info) = maybe_oracle_info {
+            let mut oracle = get_oracle(program_id, stake_pool_info.key, oracle_info)?;
+            oracle.exchange_rate = ExchangeRate::from(&stake_pool);
+            oracle.last_update_slot = clock.slot;
+            borsh::to_writer(&mut oracle_info.data.borrow_mut()[..], &oracle)?;
+        }
+
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+
+        StakePoolEvent::UpdateStakePoolBalance {
+            stake_pool: *stake_pool_info.key,
+            epoch: clock.epoch,
+            total_lamports: stake_pool.total_lamports,
+            pool_token_supply: stake_pool.pool_token_supply,
+            epoch_fee_pool_tokens: fee,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    /// Processes the `CleanupRemovedValidatorEntries` instruction
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_cleanup_removed_validator_entries(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_validator_list(validator_list_info)?;
+
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, mut validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        validator_list.retain::<ValidatorStakeInfo, _>(|x| !ValidatorStakeInfo::is_removed(x))?;
+
+        if stake_pool_info.is_writable {
+            msg!("Checking preferred validators");
+            let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+
+            // Check and reset preferred validators if they don't exist or aren't active
+            // Check preferred deposit validator
+            if let Some(preferred_deposit) = stake_pool.preferred_deposit_validator_vote_address {
+                let maybe_validator = validator_list.find::<ValidatorStakeInfo, _>(|x| {
+                    ValidatorStakeInfo::memcmp_pubkey(x, &preferred_deposit)
+                });
+
+                let should_reset = match maybe_validator {
+                    Some(validator) => {
+                        // Check if validator status is not Active
+                        match validator.status.try_into() {
+                            Ok(StakeStatus::Active) => false, // Valid, keep it
+                            _ => true,                        // Not active, reset it
+                        }
+                    }
+                    None => true, // Not found in list, reset it
+                };
+
+                if should_reset {
+                    msg!(
+                        "Preferred deposit validator {} not found or not active, resetting",
+                        preferred_deposit
+                    );
+                    stake_pool.preferred_deposit_validator_vote_address = None;
+                }
+            }
+
+            // Check preferred withdrawal validator
+            if let Some(preferred_withdraw) = stake_pool.preferred_withdraw_validator_vote_address {
+                let maybe_validator = validator_list.find::<ValidatorStakeInfo, _>(|x| {
+                    ValidatorStakeInfo::memcmp_pubkey(x, &preferred_withdraw)
+                });
+
+                let should_reset = match maybe_validator {
+                    Some(validator) => {
+                        // Check if validator status is not Active
+                        match validator.status.try_into() {
+                            Ok(StakeStatus::Active) => false, // Valid, keep it
+                            _ => true,                        // Not active, reset it
+                        }
+                    }
+                    None => true, // Not found in list, reset it
+                };
+
+                if should_reset {
+                    msg!(
+                        "Preferred withdrawal validator {} not found or not active, resetting",
+                        preferred_withdraw
+                    );
+                    stake_pool.preferred_withdraw_validator_vote_address = None;
+                }
+            }
+
+            // Save the updated stake pool state
+            borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        }
+
+        Ok(())
+    }
+
+    /// Processes [`DepositStake`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_deposit_stake(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        minimum_pool_tokens_out: Option<u64>,
+        user_stake_seed: Option<u64>,
+    ) -> ProgramResult {
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
+        let (accounts, maybe_referrer_info) = split_referrer(program_id, accounts);
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let stake_deposit_authority_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let stake_info = next_account_info(account_info_iter)?;
+        let validator_stake_account_info = next_account_info(account_info_iter)?;
+        let reserve_stake_account_info = next_account_info(account_info_iter)?;
+        let dest_user_pool_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let referrer_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+        let maybe_signer_or_session_info = user_stake_seed
+            .map(|_| next_account_info(account_info_iter))
+            .transpose()?;
+
+        check_stake_program(stake_program_info.key)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_not_paused(PausedOperation::Deposit)?;
+        if user_stake_seed.is_some() {
+            stake_pool.check_not_paused(PausedOperation::Session)?;
+        }
+
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_stake_deposit_authority(stake_deposit_authority_info.key)?;
+        stake_pool.check_mint(pool_mint_info)?;
+        stake_pool.check_validator_list(validator_list_info)?;
+        stake_pool.check_reserve_stake(reserve_stake_account_info)?;
+
+        if stake_pool.token_program_id != *token_program_info.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if stake_pool.manager_fee_account != *manager_fee_info.key {
+            return Err(StakePoolError::InvalidFeeAccount.into());
+        }
+        // There is no bypass if the manager fee account is invalid. Deposits
+        // don't hold user funds hostage, so if the fee account is invalid, users
+        // cannot deposit in the pool.  Let it fail here!
+
+        if stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        }
+
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, mut validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let (_, validator_stake) = get_stake_state(validator_stake_account_info)?;
+        let pre_all_validator_lamports = validator_stake_account_info.lamports();
+        let vote_account_address = validator_stake.delegation.voter_pubkey;
+        if let Some(preferred_deposit) = stake_pool.preferred_deposit_validator_vote_address {
+            if preferred_deposit != vote_account_address {
+                msg!(
+                    "Incorrect deposit address, expected {}, received {}",
+                    preferred_deposit,
+                    vote_account_address
+                );
+                return Err(StakePoolError::IncorrectDepositVoteAddress.into());
+            }
+        }
+
+        let validator_stake_info = validator_list
+            .find_mut::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, &vote_account_address)
+            })
+            .ok_or(StakePoolError::ValidatorNotFound)?;
+        check_validator_stake_address(
+            program_id,
+            stake_pool_info.key,
+            validator_stake_account_info.key,
+            &vote_account_address,
+            NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+        )?;
+
+        if validator_stake_info.status != StakeStatus::Active.into() {
+            msg!("Validator is marked for removal and no longer accepting deposits");
+            return Err(StakePoolError::ValidatorNotFound.into());
+        }
+
+        msg!("Stake pre merge {}", validator_stake.delegation.stake);
+
+        let (stake_deposit_authority_program_address, deposit_bump_seed) =
+            find_deposit_authority_program_address(program_id, stake_pool_info.key);
+        if let (Some(signer_or_session_info), Some(user_stake_seed)) =
+            (maybe_signer_or_session_info, user_stake_seed)
+        {
+            // a custom deposit authority must still approve the deposit
+            if *stake_deposit_authority_info.key != stake_deposit_authority_program_address
+                && !stake_deposit_authority_info.is_signer
+            {
+                msg!("Stake pool deposit authority must sign the session deposit");
+                return Err(StakePoolError::SignatureMissing.into());
+            }
+            Self::stake_authorize_user_stake_with_session(
+                program_id,
+                signer_or_session_info,
+                stake_info.clone(),
+                user_stake_seed,
+                dest_user_pool_info,
+                pool_mint_info,
+                token_program_info,
+                withdraw_authority_info.key,
+                clock_info.clone(),
+            )?;
+        } else if *stake_deposit_authority_info.key == stake_deposit_authority_program_address {
+            Self::stake_authorize_signed(
+                stake_pool_info.key,
+                stake_info.clone(),
+                stake_deposit_authority_info.clone(),
+                AUTHORITY_DEPOSIT,
+                deposit_bump_seed,
+                withdraw_authority_info.key,
+                clock_info.clone(),
+            )?;
+        } else {
+            Self::stake_authorize(
+                stake_info.clone(),
+                stake_deposit_authority_info.clone(),
+                withdraw_authority_info.key,
+                clock_info.clone(),
+            )?;
+        }
+
+        Self::stake_merge(
+            stake_pool_info.key,
+            stake_info.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            validator_stake_account_info.clone(),
+            clock_info.clone(),
+            stake_history_info.clone(),
+        )?;
+
+        let (_, post_validator_stake) = get_stake_state(validator_stake_account_info)?;
+        let post_all_validator_lamports = validator_stake_account_info.lamports();
+        msg!("Stake post merge {}", post_validator_stake.delegation.stake);
+
+        let total_deposit_lamports = post_all_validator_lamports
+            .checked_sub(pre_all_validator_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let stake_deposit_lamports = post_validator_stake
+            .delegation
+            .stake
+            .checked_sub(validator_stake.delegation.stake)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let sol_deposit_lamports = total_deposit_lamports
+            .checked_sub(stake_deposit_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let new_pool_tokens = stake_pool
+            .calc_pool_tokens_for_deposit(total_deposit_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let new_pool_tokens_from_stake = stake_pool
+            .calc_pool_tokens_for_deposit(stake_deposit_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let new_pool_tokens_from_sol = new_pool_tokens
+            .checked_sub(new_pool_tokens_from_stake)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let stake_deposit_fee = stake_pool
+            .calc_pool_tokens_stake_deposit_fee(new_pool_tokens_from_stake)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let sol_deposit_fee = stake_pool
+            .calc_pool_tokens_sol_deposit_fee(new_pool_tokens_from_sol)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let total_fee = stake_deposit_fee
+            .checked_add(sol_deposit_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let pool_tokens_user = new_pool_tokens
+            .checked_sub(total_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let pool_tokens_referral_fee = stake_pool
+            .calc_pool_tokens_stake_referral_fee(total_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let pool_tokens_manager_deposit_fee = total_fee
+            .checked_sub(pool_tokens_referral_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        if pool_tokens_user
+            .saturating_add(pool_tokens_manager_deposit_fee)
+            .saturating_add(pool_tokens_referral_fee)
+            != new_pool_tokens
+        {
+            return Err(StakePoolError::CalculationFailure.into());
+        }
+
+        if pool_tokens_user == 0 {
+            return Err(StakePoolError::DepositTooSmall.into());
+        }
+
+        if let Some(minimum_pool_tokens_out) = minimum_pool_tokens_out {
+            if pool_tokens_user < minimum_pool_tokens_out {
+                return Err(StakePoolError::ExceededSlippage.into());
+            }
+        }
+
+        stake_pool.record_deposit(clock.epoch, total_deposit_lamports)?;
+        if let Some(referrer_info) = maybe_referrer_info {
+            Self::record_referral(
+                stake_pool_info.key,
+                referrer_info,
+                referrer_fee_info,
+                total_deposit_lamports,
+                pool_tokens_referral_fee,
+            )?;
+        }
+
+        Self::token_mint_to(
+            stake_pool_info.key,
+            token_program_info.clone(),
+            pool_mint_info.clone(),
+            dest_user_pool_info.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            pool_tokens_user,
+        )?;
+        let fee_payouts = fee_payouts(
+            stake_pool_info.key,
+            &stake_pool,
+            manager_fee_info,
+            maybe_fee_split_infos,
+            pool_tokens_manager_deposit_fee,
+        )?;
+        for (fee_info, fee) in fee_payouts {
+            if fee > 0 {
+                Self::token_mint_to(
+                    stake_pool_info.key,
+                    token_program_info.clone(),
+                    pool_mint_info.clone(),
+                    fee_info.clone(),
+                    withdraw_authority_info.clone(),
+                    AUTHORITY_WITHDRAW,
+                    stake_pool.stake_withdraw_bump_seed,
+                    fee,
+                )?;
+            }
+        }
+        if pool_tokens_referral_fee > 0 {
+            Self::token_mint_to(
+                stake_pool_info.key,
+                token_program_info.clone(),
+                pool_mint_info.clone(),
+                referrer_fee_info.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                pool_tokens_referral_fee,
+            )?;
+        }
+
+        // withdraw additional lamports to the reserve
+        if sol_deposit_lamports > 0 {
+            Self::stake_withdraw(
+                stake_pool_info.key,
+                validator_stake_account_info.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                reserve_stake_account_info.clone(),
+                clock_info.clone(),
+                stake_history_info.clone(),
+                sol_deposit_lamports,
+            )?;
+        }
+
+        stake_pool.pool_token_supply = stake_pool
+            .pool_token_supply
+            .checked_add(new_pool_tokens)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        // We treat the extra lamports as though they were
+        // transferred directly to the reserve stake account.
+        stake_pool.total_lamports = stake_pool
+            .total_lamports
+            .checked_add(total_deposit_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+
+        validator_stake_info.active_stake_lamports = validator_stake_account_info.lamports().into();
+        stake_pool
+            .check_validator_share(validator_stake_info, validator_stake_info.stake_lamports()?)?;
+
+        StakePoolEvent::DepositStake {
+            stake_pool: *stake_pool_info.key,
+            vote_account: validator_stake_info.vote_account_address,
+            lamports: total_deposit_lamports,
+            pool_tokens: pool_tokens_user,
+            manager_fee_pool_tokens: pool_tokens_manager_deposit_fee,
+            referral_fee_pool_tokens: pool_tokens_referral_fee,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    /// Hands a user stake PDA over to the pool withdraw authority on behalf
+    /// of the session user, who must also own the pool token destination ATA
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)] // needed to avoid stack size violation
+    fn stake_authorize_user_stake_with_session<'a>(
+        program_id: &Pubkey,
+        signer_or_session_info: &AccountInfo<'a>,
+        stake_info: AccountInfo<'a>,
+        user_stake_seed: u64,
+        dest_user_pool_info: &AccountInfo<'a>,
+        pool_mint_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        new_stake_authority: &Pubkey,
+        clock_info: AccountInfo<'a>,
+    ) -> Result<(), ProgramError> {
+        use crate::USER_STAKE_SEED_PREFIX;
+        use fogo_sessions_sdk::session::Session;
+
+        let user_pubkey =
+            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+
+        let expected_ata =
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                &user_pubkey,
+                pool_mint_info.key,
+                token_program_info.key,
+            );
+        if *dest_user_pool_info.key != expected_ata {
+            msg!("`pool_tokens_to` is not the pool token ATA of the session user");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (expected_stake_pda, stake_pda_bump) = Pubkey::find_program_address(
+            &[
+                USER_STAKE_SEED_PREFIX,
+                user_pubkey.as_ref(),
+                &user_stake_seed.to_le_bytes(),
+            ],
+            program_id,
+        );
+        if expected_stake_pda != *stake_info.key {
+            msg!(
+                "Stake account is not a user stake PDA of the session user, wallet-owned stake \
+                 must be deposited with DepositStakeWithSlippage"
+            );
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let stake_pda_seeds: &[&[u8]] = &[
+            USER_STAKE_SEED_PREFIX,
+            user_pubkey.as_ref(),
+            &user_stake_seed.to_le_bytes(),
+            &[stake_pda_bump],
+        ];
+
+        // The PDA is its own stake authority
+        for stake_authorize in [
+            stake::state::StakeAuthorize::Staker,
+            stake::state::StakeAuthorize::Withdrawer,
+        ] {
+            invoke_signed(
+                &stake::instruction::authorize(
+                    stake_info.key,
+                    stake_info.key,
+                    new_stake_authority,
+                    stake_authorize,
+                    None,
+                ),
+                &[stake_info.clone(), clock_info.clone(), stake_info.clone()],
+                &[stake_pda_seeds],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Checks the referrer of a deposit and records the deposit in its stats.
+    /// The referral fee must be paid out to the referrer's payout account.
+    #[inline(never)] // needed to avoid stack size violation
+    fn record_referral(
+        stake_pool_address: &Pubkey,
+        referrer_info: &AccountInfo,
+        referrer_fee_info: &AccountInfo,
+        deposit_lamports: u64,
+        pool_tokens_referral_fee: u64,
+    ) -> ProgramResult {
+        let mut referrer = try_from_slice_unchecked::<Referrer>(&referrer_info.data.borrow())?;
+        if !referrer.is_valid()
+            || referrer.stake_pool != *stake_pool_address
+            || referrer.payout_token_account != *referrer_fee_info.key
+        {
+            msg!(
+                "Referrer {} does not belong to the stake pool or does not pay out to {}",
+                referrer_info.key,
+                referrer_fee_info.key
+            );
+            return Err(StakePoolError::InvalidReferrer.into());
+        }
+        referrer.record_deposit(deposit_lamports, pool_tokens_referral_fee)?;
+        borsh::to_writer(&mut referrer_info.data.borrow_mut()[..], &referrer)?;
+        Ok(())
+    }
+
+    /// Checks that `operation` is not paused, for handlers that do not
+    /// otherwise deserialize the stake pool before invoking other programs
+    #[inline(never)] // needed to avoid stack size violation
+    fn check_stake_pool_not_paused(
+        program_id: &Pubkey,
+        stake_pool_info: &AccountInfo,
+        operation: PausedOperation,
+    ) -> ProgramResult {
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_not_paused(operation)
+    }
+
+    /// Processes [`DepositWsolWithSession`](enum.Instruction.html).
+    #[inline(never)]
+    fn process_deposit_wsol_with_session(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_lamports: u64,
+        minimum_pool_tokens_out: Option<u64>,
+    ) -> ProgramResult {
+        use fogo_sessions_sdk::token::instruction::transfer_checked;
+        use fogo_sessions_sdk::{session::Session, token::PROGRAM_SIGNER_SEED};
+        use solana_program::program_pack::Pack;
+        use spl_associated_token_account::tools::account::create_pda_account;
+
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
+        let (accounts, maybe_referrer_info) = split_referrer(program_id, accounts);
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let reserve_stake_account_info = next_account_info(account_info_iter)?;
+        let signer_or_session_info = next_account_info(account_info_iter)?;
+        let dest_user_pool_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let referrer_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+
+        // wsol-specific accounts
+        let wsol_mint_info = next_account_info(account_info_iter)?;
+        let wsol_token_info = next_account_info(account_info_iter)?;
+        let wsol_transient_info = next_account_info(account_info_iter)?;
+        let program_signer_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let user_wallet_info = next_account_info(account_info_iter)?;
+
+        let sol_deposit_authority_info = next_account_info(account_info_iter);
+
+        Self::check_stake_pool_not_paused(program_id, stake_pool_info, PausedOperation::Session)?;
+
+        if *wsol_mint_info.key != spl_token::native_mint::id() {
+            msg!("`wsol_mint` is not the native SOL mint");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if *wsol_mint_info.owner != *token_program_info.key {
+            msg!("`wsol_mint` is not owned by the token program");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if *wsol_token_info.owner != *token_program_info.key {
+            msg!("`wsol_token` is not owned by the token program");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let user_pubkey =
+            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+
+        // Verify wsol_token_info is a valid wSOL token account owned by the user
+        let wsol_token_data = spl_token::state::Account::unpack(&wsol_token_info.data.borrow())?;
+        if wsol_token_data.mint != spl_token::native_mint::id() {
+            msg!("`wsol_token` is not a wSOL token account");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if wsol_token_data.owner != user_pubkey {
+            msg!("`wsol_token` is not owned by the session user");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        const TRANSIENT_SEED: &[u8] = b"transient_wsol";
+
+        let (expected_program_signer, program_signer_bump) =
+            Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
+
+        if *program_signer_info.key != expected_program_signer {
+            msg!("`program_signer` does not match expected address");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let (expected_transient_pda, transient_bump) =
+            Pubkey::find_program_address(&[TRANSIENT_SEED, user_pubkey.as_ref()], program_id);
+
+        if *wsol_transient_info.key != expected_transient_pda {
+            msg!("`wsol_transient` does not match expected address");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
+        let transient_seeds: &[&[u8]] = &[TRANSIENT_SEED, user_pubkey.as_ref(), &[transient_bump]];
+
+        let rent = Rent::get()?;
+
+        // Create the transient wSOL account
+        create_pda_account(
+            payer_info,
+            &rent,
+            spl_token::state::Account::LEN,
+            token_program_info.key,
+            system_program_info,
+            wsol_transient_info,
+            transient_seeds,
+        )?;
+
+        invoke(
+            &spl_token::instruction::initialize_account3(
+                token_program_info.key,
+                wsol_transient_info.key,
+                wsol_mint_info.key,
+                program_signer_info.key,
+            )?,
+            &[wsol_transient_info.clone(), wsol_mint_info.clone()],
+        )?;
+
+        // Transfer wSOL from user to transient account and unwrap to SOL
+        invoke_signed(
+            &transfer_checked(
+                token_program_info.key,
+                wsol_token_info.key,
+                wsol_mint_info.key,
+                wsol_transient_info.key,
+                signer_or_session_info.key,
+                Some(program_signer_info.key),
+                deposit_lamports,
+                native_mint::DECIMALS,
+            )?,
+            &[
+                token_program_info.clone(),
+                wsol_token_info.clone(),
+                wsol_mint_info.clone(),
+                wsol_transient_info.clone(),
+                signer_or_session_info.clone(),
+                program_signer_info.clone(),
+            ],
+            &[program_signer_seeds],
+        )?;
+
+        // Close the transient wSOL account to unwrap to SOL
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                token_program_info.key,
+                wsol_transient_info.key,
+                program_signer_info.key,
+                program_signer_info.key,
+                &[],
+            )?,
+            &[wsol_transient_info.clone(), program_signer_info.clone()],
+            &[program_signer_seeds],
+        )?;
+
+        // Refund transient wSOL rent to payer
+        let rent_lamports = rent.minimum_balance(spl_token::state::Account::LEN).max(1);
+
+        invoke_signed(
+            &system_instruction::transfer(program_signer_info.key, payer_info.key, rent_lamports),
+            &[
+                system_program_info.clone(),
+                program_signer_info.clone(),
+                payer_info.clone(),
+            ],
+            &[program_signer_seeds],
+        )?;
+
+        // Verify dest_user_pool_info is the correct ATA for user_wallet
+        let expected_ata =
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                user_wallet_info.key,
+                pool_mint_info.key,
+                token_program_info.key,
+            );
+        if *dest_user_pool_info.key != expected_ata {
+            msg!("dest_user_pool is not the ATA for user_wallet");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let ata_creation_cost = rent
+            .minimum_balance(spl_token::state::Account::LEN)
+            .saturating_sub(dest_user_pool_info.lamports());
+
+        if deposit_lamports < ata_creation_cost {
+            msg!("Deposit too small to cover ATA rent");
+            return Err(StakePoolError::DepositTooSmall.into());
+        }
+
+        if ata_creation_cost > 0 {
+            invoke_signed(
+                &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    program_signer_info.key,
+                    user_wallet_info.key,
+                    pool_mint_info.key,
+                    token_program_info.key,
+                ),
+                &[
+                    program_signer_info.clone(),
+                    dest_user_pool_info.clone(),
+                    user_wallet_info.clone(),
+                    pool_mint_info.clone(),
+                    system_program_info.clone(),
+                    token_program_info.clone(),
+                ],
+                &[program_signer_seeds],
+            )?;
+        }
+
+        let effective_deposit = deposit_lamports - ata_creation_cost;
+
+        invoke_signed(
+            &system_instruction::transfer(
+                program_signer_info.key,
+                reserve_stake_account_info.key,
+                effective_deposit,
+            ),
+            &[
+                system_program_info.clone(),
+                program_signer_info.clone(),
+                reserve_stake_account_info.clone(),
+            ],
+            &[program_signer_seeds],
+        )?;
+
+        let mut new_accounts = vec![
+            stake_pool_info.clone(),
+            withdraw_authority_info.clone(),
+            reserve_stake_account_info.clone(),
+            signer_or_session_info.clone(),
+            dest_user_pool_info.clone(),
+            manager_fee_info.clone(),
+            referrer_fee_info.clone(),
+            pool_mint_info.clone(),
+            system_program_info.clone(),
+            token_program_info.clone(),
+        ];
+
+        if let Ok(sol_deposit_authority_info) = sol_deposit_authority_info {
+            new_accounts.push(sol_deposit_authority_info.clone());
+        }
+        if let Some(referrer_info) = maybe_referrer_info {
+            new_accounts.push(referrer_info.clone());
+        }
+        if let Some(fee_split_infos) = maybe_fee_split_infos {
+            new_accounts.extend_from_slice(fee_split_infos);
+        }
+
+        Self::process_deposit_sol(
+            program_id,
+            &new_accounts,
+            effective_deposit,
+            minimum_pool_tokens_out,
+            // the SOL transfer has already been done above
+            true,
+        )
+    }
+
+    /// Processes [`DepositSol`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_deposit_sol(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_lamports: u64,
+        minimum_pool_tokens_out: Option<u64>,
+        is_wsol_path: bool,
+    ) -> ProgramResult {
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
+        let (accounts, maybe_referrer_info) = split_referrer(program_id, accounts);
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let reserve_stake_account_info = next_account_info(account_info_iter)?;
+        let from_user_lamports_info = next_account_info(account_info_iter)?;
+        let dest_user_pool_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let referrer_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let sol_deposit_authority_info = next_account_info(account_info_iter);
+
+        let clock = Clock::get()?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_not_paused(PausedOperation::Deposit)?;
+
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_sol_deposit_authority(sol_deposit_authority_info)?;
+        stake_pool.check_mint(pool_mint_info)?;
+        stake_pool.check_reserve_stake(reserve_stake_account_info)?;
+
+        if stake_pool.token_program_id != *token_program_info.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        check_system_program(system_program_info.key)?;
+
+        if stake_pool.manager_fee_account != *manager_fee_info.key {
+            return Err(StakePoolError::InvalidFeeAccount.into());
+        }
+        // There is no bypass if the manager fee account is invalid. Deposits
+        // don't hold user funds hostage, so if the fee account is invalid, users
+        // cannot deposit in the pool.  Let it fail here!
+
+        // We want this to hold to ensure that deposit_sol mints pool tokens
+        // at the right price
+        if stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        }
+
+        let new_pool_tokens = stake_pool
+            .calc_pool_tokens_for_deposit(deposit_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let pool_tokens_sol_deposit_fee = stake_pool
+            .calc_pool_tokens_sol_deposit_fee(new_pool_tokens)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let pool_tokens_user = new_pool_tokens
+            .checked_sub(pool_tokens_sol_deposit_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        let pool_tokens_referral_fee = stake_pool
+            .calc_pool_tokens_sol_referral_fee(pool_tokens_sol_deposit_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let pool_tokens_manager_deposit_fee = pool_tokens_sol_deposit_fee
+            .checked_sub(pool_tokens_referral_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        if pool_tokens_user
+            .saturating_add(pool_tokens_manager_deposit_fee)
+            .saturating_add(pool_tokens_referral_fee)
+            != new_pool_tokens
+        {
+            return Err(StakePoolError::CalculationFailure.into());
+        }
+
+        if pool_tokens_user == 0 {
+            return Err(StakePoolError::DepositTooSmall.into());
+        }
+
+        if let Some(minimum_pool_tokens_out) = minimum_pool_tokens_out {
+            if pool_tokens_user < minimum_pool_tokens_out {
+                return Err(StakePoolError::ExceededSlippage.into());
+            }
+        }
+
+        stake_pool.record_deposit(clock.epoch, deposit_lamports)?;
+        if let Some(referrer_info) = maybe_referrer_info {
+            Self::record_referral(
+                stake_pool_info.key,
+                referrer_info,
+                referrer_fee_info,
+                deposit_lamports,
+                pool_tokens_referral_fee,
+            )?;
+        }
+
+        if !is_wsol_path {
+            Self::sol_transfer(
+                from_user_lamports_info.clone(),
+                reserve_stake_account_info.clone(),
+                deposit_lamports,
+            )?;
+        }
+
+        Self::token_mint_to(
+            stake_pool_info.key,
+            token_program_info.clone(),
+            pool_mint_info.clone(),
+            dest_user_pool_info.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            pool_tokens_user,
+        )?;
+
+        let fee_payouts = fee_payouts(
+            stake_pool_info.key,
+            &stake_pool,
+            manager_fee_info,
+            maybe_fee_split_infos,
+            pool_tokens_manager_deposit_fee,
+        )?;
+        for (fee_info, fee) in fee_payouts {
+            if fee > 0 {
+                Self::token_mint_to(
+                    stake_pool_info.key,
+                    token_program_info.clone(),
+                    pool_mint_info.clone(),
+                    fee_info.clone(),
+                    withdraw_authority_info.clone(),
+                    AUTHORITY_WITHDRAW,
+                    stake_pool.stake_withdraw_bump_seed,
+                    fee,
+                )?;
+            }
+        }
+
+        if pool_tokens_referral_fee > 0 {
+            Self::token_mint_to(
+                stake_pool_info.key,
+                token_program_info.clone(),
+                pool_mint_info.clone(),
+                referrer_fee_info.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                pool_tokens_referral_fee,
+            )?;
+        }
+
+        stake_pool.pool_token_supply = stake_pool
+            .pool_token_supply
+            .checked_add(new_pool_tokens)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.total_lamports = stake_pool
+            .total_lamports
+            .checked_add(deposit_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+
+        StakePoolEvent::DepositSol {
+            stake_pool: *stake_pool_info.key,
+            lamports: deposit_lamports,
+            pool_tokens: pool_tokens_user,
+            manager_fee_pool_tokens: pool_tokens_manager_deposit_fee,
+            referral_fee_pool_tokens: pool_tokens_referral_fee,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    /// Processes [`DepositSolToValidator`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_deposit_sol_to_validator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_lamports: u64,
+        minimum_pool_tokens_out: u64,
+    ) -> ProgramResult {
+        use spl_associated_token_account::tools::account::create_pda_account;
+
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let direct_stake_info = next_account_info(account_info_iter)?;
+        let validator_vote_account_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let reserve_stake_account_info = next_account_info(account_info_iter)?;
+        let from_user_lamports_info = next_account_info(account_info_iter)?;
+        let dest_user_pool_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let referrer_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        // the optional SOL deposit authority, referrer and fee split accounts
+        // follow as in `DepositSolWithSlippage`
+        let optional_infos = account_info_iter.as_slice();
+
+        check_system_program(system_program_info.key)?;
+
+        let mut deposit_accounts = vec![
+            stake_pool_info.clone(),
+            withdraw_authority_info.clone(),
+            reserve_stake_account_info.clone(),
+            from_user_lamports_info.clone(),
+            dest_user_pool_info.clone(),
+            manager_fee_info.clone(),
+            referrer_fee_info.clone(),
+            pool_mint_info.clone(),
+            system_program_info.clone(),
+            token_program_info.clone(),
+        ];
+        deposit_accounts.extend_from_slice(optional_infos);
+        Self::process_deposit_sol(
+            program_id,
+            &deposit_accounts,
+            deposit_lamports,
+            Some(minimum_pool_tokens_out),
+            false,
+        )?;
+
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        stake_pool.check_validator_list(validator_list_info)?;
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let vote_account_address = validator_vote_account_info.key;
+        let validator_stake_info = validator_list
+            .find::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, vote_account_address)
+            })
+            .ok_or_else(|| {
+                msg!(
+                    "Vote account {} not found in stake pool",
+                    vote_account_address
+                );
+                StakePoolError::ValidatorNotFound
+            })?;
+        if validator_stake_info.status != StakeStatus::Active.into() {
+            msg!("Validator is marked for removal and no longer accepts deposits");
+            return Err(StakePoolError::ValidatorNotFound.into());
+        }
+
+        let (direct_stake_address, direct_stake_bump) = find_direct_stake_program_address(
+            program_id,
+            stake_pool_info.key,
+            vote_account_address,
+        );
+        if direct_stake_address != *direct_stake_info.key {
+            msg!("Invalid direct stake PDA");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let mut direct_stake = if direct_stake_info.owner == program_id {
+            let direct_stake =
+                try_from_slice_unchecked::<DirectStake>(&direct_stake_info.data.borrow())?;
+            if !direct_stake.is_valid() {
+                return Err(StakePoolError::InvalidDirectStake.into());
+            }
+            direct_stake
+        } else {
+            let direct_stake_signer_seeds: &[&[u8]] = &[
+                DIRECT_STAKE_SEED_PREFIX,
+                stake_pool_info.key.as_ref(),
+                vote_account_address.as_ref(),
+                &[direct_stake_bump],
+            ];
+            create_pda_account(
+                from_user_lamports_info,
+                &Rent::get()?,
+                get_packed_len::<DirectStake>(),
+                program_id,
+                system_program_info,
+                direct_stake_info,
+                direct_stake_signer_seeds,
+            )?;
+            DirectStake {
+                account_type: AccountType::DirectStake,
+                stake_pool: *stake_pool_info.key,
+                vote_account: *vote_account_address,
+                pending_lamports: 0,
+                deposited_lamports: 0,
+            }
+        };
+
+        direct_stake.record_deposit(deposit_lamports)?;
+        let validator_lamports = validator_stake_info
+            .stake_lamports()?
+            .checked_add(direct_stake.pending_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.check_direct_stake_share(validator_lamports)?;
+        // pending direct stake the staker could never delegate is refused
+        stake_pool.check_validator_share(validator_stake_info, validator_lamports)?;
+        borsh::to_writer(&mut direct_stake_info.data.borrow_mut()[..], &direct_stake)?;
+
+        StakePoolEvent::DirectStakeDeposited {
+            stake_pool: *stake_pool_info.key,
+            vote_account: *vote_account_address,
+            lamports: deposit_lamports,
+            pending_lamports: direct_stake.pending_lamports,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    /// Processes [`WithdrawStake`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_withdraw_stake(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        pool_tokens: u64,
+        minimum_lamports_out: Option<u64>,
+        user_stake_seed: Option<u64>,
+    ) -> ProgramResult {
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let stake_split_from = next_account_info(account_info_iter)?;
+        let stake_split_to = next_account_info(account_info_iter)?;
+        let user_stake_authority_info = next_account_info(account_info_iter)?;
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let burn_from_pool_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+
+        check_stake_program(stake_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_not_paused(PausedOperation::Withdrawal)?;
+        if user_stake_seed.is_some() {
+            stake_pool.check_not_paused(PausedOperation::Session)?;
+        }
+
+        let decimals = stake_pool.check_mint(pool_mint_info)?;
+        stake_pool.check_validator_list(validator_list_info)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+
+        if stake_pool.manager_fee_account != *manager_fee_info.key {
+            return Err(StakePoolError::InvalidFeeAccount.into());
+        }
+        if stake_pool.token_program_id != *token_program_info.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        }
+
+        check_account_owner(validator_list_info, program_id)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, mut validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        // To prevent a faulty manager fee account from preventing withdrawals
+        // if the token program does not own the account, or if the account is not
+        // initialized
+        let pool_tokens_fee = if stake_pool.manager_fee_account == *burn_from_pool_info.key
+            || stake_pool.check_manager_fee_info(manager_fee_info).is_err()
+        {
+            0
+        } else {
+            stake_pool
+                .calc_pool_tokens_stake_withdrawal_fee(pool_tokens)
+                .ok_or(StakePoolError::CalculationFailure)?
+        };
+        let pool_tokens_burnt = pool_tokens
+            .checked_sub(pool_tokens_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let fee_payouts = fee_payouts(
+            stake_pool_info.key,
+            &stake_pool,
+            manager_fee_info,
+            maybe_fee_split_infos,
+            pool_tokens_fee,
+        )?;
+
+        let mut withdraw_lamports = stake_pool
+            .calc_lamports_withdraw_amount(pool_tokens_burnt)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        if withdraw_lamports == 0 {
+            return Err(StakePoolError::WithdrawalTooSmall.into());
+        }
+
+        if let Some(minimum_lamports_out) = minimum_lamports_out {
+            if withdraw_lamports < minimum_lamports_out {
+                return Err(StakePoolError::ExceededSlippage.into());
+            }
+        }
+
+        let validator_list_item_info = check_stake_withdraw_source(
+            program_id,
+            stake_pool_info.key,
+            &mut stake_pool,
+            &mut validator_list,
+            stake_split_from,
+            &mut withdraw_lamports,
+        )?;
+
+        // WithdrawStakeWithSession path - create user stake account PDA
+        // Tuple: (user_pubkey, program_signer_bump, program_signer_info, signer_or_session_info, split_lamports)
+        let session_pda_info: Option<(Pubkey, u8, AccountInfo, AccountInfo, u64)> =
+            if let Some(seed) = user_stake_seed {
+                use crate::USER_STAKE_SEED_PREFIX;
+                use fogo_sessions_sdk::session::Session;
+                use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
+
+                let is_validator_removal = matches!(
+                    &validator_list_item_info,
+                    Some((_, StakeWithdrawSource::ValidatorRemoval))
+                );
+
+                let program_signer_info = next_account_info(account_info_iter)?;
+                let system_program_info = next_account_info(account_info_iter)?;
+                let reserve_stake_info = next_account_info(account_info_iter)?;
+                let stake_history_info = next_account_info(account_info_iter)?;
+
+                check_system_program(system_program_info.key)?;
+
+                if stake_pool.reserve_stake != *reserve_stake_info.key {
+                    msg!("Invalid reserve stake account");
+                    return Err(StakePoolError::InvalidProgramAddress.into());
+                }
+
+                let (expected_program_signer, program_signer_bump) =
+                    Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
+                if expected_program_signer != *program_signer_info.key {
+                    msg!("Invalid program signer account");
+                    return Err(ProgramError::InvalidSeeds);
+                }
+
+                let signer_or_session_info = user_stake_authority_info;
+                let user_pubkey = Session::extract_user_from_signer_or_session(
+                    signer_or_session_info,
+                    program_id,
+                )?;
+
+                // Validate stake account PDA
+                let (expected_stake_pda, stake_pda_bump) = Pubkey::find_program_address(
+                    &[
+                        USER_STAKE_SEED_PREFIX,
+                        user_pubkey.as_ref(),
+                        &seed.to_le_bytes(),
+                    ],
+                    program_id,
+                );
+                if expected_stake_pda != *stake_split_to.key {
+                    msg!("Invalid stake account PDA");
+                    return Err(ProgramError::InvalidSeeds);
+                }
+
+                // Calculate rent for stake account
+                let stake_space = stake::state::StakeStateV2::size_of();
+                let stake_rent = Rent::get()?.minimum_balance(stake_space);
+
+                let stake_pda_seeds: &[&[u8]] = &[
+                    USER_STAKE_SEED_PREFIX,
+                    user_pubkey.as_ref(),
+                    &seed.to_le_bytes(),
+                    &[stake_pda_bump],
+                ];
+
+                // Create stake account if needed
+                // - System-owned: new account or GC'd zombie, create it
+                // - Stake-owned + Uninitialized: zombie not yet GC'd, safe to reuse
+                // - Stake-owned + Initialized: active stake, reject
+                if stake_split_to.owner == &system_program::id() {
+                    create_stake_account(stake_split_to.clone(), stake_pda_seeds, stake_space)?;
+                } else if stake_split_to.owner == &stake::program::id() {
+                    let stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+                        &stake_split_to.data.borrow(),
+                    )?;
+                    if !matches!(stake_state, stake::state::StakeStateV2::Uninitialized) {
+                        msg!("Stake account PDA already in use. Use a different seed.");
+                        return Err(StakePoolError::AlreadyInUse.into());
                     }
+                    // Zombie account - already allocated and assigned, skip create
+                } else {
+                    msg!("Stake account PDA has invalid owner");
+                    return Err(ProgramError::IllegalOwner);
                 }
-                Some(stake::state::StakeStateV2::Initialized(meta))
-                    if stake_is_usable_by_pool(
-                        &meta,
-                        withdraw_authority_info.key,
-                        &stake_pool.lockup,
-                    ) =>
-                {
-                    // If a validator stake is `Initialized`, the validator could
-                    // have been destaked during a cluster restart or removed through
-                    // normal means. Either way, absorb those lamports into the reserve.
-                    // The transient stake was likely absorbed into the reserve earlier.
-                    Self::stake_merge(
+
+                let split_lamports = if is_validator_removal {
+                    // Full balance includes rent, no additional funding needed
+                    withdraw_lamports
+                } else {
+                    if stake_rent >= reserve_stake_info.lamports() {
+                        return Err(StakePoolError::ReserveDepleted.into());
+                    }
+                    Self::stake_withdraw(
                         stake_pool_info.key,
-                        validator_stake_info.clone(),
+                        reserve_stake_info.clone(),
                         withdraw_authority_info.clone(),
                         AUTHORITY_WITHDRAW,
                         stake_pool.stake_withdraw_bump_seed,
-                        reserve_stake_info.clone(),
+                        stake_split_to.clone(),
                         clock_info.clone(),
                         stake_history_info.clone(),
+                        stake_rent,
+                    )?;
+                    withdraw_lamports.saturating_sub(stake_rent)
+                };
+
+                Some((
+                    user_pubkey,
+                    program_signer_bump,
+                    program_signer_info.clone(),
+                    signer_or_session_info.clone(),
+                    split_lamports,
+                ))
+            } else {
+                None
+            };
+
+        let actual_split_amount = if let Some((_, _, _, _, split_lamports)) = &session_pda_info {
+            *split_lamports
+        } else {
+            withdraw_lamports
+        };
+        let withdraw_event = StakePoolEvent::WithdrawStake {
+            stake_pool: *stake_pool_info.key,
+            vote_account: validator_list_item_info
+                .as_ref()
+                .map(|(validator_list_item, _)| validator_list_item.vote_account_address),
+            pool_tokens: pool_tokens_burnt,
+            manager_fee_pool_tokens: pool_tokens_fee,
+            lamports: withdraw_lamports,
+        };
+
+        Self::stake_split(
+            stake_pool_info.key,
+            stake_split_from.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            actual_split_amount,
+            stake_split_to.clone(),
+        )?;
+
+        // WithdrawStakeWithSession path - use session for token ops, set PDA as its own authority
+        if let Some((
+            user_pubkey,
+            program_signer_bump,
+            program_signer_info,
+            signer_or_session_info,
+            _split_lamports,
+        )) = session_pda_info
+        {
+            use fogo_sessions_sdk::token::instruction::{burn, transfer_checked};
+            use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
+            use solana_program::program_pack::Pack;
+
+            // Validate burn_from_pool is owned by the session user
+            let burn_from_pool_data =
+                spl_token::state::Account::unpack(&burn_from_pool_info.data.borrow())?;
+            if burn_from_pool_data.owner != user_pubkey {
+                msg!("`burn_from_pool` is not owned by the session user");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
+
+            // Burn using session
+            invoke_signed(
+                &burn(
+                    token_program_info.key,
+                    burn_from_pool_info.key,
+                    pool_mint_info.key,
+                    signer_or_session_info.key,
+                    Some(program_signer_info.key),
+                    pool_tokens_burnt,
+                )?,
+                &[
+                    burn_from_pool_info.clone(),
+                    pool_mint_info.clone(),
+                    signer_or_session_info.clone(),
+                    program_signer_info.clone(),
+                ],
+                &[program_signer_seeds],
+            )?;
+
+            // Transfer fee using session
+            for (fee_info, fee) in fee_payouts {
+                if fee > 0 {
+                    invoke_signed(
+                        &transfer_checked(
+                            token_program_info.key,
+                            burn_from_pool_info.key,
+                            pool_mint_info.key,
+                            fee_info.key,
+                            signer_or_session_info.key,
+                            Some(program_signer_info.key),
+                            fee,
+                            decimals,
+                        )?,
+                        &[
+                            burn_from_pool_info.clone(),
+                            pool_mint_info.clone(),
+                            fee_info.clone(),
+                            signer_or_session_info.clone(),
+                            program_signer_info.clone(),
+                        ],
+                        &[program_signer_seeds],
                     )?;
-                    if transient_stake_lamports != 0 {
-                        validator_stake_record.status = StakeStatus::DeactivatingTransient.into();
-                    } else {
-                        validator_stake_record.status = StakeStatus::ReadyForRemoval.into();
-                    }
                 }
-                Some(stake::state::StakeStateV2::Stake(_, _, _))
-                | Some(stake::state::StakeStateV2::Initialized(_))
-                | Some(stake::state::StakeStateV2::Uninitialized)
-                | Some(stake::state::StakeStateV2::RewardsPool)
-                | None => {
-                    msg!("Validator stake account no longer part of the pool, ignoring");
+            }
+
+            // Only deactivate if the stake is active and not yet deactivated.
+            // stake_deactivate fails with AlreadyDeactivated if called on reserve/transient stake.
+            let split_to_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+                &stake_split_to.data.borrow(),
+            )?;
+            if let stake::state::StakeStateV2::Stake(_meta, stake, _) = split_to_state {
+                if stake.delegation.deactivation_epoch == Epoch::MAX {
+                    // Stake is active and not yet deactivated - deactivate it now
+                    Self::stake_deactivate(
+                        stake_split_to.clone(),
+                        clock_info.clone(),
+                        withdraw_authority_info.clone(),
+                        stake_pool_info.key,
+                        AUTHORITY_WITHDRAW,
+                        stake_pool.stake_withdraw_bump_seed,
+                    )?;
                 }
             }
 
-            validator_stake_record.last_update_epoch = clock.epoch.into();
-            validator_stake_record.active_stake_lamports = active_stake_lamports.into();
-            validator_stake_record.transient_stake_lamports = transient_stake_lamports.into();
+            // Set stake authority to the PDA itself so it can sign the later withdrawal
+            Self::stake_authorize_signed(
+                stake_pool_info.key,
+                stake_split_to.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                stake_split_to.key, // The PDA becomes its own authority
+                clock_info.clone(),
+            )?;
+
+            // Update stake pool: withdraw_lamports left the pool (split from validator + rent from reserve)
+            stake_pool.pool_token_supply = stake_pool
+                .pool_token_supply
+                .checked_sub(pool_tokens_burnt)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            stake_pool.total_lamports = stake_pool
+                .total_lamports
+                .checked_sub(withdraw_lamports)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+
+            // Update validator list (only split_lamports came from the validator stake)
+            if let Some((validator_list_item, withdraw_source)) = validator_list_item_info {
+                debit_validator_stake_info(
+                    validator_list_item,
+                    withdraw_source,
+                    actual_split_amount,
+                )?;
+            }
+
+            withdraw_event.emit();
+            return Ok(());
+        }
+
+        // Regular path - use standard token operations
+
+        Self::token_burn(
+            token_program_info.clone(),
+            burn_from_pool_info.clone(),
+            pool_mint_info.clone(),
+            user_transfer_authority_info.clone(),
+            pool_tokens_burnt,
+        )?;
+
+        Self::stake_authorize_signed(
+            stake_pool_info.key,
+            stake_split_to.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            user_stake_authority_info.key,
+            clock_info.clone(),
+        )?;
+
+        for (fee_info, fee) in fee_payouts {
+            if fee > 0 {
+                Self::token_transfer(
+                    token_program_info.clone(),
+                    burn_from_pool_info.clone(),
+                    pool_mint_info.clone(),
+                    fee_info.clone(),
+                    user_transfer_authority_info.clone(),
+                    fee,
+                    decimals,
+                )?;
+            }
+        }
+
+        stake_pool.pool_token_supply = stake_pool
+            .pool_token_supply
+            .checked_sub(pool_tokens_burnt)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.total_lamports = stake_pool
+            .total_lamports
+            .checked_sub(withdraw_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+
+        if let Some((validator_list_item, withdraw_source)) = validator_list_item_info {
+            debit_validator_stake_info(validator_list_item, withdraw_source, withdraw_lamports)?;
         }
 
+        withdraw_event.emit();
         Ok(())
     }
 
-    /// Processes `UpdateStakePoolBalance` instruction.
-    #[inline(always)] // needed to optimize number of validators
-    fn process_update_stake_pool_balance(
+    /// Processes [`WithdrawStakeMulti`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_withdraw_stake_multi(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        pool_tokens_in: Vec<u64>,
+        minimum_lamports_out: u64,
     ) -> ProgramResult {
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let withdraw_info = next_account_info(account_info_iter)?;
         let validator_list_info = next_account_info(account_info_iter)?;
-        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let user_stake_authority_info = next_account_info(account_info_iter)?;
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let burn_from_pool_info = next_account_info(account_info_iter)?;
         let manager_fee_info = next_account_info(account_info_iter)?;
         let pool_mint_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
         let token_program_info = next_account_info(account_info_iter)?;
-        let clock = Clock::get()?;
+        let stake_program_info = next_account_info(account_info_iter)?;
 
+        check_stake_program(stake_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
-        stake_pool.check_mint(pool_mint_info)?;
-        stake_pool.check_authority_withdraw(withdraw_info.key, program_id, stake_pool_info.key)?;
-        stake_pool.check_reserve_stake(reserve_stake_info)?;
+        stake_pool.check_not_paused(PausedOperation::Withdrawal)?;
+
+        let decimals = stake_pool.check_mint(pool_mint_info)?;
+        stake_pool.check_validator_list(validator_list_info)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+
         if stake_pool.manager_fee_account != *manager_fee_info.key {
             return Err(StakePoolError::InvalidFeeAccount.into());
         }
-
-        if *validator_list_info.key != stake_pool.validator_list {
-            return Err(StakePoolError::InvalidValidatorStakeList.into());
-        }
         if stake_pool.token_program_id != *token_program_info.key {
             return Err(ProgramError::IncorrectProgramId);
         }
 
+        if stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        }
+
         check_account_owner(validator_list_info, program_id)?;
         let mut validator_list_data = validator_list_info.data.borrow_mut();
-        let (header, validator_list) =
+        let (header, mut validator_list) =
             ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
         if !header.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
 
-        let previous_lamports = stake_pool.total_lamports;
-        let previous_pool_token_supply = stake_pool.pool_token_supply;
-        let reserve_stake = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-            &reserve_stake_info.data.borrow(),
-        )?;
-        let mut total_lamports =
-            if let stake::state::StakeStateV2::Initialized(meta) = reserve_stake {
-                reserve_stake_info
-                    .lamports()
-                    .checked_sub(minimum_reserve_lamports(&meta))
+        // Price every withdrawal at the exchange rate from before the
+        // instruction, so that the order of the pairs doesn't matter
+        let charge_fee = stake_pool.manager_fee_account != *burn_from_pool_info.key
+            && stake_pool.check_manager_fee_info(manager_fee_info).is_ok();
+        let mut withdrawals = Vec::with_capacity(pool_tokens_in.len());
+        let mut total_pool_tokens_fee: u64 = 0;
+        let mut total_pool_tokens_burnt: u64 = 0;
+        for pool_tokens in pool_tokens_in {
+            let pool_tokens_fee = if charge_fee {
+                stake_pool
+                    .calc_pool_tokens_stake_withdrawal_fee(pool_tokens)
                     .ok_or(StakePoolError::CalculationFailure)?
             } else {
-                msg!("Reserve stake account in unknown state, aborting");
-                return Err(StakePoolError::WrongStakeStake.into());
+                0
             };
-        for validator_stake_record in validator_list
-            .deserialize_slice::<ValidatorStakeInfo>(0, validator_list.len() as usize)?
-        {
-            if u64::from(validator_stake_record.last_update_epoch) < clock.epoch {
-                return Err(StakePoolError::StakeListOutOfDate.into());
+            let pool_tokens_burnt = pool_tokens
+                .checked_sub(pool_tokens_fee)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            let withdraw_lamports = stake_pool
+                .calc_lamports_withdraw_amount(pool_tokens_burnt)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            if withdraw_lamports == 0 {
+                return Err(StakePoolError::WithdrawalTooSmall.into());
             }
-            total_lamports = total_lamports
-                .checked_add(validator_stake_record.stake_lamports()?)
+            total_pool_tokens_fee = total_pool_tokens_fee
+                .checked_add(pool_tokens_fee)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            total_pool_tokens_burnt = total_pool_tokens_burnt
+                .checked_add(pool_tokens_burnt)
+                .ok_or(StakePoolError::CalculationFailure)?;
+            withdrawals.push((pool_tokens_fee, pool_tokens_burnt, withdraw_lamports));
+        }
+        if withdrawals.is_empty() {
+            return Err(StakePoolError::WithdrawalTooSmall.into());
+        }
+        let fee_payouts = fee_payouts(
+            stake_pool_info.key,
+            &stake_pool,
+            manager_fee_info,
+            maybe_fee_split_infos,
+            total_pool_tokens_fee,
+        )?;
+
+        // Each pair is checked against the validator list as left by the
+        // previous ones, as if they were separate withdrawals
+        let mut total_withdrawn_lamports: u64 = 0;
+        for (pool_tokens_fee, pool_tokens_burnt, mut withdraw_lamports) in withdrawals {
+            let stake_split_from = next_account_info(account_info_iter)?;
+            let stake_split_to = next_account_info(account_info_iter)?;
+
+            let validator_list_item_info = check_stake_withdraw_source(
+                program_id,
+                stake_pool_info.key,
+                &mut stake_pool,
+                &mut validator_list,
+                stake_split_from,
+                &mut withdraw_lamports,
+            )?;
+            let vote_account = validator_list_item_info
+                .as_ref()
+                .map(|(validator_list_item, _)| validator_list_item.vote_account_address);
+
+            Self::stake_split(
+                stake_pool_info.key,
+                stake_split_from.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                withdraw_lamports,
+                stake_split_to.clone(),
+            )?;
+            Self::stake_authorize_signed(
+                stake_pool_info.key,
+                stake_split_to.clone(),
+                withdraw_authority_info.clone(),
+                AUTHORITY_WITHDRAW,
+                stake_pool.stake_withdraw_bump_seed,
+                user_stake_authority_info.key,
+                clock_info.clone(),
+            )?;
+
+            if let Some((validator_list_item, withdraw_source)) = validator_list_item_info {
+                debit_validator_stake_info(
+                    validator_list_item,
+                    withdraw_source,
+                    withdraw_lamports,
+                )?;
+            }
+            total_withdrawn_lamports = total_withdrawn_lamports
+                .checked_add(withdraw_lamports)
                 .ok_or(StakePoolError::CalculationFailure)?;
+
+            StakePoolEvent::WithdrawStake {
+                stake_pool: *stake_pool_info.key,
+                vote_account,
+                pool_tokens: pool_tokens_burnt,
+                manager_fee_pool_tokens: pool_tokens_fee,
+                lamports: withdraw_lamports,
+            }
+            .emit();
+        }
+
+        // Taking a whole validator stake account pays out only what it holds,
+        // so slippage is checked against the lamports actually split
+        if total_withdrawn_lamports < minimum_lamports_out {
+            return Err(StakePoolError::ExceededSlippage.into());
+        }
+
+        Self::token_burn(
+            token_program_info.clone(),
+            burn_from_pool_info.clone(),
+            pool_mint_info.clone(),
+            user_transfer_authority_info.clone(),
+            total_pool_tokens_burnt,
+        )?;
+
+        for (fee_info, fee) in fee_payouts {
+            if fee > 0 {
+                Self::token_transfer(
+                    token_program_info.clone(),
+                    burn_from_pool_info.clone(),
+                    pool_mint_info.clone(),
+                    fee_info.clone(),
+                    user_transfer_authority_info.clone(),
+                    fee,
+                    decimals,
+                )?;
+            }
+        }
+
+        stake_pool.pool_token_supply = stake_pool
+            .pool_token_supply
+            .checked_sub(total_pool_tokens_burnt)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.total_lamports = stake_pool
+            .total_lamports
+            .checked_sub(total_withdrawn_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+
+        Ok(())
+    }
+
+    /// Processes [`WithdrawWsolWithSession`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_withdraw_wsol_with_session(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        pool_tokens: u64,
+        minimum_lamports_out: Option<u64>,
+    ) -> ProgramResult {
+        use fogo_sessions_sdk::session::Session;
+        use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
+        use solana_program::program_pack::Pack;
+
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let signer_or_session_info = next_account_info(account_info_iter)?;
+        let burn_from_pool_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let destination_account_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+
+        // wsol-specific accounts
+        let wsol_mint_info = next_account_info(account_info_iter)?;
+        let program_signer_info = next_account_info(account_info_iter)?;
+        let user_wallet_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        let sol_withdraw_authority_info = next_account_info(account_info_iter);
+
+        Self::check_stake_pool_not_paused(program_id, stake_pool_info, PausedOperation::Session)?;
+
+        if *wsol_mint_info.key != spl_token::native_mint::id() {
+            msg!("`wsol_mint` is not the native WSOL mint");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if *wsol_mint_info.owner != *token_program_info.key {
+            msg!("`wsol_mint` is not owned by the token program");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let user_pubkey =
+            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+
+        // Verify burn_from_pool_info is owned by the user
+        let burn_from_pool_data =
+            spl_token::state::Account::unpack(&burn_from_pool_info.data.borrow())?;
+        if burn_from_pool_data.owner != user_pubkey {
+            msg!("`burn_from_pool` is not owned by the session user");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (expected_program_signer, program_signer_bump) =
+            Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
+
+        if *program_signer_info.key != expected_program_signer {
+            msg!("`program_signer` does not match expected address");
+            return Err(ProgramError::InvalidSeeds);
         }
 
-        let reward_lamports = total_lamports.saturating_sub(previous_lamports);
+        let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
+
+        let rent = Rent::get()?;
+
+        // Verify destination_account_info is the correct wSOL ATA for user_wallet
+        let expected_ata =
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                user_wallet_info.key,
+                wsol_mint_info.key,
+                token_program_info.key,
+            );
+        if *destination_account_info.key != expected_ata {
+            msg!("destination_account is not the wSOL ATA for user_wallet");
+            return Err(ProgramError::InvalidAccountData);
+        }
 
-        // If the manager fee info is invalid, they don't deserve to receive the fee.
-        let fee = if stake_pool.check_manager_fee_info(manager_fee_info).is_ok() {
-            stake_pool
-                .calc_epoch_fee_amount(reward_lamports)
-                .ok_or(StakePoolError::CalculationFailure)?
-        } else {
-            0
-        };
+        let ata_creation_cost = rent
+            .minimum_balance(spl_token::state::Account::LEN)
+            .saturating_sub(destination_account_info.lamports());
 
-        if fee > 0 {
-            Self::token_mint_to(
-                stake_pool_info.key,
-                token_program_info.clone(),
-                pool_mint_info.clone(),
+        let withdraw_lamports = {
+            let mut accounts: Vec<AccountInfo> = vec![
+                stake_pool_info.clone(),
+                withdraw_authority_info.clone(),
+                signer_or_session_info.clone(),
+                burn_from_pool_info.clone(),
+                reserve_stake_info.clone(),
+                program_signer_info.clone(),
                 manager_fee_info.clone(),
-                withdraw_info.clone(),
-                AUTHORITY_WITHDRAW,
-                stake_pool.stake_withdraw_bump_seed,
-                fee,
-            )?;
-        }
+                pool_mint_info.clone(),
+                clock_info.clone(),
+                stake_history_info.clone(),
+                stake_program_info.clone(),
+                token_program_info.clone(),
+            ];
 
-        if stake_pool.last_update_epoch < clock.epoch {
-            if let Some(fee) = stake_pool.next_epoch_fee.get() {
-                stake_pool.epoch_fee = *fee;
+            if let Ok(sol_withdraw_authority_info) = sol_withdraw_authority_info {
+                accounts.push(sol_withdraw_authority_info.clone());
+            } else {
+                // dummy to keep account order consistent
+                accounts.push(stake_program_info.clone());
             }
-            stake_pool.next_epoch_fee.update_epoch();
 
-            if let Some(fee) = stake_pool.next_stake_withdrawal_fee.get() {
-                stake_pool.stake_withdrawal_fee = *fee;
+            accounts.push(program_signer_info.clone());
+            if let Some(fee_split_infos) = maybe_fee_split_infos {
+                accounts.extend_from_slice(fee_split_infos);
             }
-            stake_pool.next_stake_withdrawal_fee.update_epoch();
 
-            if let Some(fee) = stake_pool.next_sol_withdrawal_fee.get() {
-                stake_pool.sol_withdrawal_fee = *fee;
+            let balance_before = program_signer_info.lamports();
+            Self::process_withdraw_sol(program_id, &accounts, pool_tokens, minimum_lamports_out)?;
+            program_signer_info
+                .lamports()
+                .saturating_sub(balance_before)
+        };
+
+        if ata_creation_cost > 0 {
+            if withdraw_lamports < ata_creation_cost {
+                msg!("Withdrawal too small to cover ATA rent");
+                return Err(StakePoolError::WithdrawalTooSmall.into());
             }
-            stake_pool.next_sol_withdrawal_fee.update_epoch();
 
-            stake_pool.last_update_epoch = clock.epoch;
-            stake_pool.last_epoch_total_lamports = previous_lamports;
-            stake_pool.last_epoch_pool_token_supply = previous_pool_token_supply;
+            invoke_signed(
+                &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    program_signer_info.key,
+                    user_wallet_info.key,
+                    wsol_mint_info.key,
+                    token_program_info.key,
+                ),
+                &[
+                    program_signer_info.clone(),
+                    destination_account_info.clone(),
+                    user_wallet_info.clone(),
+                    wsol_mint_info.clone(),
+                    system_program_info.clone(),
+                    token_program_info.clone(),
+                ],
+                &[program_signer_seeds],
+            )?;
         }
-        stake_pool.total_lamports = total_lamports;
 
-        let pool_mint_data = pool_mint_info.try_borrow_data()?;
-        let pool_mint = StateWithExtensions::<Mint>::unpack(&pool_mint_data)?;
-        stake_pool.pool_token_supply = pool_mint.base.supply;
+        let user_lamports = withdraw_lamports.saturating_sub(ata_creation_cost);
+        if user_lamports > 0 {
+            invoke_signed(
+                &system_instruction::transfer(
+                    program_signer_info.key,
+                    destination_account_info.key,
+                    user_lamports,
+                ),
+                &[
+                    program_signer_info.clone(),
+                    destination_account_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[program_signer_seeds],
+            )?;
+        }
 
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        invoke(
+            &spl_token::instruction::sync_native(
+                token_program_info.key,
+                destination_account_info.key,
+            )?,
+            core::slice::from_ref(destination_account_info),
+        )?;
 
         Ok(())
     }
 
-    /// Processes the `CleanupRemovedValidatorEntries` instruction
+    /// Processes [`WithdrawSolWithSession`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_cleanup_removed_validator_entries(
+    fn process_withdraw_sol_with_session(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        pool_tokens: u64,
+        minimum_lamports_out: Option<u64>,
     ) -> ProgramResult {
+        use fogo_sessions_sdk::session::Session;
+        use solana_program::program_pack::Pack;
+
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let validator_list_info = next_account_info(account_info_iter)?;
-
-        check_account_owner(stake_pool_info, program_id)?;
-        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
-        if !stake_pool.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
-        }
-        stake_pool.check_validator_list(validator_list_info)?;
-
-        check_account_owner(validator_list_info, program_id)?;
-        let mut validator_list_data = validator_list_info.data.borrow_mut();
-        let (header, mut validator_list) =
-            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
-        if !header.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
-        }
-
-        validator_list.retain::<ValidatorStakeInfo, _>(|x| !ValidatorStakeInfo::is_removed(x))?;
-
-        if stake_pool_info.is_writable {
-            msg!("Checking preferred validators");
-            let mut stake_pool =
-                try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let signer_or_session_info = next_account_info(account_info_iter)?;
+        let burn_from_pool_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let user_wallet_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let program_signer_info = next_account_info(account_info_iter)?;
 
-            // Check and reset preferred validators if they don't exist or aren't active
-            // Check preferred deposit validator
-            if let Some(preferred_deposit) = stake_pool.preferred_deposit_validator_vote_address {
-                let maybe_validator = validator_list.find::<ValidatorStakeInfo, _>(|x| {
-                    ValidatorStakeInfo::memcmp_pubkey(x, &preferred_deposit)
-                });
+        let sol_withdraw_authority_info = next_account_info(account_info_iter);
 
-                let should_reset = match maybe_validator {
-                    Some(validator) => {
-                        // Check if validator status is not Active
-                        match validator.status.try_into() {
-                            Ok(StakeStatus::Active) => false, // Valid, keep it
-                            _ => true,                        // Not active, reset it
-                        }
-                    }
-                    None => true, // Not found in list, reset it
-                };
+        Self::check_stake_pool_not_paused(program_id, stake_pool_info, PausedOperation::Session)?;
 
-                if should_reset {
-                    msg!(
-                        "Preferred deposit validator {} not found or not active, resetting",
-                        preferred_deposit
-                    );
-                    stake_pool.preferred_deposit_validator_vote_address = None;
-                }
-            }
+        let user_pubkey =
+            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+        if *user_wallet_info.key != user_pubkey {
+            msg!("`user_wallet` is not the session user");
+            return Err(ProgramError::InvalidAccountData);
+        }
 
-            // Check preferred withdrawal validator
-            if let Some(preferred_withdraw) = stake_pool.preferred_withdraw_validator_vote_address {
-                let maybe_validator = validator_list.find::<ValidatorStakeInfo, _>(|x| {
-                    ValidatorStakeInfo::memcmp_pubkey(x, &preferred_withdraw)
-                });
+        // Verify burn_from_pool_info is owned by the user
+        let burn_from_pool_data =
+            spl_token::state::Account::unpack(&burn_from_pool_info.data.borrow())?;
+        if burn_from_pool_data.owner != user_pubkey {
+            msg!("`burn_from_pool` is not owned by the session user");
+            return Err(ProgramError::InvalidAccountData);
+        }
 
-                let should_reset = match maybe_validator {
-                    Some(validator) => {
-                        // Check if validator status is not Active
-                        match validator.status.try_into() {
-                            Ok(StakeStatus::Active) => false, // Valid, keep it
-                            _ => true,                        // Not active, reset it
-                        }
-                    }
-                    None => true, // Not found in list, reset it
-                };
+        let mut accounts: Vec<AccountInfo> = vec![
+            stake_pool_info.clone(),
+            withdraw_authority_info.clone(),
+            signer_or_session_info.clone(),
+            burn_from_pool_info.clone(),
+            reserve_stake_info.clone(),
+            user_wallet_info.clone(),
+            manager_fee_info.clone(),
+            pool_mint_info.clone(),
+            clock_info.clone(),
+            stake_history_info.clone(),
+            stake_program_info.clone(),
+            token_program_info.clone(),
+        ];
 
-                if should_reset {
-                    msg!(
-                        "Preferred withdrawal validator {} not found or not active, resetting",
-                        preferred_withdraw
-                    );
-                    stake_pool.preferred_withdraw_validator_vote_address = None;
-                }
-            }
+        if let Ok(sol_withdraw_authority_info) = sol_withdraw_authority_info {
+            accounts.push(sol_withdraw_authority_info.clone());
+        } else {
+            // dummy to keep account order consistent
+            accounts.push(stake_program_info.clone());
+        }
 
-            // Save the updated stake pool state
-            borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        // The program signer burns the pool tokens on behalf of the session
+        accounts.push(program_signer_info.clone());
+        if let Some(fee_split_infos) = maybe_fee_split_infos {
+            accounts.extend_from_slice(fee_split_infos);
         }
 
-        Ok(())
+        Self::process_withdraw_sol(program_id, &accounts, pool_tokens, minimum_lamports_out)
     }
 
-    /// Processes [`DepositStake`](enum.Instruction.html).
+    /// Processes [`WithdrawSol`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_deposit_stake(
+    fn process_withdraw_sol(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        minimum_pool_tokens_out: Option<u64>,
+        pool_tokens: u64,
+        minimum_lamports_out: Option<u64>,
     ) -> ProgramResult {
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let validator_list_info = next_account_info(account_info_iter)?;
-        let stake_deposit_authority_info = next_account_info(account_info_iter)?;
         let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let stake_info = next_account_info(account_info_iter)?;
-        let validator_stake_account_info = next_account_info(account_info_iter)?;
-        let reserve_stake_account_info = next_account_info(account_info_iter)?;
-        let dest_user_pool_info = next_account_info(account_info_iter)?;
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let burn_from_pool_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let destination_lamports_info = next_account_info(account_info_iter)?;
         let manager_fee_info = next_account_info(account_info_iter)?;
-        let referrer_fee_info = next_account_info(account_info_iter)?;
         let pool_mint_info = next_account_info(account_info_iter)?;
         let clock_info = next_account_info(account_info_iter)?;
-        let clock = &Clock::from_account_info(clock_info)?;
         let stake_history_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
         let stake_program_info = next_account_info(account_info_iter)?;
-
-        check_stake_program(stake_program_info.key)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let sol_withdraw_authority_info = next_account_info(account_info_iter);
 
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_not_paused(PausedOperation::Withdrawal)?;
 
         stake_pool.check_authority_withdraw(
             withdraw_authority_info.key,
             program_id,
             stake_pool_info.key,
         )?;
-        stake_pool.check_stake_deposit_authority(stake_deposit_authority_info.key)?;
-        stake_pool.check_mint(pool_mint_info)?;
-        stake_pool.check_validator_list(validator_list_info)?;
-        stake_pool.check_reserve_stake(reserve_stake_account_info)?;
+        stake_pool.check_sol_withdraw_authority(sol_withdraw_authority_info)?;
+        let decimals = stake_pool.check_mint(pool_mint_info)?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
 
         if stake_pool.token_program_id != *token_program_info.key {
             return Err(ProgramError::IncorrectProgramId);
         }
+        check_stake_program(stake_program_info.key)?;
 
         if stake_pool.manager_fee_account != *manager_fee_info.key {
             return Err(StakePoolError::InvalidFeeAccount.into());
         }
-        // There is no bypass if the manager fee account is invalid. Deposits
-        // don't hold user funds hostage, so if the fee account is invalid, users
-        // cannot deposit in the pool.  Let it fail here!
 
-        if stake_pool.last_update_epoch < clock.epoch {
+        // We want this to hold to ensure that withdraw_sol burns pool tokens
+        // at the right price
+        let epoch = Clock::get()?.epoch;
+        if stake_pool.last_update_epoch < epoch {
             return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
         }
 
-        check_account_owner(validator_list_info, program_id)?;
-        let mut validator_list_data = validator_list_info.data.borrow_mut();
-        let (header, mut validator_list) =
-            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
-        if !header.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
-        }
-
-        let (_, validator_stake) = get_stake_state(validator_stake_account_info)?;
-        let pre_all_validator_lamports = validator_stake_account_info.lamports();
-        let vote_account_address = validator_stake.delegation.voter_pubkey;
-        if let Some(preferred_deposit) = stake_pool.preferred_deposit_validator_vote_address {
-            if preferred_deposit != vote_account_address {
-                msg!(
-                    "Incorrect deposit address, expected {}, received {}",
-                    preferred_deposit,
-                    vote_account_address
-                );
-                return Err(StakePoolError::IncorrectDepositVoteAddress.into());
-            }
-        }
+        let stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+            &reserve_stake_info.data.borrow(),
+        )?;
+        let minimum_reserve_lamports =
+            if let stake::state::StakeStateV2::Initialized(meta) = stake_state {
+                minimum_reserve_lamports(&meta)
+                    .saturating_add(stake_pool.withdrawal_claim_pool_lamports)
+            } else {
+                msg!("Reserve stake account not in initialized state");
+                return Err(StakePoolError::WrongStakeStake.into());
+            };
+        let reserve_liquidity_lamports = reserve_stake_info
+            .lamports()
+            .saturating_sub(minimum_reserve_lamports);
 
-        let validator_stake_info = validator_list
-            .find_mut::<ValidatorStakeInfo, _>(|x| {
-                ValidatorStakeInfo::memcmp_pubkey(x, &vote_account_address)
-            })
-            .ok_or(StakePoolError::ValidatorNotFound)?;
-        check_validator_stake_address(
-            program_id,
+        // To prevent a faulty manager fee account from preventing withdrawals
+        // if the token program does not own the account, or if the account is not
+        // initialized
+        let pool_tokens_fee = if stake_pool.manager_fee_account == *burn_from_pool_info.key
+            || stake_pool.check_manager_fee_info(manager_fee_info).is_err()
+        {
+            0
+        } else {
+            stake_pool
+                .calc_dynamic_sol_withdrawal_fee(pool_tokens, reserve_liquidity_lamports)
+                .ok_or(StakePoolError::CalculationFailure)?
+        };
+        let pool_tokens_burnt = pool_tokens
+            .checked_sub(pool_tokens_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let fee_payouts = fee_payouts(
             stake_pool_info.key,
-            validator_stake_account_info.key,
-            &vote_account_address,
-            NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
+            &stake_pool,
+            manager_fee_info,
+            maybe_fee_split_infos,
+            pool_tokens_fee,
         )?;
 
-        if validator_stake_info.status != StakeStatus::Active.into() {
-            msg!("Validator is marked for removal and no longer accepting deposits");
-            return Err(StakePoolError::ValidatorNotFound.into());
-        }
-
-        msg!("Stake pre merge {}", validator_stake.delegation.stake);
+        let withdraw_lamports = stake_pool
+            .calc_lamports_withdraw_amount(pool_tokens_burnt)
+            .ok_or(StakePoolError::CalculationFailure)?;
 
-        let (stake_deposit_authority_program_address, deposit_bump_seed) =
-            find_deposit_authority_program_address(program_id, stake_pool_info.key);
-        if *stake_deposit_authority_info.key == stake_deposit_authority_program_address {
-            Self::stake_authorize_signed(
-                stake_pool_info.key,
-                stake_info.clone(),
-                stake_deposit_authority_info.clone(),
-                AUTHORITY_DEPOSIT,
-                deposit_bump_seed,
-                withdraw_authority_info.key,
-                clock_info.clone(),
-            )?;
-        } else {
-            Self::stake_authorize(
-                stake_info.clone(),
-                stake_deposit_authority_info.clone(),
-                withdraw_authority_info.key,
-                clock_info.clone(),
-            )?;
+        if withdraw_lamports == 0 {
+            return Err(StakePoolError::WithdrawalTooSmall.into());
         }
 
-        Self::stake_merge(
-            stake_pool_info.key,
-            stake_info.clone(),
-            withdraw_authority_info.clone(),
-            AUTHORITY_WITHDRAW,
-            stake_pool.stake_withdraw_bump_seed,
-            validator_stake_account_info.clone(),
-            clock_info.clone(),
-            stake_history_info.clone(),
-        )?;
-
-        let (_, post_validator_stake) = get_stake_state(validator_stake_account_info)?;
-        let post_all_validator_lamports = validator_stake_account_info.lamports();
-        msg!("Stake post merge {}", post_validator_stake.delegation.stake);
+        if let Some(minimum_lamports_out) = minimum_lamports_out {
+            if withdraw_lamports < minimum_lamports_out {
+                return Err(StakePoolError::ExceededSlippage.into());
+            }
+        }
 
-        let total_deposit_lamports = post_all_validator_lamports
-            .checked_sub(pre_all_validator_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let stake_deposit_lamports = post_validator_stake
-            .delegation
-            .stake
-            .checked_sub(validator_stake.delegation.stake)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let sol_deposit_lamports = total_deposit_lamports
-            .checked_sub(stake_deposit_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.record_sol_withdrawal(epoch, withdraw_lamports)?;
 
-        let new_pool_tokens = stake_pool
-            .calc_pool_tokens_for_deposit(total_deposit_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let new_pool_tokens_from_stake = stake_pool
-            .calc_pool_tokens_for_deposit(stake_deposit_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let new_pool_tokens_from_sol = new_pool_tokens
-            .checked_sub(new_pool_tokens_from_stake)
-            .ok_or(StakePoolError::CalculationFailure)?;
+        if withdraw_lamports > reserve_liquidity_lamports {
+            msg!("Attempting to withdraw {} lamports, maximum possible SOL withdrawal is {} lamports",
+                withdraw_lamports,
+                reserve_liquidity_lamports
+            );
+            return Err(StakePoolError::SolWithdrawalTooLarge.into());
+        }
 
-        let stake_deposit_fee = stake_pool
-            .calc_pool_tokens_stake_deposit_fee(new_pool_tokens_from_stake)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let sol_deposit_fee = stake_pool
-            .calc_pool_tokens_sol_deposit_fee(new_pool_tokens_from_sol)
-            .ok_or(StakePoolError::CalculationFailure)?;
+        // Determine if we are using the WSOL special path (with program signer account)
+        if let Ok(program_signer_info) = next_account_info(account_info_iter) {
+            use fogo_sessions_sdk::token::instruction::burn;
+            use fogo_sessions_sdk::token::instruction::transfer_checked;
+            use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
 
-        let total_fee = stake_deposit_fee
-            .checked_add(sol_deposit_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let pool_tokens_user = new_pool_tokens
-            .checked_sub(total_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
+            let (expected_program_signer, program_signer_bump) =
+                Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
 
-        let pool_tokens_referral_fee = stake_pool
-            .calc_pool_tokens_stake_referral_fee(total_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
+            if expected_program_signer != *program_signer_info.key {
+                msg!("Invalid program signer account");
+                return Err(ProgramError::InvalidSeeds);
+            }
 
-        let pool_tokens_manager_deposit_fee = total_fee
-            .checked_sub(pool_tokens_referral_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
+            let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
 
-        if pool_tokens_user
-            .saturating_add(pool_tokens_manager_deposit_fee)
-            .saturating_add(pool_tokens_referral_fee)
-            != new_pool_tokens
-        {
-            return Err(StakePoolError::CalculationFailure.into());
-        }
+            invoke_signed(
+                &burn(
+                    token_program_info.key,
+                    burn_from_pool_info.key,
+                    pool_mint_info.key,
+                    user_transfer_authority_info.key,
+                    Some(program_signer_info.key),
+                    pool_tokens_burnt,
+                )?,
+                &[
+                    burn_from_pool_info.clone(),
+                    pool_mint_info.clone(),
+                    user_transfer_authority_info.clone(),
+                    program_signer_info.clone(),
+                ],
+                &[program_signer_seeds],
+            )?;
 
-        if pool_tokens_user == 0 {
-            return Err(StakePoolError::DepositTooSmall.into());
-        }
+            for (fee_info, fee) in fee_payouts {
+                if fee > 0 {
+                    invoke_signed(
+                        &transfer_checked(
+                            token_program_info.key,
+                            burn_from_pool_info.key,
+                            pool_mint_info.key,
+                            fee_info.key,
+                            user_transfer_authority_info.key,
+                            Some(program_signer_info.key),
+                            fee,
+                            decimals,
+                        )?,
+                        &[
+                            burn_from_pool_info.clone(),
+                            pool_mint_info.clone(),
+                            fee_info.clone(),
+                            user_transfer_authority_info.clone(),
+                            program_signer_info.clone(),
+                        ],
+                        &[program_signer_seeds],
+                    )?;
+                }
+            }
+        } else {
+            Self::token_burn(
+                token_program_info.clone(),
+                burn_from_pool_info.clone(),
+                pool_mint_info.clone(),
+                user_transfer_authority_info.clone(),
+                pool_tokens_burnt,
+            )?;
 
-        if let Some(minimum_pool_tokens_out) = minimum_pool_tokens_out {
-            if pool_tokens_user < minimum_pool_tokens_out {
-                return Err(StakePoolError::ExceededSlippage.into());
+            for (fee_info, fee) in fee_payouts {
+                if fee > 0 {
+                    Self::token_transfer(
+                        token_program_info.clone(),
+                        burn_from_pool_info.clone(),
+                        pool_mint_info.clone(),
+                        fee_info.clone(),
+                        user_transfer_authority_info.clone(),
+                        fee,
+                        decimals,
+                    )?;
+                }
             }
         }
 
-        Self::token_mint_to(
+        Self::stake_withdraw(
             stake_pool_info.key,
-            token_program_info.clone(),
-            pool_mint_info.clone(),
-            dest_user_pool_info.clone(),
+            reserve_stake_info.clone(),
             withdraw_authority_info.clone(),
             AUTHORITY_WITHDRAW,
             stake_pool.stake_withdraw_bump_seed,
-            pool_tokens_user,
+            destination_lamports_info.clone(),
+            clock_info.clone(),
+            stake_history_info.clone(),
+            withdraw_lamports,
         )?;
-        if pool_tokens_manager_deposit_fee > 0 {
-            Self::token_mint_to(
-                stake_pool_info.key,
-                token_program_info.clone(),
-                pool_mint_info.clone(),
-                manager_fee_info.clone(),
-                withdraw_authority_info.clone(),
-                AUTHORITY_WITHDRAW,
-                stake_pool.stake_withdraw_bump_seed,
-                pool_tokens_manager_deposit_fee,
-            )?;
-        }
-        if pool_tokens_referral_fee > 0 {
-            Self::token_mint_to(
-                stake_pool_info.key,
-                token_program_info.clone(),
-                pool_mint_info.clone(),
-                referrer_fee_info.clone(),
-                withdraw_authority_info.clone(),
-                AUTHORITY_WITHDRAW,
-                stake_pool.stake_withdraw_bump_seed,
-                pool_tokens_referral_fee,
-            )?;
-        }
-
-        // withdraw additional lamports to the reserve
-        if sol_deposit_lamports > 0 {
-            Self::stake_withdraw(
-                stake_pool_info.key,
-                validator_stake_account_info.clone(),
-                withdraw_authority_info.clone(),
-                AUTHORITY_WITHDRAW,
-                stake_pool.stake_withdraw_bump_seed,
-                reserve_stake_account_info.clone(),
-                clock_info.clone(),
-                stake_history_info.clone(),
-                sol_deposit_lamports,
-            )?;
-        }
 
         stake_pool.pool_token_supply = stake_pool
             .pool_token_supply
-            .checked_add(new_pool_tokens)
+            .checked_sub(pool_tokens_burnt)
             .ok_or(StakePoolError::CalculationFailure)?;
-        // We treat the extra lamports as though they were
-        // transferred directly to the reserve stake account.
         stake_pool.total_lamports = stake_pool
             .total_lamports
-            .checked_add(total_deposit_lamports)
+            .checked_sub(withdraw_lamports)
             .ok_or(StakePoolError::CalculationFailure)?;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
-        validator_stake_info.active_stake_lamports = validator_stake_account_info.lamports().into();
+        StakePoolEvent::WithdrawSol {
+            stake_pool: *stake_pool_info.key,
+            pool_tokens: pool_tokens_burnt,
+            manager_fee_pool_tokens: pool_tokens_fee,
+            lamports: withdraw_lamports,
+        }
+        .emit();
 
         Ok(())
     }
 
-    /// Processes [`DepositWsolWithSession`](enum.Instruction.html).
     #[inline(never)]
-    fn process_deposit_wsol_with_session(
+    fn process_create_pool_token_metadata(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        deposit_lamports: u64,
-        minimum_pool_tokens_out: Option<u64>,
+        name: String,
+        symbol: String,
+        uri: String,
     ) -> ProgramResult {
-        use fogo_sessions_sdk::token::instruction::transfer_checked;
-        use fogo_sessions_sdk::{session::Session, token::PROGRAM_SIGNER_SEED};
-        use solana_program::program_pack::Pack;
-        use spl_associated_token_account::tools::account::create_pda_account;
-
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
         let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let reserve_stake_account_info = next_account_info(account_info_iter)?;
-        let signer_or_session_info = next_account_info(account_info_iter)?;
-        let dest_user_pool_info = next_account_info(account_info_iter)?;
-        let manager_fee_info = next_account_info(account_info_iter)?;
-        let referrer_fee_info = next_account_info(account_info_iter)?;
         let pool_mint_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let metadata_info = next_account_info(account_info_iter)?;
+        let mpl_token_metadata_program_info = next_account_info(account_info_iter)?;
         let system_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
 
-        // wsol-specific accounts
-        let wsol_mint_info = next_account_info(account_info_iter)?;
-        let wsol_token_info = next_account_info(account_info_iter)?;
-        let wsol_transient_info = next_account_info(account_info_iter)?;
-        let program_signer_info = next_account_info(account_info_iter)?;
-        let payer_info = next_account_info(account_info_iter)?;
-        let user_wallet_info = next_account_info(account_info_iter)?;
+        if !payer_info.is_signer {
+            msg!("Payer did not sign metadata creation");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
 
-        let sol_deposit_authority_info = next_account_info(account_info_iter);
+        check_system_program(system_program_info.key)?;
+        check_account_owner(payer_info, &system_program::id())?;
+        check_account_owner(stake_pool_info, program_id)?;
+        check_mpl_metadata_program(mpl_token_metadata_program_info.key)?;
 
-        if *wsol_mint_info.key != spl_token::native_mint::id() {
-            msg!("`wsol_mint` is not the native SOL mint");
-            return Err(ProgramError::InvalidAccountData);
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
 
-        if *wsol_mint_info.owner != *token_program_info.key {
-            msg!("`wsol_mint` is not owned by the token program");
-            return Err(ProgramError::InvalidAccountData);
-        }
+        stake_pool.check_manager(manager_info)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_mint(pool_mint_info)?;
+        check_mpl_metadata_account_address(metadata_info.key, &stake_pool.pool_mint)?;
 
-        if *wsol_token_info.owner != *token_program_info.key {
-            msg!("`wsol_token` is not owned by the token program");
-            return Err(ProgramError::InvalidAccountData);
-        }
+        // Token mint authority for stake-pool token is stake-pool withdraw authority
+        let token_mint_authority = withdraw_authority_info;
 
-        let user_pubkey =
-            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+        let new_metadata_instruction = create_metadata_accounts_v3(
+            *mpl_token_metadata_program_info.key,
+            *metadata_info.key,
+            *pool_mint_info.key,
+            *token_mint_authority.key,
+            *payer_info.key,
+            *token_mint_authority.key,
+            name,
+            symbol,
+            uri,
+        );
 
-        // Verify wsol_token_info is a valid wSOL token account owned by the user
-        let wsol_token_data = spl_token::state::Account::unpack(&wsol_token_info.data.borrow())?;
-        if wsol_token_data.mint != spl_token::native_mint::id() {
-            msg!("`wsol_token` is not a wSOL token account");
-            return Err(ProgramError::InvalidAccountData);
-        }
-        if wsol_token_data.owner != user_pubkey {
-            msg!("`wsol_token` is not owned by the session user");
-            return Err(ProgramError::InvalidAccountData);
-        }
+        let (_, stake_withdraw_bump_seed) =
+            crate::find_withdraw_authority_program_address(program_id, stake_pool_info.key);
 
-        const TRANSIENT_SEED: &[u8] = b"transient_wsol";
+        let token_mint_authority_signer_seeds: &[&[_]] = &[
+            stake_pool_info.key.as_ref(),
+            AUTHORITY_WITHDRAW,
+            &[stake_withdraw_bump_seed],
+        ];
 
-        let (expected_program_signer, program_signer_bump) =
-            Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
+        invoke_signed(
+            &new_metadata_instruction,
+            &[
+                metadata_info.clone(),
+                pool_mint_info.clone(),
+                withdraw_authority_info.clone(),
+                payer_info.clone(),
+                withdraw_authority_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[token_mint_authority_signer_seeds],
+        )?;
 
-        if *program_signer_info.key != expected_program_signer {
-            msg!("`program_signer` does not match expected address");
-            return Err(ProgramError::InvalidSeeds);
-        }
+        Ok(())
+    }
 
-        let (expected_transient_pda, transient_bump) =
-            Pubkey::find_program_address(&[TRANSIENT_SEED, user_pubkey.as_ref()], program_id);
+    #[inline(never)]
+    fn process_update_pool_token_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
 
-        if *wsol_transient_info.key != expected_transient_pda {
-            msg!("`wsol_transient` does not match expected address");
-            return Err(ProgramError::InvalidSeeds);
-        }
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let metadata_info = next_account_info(account_info_iter)?;
+        let mpl_token_metadata_program_info = next_account_info(account_info_iter)?;
 
-        let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
-        let transient_seeds: &[&[u8]] = &[TRANSIENT_SEED, user_pubkey.as_ref(), &[transient_bump]];
+        check_account_owner(stake_pool_info, program_id)?;
 
-        let rent = Rent::get()?;
+        check_mpl_metadata_program(mpl_token_metadata_program_info.key)?;
 
-        // Create the transient wSOL account
-        create_pda_account(
-            payer_info,
-            &rent,
-            spl_token::state::Account::LEN,
-            token_program_info.key,
-            system_program_info,
-            wsol_transient_info,
-            transient_seeds,
-        )?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
 
-        invoke(
-            &spl_token::instruction::initialize_account3(
-                token_program_info.key,
-                wsol_transient_info.key,
-                wsol_mint_info.key,
-                program_signer_info.key,
-            )?,
-            &[wsol_transient_info.clone(), wsol_mint_info.clone()],
+        stake_pool.check_manager(manager_info)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
         )?;
+        check_mpl_metadata_account_address(metadata_info.key, &stake_pool.pool_mint)?;
 
-        // Transfer wSOL from user to transient account and unwrap to SOL
-        invoke_signed(
-            &transfer_checked(
-                token_program_info.key,
-                wsol_token_info.key,
-                wsol_mint_info.key,
-                wsol_transient_info.key,
-                signer_or_session_info.key,
-                Some(program_signer_info.key),
-                deposit_lamports,
-                native_mint::DECIMALS,
-            )?,
-            &[
-                token_program_info.clone(),
-                wsol_token_info.clone(),
-                wsol_mint_info.clone(),
-                wsol_transient_info.clone(),
-                signer_or_session_info.clone(),
-                program_signer_info.clone(),
-            ],
-            &[program_signer_seeds],
-        )?;
+        // Token mint authority for stake-pool token is withdraw authority only
+        let token_mint_authority = withdraw_authority_info;
 
-        // Close the transient wSOL account to unwrap to SOL
-        invoke_signed(
-            &spl_token::instruction::close_account(
-                token_program_info.key,
-                wsol_transient_info.key,
-                program_signer_info.key,
-                program_signer_info.key,
-                &[],
-            )?,
-            &[wsol_transient_info.clone(), program_signer_info.clone()],
-            &[program_signer_seeds],
-        )?;
+        let update_metadata_accounts_instruction = update_metadata_accounts_v2(
+            *mpl_token_metadata_program_info.key,
+            *metadata_info.key,
+            *token_mint_authority.key,
+            None,
+            Some(DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            }),
+            None,
+            Some(true),
+        );
 
-        // Refund transient wSOL rent to payer
-        let rent_lamports = rent.minimum_balance(spl_token::state::Account::LEN).max(1);
+        let (_, stake_withdraw_bump_seed) =
+            crate::find_withdraw_authority_program_address(program_id, stake_pool_info.key);
 
-        invoke_signed(
-            &system_instruction::transfer(program_signer_info.key, payer_info.key, rent_lamports),
-            &[
-                system_program_info.clone(),
-                program_signer_info.clone(),
-                payer_info.clone(),
-            ],
-            &[program_signer_seeds],
-        )?;
+        let token_mint_authority_signer_seeds: &[&[_]] = &[
+            stake_pool_info.key.as_ref(),
+            AUTHORITY_WITHDRAW,
+            &[stake_withdraw_bump_seed],
+        ];
 
-        // Verify dest_user_pool_info is the correct ATA for user_wallet
-        let expected_ata =
-            spl_associated_token_account::get_associated_token_address_with_program_id(
-                user_wallet_info.key,
-                pool_mint_info.key,
-                token_program_info.key,
-            );
-        if *dest_user_pool_info.key != expected_ata {
-            msg!("dest_user_pool is not the ATA for user_wallet");
-            return Err(ProgramError::InvalidAccountData);
-        }
+        invoke_signed(
+            &update_metadata_accounts_instruction,
+            &[metadata_info.clone(), withdraw_authority_info.clone()],
+            &[token_mint_authority_signer_seeds],
+        )?;
 
-        let ata_creation_cost = rent
-            .minimum_balance(spl_token::state::Account::LEN)
-            .saturating_sub(dest_user_pool_info.lamports());
+        Ok(())
+    }
 
-        if deposit_lamports < ata_creation_cost {
-            msg!("Deposit too small to cover ATA rent");
-            return Err(StakePoolError::DepositTooSmall.into());
+    /// Checks the accounts shared by the Token-2022 metadata extension
+    /// instructions, returning the withdraw authority bump seed
+    fn check_token_metadata_extension_accounts(
+        program_id: &Pubkey,
+        stake_pool_info: &AccountInfo,
+        manager_info: &AccountInfo,
+        withdraw_authority_info: &AccountInfo,
+        pool_mint_info: &AccountInfo,
+        token_program_info: &AccountInfo,
+    ) -> Result<u8, ProgramError> {
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
 
-        if ata_creation_cost > 0 {
-            invoke_signed(
-                &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                    program_signer_info.key,
-                    user_wallet_info.key,
-                    pool_mint_info.key,
-                    token_program_info.key,
-                ),
+        stake_pool.check_manager(manager_info)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_mint(pool_mint_info)?;
+        if stake_pool.token_program_id != *token_program_info.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        check_pool_mint_metadata_pointer(pool_mint_info)?;
+
+        let (_, stake_withdraw_bump_seed) =
+            crate::find_withdraw_authority_program_address(program_id, stake_pool_info.key);
+        Ok(stake_withdraw_bump_seed)
+    }
+
+    /// Tops up the pool mint to the rent-exempt minimum of its current size,
+    /// after its token metadata grew
+    fn fund_pool_mint_rent<'a>(
+        payer_info: &AccountInfo<'a>,
+        pool_mint_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let minimum_balance = Rent::get()?.minimum_balance(pool_mint_info.data_len());
+        let shortfall = minimum_balance.saturating_sub(pool_mint_info.lamports());
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, pool_mint_info.key, shortfall),
                 &[
-                    program_signer_info.clone(),
-                    dest_user_pool_info.clone(),
-                    user_wallet_info.clone(),
+                    payer_info.clone(),
                     pool_mint_info.clone(),
                     system_program_info.clone(),
-                    token_program_info.clone(),
                 ],
-                &[program_signer_seeds],
             )?;
         }
+        Ok(())
+    }
 
-        let effective_deposit = deposit_lamports - ata_creation_cost;
+    /// Processes [`CreateTokenMetadataExtension`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_create_token_metadata_extension(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        if !payer_info.is_signer {
+            msg!("Payer did not sign metadata creation");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        check_system_program(system_program_info.key)?;
+        let stake_withdraw_bump_seed = Self::check_token_metadata_extension_accounts(
+            program_id,
+            stake_pool_info,
+            manager_info,
+            withdraw_authority_info,
+            pool_mint_info,
+            token_program_info,
+        )?;
 
+        // The withdraw authority is both the mint authority and the update
+        // authority of the metadata
+        let token_mint_authority_signer_seeds: &[&[_]] = &[
+            stake_pool_info.key.as_ref(),
+            AUTHORITY_WITHDRAW,
+            &[stake_withdraw_bump_seed],
+        ];
         invoke_signed(
-            &system_instruction::transfer(
-                program_signer_info.key,
-                reserve_stake_account_info.key,
-                effective_deposit,
+            &token_metadata_instruction::initialize(
+                token_program_info.key,
+                pool_mint_info.key,
+                withdraw_authority_info.key,
+                pool_mint_info.key,
+                withdraw_authority_info.key,
+                name,
+                symbol,
+                uri,
             ),
-            &[
-                system_program_info.clone(),
-                program_signer_info.clone(),
-                reserve_stake_account_info.clone(),
-            ],
-            &[program_signer_seeds],
+            &[pool_mint_info.clone(), withdraw_authority_info.clone()],
+            &[token_mint_authority_signer_seeds],
         )?;
 
-        let mut new_accounts = vec![
-            stake_pool_info.clone(),
-            withdraw_authority_info.clone(),
-            reserve_stake_account_info.clone(),
-            signer_or_session_info.clone(),
-            dest_user_pool_info.clone(),
-            manager_fee_info.clone(),
-            referrer_fee_info.clone(),
-            pool_mint_info.clone(),
-            system_program_info.clone(),
-            token_program_info.clone(),
-        ];
+        Self::fund_pool_mint_rent(payer_info, pool_mint_info, system_program_info)
+    }
 
-        if let Ok(sol_deposit_authority_info) = sol_deposit_authority_info {
-            new_accounts.push(sol_deposit_authority_info.clone());
-        }
+    /// Processes [`UpdateTokenMetadataExtensionField`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_update_token_metadata_extension_field(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        field: String,
+        value: String,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        Self::process_deposit_sol(
+        if !payer_info.is_signer {
+            msg!("Payer did not sign metadata update");
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        check_system_program(system_program_info.key)?;
+        let stake_withdraw_bump_seed = Self::check_token_metadata_extension_accounts(
             program_id,
-            &new_accounts,
-            effective_deposit,
-            minimum_pool_tokens_out,
-            // the SOL transfer has already been done above
-            true,
-        )
+            stake_pool_info,
+            manager_info,
+            withdraw_authority_info,
+            pool_mint_info,
+            token_program_info,
+        )?;
+
+        let field = match field.as_str() {
+            "name" => Field::Name,
+            "symbol" => Field::Symbol,
+            "uri" => Field::Uri,
+            _ => Field::Key(field),
+        };
+        let update_authority_signer_seeds: &[&[_]] = &[
+            stake_pool_info.key.as_ref(),
+            AUTHORITY_WITHDRAW,
+            &[stake_withdraw_bump_seed],
+        ];
+        invoke_signed(
+            &token_metadata_instruction::update_field(
+                token_program_info.key,
+                pool_mint_info.key,
+                withdraw_authority_info.key,
+                field,
+                value,
+            ),
+            &[pool_mint_info.clone(), withdraw_authority_info.clone()],
+            &[update_authority_signer_seeds],
+        )?;
+
+        Self::fund_pool_mint_rent(payer_info, pool_mint_info, system_program_info)
     }
 
-    /// Processes [`DepositSol`](enum.Instruction.html).
+    /// Processes [`RemoveTokenMetadataExtensionKey`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_deposit_sol(
+    fn process_remove_token_metadata_extension_key(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        deposit_lamports: u64,
-        minimum_pool_tokens_out: Option<u64>,
-        is_wsol_path: bool,
+        key: String,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
         let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let reserve_stake_account_info = next_account_info(account_info_iter)?;
-        let from_user_lamports_info = next_account_info(account_info_iter)?;
-        let dest_user_pool_info = next_account_info(account_info_iter)?;
-        let manager_fee_info = next_account_info(account_info_iter)?;
-        let referrer_fee_info = next_account_info(account_info_iter)?;
         let pool_mint_info = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
         let token_program_info = next_account_info(account_info_iter)?;
-        let sol_deposit_authority_info = next_account_info(account_info_iter);
 
-        let clock = Clock::get()?;
+        let stake_withdraw_bump_seed = Self::check_token_metadata_extension_accounts(
+            program_id,
+            stake_pool_info,
+            manager_info,
+            withdraw_authority_info,
+            pool_mint_info,
+            token_program_info,
+        )?;
+
+        let update_authority_signer_seeds: &[&[_]] = &[
+            stake_pool_info.key.as_ref(),
+            AUTHORITY_WITHDRAW,
+            &[stake_withdraw_bump_seed],
+        ];
+        invoke_signed(
+            &token_metadata_instruction::remove_key(
+                token_program_info.key,
+                pool_mint_info.key,
+                withdraw_authority_info.key,
+                key,
+                true,
+            ),
+            &[pool_mint_info.clone(), withdraw_authority_info.clone()],
+            &[update_authority_signer_seeds],
+        )?;
+
+        Ok(())
+    }
+
+    /// Processes [`SetManager`](enum.Instruction.html).
+    #[cfg(feature = "legacy-authority-transfer")]
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let new_manager_info = next_account_info(account_info_iter)?;
+        let new_manager_fee_info = next_account_info(account_info_iter)?;
 
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        check_account_owner(new_manager_fee_info, &stake_pool.token_program_id)?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
 
-        stake_pool.check_authority_withdraw(
-            withdraw_authority_info.key,
-            program_id,
-            stake_pool_info.key,
-        )?;
-        stake_pool.check_sol_deposit_authority(sol_deposit_authority_info)?;
-        stake_pool.check_mint(pool_mint_info)?;
-        stake_pool.check_reserve_stake(reserve_stake_account_info)?;
-
-        if stake_pool.token_program_id != *token_program_info.key {
-            return Err(ProgramError::IncorrectProgramId);
+        stake_pool.check_manager(manager_info)?;
+        if !new_manager_info.is_signer {
+            msg!("New manager signature missing");
+            return Err(StakePoolError::SignatureMissing.into());
         }
-        check_system_program(system_program_info.key)?;
 
-        if stake_pool.manager_fee_account != *manager_fee_info.key {
-            return Err(StakePoolError::InvalidFeeAccount.into());
-        }
-        // There is no bypass if the manager fee account is invalid. Deposits
-        // don't hold user funds hostage, so if the fee account is invalid, users
-        // cannot deposit in the pool.  Let it fail here!
+        stake_pool.check_manager_fee_info(new_manager_fee_info)?;
 
-        // We want this to hold to ensure that deposit_sol mints pool tokens
-        // at the right price
-        if stake_pool.last_update_epoch < clock.epoch {
-            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        stake_pool.manager = *new_manager_info.key;
+        stake_pool.manager_fee_account = *new_manager_fee_info.key;
+        stake_pool.pending_manager = None;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::AuthorityChanged {
+            stake_pool: *stake_pool_info.key,
+            authority_type: AuthorityType::Manager,
+            new_authority: Some(stake_pool.manager),
         }
+        .emit();
+        Ok(())
+    }
 
-        let new_pool_tokens = stake_pool
-            .calc_pool_tokens_for_deposit(deposit_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
-
-        let pool_tokens_sol_deposit_fee = stake_pool
-            .calc_pool_tokens_sol_deposit_fee(new_pool_tokens)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let pool_tokens_user = new_pool_tokens
-            .checked_sub(pool_tokens_sol_deposit_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
-
-        let pool_tokens_referral_fee = stake_pool
-            .calc_pool_tokens_sol_referral_fee(pool_tokens_sol_deposit_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let pool_tokens_manager_deposit_fee = pool_tokens_sol_deposit_fee
-            .checked_sub(pool_tokens_referral_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
-
-        if pool_tokens_user
-            .saturating_add(pool_tokens_manager_deposit_fee)
-            .saturating_add(pool_tokens_referral_fee)
-            != new_pool_tokens
-        {
-            return Err(StakePoolError::CalculationFailure.into());
-        }
+    /// Processes [`SetFee`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        fee: FeeType,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let clock = Clock::get()?;
 
-        if pool_tokens_user == 0 {
-            return Err(StakePoolError::DepositTooSmall.into());
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_manager(manager_info)?;
 
-        if let Some(minimum_pool_tokens_out) = minimum_pool_tokens_out {
-            if pool_tokens_user < minimum_pool_tokens_out {
-                return Err(StakePoolError::ExceededSlippage.into());
-            }
+        if fee.can_only_change_next_epoch() && stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
         }
 
-        if !is_wsol_path {
-            Self::sol_transfer(
-                from_user_lamports_info.clone(),
-                reserve_stake_account_info.clone(),
-                deposit_lamports,
-            )?;
+        fee.check_too_high()?;
+        stake_pool.update_fee(&fee, clock.epoch)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::FeeChanged {
+            stake_pool: *stake_pool_info.key,
+            fee,
         }
+        .emit();
+        Ok(())
+    }
 
-        Self::token_mint_to(
-            stake_pool_info.key,
-            token_program_info.clone(),
-            pool_mint_info.clone(),
-            dest_user_pool_info.clone(),
-            withdraw_authority_info.clone(),
-            AUTHORITY_WITHDRAW,
-            stake_pool.stake_withdraw_bump_seed,
-            pool_tokens_user,
-        )?;
+    /// Processes [`SetFeeChangeDelay`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_fee_change_delay(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        delay_epochs: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let clock = Clock::get()?;
 
-        if pool_tokens_manager_deposit_fee > 0 {
-            Self::token_mint_to(
-                stake_pool_info.key,
-                token_program_info.clone(),
-                pool_mint_info.clone(),
-                manager_fee_info.clone(),
-                withdraw_authority_info.clone(),
-                AUTHORITY_WITHDRAW,
-                stake_pool.stake_withdraw_bump_seed,
-                pool_tokens_manager_deposit_fee,
-            )?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_manager(manager_info)?;
 
-        if pool_tokens_referral_fee > 0 {
-            Self::token_mint_to(
-                stake_pool_info.key,
-                token_program_info.clone(),
-                pool_mint_info.clone(),
-                referrer_fee_info.clone(),
-                withdraw_authority_info.clone(),
-                AUTHORITY_WITHDRAW,
-                stake_pool.stake_withdraw_bump_seed,
-                pool_tokens_referral_fee,
-            )?;
+        if stake_pool.last_update_epoch < clock.epoch {
+            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
         }
 
-        stake_pool.pool_token_supply = stake_pool
-            .pool_token_supply
-            .checked_add(new_pool_tokens)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        stake_pool.total_lamports = stake_pool
-            .total_lamports
-            .checked_add(deposit_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.update_fee_change_delay(delay_epochs, clock.epoch)?;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
-
+        StakePoolEvent::FeeChangeDelayChanged {
+            stake_pool: *stake_pool_info.key,
+            delay_epochs,
+        }
+        .emit();
         Ok(())
     }
 
-    /// Processes [`WithdrawStake`](enum.Instruction.html).
+    /// Processes [`RegisterReferrer`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_withdraw_stake(
+    fn process_register_referrer(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        pool_tokens: u64,
-        minimum_lamports_out: Option<u64>,
-        user_stake_seed: Option<u64>,
+        code: String,
     ) -> ProgramResult {
+        use spl_associated_token_account::tools::account::create_pda_account;
+
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let validator_list_info = next_account_info(account_info_iter)?;
-        let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let stake_split_from = next_account_info(account_info_iter)?;
-        let stake_split_to = next_account_info(account_info_iter)?;
-        let user_stake_authority_info = next_account_info(account_info_iter)?;
-        let user_transfer_authority_info = next_account_info(account_info_iter)?;
-        let burn_from_pool_info = next_account_info(account_info_iter)?;
-        let manager_fee_info = next_account_info(account_info_iter)?;
-        let pool_mint_info = next_account_info(account_info_iter)?;
-        let clock_info = next_account_info(account_info_iter)?;
-        let clock = &Clock::from_account_info(clock_info)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let stake_program_info = next_account_info(account_info_iter)?;
+        let referrer_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let payout_token_account_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        check_stake_program(stake_program_info.key)?;
+        check_system_program(system_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
 
-        let decimals = stake_pool.check_mint(pool_mint_info)?;
-        stake_pool.check_validator_list(validator_list_info)?;
-        stake_pool.check_authority_withdraw(
-            withdraw_authority_info.key,
-            program_id,
-            stake_pool_info.key,
-        )?;
+        if !authority_info.is_signer || !payer_info.is_signer {
+            return Err(StakePoolError::SignatureMissing.into());
+        }
 
-        if stake_pool.manager_fee_account != *manager_fee_info.key {
-            return Err(StakePoolError::InvalidFeeAccount.into());
+        let packed_code = Referrer::pack_code(&code)?;
+        let (expected_referrer_address, referrer_bump) =
+            find_referrer_program_address(program_id, stake_pool_info.key, &code);
+        if expected_referrer_address != *referrer_info.key {
+            msg!("Invalid referrer PDA");
+            return Err(ProgramError::InvalidSeeds);
         }
-        if stake_pool.token_program_id != *token_program_info.key {
-            return Err(ProgramError::IncorrectProgramId);
+        if referrer_info.owner == program_id {
+            msg!("Referral code {} is already registered", code);
+            return Err(StakePoolError::AlreadyInUse.into());
         }
 
-        if stake_pool.last_update_epoch < clock.epoch {
-            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
-        }
+        // the payout account must be able to receive minted referral fees
+        stake_pool.check_manager_fee_info(payout_token_account_info)?;
 
-        check_account_owner(validator_list_info, program_id)?;
-        let mut validator_list_data = validator_list_info.data.borrow_mut();
-        let (header, mut validator_list) =
-            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
-        if !header.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
-        }
+        let referrer_signer_seeds: &[&[u8]] = &[
+            REFERRER_SEED_PREFIX,
+            stake_pool_info.key.as_ref(),
+            code.as_bytes(),
+            &[referrer_bump],
+        ];
+        create_pda_account(
+            payer_info,
+            &Rent::get()?,
+            get_packed_len::<Referrer>(),
+            program_id,
+            system_program_info,
+            referrer_info,
+            referrer_signer_seeds,
+        )?;
 
-        // To prevent a faulty manager fee account from preventing withdrawals
-        // if the token program does not own the account, or if the account is not
-        // initialized
-        let pool_tokens_fee = if stake_pool.manager_fee_account == *burn_from_pool_info.key
-            || stake_pool.check_manager_fee_info(manager_fee_info).is_err()
-        {
-            0
-        } else {
-            stake_pool
-                .calc_pool_tokens_stake_withdrawal_fee(pool_tokens)
-                .ok_or(StakePoolError::CalculationFailure)?
+        let referrer = Referrer {
+            account_type: AccountType::Referrer,
+            stake_pool: *stake_pool_info.key,
+            authority: *authority_info.key,
+            code: packed_code,
+            payout_token_account: *payout_token_account_info.key,
+            referred_lamports: 0,
+            earned_pool_tokens: 0,
         };
-        let pool_tokens_burnt = pool_tokens
-            .checked_sub(pool_tokens_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut referrer_info.data.borrow_mut()[..], &referrer)?;
+        Ok(())
+    }
 
-        let mut withdraw_lamports = stake_pool
-            .calc_lamports_withdraw_amount(pool_tokens_burnt)
-            .ok_or(StakePoolError::CalculationFailure)?;
+    /// Processes [`UpdateReferrer`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_update_referrer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let referrer_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let payout_token_account_info = next_account_info(account_info_iter)?;
+        let maybe_new_authority_info = next_account_info(account_info_iter).ok();
 
-        if withdraw_lamports == 0 {
-            return Err(StakePoolError::WithdrawalTooSmall.into());
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
 
-        if let Some(minimum_lamports_out) = minimum_lamports_out {
-            if withdraw_lamports < minimum_lamports_out {
-                return Err(StakePoolError::ExceededSlippage.into());
-            }
+        check_account_owner(referrer_info, program_id)?;
+        let mut referrer = try_from_slice_unchecked::<Referrer>(&referrer_info.data.borrow())?;
+        if !referrer.is_valid() || referrer.stake_pool != *stake_pool_info.key {
+            return Err(StakePoolError::InvalidReferrer.into());
+        }
+        if referrer.authority != *authority_info.key || !authority_info.is_signer {
+            msg!("Referrer authority signature missing");
+            return Err(StakePoolError::SignatureMissing.into());
         }
 
-        let stake_minimum_delegation = stake::tools::get_minimum_delegation()?;
-        let stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-            &stake_split_from.data.borrow(),
-        )?;
-        let meta = stake_state.meta().ok_or(StakePoolError::WrongStakeStake)?;
-        let required_lamports = minimum_stake_lamports(&meta, stake_minimum_delegation);
-
-        let lamports_per_pool_token = stake_pool
-            .get_lamports_per_pool_token()
-            .ok_or(StakePoolError::CalculationFailure)?;
-        let minimum_lamports_with_tolerance =
-            required_lamports.saturating_add(lamports_per_pool_token);
+        stake_pool.check_manager_fee_info(payout_token_account_info)?;
+        referrer.payout_token_account = *payout_token_account_info.key;
+        if let Some(new_authority_info) = maybe_new_authority_info {
+            referrer.authority = *new_authority_info.key;
+        }
+        borsh::to_writer(&mut referrer_info.data.borrow_mut()[..], &referrer)?;
+        Ok(())
+    }
 
-        let has_active_stake = validator_list
-            .find::<ValidatorStakeInfo, _>(|x| {
-                ValidatorStakeInfo::active_lamports_greater_than(
-                    x,
-                    &minimum_lamports_with_tolerance,
-                ) && ValidatorStakeInfo::is_active(x)
-            })
-            .is_some();
-        let has_transient_stake = validator_list
-            .find::<ValidatorStakeInfo, _>(|x| {
-                ValidatorStakeInfo::transient_lamports_greater_than(
-                    x,
-                    &minimum_lamports_with_tolerance,
-                ) && ValidatorStakeInfo::is_active(x)
-            })
-            .is_some();
+    /// Processes [`SetCap`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_cap(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        cap: CapType,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
 
-        let validator_list_item_info = if *stake_split_from.key == stake_pool.reserve_stake {
-            // check that the validator stake accounts have no withdrawable stake
-            if has_transient_stake || has_active_stake {
-                msg!("Error withdrawing from reserve: validator stake accounts have lamports available, please use those first.");
-                return Err(StakePoolError::StakeLamportsNotEqualToMinimum.into());
-            }
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_manager(manager_info)?;
 
-            // check that reserve has enough
-            let minimum_reserve_lamports = minimum_reserve_lamports(&meta);
-            if stake_split_from
-                .lamports()
-                .saturating_sub(withdraw_lamports)
-                < minimum_reserve_lamports
-            {
-                msg!("Attempting to withdraw {} lamports, maximum possible SOL withdrawal is {} lamports",
-                    withdraw_lamports,
-                    stake_split_from.lamports().saturating_sub(minimum_reserve_lamports)
+        if let CapType::DirectStakeShare(Some(max_share_bps))
+        | CapType::ValidatorShare(Some(max_share_bps)) = cap
+        {
+            if max_share_bps > MAX_BASIS_POINTS {
+                msg!(
+                    "A share of the pool cannot exceed {} basis points",
+                    MAX_BASIS_POINTS
                 );
-                return Err(StakePoolError::SolWithdrawalTooLarge.into());
+                return Err(ProgramError::InvalidInstructionData);
             }
-            None
-        } else {
-            let delegation = stake_state
-                .delegation()
-                .ok_or(StakePoolError::WrongStakeStake)?;
-            let vote_account_address = delegation.voter_pubkey;
-
-            if let Some(preferred_withdraw_validator) =
-                stake_pool.preferred_withdraw_validator_vote_address
-            {
-                // Defensive check, in case the preferred validator was somehow
-                // removed.
-                if let Some(preferred_validator_info) = validator_list
-                    .find::<ValidatorStakeInfo, _>(|x| {
-                        ValidatorStakeInfo::memcmp_pubkey(x, &preferred_withdraw_validator)
-                    })
-                {
-                    let available_lamports =
-                        u64::from(preferred_validator_info.active_stake_lamports)
-                            .saturating_sub(minimum_lamports_with_tolerance);
-                    if preferred_withdraw_validator != vote_account_address
-                        && available_lamports > 0
-                    {
-                        msg!("Validator vote address {} is preferred for withdrawals, it currently has {} lamports available. Please withdraw those before using other validator stake accounts.", preferred_withdraw_validator, u64::from(preferred_validator_info.active_stake_lamports));
-                        return Err(StakePoolError::IncorrectWithdrawVoteAddress.into());
-                    }
-                } else {
-                    msg!("Preferred withdraw validator not found, allowing withdrawal from any validator");
-                }
+        }
+        if let CapType::ValidatorCommission(Some(max_commission)) = cap {
+            if max_commission > MAX_COMMISSION {
+                msg!("A commission cannot exceed {}%", MAX_COMMISSION);
+                return Err(ProgramError::InvalidInstructionData);
             }
+        }
+        if cap == CapType::ValidatorVoteAge(Some(0)) {
+            msg!("Validators need at least one epoch to vote");
+            return Err(ProgramError::InvalidInstructionData);
+        }
 
-            let validator_stake_info = validator_list
-                .find_mut::<ValidatorStakeInfo, _>(|x| {
-                    ValidatorStakeInfo::memcmp_pubkey(x, &vote_account_address)
-                })
-                .ok_or(StakePoolError::ValidatorNotFound)?;
-
-            let withdraw_source = if has_active_stake {
-                // if there's any active stake, we must withdraw from an active
-                // stake account
-                check_validator_stake_address(
-                    program_id,
-                    stake_pool_info.key,
-                    stake_split_from.key,
-                    &vote_account_address,
-                    NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
-                )?;
-                StakeWithdrawSource::Active
-            } else if has_transient_stake
-                || validator_stake_info.transient_stake_lamports != 0.into()
-            {
-                // if there's any transient stake, we must withdraw from there
-                // Be particularly cautious to avoid removing a validator with
-                // transient lamports tied to it
-                check_transient_stake_address(
-                    program_id,
-                    stake_pool_info.key,
-                    stake_split_from.key,
-                    &vote_account_address,
-                    validator_stake_info.transient_seed_suffix.into(),
-                )?;
-                StakeWithdrawSource::Transient
-            } else {
-                // if there's no active or transient stake, we can take the whole account
-                check_validator_stake_address(
-                    program_id,
-                    stake_pool_info.key,
-                    stake_split_from.key,
-                    &vote_account_address,
-                    NonZeroU32::new(validator_stake_info.validator_seed_suffix.into()),
-                )?;
-                StakeWithdrawSource::ValidatorRemoval
-            };
-
-            if validator_stake_info.status != StakeStatus::Active.into() {
-                msg!("Validator is marked for removal and no longer allowing withdrawals");
-                return Err(StakePoolError::ValidatorNotFound.into());
-            }
+        stake_pool.update_cap(&cap);
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::CapChanged {
+            stake_pool: *stake_pool_info.key,
+            cap,
+        }
+        .emit();
+        Ok(())
+    }
 
-            match withdraw_source {
-                StakeWithdrawSource::Active | StakeWithdrawSource::Transient => {
-                    let remaining_lamports = stake_split_from
-                        .lamports()
-                        .saturating_sub(withdraw_lamports);
-                    if remaining_lamports < required_lamports {
-                        msg!("Attempting to withdraw {} lamports from validator account with {} stake lamports, {} must remain", withdraw_lamports, stake_split_from.lamports(), required_lamports);
-                        return Err(StakePoolError::StakeLamportsNotEqualToMinimum.into());
-                    }
-                }
-                StakeWithdrawSource::ValidatorRemoval => {
-                    let split_from_lamports = stake_split_from.lamports();
-                    let upper_bound = split_from_lamports.saturating_add(lamports_per_pool_token);
-                    if withdraw_lamports < split_from_lamports || withdraw_lamports > upper_bound {
-                        msg!(
-                            "Cannot withdraw a whole account worth {} lamports, \
-                              must withdraw at least {} lamports worth of pool tokens \
-                              with a margin of {} lamports",
-                            withdraw_lamports,
-                            split_from_lamports,
-                            lamports_per_pool_token
-                        );
-                        return Err(StakePoolError::StakeLamportsNotEqualToMinimum.into());
-                    }
-                    // truncate the lamports down to the amount in the account
-                    withdraw_lamports = split_from_lamports;
-
-                    // reset the preferred validator if needed
-                    if stake_pool.preferred_deposit_validator_vote_address
-                        == Some(vote_account_address)
-                    {
-                        stake_pool.preferred_deposit_validator_vote_address = None;
-                    }
-                    if stake_pool.preferred_withdraw_validator_vote_address
-                        == Some(vote_account_address)
-                    {
-                        stake_pool.preferred_withdraw_validator_vote_address = None;
-                    }
-                }
-            }
-            Some((validator_stake_info, withdraw_source))
-        };
+    /// Processes [`SetValidatorMaxShare`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_validator_max_share(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_share_bps: Option<u16>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let validator_vote_info = next_account_info(account_info_iter)?;
 
-        // WithdrawStakeWithSession path - create user stake account PDA
-        // Tuple: (user_pubkey, program_signer_bump, program_signer_info, signer_or_session_info, split_lamports)
-        let session_pda_info: Option<(Pubkey, u8, AccountInfo, AccountInfo, u64)> =
-            if let Some(seed) = user_stake_seed {
-                use crate::USER_STAKE_SEED_PREFIX;
-                use fogo_sessions_sdk::session::Session;
-                use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_manager(manager_info)?;
+        stake_pool.check_validator_list(validator_list_info)?;
+        check_account_owner(validator_list_info, program_id)?;
 
-                let is_validator_removal = matches!(
-                    &validator_list_item_info,
-                    Some((_, StakeWithdrawSource::ValidatorRemoval))
+        if let Some(max_share_bps) = max_share_bps {
+            if max_share_bps == 0 || max_share_bps > MAX_BASIS_POINTS {
+                msg!(
+                    "A validator's share of the pool must be between 1 and {} basis points",
+                    MAX_BASIS_POINTS
                 );
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
 
-                let program_signer_info = next_account_info(account_info_iter)?;
-                let system_program_info = next_account_info(account_info_iter)?;
-                let reserve_stake_info = next_account_info(account_info_iter)?;
-                let stake_history_info = next_account_info(account_info_iter)?;
-
-                check_system_program(system_program_info.key)?;
+        let mut validator_list_data = validator_list_info.data.borrow_mut();
+        let (header, mut validator_list) =
+            ValidatorListHeader::deserialize_vec(&mut validator_list_data)?;
+        if !header.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        let validator_stake_info = validator_list
+            .find_mut::<ValidatorStakeInfo, _>(|x| {
+                ValidatorStakeInfo::memcmp_pubkey(x, validator_vote_info.key)
+            })
+            .ok_or_else(|| {
+                msg!(
+                    "Vote account {} not found in stake pool",
+                    validator_vote_info.key
+                );
+                StakePoolError::ValidatorNotFound
+            })?;
+        validator_stake_info.max_share_bps = u32::from(max_share_bps.unwrap_or_default()).into();
+        StakePoolEvent::ValidatorMaxShareChanged {
+            stake_pool: *stake_pool_info.key,
+            vote_account: *validator_vote_info.key,
+            max_share_bps,
+        }
+        .emit();
+        Ok(())
+    }
 
-                if stake_pool.reserve_stake != *reserve_stake_info.key {
-                    msg!("Invalid reserve stake account");
-                    return Err(StakePoolError::InvalidProgramAddress.into());
-                }
+    /// Processes [`UpdateValidatorMetadata`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_update_validator_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        notes_hash: [u8; 32],
+        max_commission: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let staker_info = next_account_info(account_info_iter)?;
+        let validator_metadata_info = next_account_info(account_info_iter)?;
 
-                let (expected_program_signer, program_signer_bump) =
-                    Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
-                if expected_program_signer != *program_signer_info.key {
-                    msg!("Invalid program signer account");
-                    return Err(ProgramError::InvalidSeeds);
-                }
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_staker(staker_info)?;
 
-                let signer_or_session_info = user_stake_authority_info;
-                let user_pubkey = Session::extract_user_from_signer_or_session(
-                    signer_or_session_info,
-                    program_id,
-                )?;
+        if max_commission > MAX_COMMISSION {
+            msg!("A commission cannot exceed {}%", MAX_COMMISSION);
+            return Err(ProgramError::InvalidInstructionData);
+        }
 
-                // Validate stake account PDA
-                let (expected_stake_pda, stake_pda_bump) = Pubkey::find_program_address(
-                    &[
-                        USER_STAKE_SEED_PREFIX,
-                        user_pubkey.as_ref(),
-                        &seed.to_le_bytes(),
-                    ],
-                    program_id,
-                );
-                if expected_stake_pda != *stake_split_to.key {
-                    msg!("Invalid stake account PDA");
-                    return Err(ProgramError::InvalidSeeds);
-                }
+        check_account_owner(validator_metadata_info, program_id)?;
+        let mut validator_metadata =
+            try_from_slice_unchecked::<ValidatorMetadata>(&validator_metadata_info.data.borrow())?;
+        if !validator_metadata.is_valid() || validator_metadata.stake_pool != *stake_pool_info.key {
+            return Err(StakePoolError::InvalidValidatorMetadata.into());
+        }
 
-                // Calculate rent for stake account
-                let stake_space = stake::state::StakeStateV2::size_of();
-                let stake_rent = Rent::get()?.minimum_balance(stake_space);
+        validator_metadata.notes_hash = notes_hash;
+        validator_metadata.max_commission = max_commission;
+        borsh::to_writer(
+            &mut validator_metadata_info.data.borrow_mut()[..],
+            &validator_metadata,
+        )?;
+        StakePoolEvent::ValidatorMetadataChanged {
+            stake_pool: *stake_pool_info.key,
+            vote_account: validator_metadata.vote_account,
+            notes_hash,
+            max_commission,
+        }
+        .emit();
+        Ok(())
+    }
 
-                let stake_pda_seeds: &[&[u8]] = &[
-                    USER_STAKE_SEED_PREFIX,
-                    user_pubkey.as_ref(),
-                    &seed.to_le_bytes(),
-                    &[stake_pda_bump],
-                ];
+    /// Processes [`DeactivateHighCommissionValidator`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_deactivate_high_commission_validator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let transient_stake_account_info = next_account_info(account_info_iter)?;
+        let validator_vote_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+        let maybe_validator_metadata_info = next_account_info(account_info_iter).ok();
 
-                // Create stake account if needed
-                // - System-owned: new account or GC'd zombie, create it
-                // - Stake-owned + Uninitialized: zombie not yet GC'd, safe to reuse
-                // - Stake-owned + Initialized: active stake, reject
-                if stake_split_to.owner == &system_program::id() {
-                    create_stake_account(stake_split_to.clone(), stake_pda_seeds, stake_space)?;
-                } else if stake_split_to.owner == &stake::program::id() {
-                    let stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-                        &stake_split_to.data.borrow(),
-                    )?;
-                    if !matches!(stake_state, stake::state::StakeStateV2::Uninitialized) {
-                        msg!("Stake account PDA already in use. Use a different seed.");
-                        return Err(StakePoolError::AlreadyInUse.into());
-                    }
-                    // Zombie account - already allocated and assigned, skip create
-                } else {
-                    msg!("Stake account PDA has invalid owner");
-                    return Err(ProgramError::IllegalOwner);
-                }
+        check_stake_program(stake_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
 
-                let split_lamports = if is_validator_removal {
-                    // Full balance includes rent, no additional funding needed
-                    withdraw_lamports
-                } else {
-                    if stake_rent >= reserve_stake_info.lamports() {
-                        return Err(StakePoolError::ReserveDepleted.into());
-                    }
-                    Self::stake_withdraw(
-                        stake_pool_info.key,
-                        reserve_stake_info.clone(),
-                        withdraw_authority_info.clone(),
-                        AUTHORITY_WITHDRAW,
-                        stake_pool.stake_withdraw_bump_seed,
-                        stake_split_to.clone(),
-                        clock_info.clone(),
-                        stake_history_info.clone(),
-                        stake_rent,
-                    )?;
-                    withdraw_lamports.saturating_sub(stake_rent)
-                };
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_not_paused(PausedOperation::Rebalance)?;
 
-                Some((
-                    user_pubkey,
-                    program_signer_bump,
-                    program_signer_info.clone(),
-                    signer_or_session_info.clone(),
-                    split_lamports,
-                ))
-            } else {
-                None
-            };
+        let (_, stake) = get_stake_state(stake_account_info)?;
+        if stake.delegation.voter_pubkey != *validator_vote_info.key {
+            msg!(
+                "Stake account is delegated to {}, not {}",
+                stake.delegation.voter_pubkey,
+                validator_vote_info.key
+            );
+            return Err(StakePoolError::InvalidStakeAccountAddress.into());
+        }
 
-        let actual_split_amount = if let Some((_, _, _, _, split_lamports)) = &session_pda_info {
-            *split_lamports
-        } else {
-            withdraw_lamports
+        let maybe_validator_metadata = maybe_validator_metadata_info
+            .map(|validator_metadata_info| {
+                get_validator_metadata(
+                    program_id,
+                    stake_pool_info.key,
+                    validator_vote_info.key,
+                    validator_metadata_info,
+                )
+            })
+            .transpose()?
+            .flatten();
+        let max_commission = match maybe_validator_metadata {
+            Some(validator_metadata) => validator_metadata.max_commission(&stake_pool),
+            None => stake_pool
+                .max_validator_commission
+                .unwrap_or(MAX_COMMISSION),
         };
+        let commission = get_vote_commission(validator_vote_info)?;
+        if commission <= max_commission {
+            msg!(
+                "Validator commission of {}% is within the maximum of {}%",
+                commission,
+                max_commission
+            );
+            return Err(StakePoolError::CommissionWithinLimit.into());
+        }
 
-        Self::stake_split(
-            stake_pool_info.key,
-            stake_split_from.clone(),
-            withdraw_authority_info.clone(),
-            AUTHORITY_WITHDRAW,
-            stake_pool.stake_withdraw_bump_seed,
-            actual_split_amount,
-            stake_split_to.clone(),
+        let vote_account_address = Self::deactivate_validator(
+            program_id,
+            stake_pool,
+            stake_pool_info,
+            withdraw_authority_info,
+            validator_list_info,
+            stake_account_info,
+            transient_stake_account_info,
+            clock_info,
         )?;
+        Self::record_offboarding_reason(
+            program_id,
+            stake_pool_info.key,
+            &vote_account_address,
+            maybe_validator_metadata_info,
+            OffboardingReason::CommissionTooHigh,
+        )
+    }
 
-        // WithdrawStakeWithSession path - use session for token ops, set PDA as its own authority
-        if let Some((
-            user_pubkey,
-            program_signer_bump,
-            program_signer_info,
-            signer_or_session_info,
-            _split_lamports,
-        )) = session_pda_info
-        {
-            use fogo_sessions_sdk::token::instruction::{burn, transfer_checked};
-            use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
-            use solana_program::program_pack::Pack;
-
-            // Validate burn_from_pool is owned by the session user
-            let burn_from_pool_data =
-                spl_token::state::Account::unpack(&burn_from_pool_info.data.borrow())?;
-            if burn_from_pool_data.owner != user_pubkey {
-                msg!("`burn_from_pool` is not owned by the session user");
-                return Err(ProgramError::InvalidAccountData);
-            }
+    /// Processes [`DeactivateDelinquentValidator`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_deactivate_delinquent_validator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let validator_list_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let transient_stake_account_info = next_account_info(account_info_iter)?;
+        let validator_vote_info = next_account_info(account_info_iter)?;
+        let reward_recipient_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+        let validator_metadata_info = next_account_info(account_info_iter)?;
 
-            let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
+        check_stake_program(stake_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
 
-            // Burn using session
-            invoke_signed(
-                &burn(
-                    token_program_info.key,
-                    burn_from_pool_info.key,
-                    pool_mint_info.key,
-                    signer_or_session_info.key,
-                    Some(program_signer_info.key),
-                    pool_tokens_burnt,
-                )?,
-                &[
-                    burn_from_pool_info.clone(),
-                    pool_mint_info.clone(),
-                    signer_or_session_info.clone(),
-                    program_signer_info.clone(),
-                ],
-                &[program_signer_seeds],
-            )?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_not_paused(PausedOperation::Rebalance)?;
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
+            program_id,
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
 
-            // Transfer fee using session
-            if pool_tokens_fee > 0 {
-                invoke_signed(
-                    &transfer_checked(
-                        token_program_info.key,
-                        burn_from_pool_info.key,
-                        pool_mint_info.key,
-                        manager_fee_info.key,
-                        signer_or_session_info.key,
-                        Some(program_signer_info.key),
-                        pool_tokens_fee,
-                        decimals,
-                    )?,
-                    &[
-                        burn_from_pool_info.clone(),
-                        pool_mint_info.clone(),
-                        manager_fee_info.clone(),
-                        signer_or_session_info.clone(),
-                        program_signer_info.clone(),
-                    ],
-                    &[program_signer_seeds],
-                )?;
-            }
+        let (_, stake) = get_stake_state(stake_account_info)?;
+        if stake.delegation.voter_pubkey != *validator_vote_info.key {
+            msg!(
+                "Stake account is delegated to {}, not {}",
+                stake.delegation.voter_pubkey,
+                validator_vote_info.key
+            );
+            return Err(StakePoolError::InvalidStakeAccountAddress.into());
+        }
 
-            // Only deactivate if the stake is active and not yet deactivated.
-            // stake_deactivate fails with AlreadyDeactivated if called on reserve/transient stake.
-            let split_to_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-                &stake_split_to.data.borrow(),
-            )?;
-            if let stake::state::StakeStateV2::Stake(_meta, stake, _) = split_to_state {
-                if stake.delegation.deactivation_epoch == Epoch::MAX {
-                    // Stake is active and not yet deactivated - deactivate it now
-                    Self::stake_deactivate(
-                        stake_split_to.clone(),
-                        clock_info.clone(),
-                        withdraw_authority_info.clone(),
-                        stake_pool_info.key,
-                        AUTHORITY_WITHDRAW,
-                        stake_pool.stake_withdraw_bump_seed,
-                    )?;
-                }
-            }
+        let Some(max_vote_age_epochs) = stake_pool.max_validator_vote_age_epochs else {
+            msg!("The pool does not deactivate delinquent validators");
+            return Err(StakePoolError::ValidatorNotDelinquent.into());
+        };
+        // a validator admitted recently gets the full vote age from its
+        // admission, even if it has not voted yet, so its metadata is required
+        let maybe_admission_epoch = get_validator_metadata(
+            program_id,
+            stake_pool_info.key,
+            validator_vote_info.key,
+            validator_metadata_info,
+        )?
+        .map(|validator_metadata| validator_metadata.admission_epoch);
+        let last_active_epoch = get_vote_last_credited_epoch(validator_vote_info)?
+            .max(maybe_admission_epoch)
+            .unwrap_or_default();
+        let vote_age_epochs = clock.epoch.saturating_sub(last_active_epoch);
+        if vote_age_epochs <= max_vote_age_epochs {
+            msg!(
+                "Validator last earned vote credits {} epochs ago, within the maximum of {}",
+                vote_age_epochs,
+                max_vote_age_epochs
+            );
+            return Err(StakePoolError::ValidatorNotDelinquent.into());
+        }
 
-            // Set stake authority to the PDA itself so it can sign the later withdrawal
-            Self::stake_authorize_signed(
+        // the reward is skipped rather than failing the crank when the
+        // reserve cannot spare it
+        let reserve_stake = try_from_slice_unchecked::<stake::state::StakeStateV2>(
+            &reserve_stake_info.data.borrow(),
+        )?;
+        let reserve_meta = reserve_stake
+            .meta()
+            .ok_or(StakePoolError::WrongStakeStake)?;
+        let minimum_lamports = minimum_reserve_lamports(&reserve_meta)
+            .saturating_add(stake_pool.withdrawal_claim_pool_lamports);
+        if reserve_stake_info
+            .lamports()
+            .saturating_sub(DELINQUENT_VALIDATOR_CRANK_REWARD)
+            >= minimum_lamports
+        {
+            Self::stake_withdraw(
                 stake_pool_info.key,
-                stake_split_to.clone(),
+                reserve_stake_info.clone(),
                 withdraw_authority_info.clone(),
                 AUTHORITY_WITHDRAW,
                 stake_pool.stake_withdraw_bump_seed,
-                stake_split_to.key, // The PDA becomes its own authority
+                reward_recipient_info.clone(),
                 clock_info.clone(),
+                stake_history_info.clone(),
+                DELINQUENT_VALIDATOR_CRANK_REWARD,
             )?;
-
-            // Update stake pool: withdraw_lamports left the pool (split from validator + rent from reserve)
-            stake_pool.pool_token_supply = stake_pool
-                .pool_token_supply
-                .checked_sub(pool_tokens_burnt)
-                .ok_or(StakePoolError::CalculationFailure)?;
             stake_pool.total_lamports = stake_pool
                 .total_lamports
-                .checked_sub(withdraw_lamports)
+                .checked_sub(DELINQUENT_VALIDATOR_CRANK_REWARD)
                 .ok_or(StakePoolError::CalculationFailure)?;
-            borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
-
-            // Update validator list (only split_lamports came from the validator stake)
-            if let Some((validator_list_item, withdraw_source)) = validator_list_item_info {
-                match withdraw_source {
-                    StakeWithdrawSource::Active => {
-                        validator_list_item.active_stake_lamports =
-                            u64::from(validator_list_item.active_stake_lamports)
-                                .checked_sub(actual_split_amount)
-                                .ok_or(StakePoolError::CalculationFailure)?
-                                .into()
-                    }
-                    StakeWithdrawSource::Transient => {
-                        validator_list_item.transient_stake_lamports =
-                            u64::from(validator_list_item.transient_stake_lamports)
-                                .checked_sub(actual_split_amount)
-                                .ok_or(StakePoolError::CalculationFailure)?
-                                .into()
-                    }
-                    StakeWithdrawSource::ValidatorRemoval => {
-                        validator_list_item.active_stake_lamports =
-                            u64::from(validator_list_item.active_stake_lamports)
-                                .checked_sub(actual_split_amount)
-                                .ok_or(StakePoolError::CalculationFailure)?
-                                .into();
-                        if u64::from(validator_list_item.active_stake_lamports) != 0 {
-                            msg!("Attempting to remove a validator from the pool, but withdrawal leaves {} lamports, update the pool to merge any unaccounted lamports",
-                                u64::from(validator_list_item.active_stake_lamports));
-                            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
-                        }
-                        validator_list_item.status = StakeStatus::ReadyForRemoval.into();
-                    }
-                }
-            }
-
-            return Ok(());
+        } else {
+            msg!("Reserve cannot afford the crank reward, skipping it");
         }
 
-        // Regular path - use standard token operations
-
-        Self::token_burn(
-            token_program_info.clone(),
-            burn_from_pool_info.clone(),
-            pool_mint_info.clone(),
-            user_transfer_authority_info.clone(),
-            pool_tokens_burnt,
+        let vote_account_address = Self::deactivate_validator(
+            program_id,
+            stake_pool,
+            stake_pool_info,
+            withdraw_authority_info,
+            validator_list_info,
+            stake_account_info,
+            transient_stake_account_info,
+            clock_info,
         )?;
-
-        Self::stake_authorize_signed(
+        Self::record_offboarding_reason(
+            program_id,
             stake_pool_info.key,
-            stake_split_to.clone(),
-            withdraw_authority_info.clone(),
-            AUTHORITY_WITHDRAW,
-            stake_pool.stake_withdraw_bump_seed,
-            user_stake_authority_info.key,
-            clock_info.clone(),
-        )?;
+            &vote_account_address,
+            Some(validator_metadata_info),
+            OffboardingReason::Delinquent,
+        )
+    }
 
-        if pool_tokens_fee > 0 {
-            Self::token_transfer(
-                token_program_info.clone(),
-                burn_from_pool_info.clone(),
-                pool_mint_info.clone(),
-                manager_fee_info.clone(),
-                user_transfer_authority_info.clone(),
-                pool_tokens_fee,
-                decimals,
-            )?;
+    /// Processes [`SetFeeSplit`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_fee_split(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        weights_bps: Vec<u16>,
+    ) -> ProgramResult {
+        use spl_associated_token_account::tools::account::create_pda_account;
+
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let fee_split_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let recipient_infos = account_info_iter.as_slice();
+
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_manager(manager_info)?;
 
-        stake_pool.pool_token_supply = stake_pool
-            .pool_token_supply
-            .checked_sub(pool_tokens_burnt)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        stake_pool.total_lamports = stake_pool
-            .total_lamports
-            .checked_sub(withdraw_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        FeeSplit::check_weights(&weights_bps)?;
+        if recipient_infos.len() != weights_bps.len() {
+            msg!(
+                "Expected {} recipient token accounts, received {}",
+                weights_bps.len(),
+                recipient_infos.len()
+            );
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        // recipients must be able to receive minted and transferred fees
+        for recipient_info in recipient_infos {
+            stake_pool.check_manager_fee_info(recipient_info)?;
+        }
 
-        if let Some((validator_list_item, withdraw_source)) = validator_list_item_info {
-            match withdraw_source {
-                StakeWithdrawSource::Active => {
-                    validator_list_item.active_stake_lamports =
-                        u64::from(validator_list_item.active_stake_lamports)
-                            .checked_sub(withdraw_lamports)
-                            .ok_or(StakePoolError::CalculationFailure)?
-                            .into()
-                }
-                StakeWithdrawSource::Transient => {
-                    validator_list_item.transient_stake_lamports =
-                        u64::from(validator_list_item.transient_stake_lamports)
-                            .checked_sub(withdraw_lamports)
-                            .ok_or(StakePoolError::CalculationFailure)?
-                            .into()
-                }
-                StakeWithdrawSource::ValidatorRemoval => {
-                    validator_list_item.active_stake_lamports =
-                        u64::from(validator_list_item.active_stake_lamports)
-                            .checked_sub(withdraw_lamports)
-                            .ok_or(StakePoolError::CalculationFailure)?
-                            .into();
-                    if u64::from(validator_list_item.active_stake_lamports) != 0 {
-                        msg!("Attempting to remove a validator from the pool, but withdrawal leaves {} lamports, update the pool to merge any unaccounted lamports",
-                            u64::from(validator_list_item.active_stake_lamports));
-                        return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
-                    }
-                    // since we already checked that there's no transient stake,
-                    // we can immediately set this as ready for removal
-                    validator_list_item.status = StakeStatus::ReadyForRemoval.into();
-                }
+        let (expected_fee_split_address, fee_split_bump) =
+            find_fee_split_program_address(program_id, stake_pool_info.key);
+        if expected_fee_split_address != *fee_split_info.key {
+            msg!("Invalid fee split PDA");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if fee_split_info.owner == program_id {
+            let fee_split = try_from_slice_unchecked::<FeeSplit>(&fee_split_info.data.borrow())?;
+            if !fee_split.is_valid() {
+                return Err(StakePoolError::InvalidFeeSplit.into());
             }
+        } else {
+            if !payer_info.is_signer {
+                return Err(StakePoolError::SignatureMissing.into());
+            }
+            let fee_split_signer_seeds: &[&[u8]] = &[
+                FEE_SPLIT_SEED_PREFIX,
+                stake_pool_info.key.as_ref(),
+                &[fee_split_bump],
+            ];
+            create_pda_account(
+                payer_info,
+                &Rent::get()?,
+                get_packed_len::<FeeSplit>(),
+                program_id,
+                system_program_info,
+                fee_split_info,
+                fee_split_signer_seeds,
+            )?;
+        }
+
+        let mut fee_split = FeeSplit {
+            account_type: AccountType::FeeSplit,
+            stake_pool: *stake_pool_info.key,
+            recipient_count: weights_bps.len() as u8,
+            ..FeeSplit::default()
+        };
+        for ((recipient, recipient_info), weight_bps) in fee_split
+            .recipients
+            .iter_mut()
+            .zip(recipient_infos)
+            .zip(weights_bps)
+        {
+            recipient.token_account = *recipient_info.key;
+            recipient.weight_bps = weight_bps;
         }
+        borsh::to_writer(&mut fee_split_info.data.borrow_mut()[..], &fee_split)?;
 
+        stake_pool.has_fee_split = fee_split.recipient_count > 0;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::FeeSplitChanged {
+            stake_pool: *stake_pool_info.key,
+            recipients: fee_split.recipients().to_vec(),
+        }
+        .emit();
         Ok(())
     }
 
-    /// Processes [`WithdrawWsolWithSession`](enum.Instruction.html).
+    /// Processes [`SetInsuranceFee`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_withdraw_wsol_with_session(
+    fn process_set_insurance_fee(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        pool_tokens: u64,
-        minimum_lamports_out: Option<u64>,
+        insurance_fee_bps: u16,
     ) -> ProgramResult {
-        use fogo_sessions_sdk::session::Session;
-        use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
-        use solana_program::program_pack::Pack;
+        use spl_associated_token_account::tools::account::create_pda_account;
 
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let signer_or_session_info = next_account_info(account_info_iter)?;
-        let burn_from_pool_info = next_account_info(account_info_iter)?;
-        let reserve_stake_info = next_account_info(account_info_iter)?;
-        let destination_account_info = next_account_info(account_info_iter)?;
-        let manager_fee_info = next_account_info(account_info_iter)?;
-        let pool_mint_info = next_account_info(account_info_iter)?;
-        let clock_info = next_account_info(account_info_iter)?;
-        let stake_history_info = next_account_info(account_info_iter)?;
-        let stake_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-
-        // wsol-specific accounts
-        let wsol_mint_info = next_account_info(account_info_iter)?;
-        let program_signer_info = next_account_info(account_info_iter)?;
-        let user_wallet_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let insurance_fund_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
         let system_program_info = next_account_info(account_info_iter)?;
 
-        let sol_withdraw_authority_info = next_account_info(account_info_iter);
-
-        if *wsol_mint_info.key != spl_token::native_mint::id() {
-            msg!("`wsol_mint` is not the native WSOL mint");
-            return Err(ProgramError::InvalidAccountData);
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_manager(manager_info)?;
 
-        if *wsol_mint_info.owner != *token_program_info.key {
-            msg!("`wsol_mint` is not owned by the token program");
-            return Err(ProgramError::InvalidAccountData);
+        if insurance_fee_bps > MAX_BASIS_POINTS {
+            msg!(
+                "Insurance fee of {} basis points is above {}",
+                insurance_fee_bps,
+                MAX_BASIS_POINTS
+            );
+            return Err(StakePoolError::InvalidInsuranceFund.into());
         }
 
-        let user_pubkey =
-            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
-
-        // Verify burn_from_pool_info is owned by the user
-        let burn_from_pool_data =
-            spl_token::state::Account::unpack(&burn_from_pool_info.data.borrow())?;
-        if burn_from_pool_data.owner != user_pubkey {
-            msg!("`burn_from_pool` is not owned by the session user");
-            return Err(ProgramError::InvalidAccountData);
+        let (expected_insurance_fund_address, insurance_fund_bump) =
+            find_insurance_fund_program_address(program_id, stake_pool_info.key);
+        if expected_insurance_fund_address != *insurance_fund_info.key {
+            msg!("Invalid insurance fund PDA");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if insurance_fund_info.owner == program_id {
+            get_insurance_fund(program_id, stake_pool_info.key, insurance_fund_info)?;
+        } else {
+            if !payer_info.is_signer {
+                return Err(StakePoolError::SignatureMissing.into());
+            }
+            let insurance_fund_signer_seeds: &[&[u8]] = &[
+                INSURANCE_FUND_SEED_PREFIX,
+                stake_pool_info.key.as_ref(),
+                &[insurance_fund_bump],
+            ];
+            create_pda_account(
+                payer_info,
+                &Rent::get()?,
+                get_packed_len::<InsuranceFund>(),
+                program_id,
+                system_program_info,
+                insurance_fund_info,
+                insurance_fund_signer_seeds,
+            )?;
+            let insurance_fund = InsuranceFund {
+                account_type: AccountType::InsuranceFund,
+                stake_pool: *stake_pool_info.key,
+                ..InsuranceFund::default()
+            };
+            borsh::to_writer(
+                &mut insurance_fund_info.data.borrow_mut()[..],
+                &insurance_fund,
+            )?;
         }
 
-        let (expected_program_signer, program_signer_bump) =
-            Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
-
-        if *program_signer_info.key != expected_program_signer {
-            msg!("`program_signer` does not match expected address");
-            return Err(ProgramError::InvalidSeeds);
+        stake_pool.insurance_fee_bps = insurance_fee_bps;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::InsuranceFeeChanged {
+            stake_pool: *stake_pool_info.key,
+            insurance_fee_bps,
         }
+        .emit();
+        Ok(())
+    }
 
-        let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
+    /// Processes [`CreateRateHistory`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_create_rate_history(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        use spl_associated_token_account::tools::account::create_pda_account;
 
-        let rent = Rent::get()?;
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let rate_history_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        // Verify destination_account_info is the correct wSOL ATA for user_wallet
-        let expected_ata =
-            spl_associated_token_account::get_associated_token_address_with_program_id(
-                user_wallet_info.key,
-                wsol_mint_info.key,
-                token_program_info.key,
-            );
-        if *destination_account_info.key != expected_ata {
-            msg!("destination_account is not the wSOL ATA for user_wallet");
-            return Err(ProgramError::InvalidAccountData);
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_manager(manager_info)?;
+        if !payer_info.is_signer {
+            return Err(StakePoolError::SignatureMissing.into());
         }
 
-        let ata_creation_cost = rent
-            .minimum_balance(spl_token::state::Account::LEN)
-            .saturating_sub(destination_account_info.lamports());
-
-        let withdraw_lamports = {
-            let mut accounts: Vec<AccountInfo> = vec![
-                stake_pool_info.clone(),
-                withdraw_authority_info.clone(),
-                signer_or_session_info.clone(),
-                burn_from_pool_info.clone(),
-                reserve_stake_info.clone(),
-                program_signer_info.clone(),
-                manager_fee_info.clone(),
-                pool_mint_info.clone(),
-                clock_info.clone(),
-                stake_history_info.clone(),
-                stake_program_info.clone(),
-                token_program_info.clone(),
-            ];
-
-            if let Ok(sol_withdraw_authority_info) = sol_withdraw_authority_info {
-                accounts.push(sol_withdraw_authority_info.clone());
-            } else {
-                // dummy to keep account order consistent
-                accounts.push(stake_program_info.clone());
-            }
+        let (expected_rate_history_address, rate_history_bump) =
+            find_rate_history_program_address(program_id, stake_pool_info.key);
+        if expected_rate_history_address != *rate_history_info.key {
+            msg!("Invalid rate history PDA");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if stake_pool.has_rate_history || rate_history_info.owner == program_id {
+            return Err(StakePoolError::AlreadyInUse.into());
+        }
+        let rate_history_signer_seeds: &[&[u8]] = &[
+            RATE_HISTORY_SEED_PREFIX,
+            stake_pool_info.key.as_ref(),
+            &[rate_history_bump],
+        ];
+        create_pda_account(
+            payer_info,
+            &Rent::get()?,
+            get_packed_len::<RateHistory>(),
+            program_id,
+            system_program_info,
+            rate_history_info,
+            rate_history_signer_seeds,
+        )?;
+        // The entries are too large for the stack, so only the header is
+        // written over the zeroed account data
+        let mut rate_history_data = rate_history_info.try_borrow_mut_data()?;
+        rate_history_data[0] = AccountType::RateHistory as u8;
+        rate_history_data[1..33].copy_from_slice(stake_pool_info.key.as_ref());
 
-            accounts.push(program_signer_info.clone());
+        stake_pool.has_rate_history = true;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        Ok(())
+    }
 
-            let balance_before = program_signer_info.lamports();
-            Self::process_withdraw_sol(program_id, &accounts, pool_tokens, minimum_lamports_out)?;
-            program_signer_info
-                .lamports()
-                .saturating_sub(balance_before)
-        };
+    /// Processes [`CreateOracle`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_create_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        use spl_associated_token_account::tools::account::create_pda_account;
 
-        if ata_creation_cost > 0 {
-            if withdraw_lamports < ata_creation_cost {
-                msg!("Withdrawal too small to cover ATA rent");
-                return Err(StakePoolError::WithdrawalTooSmall.into());
-            }
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let oracle_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
-            invoke_signed(
-                &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                    program_signer_info.key,
-                    user_wallet_info.key,
-                    wsol_mint_info.key,
-                    token_program_info.key,
-                ),
-                &[
-                    program_signer_info.clone(),
-                    destination_account_info.clone(),
-                    user_wallet_info.clone(),
-                    wsol_mint_info.clone(),
-                    system_program_info.clone(),
-                    token_program_info.clone(),
-                ],
-                &[program_signer_seeds],
-            )?;
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
-
-        let user_lamports = withdraw_lamports.saturating_sub(ata_creation_cost);
-        if user_lamports > 0 {
-            invoke_signed(
-                &system_instruction::transfer(
-                    program_signer_info.key,
-                    destination_account_info.key,
-                    user_lamports,
-                ),
-                &[
-                    program_signer_info.clone(),
-                    destination_account_info.clone(),
-                    system_program_info.clone(),
-                ],
-                &[program_signer_seeds],
-            )?;
+        stake_pool.check_manager(manager_info)?;
+        if !payer_info.is_signer {
+            return Err(StakePoolError::SignatureMissing.into());
         }
 
-        invoke(
-            &spl_token::instruction::sync_native(
-                token_program_info.key,
-                destination_account_info.key,
-            )?,
-            core::slice::from_ref(destination_account_info),
+        let (expected_oracle_address, oracle_bump) =
+            find_oracle_program_address(program_id, stake_pool_info.key);
+        if expected_oracle_address != *oracle_info.key {
+            msg!("Invalid price oracle PDA");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if stake_pool.has_oracle || oracle_info.owner == program_id {
+            return Err(StakePoolError::AlreadyInUse.into());
+        }
+        let oracle_signer_seeds: &[&[u8]] = &[
+            ORACLE_SEED_PREFIX,
+            stake_pool_info.key.as_ref(),
+            &[oracle_bump],
+        ];
+        create_pda_account(
+            payer_info,
+            &Rent::get()?,
+            get_packed_len::<Oracle>(),
+            program_id,
+            system_program_info,
+            oracle_info,
+            oracle_signer_seeds,
         )?;
+        let oracle = Oracle {
+            account_type: AccountType::Oracle,
+            stake_pool: *stake_pool_info.key,
+            pool_mint: stake_pool.pool_mint,
+            exchange_rate: ExchangeRate::from(&stake_pool),
+            last_update_slot: Clock::get()?.slot,
+        };
+        borsh::to_writer(&mut oracle_info.data.borrow_mut()[..], &oracle)?;
 
+        stake_pool.has_oracle = true;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
         Ok(())
     }
 
-    /// Processes [`WithdrawSol`](enum.Instruction.html).
+    /// Processes [`GetExchangeRate`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_withdraw_sol(
+    fn process_get_exchange_rate(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        set_return_data(&borsh::to_vec(&ExchangeRate::from(&stake_pool))?);
+        Ok(())
+    }
+
+    /// Processes [`InjectInsuranceFund`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_inject_insurance_fund(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        pool_tokens: u64,
-        minimum_lamports_out: Option<u64>,
+        lamports: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let user_transfer_authority_info = next_account_info(account_info_iter)?;
-        let burn_from_pool_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let insurance_fund_info = next_account_info(account_info_iter)?;
         let reserve_stake_info = next_account_info(account_info_iter)?;
-        let destination_lamports_info = next_account_info(account_info_iter)?;
-        let manager_fee_info = next_account_info(account_info_iter)?;
-        let pool_mint_info = next_account_info(account_info_iter)?;
-        let clock_info = next_account_info(account_info_iter)?;
-        let stake_history_info = next_account_info(account_info_iter)?;
-        let stake_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let sol_withdraw_authority_info = next_account_info(account_info_iter);
 
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
+        let manager_signed = stake_pool.check_manager(authority_info);
+        let staker_signed = stake_pool.check_staker(authority_info);
+        if manager_signed.is_err() && staker_signed.is_err() {
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
+        let mut insurance_fund =
+            get_insurance_fund(program_id, stake_pool_info.key, insurance_fund_info)?;
 
-        stake_pool.check_authority_withdraw(
-            withdraw_authority_info.key,
-            program_id,
-            stake_pool_info.key,
+        let fund_balance = insurance_fund_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(insurance_fund_info.data_len()));
+        if lamports > stake_pool.uncovered_loss_lamports || lamports > fund_balance {
+            msg!(
+                "Cannot inject {} lamports, uncovered losses are {} and the fund holds {}",
+                lamports,
+                stake_pool.uncovered_loss_lamports,
+                fund_balance
+            );
+            return Err(StakePoolError::InsuranceInjectionTooLarge.into());
+        }
+
+        // the fund is owned by the program, and anyone can credit the reserve
+        **insurance_fund_info.try_borrow_mut_lamports()? = insurance_fund_info
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        **reserve_stake_info.try_borrow_mut_lamports()? = reserve_stake_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        insurance_fund.injected_lamports = insurance_fund
+            .injected_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(
+            &mut insurance_fund_info.data.borrow_mut()[..],
+            &insurance_fund,
         )?;
-        stake_pool.check_sol_withdraw_authority(sol_withdraw_authority_info)?;
-        let decimals = stake_pool.check_mint(pool_mint_info)?;
-        stake_pool.check_reserve_stake(reserve_stake_info)?;
 
-        if stake_pool.token_program_id != *token_program_info.key {
-            return Err(ProgramError::IncorrectProgramId);
+        stake_pool.total_lamports = stake_pool
+            .total_lamports
+            .checked_add(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.uncovered_loss_lamports = stake_pool
+            .uncovered_loss_lamports
+            .checked_sub(lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+
+        StakePoolEvent::InsuranceFundInjected {
+            stake_pool: *stake_pool_info.key,
+            lamports,
+            uncovered_loss_lamports: stake_pool.uncovered_loss_lamports,
         }
-        check_stake_program(stake_program_info.key)?;
+        .emit();
+        Ok(())
+    }
 
-        if stake_pool.manager_fee_account != *manager_fee_info.key {
-            return Err(StakePoolError::InvalidFeeAccount.into());
+    /// Processes [`SetStaker`](enum.Instruction.html).
+    #[cfg(feature = "legacy-authority-transfer")]
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_staker(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let set_staker_authority_info = next_account_info(account_info_iter)?;
+        let new_staker_info = next_account_info(account_info_iter)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
 
-        // We want this to hold to ensure that withdraw_sol burns pool tokens
-        // at the right price
-        if stake_pool.last_update_epoch < Clock::get()?.epoch {
-            return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
+        let staker_signed = stake_pool.check_staker(set_staker_authority_info);
+        let manager_signed = stake_pool.check_manager(set_staker_authority_info);
+        if staker_signed.is_err() && manager_signed.is_err() {
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        stake_pool.staker = *new_staker_info.key;
+        stake_pool.pending_staker = None;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::AuthorityChanged {
+            stake_pool: *stake_pool_info.key,
+            authority_type: AuthorityType::Staker,
+            new_authority: Some(stake_pool.staker),
         }
+        .emit();
+        Ok(())
+    }
 
-        // To prevent a faulty manager fee account from preventing withdrawals
-        // if the token program does not own the account, or if the account is not
-        // initialized
-        let pool_tokens_fee = if stake_pool.manager_fee_account == *burn_from_pool_info.key
-            || stake_pool.check_manager_fee_info(manager_fee_info).is_err()
-        {
-            0
-        } else {
-            stake_pool
-                .calc_pool_tokens_sol_withdrawal_fee(pool_tokens)
-                .ok_or(StakePoolError::CalculationFailure)?
-        };
-        let pool_tokens_burnt = pool_tokens
-            .checked_sub(pool_tokens_fee)
-            .ok_or(StakePoolError::CalculationFailure)?;
+    /// Processes [`ProposeManager`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_propose_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
 
-        let withdraw_lamports = stake_pool
-            .calc_lamports_withdraw_amount(pool_tokens_burnt)
-            .ok_or(StakePoolError::CalculationFailure)?;
+        let new_manager = next_account_info(account_info_iter)
+            .ok()
+            .map(|new_manager_account_info| *new_manager_account_info.key);
 
-        if withdraw_lamports == 0 {
-            return Err(StakePoolError::WithdrawalTooSmall.into());
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_manager(manager_info)?;
 
-        if let Some(minimum_lamports_out) = minimum_lamports_out {
-            if withdraw_lamports < minimum_lamports_out {
-                return Err(StakePoolError::ExceededSlippage.into());
-            }
+        stake_pool.pending_manager = new_manager;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::AuthorityProposed {
+            stake_pool: *stake_pool_info.key,
+            authority_type: AuthorityType::Manager,
+            proposed_authority: new_manager,
+        }
+        .emit();
+        Ok(())
+    }
+
+    /// Processes [`AcceptManager`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_accept_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let pending_manager_info = next_account_info(account_info_iter)?;
+        let new_manager_fee_info = next_account_info(account_info_iter)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        check_account_owner(new_manager_fee_info, &stake_pool.token_program_id)?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
 
-        let new_reserve_lamports = reserve_stake_info
-            .lamports()
-            .saturating_sub(withdraw_lamports);
-        let stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-            &reserve_stake_info.data.borrow(),
-        )?;
-        if let stake::state::StakeStateV2::Initialized(meta) = stake_state {
-            let minimum_reserve_lamports = minimum_reserve_lamports(&meta);
-            if new_reserve_lamports < minimum_reserve_lamports {
-                msg!("Attempting to withdraw {} lamports, maximum possible SOL withdrawal is {} lamports",
-                    withdraw_lamports,
-                    reserve_stake_info.lamports().saturating_sub(minimum_reserve_lamports)
-                );
-                return Err(StakePoolError::SolWithdrawalTooLarge.into());
-            }
-        } else {
-            msg!("Reserve stake account not in initialized state");
-            return Err(StakePoolError::WrongStakeStake.into());
-        };
+        StakePool::check_pending_authority(stake_pool.pending_manager, pending_manager_info)?;
+        stake_pool.check_manager_fee_info(new_manager_fee_info)?;
 
-        // Determine if we are using the WSOL special path (with program signer account)
-        if let Ok(program_signer_info) = next_account_info(account_info_iter) {
-            use fogo_sessions_sdk::token::instruction::burn;
-            use fogo_sessions_sdk::token::instruction::transfer_checked;
-            use fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED;
+        stake_pool.manager = *pending_manager_info.key;
+        stake_pool.manager_fee_account = *new_manager_fee_info.key;
+        stake_pool.pending_manager = None;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::AuthorityChanged {
+            stake_pool: *stake_pool_info.key,
+            authority_type: AuthorityType::Manager,
+            new_authority: Some(stake_pool.manager),
+        }
+        .emit();
+        Ok(())
+    }
 
-            let (expected_program_signer, program_signer_bump) =
-                Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], program_id);
+    /// Processes [`ProposeStaker`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_propose_staker(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let set_staker_authority_info = next_account_info(account_info_iter)?;
 
-            if expected_program_signer != *program_signer_info.key {
-                msg!("Invalid program signer account");
-                return Err(ProgramError::InvalidSeeds);
-            }
+        let new_staker = next_account_info(account_info_iter)
+            .ok()
+            .map(|new_staker_account_info| *new_staker_account_info.key);
 
-            let program_signer_seeds: &[&[u8]] = &[PROGRAM_SIGNER_SEED, &[program_signer_bump]];
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
 
-            invoke_signed(
-                &burn(
-                    token_program_info.key,
-                    burn_from_pool_info.key,
-                    pool_mint_info.key,
-                    user_transfer_authority_info.key,
-                    Some(program_signer_info.key),
-                    pool_tokens_burnt,
-                )?,
-                &[
-                    burn_from_pool_info.clone(),
-                    pool_mint_info.clone(),
-                    user_transfer_authority_info.clone(),
-                    program_signer_info.clone(),
-                ],
-                &[program_signer_seeds],
-            )?;
+        let staker_signed = stake_pool.check_staker(set_staker_authority_info);
+        let manager_signed = stake_pool.check_manager(set_staker_authority_info);
+        if staker_signed.is_err() && manager_signed.is_err() {
+            return Err(StakePoolError::SignatureMissing.into());
+        }
 
-            if pool_tokens_fee > 0 {
-                invoke_signed(
-                    &transfer_checked(
-                        token_program_info.key,
-                        burn_from_pool_info.key,
-                        pool_mint_info.key,
-                        manager_fee_info.key,
-                        user_transfer_authority_info.key,
-                        Some(program_signer_info.key),
-                        pool_tokens_fee,
-                        decimals,
-                    )?,
-                    &[
-                        burn_from_pool_info.clone(),
-                        pool_mint_info.clone(),
-                        manager_fee_info.clone(),
-                        user_transfer_authority_info.clone(),
-                        program_signer_info.clone(),
-                    ],
-                    &[program_signer_seeds],
-                )?;
-            }
-        } else {
-            Self::token_burn(
-                token_program_info.clone(),
-                burn_from_pool_info.clone(),
-                pool_mint_info.clone(),
-                user_transfer_authority_info.clone(),
-                pool_tokens_burnt,
-            )?;
+        stake_pool.pending_staker = new_staker;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::AuthorityProposed {
+            stake_pool: *stake_pool_info.key,
+            authority_type: AuthorityType::Staker,
+            proposed_authority: new_staker,
+        }
+        .emit();
+        Ok(())
+    }
 
-            if pool_tokens_fee > 0 {
-                Self::token_transfer(
-                    token_program_info.clone(),
-                    burn_from_pool_info.clone(),
-                    pool_mint_info.clone(),
-                    manager_fee_info.clone(),
-                    user_transfer_authority_info.clone(),
-                    pool_tokens_fee,
-                    decimals,
-                )?;
-            }
+    /// Processes [`AcceptStaker`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_accept_staker(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let pending_staker_info = next_account_info(account_info_iter)?;
+
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
 
-        Self::stake_withdraw(
-            stake_pool_info.key,
-            reserve_stake_info.clone(),
-            withdraw_authority_info.clone(),
-            AUTHORITY_WITHDRAW,
-            stake_pool.stake_withdraw_bump_seed,
-            destination_lamports_info.clone(),
-            clock_info.clone(),
-            stake_history_info.clone(),
-            withdraw_lamports,
-        )?;
+        StakePool::check_pending_authority(stake_pool.pending_staker, pending_staker_info)?;
 
-        stake_pool.pool_token_supply = stake_pool
-            .pool_token_supply
-            .checked_sub(pool_tokens_burnt)
-            .ok_or(StakePoolError::CalculationFailure)?;
-        stake_pool.total_lamports = stake_pool
-            .total_lamports
-            .checked_sub(withdraw_lamports)
-            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.staker = *pending_staker_info.key;
+        stake_pool.pending_staker = None;
         borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
-
+        StakePoolEvent::AuthorityChanged {
+            stake_pool: *stake_pool_info.key,
+            authority_type: AuthorityType::Staker,
+            new_authority: Some(stake_pool.staker),
+        }
+        .emit();
         Ok(())
     }
 
-    #[inline(never)]
-    fn process_create_pool_token_metadata(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        name: String,
-        symbol: String,
-        uri: String,
-    ) -> ProgramResult {
+    /// Processes [`SetPauser`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_pauser(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
-        let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let pool_mint_info = next_account_info(account_info_iter)?;
-        let payer_info = next_account_info(account_info_iter)?;
-        let metadata_info = next_account_info(account_info_iter)?;
-        let mpl_token_metadata_program_info = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
 
-        if !payer_info.is_signer {
-            msg!("Payer did not sign metadata creation");
-            return Err(StakePoolError::SignatureMissing.into());
-        }
+        let new_pauser = next_account_info(account_info_iter)
+            .ok()
+            .map(|new_pauser_account_info| *new_pauser_account_info.key);
 
-        check_system_program(system_program_info.key)?;
-        check_account_owner(payer_info, &system_program::id())?;
         check_account_owner(stake_pool_info, program_id)?;
-        check_mpl_metadata_program(mpl_token_metadata_program_info.key)?;
-
-        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
-
         stake_pool.check_manager(manager_info)?;
-        stake_pool.check_authority_withdraw(
-            withdraw_authority_info.key,
-            program_id,
-            stake_pool_info.key,
-        )?;
-        stake_pool.check_mint(pool_mint_info)?;
-        check_mpl_metadata_account_address(metadata_info.key, &stake_pool.pool_mint)?;
 
-        // Token mint authority for stake-pool token is stake-pool withdraw authority
-        let token_mint_authority = withdraw_authority_info;
+        stake_pool.pauser = new_pauser;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::AuthorityChanged {
+            stake_pool: *stake_pool_info.key,
+            authority_type: AuthorityType::Pauser,
+            new_authority: new_pauser,
+        }
+        .emit();
+        Ok(())
+    }
 
-        let new_metadata_instruction = create_metadata_accounts_v3(
-            *mpl_token_metadata_program_info.key,
-            *metadata_info.key,
-            *pool_mint_info.key,
-            *token_mint_authority.key,
-            *payer_info.key,
-            *token_mint_authority.key,
-            name,
-            symbol,
-            uri,
-        );
+    /// Processes [`SetPaused`](enum.Instruction.html) and
+    /// [`UpdatePaused`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_update_paused(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        pause: u8,
+        unpause: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let set_paused_authority_info = next_account_info(account_info_iter)?;
 
-        let (_, stake_withdraw_bump_seed) =
-            crate::find_withdraw_authority_program_address(program_id, stake_pool_info.key);
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
 
-        let token_mint_authority_signer_seeds: &[&[_]] = &[
-            stake_pool_info.key.as_ref(),
-            AUTHORITY_WITHDRAW,
-            &[stake_withdraw_bump_seed],
-        ];
+        let pauser_signed = stake_pool.check_pauser(set_paused_authority_info);
+        let manager_signed = stake_pool.check_manager(set_paused_authority_info);
+        if pauser_signed.is_err() && manager_signed.is_err() {
+            return Err(StakePoolError::SignatureMissing.into());
+        }
 
-        invoke_signed(
-            &new_metadata_instruction,
-            &[
-                metadata_info.clone(),
-                pool_mint_info.clone(),
-                withdraw_authority_info.clone(),
-                payer_info.clone(),
-                withdraw_authority_info.clone(),
-                system_program_info.clone(),
-            ],
-            &[token_mint_authority_signer_seeds],
-        )?;
+        if (pause | unpause) & !PausedOperation::ALL != 0 {
+            msg!(
+                "Unknown paused operations bitmask {:#010b}",
+                pause | unpause
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if pause & unpause != 0 {
+            msg!(
+                "Operations {:#010b} both paused and unpaused",
+                pause & unpause
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
 
+        let paused_operations = (stake_pool.paused_operations | pause) & !unpause;
+        stake_pool.paused_operations = paused_operations;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        StakePoolEvent::PausedOperationsChanged {
+            stake_pool: *stake_pool_info.key,
+            paused_operations,
+        }
+        .emit();
         Ok(())
     }
 
-    #[inline(never)]
-    fn process_update_pool_token_metadata(
+    /// Processes [`SetFundingAuthority`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_set_funding_authority(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        name: String,
-        symbol: String,
-        uri: String,
+        funding_type: FundingType,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-
         let stake_pool_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
-        let withdraw_authority_info = next_account_info(account_info_iter)?;
-        let metadata_info = next_account_info(account_info_iter)?;
-        let mpl_token_metadata_program_info = next_account_info(account_info_iter)?;
+
+        let new_authority = next_account_info(account_info_iter)
+            .ok()
+            .map(|new_authority_account_info| *new_authority_account_info.key);
 
         check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+        stake_pool.check_manager(manager_info)?;
+        let (authority_type, new_authority) = match funding_type {
+            FundingType::StakeDeposit => {
+                stake_pool.stake_deposit_authority = new_authority.unwrap_or(
+                    find_deposit_authority_program_address(program_id, stake_pool_info.key).0,
+                );
+                (
+                    AuthorityType::StakeDeposit,
+                    Some(stake_pool.stake_deposit_authority),
+                )
+            }
+            FundingType::SolDeposit => {
+                stake_pool.sol_deposit_authority = new_authority;
+                (AuthorityType::SolDeposit, new_authority)
+            }
+            FundingType::SolWithdraw => {
+                stake_pool.sol_withdraw_authority = new_authority;
+                (AuthorityType::SolWithdraw, new_authority)
+            }
+        };
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
-        check_mpl_metadata_program(mpl_token_metadata_program_info.key)?;
+        StakePoolEvent::AuthorityChanged {
+            stake_pool: *stake_pool_info.key,
+            authority_type,
+            new_authority,
+        }
+        .emit();
+        Ok(())
+    }
+
+    /// Process `WithdrawFromStakeAccountWithSession` instruction
+    /// Withdraws lamports from a user stake account after cooldown.
+    fn process_withdraw_from_stake_account_with_session(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        lamports: u64,
+        user_stake_seed: u64,
+    ) -> ProgramResult {
+        use crate::USER_STAKE_SEED_PREFIX;
+        use fogo_sessions_sdk::session::Session;
+
+        let account_info_iter = &mut accounts.iter();
+        let stake_account_info = next_account_info(account_info_iter)?;
+        let recipient_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let signer_or_session_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
+        let stake_pool_info = next_account_info(account_info_iter)?;
 
-        let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        check_stake_program(stake_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_not_paused(PausedOperation::Session)?;
 
-        stake_pool.check_manager(manager_info)?;
-        stake_pool.check_authority_withdraw(
-            withdraw_authority_info.key,
+        let user_pubkey =
+            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+
+        if *recipient_info.key != user_pubkey {
+            msg!("Recipient must be the session user wallet");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Validate stake account PDA
+        let (expected_stake_pda, stake_pda_bump) = Pubkey::find_program_address(
+            &[
+                USER_STAKE_SEED_PREFIX,
+                user_pubkey.as_ref(),
+                &user_stake_seed.to_le_bytes(),
+            ],
             program_id,
-            stake_pool_info.key,
-        )?;
-        check_mpl_metadata_account_address(metadata_info.key, &stake_pool.pool_mint)?;
+        );
+        if expected_stake_pda != *stake_account_info.key {
+            msg!("Invalid stake account PDA");
+            return Err(ProgramError::InvalidSeeds);
+        }
 
-        // Token mint authority for stake-pool token is withdraw authority only
-        let token_mint_authority = withdraw_authority_info;
+        // Verify stake account is deactivated and ready for withdrawal
+        check_deactivated_user_stake(stake_account_info, Clock::get()?.epoch)?;
 
-        let update_metadata_accounts_instruction = update_metadata_accounts_v2(
-            *mpl_token_metadata_program_info.key,
-            *metadata_info.key,
-            *token_mint_authority.key,
-            None,
-            Some(DataV2 {
-                name,
-                symbol,
-                uri,
-                seller_fee_basis_points: 0,
-                creators: None,
-                collection: None,
-                uses: None,
-            }),
-            None,
-            Some(true),
-        );
+        let stake_balance = stake_account_info.lamports();
 
-        let (_, stake_withdraw_bump_seed) =
-            crate::find_withdraw_authority_program_address(program_id, stake_pool_info.key);
+        // Determine withdrawal amount (u64::MAX means full withdrawal)
+        let withdraw_amount = if lamports == u64::MAX {
+            stake_balance
+        } else {
+            lamports
+        };
 
-        let token_mint_authority_signer_seeds: &[&[_]] = &[
-            stake_pool_info.key.as_ref(),
-            AUTHORITY_WITHDRAW,
-            &[stake_withdraw_bump_seed],
+        let stake_pda_seeds: &[&[u8]] = &[
+            USER_STAKE_SEED_PREFIX,
+            user_pubkey.as_ref(),
+            &user_stake_seed.to_le_bytes(),
+            &[stake_pda_bump],
         ];
 
         invoke_signed(
-            &update_metadata_accounts_instruction,
-            &[metadata_info.clone(), withdraw_authority_info.clone()],
-            &[token_mint_authority_signer_seeds],
+            &stake::instruction::withdraw(
+                stake_account_info.key,
+                stake_account_info.key,
+                recipient_info.key,
+                withdraw_amount,
+                None,
+            ),
+            &[
+                stake_account_info.clone(),
+                recipient_info.clone(),
+                clock_info.clone(),
+                stake_history_info.clone(),
+                stake_account_info.clone(),
+            ],
+            &[stake_pda_seeds],
         )?;
 
         Ok(())
     }
 
-    /// Processes [`SetManager`](enum.Instruction.html).
+    /// Processes [`CloseUserStakeAccountsWithSession`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_set_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    fn process_close_user_stake_accounts_with_session(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        user_stake_seeds: Vec<u64>,
+    ) -> ProgramResult {
+        use crate::USER_STAKE_SEED_PREFIX;
+        use fogo_sessions_sdk::session::Session;
+
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let manager_info = next_account_info(account_info_iter)?;
-        let new_manager_info = next_account_info(account_info_iter)?;
-        let new_manager_fee_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let recipient_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let stake_history_info = next_account_info(account_info_iter)?;
+        let signer_or_session_info = next_account_info(account_info_iter)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
 
+        check_stake_program(stake_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
-        check_account_owner(new_manager_fee_info, &stake_pool.token_program_id)?;
+        let stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_not_paused(PausedOperation::Session)?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
 
-        stake_pool.check_manager(manager_info)?;
-        if !new_manager_info.is_signer {
-            msg!("New manager signature missing");
-            return Err(StakePoolError::SignatureMissing.into());
+        let user_pubkey =
+            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+        if *recipient_info.key != user_pubkey {
+            msg!("Recipient must be the session user wallet");
+            return Err(ProgramError::InvalidAccountData);
         }
 
-        stake_pool.check_manager_fee_info(new_manager_fee_info)?;
+        let clock = Clock::from_account_info(clock_info)?;
+        for user_stake_seed in user_stake_seeds {
+            let stake_account_info = next_account_info(account_info_iter)?;
+            let (expected_stake_pda, stake_pda_bump) = Pubkey::find_program_address(
+                &[
+                    USER_STAKE_SEED_PREFIX,
+                    user_pubkey.as_ref(),
+                    &user_stake_seed.to_le_bytes(),
+                ],
+                program_id,
+            );
+            if expected_stake_pda != *stake_account_info.key {
+                msg!("Invalid stake account PDA for seed {}", user_stake_seed);
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let meta = check_deactivated_user_stake(stake_account_info, clock.epoch)?;
+
+            let stake_pda_seeds: &[&[u8]] = &[
+                USER_STAKE_SEED_PREFIX,
+                user_pubkey.as_ref(),
+                &user_stake_seed.to_le_bytes(),
+                &[stake_pda_bump],
+            ];
+
+            // The rent goes back to the reserve that funded it, the rest to
+            // the user, closing the account
+            let stake_balance = stake_account_info.lamports();
+            let rent_lamports = meta.rent_exempt_reserve.min(stake_balance);
+            for (destination_info, lamports) in [
+                (recipient_info, stake_balance.saturating_sub(rent_lamports)),
+                (reserve_stake_info, rent_lamports),
+            ] {
+                if lamports == 0 {
+                    continue;
+                }
+                invoke_signed(
+                    &stake::instruction::withdraw(
+                        stake_account_info.key,
+                        stake_account_info.key,
+                        destination_info.key,
+                        lamports,
+                        None,
+                    ),
+                    &[
+                        stake_account_info.clone(),
+                        destination_info.clone(),
+                        clock_info.clone(),
+                        stake_history_info.clone(),
+                        stake_account_info.clone(),
+                    ],
+                    &[stake_pda_seeds],
+                )?;
+            }
+        }
 
-        stake_pool.manager = *new_manager_info.key;
-        stake_pool.manager_fee_account = *new_manager_fee_info.key;
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
         Ok(())
     }
 
-    /// Processes [`SetFee`](enum.Instruction.html).
+    /// Processes [`RequestWithdrawalTicket`](enum.Instruction.html).
     #[inline(never)] // needed to avoid stack size violation
-    fn process_set_fee(
+    fn process_request_withdrawal_ticket(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        fee: FeeType,
+        pool_tokens: u64,
+        ticket_seed: u64,
     ) -> ProgramResult {
+        use spl_associated_token_account::tools::account::create_pda_account;
+
+        let (accounts, maybe_fee_split_infos) = split_fee_split(program_id, accounts);
         let account_info_iter = &mut accounts.iter();
         let stake_pool_info = next_account_info(account_info_iter)?;
-        let manager_info = next_account_info(account_info_iter)?;
-        let clock = Clock::get()?;
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let burn_from_pool_info = next_account_info(account_info_iter)?;
+        let manager_fee_info = next_account_info(account_info_iter)?;
+        let pool_mint_info = next_account_info(account_info_iter)?;
+        let withdrawal_ticket_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
 
+        check_system_program(system_program_info.key)?;
         check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
         if !stake_pool.is_valid() {
             return Err(StakePoolError::InvalidState.into());
         }
-        stake_pool.check_manager(manager_info)?;
+        stake_pool.check_not_paused(PausedOperation::Withdrawal)?;
 
-        if fee.can_only_change_next_epoch() && stake_pool.last_update_epoch < clock.epoch {
+        let decimals = stake_pool.check_mint(pool_mint_info)?;
+        if stake_pool.token_program_id != *token_program_info.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if stake_pool.manager_fee_account != *manager_fee_info.key {
+            return Err(StakePoolError::InvalidFeeAccount.into());
+        }
+
+        // The ticket locks in the exchange rate, so it must be current
+        let clock = Clock::get()?;
+        if stake_pool.last_update_epoch < clock.epoch {
             return Err(StakePoolError::StakeListAndPoolOutOfDate.into());
         }
 
-        fee.check_too_high()?;
-        stake_pool.update_fee(&fee)?;
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
-        Ok(())
-    }
+        if !user_transfer_authority_info.is_signer || !payer_info.is_signer {
+            return Err(StakePoolError::SignatureMissing.into());
+        }
 
-    /// Processes [`SetStaker`](enum.Instruction.html).
-    #[inline(never)] // needed to avoid stack size violation
-    fn process_set_staker(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let stake_pool_info = next_account_info(account_info_iter)?;
-        let set_staker_authority_info = next_account_info(account_info_iter)?;
-        let new_staker_info = next_account_info(account_info_iter)?;
+        let (expected_ticket_address, ticket_bump) = find_withdrawal_ticket_program_address(
+            program_id,
+            stake_pool_info.key,
+            user_transfer_authority_info.key,
+            ticket_seed,
+        );
+        if expected_ticket_address != *withdrawal_ticket_info.key {
+            msg!("Invalid withdrawal ticket PDA");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if withdrawal_ticket_info.owner == program_id {
+            msg!("Withdrawal ticket PDA already in use. Use a different seed.");
+            return Err(StakePoolError::AlreadyInUse.into());
+        }
 
-        check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
-        if !stake_pool.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
+        // Same fee as a SOL withdrawal, the ticket is a delayed one
+        let pool_tokens_fee = if stake_pool.manager_fee_account == *burn_from_pool_info.key
+            || stake_pool.check_manager_fee_info(manager_fee_info).is_err()
+        {
+            0
+        } else {
+            stake_pool
+                .calc_pool_tokens_sol_withdrawal_fee(pool_tokens)
+                .ok_or(StakePoolError::CalculationFailure)?
+        };
+        let pool_tokens_burnt = pool_tokens
+            .checked_sub(pool_tokens_fee)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        let fee_payouts = fee_payouts(
+            stake_pool_info.key,
+            &stake_pool,
+            manager_fee_info,
+            maybe_fee_split_infos,
+            pool_tokens_fee,
+        )?;
+
+        let withdraw_lamports = stake_pool
+            .calc_lamports_withdraw_amount(pool_tokens_burnt)
+            .ok_or(StakePoolError::CalculationFailure)?;
+
+        if withdraw_lamports == 0 {
+            return Err(StakePoolError::WithdrawalTooSmall.into());
         }
 
-        let staker_signed = stake_pool.check_staker(set_staker_authority_info);
-        let manager_signed = stake_pool.check_manager(set_staker_authority_info);
-        if staker_signed.is_err() && manager_signed.is_err() {
-            return Err(StakePoolError::SignatureMissing.into());
+        // Tickets are paid out in SOL, so they count against the SOL
+        // withdrawal cap of the epoch they are requested in
+        stake_pool.record_sol_withdrawal(clock.epoch, withdraw_lamports)?;
+
+        let ticket_signer_seeds: &[&[u8]] = &[
+            WITHDRAWAL_TICKET_SEED_PREFIX,
+            stake_pool_info.key.as_ref(),
+            user_transfer_authority_info.key.as_ref(),
+            &ticket_seed.to_le_bytes(),
+            &[ticket_bump],
+        ];
+        create_pda_account(
+            payer_info,
+            &Rent::get()?,
+            get_packed_len::<WithdrawalTicket>(),
+            program_id,
+            system_program_info,
+            withdrawal_ticket_info,
+            ticket_signer_seeds,
+        )?;
+
+        Self::token_burn(
+            token_program_info.clone(),
+            burn_from_pool_info.clone(),
+            pool_mint_info.clone(),
+            user_transfer_authority_info.clone(),
+            pool_tokens_burnt,
+        )?;
+
+        for (fee_info, fee) in fee_payouts {
+            if fee > 0 {
+                Self::token_transfer(
+                    token_program_info.clone(),
+                    burn_from_pool_info.clone(),
+                    pool_mint_info.clone(),
+                    fee_info.clone(),
+                    user_transfer_authority_info.clone(),
+                    fee,
+                    decimals,
+                )?;
+            }
         }
-        stake_pool.staker = *new_staker_info.key;
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
-        Ok(())
-    }
 
-    /// Processes [`SetFundingAuthority`](enum.Instruction.html).
-    #[inline(never)] // needed to avoid stack size violation
-    fn process_set_funding_authority(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        funding_type: FundingType,
-    ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let stake_pool_info = next_account_info(account_info_iter)?;
-        let manager_info = next_account_info(account_info_iter)?;
+        stake_pool.pool_token_supply = stake_pool
+            .pool_token_supply
+            .checked_sub(pool_tokens_burnt)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.total_lamports = stake_pool
+            .total_lamports
+            .checked_sub(withdraw_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.pending_withdrawal_lamports = stake_pool
+            .pending_withdrawal_lamports
+            .checked_add(withdraw_lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
-        let new_authority = next_account_info(account_info_iter)
-            .ok()
-            .map(|new_authority_account_info| *new_authority_account_info.key);
+        let withdrawal_ticket = WithdrawalTicket {
+            account_type: AccountType::WithdrawalTicket,
+            stake_pool: *stake_pool_info.key,
+            owner: *user_transfer_authority_info.key,
+            lamports: withdraw_lamports,
+            pool_tokens: pool_tokens_burnt,
+            request_epoch: clock.epoch,
+            claimable_epoch: clock
+                .epoch
+                .checked_add(1)
+                .ok_or(StakePoolError::CalculationFailure)?,
+        };
+        borsh::to_writer(
+            &mut withdrawal_ticket_info.data.borrow_mut()[..],
+            &withdrawal_ticket,
+        )?;
 
-        check_account_owner(stake_pool_info, program_id)?;
-        let mut stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_info.data.borrow())?;
-        if !stake_pool.is_valid() {
-            return Err(StakePoolError::InvalidState.into());
-        }
-        stake_pool.check_manager(manager_info)?;
-        match funding_type {
-            FundingType::StakeDeposit => {
-                stake_pool.stake_deposit_authority = new_authority.unwrap_or(
-                    find_deposit_authority_program_address(program_id, stake_pool_info.key).0,
-                );
-            }
-            FundingType::SolDeposit => stake_pool.sol_deposit_authority = new_authority,
-            FundingType::SolWithdraw => stake_pool.sol_withdraw_authority = new_authority,
+        StakePoolEvent::WithdrawalTicketRequested {
+            stake_pool: *stake_pool_info.key,
+            ticket: *withdrawal_ticket_info.key,
+            pool_tokens: pool_tokens_burnt,
+            manager_fee_pool_tokens: pool_tokens_fee,
+            lamports: withdraw_lamports,
         }
-        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
+        .emit();
+
         Ok(())
     }
 
-    /// Process `WithdrawFromStakeAccountWithSession` instruction
-    /// Withdraws lamports from a user stake account after cooldown.
-    fn process_withdraw_from_stake_account_with_session(
+    /// Processes [`ClaimWithdrawalTicket`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_claim_withdrawal_ticket(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        lamports: u64,
-        user_stake_seed: u64,
     ) -> ProgramResult {
-        use crate::USER_STAKE_SEED_PREFIX;
-        use fogo_sessions_sdk::session::Session;
-
         let account_info_iter = &mut accounts.iter();
-        let stake_account_info = next_account_info(account_info_iter)?;
-        let recipient_info = next_account_info(account_info_iter)?;
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let withdraw_authority_info = next_account_info(account_info_iter)?;
+        let reserve_stake_info = next_account_info(account_info_iter)?;
+        let withdrawal_ticket_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let destination_lamports_info = next_account_info(account_info_iter)?;
         let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
         let stake_history_info = next_account_info(account_info_iter)?;
-        let signer_or_session_info = next_account_info(account_info_iter)?;
-
-        let user_pubkey =
-            Session::extract_user_from_signer_or_session(signer_or_session_info, program_id)?;
+        let stake_program_info = next_account_info(account_info_iter)?;
 
-        if *recipient_info.key != user_pubkey {
-            msg!("Recipient must be the session user wallet");
-            return Err(ProgramError::InvalidAccountData);
+        check_account_owner(stake_pool_info, program_id)?;
+        let mut stake_pool = StakePool::try_from_account_data(&stake_pool_info.data.borrow())?;
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
         }
+        stake_pool.check_not_paused(PausedOperation::Withdrawal)?;
 
-        // Validate stake account PDA
-        let (expected_stake_pda, stake_pda_bump) = Pubkey::find_program_address(
-            &[
-                USER_STAKE_SEED_PREFIX,
-                user_pubkey.as_ref(),
-                &user_stake_seed.to_le_bytes(),
-            ],
+        stake_pool.check_authority_withdraw(
+            withdraw_authority_info.key,
             program_id,
-        );
-        if expected_stake_pda != *stake_account_info.key {
-            msg!("Invalid stake account PDA");
-            return Err(ProgramError::InvalidSeeds);
+            stake_pool_info.key,
+        )?;
+        stake_pool.check_reserve_stake(reserve_stake_info)?;
+        check_stake_program(stake_program_info.key)?;
+
+        check_account_owner(withdrawal_ticket_info, program_id)?;
+        let withdrawal_ticket =
+            try_from_slice_unchecked::<WithdrawalTicket>(&withdrawal_ticket_info.data.borrow())?;
+        if !withdrawal_ticket.is_valid()
+            || withdrawal_ticket.stake_pool != *stake_pool_info.key
+            || withdrawal_ticket.owner != *owner_info.key
+        {
+            return Err(StakePoolError::InvalidWithdrawalTicket.into());
+        }
+        if !owner_info.is_signer {
+            return Err(StakePoolError::SignatureMissing.into());
         }
 
-        // Verify stake account is deactivated and ready for withdrawal
-        let stake_state = try_from_slice_unchecked::<stake::state::StakeStateV2>(
-            &stake_account_info.data.borrow(),
+        if !withdrawal_ticket.is_claimable(clock.epoch) {
+            msg!(
+                "Withdrawal ticket is claimable from epoch {}, current epoch {}",
+                withdrawal_ticket.claimable_epoch,
+                clock.epoch
+            );
+            return Err(StakePoolError::WithdrawalTicketNotClaimable.into());
+        }
+        if withdrawal_ticket.lamports > stake_pool.withdrawal_claim_pool_lamports {
+            msg!(
+                "Withdrawal ticket requires {} lamports, claim pool only holds {} lamports",
+                withdrawal_ticket.lamports,
+                stake_pool.withdrawal_claim_pool_lamports
+            );
+            return Err(StakePoolError::InsufficientWithdrawalClaimPool.into());
+        }
+
+        Self::stake_withdraw(
+            stake_pool_info.key,
+            reserve_stake_info.clone(),
+            withdraw_authority_info.clone(),
+            AUTHORITY_WITHDRAW,
+            stake_pool.stake_withdraw_bump_seed,
+            destination_lamports_info.clone(),
+            clock_info.clone(),
+            stake_history_info.clone(),
+            withdrawal_ticket.lamports,
         )?;
 
-        let clock = Clock::get()?;
+        stake_pool.pending_withdrawal_lamports = stake_pool
+            .pending_withdrawal_lamports
+            .checked_sub(withdrawal_ticket.lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        stake_pool.withdrawal_claim_pool_lamports = stake_pool
+            .withdrawal_claim_pool_lamports
+            .checked_sub(withdrawal_ticket.lamports)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        borsh::to_writer(&mut stake_pool_info.data.borrow_mut()[..], &stake_pool)?;
 
-        match stake_state {
-            stake::state::StakeStateV2::Stake(meta, stake, _) => {
-                // Verify the stake's withdrawer is the PDA itself
-                if meta.authorized.withdrawer != expected_stake_pda {
-                    msg!(
-                        "Stake withdrawer mismatch. Expected: {}, got: {}",
-                        expected_stake_pda,
-                        meta.authorized.withdrawer
-                    );
-                    return Err(ProgramError::InvalidAccountData);
-                }
+        // close the ticket, returning its rent to the destination
+        let ticket_rent = withdrawal_ticket_info.lamports();
+        **withdrawal_ticket_info.try_borrow_mut_lamports()? = 0;
+        **destination_lamports_info.try_borrow_mut_lamports()? = destination_lamports_info
+            .lamports()
+            .checked_add(ticket_rent)
+            .ok_or(StakePoolError::CalculationFailure)?;
+        withdrawal_ticket_info.data.borrow_mut().fill(0);
 
-                // Check if stake is fully deactivated
-                if stake.delegation.deactivation_epoch == Epoch::MAX {
-                    msg!("Stake account is not deactivated yet");
-                    return Err(StakePoolError::UserStakeNotActive.into());
-                }
-                if stake.delegation.deactivation_epoch >= clock.epoch {
-                    msg!(
-                        "Stake account not fully deactivated. Deactivation epoch: {}, current: {}",
-                        stake.delegation.deactivation_epoch,
-                        clock.epoch
-                    );
-                    return Err(StakePoolError::UserStakeNotActive.into());
-                }
-            }
-            _ => {
-                msg!("Stake account is not in staked state");
-                return Err(StakePoolError::WrongStakeStake.into());
-            }
+        StakePoolEvent::WithdrawalTicketClaimed {
+            stake_pool: *stake_pool_info.key,
+            ticket: *withdrawal_ticket_info.key,
+            lamports: withdrawal_ticket.lamports,
         }
+        .emit();
 
-        let stake_balance = stake_account_info.lamports();
+        Ok(())
+    }
 
-        // Determine withdrawal amount (u64::MAX means full withdrawal)
-        let withdraw_amount = if lamports == u64::MAX {
-            stake_balance
-        } else {
-            lamports
-        };
+    /// Processes [`MigrateStakePool`](enum.Instruction.html).
+    #[inline(never)] // needed to avoid stack size violation
+    fn process_migrate_stake_pool(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let stake_pool_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        let stake_pda_seeds: &[&[u8]] = &[
-            USER_STAKE_SEED_PREFIX,
-            user_pubkey.as_ref(),
-            &user_stake_seed.to_le_bytes(),
-            &[stake_pda_bump],
-        ];
+        check_system_program(system_program_info.key)?;
+        check_account_owner(stake_pool_info, program_id)?;
+        if !payer_info.is_signer {
+            return Err(StakePoolError::SignatureMissing.into());
+        }
+        if stake_pool_info.data.borrow().first() != Some(&(AccountType::StakePool as u8)) {
+            return Err(StakePoolError::InvalidState.into());
+        }
 
-        invoke_signed(
-            &stake::instruction::withdraw(
-                stake_account_info.key,
-                stake_account_info.key,
-                recipient_info.key,
-                withdraw_amount,
-                None,
-            ),
-            &[
-                stake_account_info.clone(),
-                recipient_info.clone(),
-                clock_info.clone(),
-                stake_history_info.clone(),
-                stake_account_info.clone(),
-            ],
-            &[stake_pda_seeds],
-        )?;
+        if stake_pool_info.data_len() >= STAKE_POOL_LEN {
+            msg!("Stake pool account already has the current layout");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // the bytes past the legacy encoding may be leftovers of a longer
+        // encoding, so only the legacy fields are read
+        let legacy_stake_pool =
+            try_from_slice_unchecked::<LegacyStakePool>(&stake_pool_info.data.borrow())?;
+        let stake_pool = StakePool::from(legacy_stake_pool);
+        if !stake_pool.is_valid() {
+            return Err(StakePoolError::InvalidState.into());
+        }
+
+        let minimum_balance = Rent::get()?.minimum_balance(STAKE_POOL_LEN);
+        let shortfall = minimum_balance.saturating_sub(stake_pool_info.lamports());
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, stake_pool_info.key, shortfall),
+                &[
+                    payer_info.clone(),
+                    stake_pool_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+        stake_pool_info.resize(STAKE_POOL_LEN)?;
+
+        let mut stake_pool_data = stake_pool_info.data.borrow_mut();
+        stake_pool_data.fill(0);
+        borsh::to_writer(&mut stake_pool_data[..], &stake_pool)?;
 
         Ok(())
     }
@@ -4556,7 +8195,7 @@ impl Processor {
             }
             StakePoolInstruction::DepositStake => {
                 msg!("Instruction: DepositStake");
-                Self::process_deposit_stake(program_id, accounts, None)
+                Self::process_deposit_stake(program_id, accounts, None, None)
             }
             StakePoolInstruction::WithdrawStake(amount) => {
                 msg!("Instruction: WithdrawStake");
@@ -4566,14 +8205,26 @@ impl Processor {
                 msg!("Instruction: SetFee");
                 Self::process_set_fee(program_id, accounts, fee)
             }
+            #[cfg(feature = "legacy-authority-transfer")]
             StakePoolInstruction::SetManager => {
                 msg!("Instruction: SetManager");
                 Self::process_set_manager(program_id, accounts)
             }
+            #[cfg(not(feature = "legacy-authority-transfer"))]
+            StakePoolInstruction::SetManager => {
+                msg!("Instruction: SetManager is disabled, use ProposeManager and AcceptManager");
+                Err(ProgramError::InvalidInstructionData)
+            }
+            #[cfg(feature = "legacy-authority-transfer")]
             StakePoolInstruction::SetStaker => {
                 msg!("Instruction: SetStaker");
                 Self::process_set_staker(program_id, accounts)
             }
+            #[cfg(not(feature = "legacy-authority-transfer"))]
+            StakePoolInstruction::SetStaker => {
+                msg!("Instruction: SetStaker is disabled, use ProposeStaker and AcceptStaker");
+                Err(ProgramError::InvalidInstructionData)
+            }
             StakePoolInstruction::SetFundingAuthority(funding_type) => {
                 msg!("Instruction: SetFundingAuthority");
                 Self::process_set_funding_authority(program_id, accounts, funding_type)
@@ -4594,16 +8245,32 @@ impl Processor {
                 msg!("Instruction: UpdateTokenMetadata");
                 Self::process_update_pool_token_metadata(program_id, accounts, name, symbol, uri)
             }
-            #[allow(deprecated)]
-            StakePoolInstruction::Redelegate { .. } => {
-                msg!("Instruction: Redelegate will not be enabled");
-                Err(ProgramError::InvalidInstructionData)
+            StakePoolInstruction::Redelegate {
+                lamports,
+                source_transient_stake_seed,
+                ephemeral_stake_seed,
+                destination_transient_stake_seed,
+            } => {
+                msg!("Instruction: Redelegate");
+                Self::process_redelegate(
+                    program_id,
+                    accounts,
+                    lamports,
+                    source_transient_stake_seed,
+                    ephemeral_stake_seed,
+                    destination_transient_stake_seed,
+                )
             }
             StakePoolInstruction::DepositStakeWithSlippage {
                 minimum_pool_tokens_out,
             } => {
                 msg!("Instruction: DepositStakeWithSlippage");
-                Self::process_deposit_stake(program_id, accounts, Some(minimum_pool_tokens_out))
+                Self::process_deposit_stake(
+                    program_id,
+                    accounts,
+                    Some(minimum_pool_tokens_out),
+                    None,
+                )
             }
             StakePoolInstruction::WithdrawStakeWithSlippage {
                 pool_tokens_in,
@@ -4693,63 +8360,195 @@ impl Processor {
                     user_stake_seed,
                 )
             }
-        }
-    }
-}
-
-impl PrintProgramError for StakePoolError {
-    fn print<E>(&self)
-    where
-        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
-    {
-        match self {
-            StakePoolError::AlreadyInUse => msg!("Error: The account cannot be initialized because it is already being used"),
-            StakePoolError::InvalidProgramAddress => msg!("Error: The program address provided doesn't match the value generated by the program"),
-            StakePoolError::InvalidState => msg!("Error: The stake pool state is invalid"),
-            StakePoolError::CalculationFailure => msg!("Error: The calculation failed"),
-            StakePoolError::FeeTooHigh => msg!("Error: Stake pool fee > 1"),
-            StakePoolError::WrongAccountMint => msg!("Error: Token account is associated with the wrong mint"),
-            StakePoolError::WrongManager => msg!("Error: Wrong pool manager account"),
-            StakePoolError::SignatureMissing => msg!("Error: Required signature is missing"),
-            StakePoolError::InvalidValidatorStakeList => msg!("Error: Invalid validator stake list account"),
-            StakePoolError::InvalidFeeAccount => msg!("Error: Invalid manager fee account"),
-            StakePoolError::WrongPoolMint => msg!("Error: Specified pool mint account is wrong"),
-            StakePoolError::WrongStakeStake => msg!("Error: Stake account is not in the state expected by the program"),
-            StakePoolError::UserStakeNotActive => msg!("Error: User stake is not active"),
-            StakePoolError::ValidatorAlreadyAdded => msg!("Error: Stake account voting for this validator already exists in the pool"),
-            StakePoolError::ValidatorNotFound => msg!("Error: Stake account for this validator not found in the pool"),
-            StakePoolError::InvalidStakeAccountAddress => msg!("Error: Stake account address not properly derived from the validator address"),
-            StakePoolError::StakeListOutOfDate => msg!("Error: Identify validator stake accounts with old balances and update them"),
-            StakePoolError::StakeListAndPoolOutOfDate => msg!("Error: First update old validator stake account balances and then pool stake balance"),
-            StakePoolError::UnknownValidatorStakeAccount => {
-                msg!("Error: Validator stake account is not found in the list storage")
-            }
-            StakePoolError::WrongMintingAuthority => msg!("Error: Wrong minting authority set for mint pool account"),
-            StakePoolError::UnexpectedValidatorListAccountSize=> msg!("Error: The size of the given validator stake list does match the expected amount"),
-            StakePoolError::WrongStaker=> msg!("Error: Wrong pool staker account"),
-            StakePoolError::NonZeroPoolTokenSupply => msg!("Error: Pool token supply is not zero on initialization"),
-            StakePoolError::StakeLamportsNotEqualToMinimum => msg!("Error: The lamports in the validator stake account is not equal to the minimum"),
-            StakePoolError::IncorrectDepositVoteAddress => msg!("Error: The provided deposit stake account is not delegated to the preferred deposit vote account"),
-            StakePoolError::IncorrectWithdrawVoteAddress => msg!("Error: The provided withdraw stake account is not the preferred deposit vote account"),
-            StakePoolError::InvalidMintFreezeAuthority => msg!("Error: The mint has an invalid freeze authority"),
-            StakePoolError::FeeIncreaseTooHigh => msg!("Error: The fee cannot increase by a factor exceeding the stipulated ratio"),
-            StakePoolError::WithdrawalTooSmall => msg!("Error: Not enough pool tokens provided to withdraw 1-lamport stake"),
-            StakePoolError::DepositTooSmall => msg!("Error: Not enough lamports provided for deposit to result in one pool token"),
-            StakePoolError::InvalidStakeDepositAuthority => msg!("Error: Provided stake deposit authority does not match the program's"),
-            StakePoolError::InvalidSolDepositAuthority => msg!("Error: Provided sol deposit authority does not match the program's"),
-            StakePoolError::InvalidPreferredValidator => msg!("Error: Provided preferred validator is invalid"),
-            StakePoolError::TransientAccountInUse => msg!("Error: Provided validator stake account already has a transient stake account in use"),
-            StakePoolError::InvalidSolWithdrawAuthority => msg!("Error: Provided sol withdraw authority does not match the program's"),
-            StakePoolError::SolWithdrawalTooLarge => msg!("Error: Too much SOL withdrawn from the stake pool's reserve account"),
-            StakePoolError::InvalidMetadataAccount => msg!("Error: Metadata account derived from pool mint account does not match the one passed to program"),
-            StakePoolError::UnsupportedMintExtension => msg!("Error: mint has an unsupported extension"),
-            StakePoolError::UnsupportedFeeAccountExtension => msg!("Error: fee account has an unsupported extension"),
-            StakePoolError::ExceededSlippage => msg!("Error: instruction exceeds desired slippage limit"),
-            StakePoolError::IncorrectMintDecimals => msg!("Error: Provided mint does not have 9 decimals to match SOL"),
-            StakePoolError::ReserveDepleted => msg!("Error: Pool reserve does not have enough lamports to fund rent-exempt reserve in split destination. Deposit more SOL in reserve, or pre-fund split destination with the rent-exempt reserve for a stake account."),
-            StakePoolError::MissingRequiredSysvar => msg!("Missing required sysvar account"),
-            StakePoolError::EpochRewardDistributionInProgress => msg!("Epoch reward distribution is currently in progress, stakes are still being updated"),
-            StakePoolError::TooManyValidatorsInPool => msg!("The stake pool has too many validators in the pool"),
+            StakePoolInstruction::RequestWithdrawalTicket {
+                pool_tokens_in,
+                ticket_seed,
+            } => {
+                msg!("Instruction: RequestWithdrawalTicket");
+                Self::process_request_withdrawal_ticket(
+                    program_id,
+                    accounts,
+                    pool_tokens_in,
+                    ticket_seed,
+                )
+            }
+            StakePoolInstruction::ClaimWithdrawalTicket => {
+                msg!("Instruction: ClaimWithdrawalTicket");
+                Self::process_claim_withdrawal_ticket(program_id, accounts)
+            }
+            StakePoolInstruction::DepositStakeWithSession {
+                minimum_pool_tokens_out,
+                user_stake_seed,
+            } => {
+                msg!("Instruction: DepositStakeWithSession");
+                Self::process_deposit_stake(
+                    program_id,
+                    accounts,
+                    Some(minimum_pool_tokens_out),
+                    Some(user_stake_seed),
+                )
+            }
+            StakePoolInstruction::SetCap { cap } => {
+                msg!("Instruction: SetCap");
+                Self::process_set_cap(program_id, accounts, cap)
+            }
+            StakePoolInstruction::SetPauser => {
+                msg!("Instruction: SetPauser");
+                Self::process_set_pauser(program_id, accounts)
+            }
+            StakePoolInstruction::SetPaused { paused_operations } => {
+                msg!("Instruction: SetPaused");
+                Self::process_update_paused(
+                    program_id,
+                    accounts,
+                    paused_operations,
+                    !paused_operations & PausedOperation::ALL,
+                )
+            }
+            StakePoolInstruction::ProposeManager => {
+                msg!("Instruction: ProposeManager");
+                Self::process_propose_manager(program_id, accounts)
+            }
+            StakePoolInstruction::AcceptManager => {
+                msg!("Instruction: AcceptManager");
+                Self::process_accept_manager(program_id, accounts)
+            }
+            StakePoolInstruction::ProposeStaker => {
+                msg!("Instruction: ProposeStaker");
+                Self::process_propose_staker(program_id, accounts)
+            }
+            StakePoolInstruction::AcceptStaker => {
+                msg!("Instruction: AcceptStaker");
+                Self::process_accept_staker(program_id, accounts)
+            }
+            StakePoolInstruction::SetFeeChangeDelay { delay_epochs } => {
+                msg!("Instruction: SetFeeChangeDelay");
+                Self::process_set_fee_change_delay(program_id, accounts, delay_epochs)
+            }
+            StakePoolInstruction::RegisterReferrer { code } => {
+                msg!("Instruction: RegisterReferrer");
+                Self::process_register_referrer(program_id, accounts, code)
+            }
+            StakePoolInstruction::UpdateReferrer => {
+                msg!("Instruction: UpdateReferrer");
+                Self::process_update_referrer(program_id, accounts)
+            }
+            StakePoolInstruction::DepositSolToValidator {
+                lamports_in,
+                minimum_pool_tokens_out,
+            } => {
+                msg!("Instruction: DepositSolToValidator");
+                Self::process_deposit_sol_to_validator(
+                    program_id,
+                    accounts,
+                    lamports_in,
+                    minimum_pool_tokens_out,
+                )
+            }
+            StakePoolInstruction::SetValidatorMaxShare { max_share_bps } => {
+                msg!("Instruction: SetValidatorMaxShare");
+                Self::process_set_validator_max_share(program_id, accounts, max_share_bps)
+            }
+            StakePoolInstruction::UpdateValidatorMetadata {
+                notes_hash,
+                max_commission,
+            } => {
+                msg!("Instruction: UpdateValidatorMetadata");
+                Self::process_update_validator_metadata(
+                    program_id,
+                    accounts,
+                    notes_hash,
+                    max_commission,
+                )
+            }
+            StakePoolInstruction::DeactivateHighCommissionValidator => {
+                msg!("Instruction: DeactivateHighCommissionValidator");
+                Self::process_deactivate_high_commission_validator(program_id, accounts)
+            }
+            StakePoolInstruction::DeactivateDelinquentValidator => {
+                msg!("Instruction: DeactivateDelinquentValidator");
+                Self::process_deactivate_delinquent_validator(program_id, accounts)
+            }
+            StakePoolInstruction::SetFeeSplit { weights_bps } => {
+                msg!("Instruction: SetFeeSplit");
+                Self::process_set_fee_split(program_id, accounts, weights_bps)
+            }
+            StakePoolInstruction::SetInsuranceFee { insurance_fee_bps } => {
+                msg!("Instruction: SetInsuranceFee");
+                Self::process_set_insurance_fee(program_id, accounts, insurance_fee_bps)
+            }
+            StakePoolInstruction::InjectInsuranceFund { lamports } => {
+                msg!("Instruction: InjectInsuranceFund");
+                Self::process_inject_insurance_fund(program_id, accounts, lamports)
+            }
+            StakePoolInstruction::CreateRateHistory => {
+                msg!("Instruction: CreateRateHistory");
+                Self::process_create_rate_history(program_id, accounts)
+            }
+            StakePoolInstruction::CreateOracle => {
+                msg!("Instruction: CreateOracle");
+                Self::process_create_oracle(program_id, accounts)
+            }
+            StakePoolInstruction::GetExchangeRate => {
+                msg!("Instruction: GetExchangeRate");
+                Self::process_get_exchange_rate(program_id, accounts)
+            }
+            StakePoolInstruction::CreateTokenMetadataExtension { name, symbol, uri } => {
+                msg!("Instruction: CreateTokenMetadataExtension");
+                Self::process_create_token_metadata_extension(
+                    program_id, accounts, name, symbol, uri,
+                )
+            }
+            StakePoolInstruction::UpdateTokenMetadataExtensionField { field, value } => {
+                msg!("Instruction: UpdateTokenMetadataExtensionField");
+                Self::process_update_token_metadata_extension_field(
+                    program_id, accounts, field, value,
+                )
+            }
+            StakePoolInstruction::RemoveTokenMetadataExtensionKey { key } => {
+                msg!("Instruction: RemoveTokenMetadataExtensionKey");
+                Self::process_remove_token_metadata_extension_key(program_id, accounts, key)
+            }
+            StakePoolInstruction::WithdrawSolWithSession {
+                pool_tokens_in,
+                minimum_lamports_out,
+            } => {
+                msg!("Instruction: WithdrawSolWithSession");
+                Self::process_withdraw_sol_with_session(
+                    program_id,
+                    accounts,
+                    pool_tokens_in,
+                    Some(minimum_lamports_out),
+                )
+            }
+            StakePoolInstruction::CloseUserStakeAccountsWithSession { user_stake_seeds } => {
+                msg!("Instruction: CloseUserStakeAccountsWithSession");
+                Self::process_close_user_stake_accounts_with_session(
+                    program_id,
+                    accounts,
+                    user_stake_seeds,
+                )
+            }
+            StakePoolInstruction::WithdrawStakeMulti {
+                pool_tokens_in,
+                minimum_lamports_out,
+            } => {
+                msg!("Instruction: WithdrawStakeMulti");
+                Self::process_withdraw_stake_multi(
+                    program_id,
+                    accounts,
+                    pool_tokens_in,
+                    minimum_lamports_out,
+                )
+            }
+            StakePoolInstruction::MigrateStakePool => {
+                msg!("Instruction: MigrateStakePool");
+                Self::process_migrate_stake_pool(program_id, accounts)
+            }
+            StakePoolInstruction::UpdatePaused { pause, unpause } => {
+                msg!("Instruction: UpdatePaused");
+                Self::process_update_paused(program_id, accounts, pause, unpause)
+            }
         }
     }
 }
@@ -0,0 +1,261 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    fogo_sessions_sdk::{session::SESSION_MANAGER_ID, token::PROGRAM_SIGNER_SEED},
+    helpers::{wsol::manually_serialize_session, *},
+    solana_program::{clock::Clock, instruction::InstructionError, stake::state::StakeStateV2},
+    solana_program_test::*,
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    solana_stake_interface as stake,
+    spl_stake_pool::{
+        error::StakePoolError, find_user_stake_program_address, id,
+        instruction::close_user_stake_accounts_with_session, MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+/// Writes a user stake PDA delegated with `stake_lamports` and deactivated
+/// at `deactivation_epoch`, returning its rent-exempt reserve
+async fn set_user_stake_account(
+    context: &mut ProgramTestContext,
+    user: &Pubkey,
+    user_stake_seed: u64,
+    stake_lamports: u64,
+    deactivation_epoch: u64,
+) -> u64 {
+    let (user_stake_pda, _) = find_user_stake_program_address(&id(), user, user_stake_seed);
+    let rent_exempt_reserve = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(StakeStateV2::size_of());
+    let meta = stake::state::Meta {
+        rent_exempt_reserve,
+        authorized: stake::state::Authorized {
+            staker: user_stake_pda,
+            withdrawer: user_stake_pda,
+        },
+        lockup: stake::state::Lockup::default(),
+    };
+    let stake = stake::state::Stake {
+        delegation: stake::state::Delegation {
+            voter_pubkey: Pubkey::new_unique(),
+            stake: stake_lamports,
+            activation_epoch: 0,
+            deactivation_epoch,
+            ..Default::default()
+        },
+        credits_observed: 0,
+    };
+    let mut data = vec![0u8; StakeStateV2::size_of()];
+    let stake_data = bincode::serialize(&StakeStateV2::Stake(
+        meta,
+        stake,
+        stake::stake_flags::StakeFlags::empty(),
+    ))
+    .unwrap();
+    data[..stake_data.len()].copy_from_slice(&stake_data);
+    let stake_account = Account {
+        lamports: stake_lamports + rent_exempt_reserve,
+        data,
+        owner: stake::program::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&user_stake_pda, &AccountSharedData::from(stake_account));
+    rent_exempt_reserve
+}
+
+async fn close_user_stake_accounts(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    session_signer: &Keypair,
+    user_wallet: &Pubkey,
+    user_stake_seeds: Vec<u64>,
+) -> Option<TransactionError> {
+    let instruction = close_user_stake_accounts_with_session(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &session_signer.pubkey(),
+        user_wallet,
+        user_stake_seeds,
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, session_signer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+/// Initializes a stake pool and moves to the next epoch, so that stake
+/// deactivated in epoch 0 is inactive, then opens a session for a new user.
+/// Accounts are only written after the warp, since the test bank cannot warp
+/// past accounts set directly.
+async fn setup() -> (ProgramTestContext, StakePoolAccounts, Keypair, Keypair) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    context.warp_to_slot(first_normal_slot + 1).unwrap();
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let session_signer = Keypair::new();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let (program_signer, _) = Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], &id());
+    let session_data = manually_serialize_session(
+        &context.payer.pubkey(),
+        &user.pubkey(),
+        clock.unix_timestamp + 3600,
+        &id(),
+        &program_signer,
+    );
+    let session_account = Account {
+        lamports: context
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap()
+            .minimum_balance(session_data.len()),
+        data: session_data,
+        owner: SESSION_MANAGER_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(
+        &session_signer.pubkey(),
+        &AccountSharedData::from(session_account),
+    );
+
+    (context, stake_pool_accounts, user, session_signer)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, stake_pool_accounts, user, session_signer) = setup().await;
+    let user_stake_seeds = vec![0, 3, 7];
+    let mut rent_lamports = 0;
+    for (i, user_stake_seed) in user_stake_seeds.iter().enumerate() {
+        rent_lamports += set_user_stake_account(
+            &mut context,
+            &user.pubkey(),
+            *user_stake_seed,
+            TEST_STAKE_AMOUNT * (i as u64 + 1),
+            0,
+        )
+        .await;
+    }
+    let initial_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+
+    let error = close_user_stake_accounts(
+        &mut context,
+        &stake_pool_accounts,
+        &session_signer,
+        &user.pubkey(),
+        user_stake_seeds.clone(),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    for user_stake_seed in user_stake_seeds {
+        let (user_stake_pda, _) =
+            find_user_stake_program_address(&id(), &user.pubkey(), user_stake_seed);
+        assert!(context
+            .banks_client
+            .get_account(user_stake_pda)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    let reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(reserve_lamports, initial_reserve_lamports + rent_lamports);
+    let user_lamports = get_account(&mut context.banks_client, &user.pubkey())
+        .await
+        .lamports;
+    assert_eq!(user_lamports, TEST_STAKE_AMOUNT * 6);
+}
+
+#[tokio::test]
+async fn fail_not_deactivated() {
+    let (mut context, stake_pool_accounts, user, session_signer) = setup().await;
+    set_user_stake_account(&mut context, &user.pubkey(), 0, TEST_STAKE_AMOUNT, 0).await;
+    set_user_stake_account(&mut context, &user.pubkey(), 1, TEST_STAKE_AMOUNT, u64::MAX).await;
+
+    let error = close_user_stake_accounts(
+        &mut context,
+        &stake_pool_accounts,
+        &session_signer,
+        &user.pubkey(),
+        vec![0, 1],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::UserStakeNotActive as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_recipient() {
+    let (mut context, stake_pool_accounts, user, session_signer) = setup().await;
+    set_user_stake_account(&mut context, &user.pubkey(), 0, TEST_STAKE_AMOUNT, 0).await;
+
+    // the PDAs are derived from the recipient, so they no longer match the
+    // session user either
+    let error = close_user_stake_accounts(
+        &mut context,
+        &stake_pool_accounts,
+        &session_signer,
+        &Pubkey::new_unique(),
+        vec![0],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
@@ -0,0 +1,457 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        clock::{Clock, Epoch},
+        instruction::{Instruction, InstructionError},
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+        rent::Rent,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::{Account as SolanaAccount, AccountSharedData, WritableAccount},
+        signature::Signer,
+        transaction::{Transaction, TransactionError},
+    },
+    solana_vote_program::vote_state::{VoteState, VoteState1_14_11, VoteStateVersions},
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_stake_program_address, find_transient_stake_program_address,
+        find_validator_metadata_program_address, id, instruction,
+        state::{AccountType, CapType, StakeStatus, ValidatorMetadata},
+        DELINQUENT_VALIDATOR_CRANK_REWARD,
+    },
+    test_case::test_case,
+};
+
+const MAX_VOTE_AGE_EPOCHS: u64 = 2;
+
+#[derive(Clone, Copy)]
+enum VoteStateVersion {
+    V1_14_11,
+    Current,
+}
+
+fn add_vote_account_with_credits(
+    program_test: &mut ProgramTest,
+    version: VoteStateVersion,
+    last_credited_epoch: Option<Epoch>,
+) -> Pubkey {
+    let vote_account_address = Pubkey::new_unique();
+    // votes and a root slot in front of the epoch credits
+    let mut vote_state = VoteState::new_rand_for_tests(Pubkey::new_unique(), 100);
+    if let Some(last_credited_epoch) = last_credited_epoch {
+        for epoch in last_credited_epoch.saturating_sub(3)..=last_credited_epoch {
+            vote_state.increment_credits(epoch, 100);
+        }
+    }
+    let vote_state = match version {
+        VoteStateVersion::V1_14_11 => {
+            VoteStateVersions::V1_14_11(Box::new(VoteState1_14_11::from(vote_state)))
+        }
+        VoteStateVersion::Current => VoteStateVersions::new_current(vote_state),
+    };
+    let mut data = bincode::serialize(&vote_state).unwrap();
+    data.resize(VoteState::size_of(), 0);
+    program_test.add_account(
+        vote_account_address,
+        SolanaAccount::create(
+            LAMPORTS_PER_SOL,
+            data,
+            solana_vote_program::id(),
+            false,
+            Epoch::default(),
+        ),
+    );
+    vote_account_address
+}
+
+async fn setup(
+    version: VoteStateVersion,
+    last_credited_epoch: Option<Epoch>,
+    max_vote_age_epochs: Option<u64>,
+    admission_epoch: Option<Epoch>,
+) -> (ProgramTestContext, StakePoolAccounts, Pubkey) {
+    let mut program_test = program_test();
+    let stake_pool_accounts = StakePoolAccounts::default();
+    let stake_pool_pubkey = stake_pool_accounts.stake_pool.pubkey();
+    let (mut stake_pool, mut validator_list) = stake_pool_accounts.state();
+    stake_pool.last_update_epoch = FIRST_NORMAL_EPOCH;
+    stake_pool.max_validator_vote_age_epochs = max_vote_age_epochs;
+
+    let vote_account_address =
+        add_vote_account_with_credits(&mut program_test, version, last_credited_epoch);
+    add_validator_stake_account(
+        &mut program_test,
+        &mut stake_pool,
+        &mut validator_list,
+        &stake_pool_pubkey,
+        &stake_pool_accounts.withdraw_authority,
+        &vote_account_address,
+        10 * LAMPORTS_PER_SOL,
+        StakeStatus::Active,
+    );
+    if let Some(admission_epoch) = admission_epoch {
+        let validator_metadata = ValidatorMetadata {
+            account_type: AccountType::ValidatorMetadata,
+            stake_pool: stake_pool_pubkey,
+            vote_account: vote_account_address,
+            admission_epoch,
+            max_commission: 100,
+            ..ValidatorMetadata::default()
+        };
+        let data = borsh::to_vec(&validator_metadata).unwrap();
+        let (validator_metadata_address, _) = find_validator_metadata_program_address(
+            &id(),
+            &stake_pool_pubkey,
+            &vote_account_address,
+        );
+        program_test.add_account(
+            validator_metadata_address,
+            SolanaAccount::create(
+                Rent::default().minimum_balance(data.len()),
+                data,
+                id(),
+                false,
+                Epoch::default(),
+            ),
+        );
+    }
+
+    add_reserve_stake_account(
+        &mut program_test,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        LAMPORTS_PER_SOL,
+    );
+    stake_pool.total_lamports += LAMPORTS_PER_SOL;
+    add_stake_pool_account(&mut program_test, &stake_pool_pubkey, &stake_pool);
+    add_validator_list_account(
+        &mut program_test,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &validator_list,
+        stake_pool_accounts.max_validators,
+    );
+    add_mint_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        stake_pool.pool_token_supply,
+    );
+    add_token_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.manager.pubkey(),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let epoch_schedule = &context.genesis_config().epoch_schedule;
+    let slot = epoch_schedule.first_normal_slot + epoch_schedule.slots_per_epoch + 1;
+    context.warp_to_slot(slot).unwrap();
+
+    (context, stake_pool_accounts, vote_account_address)
+}
+
+fn deactivate_delinquent_validator_instruction(
+    context: &ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    stake_vote_account: &Pubkey,
+    vote_account: &Pubkey,
+) -> Instruction {
+    let stake_pool_pubkey = stake_pool_accounts.stake_pool.pubkey();
+    let (stake_account, _) =
+        find_stake_program_address(&id(), stake_vote_account, &stake_pool_pubkey, None);
+    let (transient_stake_account, _) =
+        find_transient_stake_program_address(&id(), stake_vote_account, &stake_pool_pubkey, 0);
+    instruction::deactivate_delinquent_validator(
+        &id(),
+        &stake_pool_pubkey,
+        &stake_pool_accounts.withdraw_authority,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_account,
+        &transient_stake_account,
+        vote_account,
+        &context.payer.pubkey(),
+    )
+}
+
+async fn deactivate_delinquent_validator(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    stake_vote_account: &Pubkey,
+    vote_account: &Pubkey,
+) -> Option<TransactionError> {
+    let instruction = deactivate_delinquent_validator_instruction(
+        context,
+        stake_pool_accounts,
+        stake_vote_account,
+        vote_account,
+    );
+    process_instruction(context, instruction, &[]).await
+}
+
+fn not_delinquent() -> TransactionError {
+    TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(StakePoolError::ValidatorNotDelinquent as u32),
+    )
+}
+
+#[test_case(VoteStateVersion::V1_14_11; "v1_14_11")]
+#[test_case(VoteStateVersion::Current; "current")]
+#[tokio::test]
+async fn success(version: VoteStateVersion) {
+    let (mut context, stake_pool_accounts, vote_account) = setup(
+        version,
+        Some(FIRST_NORMAL_EPOCH - MAX_VOTE_AGE_EPOCHS - 1),
+        Some(MAX_VOTE_AGE_EPOCHS),
+        None,
+    )
+    .await;
+    let pre_stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let pre_payer_lamports = get_account(&mut context.banks_client, &context.payer.pubkey())
+        .await
+        .lamports;
+
+    let error = deactivate_delinquent_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &vote_account,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    let validator_stake_info = validator_list.find(&vote_account).unwrap();
+    assert_eq!(
+        validator_stake_info.status,
+        StakeStatus::DeactivatingValidator.into()
+    );
+
+    // the reward covers more than the transaction fee
+    let payer_lamports = get_account(&mut context.banks_client, &context.payer.pubkey())
+        .await
+        .lamports;
+    assert!(payer_lamports > pre_payer_lamports);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool.total_lamports,
+        pre_stake_pool.total_lamports - DELINQUENT_VALIDATOR_CRANK_REWARD
+    );
+}
+
+#[tokio::test]
+async fn success_never_voted() {
+    let (mut context, stake_pool_accounts, vote_account) = setup(
+        VoteStateVersion::Current,
+        None,
+        Some(MAX_VOTE_AGE_EPOCHS),
+        None,
+    )
+    .await;
+
+    let error = deactivate_delinquent_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &vote_account,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn fail_recently_admitted() {
+    let (mut context, stake_pool_accounts, vote_account) = setup(
+        VoteStateVersion::Current,
+        None,
+        Some(MAX_VOTE_AGE_EPOCHS),
+        Some(FIRST_NORMAL_EPOCH),
+    )
+    .await;
+
+    let error = deactivate_delinquent_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &vote_account,
+    )
+    .await
+    .unwrap();
+    assert_eq!(error, not_delinquent());
+}
+
+#[tokio::test]
+async fn fail_recently_admitted_without_metadata() {
+    let (mut context, stake_pool_accounts, vote_account) = setup(
+        VoteStateVersion::Current,
+        None,
+        Some(MAX_VOTE_AGE_EPOCHS),
+        Some(FIRST_NORMAL_EPOCH),
+    )
+    .await;
+
+    // leaving out the metadata must not make a new validator look delinquent
+    let mut instruction = deactivate_delinquent_validator_instruction(
+        &context,
+        &stake_pool_accounts,
+        &vote_account,
+        &vote_account,
+    );
+    instruction.accounts.pop();
+    let error = process_instruction(&mut context, instruction, &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+#[test_case(VoteStateVersion::V1_14_11; "v1_14_11")]
+#[test_case(VoteStateVersion::Current; "current")]
+#[tokio::test]
+async fn fail_recent_vote(version: VoteStateVersion) {
+    let (mut context, stake_pool_accounts, vote_account) = setup(
+        version,
+        Some(FIRST_NORMAL_EPOCH - MAX_VOTE_AGE_EPOCHS),
+        Some(MAX_VOTE_AGE_EPOCHS),
+        None,
+    )
+    .await;
+
+    let error = deactivate_delinquent_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &vote_account,
+    )
+    .await
+    .unwrap();
+    assert_eq!(error, not_delinquent());
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(clock.epoch, FIRST_NORMAL_EPOCH);
+}
+
+#[tokio::test]
+async fn fail_without_policy() {
+    let (mut context, stake_pool_accounts, vote_account) =
+        setup(VoteStateVersion::Current, None, None, None).await;
+
+    let error = deactivate_delinquent_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &vote_account,
+    )
+    .await
+    .unwrap();
+    assert_eq!(error, not_delinquent());
+}
+
+#[tokio::test]
+async fn fail_wrong_vote_account() {
+    let (mut context, stake_pool_accounts, vote_account) = setup(
+        VoteStateVersion::Current,
+        Some(FIRST_NORMAL_EPOCH),
+        Some(MAX_VOTE_AGE_EPOCHS),
+        None,
+    )
+    .await;
+
+    // a silent vote account can't stand in for the validator's own
+    let error = deactivate_delinquent_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &Pubkey::new_unique(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidStakeAccountAddress as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_unsupported_vote_state_version() {
+    let (mut context, stake_pool_accounts, vote_account) = setup(
+        VoteStateVersion::Current,
+        Some(FIRST_NORMAL_EPOCH),
+        Some(MAX_VOTE_AGE_EPOCHS),
+        None,
+    )
+    .await;
+
+    // a version the vote program doesn't know can't be read
+    let mut account = context
+        .banks_client
+        .get_account(vote_account)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data[..4].copy_from_slice(&4u32.to_le_bytes());
+    context.set_account(&vote_account, &AccountSharedData::from(account));
+
+    let error = deactivate_delinquent_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &vote_account,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[tokio::test]
+async fn fail_zero_vote_age() {
+    let (context, stake_pool_accounts, _vote_account) =
+        setup(VoteStateVersion::Current, None, None, None).await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_cap(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            CapType::ValidatorVoteAge(Some(0)),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
@@ -15,7 +15,7 @@ use {
         transport::TransportError,
     },
     spl_stake_pool::{
-        error, id,
+        error, event, id,
         instruction::{self, FundingType},
         state, MINIMUM_RESERVE_LAMPORTS,
     },
@@ -449,19 +449,27 @@ async fn success_with_referral_fee() {
         Some(&context.payer.pubkey()),
     );
     transaction.sign(&[&context.payer], context.last_blockhash);
-    context
-        .banks_client
-        .process_transaction(transaction)
+    let events = process_transaction_with_events(&mut context.banks_client, transaction)
         .await
         .unwrap();
 
     let referrer_balance_post =
         get_token_balance(&mut context.banks_client, &referrer_token_account.pubkey()).await;
-    let referral_fee = stake_pool_accounts.calculate_sol_referral_fee(
-        stake_pool_accounts.calculate_sol_deposit_fee(TEST_STAKE_AMOUNT),
-    );
+    let deposit_fee = stake_pool_accounts.calculate_sol_deposit_fee(TEST_STAKE_AMOUNT);
+    let referral_fee = stake_pool_accounts.calculate_sol_referral_fee(deposit_fee);
     assert!(referral_fee > 0);
     assert_eq!(referrer_balance_pre + referral_fee, referrer_balance_post);
+
+    assert_eq!(
+        events,
+        vec![event::StakePoolEvent::DepositSol {
+            stake_pool: stake_pool_accounts.stake_pool.pubkey(),
+            lamports: TEST_STAKE_AMOUNT,
+            pool_tokens: TEST_STAKE_AMOUNT - deposit_fee,
+            manager_fee_pool_tokens: deposit_fee - referral_fee,
+            referral_fee_pool_tokens: referral_fee,
+        }]
+    );
 }
 
 #[tokio::test]
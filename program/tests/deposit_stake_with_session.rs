@@ -0,0 +1,507 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    fogo_sessions_sdk::session::SESSION_MANAGER_ID,
+    fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED,
+    helpers::{wsol::manually_serialize_session, *},
+    solana_program::{
+        borsh1::try_from_slice_unchecked, instruction::InstructionError, stake::state::StakeStateV2,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        clock::Clock,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    solana_stake_interface as stake,
+    spl_stake_pool::{
+        error::StakePoolError, find_user_stake_program_address, id,
+        instruction::deposit_stake_with_session, MINIMUM_RESERVE_LAMPORTS,
+    },
+    test_case::test_case,
+};
+
+const USER_STAKE_SEED: u64 = 7;
+
+/// Setup helper for deposit stake with session tests.
+///
+/// Creates an active stake account delegated to the pool's validator and
+/// authorized to the user stake PDA, a session for the user, and the user's
+/// pool token ATA.
+async fn setup(
+    token_program_id: Pubkey,
+) -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    ValidatorStakeAccount,
+    Keypair, // user
+    Keypair, // session_keypair
+    Pubkey,  // user stake PDA
+    Pubkey,  // pool_token_ata
+    u64,     // stake lamports
+) {
+    let mut context = program_test().start_with_context().await;
+
+    let stake_pool_accounts = StakePoolAccounts::new_with_token_program(token_program_id);
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let validator_stake_account = simple_add_validator_to_pool(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        None,
+    )
+    .await;
+
+    let user = Keypair::new();
+    let (user_stake_pda, _) =
+        find_user_stake_program_address(&id(), &user.pubkey(), USER_STAKE_SEED);
+
+    // make a delegated stake account, then hand both authorities to the user
+    // stake PDA and move it to the PDA address
+    let authority = Keypair::new();
+    let deposit_stake = Keypair::new();
+    let authorized = stake::state::Authorized {
+        staker: authority.pubkey(),
+        withdrawer: authority.pubkey(),
+    };
+    let stake_lamports = create_independent_stake_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &deposit_stake,
+        &authorized,
+        &stake::state::Lockup::default(),
+        TEST_STAKE_AMOUNT,
+    )
+    .await;
+    delegate_stake_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &deposit_stake.pubkey(),
+        &authority,
+        &validator_stake_account.vote.pubkey(),
+    )
+    .await;
+    for stake_authorize in [
+        stake::state::StakeAuthorize::Staker,
+        stake::state::StakeAuthorize::Withdrawer,
+    ] {
+        authorize_stake_account(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &deposit_stake.pubkey(),
+            &authority,
+            &user_stake_pda,
+            stake_authorize,
+        )
+        .await;
+    }
+    let stake_account = get_account(&mut context.banks_client, &deposit_stake.pubkey()).await;
+    context.set_account(&user_stake_pda, &AccountSharedData::from(stake_account));
+
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    context.warp_to_slot(first_normal_slot + 1).unwrap();
+    stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+
+    // make the session
+    let session_keypair = Keypair::new();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let expiration = clock.unix_timestamp + 3600;
+    let (signer_pda, _) = Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], &id());
+    let session_data = manually_serialize_session(
+        &context.payer.pubkey(),
+        &user.pubkey(),
+        expiration,
+        &id(),
+        &signer_pda,
+    );
+    let session_account = Account {
+        lamports: context
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap()
+            .minimum_balance(session_data.len()),
+        data: session_data,
+        owner: SESSION_MANAGER_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&session_keypair.pubkey(), &session_account.into());
+
+    // make the user's pool token ATA
+    let pool_token_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &user.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &token_program_id,
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &context.payer.pubkey(),
+                &user.pubkey(),
+                &stake_pool_accounts.pool_mint.pubkey(),
+                &token_program_id,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    (
+        context,
+        stake_pool_accounts,
+        validator_stake_account,
+        user,
+        session_keypair,
+        user_stake_pda,
+        pool_token_ata,
+        stake_lamports,
+    )
+}
+
+fn build_deposit(
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake_account: &ValidatorStakeAccount,
+    session_keypair: &Keypair,
+    user_stake_account: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    minimum_pool_tokens_out: u64,
+    user_stake_seed: u64,
+) -> solana_program::instruction::Instruction {
+    deposit_stake_with_session(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.validator_list.pubkey(),
+        None,
+        &stake_pool_accounts.withdraw_authority,
+        user_stake_account,
+        &validator_stake_account.stake_account,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        pool_tokens_to,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        &session_keypair.pubkey(),
+        minimum_pool_tokens_out,
+        user_stake_seed,
+    )
+}
+
+#[test_case(spl_token::id(); "token")]
+#[test_case(spl_token_2022::id(); "token-2022")]
+#[tokio::test]
+async fn success(token_program_id: Pubkey) {
+    let (
+        mut context,
+        stake_pool_accounts,
+        validator_stake_account,
+        _user,
+        session_keypair,
+        user_stake_pda,
+        pool_token_ata,
+        stake_lamports,
+    ) = setup(token_program_id).await;
+
+    let pre_stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let pre_stake_pool =
+        try_from_slice_unchecked::<spl_stake_pool::state::StakePool>(&pre_stake_pool.data).unwrap();
+    let tokens_issued = pre_stake_pool
+        .calc_pool_tokens_for_deposit(stake_lamports)
+        .unwrap();
+    let tokens_issued_user = tokens_issued
+        - pre_stake_pool
+            .calc_pool_tokens_stake_deposit_fee(tokens_issued)
+            .unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[build_deposit(
+            &stake_pool_accounts,
+            &validator_stake_account,
+            &session_keypair,
+            &user_stake_pda,
+            &pool_token_ata,
+            tokens_issued_user,
+            USER_STAKE_SEED,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &session_keypair],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    // user stake PDA was merged into the validator stake account
+    assert!(context
+        .banks_client
+        .get_account(user_stake_pda)
+        .await
+        .unwrap()
+        .is_none());
+
+    let user_token_balance = get_token_balance(&mut context.banks_client, &pool_token_ata).await;
+    assert_eq!(user_token_balance, tokens_issued_user);
+
+    let post_stake_pool = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let post_stake_pool =
+        try_from_slice_unchecked::<spl_stake_pool::state::StakePool>(&post_stake_pool.data)
+            .unwrap();
+    assert_eq!(
+        post_stake_pool.total_lamports,
+        pre_stake_pool.total_lamports + stake_lamports
+    );
+}
+
+#[test_case(spl_token::id(); "token")]
+#[tokio::test]
+async fn fail_with_wrong_pool_token_account(token_program_id: Pubkey) {
+    let (
+        mut context,
+        stake_pool_accounts,
+        validator_stake_account,
+        _user,
+        session_keypair,
+        user_stake_pda,
+        _pool_token_ata,
+        _stake_lamports,
+    ) = setup(token_program_id).await;
+
+    let other_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &token_program_id,
+        &other_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &Keypair::new(),
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[build_deposit(
+            &stake_pool_accounts,
+            &validator_stake_account,
+            &session_keypair,
+            &user_stake_pda,
+            &other_account.pubkey(),
+            0,
+            USER_STAKE_SEED,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &session_keypair],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[test_case(spl_token::id(); "token")]
+#[tokio::test]
+async fn fail_with_wrong_user_stake_seed(token_program_id: Pubkey) {
+    let (
+        mut context,
+        stake_pool_accounts,
+        validator_stake_account,
+        _user,
+        session_keypair,
+        user_stake_pda,
+        pool_token_ata,
+        _stake_lamports,
+    ) = setup(token_program_id).await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[build_deposit(
+            &stake_pool_accounts,
+            &validator_stake_account,
+            &session_keypair,
+            &user_stake_pda,
+            &pool_token_ata,
+            0,
+            USER_STAKE_SEED + 1,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &session_keypair],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidSeeds)
+    );
+
+    // the stake account was left untouched
+    let account = get_account(&mut context.banks_client, &user_stake_pda).await;
+    let stake_state = bincode::deserialize::<StakeStateV2>(&account.data).unwrap();
+    assert_eq!(stake_state.authorized().unwrap().withdrawer, user_stake_pda);
+}
+
+#[test_case(spl_token::id(); "token")]
+#[tokio::test]
+async fn fail_with_wallet_owned_stake(token_program_id: Pubkey) {
+    let (
+        mut context,
+        stake_pool_accounts,
+        validator_stake_account,
+        user,
+        session_keypair,
+        _user_stake_pda,
+        pool_token_ata,
+        _stake_lamports,
+    ) = setup(token_program_id).await;
+
+    // the session can't sign as the wallet, the stake authority
+    let wallet_stake = Keypair::new();
+    let authorized = stake::state::Authorized {
+        staker: user.pubkey(),
+        withdrawer: user.pubkey(),
+    };
+    create_independent_stake_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &wallet_stake,
+        &authorized,
+        &stake::state::Lockup::default(),
+        TEST_STAKE_AMOUNT,
+    )
+    .await;
+    delegate_stake_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &wallet_stake.pubkey(),
+        &user,
+        &validator_stake_account.vote.pubkey(),
+    )
+    .await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[build_deposit(
+            &stake_pool_accounts,
+            &validator_stake_account,
+            &session_keypair,
+            &wallet_stake.pubkey(),
+            &pool_token_ata,
+            0,
+            USER_STAKE_SEED,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &session_keypair],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidSeeds)
+    );
+
+    let account = get_account(&mut context.banks_client, &wallet_stake.pubkey()).await;
+    let stake_state = bincode::deserialize::<StakeStateV2>(&account.data).unwrap();
+    assert_eq!(stake_state.authorized().unwrap().withdrawer, user.pubkey());
+}
+
+#[test_case(spl_token::id(); "token")]
+#[tokio::test]
+async fn fail_with_slippage(token_program_id: Pubkey) {
+    let (
+        context,
+        stake_pool_accounts,
+        validator_stake_account,
+        _user,
+        session_keypair,
+        user_stake_pda,
+        pool_token_ata,
+        stake_lamports,
+    ) = setup(token_program_id).await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[build_deposit(
+            &stake_pool_accounts,
+            &validator_stake_account,
+            &session_keypair,
+            &user_stake_pda,
+            &pool_token_ata,
+            stake_lamports * 2,
+            USER_STAKE_SEED,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &session_keypair],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::ExceededSlippage as u32)
+        )
+    );
+}
@@ -0,0 +1,424 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked,
+        instruction::{AccountMeta, InstructionError},
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_direct_stake_program_address, id, instruction,
+        state::{CapType, DirectStake},
+        MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+async fn setup() -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    ValidatorStakeAccount,
+    Pubkey,
+) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            100 * LAMPORTS_PER_SOL + MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let validator_stake = simple_add_validator_to_pool(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        None,
+    )
+    .await;
+
+    let user = Keypair::new();
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    (
+        context,
+        stake_pool_accounts,
+        validator_stake,
+        pool_token_account.pubkey(),
+    )
+}
+
+async fn set_direct_stake_share(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    max_direct_stake_bps: Option<u16>,
+) -> Option<TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_cap(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            CapType::DirectStakeShare(max_direct_stake_bps),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn deposit_sol_to_validator(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    vote_account: &Pubkey,
+    pool_token_account: &Pubkey,
+    lamports: u64,
+) -> Option<TransactionError> {
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::deposit_sol_to_validator(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.validator_list.pubkey(),
+            vote_account,
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &context.payer.pubkey(),
+            pool_token_account,
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            &stake_pool_accounts.pool_mint.pubkey(),
+            &stake_pool_accounts.token_program_id,
+            None,
+            lamports,
+            0,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn get_direct_stake(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    vote_account: &Pubkey,
+) -> DirectStake {
+    let (direct_stake_address, _) = find_direct_stake_program_address(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        vote_account,
+    );
+    let account = get_account(&mut context.banks_client, &direct_stake_address).await;
+    assert_eq!(account.owner, id());
+    try_from_slice_unchecked::<DirectStake>(&account.data).unwrap()
+}
+
+fn direct_stake_error(error: StakePoolError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, stake_pool_accounts, validator_stake, pool_token_account) = setup().await;
+    let vote_account = validator_stake.vote.pubkey();
+
+    let error = set_direct_stake_share(&mut context, &stake_pool_accounts, Some(5_000)).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let pre_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    let error = deposit_sol_to_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &pool_token_account,
+        10 * LAMPORTS_PER_SOL,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // the deposit lands in the reserve and mints pool tokens like a SOL deposit
+    let reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(
+        reserve_lamports,
+        pre_reserve_lamports + 10 * LAMPORTS_PER_SOL
+    );
+    assert!(get_token_balance(&mut context.banks_client, &pool_token_account).await > 0);
+
+    let direct_stake = get_direct_stake(&mut context, &stake_pool_accounts, &vote_account).await;
+    assert!(direct_stake.is_valid());
+    assert_eq!(
+        direct_stake.stake_pool,
+        stake_pool_accounts.stake_pool.pubkey()
+    );
+    assert_eq!(direct_stake.vote_account, vote_account);
+    assert_eq!(direct_stake.pending_lamports, 10 * LAMPORTS_PER_SOL);
+    assert_eq!(direct_stake.deposited_lamports, 10 * LAMPORTS_PER_SOL);
+
+    let error = deposit_sol_to_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &pool_token_account,
+        5 * LAMPORTS_PER_SOL,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    let direct_stake = get_direct_stake(&mut context, &stake_pool_accounts, &vote_account).await;
+    assert_eq!(direct_stake.pending_lamports, 15 * LAMPORTS_PER_SOL);
+    assert_eq!(direct_stake.deposited_lamports, 15 * LAMPORTS_PER_SOL);
+
+    // delegating to the validator counts towards the pending direct stake
+    let (direct_stake_address, _) = find_direct_stake_program_address(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &vote_account,
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::with_direct_stake(
+            instruction::increase_validator_stake(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.staker.pubkey(),
+                &stake_pool_accounts.withdraw_authority,
+                &stake_pool_accounts.validator_list.pubkey(),
+                &stake_pool_accounts.reserve_stake.pubkey(),
+                &validator_stake.transient_stake_account,
+                &validator_stake.stake_account,
+                &vote_account,
+                4 * LAMPORTS_PER_SOL,
+                validator_stake.transient_stake_seed,
+            ),
+            &direct_stake_address,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.staker],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let direct_stake = get_direct_stake(&mut context, &stake_pool_accounts, &vote_account).await;
+    assert_eq!(direct_stake.pending_lamports, 11 * LAMPORTS_PER_SOL);
+    assert_eq!(direct_stake.deposited_lamports, 15 * LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn fail_not_accepted_by_default() {
+    let (mut context, stake_pool_accounts, validator_stake, pool_token_account) = setup().await;
+
+    let error = deposit_sol_to_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake.vote.pubkey(),
+        &pool_token_account,
+        LAMPORTS_PER_SOL,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        direct_stake_error(StakePoolError::DirectStakeShareExceeded)
+    );
+}
+
+#[tokio::test]
+async fn fail_above_share() {
+    let (mut context, stake_pool_accounts, validator_stake, pool_token_account) = setup().await;
+    let vote_account = validator_stake.vote.pubkey();
+
+    // 10% of a pool of about 100 SOL
+    let error = set_direct_stake_share(&mut context, &stake_pool_accounts, Some(1_000)).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = deposit_sol_to_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &pool_token_account,
+        20 * LAMPORTS_PER_SOL,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        direct_stake_error(StakePoolError::DirectStakeShareExceeded)
+    );
+
+    let error = deposit_sol_to_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &vote_account,
+        &pool_token_account,
+        5 * LAMPORTS_PER_SOL,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn fail_validator_not_in_pool() {
+    let (mut context, stake_pool_accounts, _validator_stake, pool_token_account) = setup().await;
+
+    let error = set_direct_stake_share(&mut context, &stake_pool_accounts, Some(5_000)).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = deposit_sol_to_validator(
+        &mut context,
+        &stake_pool_accounts,
+        &Pubkey::new_unique(),
+        &pool_token_account,
+        LAMPORTS_PER_SOL,
+    )
+    .await
+    .unwrap();
+    assert_eq!(error, direct_stake_error(StakePoolError::ValidatorNotFound));
+}
+
+#[tokio::test]
+async fn fail_wrong_system_program() {
+    let (mut context, stake_pool_accounts, validator_stake, pool_token_account) = setup().await;
+
+    let error = set_direct_stake_share(&mut context, &stake_pool_accounts, Some(5_000)).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let mut instruction = instruction::deposit_sol_to_validator(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.validator_list.pubkey(),
+        &validator_stake.vote.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &context.payer.pubkey(),
+        &pool_token_account,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        None,
+        LAMPORTS_PER_SOL,
+        0,
+    );
+    // the system program would fund the new direct stake account
+    instruction.accounts[11] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::IncorrectProgramId)
+    );
+}
+
+#[tokio::test]
+async fn fail_increase_with_wrong_direct_stake() {
+    let (context, stake_pool_accounts, validator_stake, _pool_token_account) = setup().await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::with_direct_stake(
+            instruction::increase_validator_stake(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.staker.pubkey(),
+                &stake_pool_accounts.withdraw_authority,
+                &stake_pool_accounts.validator_list.pubkey(),
+                &stake_pool_accounts.reserve_stake.pubkey(),
+                &validator_stake.transient_stake_account,
+                &validator_stake.stake_account,
+                &validator_stake.vote.pubkey(),
+                LAMPORTS_PER_SOL,
+                validator_stake.transient_stake_seed,
+            ),
+            &stake_pool_accounts.stake_pool.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.staker],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        direct_stake_error(StakePoolError::InvalidDirectStake)
+    );
+}
+
+#[tokio::test]
+async fn fail_share_above_whole() {
+    let (mut context, stake_pool_accounts, _validator_stake, _pool_token_account) = setup().await;
+
+    let error = set_direct_stake_share(&mut context, &stake_pool_accounts, Some(10_001))
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
@@ -0,0 +1,542 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked,
+        instruction::{Instruction, InstructionError},
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::AccountSharedData,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    solana_system_interface::program as system_program,
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_fee_split_program_address, id, instruction,
+        state::{AccountType, FeeSplit, StakePool},
+        MAX_BASIS_POINTS, MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+const WEIGHTS_BPS: [u16; 2] = [5_000, 2_500];
+
+struct FeeSplitAccounts {
+    recipients: Vec<Pubkey>,
+    referral_fee_account: Pubkey,
+    user: Keypair,
+    user_pool_account: Pubkey,
+}
+
+async fn create_pool_token_account(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    owner: &Keypair,
+) -> Pubkey {
+    let token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        owner,
+        &[],
+    )
+    .await
+    .unwrap();
+    token_account.pubkey()
+}
+
+async fn set_fee_split(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    manager: &Keypair,
+    recipients: &[(Pubkey, u16)],
+) -> Option<TransactionError> {
+    let instruction = instruction::set_fee_split(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &manager.pubkey(),
+        &context.payer.pubkey(),
+        recipients,
+    );
+    process_instruction(context, instruction, &[manager]).await
+}
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts, FeeSplitAccounts) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let mut recipients = vec![];
+    for _ in WEIGHTS_BPS {
+        recipients.push(
+            create_pool_token_account(&mut context, &stake_pool_accounts, &Keypair::new()).await,
+        );
+    }
+    let referral_fee_account =
+        create_pool_token_account(&mut context, &stake_pool_accounts, &Keypair::new()).await;
+    let user = Keypair::new();
+    let user_pool_account =
+        create_pool_token_account(&mut context, &stake_pool_accounts, &user).await;
+
+    let weighted_recipients: Vec<(Pubkey, u16)> =
+        recipients.iter().copied().zip(WEIGHTS_BPS).collect();
+    let error = set_fee_split(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        &weighted_recipients,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (
+        context,
+        stake_pool_accounts,
+        FeeSplitAccounts {
+            recipients,
+            referral_fee_account,
+            user,
+            user_pool_account,
+        },
+    )
+}
+
+fn deposit_sol_instruction(
+    context: &ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    fee_split_accounts: &FeeSplitAccounts,
+    amount: u64,
+) -> Instruction {
+    instruction::deposit_sol(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &context.payer.pubkey(),
+        &fee_split_accounts.user_pool_account,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &fee_split_accounts.referral_fee_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        amount,
+    )
+}
+
+async fn get_fee_split(context: &mut ProgramTestContext, stake_pool: &Pubkey) -> FeeSplit {
+    let (address, _) = find_fee_split_program_address(&id(), stake_pool);
+    let account = get_account(&mut context.banks_client, &address).await;
+    try_from_slice_unchecked::<FeeSplit>(account.data.as_slice()).unwrap()
+}
+
+async fn get_stake_pool(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> StakePool {
+    let account = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    try_from_slice_unchecked::<StakePool>(account.data.as_slice()).unwrap()
+}
+
+async fn get_token_balances(context: &mut ProgramTestContext, accounts: &[Pubkey]) -> Vec<u64> {
+    let mut balances = vec![];
+    for account in accounts {
+        balances.push(get_token_balance(&mut context.banks_client, account).await);
+    }
+    balances
+}
+
+#[tokio::test]
+async fn success_set_fee_split() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    let fee_split = get_fee_split(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(fee_split.account_type, AccountType::FeeSplit);
+    assert_eq!(
+        fee_split.stake_pool,
+        stake_pool_accounts.stake_pool.pubkey()
+    );
+    assert_eq!(fee_split.recipients().len(), WEIGHTS_BPS.len());
+    for ((recipient, token_account), weight_bps) in fee_split
+        .recipients()
+        .iter()
+        .zip(&fee_split_accounts.recipients)
+        .zip(WEIGHTS_BPS)
+    {
+        assert_eq!(recipient.token_account, *token_account);
+        assert_eq!(recipient.weight_bps, weight_bps);
+    }
+    assert!(
+        get_stake_pool(&mut context, &stake_pool_accounts)
+            .await
+            .has_fee_split
+    );
+
+    // replace the recipients, reusing the existing account
+    let error = set_fee_split(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        &[(fee_split_accounts.recipients[1], MAX_BASIS_POINTS)],
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    let fee_split = get_fee_split(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(fee_split.recipients().len(), 1);
+    assert_eq!(
+        fee_split.recipients()[0].token_account,
+        fee_split_accounts.recipients[1]
+    );
+    assert_eq!(fee_split.recipients()[0].weight_bps, MAX_BASIS_POINTS);
+}
+
+#[tokio::test]
+async fn fail_set_fee_split_wrong_manager() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    let error = set_fee_split(
+        &mut context,
+        &stake_pool_accounts,
+        &Keypair::new(),
+        &[(fee_split_accounts.recipients[0], 1_000)],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_set_fee_split_invalid_weights() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    for weights_bps in [[MAX_BASIS_POINTS, 1], [1_000, 0]] {
+        let recipients: Vec<(Pubkey, u16)> = fee_split_accounts
+            .recipients
+            .iter()
+            .copied()
+            .zip(weights_bps)
+            .collect();
+        let error = set_fee_split(
+            &mut context,
+            &stake_pool_accounts,
+            &stake_pool_accounts.manager,
+            &recipients,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(StakePoolError::InvalidFeeSplit as u32)
+            )
+        );
+    }
+}
+
+#[tokio::test]
+async fn success_deposit_sol_split() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    let instruction = instruction::with_fee_split(
+        deposit_sol_instruction(
+            &context,
+            &stake_pool_accounts,
+            &fee_split_accounts,
+            TEST_STAKE_AMOUNT,
+        ),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &fee_split_accounts.recipients,
+    );
+    let error = process_instruction(&mut context, instruction, &[]).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // the first deposit mints pool tokens one for one
+    let deposit_fee = stake_pool_accounts.calculate_sol_deposit_fee(TEST_STAKE_AMOUNT);
+    let referral_fee = stake_pool_accounts.calculate_sol_referral_fee(deposit_fee);
+    let manager_deposit_fee = deposit_fee - referral_fee;
+    let shares: Vec<u64> = WEIGHTS_BPS
+        .iter()
+        .map(|weight_bps| manager_deposit_fee * *weight_bps as u64 / MAX_BASIS_POINTS as u64)
+        .collect();
+    assert!(shares.iter().all(|share| *share > 0));
+
+    assert_eq!(
+        get_token_balances(&mut context, &fee_split_accounts.recipients).await,
+        shares
+    );
+    assert_eq!(
+        get_token_balance(
+            &mut context.banks_client,
+            &stake_pool_accounts.pool_fee_account.pubkey()
+        )
+        .await,
+        manager_deposit_fee - shares.iter().sum::<u64>()
+    );
+    assert_eq!(
+        get_token_balance(
+            &mut context.banks_client,
+            &fee_split_accounts.referral_fee_account
+        )
+        .await,
+        referral_fee
+    );
+}
+
+#[tokio::test]
+async fn fail_deposit_sol_without_fee_split_accounts() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    // leaving out the fee split must not hand the whole fee to the manager
+    let instruction = deposit_sol_instruction(
+        &context,
+        &stake_pool_accounts,
+        &fee_split_accounts,
+        TEST_STAKE_AMOUNT,
+    );
+    let error = process_instruction(&mut context, instruction, &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidFeeSplit as u32)
+        )
+    );
+    assert_eq!(
+        get_token_balance(
+            &mut context.banks_client,
+            &stake_pool_accounts.pool_fee_account.pubkey()
+        )
+        .await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn fail_deposit_sol_wrong_fee_split_accounts() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    // the recipients must be passed in order
+    let mut recipients = fee_split_accounts.recipients.clone();
+    recipients.reverse();
+    let instruction = instruction::with_fee_split(
+        deposit_sol_instruction(
+            &context,
+            &stake_pool_accounts,
+            &fee_split_accounts,
+            TEST_STAKE_AMOUNT,
+        ),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &recipients,
+    );
+    let error = process_instruction(&mut context, instruction, &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidFeeSplit as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_deposit_sol_invalid_recipient() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    // a recipient whose token account was closed cannot take its share
+    context.set_account(
+        &fee_split_accounts.recipients[0],
+        &AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program::id()),
+    );
+    let instruction = instruction::with_fee_split(
+        deposit_sol_instruction(
+            &context,
+            &stake_pool_accounts,
+            &fee_split_accounts,
+            TEST_STAKE_AMOUNT,
+        ),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &fee_split_accounts.recipients,
+    );
+    let error = process_instruction(&mut context, instruction, &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[tokio::test]
+async fn success_withdraw_sol_split() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    let instruction = instruction::with_fee_split(
+        deposit_sol_instruction(
+            &context,
+            &stake_pool_accounts,
+            &fee_split_accounts,
+            TEST_STAKE_AMOUNT,
+        ),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &fee_split_accounts.recipients,
+    );
+    let error = process_instruction(&mut context, instruction, &[]).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let recipient_balances_before =
+        get_token_balances(&mut context, &fee_split_accounts.recipients).await;
+    let manager_balance_before = get_token_balance(
+        &mut context.banks_client,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+    )
+    .await;
+    let pool_tokens = get_token_balance(
+        &mut context.banks_client,
+        &fee_split_accounts.user_pool_account,
+    )
+    .await
+        / 2;
+
+    let instruction = instruction::with_fee_split(
+        instruction::withdraw_sol(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &fee_split_accounts.user.pubkey(),
+            &fee_split_accounts.user_pool_account,
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &fee_split_accounts.user.pubkey(),
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            &stake_pool_accounts.pool_mint.pubkey(),
+            &stake_pool_accounts.token_program_id,
+            pool_tokens,
+        ),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &fee_split_accounts.recipients,
+    );
+    let error = process_instruction(&mut context, instruction, &[&fee_split_accounts.user]).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let withdrawal_fee = stake_pool_accounts.calculate_withdrawal_fee(pool_tokens);
+    let shares: Vec<u64> = WEIGHTS_BPS
+        .iter()
+        .map(|weight_bps| withdrawal_fee * *weight_bps as u64 / MAX_BASIS_POINTS as u64)
+        .collect();
+    let recipient_balances_after =
+        get_token_balances(&mut context, &fee_split_accounts.recipients).await;
+    for ((before, after), share) in recipient_balances_before
+        .iter()
+        .zip(recipient_balances_after)
+        .zip(&shares)
+    {
+        assert_eq!(after - before, *share);
+    }
+    assert_eq!(
+        get_token_balance(
+            &mut context.banks_client,
+            &stake_pool_accounts.pool_fee_account.pubkey()
+        )
+        .await
+            - manager_balance_before,
+        withdrawal_fee - shares.iter().sum::<u64>()
+    );
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_with_fee_split() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    let update_instruction = instruction::update_stake_pool_balance(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+    );
+    let error = process_instruction(&mut context, update_instruction.clone(), &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidFeeSplit as u32)
+        )
+    );
+
+    let instruction = instruction::with_fee_split(
+        update_instruction,
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &fee_split_accounts.recipients,
+    );
+    let error = process_instruction(&mut context, instruction, &[]).await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn success_unset_fee_split() {
+    let (mut context, stake_pool_accounts, fee_split_accounts) = setup().await;
+
+    let error = set_fee_split(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        &[],
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    assert!(
+        !get_stake_pool(&mut context, &stake_pool_accounts)
+            .await
+            .has_fee_split
+    );
+
+    // fees go back to the manager fee account alone
+    let instruction = deposit_sol_instruction(
+        &context,
+        &stake_pool_accounts,
+        &fee_split_accounts,
+        TEST_STAKE_AMOUNT,
+    );
+    let error = process_instruction(&mut context, instruction, &[]).await;
+    assert!(error.is_none(), "{:?}", error);
+    assert_eq!(
+        get_token_balances(&mut context, &fee_split_accounts.recipients).await,
+        vec![0; WEIGHTS_BPS.len()]
+    );
+}
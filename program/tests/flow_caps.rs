@@ -0,0 +1,425 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{instruction::InstructionError, pubkey::Pubkey},
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{
+        error::StakePoolError, id, instruction, state::CapType, MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts, Keypair, Pubkey) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    (
+        context,
+        stake_pool_accounts,
+        user,
+        pool_token_account.pubkey(),
+    )
+}
+
+async fn set_cap(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    manager: &Keypair,
+    cap: CapType,
+) -> Option<TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_cap(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &manager.pubkey(),
+            cap,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+#[tokio::test]
+async fn success_set_cap() {
+    let (mut context, stake_pool_accounts, _user, _pool_token_account) = setup().await;
+    let manager = stake_pool_accounts.manager.insecure_clone();
+
+    for cap in [
+        CapType::DepositPerEpoch(Some(1)),
+        CapType::SolWithdrawalPerEpoch(Some(2)),
+        CapType::TotalLamports(Some(3)),
+    ] {
+        let error = set_cap(&mut context, &stake_pool_accounts, &manager, cap).await;
+        assert!(error.is_none(), "{:?}", error);
+    }
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.max_deposit_lamports_per_epoch, Some(1));
+    assert_eq!(stake_pool.max_sol_withdrawal_lamports_per_epoch, Some(2));
+    assert_eq!(stake_pool.max_total_lamports, Some(3));
+
+    let error = set_cap(
+        &mut context,
+        &stake_pool_accounts,
+        &manager,
+        CapType::TotalLamports(None),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.max_total_lamports, None);
+}
+
+#[tokio::test]
+async fn fail_set_cap_wrong_manager() {
+    let (mut context, stake_pool_accounts, _user, _pool_token_account) = setup().await;
+
+    let error = set_cap(
+        &mut context,
+        &stake_pool_accounts,
+        &Keypair::new(),
+        CapType::DepositPerEpoch(Some(1)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_deposit_sol_over_epoch_cap() {
+    let (mut context, stake_pool_accounts, _user, pool_token_account) = setup().await;
+    let manager = stake_pool_accounts.manager.insecure_clone();
+
+    let error = set_cap(
+        &mut context,
+        &stake_pool_accounts,
+        &manager,
+        CapType::DepositPerEpoch(Some(TEST_STAKE_AMOUNT)),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT / 2,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT / 2 + 1,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::DepositCapExceeded as u32)
+        )
+    );
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.epoch_deposited_lamports, TEST_STAKE_AMOUNT / 2);
+
+    // the counter resets on the next epoch
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn fail_deposit_sol_over_total_lamports_cap() {
+    let (mut context, stake_pool_accounts, _user, pool_token_account) = setup().await;
+    let manager = stake_pool_accounts.manager.insecure_clone();
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let error = set_cap(
+        &mut context,
+        &stake_pool_accounts,
+        &manager,
+        CapType::TotalLamports(Some(stake_pool.total_lamports + TEST_STAKE_AMOUNT)),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT + 1,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::TotalLamportsCapExceeded as u32)
+        )
+    );
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn fail_withdraw_sol_over_epoch_cap() {
+    let (mut context, stake_pool_accounts, user, pool_token_account) = setup().await;
+    let manager = stake_pool_accounts.manager.insecure_clone();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let pool_tokens = get_token_balance(&mut context.banks_client, &pool_token_account).await;
+
+    let error = set_cap(
+        &mut context,
+        &stake_pool_accounts,
+        &manager,
+        CapType::SolWithdrawalPerEpoch(Some(TEST_STAKE_AMOUNT / 4)),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .withdraw_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 2,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SolWithdrawalCapExceeded as u32)
+        )
+    );
+
+    let error = stake_pool_accounts
+        .withdraw_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 8,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert!(stake_pool.epoch_sol_withdrawn_lamports > 0);
+    assert!(stake_pool.epoch_sol_withdrawn_lamports <= TEST_STAKE_AMOUNT / 4);
+}
+
+#[tokio::test]
+async fn fail_withdrawal_ticket_over_epoch_cap() {
+    let (mut context, stake_pool_accounts, user, pool_token_account) = setup().await;
+    let manager = stake_pool_accounts.manager.insecure_clone();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let pool_tokens = get_token_balance(&mut context.banks_client, &pool_token_account).await;
+
+    let error = set_cap(
+        &mut context,
+        &stake_pool_accounts,
+        &manager,
+        CapType::SolWithdrawalPerEpoch(Some(TEST_STAKE_AMOUNT / 4)),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 2,
+            0,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SolWithdrawalCapExceeded as u32)
+        )
+    );
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 8,
+            0,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool.epoch_sol_withdrawn_lamports,
+        stake_pool.pending_withdrawal_lamports
+    );
+
+    // the ticket used up part of the cap left to SOL withdrawals
+    let error = stake_pool_accounts
+        .withdraw_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 6,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SolWithdrawalCapExceeded as u32)
+        )
+    );
+}
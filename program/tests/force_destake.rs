@@ -65,7 +65,7 @@ async fn setup(
         transient_stake_lamports: 0.into(),
         last_update_epoch: 0.into(),
         transient_seed_suffix: 0.into(),
-        unused: 0.into(),
+        max_share_bps: 0.into(),
         validator_seed_suffix: raw_validator_seed.into(),
     });
 
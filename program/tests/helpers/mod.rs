@@ -3,13 +3,17 @@
 pub mod wsol;
 
 use {
+    base64::{engine::general_purpose::STANDARD, Engine},
     borsh::BorshDeserialize,
     solana_program::{
+        account_info::AccountInfo,
         borsh1::{get_instance_packed_len, get_packed_len, try_from_slice_unchecked},
+        entrypoint::ProgramResult,
         hash::Hash,
         instruction::Instruction,
         program_option::COption,
         program_pack::Pack,
+        program_stubs,
         pubkey::Pubkey,
     },
     solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestContext},
@@ -17,8 +21,9 @@ use {
         account::{Account as SolanaAccount, WritableAccount},
         clock::{Clock, Epoch},
         compute_budget::ComputeBudgetInstruction,
+        native_token::LAMPORTS_PER_SOL,
         signature::{Keypair, Signer},
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
         transport::TransportError,
     },
     solana_stake_interface as stake,
@@ -28,21 +33,28 @@ use {
         vote_state::{VoteInit, VoteState, VoteStateVersions},
     },
     spl_stake_pool::{
-        find_deposit_authority_program_address, find_ephemeral_stake_program_address,
+        event, find_deposit_authority_program_address, find_ephemeral_stake_program_address,
         find_stake_program_address, find_transient_stake_program_address,
-        find_withdraw_authority_program_address, id,
+        find_withdraw_authority_program_address, find_withdrawal_ticket_program_address, id,
         inline_mpl_token_metadata::{self, pda::find_metadata_account},
         instruction, minimum_delegation,
         processor::Processor,
-        state::{self, FeeType, FutureEpoch, StakePool, ValidatorList},
-        MAX_VALIDATORS_TO_UPDATE, MINIMUM_RESERVE_LAMPORTS,
+        state::{
+            self, FeeType, FutureEpochFee, FutureEpochReferralFee,
+            FutureEpochSolWithdrawalFeeCurve, SolWithdrawalFeeCurve, StakePool, ValidatorList,
+        },
+        MAX_VALIDATORS_TO_UPDATE, MINIMUM_RESERVE_LAMPORTS, STAKE_POOL_LEN,
     },
     spl_token_2022::{
         extension::{ExtensionType, StateWithExtensionsOwned},
         native_mint,
         state::{Account, Mint},
     },
-    std::{convert::TryInto, num::NonZeroU32},
+    std::{
+        convert::TryInto,
+        num::NonZeroU32,
+        sync::{Once, OnceLock},
+    },
 };
 
 pub const FIRST_NORMAL_EPOCH: u64 = 15;
@@ -51,6 +63,7 @@ pub const MAX_TEST_VALIDATORS: u32 = 10_000;
 pub const DEFAULT_VALIDATOR_STAKE_SEED: Option<NonZeroU32> = NonZeroU32::new(1_010);
 pub const DEFAULT_TRANSIENT_STAKE_SEED: u64 = 42;
 pub const STAKE_ACCOUNT_RENT_EXEMPTION: u64 = 2_282_880;
+pub const REWARD_LAMPORTS: u64 = 10 * LAMPORTS_PER_SOL;
 const ACCOUNT_RENT_EXEMPTION: u64 = 1_000_000_000; // go with something big to be safe
 
 pub fn program_test() -> ProgramTest {
@@ -85,6 +98,157 @@ pub async fn get_account(banks_client: &mut BanksClient, pubkey: &Pubkey) -> Sol
         .expect("account not found")
 }
 
+/// Prefix of the log lines carrying `sol_log_data` fields, as the runtime
+/// logs them
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Syscall stubs of program-test, except that `sol_log_data` reaches the
+/// transaction logs. program-test only prints it when running a program
+/// natively, so it is logged through `sol_log` instead, which shows up as
+/// `Program log: Program data: <base64>`.
+struct LogDataSyscallStubs;
+
+/// Stubs of program-test, wrapped by `LogDataSyscallStubs`
+static PROGRAM_TEST_SYSCALL_STUBS: OnceLock<Box<dyn program_stubs::SyscallStubs>> = OnceLock::new();
+
+impl LogDataSyscallStubs {
+    /// Stubs of program-test, waiting for them if another test got here
+    /// while they were being swapped
+    fn inner(&self) -> &dyn program_stubs::SyscallStubs {
+        loop {
+            if let Some(stubs) = PROGRAM_TEST_SYSCALL_STUBS.get() {
+                return stubs.as_ref();
+            }
+            std::thread::yield_now();
+        }
+    }
+}
+
+impl program_stubs::SyscallStubs for LogDataSyscallStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner().sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.inner().sol_log_compute_units()
+    }
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.inner().sol_remaining_compute_units()
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.inner()
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_sysvar(
+        &self,
+        sysvar_id_addr: *const u8,
+        var_addr: *mut u8,
+        offset: u64,
+        length: u64,
+    ) -> u64 {
+        self.inner()
+            .sol_get_sysvar(sysvar_id_addr, var_addr, offset, length)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_last_restart_slot(var_addr)
+    }
+    fn sol_get_epoch_stake(&self, vote_address: *const u8) -> u64 {
+        self.inner().sol_get_epoch_stake(vote_address)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner().sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner().sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields = fields
+            .iter()
+            .map(|field| STANDARD.encode(field))
+            .collect::<Vec<_>>();
+        self.inner()
+            .sol_log(&format!("{}{}", PROGRAM_DATA_PREFIX, fields.join(" ")))
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner().sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner().sol_get_stack_height()
+    }
+}
+
+/// Processes `transaction`, returning the events the stake pool program
+/// logged while doing so
+pub async fn process_transaction_with_events(
+    banks_client: &mut BanksClient,
+    transaction: Transaction,
+) -> Result<Vec<event::StakePoolEvent>, TransportError> {
+    // program-test installs its own stubs when the first test starts
+    static LOG_DATA_SYSCALL_STUBS: Once = Once::new();
+    LOG_DATA_SYSCALL_STUBS.call_once(|| {
+        let stubs = program_stubs::set_syscall_stubs(Box::new(LogDataSyscallStubs));
+        let _ = PROGRAM_TEST_SYSCALL_STUBS.set(stubs);
+    });
+
+    let result = banks_client
+        .process_transaction_with_metadata(transaction)
+        .await?;
+    result.result?;
+    let logs = result
+        .metadata
+        .map(|metadata| metadata.log_messages)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|log| match log.strip_prefix("Program log: ") {
+            Some(data) if data.starts_with(PROGRAM_DATA_PREFIX) => data.to_string(),
+            _ => log,
+        })
+        .collect::<Vec<_>>();
+    Ok(event::parse_events(&id(), &logs))
+}
+
+/// Processes `instruction`, signed by the payer and `signers`, returning the
+/// error if it failed
+pub async fn process_instruction(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Option<TransactionError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn create_mint(
     banks_client: &mut BanksClient,
@@ -139,6 +303,12 @@ pub async fn create_mint(
             ),
             ExtensionType::NonTransferable =>
                 spl_token_2022::instruction::initialize_non_transferable_mint(program_id, &mint_pubkey),
+            ExtensionType::MetadataPointer => spl_token_2022::extension::metadata_pointer::instruction::initialize(
+                program_id,
+                &mint_pubkey,
+                Some(*manager),
+                Some(mint_pubkey),
+            ),
             _ => unimplemented!(),
         };
         instructions.push(instruction.unwrap());
@@ -1400,6 +1570,82 @@ impl StakePoolAccounts {
             .err()
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn request_withdrawal_ticket(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        user: &Keypair,
+        pool_account: &Pubkey,
+        amount: u64,
+        ticket_seed: u64,
+    ) -> Option<TransportError> {
+        let withdrawal_ticket = self.find_withdrawal_ticket(&user.pubkey(), ticket_seed);
+        let mut instructions = vec![instruction::request_withdrawal_ticket(
+            &id(),
+            &self.stake_pool.pubkey(),
+            &user.pubkey(),
+            pool_account,
+            &self.pool_fee_account.pubkey(),
+            &self.pool_mint.pubkey(),
+            &withdrawal_ticket,
+            &payer.pubkey(),
+            &self.token_program_id,
+            amount,
+            ticket_seed,
+        )];
+        self.maybe_add_compute_budget_instruction(&mut instructions);
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, user],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
+    pub async fn claim_withdrawal_ticket(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        owner: &Keypair,
+        withdrawal_ticket: &Pubkey,
+        lamports_to: &Pubkey,
+    ) -> Option<TransportError> {
+        let mut instructions = vec![instruction::claim_withdrawal_ticket(
+            &id(),
+            &self.stake_pool.pubkey(),
+            &self.withdraw_authority,
+            &self.reserve_stake.pubkey(),
+            withdrawal_ticket,
+            &owner.pubkey(),
+            lamports_to,
+        )];
+        self.maybe_add_compute_budget_instruction(&mut instructions);
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, owner],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
+    pub fn find_withdrawal_ticket(&self, owner: &Pubkey, ticket_seed: u64) -> Pubkey {
+        find_withdrawal_ticket_program_address(&id(), &self.stake_pool.pubkey(), owner, ticket_seed)
+            .0
+    }
+
     pub async fn get_stake_pool(&self, banks_client: &mut BanksClient) -> StakePool {
         let stake_pool_account = get_account(banks_client, &self.stake_pool.pubkey()).await;
         try_from_slice_unchecked::<StakePool>(stake_pool_account.data.as_slice()).unwrap()
@@ -1942,6 +2188,54 @@ impl StakePoolAccounts {
         }
     }
 
+    pub async fn redelegate(
+        &self,
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: &Hash,
+        source: &ValidatorStakeAccount,
+        destination: &ValidatorStakeAccount,
+        lamports: u64,
+    ) -> Option<TransportError> {
+        let ephemeral_stake_seed = 0;
+        let ephemeral_stake = find_ephemeral_stake_program_address(
+            &id(),
+            &self.stake_pool.pubkey(),
+            ephemeral_stake_seed,
+        )
+        .0;
+        let mut instructions = vec![instruction::redelegate(
+            &id(),
+            &self.stake_pool.pubkey(),
+            &self.staker.pubkey(),
+            &self.withdraw_authority,
+            &self.validator_list.pubkey(),
+            &self.reserve_stake.pubkey(),
+            &source.stake_account,
+            &source.transient_stake_account,
+            &ephemeral_stake,
+            &destination.transient_stake_account,
+            &destination.stake_account,
+            &destination.vote.pubkey(),
+            lamports,
+            source.transient_stake_seed,
+            ephemeral_stake_seed,
+            destination.transient_stake_seed,
+        )];
+        self.maybe_add_compute_budget_instruction(&mut instructions);
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, &self.staker],
+            *recent_blockhash,
+        );
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.into())
+            .err()
+    }
+
     pub async fn set_preferred_validator(
         &self,
         banks_client: &mut BanksClient,
@@ -1991,21 +2285,52 @@ impl StakePoolAccounts {
             last_update_epoch: 0,
             lockup: stake::state::Lockup::default(),
             epoch_fee: self.epoch_fee,
-            next_epoch_fee: FutureEpoch::None,
+            next_epoch_fee: FutureEpochFee::None,
             preferred_deposit_validator_vote_address: None,
             preferred_withdraw_validator_vote_address: None,
             stake_deposit_fee: state::Fee::default(),
             sol_deposit_fee: state::Fee::default(),
             stake_withdrawal_fee: state::Fee::default(),
-            next_stake_withdrawal_fee: FutureEpoch::None,
+            next_stake_withdrawal_fee: FutureEpochFee::None,
             stake_referral_fee: 0,
             sol_referral_fee: 0,
             sol_deposit_authority: None,
             sol_withdraw_authority: None,
             sol_withdrawal_fee: state::Fee::default(),
-            next_sol_withdrawal_fee: FutureEpoch::None,
+            next_sol_withdrawal_fee: FutureEpochFee::None,
             last_epoch_pool_token_supply: 0,
             last_epoch_total_lamports: 0,
+            pending_withdrawal_lamports: 0,
+            withdrawal_claim_pool_lamports: 0,
+            max_deposit_lamports_per_epoch: None,
+            max_sol_withdrawal_lamports_per_epoch: None,
+            max_total_lamports: None,
+            flow_epoch: 0,
+            epoch_deposited_lamports: 0,
+            epoch_sol_withdrawn_lamports: 0,
+            pauser: None,
+            paused_operations: 0,
+            pending_manager: None,
+            pending_staker: None,
+            next_stake_deposit_fee: FutureEpochFee::None,
+            next_sol_deposit_fee: FutureEpochFee::None,
+            next_stake_referral_fee: FutureEpochReferralFee::None,
+            next_sol_referral_fee: FutureEpochReferralFee::None,
+            fee_change_delay_epochs: 0,
+            next_fee_change_delay_epochs: None,
+            fee_change_epoch: 0,
+            max_direct_stake_bps: None,
+            max_validator_share_bps: None,
+            max_validator_commission: None,
+            max_validator_vote_age_epochs: None,
+            sol_withdrawal_fee_curve: SolWithdrawalFeeCurve::default(),
+            next_sol_withdrawal_fee_curve: FutureEpochSolWithdrawalFeeCurve::None,
+            has_fee_split: false,
+            insurance_fee_bps: 0,
+            uncovered_loss_lamports: 0,
+            has_rate_history: false,
+            has_oracle: false,
+            pending_insurance_lamports: 0,
         };
         let mut validator_list = ValidatorList::new(self.max_validators);
         validator_list.validators = vec![];
@@ -2451,7 +2776,7 @@ pub fn add_validator_stake_account(
         transient_stake_lamports: 0.into(),
         last_update_epoch: FIRST_NORMAL_EPOCH.into(),
         transient_seed_suffix: 0.into(),
-        unused: 0.into(),
+        max_share_bps: 0.into(),
         validator_seed_suffix: raw_suffix.into(),
     });
 
@@ -2493,8 +2818,7 @@ pub fn add_stake_pool_account(
 ) {
     let mut stake_pool_bytes = borsh::to_vec(&stake_pool).unwrap();
     // more room for optionals
-    stake_pool_bytes.extend_from_slice(Pubkey::default().as_ref());
-    stake_pool_bytes.extend_from_slice(Pubkey::default().as_ref());
+    stake_pool_bytes.resize(STAKE_POOL_LEN, 0);
     let stake_pool_account = SolanaAccount::create(
         ACCOUNT_RENT_EXEMPTION,
         stake_pool_bytes,
@@ -2582,6 +2906,99 @@ pub fn add_token_account(
     program_test.add_account(*account_key, fee_account);
 }
 
+pub fn update_stake_pool_balance_instruction(
+    stake_pool_accounts: &StakePoolAccounts,
+) -> Instruction {
+    instruction::update_stake_pool_balance(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+    )
+}
+
+/// Initializes a stake pool and deposits `TEST_STAKE_AMOUNT` of SOL into it
+pub async fn setup_with_sol_deposit() -> (ProgramTestContext, StakePoolAccounts) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_pool_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &user_pool_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user_pool_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (context, stake_pool_accounts)
+}
+
+/// Adds `REWARD_LAMPORTS` to the reserve and collects them with an
+/// `UpdateStakePoolBalance` extended by `with_accounts`, returning the fee
+/// minted
+pub async fn collect_rewards(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    with_accounts: fn(Instruction, &Pubkey) -> Instruction,
+) -> u64 {
+    transfer(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        REWARD_LAMPORTS,
+    )
+    .await;
+    let fee_before = get_token_balance(
+        &mut context.banks_client,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+    )
+    .await;
+    let instruction = with_accounts(
+        update_stake_pool_balance_instruction(stake_pool_accounts),
+        &stake_pool_accounts.stake_pool.pubkey(),
+    );
+    let error = process_instruction(context, instruction, &[]).await;
+    assert!(error.is_none(), "{:?}", error);
+    get_token_balance(
+        &mut context.banks_client,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+    )
+    .await
+        - fee_before
+}
+
 pub async fn setup_for_withdraw(
     token_program_id: Pubkey,
     reserve_lamports: u64,
@@ -0,0 +1,514 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        native_token::LAMPORTS_PER_SOL,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_insurance_fund_program_address, id, instruction,
+        state::{AccountType, InsuranceFund, StakePool, StakeStatus},
+        MAX_BASIS_POINTS,
+    },
+};
+
+const INSURANCE_FEE_BPS: u16 = 2_500;
+
+async fn set_insurance_fee(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    manager: &Keypair,
+    insurance_fee_bps: u16,
+) -> Option<TransactionError> {
+    let instruction = instruction::set_insurance_fee(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &manager.pubkey(),
+        &context.payer.pubkey(),
+        insurance_fee_bps,
+    );
+    process_instruction(context, instruction, &[manager]).await
+}
+
+async fn inject_insurance_fund(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    authority: &Keypair,
+    lamports: u64,
+) -> Option<TransactionError> {
+    let instruction = instruction::inject_insurance_fund(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &authority.pubkey(),
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        lamports,
+    );
+    process_instruction(context, instruction, &[authority]).await
+}
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts) {
+    let (mut context, stake_pool_accounts) = setup_with_sol_deposit().await;
+    let error = set_insurance_fee(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        INSURANCE_FEE_BPS,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (context, stake_pool_accounts)
+}
+
+async fn get_insurance_fund(
+    context: &mut ProgramTestContext,
+    stake_pool: &Pubkey,
+) -> (InsuranceFund, u64) {
+    let (address, _) = find_insurance_fund_program_address(&id(), stake_pool);
+    let account = get_account(&mut context.banks_client, &address).await;
+    (
+        try_from_slice_unchecked::<InsuranceFund>(account.data.as_slice()).unwrap(),
+        account.lamports,
+    )
+}
+
+async fn set_stake_pool(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    stake_pool: &StakePool,
+) {
+    let account = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    let mut data = account.data.clone();
+    borsh::to_writer(&mut data[..], stake_pool).unwrap();
+    context.set_account(
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &AccountSharedData::from(Account { data, ..account }),
+    );
+}
+
+/// Builds a pool with one validator whose stake account holds `loss_lamports`
+/// fewer lamports than recorded, and moves to the next epoch to update it
+async fn setup_with_stake_loss(loss_lamports: u64) -> (ProgramTestContext, StakePoolAccounts) {
+    let mut program_test = program_test();
+    let stake_pool_accounts = StakePoolAccounts::default();
+    let stake_pool_pubkey = stake_pool_accounts.stake_pool.pubkey();
+    let (mut stake_pool, mut validator_list) = stake_pool_accounts.state();
+    stake_pool.last_update_epoch = FIRST_NORMAL_EPOCH;
+
+    let vote_account_address = add_vote_account(&mut program_test);
+    add_validator_stake_account(
+        &mut program_test,
+        &mut stake_pool,
+        &mut validator_list,
+        &stake_pool_pubkey,
+        &stake_pool_accounts.withdraw_authority,
+        &vote_account_address,
+        10 * LAMPORTS_PER_SOL,
+        StakeStatus::Active,
+    );
+    let validator = &mut validator_list.validators[0];
+    validator.active_stake_lamports =
+        (u64::from(validator.active_stake_lamports) + loss_lamports).into();
+    stake_pool.total_lamports += loss_lamports;
+    stake_pool.pool_token_supply += loss_lamports;
+
+    add_reserve_stake_account(
+        &mut program_test,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        LAMPORTS_PER_SOL,
+    );
+    stake_pool.total_lamports += LAMPORTS_PER_SOL;
+    add_stake_pool_account(&mut program_test, &stake_pool_pubkey, &stake_pool);
+    add_validator_list_account(
+        &mut program_test,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &validator_list,
+        stake_pool_accounts.max_validators,
+    );
+    add_mint_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        stake_pool.pool_token_supply,
+    );
+    add_token_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.manager.pubkey(),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let epoch_schedule = &context.genesis_config().epoch_schedule;
+    let slot = epoch_schedule.first_normal_slot + epoch_schedule.slots_per_epoch + 1;
+    context.warp_to_slot(slot).unwrap();
+
+    (context, stake_pool_accounts)
+}
+
+async fn update_validator_list_balance_instruction(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> Instruction {
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    instruction::update_validator_list_balance_chunk(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &validator_list,
+        validator_list.validators.len(),
+        0,
+        false,
+    )
+    .unwrap()
+}
+
+/// Adds rewards to the reserve and collects them, paying the insurance fund
+/// Collects rewards into the insurance fund, returning its share
+async fn collect_insurance_fee(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> u64 {
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    collect_rewards(
+        context,
+        stake_pool_accounts,
+        instruction::with_insurance_fund,
+    )
+    .await;
+    stake_pool
+        .calc_insurance_fee_lamports(REWARD_LAMPORTS)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success_set_insurance_fee() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let (insurance_fund, _) =
+        get_insurance_fund(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(insurance_fund.account_type, AccountType::InsuranceFund);
+    assert_eq!(
+        insurance_fund.stake_pool,
+        stake_pool_accounts.stake_pool.pubkey()
+    );
+    assert_eq!(insurance_fund.deposited_lamports, 0);
+    assert_eq!(insurance_fund.injected_lamports, 0);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.insurance_fee_bps, INSURANCE_FEE_BPS);
+
+    // change the fee, reusing the existing account
+    let error = set_insurance_fee(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        0,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.insurance_fee_bps, 0);
+}
+
+#[tokio::test]
+async fn fail_set_insurance_fee_wrong_manager() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = set_insurance_fee(&mut context, &stake_pool_accounts, &Keypair::new(), 1_000)
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_set_insurance_fee_too_high() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = set_insurance_fee(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        MAX_BASIS_POINTS + 1,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidInsuranceFund as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_pays_insurance_fund() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let (_, lamports_before) =
+        get_insurance_fund(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    let total_lamports_before = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await
+        .total_lamports;
+
+    let insurance_lamports = collect_insurance_fee(&mut context, &stake_pool_accounts).await;
+    assert!(insurance_lamports > 0);
+
+    let (insurance_fund, lamports_after) =
+        get_insurance_fund(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(lamports_after - lamports_before, insurance_lamports);
+    assert_eq!(insurance_fund.deposited_lamports, insurance_lamports);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool.total_lamports,
+        total_lamports_before + REWARD_LAMPORTS - insurance_lamports
+    );
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_accrues_without_insurance_fund() {
+    let (mut context, stake_pool_accounts) = setup().await;
+    let stake_pool_before = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let insurance_lamports = stake_pool_before
+        .calc_insurance_fee_lamports(REWARD_LAMPORTS)
+        .unwrap();
+    assert!(insurance_lamports > 0);
+
+    // without the insurance fund accounts, the share is only set aside
+    collect_rewards(&mut context, &stake_pool_accounts, |instruction, _| {
+        instruction
+    })
+    .await;
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.pending_insurance_lamports, insurance_lamports);
+    let expected_total_lamports =
+        stake_pool_before.total_lamports + REWARD_LAMPORTS - insurance_lamports;
+    assert_eq!(stake_pool.total_lamports, expected_total_lamports);
+    let (insurance_fund, lamports_before) =
+        get_insurance_fund(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(insurance_fund.deposited_lamports, 0);
+
+    // a later update with the accounts pays out what accrued
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let instruction = instruction::with_insurance_fund(
+        update_stake_pool_balance_instruction(&stake_pool_accounts),
+        &stake_pool_accounts.stake_pool.pubkey(),
+    );
+    let error = process_instruction(&mut context, instruction, &[]).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.pending_insurance_lamports, 0);
+    assert_eq!(stake_pool.total_lamports, expected_total_lamports);
+    let (insurance_fund, lamports_after) =
+        get_insurance_fund(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(insurance_fund.deposited_lamports, insurance_lamports);
+    assert_eq!(lamports_after - lamports_before, insurance_lamports);
+}
+
+#[tokio::test]
+async fn success_inject_insurance_fund() {
+    let (mut context, stake_pool_accounts) = setup().await;
+    let insurance_lamports = collect_insurance_fee(&mut context, &stake_pool_accounts).await;
+
+    // record a loss as if a validator had been slashed
+    let loss_lamports = insurance_lamports * 2;
+    let mut stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    stake_pool.uncovered_loss_lamports = loss_lamports;
+    set_stake_pool(&mut context, &stake_pool_accounts, &stake_pool).await;
+
+    let reserve_lamports_before = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    let error = inject_insurance_fund(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.staker,
+        insurance_lamports,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let reserve_lamports_after = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(
+        reserve_lamports_after - reserve_lamports_before,
+        insurance_lamports
+    );
+    let (insurance_fund, _) =
+        get_insurance_fund(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(insurance_fund.injected_lamports, insurance_lamports);
+    let stake_pool_after = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool_after.total_lamports,
+        stake_pool.total_lamports + insurance_lamports
+    );
+    assert_eq!(
+        stake_pool_after.uncovered_loss_lamports,
+        loss_lamports - insurance_lamports
+    );
+}
+
+#[tokio::test]
+async fn fail_inject_insurance_fund_too_large() {
+    let (mut context, stake_pool_accounts) = setup().await;
+    let insurance_lamports = collect_insurance_fee(&mut context, &stake_pool_accounts).await;
+
+    // nothing to cover
+    let error = inject_insurance_fund(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        1,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InsuranceInjectionTooLarge as u32)
+        )
+    );
+
+    // more than the fund holds
+    let mut stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    stake_pool.uncovered_loss_lamports = insurance_lamports * 2;
+    set_stake_pool(&mut context, &stake_pool_accounts, &stake_pool).await;
+    let error = inject_insurance_fund(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+        insurance_lamports + 1,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InsuranceInjectionTooLarge as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_inject_insurance_fund_wrong_authority() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = inject_insurance_fund(&mut context, &stake_pool_accounts, &Keypair::new(), 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SignatureMissing as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_update_validator_list_balance_records_loss() {
+    let loss_lamports = LAMPORTS_PER_SOL;
+    let (mut context, stake_pool_accounts) = setup_with_stake_loss(loss_lamports).await;
+
+    let instruction =
+        update_validator_list_balance_instruction(&mut context, &stake_pool_accounts).await;
+    let error = process_instruction(&mut context, instruction, &[]).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.uncovered_loss_lamports, loss_lamports);
+}
+
+#[tokio::test]
+async fn fail_update_validator_list_balance_loss_with_read_only_pool() {
+    let (mut context, stake_pool_accounts) = setup_with_stake_loss(LAMPORTS_PER_SOL).await;
+
+    let mut instruction =
+        update_validator_list_balance_instruction(&mut context, &stake_pool_accounts).await;
+    instruction.accounts[0] =
+        AccountMeta::new_readonly(stake_pool_accounts.stake_pool.pubkey(), false);
+    let error = process_instruction(&mut context, instruction, &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    // the loss is still there to be recorded
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        validator_list.validators[0].last_update_epoch,
+        FIRST_NORMAL_EPOCH.into()
+    );
+}
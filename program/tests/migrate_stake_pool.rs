@@ -0,0 +1,346 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked, instruction::InstructionError, pubkey::Pubkey, rent::Rent,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::{Account as SolanaAccount, AccountSharedData},
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        id, instruction,
+        state::{
+            FutureEpochFee, FutureEpochReferralFee, FutureEpochSolWithdrawalFeeCurve,
+            LegacyStakePool, SolWithdrawalFeeCurve, StakePool,
+        },
+        MINIMUM_RESERVE_LAMPORTS, STAKE_POOL_LEN,
+    },
+};
+
+/// Size of the original stake pool layout, ending with
+/// `last_epoch_total_lamports`
+const LEGACY_STAKE_POOL_LEN: usize = 611;
+
+async fn setup() -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    Keypair,
+    LegacyStakePool,
+) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let user_pool_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &user_pool_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &Keypair::new(),
+        &[],
+    )
+    .await
+    .unwrap();
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user_pool_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // rewrite the pool with the original layout, as left by an earlier
+    // version of the program
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let mut legacy_stake_pool = LegacyStakePool {
+        account_type: stake_pool.account_type,
+        manager: stake_pool.manager,
+        staker: stake_pool.staker,
+        stake_deposit_authority: stake_pool.stake_deposit_authority,
+        stake_withdraw_bump_seed: stake_pool.stake_withdraw_bump_seed,
+        validator_list: stake_pool.validator_list,
+        reserve_stake: stake_pool.reserve_stake,
+        pool_mint: stake_pool.pool_mint,
+        manager_fee_account: stake_pool.manager_fee_account,
+        token_program_id: stake_pool.token_program_id,
+        total_lamports: stake_pool.total_lamports,
+        pool_token_supply: stake_pool.pool_token_supply,
+        last_update_epoch: stake_pool.last_update_epoch,
+        lockup: stake_pool.lockup,
+        epoch_fee: stake_pool.epoch_fee,
+        next_epoch_fee: stake_pool.next_epoch_fee,
+        preferred_deposit_validator_vote_address: Some(Pubkey::new_unique()),
+        preferred_withdraw_validator_vote_address: None,
+        stake_deposit_fee: stake_pool.stake_deposit_fee,
+        stake_withdrawal_fee: stake_pool.stake_withdrawal_fee,
+        next_stake_withdrawal_fee: stake_pool.next_stake_withdrawal_fee,
+        stake_referral_fee: stake_pool.stake_referral_fee,
+        sol_deposit_authority: stake_pool.sol_deposit_authority,
+        sol_deposit_fee: stake_pool.sol_deposit_fee,
+        sol_referral_fee: stake_pool.sol_referral_fee,
+        sol_withdraw_authority: stake_pool.sol_withdraw_authority,
+        sol_withdrawal_fee: stake_pool.sol_withdrawal_fee,
+        next_sol_withdrawal_fee: stake_pool.next_sol_withdrawal_fee,
+        last_epoch_pool_token_supply: stake_pool.last_epoch_pool_token_supply,
+        last_epoch_total_lamports: stake_pool.last_epoch_total_lamports,
+    };
+    let mut legacy_data = vec![0u8; LEGACY_STAKE_POOL_LEN];
+    borsh::to_writer(&mut legacy_data[..], &legacy_stake_pool).unwrap();
+    // clearing the preferred validator shortens the encoding and leaves the
+    // end of the longer one behind
+    legacy_stake_pool.preferred_deposit_validator_vote_address = None;
+    borsh::to_writer(&mut legacy_data[..], &legacy_stake_pool).unwrap();
+    let encoded_len = borsh::to_vec(&legacy_stake_pool).unwrap().len();
+    assert!(legacy_data[encoded_len..].iter().any(|byte| *byte != 0));
+
+    context.set_account(
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &AccountSharedData::from(SolanaAccount {
+            lamports: Rent::default().minimum_balance(legacy_data.len()),
+            data: legacy_data,
+            owner: id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+
+    (
+        context,
+        stake_pool_accounts,
+        user_pool_account,
+        legacy_stake_pool,
+    )
+}
+
+async fn migrate_stake_pool(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> Option<TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::migrate_stake_pool(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, stake_pool_accounts, user_pool_account, legacy_stake_pool) = setup().await;
+
+    // the old layout is rejected until migrated
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user_pool_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::StakePoolNeedsMigration as u32)
+        )
+    );
+
+    let error = migrate_stake_pool(&mut context, &stake_pool_accounts).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let account = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.stake_pool.pubkey(),
+    )
+    .await;
+    assert_eq!(account.data.len(), STAKE_POOL_LEN);
+    assert_eq!(
+        account.lamports,
+        Rent::default().minimum_balance(STAKE_POOL_LEN)
+    );
+    let stake_pool = try_from_slice_unchecked::<StakePool>(&account.data).unwrap();
+
+    // the legacy fields are carried over
+    assert_eq!(stake_pool.manager, legacy_stake_pool.manager);
+    assert_eq!(stake_pool.staker, legacy_stake_pool.staker);
+    assert_eq!(stake_pool.pool_mint, legacy_stake_pool.pool_mint);
+    assert_eq!(stake_pool.total_lamports, legacy_stake_pool.total_lamports);
+    assert_eq!(
+        stake_pool.pool_token_supply,
+        legacy_stake_pool.pool_token_supply
+    );
+    assert_eq!(stake_pool.epoch_fee, legacy_stake_pool.epoch_fee);
+    assert_eq!(
+        stake_pool.sol_deposit_fee,
+        legacy_stake_pool.sol_deposit_fee
+    );
+    assert_eq!(stake_pool.preferred_deposit_validator_vote_address, None);
+
+    // the appended fields start at their defaults, whatever the legacy
+    // account held past its encoding
+    assert_eq!(stake_pool.pending_withdrawal_lamports, 0);
+    assert_eq!(stake_pool.withdrawal_claim_pool_lamports, 0);
+    assert_eq!(stake_pool.max_deposit_lamports_per_epoch, None);
+    assert_eq!(stake_pool.max_sol_withdrawal_lamports_per_epoch, None);
+    assert_eq!(stake_pool.max_total_lamports, None);
+    assert_eq!(stake_pool.flow_epoch, 0);
+    assert_eq!(stake_pool.epoch_deposited_lamports, 0);
+    assert_eq!(stake_pool.epoch_sol_withdrawn_lamports, 0);
+    assert_eq!(stake_pool.pauser, None);
+    assert_eq!(stake_pool.paused_operations, 0);
+    assert_eq!(stake_pool.pending_manager, None);
+    assert_eq!(stake_pool.pending_staker, None);
+    assert_eq!(stake_pool.next_stake_deposit_fee, FutureEpochFee::None);
+    assert_eq!(stake_pool.next_sol_deposit_fee, FutureEpochFee::None);
+    assert_eq!(
+        stake_pool.next_stake_referral_fee,
+        FutureEpochReferralFee::None
+    );
+    assert_eq!(
+        stake_pool.next_sol_referral_fee,
+        FutureEpochReferralFee::None
+    );
+    assert_eq!(stake_pool.fee_change_delay_epochs, 0);
+    assert_eq!(stake_pool.next_fee_change_delay_epochs, None);
+    assert_eq!(stake_pool.fee_change_epoch, 0);
+    assert_eq!(stake_pool.max_direct_stake_bps, None);
+    assert_eq!(stake_pool.max_validator_share_bps, None);
+    assert_eq!(stake_pool.max_validator_commission, None);
+    assert_eq!(stake_pool.max_validator_vote_age_epochs, None);
+    assert_eq!(
+        stake_pool.sol_withdrawal_fee_curve,
+        SolWithdrawalFeeCurve::default()
+    );
+    assert_eq!(
+        stake_pool.next_sol_withdrawal_fee_curve,
+        FutureEpochSolWithdrawalFeeCurve::None
+    );
+    assert!(!stake_pool.has_fee_split);
+    assert_eq!(stake_pool.insurance_fee_bps, 0);
+    assert_eq!(stake_pool.uncovered_loss_lamports, 0);
+    assert!(!stake_pool.has_rate_history);
+    assert!(!stake_pool.has_oracle);
+    assert_eq!(stake_pool.pending_insurance_lamports, 0);
+
+    // and nothing is left past the new encoding
+    let encoded_len = borsh::to_vec(&stake_pool).unwrap().len();
+    assert!(account.data[encoded_len..].iter().all(|byte| *byte == 0));
+
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user_pool_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool.total_lamports,
+        legacy_stake_pool.total_lamports + TEST_STAKE_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn fail_already_migrated() {
+    let (mut context, stake_pool_accounts, _, _) = setup().await;
+    let error = migrate_stake_pool(&mut context, &stake_pool_accounts).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = migrate_stake_pool(&mut context, &stake_pool_accounts)
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[tokio::test]
+async fn fail_not_stake_pool() {
+    let (context, stake_pool_accounts, _, _) = setup().await;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::migrate_stake_pool(
+            &id(),
+            &stake_pool_accounts.validator_list.pubkey(),
+            &context.payer.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidState as u32)
+        )
+    );
+}
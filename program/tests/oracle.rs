@@ -0,0 +1,228 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    borsh::BorshDeserialize,
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked, instruction::InstructionError, pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_oracle_program_address, id, instruction,
+        state::{AccountType, ExchangeRate, Oracle},
+        MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+async fn create_oracle(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    manager: &Keypair,
+) -> Option<TransactionError> {
+    let instruction = instruction::create_oracle(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &manager.pubkey(),
+        &context.payer.pubkey(),
+    );
+    process_instruction(context, instruction, &[manager]).await
+}
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts) {
+    let (mut context, stake_pool_accounts) = setup_with_sol_deposit().await;
+    let error = create_oracle(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (context, stake_pool_accounts)
+}
+
+async fn get_oracle(context: &mut ProgramTestContext, stake_pool: &Pubkey) -> Oracle {
+    let (address, _) = find_oracle_program_address(&id(), stake_pool);
+    let account = get_account(&mut context.banks_client, &address).await;
+    try_from_slice_unchecked::<Oracle>(account.data.as_slice()).unwrap()
+}
+
+async fn get_exchange_rate(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) -> ExchangeRate {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::get_exchange_rate(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let return_data = context
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap()
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+    assert_eq!(return_data.program_id, id());
+    ExchangeRate::try_from_slice(&return_data.data).unwrap()
+}
+
+#[tokio::test]
+async fn success_create_oracle() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert!(stake_pool.has_oracle);
+    let oracle = get_oracle(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(oracle.account_type, AccountType::Oracle);
+    assert_eq!(oracle.stake_pool, stake_pool_accounts.stake_pool.pubkey());
+    assert_eq!(oracle.pool_mint, stake_pool_accounts.pool_mint.pubkey());
+    assert_eq!(oracle.exchange_rate, ExchangeRate::from(&stake_pool));
+}
+
+#[tokio::test]
+async fn fail_create_oracle_twice() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = create_oracle(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::AlreadyInUse as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_create_oracle_wrong_manager() {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let error = create_oracle(&mut context, &stake_pool_accounts, &Keypair::new())
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_updates_oracle() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    collect_rewards(&mut context, &stake_pool_accounts, instruction::with_oracle).await;
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let oracle = get_oracle(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(oracle.exchange_rate, ExchangeRate::from(&stake_pool));
+    assert_eq!(
+        oracle.exchange_rate.last_update_epoch,
+        stake_pool.last_update_epoch
+    );
+    assert!(oracle.last_update_slot >= slot);
+    assert!(oracle.exchange_rate.lamports_per_pool_token().unwrap() > 1.0);
+
+    let exchange_rate = get_exchange_rate(&mut context, &stake_pool_accounts).await;
+    assert_eq!(exchange_rate, oracle.exchange_rate);
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_without_oracle() {
+    let (mut context, stake_pool_accounts) = setup().await;
+    let oracle_before = get_oracle(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = process_instruction(
+        &mut context,
+        update_stake_pool_balance_instruction(&stake_pool_accounts),
+        &[],
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // the oracle keeps its previous rate, now stale
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let oracle = get_oracle(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(oracle, oracle_before);
+    assert!(oracle.exchange_rate.is_stale(stake_pool.last_update_epoch));
+}
+
+#[tokio::test]
+async fn success_get_exchange_rate() {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let exchange_rate = get_exchange_rate(&mut context, &stake_pool_accounts).await;
+    assert_eq!(exchange_rate, ExchangeRate::from(&stake_pool));
+    assert!(!exchange_rate.is_stale(stake_pool.last_update_epoch));
+}
@@ -0,0 +1,601 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{instruction::InstructionError, pubkey::Pubkey},
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    },
+    spl_stake_pool::{
+        error::StakePoolError, event, find_user_stake_program_address, id, instruction,
+        state::PausedOperation, MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+async fn setup() -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    Keypair,
+    Keypair,
+    Pubkey,
+) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let pauser = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_pauser(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            Some(&pauser.pubkey()),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account.pubkey(),
+            TEST_STAKE_AMOUNT * 2,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (
+        context,
+        stake_pool_accounts,
+        pauser,
+        user,
+        pool_token_account.pubkey(),
+    )
+}
+
+async fn set_paused(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    authority: &Keypair,
+    paused_operations: u8,
+) -> Option<TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_paused(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &authority.pubkey(),
+            paused_operations,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn update_paused(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    authority: &Keypair,
+    pause: u8,
+    unpause: u8,
+) -> Result<Vec<event::StakePoolEvent>, TransactionError> {
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::update_paused(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &authority.pubkey(),
+            pause,
+            unpause,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, authority],
+        last_blockhash,
+    );
+    process_transaction_with_events(&mut context.banks_client, transaction)
+        .await
+        .map_err(|e| match e {
+            TransportError::TransactionError(error) => error,
+            _ => panic!("Unexpected error {:?}", e),
+        })
+}
+
+fn paused_error() -> TransactionError {
+    TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(StakePoolError::OperationPaused as u32),
+    )
+}
+
+#[tokio::test]
+async fn success_set_pauser() {
+    let (mut context, stake_pool_accounts, pauser, _user, _pool_token_account) = setup().await;
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.pauser, Some(pauser.pubkey()));
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_pauser(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.pauser, None);
+
+    // the old pauser can no longer pause
+    let error = set_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::ALL,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SignatureMissing as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_set_pauser_wrong_manager() {
+    let (context, stake_pool_accounts, pauser, _user, _pool_token_account) = setup().await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_pauser(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &pauser.pubkey(),
+            Some(&pauser.pubkey()),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &pauser],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_pause_and_unpause_deposits() {
+    let (mut context, stake_pool_accounts, pauser, _user, pool_token_account) = setup().await;
+
+    let error = set_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::Deposit.mask(),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(error, paused_error());
+
+    // the manager can unpause as well
+    let manager = stake_pool_accounts.manager.insecure_clone();
+    let error = set_paused(&mut context, &stake_pool_accounts, &manager, 0).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn success_set_paused_logs_event() {
+    let (mut context, stake_pool_accounts, pauser, _user, _pool_token_account) = setup().await;
+
+    let paused_operations = PausedOperation::Deposit.mask() | PausedOperation::Withdrawal.mask();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_paused(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &pauser.pubkey(),
+            paused_operations,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &pauser],
+        context.last_blockhash,
+    );
+    let events = process_transaction_with_events(&mut context.banks_client, transaction)
+        .await
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![event::StakePoolEvent::PausedOperationsChanged {
+            stake_pool: stake_pool_accounts.stake_pool.pubkey(),
+            paused_operations,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn success_pause_withdrawals_only() {
+    let (mut context, stake_pool_accounts, pauser, user, pool_token_account) = setup().await;
+
+    let error = set_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::Withdrawal.mask(),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let pool_tokens = get_token_balance(&mut context.banks_client, &pool_token_account).await;
+    let error = stake_pool_accounts
+        .withdraw_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 2,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(error, paused_error());
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 2,
+            0,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(error, paused_error());
+
+    // deposits are unaffected
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account,
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn fail_set_paused_wrong_authority() {
+    let (mut context, stake_pool_accounts, _pauser, _user, _pool_token_account) = setup().await;
+
+    let error = set_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &Keypair::new(),
+        PausedOperation::ALL,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SignatureMissing as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_set_paused_unknown_operation() {
+    let (mut context, stake_pool_accounts, pauser, _user, _pool_token_account) = setup().await;
+
+    let error = set_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::ALL + 1,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
+
+#[tokio::test]
+async fn success_update_paused_keeps_other_operations() {
+    let (mut context, stake_pool_accounts, pauser, _user, _pool_token_account) = setup().await;
+    let manager = stake_pool_accounts.manager.insecure_clone();
+
+    // two updates built against the same unpaused pool both take effect
+    let events = update_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::Deposit.mask(),
+        0,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![event::StakePoolEvent::PausedOperationsChanged {
+            stake_pool: stake_pool_accounts.stake_pool.pubkey(),
+            paused_operations: PausedOperation::Deposit.mask(),
+        }]
+    );
+    update_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &manager,
+        PausedOperation::Session.mask(),
+        0,
+    )
+    .await
+    .unwrap();
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool.paused_operations,
+        PausedOperation::Deposit.mask() | PausedOperation::Session.mask()
+    );
+
+    update_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::Withdrawal.mask(),
+        PausedOperation::Deposit.mask(),
+    )
+    .await
+    .unwrap();
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool.paused_operations,
+        PausedOperation::Withdrawal.mask() | PausedOperation::Session.mask()
+    );
+}
+
+#[tokio::test]
+async fn fail_update_paused_overlapping_operations() {
+    let (mut context, stake_pool_accounts, pauser, _user, _pool_token_account) = setup().await;
+
+    let error = update_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::Deposit.mask() | PausedOperation::Withdrawal.mask(),
+        PausedOperation::Withdrawal.mask(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
+
+#[tokio::test]
+async fn fail_update_paused_wrong_authority() {
+    let (mut context, stake_pool_accounts, _pauser, _user, _pool_token_account) = setup().await;
+
+    let error = update_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &Keypair::new(),
+        PausedOperation::ALL,
+        0,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SignatureMissing as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_withdraw_from_stake_account_with_session_paused() {
+    let (mut context, stake_pool_accounts, pauser, user, _pool_token_account) = setup().await;
+
+    let error = set_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::Session.mask(),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let user_stake_seed = 0;
+    let (user_stake, _) = find_user_stake_program_address(&id(), &user.pubkey(), user_stake_seed);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::withdraw_from_stake_account_with_session(
+            &id(),
+            &user_stake,
+            &user.pubkey(),
+            &user.pubkey(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            u64::MAX,
+            user_stake_seed,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(error, paused_error());
+}
+
+#[tokio::test]
+async fn fail_deactivate_validator_cranks_paused() {
+    let (mut context, stake_pool_accounts, pauser, _user, _pool_token_account) = setup().await;
+
+    let error = set_paused(
+        &mut context,
+        &stake_pool_accounts,
+        &pauser,
+        PausedOperation::Rebalance.mask(),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let vote_account = Pubkey::new_unique();
+    let stake_account = Pubkey::new_unique();
+    let transient_stake_account = Pubkey::new_unique();
+    let instructions = [
+        instruction::deactivate_high_commission_validator(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.validator_list.pubkey(),
+            &stake_account,
+            &transient_stake_account,
+            &vote_account,
+        ),
+        instruction::deactivate_delinquent_validator(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.validator_list.pubkey(),
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &stake_account,
+            &transient_stake_account,
+            &vote_account,
+            &context.payer.pubkey(),
+        ),
+    ];
+    for instruction in instructions {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(error, paused_error());
+    }
+}
@@ -0,0 +1,238 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked, clock::Clock, instruction::InstructionError,
+        pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_rate_history_program_address, id, instruction,
+        state::{AccountType, RateHistory, RateHistoryEntry},
+        MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+async fn create_rate_history(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    manager: &Keypair,
+) -> Option<TransactionError> {
+    let instruction = instruction::create_rate_history(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &manager.pubkey(),
+        &context.payer.pubkey(),
+    );
+    process_instruction(context, instruction, &[manager]).await
+}
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts) {
+    let (mut context, stake_pool_accounts) = setup_with_sol_deposit().await;
+    let error = create_rate_history(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (context, stake_pool_accounts)
+}
+
+async fn get_rate_history(context: &mut ProgramTestContext, stake_pool: &Pubkey) -> RateHistory {
+    let (address, _) = find_rate_history_program_address(&id(), stake_pool);
+    let account = get_account(&mut context.banks_client, &address).await;
+    try_from_slice_unchecked::<RateHistory>(account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn success_create_rate_history() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let rate_history =
+        get_rate_history(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(rate_history.account_type, AccountType::RateHistory);
+    assert_eq!(
+        rate_history.stake_pool,
+        stake_pool_accounts.stake_pool.pubkey()
+    );
+    assert_eq!(rate_history.len, 0);
+    assert!(rate_history.latest().is_none());
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert!(stake_pool.has_rate_history);
+}
+
+#[tokio::test]
+async fn fail_create_rate_history_twice() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = create_rate_history(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::AlreadyInUse as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_create_rate_history_wrong_manager() {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let error = create_rate_history(&mut context, &stake_pool_accounts, &Keypair::new())
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_records_rate() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let fee_pool_tokens = collect_rewards(
+        &mut context,
+        &stake_pool_accounts,
+        instruction::with_rate_history,
+    )
+    .await;
+    assert!(fee_pool_tokens > 0);
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let rate_history =
+        get_rate_history(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(rate_history.len, 1);
+    let entry = rate_history.latest().unwrap();
+    assert_eq!(
+        entry.epoch,
+        context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .epoch
+    );
+    assert_eq!(entry.total_lamports, stake_pool.total_lamports);
+    assert_eq!(entry.pool_token_supply, stake_pool.pool_token_supply);
+    assert_eq!(entry.fee_pool_tokens, fee_pool_tokens);
+
+    // a second update in the same epoch replaces the entry, adding up fees
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let second_fee_pool_tokens = collect_rewards(
+        &mut context,
+        &stake_pool_accounts,
+        instruction::with_rate_history,
+    )
+    .await;
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let rate_history =
+        get_rate_history(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(rate_history.len, 1);
+    let entry = rate_history.latest().unwrap();
+    assert_eq!(entry.total_lamports, stake_pool.total_lamports);
+    assert_eq!(entry.pool_token_supply, stake_pool.pool_token_supply);
+    assert_eq!(
+        entry.fee_pool_tokens,
+        fee_pool_tokens + second_fee_pool_tokens
+    );
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_appends_epochs() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    collect_rewards(
+        &mut context,
+        &stake_pool_accounts,
+        instruction::with_rate_history,
+    )
+    .await;
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    collect_rewards(
+        &mut context,
+        &stake_pool_accounts,
+        instruction::with_rate_history,
+    )
+    .await;
+
+    let rate_history =
+        get_rate_history(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(rate_history.len, 2);
+    let entries = rate_history.entries().collect::<Vec<_>>();
+    assert!(entries[0].epoch < entries[1].epoch);
+    let rate = |entry: &RateHistoryEntry| {
+        u128::from(entry.total_lamports) * 1_000_000_000 / u128::from(entry.pool_token_supply)
+    };
+    assert!(rate(entries[0]) < rate(entries[1]));
+}
+
+#[tokio::test]
+async fn success_update_stake_pool_balance_without_rate_history() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = process_instruction(
+        &mut context,
+        update_stake_pool_balance_instruction(&stake_pool_accounts),
+        &[],
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let rate_history =
+        get_rate_history(&mut context, &stake_pool_accounts.stake_pool.pubkey()).await;
+    assert_eq!(rate_history.len, 0);
+}
@@ -0,0 +1,546 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    bincode::deserialize,
+    helpers::*,
+    solana_program::{clock::Epoch, instruction::InstructionError, native_token::LAMPORTS_PER_SOL},
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    solana_stake_interface as stake,
+    spl_stake_pool::{
+        error::StakePoolError, find_ephemeral_stake_program_address, id, instruction,
+        state::StakeStatus, MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+/// Adds two validators to a pool, deposits stake into the first one and moves
+/// to the next epoch so that it is fully active, as required to move it
+async fn setup() -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    ValidatorStakeAccount,
+    ValidatorStakeAccount,
+    u64,
+) {
+    let mut context = program_test().start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let stake_rent = rent.minimum_balance(std::mem::size_of::<stake::state::StakeStateV2>());
+    let current_minimum_delegation = stake_pool_get_minimum_delegation(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+    )
+    .await;
+    let redelegate_lamports = current_minimum_delegation * 2;
+
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS + (redelegate_lamports + stake_rent * 3) * 2,
+        )
+        .await
+        .unwrap();
+
+    let source = simple_add_validator_to_pool(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        None,
+    )
+    .await;
+    let destination = simple_add_validator_to_pool(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        None,
+    )
+    .await;
+    simple_deposit_stake(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        &source,
+        redelegate_lamports * 3,
+    )
+    .await
+    .unwrap();
+
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    context.warp_to_slot(first_normal_slot + 1).unwrap();
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    (
+        context,
+        stake_pool_accounts,
+        source,
+        destination,
+        redelegate_lamports,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, stake_pool_accounts, source, destination, redelegate_lamports) =
+        setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let stake_rent = rent.minimum_balance(std::mem::size_of::<stake::state::StakeStateV2>());
+    let pre_source_stake_account =
+        get_account(&mut context.banks_client, &source.stake_account).await;
+    let pre_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+
+    let error = stake_pool_accounts
+        .redelegate(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &source,
+            &destination,
+            redelegate_lamports,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // source keeps its remaining stake active, the moved stake deactivates
+    let source_stake_account = get_account(&mut context.banks_client, &source.stake_account).await;
+    assert_eq!(
+        source_stake_account.lamports,
+        pre_source_stake_account.lamports - redelegate_lamports
+    );
+    let source_transient_account =
+        get_account(&mut context.banks_client, &source.transient_stake_account).await;
+    assert_eq!(
+        source_transient_account.lamports,
+        redelegate_lamports + stake_rent
+    );
+    let source_transient_state =
+        deserialize::<stake::state::StakeStateV2>(&source_transient_account.data).unwrap();
+    let clock = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap();
+    let delegation = source_transient_state.delegation().unwrap();
+    assert_eq!(delegation.voter_pubkey, source.vote.pubkey());
+    assert_eq!(delegation.deactivation_epoch, clock.epoch);
+
+    // destination starts activating the same amount right away
+    let destination_transient_account = get_account(
+        &mut context.banks_client,
+        &destination.transient_stake_account,
+    )
+    .await;
+    assert_eq!(
+        destination_transient_account.lamports,
+        redelegate_lamports + stake_rent
+    );
+    let destination_transient_state =
+        deserialize::<stake::state::StakeStateV2>(&destination_transient_account.data).unwrap();
+    let delegation = destination_transient_state.delegation().unwrap();
+    assert_eq!(delegation.voter_pubkey, destination.vote.pubkey());
+    assert_eq!(delegation.activation_epoch, clock.epoch);
+    assert_eq!(delegation.deactivation_epoch, Epoch::MAX);
+
+    // the ephemeral account is drained and the reserve fronts the stake
+    let ephemeral_stake =
+        find_ephemeral_stake_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey(), 0).0;
+    assert!(context
+        .banks_client
+        .get_account(ephemeral_stake)
+        .await
+        .unwrap()
+        .is_none());
+    let reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(
+        reserve_lamports,
+        pre_reserve_lamports - redelegate_lamports - stake_rent * 2
+    );
+
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    let source_info = validator_list.find(&source.vote.pubkey()).unwrap();
+    assert_eq!(
+        u64::from(source_info.transient_stake_lamports),
+        redelegate_lamports + stake_rent
+    );
+    let destination_info = validator_list.find(&destination.vote.pubkey()).unwrap();
+    assert_eq!(
+        u64::from(destination_info.transient_stake_lamports),
+        redelegate_lamports + stake_rent
+    );
+
+    // on the next epoch, the source stake returns to the reserve and the
+    // destination stake is merged into its validator stake account
+    let pre_total_lamports = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await
+        .total_lamports;
+    let pre_destination_stake_lamports =
+        get_account(&mut context.banks_client, &destination.stake_account)
+            .await
+            .lamports;
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    context.warp_to_slot(clock.slot + slots_per_epoch).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let destination_stake_account =
+        get_account(&mut context.banks_client, &destination.stake_account).await;
+    assert_eq!(
+        destination_stake_account.lamports,
+        pre_destination_stake_lamports + redelegate_lamports
+    );
+    let reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(reserve_lamports, pre_reserve_lamports);
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.total_lamports, pre_total_lamports);
+}
+
+#[tokio::test]
+async fn success_with_existing_destination_transient() {
+    let (mut context, stake_pool_accounts, source, destination, redelegate_lamports) =
+        setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let stake_rent = rent.minimum_balance(std::mem::size_of::<stake::state::StakeStateV2>());
+
+    let error = stake_pool_accounts
+        .increase_validator_stake_either(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &destination.transient_stake_account,
+            &destination.stake_account,
+            &destination.vote.pubkey(),
+            redelegate_lamports,
+            destination.transient_stake_seed,
+            true,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let pre_destination_transient_lamports = get_account(
+        &mut context.banks_client,
+        &destination.transient_stake_account,
+    )
+    .await
+    .lamports;
+
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .redelegate(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &source,
+            &destination,
+            redelegate_lamports,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // the activating destination stake absorbs the ephemeral stake by merge
+    let destination_transient_account = get_account(
+        &mut context.banks_client,
+        &destination.transient_stake_account,
+    )
+    .await;
+    assert_eq!(
+        destination_transient_account.lamports,
+        pre_destination_transient_lamports + redelegate_lamports + stake_rent
+    );
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    let destination_info = validator_list.find(&destination.vote.pubkey()).unwrap();
+    assert_eq!(
+        u64::from(destination_info.transient_stake_lamports),
+        destination_transient_account.lamports
+    );
+}
+
+#[tokio::test]
+async fn fail_same_validator() {
+    let (mut context, stake_pool_accounts, source, _destination, redelegate_lamports) =
+        setup().await;
+
+    let error = stake_pool_accounts
+        .redelegate(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &source,
+            &source,
+            redelegate_lamports,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidStakeAccountAddress as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_staker() {
+    let (context, stake_pool_accounts, source, destination, redelegate_lamports) = setup().await;
+    let wrong_staker = Keypair::new();
+    let ephemeral_stake =
+        find_ephemeral_stake_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey(), 0).0;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::redelegate(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &wrong_staker.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.validator_list.pubkey(),
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &source.stake_account,
+            &source.transient_stake_account,
+            &ephemeral_stake,
+            &destination.transient_stake_account,
+            &destination.stake_account,
+            &destination.vote.pubkey(),
+            redelegate_lamports,
+            source.transient_stake_seed,
+            0,
+            destination.transient_stake_seed,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &wrong_staker],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongStaker as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_too_much_stake() {
+    let (mut context, stake_pool_accounts, source, destination, _redelegate_lamports) =
+        setup().await;
+    let source_stake_account = get_account(&mut context.banks_client, &source.stake_account).await;
+
+    let error = stake_pool_accounts
+        .redelegate(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &source,
+            &destination,
+            source_stake_account.lamports,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InsufficientFunds)
+    );
+}
+
+/// Builds a pool with two active validators directly in its accounts, and a
+/// reserve holding `reserve_lamports` on top of its rent-exemption, without
+/// going through `AddValidatorToPool`
+async fn setup_with_pool_state(
+    reserve_lamports: u64,
+) -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    ValidatorStakeAccount,
+    ValidatorStakeAccount,
+) {
+    let mut program_test = program_test();
+    let stake_pool_accounts = StakePoolAccounts::default();
+    let stake_pool_pubkey = stake_pool_accounts.stake_pool.pubkey();
+    let (mut stake_pool, mut validator_list) = stake_pool_accounts.state();
+    stake_pool.last_update_epoch = FIRST_NORMAL_EPOCH + 10;
+
+    let source = ValidatorStakeAccount::new(&stake_pool_pubkey, None, 0);
+    let destination = ValidatorStakeAccount::new(&stake_pool_pubkey, None, 0);
+    for validator in [&source, &destination] {
+        add_vote_account_with_pubkey(&validator.vote.pubkey(), &mut program_test);
+        add_validator_stake_account(
+            &mut program_test,
+            &mut stake_pool,
+            &mut validator_list,
+            &stake_pool_pubkey,
+            &stake_pool_accounts.withdraw_authority,
+            &validator.vote.pubkey(),
+            10 * LAMPORTS_PER_SOL,
+            StakeStatus::Active,
+        );
+    }
+
+    add_reserve_stake_account(
+        &mut program_test,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        reserve_lamports,
+    );
+    stake_pool.total_lamports += reserve_lamports;
+    add_stake_pool_account(&mut program_test, &stake_pool_pubkey, &stake_pool);
+    add_validator_list_account(
+        &mut program_test,
+        &stake_pool_accounts.validator_list.pubkey(),
+        &validator_list,
+        stake_pool_accounts.max_validators,
+    );
+    add_mint_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        stake_pool.pool_token_supply,
+    );
+    add_token_account(
+        &mut program_test,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.manager.pubkey(),
+    );
+
+    // the validator stakes activate in the first normal epoch and count
+    // towards the cluster's warmup, so leave a few epochs for them to be fully
+    // active, which moving stake requires
+    let mut context = program_test.start_with_context().await;
+    let epoch_schedule = &context.genesis_config().epoch_schedule;
+    let slot = epoch_schedule.first_normal_slot + 10 * epoch_schedule.slots_per_epoch + 1;
+    context.warp_to_slot(slot).unwrap();
+
+    (context, stake_pool_accounts, source, destination)
+}
+
+#[tokio::test]
+async fn success_with_pool_state() {
+    let redelegate_lamports = 2 * LAMPORTS_PER_SOL;
+    // exactly what the reserve pays, keeping its own rent-exemption
+    let (mut context, stake_pool_accounts, source, destination) =
+        setup_with_pool_state(redelegate_lamports + 2 * STAKE_ACCOUNT_RENT_EXEMPTION).await;
+
+    let error = stake_pool_accounts
+        .redelegate(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &source,
+            &destination,
+            redelegate_lamports,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(reserve_lamports, STAKE_ACCOUNT_RENT_EXEMPTION);
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    for validator in [&source, &destination] {
+        let info = validator_list.find(&validator.vote.pubkey()).unwrap();
+        assert_eq!(
+            u64::from(info.transient_stake_lamports),
+            redelegate_lamports + STAKE_ACCOUNT_RENT_EXEMPTION
+        );
+    }
+}
+
+#[tokio::test]
+async fn fail_reserve_too_small_with_pool_state() {
+    let redelegate_lamports = 2 * LAMPORTS_PER_SOL;
+    let (mut context, stake_pool_accounts, source, destination) =
+        setup_with_pool_state(redelegate_lamports + 2 * STAKE_ACCOUNT_RENT_EXEMPTION - 1).await;
+
+    let error = stake_pool_accounts
+        .redelegate(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &source,
+            &destination,
+            redelegate_lamports,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InsufficientFunds)
+    );
+}
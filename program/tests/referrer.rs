@@ -0,0 +1,456 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked, instruction::InstructionError, pubkey::Pubkey, rent::Rent,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        find_referrer_program_address, id,
+        instruction::{self, FundingType},
+        state::{AccountType, Referrer},
+        MINIMUM_RESERVE_LAMPORTS,
+    },
+};
+
+const CODE: &str = "xlabstest";
+
+struct ReferrerAccounts {
+    address: Pubkey,
+    authority: Keypair,
+    payout_token_account: Pubkey,
+}
+
+async fn create_pool_token_account(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    owner: &Keypair,
+) -> Pubkey {
+    let token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        owner,
+        &[],
+    )
+    .await
+    .unwrap();
+    token_account.pubkey()
+}
+
+async fn register_referrer(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    authority: &Keypair,
+    payout_token_account: &Pubkey,
+    code: &str,
+) -> Option<TransactionError> {
+    let (referrer, _) =
+        find_referrer_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey(), code);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::register_referrer(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &referrer,
+            &authority.pubkey(),
+            payout_token_account,
+            &context.payer.pubkey(),
+            code,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn setup() -> (
+    ProgramTestContext,
+    StakePoolAccounts,
+    ReferrerAccounts,
+    Pubkey,
+) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let authority = Keypair::new();
+    let payout_token_account =
+        create_pool_token_account(&mut context, &stake_pool_accounts, &authority).await;
+    let error = register_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &authority,
+        &payout_token_account,
+        CODE,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let user = Keypair::new();
+    let user_pool_account =
+        create_pool_token_account(&mut context, &stake_pool_accounts, &user).await;
+
+    let (address, _) =
+        find_referrer_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey(), CODE);
+    (
+        context,
+        stake_pool_accounts,
+        ReferrerAccounts {
+            address,
+            authority,
+            payout_token_account,
+        },
+        user_pool_account,
+    )
+}
+
+async fn get_referrer(context: &mut ProgramTestContext, address: &Pubkey) -> Referrer {
+    let account = get_account(&mut context.banks_client, address).await;
+    try_from_slice_unchecked::<Referrer>(account.data.as_slice()).unwrap()
+}
+
+async fn deposit_sol_with_referrer(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    user_pool_account: &Pubkey,
+    referrer_fee_account: &Pubkey,
+    referrer: &Pubkey,
+    sol_deposit_authority: Option<&Keypair>,
+    amount: u64,
+) -> Option<TransactionError> {
+    let mut signers = vec![&context.payer];
+    let instruction = if let Some(sol_deposit_authority) = sol_deposit_authority {
+        signers.push(sol_deposit_authority);
+        instruction::deposit_sol_with_authority(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &sol_deposit_authority.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &context.payer.pubkey(),
+            user_pool_account,
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            referrer_fee_account,
+            &stake_pool_accounts.pool_mint.pubkey(),
+            &stake_pool_accounts.token_program_id,
+            amount,
+        )
+    } else {
+        instruction::deposit_sol(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &context.payer.pubkey(),
+            user_pool_account,
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            referrer_fee_account,
+            &stake_pool_accounts.pool_mint.pubkey(),
+            &stake_pool_accounts.token_program_id,
+            amount,
+        )
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::with_referrer(instruction, referrer)],
+        Some(&context.payer.pubkey()),
+        &signers,
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+#[tokio::test]
+async fn success_register() {
+    let (mut context, stake_pool_accounts, referrer_accounts, _user_pool_account) = setup().await;
+
+    let referrer = get_referrer(&mut context, &referrer_accounts.address).await;
+    assert_eq!(referrer.account_type, AccountType::Referrer);
+    assert_eq!(referrer.stake_pool, stake_pool_accounts.stake_pool.pubkey());
+    assert_eq!(referrer.authority, referrer_accounts.authority.pubkey());
+    assert_eq!(referrer.code(), CODE);
+    assert_eq!(
+        referrer.payout_token_account,
+        referrer_accounts.payout_token_account
+    );
+    assert_eq!(referrer.referred_lamports, 0);
+    assert_eq!(referrer.earned_pool_tokens, 0);
+}
+
+#[tokio::test]
+async fn fail_register_taken_code() {
+    let (mut context, stake_pool_accounts, _referrer_accounts, user_pool_account) = setup().await;
+
+    let error = register_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &Keypair::new(),
+        &user_pool_account,
+        CODE,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::AlreadyInUse as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_register_prefunded_code() {
+    let (mut context, stake_pool_accounts, _referrer_accounts, user_pool_account) = setup().await;
+
+    // anyone may send lamports to the address of a code before it is taken
+    let code = "prefunded";
+    let (address, _) =
+        find_referrer_program_address(&id(), &stake_pool_accounts.stake_pool.pubkey(), code);
+    transfer(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &address,
+        Rent::default().minimum_balance(0),
+    )
+    .await;
+
+    let authority = Keypair::new();
+    let error = register_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &authority,
+        &user_pool_account,
+        code,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let referrer = get_referrer(&mut context, &address).await;
+    assert_eq!(referrer.authority, authority.pubkey());
+    assert_eq!(referrer.code(), code);
+}
+
+#[tokio::test]
+async fn fail_register_invalid_code() {
+    let (mut context, stake_pool_accounts, referrer_accounts, _user_pool_account) = setup().await;
+
+    let error = register_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &referrer_accounts.authority,
+        &referrer_accounts.payout_token_account,
+        "ref:code",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidReferralCode as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_deposit_sol_with_referrer() {
+    let (mut context, stake_pool_accounts, referrer_accounts, user_pool_account) = setup().await;
+
+    let error = deposit_sol_with_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &user_pool_account,
+        &referrer_accounts.payout_token_account,
+        &referrer_accounts.address,
+        None,
+        TEST_STAKE_AMOUNT,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let referral_fee = get_token_balance(
+        &mut context.banks_client,
+        &referrer_accounts.payout_token_account,
+    )
+    .await;
+    assert!(referral_fee > 0);
+
+    let referrer = get_referrer(&mut context, &referrer_accounts.address).await;
+    assert_eq!(referrer.referred_lamports, TEST_STAKE_AMOUNT);
+    assert_eq!(referrer.earned_pool_tokens, referral_fee);
+}
+
+#[tokio::test]
+async fn success_deposit_sol_with_authority_and_referrer() {
+    let (mut context, stake_pool_accounts, referrer_accounts, user_pool_account) = setup().await;
+
+    let sol_deposit_authority = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_funding_authority(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            Some(&sol_deposit_authority.pubkey()),
+            FundingType::SolDeposit,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    // the referrer cannot take the place of the deposit authority
+    let error = deposit_sol_with_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &user_pool_account,
+        &referrer_accounts.payout_token_account,
+        &referrer_accounts.address,
+        None,
+        TEST_STAKE_AMOUNT,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+
+    let error = deposit_sol_with_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &user_pool_account,
+        &referrer_accounts.payout_token_account,
+        &referrer_accounts.address,
+        Some(&sol_deposit_authority),
+        TEST_STAKE_AMOUNT,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let referrer = get_referrer(&mut context, &referrer_accounts.address).await;
+    assert_eq!(referrer.referred_lamports, TEST_STAKE_AMOUNT);
+}
+
+#[tokio::test]
+async fn fail_deposit_sol_wrong_payout_account() {
+    let (mut context, stake_pool_accounts, referrer_accounts, user_pool_account) = setup().await;
+
+    let error = deposit_sol_with_referrer(
+        &mut context,
+        &stake_pool_accounts,
+        &user_pool_account,
+        &user_pool_account,
+        &referrer_accounts.address,
+        None,
+        TEST_STAKE_AMOUNT,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidReferrer as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_update_referrer() {
+    let (mut context, stake_pool_accounts, referrer_accounts, user_pool_account) = setup().await;
+
+    let new_authority = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::update_referrer(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &referrer_accounts.address,
+            &referrer_accounts.authority.pubkey(),
+            &user_pool_account,
+            Some(&new_authority.pubkey()),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &referrer_accounts.authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let referrer = get_referrer(&mut context, &referrer_accounts.address).await;
+    assert_eq!(referrer.payout_token_account, user_pool_account);
+    assert_eq!(referrer.authority, new_authority.pubkey());
+}
+
+#[tokio::test]
+async fn fail_update_referrer_wrong_authority() {
+    let (context, stake_pool_accounts, referrer_accounts, user_pool_account) = setup().await;
+
+    let wrong_authority = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::update_referrer(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &referrer_accounts.address,
+            &wrong_authority.pubkey(),
+            &user_pool_account,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &wrong_authority],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::SignatureMissing as u32)
+        )
+    );
+}
@@ -14,8 +14,8 @@ use {
     },
     spl_stake_pool::{
         error, id, instruction,
-        state::{Fee, FeeType, StakePool},
-        MINIMUM_RESERVE_LAMPORTS,
+        state::{Fee, FeeType, FutureEpochFee, StakePool},
+        MAX_FEE_CHANGE_DELAY_EPOCHS, MINIMUM_RESERVE_LAMPORTS,
     },
 };
 
@@ -277,3 +277,256 @@ async fn fail_sol_high_deposit_fee() {
         _ => panic!("Wrong error occurs when setting fee too high"),
     }
 }
+
+async fn deposit_into_pool(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+) {
+    let user = Keypair::new();
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn success_sol_timelocked() {
+    let (mut context, stake_pool_accounts, _) = setup(None).await;
+    deposit_into_pool(&mut context, &stake_pool_accounts).await;
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let old_fee = stake_pool.sol_deposit_fee;
+    let new_fee = Fee {
+        numerator: old_fee.numerator * 3,
+        denominator: old_fee.denominator * 2,
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            FeeType::SolDeposit(new_fee),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.sol_deposit_fee, old_fee);
+    assert_eq!(
+        stake_pool.next_sol_deposit_fee,
+        FutureEpochFee::Two(new_fee)
+    );
+
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    let slot = first_normal_slot + 1;
+    for (i, expected_fee) in [old_fee, new_fee].into_iter().enumerate() {
+        context
+            .warp_to_slot(slot + i as u64 * slots_per_epoch)
+            .unwrap();
+        let last_blockhash = context
+            .banks_client
+            .get_new_latest_blockhash(&context.last_blockhash)
+            .await
+            .unwrap();
+        stake_pool_accounts
+            .update_all(
+                &mut context.banks_client,
+                &context.payer,
+                &last_blockhash,
+                false,
+            )
+            .await;
+
+        let stake_pool = stake_pool_accounts
+            .get_stake_pool(&mut context.banks_client)
+            .await;
+        assert_eq!(stake_pool.sol_deposit_fee, expected_fee);
+    }
+}
+
+#[tokio::test]
+async fn fail_stake_high_fee_increase() {
+    let (mut context, stake_pool_accounts, _) = setup(None).await;
+    deposit_into_pool(&mut context, &stake_pool_accounts).await;
+
+    let new_deposit_fee = Fee {
+        numerator: stake_pool_accounts.deposit_fee.numerator * 2,
+        denominator: stake_pool_accounts.deposit_fee.denominator,
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            FeeType::StakeDeposit(new_deposit_fee),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .unwrap();
+
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(error_index)) => {
+            let program_error = error::StakePoolError::FeeIncreaseTooHigh as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs when increasing fee too much"),
+    }
+}
+
+#[tokio::test]
+async fn success_fee_change_delay() {
+    let (mut context, stake_pool_accounts, _) = setup(None).await;
+    deposit_into_pool(&mut context, &stake_pool_accounts).await;
+
+    // start from a normal epoch, so each warp below crosses a single boundary
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    let slot = first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+
+    let new_fee = Fee {
+        numerator: 0,
+        denominator: 1,
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            instruction::set_fee_change_delay(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.manager.pubkey(),
+                2,
+            ),
+            instruction::set_fee(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.manager.pubkey(),
+                FeeType::StakeDeposit(new_fee),
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    // held for two epochs, then counted down for two more
+    for i in 1..=4 {
+        let stake_pool = stake_pool_accounts
+            .get_stake_pool(&mut context.banks_client)
+            .await;
+        assert_eq!(
+            stake_pool.stake_deposit_fee,
+            stake_pool_accounts.deposit_fee
+        );
+
+        context.warp_to_slot(slot + i * slots_per_epoch).unwrap();
+        let last_blockhash = context
+            .banks_client
+            .get_new_latest_blockhash(&context.last_blockhash)
+            .await
+            .unwrap();
+        stake_pool_accounts
+            .update_all(
+                &mut context.banks_client,
+                &context.payer,
+                &last_blockhash,
+                false,
+            )
+            .await;
+    }
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.stake_deposit_fee, new_fee);
+}
+
+#[tokio::test]
+async fn fail_fee_change_delay_too_long() {
+    let (context, stake_pool_accounts, _) = setup(None).await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee_change_delay(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            MAX_FEE_CHANGE_DELAY_EPOCHS + 1,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .unwrap();
+
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(error_index)) => {
+            let program_error = error::StakePoolError::FeeChangeDelayTooLong as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs when setting fee change delay too long"),
+    }
+}
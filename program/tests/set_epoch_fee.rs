@@ -14,7 +14,7 @@ use {
     },
     spl_stake_pool::{
         error, id, instruction,
-        state::{Fee, FeeType, FutureEpoch, StakePool},
+        state::{Fee, FeeType, FutureEpochFee, StakePool},
         MINIMUM_RESERVE_LAMPORTS,
     },
 };
@@ -76,7 +76,7 @@ async fn success() {
     let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
 
     assert_eq!(stake_pool.epoch_fee, old_fee);
-    assert_eq!(stake_pool.next_epoch_fee, FutureEpoch::Two(new_fee));
+    assert_eq!(stake_pool.next_epoch_fee, FutureEpochFee::Two(new_fee));
 
     let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
     let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
@@ -98,7 +98,7 @@ async fn success() {
     .await;
     let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
     assert_eq!(stake_pool.epoch_fee, old_fee);
-    assert_eq!(stake_pool.next_epoch_fee, FutureEpoch::One(new_fee));
+    assert_eq!(stake_pool.next_epoch_fee, FutureEpochFee::One(new_fee));
 
     let last_blockhash = context
         .banks_client
@@ -122,7 +122,7 @@ async fn success() {
     .await;
     let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
     assert_eq!(stake_pool.epoch_fee, new_fee);
-    assert_eq!(stake_pool.next_epoch_fee, FutureEpoch::None);
+    assert_eq!(stake_pool.next_epoch_fee, FutureEpochFee::None);
 }
 
 #[tokio::test]
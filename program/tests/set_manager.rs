@@ -3,13 +3,11 @@
 
 mod helpers;
 
+#[cfg(feature = "legacy-authority-transfer")]
+use solana_program::instruction::{AccountMeta, Instruction};
 use {
     helpers::*,
-    solana_program::{
-        borsh1::try_from_slice_unchecked,
-        hash::Hash,
-        instruction::{AccountMeta, Instruction},
-    },
+    solana_program::{borsh1::try_from_slice_unchecked, hash::Hash},
     solana_program_test::*,
     solana_sdk::{
         instruction::InstructionError,
@@ -17,7 +15,7 @@ use {
         transaction::{Transaction, TransactionError},
         transport::TransportError,
     },
-    spl_stake_pool::{error, id, instruction, state, MINIMUM_RESERVE_LAMPORTS},
+    spl_stake_pool::{error, event, id, instruction, state, MINIMUM_RESERVE_LAMPORTS},
 };
 
 async fn setup() -> (
@@ -65,6 +63,7 @@ async fn setup() -> (
     )
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn test_set_manager() {
     let (mut banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
@@ -93,6 +92,7 @@ async fn test_set_manager() {
     assert_eq!(stake_pool.manager, new_manager.pubkey());
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn test_set_manager_by_malicious() {
     let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
@@ -128,6 +128,7 @@ async fn test_set_manager_by_malicious() {
     }
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn test_set_manager_without_existing_signature() {
     let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
@@ -169,6 +170,7 @@ async fn test_set_manager_without_existing_signature() {
     }
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn test_set_manager_without_new_signature() {
     let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
@@ -210,6 +212,7 @@ async fn test_set_manager_without_new_signature() {
     }
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn test_set_manager_with_wrong_mint_for_pool_fee_acc() {
     let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
@@ -286,3 +289,218 @@ async fn test_set_manager_with_wrong_mint_for_pool_fee_acc() {
         _ => panic!("Wrong error occurs while try to set new manager with wrong mint"),
     }
 }
+
+#[cfg(not(feature = "legacy-authority-transfer"))]
+#[tokio::test]
+async fn fail_set_manager_disabled() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
+        setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::set_manager(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            &new_manager.pubkey(),
+            &new_pool_fee.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &stake_pool_accounts.manager, &new_manager],
+        recent_blockhash,
+    );
+    let error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
+
+#[tokio::test]
+async fn success_propose_and_accept_manager() {
+    let (mut banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
+        setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::propose_manager(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            Some(&new_manager.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &stake_pool_accounts.manager], recent_blockhash);
+    let events = process_transaction_with_events(&mut banks_client, transaction)
+        .await
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![event::StakePoolEvent::AuthorityProposed {
+            stake_pool: stake_pool_accounts.stake_pool.pubkey(),
+            authority_type: event::AuthorityType::Manager,
+            proposed_authority: Some(new_manager.pubkey()),
+        }]
+    );
+
+    let stake_pool = get_account(&mut banks_client, &stake_pool_accounts.stake_pool.pubkey()).await;
+    let stake_pool =
+        try_from_slice_unchecked::<state::StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.manager, stake_pool_accounts.manager.pubkey());
+    assert_eq!(stake_pool.pending_manager, Some(new_manager.pubkey()));
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::accept_manager(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &new_manager.pubkey(),
+            &new_pool_fee.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &new_manager], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let stake_pool = get_account(&mut banks_client, &stake_pool_accounts.stake_pool.pubkey()).await;
+    let stake_pool =
+        try_from_slice_unchecked::<state::StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.manager, new_manager.pubkey());
+    assert_eq!(stake_pool.manager_fee_account, new_pool_fee.pubkey());
+    assert_eq!(stake_pool.pending_manager, None);
+}
+
+#[tokio::test]
+async fn fail_propose_manager_by_malicious() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, _new_pool_fee, new_manager) =
+        setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::propose_manager(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &new_manager.pubkey(),
+            Some(&new_manager.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &new_manager], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .into();
+
+    match transaction_error {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(error_index),
+        )) => {
+            let program_error = error::StakePoolError::WrongManager as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs while malicious try to propose manager"),
+    }
+}
+
+#[tokio::test]
+async fn fail_accept_manager_not_pending() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
+        setup().await;
+
+    let malicious = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::propose_manager(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.manager.pubkey(),
+                Some(&new_manager.pubkey()),
+            ),
+            instruction::accept_manager(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &malicious.pubkey(),
+                &new_pool_fee.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &stake_pool_accounts.manager, &malicious],
+        recent_blockhash,
+    );
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .into();
+
+    match transaction_error {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error_index),
+        )) => {
+            let program_error = error::StakePoolError::WrongPendingAuthority as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs while accepting manager without proposal"),
+    }
+}
+
+#[tokio::test]
+async fn fail_accept_manager_after_cancel() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_pool_fee, new_manager) =
+        setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::propose_manager(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.manager.pubkey(),
+                Some(&new_manager.pubkey()),
+            ),
+            instruction::propose_manager(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.manager.pubkey(),
+                None,
+            ),
+            instruction::accept_manager(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &new_manager.pubkey(),
+                &new_pool_fee.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &stake_pool_accounts.manager, &new_manager],
+        recent_blockhash,
+    );
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .into();
+
+    match transaction_error {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            2,
+            InstructionError::Custom(error_index),
+        )) => {
+            let program_error = error::StakePoolError::WrongPendingAuthority as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs while accepting a cancelled manager proposal"),
+    }
+}
@@ -44,6 +44,7 @@ async fn setup() -> (BanksClient, Keypair, Hash, StakePoolAccounts, Keypair) {
     )
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn success_set_staker_as_manager() {
     let (mut banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) =
@@ -68,6 +69,7 @@ async fn success_set_staker_as_manager() {
     assert_eq!(stake_pool.staker, new_staker.pubkey());
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn success_set_staker_as_staker() {
     let (mut banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) =
@@ -110,6 +112,7 @@ async fn success_set_staker_as_staker() {
     assert_eq!(stake_pool.staker, stake_pool_accounts.staker.pubkey());
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn fail_wrong_manager() {
     let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) = setup().await;
@@ -143,6 +146,7 @@ async fn fail_wrong_manager() {
     }
 }
 
+#[cfg(feature = "legacy-authority-transfer")]
 #[tokio::test]
 async fn fail_set_staker_without_signature() {
     let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) = setup().await;
@@ -179,3 +183,194 @@ async fn fail_set_staker_without_signature() {
         _ => panic!("Wrong error occurs while try to set new manager without signature"),
     }
 }
+
+#[cfg(not(feature = "legacy-authority-transfer"))]
+#[tokio::test]
+async fn fail_set_staker_disabled() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) = setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::set_staker(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            &new_staker.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &stake_pool_accounts.manager], recent_blockhash);
+    let error = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
+
+#[tokio::test]
+async fn success_propose_and_accept_staker() {
+    let (mut banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) =
+        setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::propose_staker(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.staker.pubkey(),
+            Some(&new_staker.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &stake_pool_accounts.staker], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let stake_pool = get_account(&mut banks_client, &stake_pool_accounts.stake_pool.pubkey()).await;
+    let stake_pool =
+        try_from_slice_unchecked::<state::StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.staker, stake_pool_accounts.staker.pubkey());
+    assert_eq!(stake_pool.pending_staker, Some(new_staker.pubkey()));
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::accept_staker(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &new_staker.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &new_staker], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let stake_pool = get_account(&mut banks_client, &stake_pool_accounts.stake_pool.pubkey()).await;
+    let stake_pool =
+        try_from_slice_unchecked::<state::StakePool>(stake_pool.data.as_slice()).unwrap();
+    assert_eq!(stake_pool.staker, new_staker.pubkey());
+    assert_eq!(stake_pool.pending_staker, None);
+}
+
+#[tokio::test]
+async fn fail_propose_staker_wrong_manager() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) = setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::propose_staker(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &new_staker.pubkey(),
+            Some(&new_staker.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &new_staker], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .into();
+
+    match transaction_error {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(error_index),
+        )) => {
+            let program_error = error::StakePoolError::SignatureMissing as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs while malicious try to propose staker"),
+    }
+}
+
+#[tokio::test]
+async fn fail_accept_staker_after_cancel() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) = setup().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::propose_staker(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.manager.pubkey(),
+                Some(&new_staker.pubkey()),
+            ),
+            instruction::propose_staker(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.manager.pubkey(),
+                None,
+            ),
+            instruction::accept_staker(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &new_staker.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(
+        &[&payer, &stake_pool_accounts.manager, &new_staker],
+        recent_blockhash,
+    );
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .into();
+
+    match transaction_error {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            2,
+            InstructionError::Custom(error_index),
+        )) => {
+            let program_error = error::StakePoolError::WrongPendingAuthority as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs while accepting a cancelled staker proposal"),
+    }
+}
+
+#[tokio::test]
+async fn fail_accept_staker_without_signature() {
+    let (banks_client, payer, recent_blockhash, stake_pool_accounts, new_staker) = setup().await;
+
+    let propose = instruction::propose_staker(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.manager.pubkey(),
+        Some(&new_staker.pubkey()),
+    );
+    let data = borsh::to_vec(&instruction::StakePoolInstruction::AcceptStaker).unwrap();
+    let accounts = vec![
+        AccountMeta::new(stake_pool_accounts.stake_pool.pubkey(), false),
+        AccountMeta::new_readonly(new_staker.pubkey(), false),
+    ];
+    let accept = Instruction {
+        program_id: id(),
+        accounts,
+        data,
+    };
+
+    let mut transaction = Transaction::new_with_payer(&[propose, accept], Some(&payer.pubkey()));
+    transaction.sign(&[&payer, &stake_pool_accounts.manager], recent_blockhash);
+    let transaction_error = banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .unwrap()
+        .into();
+
+    match transaction_error {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(error_index),
+        )) => {
+            let program_error = error::StakePoolError::SignatureMissing as u32;
+            assert_eq!(error_index, program_error);
+        }
+        _ => panic!("Wrong error occurs while accepting staker without signature"),
+    }
+}
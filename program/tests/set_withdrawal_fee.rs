@@ -14,7 +14,7 @@ use {
     },
     spl_stake_pool::{
         error, id, instruction,
-        state::{Fee, FeeType, FutureEpoch, StakePool},
+        state::{Fee, FeeType, FutureEpochFee, StakePool},
         MINIMUM_RESERVE_LAMPORTS,
     },
 };
@@ -99,12 +99,12 @@ async fn success() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
 
     let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
@@ -130,12 +130,12 @@ async fn success() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
 
     let last_blockhash = context
@@ -160,9 +160,9 @@ async fn success() {
     .await;
     let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
     assert_eq!(stake_pool.stake_withdrawal_fee, new_withdrawal_fee);
-    assert_eq!(stake_pool.next_stake_withdrawal_fee, FutureEpoch::None);
+    assert_eq!(stake_pool.next_stake_withdrawal_fee, FutureEpochFee::None);
     assert_eq!(stake_pool.sol_withdrawal_fee, new_withdrawal_fee);
-    assert_eq!(stake_pool.next_sol_withdrawal_fee, FutureEpoch::None);
+    assert_eq!(stake_pool.next_sol_withdrawal_fee, FutureEpochFee::None);
 }
 
 #[tokio::test]
@@ -222,12 +222,12 @@ async fn success_fee_cannot_increase_more_than_once() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
 
     let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
@@ -253,12 +253,12 @@ async fn success_fee_cannot_increase_more_than_once() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
 
     let last_blockhash = context
@@ -284,9 +284,9 @@ async fn success_fee_cannot_increase_more_than_once() {
     let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
 
     assert_eq!(stake_pool.stake_withdrawal_fee, new_withdrawal_fee);
-    assert_eq!(stake_pool.next_stake_withdrawal_fee, FutureEpoch::None);
+    assert_eq!(stake_pool.next_stake_withdrawal_fee, FutureEpochFee::None);
     assert_eq!(stake_pool.sol_withdrawal_fee, new_withdrawal_fee);
-    assert_eq!(stake_pool.next_sol_withdrawal_fee, FutureEpoch::None);
+    assert_eq!(stake_pool.next_sol_withdrawal_fee, FutureEpochFee::None);
 
     // try setting to the old fee in the same epoch
     let transaction = Transaction::new_signed_with_payer(
@@ -331,12 +331,12 @@ async fn success_fee_cannot_increase_more_than_once() {
     assert_eq!(stake_pool.stake_withdrawal_fee, new_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::Two(old_stake_withdrawal_fee)
+        FutureEpochFee::Two(old_stake_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, new_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::Two(old_sol_withdrawal_fee)
+        FutureEpochFee::Two(old_sol_withdrawal_fee)
     );
 
     let error = stake_pool_accounts
@@ -358,12 +358,12 @@ async fn success_fee_cannot_increase_more_than_once() {
     assert_eq!(stake_pool.stake_withdrawal_fee, new_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::Two(old_stake_withdrawal_fee)
+        FutureEpochFee::Two(old_stake_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, new_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::Two(old_sol_withdrawal_fee)
+        FutureEpochFee::Two(old_sol_withdrawal_fee)
     );
 }
 
@@ -424,12 +424,12 @@ async fn success_reset_fee_after_one_epoch() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
 
     let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
@@ -452,12 +452,12 @@ async fn success_reset_fee_after_one_epoch() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
 
     // Flip the two fees, resets the counter to two future epochs
@@ -504,12 +504,12 @@ async fn success_reset_fee_after_one_epoch() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::Two(old_sol_withdrawal_fee)
+        FutureEpochFee::Two(old_sol_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::Two(old_stake_withdrawal_fee)
+        FutureEpochFee::Two(old_stake_withdrawal_fee)
     );
 }
 
@@ -578,12 +578,12 @@ async fn success_increase_fee_from_0() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::Two(new_withdrawal_fee)
+        FutureEpochFee::Two(new_withdrawal_fee)
     );
 
     let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
@@ -608,12 +608,12 @@ async fn success_increase_fee_from_0() {
     assert_eq!(stake_pool.stake_withdrawal_fee, old_stake_withdrawal_fee);
     assert_eq!(
         stake_pool.next_stake_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
     assert_eq!(stake_pool.sol_withdrawal_fee, old_sol_withdrawal_fee);
     assert_eq!(
         stake_pool.next_sol_withdrawal_fee,
-        FutureEpoch::One(new_withdrawal_fee)
+        FutureEpochFee::One(new_withdrawal_fee)
     );
 
     let last_blockhash = context
@@ -638,9 +638,9 @@ async fn success_increase_fee_from_0() {
     .await;
     let stake_pool = try_from_slice_unchecked::<StakePool>(stake_pool.data.as_slice()).unwrap();
     assert_eq!(stake_pool.stake_withdrawal_fee, new_withdrawal_fee);
-    assert_eq!(stake_pool.next_stake_withdrawal_fee, FutureEpoch::None);
+    assert_eq!(stake_pool.next_stake_withdrawal_fee, FutureEpochFee::None);
     assert_eq!(stake_pool.sol_withdrawal_fee, new_withdrawal_fee);
-    assert_eq!(stake_pool.next_sol_withdrawal_fee, FutureEpoch::None);
+    assert_eq!(stake_pool.next_sol_withdrawal_fee, FutureEpochFee::None);
 }
 
 #[tokio::test]
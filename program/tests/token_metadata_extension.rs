@@ -0,0 +1,246 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{instruction::InstructionError, pubkey::Pubkey},
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    solana_stake_interface as stake,
+    spl_stake_pool::{error::StakePoolError, id, instruction, MINIMUM_RESERVE_LAMPORTS},
+    spl_token_2022::{
+        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        state::Mint,
+    },
+    spl_token_metadata_interface::state::TokenMetadata,
+};
+
+/// Initializes a Token-2022 stake pool whose mint has the given extensions
+async fn initialize_stake_pool(
+    context: &mut ProgramTestContext,
+    mint_extensions: &[ExtensionType],
+) -> StakePoolAccounts {
+    let stake_pool_accounts = StakePoolAccounts::new_with_token_program(spl_token_2022::id());
+    create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_mint,
+        &stake_pool_accounts.withdraw_authority,
+        stake_pool_accounts.pool_decimals,
+        mint_extensions,
+    )
+    .await
+    .unwrap();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_fee_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.manager,
+        &[],
+    )
+    .await
+    .unwrap();
+    create_independent_stake_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.reserve_stake,
+        &stake::state::Authorized {
+            staker: stake_pool_accounts.withdraw_authority,
+            withdrawer: stake_pool_accounts.withdraw_authority,
+        },
+        &stake::state::Lockup::default(),
+        MINIMUM_RESERVE_LAMPORTS,
+    )
+    .await;
+    create_stake_pool(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.stake_pool,
+        &stake_pool_accounts.validator_list,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.manager,
+        &stake_pool_accounts.staker.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &stake_pool_accounts.stake_deposit_authority_keypair,
+        &stake_pool_accounts.epoch_fee,
+        &stake_pool_accounts.withdrawal_fee,
+        &stake_pool_accounts.deposit_fee,
+        stake_pool_accounts.referral_fee,
+        &stake_pool_accounts.sol_deposit_fee,
+        stake_pool_accounts.sol_referral_fee,
+        stake_pool_accounts.max_validators,
+    )
+    .await
+    .unwrap();
+    stake_pool_accounts
+}
+
+async fn create_token_metadata_extension(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    manager: &Keypair,
+) -> Option<TransactionError> {
+    let instruction = instruction::create_token_metadata_extension(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &manager.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &context.payer.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        "test_name".to_string(),
+        "SYM".to_string(),
+        "test_uri".to_string(),
+    );
+    process_instruction(context, instruction, &[manager]).await
+}
+
+async fn update_field(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    field: &str,
+    value: &str,
+) -> Option<TransactionError> {
+    let instruction = instruction::update_token_metadata_extension_field(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.manager.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &context.payer.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        field.to_string(),
+        value.to_string(),
+    );
+    process_instruction(context, instruction, &[&stake_pool_accounts.manager]).await
+}
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts =
+        initialize_stake_pool(&mut context, &[ExtensionType::MetadataPointer]).await;
+    let error = create_token_metadata_extension(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    (context, stake_pool_accounts)
+}
+
+async fn get_token_metadata(context: &mut ProgramTestContext, pool_mint: &Pubkey) -> TokenMetadata {
+    let account = get_account(&mut context.banks_client, pool_mint).await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert!(rent.is_exempt(account.lamports, account.data.len()));
+    let mint = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+    mint.get_variable_len_extension::<TokenMetadata>().unwrap()
+}
+
+#[tokio::test]
+async fn success_create_token_metadata_extension() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let metadata = get_token_metadata(&mut context, &stake_pool_accounts.pool_mint.pubkey()).await;
+    assert_eq!(metadata.name, "test_name");
+    assert_eq!(metadata.symbol, "SYM");
+    assert_eq!(metadata.uri, "test_uri");
+    assert_eq!(metadata.mint, stake_pool_accounts.pool_mint.pubkey());
+    assert_eq!(
+        Option::<Pubkey>::from(metadata.update_authority),
+        Some(stake_pool_accounts.withdraw_authority)
+    );
+    assert!(metadata.additional_metadata.is_empty());
+}
+
+#[tokio::test]
+async fn success_update_and_remove_fields() {
+    let (mut context, stake_pool_accounts) = setup().await;
+
+    let error = update_field(&mut context, &stake_pool_accounts, "name", "updated_name").await;
+    assert!(error.is_none(), "{:?}", error);
+    let error = update_field(
+        &mut context,
+        &stake_pool_accounts,
+        "website",
+        "https://example.com",
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let metadata = get_token_metadata(&mut context, &stake_pool_accounts.pool_mint.pubkey()).await;
+    assert_eq!(metadata.name, "updated_name");
+    assert_eq!(metadata.symbol, "SYM");
+    assert_eq!(
+        metadata.additional_metadata,
+        vec![("website".to_string(), "https://example.com".to_string())]
+    );
+
+    let instruction = instruction::remove_token_metadata_extension_key(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.manager.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        "website".to_string(),
+    );
+    let error =
+        process_instruction(&mut context, instruction, &[&stake_pool_accounts.manager]).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let metadata = get_token_metadata(&mut context, &stake_pool_accounts.pool_mint.pubkey()).await;
+    assert!(metadata.additional_metadata.is_empty());
+}
+
+#[tokio::test]
+async fn fail_wrong_manager() {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts =
+        initialize_stake_pool(&mut context, &[ExtensionType::MetadataPointer]).await;
+
+    let error =
+        create_token_metadata_extension(&mut context, &stake_pool_accounts, &Keypair::new())
+            .await
+            .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_without_metadata_pointer() {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = initialize_stake_pool(&mut context, &[]).await;
+
+    let error = create_token_metadata_extension(
+        &mut context,
+        &stake_pool_accounts,
+        &stake_pool_accounts.manager,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidMetadataPointer as u32)
+        )
+    );
+}
@@ -8,12 +8,15 @@ use {
     solana_program::{borsh1::try_from_slice_unchecked, instruction::InstructionError},
     solana_program_test::*,
     solana_sdk::{
+        clock::Clock,
         hash::Hash,
         signature::{Keypair, Signer},
-        transaction::TransactionError,
+        transaction::{Transaction, TransactionError},
     },
     solana_stake_interface::state::StakeStateV2,
-    spl_stake_pool::{error::StakePoolError, state::StakePool, MINIMUM_RESERVE_LAMPORTS},
+    spl_stake_pool::{
+        error::StakePoolError, event, id, instruction, state::StakePool, MINIMUM_RESERVE_LAMPORTS,
+    },
     std::num::NonZeroU32,
 };
 
@@ -307,6 +310,87 @@ async fn success_absorbing_extra_lamports() {
     assert_eq!(pool_token_supply, pre_token_supply + expected_fee);
 }
 
+#[tokio::test]
+async fn success_logs_event() {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    // Rewards landing in the reserve
+    const EXTRA_RESERVE_AMOUNT: u64 = 1_000_000;
+    transfer(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        EXTRA_RESERVE_AMOUNT,
+    )
+    .await;
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let expected_fee = stake_pool
+        .calc_epoch_fee_amount(EXTRA_RESERVE_AMOUNT)
+        .unwrap();
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_validator_list_balance(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            0,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::update_stake_pool_balance(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.validator_list.pubkey(),
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            &stake_pool_accounts.pool_mint.pubkey(),
+            &stake_pool_accounts.token_program_id,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        last_blockhash,
+    );
+    let events = process_transaction_with_events(&mut context.banks_client, transaction)
+        .await
+        .unwrap();
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(
+        events,
+        vec![event::StakePoolEvent::UpdateStakePoolBalance {
+            stake_pool: stake_pool_accounts.stake_pool.pubkey(),
+            epoch: clock.epoch,
+            total_lamports: stake_pool.total_lamports + EXTRA_RESERVE_AMOUNT,
+            pool_token_supply: stake_pool.pool_token_supply + expected_fee,
+            epoch_fee_pool_tokens: expected_fee,
+        }]
+    );
+}
+
 #[tokio::test]
 async fn fail_with_wrong_validator_list() {
     let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
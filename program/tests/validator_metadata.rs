@@ -0,0 +1,375 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked, clock::Clock, instruction::InstructionError,
+        native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        account::AccountSharedData,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{
+        error::StakePoolError,
+        event, find_validator_metadata_program_address, id, instruction,
+        state::{CapType, OffboardingReason, StakeStatus, ValidatorMetadata},
+        MAX_COMMISSION,
+    },
+};
+
+// tag, node pubkey and authorized withdrawer of the vote state
+const VOTE_COMMISSION_OFFSET: usize = 4 + 32 + 32;
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts, ValidatorStakeAccount) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            100 * LAMPORTS_PER_SOL,
+        )
+        .await
+        .unwrap();
+
+    let validator_stake = ValidatorStakeAccount::new(
+        &stake_pool_accounts.stake_pool.pubkey(),
+        DEFAULT_VALIDATOR_STAKE_SEED,
+        DEFAULT_TRANSIENT_STAKE_SEED,
+    );
+    create_vote(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &validator_stake.validator,
+        &validator_stake.vote,
+    )
+    .await;
+
+    (context, stake_pool_accounts, validator_stake)
+}
+
+fn validator_metadata_address(
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+) -> Pubkey {
+    find_validator_metadata_program_address(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &validator_stake.vote.pubkey(),
+    )
+    .0
+}
+
+async fn add_validator(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+) -> Option<TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::with_validator_metadata(
+            instruction::add_validator_to_pool(
+                &id(),
+                &stake_pool_accounts.stake_pool.pubkey(),
+                &stake_pool_accounts.staker.pubkey(),
+                &stake_pool_accounts.reserve_stake.pubkey(),
+                &stake_pool_accounts.withdraw_authority,
+                &stake_pool_accounts.validator_list.pubkey(),
+                &validator_stake.stake_account,
+                &validator_stake.vote.pubkey(),
+                validator_stake.validator_stake_seed,
+            ),
+            &validator_metadata_address(stake_pool_accounts, validator_stake),
+            Some(&context.payer.pubkey()),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.staker],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn set_pool_max_commission(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    max_commission: Option<u8>,
+) -> Option<TransactionError> {
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_cap(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            CapType::ValidatorCommission(max_commission),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn set_vote_commission(
+    context: &mut ProgramTestContext,
+    validator_stake: &ValidatorStakeAccount,
+    commission: u8,
+) {
+    let mut vote_account =
+        get_account(&mut context.banks_client, &validator_stake.vote.pubkey()).await;
+    vote_account.data[VOTE_COMMISSION_OFFSET] = commission;
+    context.set_account(
+        &validator_stake.vote.pubkey(),
+        &AccountSharedData::from(vote_account),
+    );
+}
+
+async fn deactivate_high_commission_validator(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+) -> Option<TransactionError> {
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::deactivate_high_commission_validator(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &stake_pool_accounts.validator_list.pubkey(),
+            &validator_stake.stake_account,
+            &validator_stake.transient_stake_account,
+            &validator_stake.vote.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn get_validator_metadata(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+) -> ValidatorMetadata {
+    let account = get_account(
+        &mut context.banks_client,
+        &validator_metadata_address(stake_pool_accounts, validator_stake),
+    )
+    .await;
+    assert_eq!(account.owner, id());
+    try_from_slice_unchecked::<ValidatorMetadata>(&account.data).unwrap()
+}
+
+fn stake_pool_error(error: StakePoolError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+#[tokio::test]
+async fn success_add_creates_metadata() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    let error = add_validator(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let validator_metadata =
+        get_validator_metadata(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(validator_metadata.is_valid());
+    assert_eq!(
+        validator_metadata.stake_pool,
+        stake_pool_accounts.stake_pool.pubkey()
+    );
+    assert_eq!(
+        validator_metadata.vote_account,
+        validator_stake.vote.pubkey()
+    );
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(validator_metadata.admission_epoch, clock.epoch);
+    assert_eq!(validator_metadata.max_commission, MAX_COMMISSION);
+    assert_eq!(
+        validator_metadata.offboarding_reason,
+        OffboardingReason::None
+    );
+
+    // the staker can record notes and tighten the validator's own limit
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::update_validator_metadata(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.staker.pubkey(),
+            &validator_stake.vote.pubkey(),
+            [7; 32],
+            10,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.staker],
+        context.last_blockhash,
+    );
+    let events = process_transaction_with_events(&mut context.banks_client, transaction)
+        .await
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![event::StakePoolEvent::ValidatorMetadataChanged {
+            stake_pool: stake_pool_accounts.stake_pool.pubkey(),
+            vote_account: validator_stake.vote.pubkey(),
+            notes_hash: [7; 32],
+            max_commission: 10,
+        }]
+    );
+    let validator_metadata =
+        get_validator_metadata(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_eq!(validator_metadata.notes_hash, [7; 32]);
+    assert_eq!(validator_metadata.max_commission, 10);
+}
+
+#[tokio::test]
+async fn success_deactivate_high_commission() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    let error = set_pool_max_commission(&mut context, &stake_pool_accounts, Some(5)).await;
+    assert!(error.is_none(), "{:?}", error);
+    let error = add_validator(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+    let validator_metadata =
+        get_validator_metadata(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_eq!(validator_metadata.max_commission, 5);
+
+    set_vote_commission(&mut context, &validator_stake, 6).await;
+    let error =
+        deactivate_high_commission_validator(&mut context, &stake_pool_accounts, &validator_stake)
+            .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    let validator_stake_info = validator_list.find(&validator_stake.vote.pubkey()).unwrap();
+    assert_eq!(
+        validator_stake_info.status,
+        StakeStatus::DeactivatingValidator.into()
+    );
+    let validator_metadata =
+        get_validator_metadata(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert_eq!(
+        validator_metadata.offboarding_reason,
+        OffboardingReason::CommissionTooHigh
+    );
+}
+
+#[tokio::test]
+async fn fail_deactivate_within_limit() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    let error = add_validator(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // no limit at all
+    set_vote_commission(&mut context, &validator_stake, 100).await;
+    let error =
+        deactivate_high_commission_validator(&mut context, &stake_pool_accounts, &validator_stake)
+            .await
+            .unwrap();
+    assert_eq!(
+        error,
+        stake_pool_error(StakePoolError::CommissionWithinLimit)
+    );
+
+    set_vote_commission(&mut context, &validator_stake, 5).await;
+    let error = set_pool_max_commission(&mut context, &stake_pool_accounts, Some(5)).await;
+    assert!(error.is_none(), "{:?}", error);
+    let error =
+        deactivate_high_commission_validator(&mut context, &stake_pool_accounts, &validator_stake)
+            .await
+            .unwrap();
+    assert_eq!(
+        error,
+        stake_pool_error(StakePoolError::CommissionWithinLimit)
+    );
+}
+
+#[tokio::test]
+async fn fail_add_above_pool_commission() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    let error = set_pool_max_commission(&mut context, &stake_pool_accounts, Some(5)).await;
+    assert!(error.is_none(), "{:?}", error);
+    set_vote_commission(&mut context, &validator_stake, 6).await;
+
+    let error = add_validator(&mut context, &stake_pool_accounts, &validator_stake)
+        .await
+        .unwrap();
+    assert_eq!(error, stake_pool_error(StakePoolError::CommissionTooHigh));
+}
+
+#[tokio::test]
+async fn fail_invalid_commission() {
+    let (mut context, stake_pool_accounts, _validator_stake) = setup().await;
+
+    let error = set_pool_max_commission(&mut context, &stake_pool_accounts, Some(101))
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
+
+#[tokio::test]
+async fn fail_update_wrong_staker() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    let error = add_validator(&mut context, &stake_pool_accounts, &validator_stake).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let wrong_staker = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::update_validator_metadata(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &wrong_staker.pubkey(),
+            &validator_stake.vote.pubkey(),
+            [7; 32],
+            10,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &wrong_staker],
+        context.last_blockhash,
+    );
+    let error = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(error, stake_pool_error(StakePoolError::WrongStaker));
+}
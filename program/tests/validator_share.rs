@@ -0,0 +1,267 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{instruction::InstructionError, native_token::LAMPORTS_PER_SOL},
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{error::StakePoolError, id, instruction, state::CapType},
+};
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts, ValidatorStakeAccount) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            100 * LAMPORTS_PER_SOL,
+        )
+        .await
+        .unwrap();
+
+    let validator_stake = simple_add_validator_to_pool(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        None,
+    )
+    .await;
+
+    (context, stake_pool_accounts, validator_stake)
+}
+
+async fn set_validator_max_share(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+    manager: &Keypair,
+    max_share_bps: Option<u16>,
+) -> Option<TransactionError> {
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_validator_max_share(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &manager.pubkey(),
+            &stake_pool_accounts.validator_list.pubkey(),
+            &validator_stake.vote.pubkey(),
+            max_share_bps,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, manager],
+        last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn set_pool_max_share(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    max_share_bps: Option<u16>,
+) -> Option<TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_cap(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            CapType::ValidatorShare(max_share_bps),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+async fn increase(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    validator_stake: &ValidatorStakeAccount,
+    lamports: u64,
+) -> Option<TransactionError> {
+    stake_pool_accounts
+        .increase_validator_stake(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &validator_stake.transient_stake_account,
+            &validator_stake.stake_account,
+            &validator_stake.vote.pubkey(),
+            lamports,
+            validator_stake.transient_stake_seed,
+        )
+        .await
+        .map(|e| e.unwrap())
+}
+
+fn share_exceeded() -> TransactionError {
+    TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(StakePoolError::ValidatorShareExceeded as u32),
+    )
+}
+
+#[tokio::test]
+async fn success_set_validator_max_share() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    let error = set_validator_max_share(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake,
+        &stake_pool_accounts.manager,
+        Some(2_500),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    let validator_stake_info = validator_list.find(&validator_stake.vote.pubkey()).unwrap();
+    assert_eq!(validator_stake_info.max_share_bps(), Some(2_500));
+
+    let error = set_validator_max_share(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake,
+        &stake_pool_accounts.manager,
+        None,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+    let validator_list = stake_pool_accounts
+        .get_validator_list(&mut context.banks_client)
+        .await;
+    let validator_stake_info = validator_list.find(&validator_stake.vote.pubkey()).unwrap();
+    assert_eq!(validator_stake_info.max_share_bps(), None);
+}
+
+#[tokio::test]
+async fn fail_increase_above_pool_share() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    // 20% of a pool of about 100 SOL
+    let error = set_pool_max_share(&mut context, &stake_pool_accounts, Some(2_000)).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = increase(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake,
+        30 * LAMPORTS_PER_SOL,
+    )
+    .await
+    .unwrap();
+    assert_eq!(error, share_exceeded());
+
+    let error = increase(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake,
+        10 * LAMPORTS_PER_SOL,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+}
+
+#[tokio::test]
+async fn fail_increase_above_validator_share() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    // the validator's own limit is tighter than the pool-wide one
+    let error = set_pool_max_share(&mut context, &stake_pool_accounts, Some(5_000)).await;
+    assert!(error.is_none(), "{:?}", error);
+    let error = set_validator_max_share(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake,
+        &stake_pool_accounts.manager,
+        Some(1_000),
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let error = increase(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake,
+        20 * LAMPORTS_PER_SOL,
+    )
+    .await
+    .unwrap();
+    assert_eq!(error, share_exceeded());
+}
+
+#[tokio::test]
+async fn fail_invalid_share() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    for max_share_bps in [0, 10_001] {
+        let error = set_validator_max_share(
+            &mut context,
+            &stake_pool_accounts,
+            &validator_stake,
+            &stake_pool_accounts.manager,
+            Some(max_share_bps),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        );
+    }
+
+    let error = set_pool_max_share(&mut context, &stake_pool_accounts, Some(10_001))
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_manager() {
+    let (mut context, stake_pool_accounts, validator_stake) = setup().await;
+
+    let error = set_validator_max_share(
+        &mut context,
+        &stake_pool_accounts,
+        &validator_stake,
+        &Keypair::new(),
+        Some(1_000),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WrongManager as u32)
+        )
+    );
+}
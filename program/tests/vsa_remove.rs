@@ -550,7 +550,7 @@ async fn success_with_deactivating_transient_stake() {
             active_stake_lamports: (stake_rent + current_minimum_delegation).into(),
             transient_stake_lamports: (TEST_STAKE_AMOUNT + stake_rent * 2).into(),
             transient_seed_suffix: validator_stake.transient_stake_seed.into(),
-            unused: 0.into(),
+            max_share_bps: 0.into(),
             validator_seed_suffix: validator_stake
                 .validator_stake_seed
                 .map(|s| s.get())
@@ -995,7 +995,7 @@ async fn update_no_merge_after_removal() {
                 .into(),
             transient_stake_lamports: 0.into(),
             transient_seed_suffix: 0.into(),
-            unused: 0.into(),
+            max_share_bps: 0.into(),
             validator_seed_suffix: validator_stake
                 .validator_stake_seed
                 .map(|s| s.get())
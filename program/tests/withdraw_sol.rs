@@ -16,9 +16,9 @@ use {
     solana_stake_interface as stake,
     spl_stake_pool::{
         error::StakePoolError,
-        id,
+        event, id,
         instruction::{self, FundingType},
-        state, MINIMUM_RESERVE_LAMPORTS,
+        state, MAX_BASIS_POINTS, MINIMUM_RESERVE_LAMPORTS,
     },
     test_case::test_case,
 };
@@ -104,18 +104,27 @@ async fn success(token_program_id: Pubkey) {
     .await
     .lamports;
 
-    let error = stake_pool_accounts
-        .withdraw_sol(
-            &mut context.banks_client,
-            &context.payer,
-            &context.last_blockhash,
-            &user,
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::withdraw_sol(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.withdraw_authority,
+            &user.pubkey(),
             &pool_token_account,
+            &stake_pool_accounts.reserve_stake.pubkey(),
+            &user.pubkey(),
+            &stake_pool_accounts.pool_fee_account.pubkey(),
+            &stake_pool_accounts.pool_mint.pubkey(),
+            &stake_pool_accounts.token_program_id,
             pool_tokens,
-            None,
-        )
-        .await;
-    assert!(error.is_none(), "{:?}", error);
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user],
+        context.last_blockhash,
+    );
+    let events = process_transaction_with_events(&mut context.banks_client, transaction)
+        .await
+        .unwrap();
 
     // Stake pool should add its balance to the pool balance
     let post_stake_pool = get_account(
@@ -152,6 +161,16 @@ async fn success(token_program_id: Pubkey) {
         post_reserve_lamports,
         pre_reserve_lamports - amount_withdrawn_minus_fee
     );
+
+    assert_eq!(
+        events,
+        vec![event::StakePoolEvent::WithdrawSol {
+            stake_pool: stake_pool_accounts.stake_pool.pubkey(),
+            pool_tokens: amount_withdrawn_minus_fee,
+            manager_fee_pool_tokens: stake_pool_accounts.calculate_withdrawal_fee(pool_tokens),
+            lamports: amount_withdrawn_minus_fee,
+        }]
+    );
 }
 
 #[tokio::test]
@@ -393,3 +412,112 @@ async fn success_with_slippage(token_program_id: Pubkey) {
         pre_reserve_lamports - amount_received
     );
 }
+
+#[tokio::test]
+async fn success_with_fee_curve() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(spl_token::id()).await;
+
+    // the whole pool should sit in the reserve, so any withdrawal pays extra,
+    // up to the highest fee the flat 0.3% withdrawal fee can rise to at once
+    let curve = state::SolWithdrawalFeeCurve {
+        target_reserve_bps: MAX_BASIS_POINTS,
+        max_fee: state::Fee {
+            numerator: 9,
+            denominator: 2000,
+        },
+        exponent: 1,
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::set_fee(
+            &id(),
+            &stake_pool_accounts.stake_pool.pubkey(),
+            &stake_pool_accounts.manager.pubkey(),
+            state::FeeType::SolWithdrawalCurve(curve),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stake_pool_accounts.manager],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    // fee changes take effect two epochs later
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
+    let mut last_blockhash = context.last_blockhash;
+    for slot in [
+        first_normal_slot + 1,
+        first_normal_slot + slots_per_epoch + 1,
+    ] {
+        context.warp_to_slot(slot).unwrap();
+        last_blockhash = context
+            .banks_client
+            .get_new_latest_blockhash(&last_blockhash)
+            .await
+            .unwrap();
+        stake_pool_accounts
+            .update_all(
+                &mut context.banks_client,
+                &context.payer,
+                &last_blockhash,
+                false,
+            )
+            .await;
+    }
+    // the pool previews the fee the program charges
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.sol_withdrawal_fee_curve.max_fee.numerator, 9);
+
+    let reserve_account = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await;
+    let stake::state::StakeStateV2::Initialized(meta) =
+        try_from_slice_unchecked::<stake::state::StakeStateV2>(reserve_account.data.as_slice())
+            .unwrap()
+    else {
+        panic!("Reserve stake account not initialized");
+    };
+    let reserve_liquidity_lamports =
+        reserve_account.lamports - meta.rent_exempt_reserve - MINIMUM_RESERVE_LAMPORTS;
+
+    let withdraw_pool_tokens = pool_tokens / 2;
+    let pool_tokens_fee = stake_pool
+        .calc_dynamic_sol_withdrawal_fee(withdraw_pool_tokens, reserve_liquidity_lamports)
+        .unwrap();
+    assert!(pool_tokens_fee > stake_pool_accounts.calculate_withdrawal_fee(withdraw_pool_tokens));
+
+    let error = stake_pool_accounts
+        .withdraw_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &user,
+            &pool_token_account,
+            withdraw_pool_tokens,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let post_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    let withdraw_lamports = stake_pool
+        .calc_lamports_withdraw_amount(withdraw_pool_tokens - pool_tokens_fee)
+        .unwrap();
+    assert_eq!(
+        post_reserve_lamports,
+        reserve_account.lamports - withdraw_lamports
+    );
+}
@@ -0,0 +1,169 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    fogo_sessions_sdk::token::PROGRAM_SIGNER_SEED,
+    helpers::{wsol::setup_with_session_account, *},
+    solana_program::instruction::InstructionError,
+    solana_program_test::*,
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_stake_pool::{id, instruction::withdraw_sol_with_session},
+    spl_token::native_mint,
+};
+
+async fn withdraw(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    session_signer: &Keypair,
+    pool_token_account: &Pubkey,
+    user_wallet: &Pubkey,
+    pool_tokens: u64,
+) -> Option<TransactionError> {
+    let (program_signer, _) = Pubkey::find_program_address(&[PROGRAM_SIGNER_SEED], &id());
+    let instruction = withdraw_sol_with_session(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &session_signer.pubkey(),
+        pool_token_account,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        user_wallet,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        &program_signer,
+        None,
+        pool_tokens,
+        0,
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, session_signer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .err()
+        .map(|e| e.unwrap())
+}
+
+/// Approves the session signer to burn the user's pool tokens
+async fn approve_session(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    session_signer: &Keypair,
+    pool_token_account: &Pubkey,
+    user: &Keypair,
+    pool_tokens: u64,
+) {
+    let approve_ix = spl_token::instruction::approve_checked(
+        &spl_token::id(),
+        pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &session_signer.pubkey(),
+        &user.pubkey(),
+        &[],
+        pool_tokens,
+        native_mint::DECIMALS,
+    )
+    .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, user],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn success_withdraw() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, session_signer, pool_tokens) =
+        setup_with_session_account(spl_token::id()).await;
+    let pool_tokens_to_withdraw = pool_tokens / 2;
+    approve_session(
+        &mut context,
+        &stake_pool_accounts,
+        &session_signer,
+        &pool_token_account,
+        &user,
+        pool_tokens_to_withdraw,
+    )
+    .await;
+    let initial_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+
+    let error = withdraw(
+        &mut context,
+        &stake_pool_accounts,
+        &session_signer,
+        &pool_token_account,
+        &user.pubkey(),
+        pool_tokens_to_withdraw,
+    )
+    .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let user_lamports = get_account(&mut context.banks_client, &user.pubkey())
+        .await
+        .lamports;
+    assert!(user_lamports > 0);
+    let reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(reserve_lamports, initial_reserve_lamports - user_lamports);
+    assert_eq!(
+        get_token_balance(&mut context.banks_client, &pool_token_account).await,
+        pool_tokens - pool_tokens_to_withdraw
+    );
+}
+
+#[tokio::test]
+async fn fail_withdraw_wrong_user_wallet() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, session_signer, pool_tokens) =
+        setup_with_session_account(spl_token::id()).await;
+    approve_session(
+        &mut context,
+        &stake_pool_accounts,
+        &session_signer,
+        &pool_token_account,
+        &user,
+        pool_tokens,
+    )
+    .await;
+
+    let error = withdraw(
+        &mut context,
+        &stake_pool_accounts,
+        &session_signer,
+        &pool_token_account,
+        &Pubkey::new_unique(),
+        pool_tokens,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
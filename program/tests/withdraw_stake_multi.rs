@@ -0,0 +1,353 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        stake::state::StakeStateV2,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    spl_stake_pool::{error::StakePoolError, id, instruction, MINIMUM_RESERVE_LAMPORTS},
+};
+
+async fn setup() -> (ProgramTestContext, StakePoolAccounts, Keypair, Pubkey, u64) {
+    let mut context = program_test().start_with_context().await;
+    let stake_pool_accounts = StakePoolAccounts::default();
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let pool_tokens =
+        get_token_balance(&mut context.banks_client, &pool_token_account.pubkey()).await;
+
+    (
+        context,
+        stake_pool_accounts,
+        user,
+        pool_token_account.pubkey(),
+        pool_tokens,
+    )
+}
+
+/// Creates a blank stake account for each entry of `pool_tokens`, to receive
+/// a withdrawal of those pool tokens from the reserve
+async fn reserve_withdrawals(
+    context: &mut ProgramTestContext,
+    stake_pool_accounts: &StakePoolAccounts,
+    pool_tokens: &[u64],
+) -> Vec<(Pubkey, Pubkey, u64)> {
+    let mut withdrawals = vec![];
+    for pool_tokens in pool_tokens {
+        let stake_recipient = Keypair::new();
+        create_blank_stake_account(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &stake_recipient,
+        )
+        .await;
+        withdrawals.push((
+            stake_pool_accounts.reserve_stake.pubkey(),
+            stake_recipient.pubkey(),
+            *pool_tokens,
+        ));
+    }
+    withdrawals
+}
+
+fn withdraw_stake_multi_instruction(
+    stake_pool_accounts: &StakePoolAccounts,
+    user: &Keypair,
+    pool_token_account: &Pubkey,
+    withdrawals: &[(Pubkey, Pubkey, u64)],
+    minimum_lamports_out: u64,
+) -> Instruction {
+    instruction::withdraw_stake_multi(
+        &id(),
+        &stake_pool_accounts.stake_pool.pubkey(),
+        &stake_pool_accounts.validator_list.pubkey(),
+        &stake_pool_accounts.withdraw_authority,
+        &user.pubkey(),
+        &user.pubkey(),
+        pool_token_account,
+        &stake_pool_accounts.pool_fee_account.pubkey(),
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &stake_pool_accounts.token_program_id,
+        withdrawals,
+        minimum_lamports_out,
+    )
+}
+
+#[tokio::test]
+async fn success_from_reserve() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) = setup().await;
+    let stake_pool_before = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let pool_tokens_in = [pool_tokens / 4, pool_tokens / 2];
+    let expected_lamports: Vec<u64> = pool_tokens_in
+        .iter()
+        .map(|pool_tokens| {
+            let fee = stake_pool_before
+                .calc_pool_tokens_stake_withdrawal_fee(*pool_tokens)
+                .unwrap();
+            stake_pool_before
+                .calc_lamports_withdraw_amount(pool_tokens - fee)
+                .unwrap()
+        })
+        .collect();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let stake_rent = rent.minimum_balance(StakeStateV2::size_of());
+
+    let withdrawals =
+        reserve_withdrawals(&mut context, &stake_pool_accounts, &pool_tokens_in).await;
+    let instruction = withdraw_stake_multi_instruction(
+        &stake_pool_accounts,
+        &user,
+        &pool_token_account,
+        &withdrawals,
+        expected_lamports.iter().sum(),
+    );
+    let error = process_instruction(&mut context, instruction, &[&user]).await;
+    assert!(error.is_none(), "{:?}", error);
+
+    for ((_, stake_recipient, _), lamports) in withdrawals.iter().zip(&expected_lamports) {
+        let stake_account = get_account(&mut context.banks_client, stake_recipient).await;
+        assert_eq!(stake_account.lamports, stake_rent + lamports);
+    }
+    let pool_tokens_withdrawn: u64 = pool_tokens_in.iter().sum();
+    assert_eq!(
+        get_token_balance(&mut context.banks_client, &pool_token_account).await,
+        pool_tokens - pool_tokens_withdrawn
+    );
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        stake_pool.total_lamports,
+        stake_pool_before.total_lamports - expected_lamports.iter().sum::<u64>()
+    );
+    assert_eq!(
+        stake_pool.pool_token_supply,
+        get_token_supply(
+            &mut context.banks_client,
+            &stake_pool_accounts.pool_mint.pubkey()
+        )
+        .await
+    );
+}
+
+#[tokio::test]
+async fn fail_exceeded_slippage() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) = setup().await;
+    let pool_tokens_in = [pool_tokens / 4, pool_tokens / 4];
+
+    let withdrawals =
+        reserve_withdrawals(&mut context, &stake_pool_accounts, &pool_tokens_in).await;
+    let instruction = withdraw_stake_multi_instruction(
+        &stake_pool_accounts,
+        &user,
+        &pool_token_account,
+        &withdrawals,
+        pool_tokens,
+    );
+    let error = process_instruction(&mut context, instruction, &[&user]).await;
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::ExceededSlippage as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_missing_stake_account() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) = setup().await;
+    let pool_tokens_in = [pool_tokens / 4, pool_tokens / 4];
+
+    let withdrawals =
+        reserve_withdrawals(&mut context, &stake_pool_accounts, &pool_tokens_in).await;
+    let mut instruction = withdraw_stake_multi_instruction(
+        &stake_pool_accounts,
+        &user,
+        &pool_token_account,
+        &withdrawals,
+        0,
+    );
+    // drop the stake account receiving the second withdrawal
+    instruction.accounts.pop();
+    let error = process_instruction(&mut context, instruction, &[&user]).await;
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+#[tokio::test]
+async fn fail_exceeded_slippage_after_validator_removal() {
+    let (
+        mut context,
+        stake_pool_accounts,
+        validator_stake,
+        deposit_info,
+        user_transfer_authority,
+        user_stake_recipient,
+        _,
+    ) = setup_for_withdraw(spl_token::id(), STAKE_ACCOUNT_RENT_EXEMPTION).await;
+
+    // make pool tokens worth many lamports, so that withdrawing a whole
+    // validator stake account takes more lamports worth of pool tokens than
+    // it holds
+    transfer(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+        deposit_info.stake_lamports * 29,
+    )
+    .await;
+    let first_normal_slot = context.genesis_config().epoch_schedule.first_normal_slot;
+    let mut slot = first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let stake_rent = rent.minimum_balance(StakeStateV2::size_of());
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let lamports_per_pool_token = stake_pool.get_lamports_per_pool_token().unwrap();
+    let error = stake_pool_accounts
+        .decrease_validator_stake_either(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &validator_stake.stake_account,
+            &validator_stake.transient_stake_account,
+            deposit_info.stake_lamports + stake_rent - lamports_per_pool_token,
+            validator_stake.transient_stake_seed,
+            DecreaseInstruction::Reserve,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    slot += context.genesis_config().epoch_schedule.slots_per_epoch;
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // round up so the pair takes the whole validator stake account, which
+    // pays out only the lamports it holds
+    let remaining_lamports = get_account(&mut context.banks_client, &validator_stake.stake_account)
+        .await
+        .lamports;
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let pool_tokens_post_fee =
+        (remaining_lamports * stake_pool.pool_token_supply).div_ceil(stake_pool.total_lamports);
+    let pool_tokens = stake_pool_accounts.calculate_inverse_withdrawal_fee(pool_tokens_post_fee);
+    let pool_tokens_fee = stake_pool
+        .calc_pool_tokens_stake_withdrawal_fee(pool_tokens)
+        .unwrap();
+    let priced_lamports = stake_pool
+        .calc_lamports_withdraw_amount(pool_tokens - pool_tokens_fee)
+        .unwrap();
+    assert!(priced_lamports > remaining_lamports);
+
+    let withdrawals = [(
+        validator_stake.stake_account,
+        user_stake_recipient.pubkey(),
+        pool_tokens,
+    )];
+    let instruction = withdraw_stake_multi_instruction(
+        &stake_pool_accounts,
+        &user_transfer_authority,
+        &deposit_info.pool_account.pubkey(),
+        &withdrawals,
+        priced_lamports,
+    );
+    let error = process_instruction(&mut context, instruction, &[&user_transfer_authority]).await;
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::ExceededSlippage as u32)
+        )
+    );
+
+    let instruction = withdraw_stake_multi_instruction(
+        &stake_pool_accounts,
+        &user_transfer_authority,
+        &deposit_info.pool_account.pubkey(),
+        &withdrawals,
+        remaining_lamports,
+    );
+    let error = process_instruction(&mut context, instruction, &[&user_transfer_authority]).await;
+    assert!(error.is_none(), "{:?}", error);
+    let user_stake_recipient_account =
+        get_account(&mut context.banks_client, &user_stake_recipient.pubkey()).await;
+    assert_eq!(
+        user_stake_recipient_account.lamports,
+        remaining_lamports + stake_rent
+    );
+}
@@ -416,6 +416,7 @@ async fn success_withdraw_from_stake_account_with_session(token_program_id: Pubk
         &user_stake_pda,
         &user.pubkey(),
         &session_keypair.pubkey(),
+        &stake_pool_accounts.stake_pool.pubkey(),
         u64::MAX, // Full withdrawal (lamports)
         user_stake_seed,
     );
@@ -520,6 +521,7 @@ async fn fail_withdraw_from_wrong_user_stake_account(token_program_id: Pubkey) {
         &wrong_stake_pda, // Wrong PDA
         &user.pubkey(),
         &session_keypair.pubkey(),
+        &stake_pool_accounts.stake_pool.pubkey(),
         u64::MAX, // Full withdrawal (lamports)
         wrong_seed,
     );
@@ -599,6 +601,7 @@ async fn fail_withdraw_to_different_user_wallet(token_program_id: Pubkey) {
         &user_stake_pda,
         &attacker.pubkey(), // Different wallet than session user
         &session_keypair.pubkey(),
+        &stake_pool_accounts.stake_pool.pubkey(),
         u64::MAX, // Full withdrawal (lamports)
         user_stake_seed,
     );
@@ -0,0 +1,619 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use {
+    helpers::*,
+    solana_program::{
+        borsh1::try_from_slice_unchecked, clock::Clock, instruction::InstructionError,
+        pubkey::Pubkey, rent::Rent,
+    },
+    solana_program_test::*,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    solana_stake_interface as stake,
+    spl_stake_pool::{error::StakePoolError, id, state, MINIMUM_RESERVE_LAMPORTS},
+    test_case::test_case,
+};
+
+const TICKET_SEED: u64 = 0;
+
+async fn setup(
+    token_program_id: Pubkey,
+) -> (ProgramTestContext, StakePoolAccounts, Keypair, Pubkey, u64) {
+    let mut context = program_test().start_with_context().await;
+
+    let stake_pool_accounts = StakePoolAccounts::new_with_token_program(token_program_id);
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            MINIMUM_RESERVE_LAMPORTS,
+        )
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+
+    // make pool token account for user
+    let pool_token_account = Keypair::new();
+    create_token_account(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts.token_program_id,
+        &pool_token_account,
+        &stake_pool_accounts.pool_mint.pubkey(),
+        &user,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let error = stake_pool_accounts
+        .deposit_sol(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &pool_token_account.pubkey(),
+            TEST_STAKE_AMOUNT,
+            None,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let tokens_issued =
+        get_token_balance(&mut context.banks_client, &pool_token_account.pubkey()).await;
+
+    (
+        context,
+        stake_pool_accounts,
+        user,
+        pool_token_account.pubkey(),
+        tokens_issued,
+    )
+}
+
+async fn get_withdrawal_ticket(
+    banks_client: &mut BanksClient,
+    withdrawal_ticket: &Pubkey,
+) -> state::WithdrawalTicket {
+    let account = get_account(banks_client, withdrawal_ticket).await;
+    assert_eq!(account.owner, id());
+    try_from_slice_unchecked::<state::WithdrawalTicket>(account.data.as_slice()).unwrap()
+}
+
+#[test_case(spl_token::id(); "token")]
+#[test_case(spl_token_2022::id(); "token-2022")]
+#[tokio::test]
+async fn success_request(token_program_id: Pubkey) {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(token_program_id).await;
+
+    let pre_stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let pre_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // Lamports move from the pool balance into the pending tickets
+    let post_stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    let amount_withdrawn_minus_fee =
+        pool_tokens - stake_pool_accounts.calculate_withdrawal_fee(pool_tokens);
+    assert_eq!(
+        post_stake_pool.total_lamports,
+        pre_stake_pool.total_lamports - amount_withdrawn_minus_fee
+    );
+    assert_eq!(
+        post_stake_pool.pool_token_supply,
+        pre_stake_pool.pool_token_supply - amount_withdrawn_minus_fee
+    );
+    assert_eq!(
+        post_stake_pool.pending_withdrawal_lamports,
+        amount_withdrawn_minus_fee
+    );
+    assert_eq!(post_stake_pool.withdrawal_claim_pool_lamports, 0);
+
+    // Check burnt tokens
+    let user_token_balance =
+        get_token_balance(&mut context.banks_client, &pool_token_account).await;
+    assert_eq!(user_token_balance, 0);
+
+    // Reserve is untouched until the ticket is claimed
+    let post_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(post_reserve_lamports, pre_reserve_lamports);
+
+    // Check ticket
+    let withdrawal_ticket = stake_pool_accounts.find_withdrawal_ticket(&user.pubkey(), TICKET_SEED);
+    let ticket = get_withdrawal_ticket(&mut context.banks_client, &withdrawal_ticket).await;
+    let epoch = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .epoch;
+    assert!(ticket.is_valid());
+    assert_eq!(ticket.stake_pool, stake_pool_accounts.stake_pool.pubkey());
+    assert_eq!(ticket.owner, user.pubkey());
+    assert_eq!(ticket.lamports, amount_withdrawn_minus_fee);
+    assert_eq!(ticket.pool_tokens, amount_withdrawn_minus_fee);
+    assert_eq!(ticket.request_epoch, epoch);
+    assert_eq!(ticket.claimable_epoch, epoch + 1);
+}
+
+#[tokio::test]
+async fn success_update_excludes_pending_lamports() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(spl_token::id()).await;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 2,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+    let pre_stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // The exchange rate is unaffected by the unclaimed ticket
+    let post_stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(
+        post_stake_pool.total_lamports,
+        pre_stake_pool.total_lamports
+    );
+    assert_eq!(
+        post_stake_pool.pool_token_supply,
+        pre_stake_pool.pool_token_supply
+    );
+    assert_eq!(
+        post_stake_pool.pending_withdrawal_lamports,
+        pre_stake_pool.pending_withdrawal_lamports
+    );
+}
+
+#[tokio::test]
+async fn fail_request_twice_same_seed() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(spl_token::id()).await;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 2,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens / 2,
+            TICKET_SEED,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::AlreadyInUse as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_request_prefunded_ticket() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(spl_token::id()).await;
+
+    // anyone may send lamports to the ticket address before it is requested
+    let withdrawal_ticket = stake_pool_accounts.find_withdrawal_ticket(&user.pubkey(), TICKET_SEED);
+    transfer(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &withdrawal_ticket,
+        Rent::default().minimum_balance(0),
+    )
+    .await;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let ticket = get_withdrawal_ticket(&mut context.banks_client, &withdrawal_ticket).await;
+    assert_eq!(ticket.owner, user.pubkey());
+}
+
+#[tokio::test]
+async fn fail_request_too_small() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, _) =
+        setup(spl_token::id()).await;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            1,
+            TICKET_SEED,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WithdrawalTooSmall as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_claim_before_cooldown() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(spl_token::id()).await;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let withdrawal_ticket = stake_pool_accounts.find_withdrawal_ticket(&user.pubkey(), TICKET_SEED);
+    let error = stake_pool_accounts
+        .claim_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &withdrawal_ticket,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::WithdrawalTicketNotClaimable as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_claim_unfunded() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(spl_token::id()).await;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+
+    // the reserve holds the lamports, but the staker has not set them aside
+    let withdrawal_ticket = stake_pool_accounts.find_withdrawal_ticket(&user.pubkey(), TICKET_SEED);
+    let error = stake_pool_accounts
+        .claim_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &withdrawal_ticket,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InsufficientWithdrawalClaimPool as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_claim_wrong_owner() {
+    let (mut context, stake_pool_accounts, user, pool_token_account, pool_tokens) =
+        setup(spl_token::id()).await;
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &user,
+            &pool_token_account,
+            pool_tokens,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+
+    let wrong_owner = Keypair::new();
+    let withdrawal_ticket = stake_pool_accounts.find_withdrawal_ticket(&user.pubkey(), TICKET_SEED);
+    let error = stake_pool_accounts
+        .claim_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &wrong_owner,
+            &withdrawal_ticket,
+            &wrong_owner.pubkey(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(StakePoolError::InvalidWithdrawalTicket as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_claim_after_decrease_with_reserve() {
+    let mut context = program_test().start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let stake_rent = rent.minimum_balance(std::mem::size_of::<stake::state::StakeStateV2>());
+    let current_minimum_delegation = stake_pool_get_minimum_delegation(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+    )
+    .await;
+
+    let stake_pool_accounts = StakePoolAccounts::default();
+    let reserve_lamports = MINIMUM_RESERVE_LAMPORTS + stake_rent + current_minimum_delegation;
+    stake_pool_accounts
+        .initialize_stake_pool(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            reserve_lamports,
+        )
+        .await
+        .unwrap();
+
+    let validator_stake = simple_add_validator_to_pool(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        None,
+    )
+    .await;
+
+    let deposit_lamports = (current_minimum_delegation + stake_rent) * 3;
+    let deposit_info = simple_deposit_stake(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &stake_pool_accounts,
+        &validator_stake,
+        deposit_lamports,
+    )
+    .await
+    .unwrap();
+
+    let error = stake_pool_accounts
+        .request_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &deposit_info.authority,
+            &deposit_info.pool_account.pubkey(),
+            deposit_info.pool_tokens,
+            TICKET_SEED,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let withdrawal_ticket =
+        stake_pool_accounts.find_withdrawal_ticket(&deposit_info.authority.pubkey(), TICKET_SEED);
+    let ticket = get_withdrawal_ticket(&mut context.banks_client, &withdrawal_ticket).await;
+
+    // staker unstakes enough to cover the ticket, funding the claim pool
+    let error = stake_pool_accounts
+        .decrease_validator_stake_with_reserve(
+            &mut context.banks_client,
+            &context.payer,
+            &context.last_blockhash,
+            &validator_stake.stake_account,
+            &validator_stake.transient_stake_account,
+            ticket.lamports,
+            validator_stake.transient_stake_seed,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.withdrawal_claim_pool_lamports, ticket.lamports);
+
+    // merge the transient stake into the reserve
+    let slot = context.genesis_config().epoch_schedule.first_normal_slot + 1;
+    context.warp_to_slot(slot).unwrap();
+    let last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let error = stake_pool_accounts
+        .update_all(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            false,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    let pre_ticket_rent = get_account(&mut context.banks_client, &withdrawal_ticket)
+        .await
+        .lamports;
+    let pre_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+
+    let receiver = Pubkey::new_unique();
+    let error = stake_pool_accounts
+        .claim_withdrawal_ticket(
+            &mut context.banks_client,
+            &context.payer,
+            &last_blockhash,
+            &deposit_info.authority,
+            &withdrawal_ticket,
+            &receiver,
+        )
+        .await;
+    assert!(error.is_none(), "{:?}", error);
+
+    // Receiver gets the ticket lamports and its rent
+    let receiver_lamports = get_account(&mut context.banks_client, &receiver)
+        .await
+        .lamports;
+    assert_eq!(receiver_lamports, ticket.lamports + pre_ticket_rent);
+
+    let post_reserve_lamports = get_account(
+        &mut context.banks_client,
+        &stake_pool_accounts.reserve_stake.pubkey(),
+    )
+    .await
+    .lamports;
+    assert_eq!(
+        post_reserve_lamports,
+        pre_reserve_lamports - ticket.lamports
+    );
+
+    let stake_pool = stake_pool_accounts
+        .get_stake_pool(&mut context.banks_client)
+        .await;
+    assert_eq!(stake_pool.pending_withdrawal_lamports, 0);
+    assert_eq!(stake_pool.withdrawal_claim_pool_lamports, 0);
+
+    // Ticket is closed
+    let ticket_account = context
+        .banks_client
+        .get_account(withdrawal_ticket)
+        .await
+        .unwrap();
+    assert!(ticket_account.is_none());
+}
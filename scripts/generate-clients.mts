@@ -62,6 +62,78 @@ codama.update(
                 }),
               ]),
             }),
+            c.accountNode({
+              name: 'withdrawalTicketAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('withdrawalTicket'),
+                }),
+              ]),
+            }),
+            c.accountNode({
+              name: 'referrerAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('referrer'),
+                }),
+              ]),
+            }),
+            c.accountNode({
+              name: 'directStakeAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('directStake'),
+                }),
+              ]),
+            }),
+            c.accountNode({
+              name: 'validatorMetadataAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('validatorMetadata'),
+                }),
+              ]),
+            }),
+            c.accountNode({
+              name: 'feeSplitAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('feeSplit'),
+                }),
+              ]),
+            }),
+            c.accountNode({
+              name: 'insuranceFundAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('insuranceFund'),
+                }),
+              ]),
+            }),
+            c.accountNode({
+              name: 'rateHistoryAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('rateHistory'),
+                }),
+              ]),
+            }),
+            c.accountNode({
+              name: 'oracleAccount',
+              data: c.structTypeNode([
+                c.structFieldTypeNode({
+                  name: 'data',
+                  type: c.definedTypeLinkNode('oracle'),
+                }),
+              ]),
+            }),
           ],
         }
       },
@@ -69,6 +141,20 @@ codama.update(
   ]),
 )
 
+// The program detects optional accounts by their presence, so omit them
+// from the account list instead of substituting the program ID.
+codama.update(
+  c.bottomUpTransformerVisitor([
+    {
+      select: '[instructionNode]',
+      transform: (node) => {
+        c.assertIsNode(node, 'instructionNode')
+        return { ...node, optionalAccountStrategy: 'omitted' }
+      },
+    },
+  ]),
+)
+
 // Render JavaScript.
 const jsClient = path.join(workingDirectory, 'clients', 'js-new')
 if (fs.existsSync(jsClient)) {